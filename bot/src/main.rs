@@ -2,8 +2,8 @@ use anyhow::{Context, Result, anyhow};
 use chrono::Utc;
 use clap::Parser;
 use common::{
-    ClientCommandIdentityV2, GameCommand, GameEngine, GameEvent, GameEventMessage, GameState,
-    GameStatus, GameType, QueueMode, calculate_ai_command,
+    AiDifficulty, BotPolicy, BotPolicyRegistry, ClientCommandIdentityV2, GameCommand, GameEngine,
+    GameEvent, GameEventMessage, GameState, GameStatus, GameType, QueueMode, SearchProfile,
 };
 use futures_util::{Sink, SinkExt, Stream, StreamExt};
 use reqwest::Client;
@@ -12,6 +12,7 @@ use server::ads::AdBreakResolution;
 use server::lifecycle::WS_PROTOCOL_VERSION as CLIENT_PROTOCOL_VERSION;
 use server::ws_server::WSMessage;
 use std::pin::Pin;
+use std::sync::Arc;
 use tokio::sync::watch;
 use tokio::time::{Duration, Instant, Interval, MissedTickBehavior, Sleep};
 use tokio_tungstenite::{
@@ -36,7 +37,7 @@ struct Args {
     #[arg(long, default_value = "http://localhost:8080")]
     url: String,
 
-    /// Game mode to queue for: duel | 2v2 | solo | ffa
+    /// Game mode to queue for: duel | 2v2 | solo | ffa | koth | koth-2v2 | ctf | ctf-2v2
     #[arg(long, default_value = "duel")]
     mode: String,
 
@@ -52,6 +53,23 @@ struct Args {
     #[arg(long, default_value = "quickmatch")]
     queue_mode: String,
 
+    /// AI difficulty: easy | medium | hard. Sets how often the bot reacts,
+    /// how far it looks ahead and when it spends Boost.
+    #[arg(long, default_value = "medium")]
+    difficulty: AiDifficulty,
+
+    /// Bot policy by name: easy | medium | hard | medium-threshold | search.
+    /// Defaults to the difficulty's own tier; the difficulty still sets how
+    /// often the bot decides. `search` plays each candidate a few steps
+    /// ahead on a copy of the match before committing to it.
+    #[arg(long)]
+    policy: Option<String>,
+
+    /// Wall-clock budget for one `search` decision, in milliseconds. Keep it
+    /// well under the decision period.
+    #[arg(long, default_value_t = 30)]
+    search_budget_ms: u64,
+
     /// Server-derived stress admission key. Bots are always placed in the
     /// trusted synthetic pool so their games are never written to the
     /// production replay corpus.
@@ -66,6 +84,8 @@ struct BotRunConfig {
     ws_url: Url,
     game_type: GameType,
     queue_mode: QueueMode,
+    difficulty: AiDifficulty,
+    policy: Arc<dyn BotPolicy>,
     http_client: Client,
     stress_test_key: String,
 }
@@ -125,11 +145,16 @@ async fn main() -> Result<()> {
     let ws_url = websocket_url(&base_url)?;
     let game_type = parse_game_type(&args.mode);
     let queue_mode = parse_queue_mode(&args.queue_mode)?;
+    let policy_name = args
+        .policy
+        .clone()
+        .unwrap_or_else(|| args.difficulty.to_string());
+    let policy = resolve_policy(&policy_name, args.search_budget_ms)?;
     let http_client = Client::new();
 
     info!(
-        "Starting {} bot(s) targeting {} ({}) in {:?} mode, {} game(s) each",
-        args.bots, base_url, ws_url, queue_mode, args.games
+        "Starting {} {} bot(s) playing {} targeting {} ({}) in {:?} mode, {} game(s) each",
+        args.bots, args.difficulty, policy_name, base_url, ws_url, queue_mode, args.games
     );
 
     let mut handles = Vec::new();
@@ -141,6 +166,7 @@ async fn main() -> Result<()> {
         let http_client = http_client.clone();
         let games = args.games;
         let stress_test_key = args.stress_test_key.clone();
+        let policy = policy.clone();
 
         let handle = tokio::spawn(async move {
             if let Err(err) = run_bot(BotRunConfig {
@@ -150,6 +176,8 @@ async fn main() -> Result<()> {
                 ws_url,
                 game_type,
                 queue_mode,
+                difficulty: args.difficulty,
+                policy,
                 http_client,
                 stress_test_key,
             })
@@ -176,6 +204,8 @@ async fn run_bot(config: BotRunConfig) -> Result<()> {
         ws_url,
         game_type,
         queue_mode,
+        difficulty,
+        policy,
         http_client,
         stress_test_key,
     } = config;
@@ -204,6 +234,8 @@ async fn run_bot(config: BotRunConfig) -> Result<()> {
             &guest.token,
             &game_type,
             &queue_mode,
+            difficulty,
+            policy.as_ref(),
             user_id,
             &status_tx,
         )
@@ -234,6 +266,8 @@ async fn play_single_game(
     token: &str,
     game_type: &GameType,
     queue_mode: &QueueMode,
+    difficulty: AiDifficulty,
+    policy: &dyn BotPolicy,
     user_id: u32,
     status_tx: &watch::Sender<String>,
 ) -> Result<()> {
@@ -301,6 +335,7 @@ async fn play_single_game(
                             &mut snake_id,
                             &mut tick_interval,
                             user_id,
+                            difficulty,
                             &mut game_id,
                             &mut game_started,
                             &mut game_completed,
@@ -321,6 +356,7 @@ async fn play_single_game(
                             &mut snake_id,
                             &mut tick_interval,
                             user_id,
+                            difficulty,
                             &mut game_started,
                             &mut game_completed,
                             &mut hang_timer,
@@ -350,6 +386,7 @@ async fn play_single_game(
                         &mut ws_writer,
                         snake_id,
                         game_id,
+                        policy,
                         &mut command_session,
                     )
                     .await?;
@@ -397,6 +434,7 @@ where
             protocol_version: CLIENT_PROTOCOL_VERSION,
             anon_id: None,
             distribution: None,
+            encoding: None,
         },
     )
     .await?;
@@ -522,6 +560,7 @@ async fn handle_ws_message<S>(
     snake_id: &mut Option<u32>,
     tick_interval: &mut Option<Interval>,
     user_id: u32,
+    difficulty: AiDifficulty,
     game_id: &mut Option<u32>,
     game_started: &mut bool,
     game_completed: &mut bool,
@@ -583,6 +622,7 @@ where
                 snake_id,
                 tick_interval,
                 user_id,
+                difficulty,
                 game_started,
                 game_completed,
                 hang_timer,
@@ -644,6 +684,7 @@ async fn handle_game_event(
     snake_id: &mut Option<u32>,
     tick_interval: &mut Option<Interval>,
     user_id: u32,
+    difficulty: AiDifficulty,
     game_started: &mut bool,
     game_completed: &mut bool,
     hang_timer: &mut Pin<&mut Sleep>,
@@ -670,7 +711,7 @@ async fn handle_game_event(
             // option unconditionally also clears an interval left over from a
             // pre-gate/resync snapshot, so the bot cannot issue commands while
             // the authoritative engine is intentionally parked.
-            *tick_interval = build_interval(game_state);
+            *tick_interval = build_interval(game_state, difficulty);
 
             info!(
                 "Bot {} received snapshot for game {}, tick {}, snake {:?}",
@@ -740,7 +781,7 @@ async fn handle_game_event(
                 // committed state. Rebuild from that state rather than the
                 // immutable legacy `start_ms`, which can be many seconds old
                 // after the readiness briefing.
-                *tick_interval = build_interval(engine.get_committed_state());
+                *tick_interval = build_interval(engine.get_committed_state(), difficulty);
             }
         }
         _ => {
@@ -759,6 +800,7 @@ async fn drive_bot<S>(
     ws_writer: &mut S,
     snake_id: u32,
     game_id: u32,
+    policy: &dyn BotPolicy,
     command_session: &mut BotCommandSession,
 ) -> Result<()>
 where
@@ -778,7 +820,8 @@ where
         return Ok(());
     }
 
-    let command = calculate_ai_command(predicted_state, snake_id)
+    let command = policy
+        .decide(predicted_state, snake_id)
         .expect("a living snake should always produce a bot command");
     let command_msg = engine.process_local_command(command)?;
     match &command_msg.command {
@@ -903,6 +946,10 @@ fn parse_game_type(mode: &str) -> GameType {
         "solo" => GameType::Solo,
         "ffa" | "free-for-all" => GameType::FreeForAll { max_players: 4 },
         "2v2" | "team" => GameType::TeamMatch { per_team: 2 },
+        "koth" | "hill" => GameType::KingOfTheHill { per_team: 1 },
+        "koth-2v2" => GameType::KingOfTheHill { per_team: 2 },
+        "ctf" => GameType::CaptureTheFlag { per_team: 1 },
+        "ctf-2v2" => GameType::CaptureTheFlag { per_team: 2 },
         _ => GameType::TeamMatch { per_team: 1 }, // Duel default
     }
 }
@@ -915,9 +962,23 @@ fn parse_queue_mode(mode: &str) -> Result<QueueMode> {
     }
 }
 
-fn build_interval(game_state: &GameState) -> Option<Interval> {
+/// Look `name` up among the built-in policies, with `search` capped at
+/// `search_budget_ms` of wall clock per decision.
+fn resolve_policy(name: &str, search_budget_ms: u64) -> Result<Arc<dyn BotPolicy>> {
+    if search_budget_ms == 0 {
+        return Err(anyhow!("--search-budget-ms must be positive"));
+    }
+    let mut registry = BotPolicyRegistry::builtin();
+    registry.register(
+        "search",
+        SearchProfile::timed(Duration::from_millis(search_budget_ms)),
+    );
+    registry.get(name)
+}
+
+fn build_interval(game_state: &GameState, difficulty: AiDifficulty) -> Option<Interval> {
     let now_ms = Utc::now().timestamp_millis();
-    let (decision_ms, delay_ms) = decision_schedule_ms(game_state, now_ms)?;
+    let (decision_ms, delay_ms) = decision_schedule_ms(game_state, difficulty, now_ms)?;
 
     let mut interval = tokio::time::interval_at(
         Instant::now() + Duration::from_millis(delay_ms),
@@ -930,14 +991,20 @@ fn build_interval(game_state: &GameState) -> Option<Interval> {
 /// Return the decision period and delay to its next epoch-aligned boundary.
 /// A readiness-gated match deliberately has no schedule until the server
 /// publishes `MatchStartScheduled`; legacy ungated states fall back to their
-/// immutable `start_ms` through `GameState::simulation_start_ms`.
-fn decision_schedule_ms(game_state: &GameState, now_ms: i64) -> Option<(u64, u64)> {
+/// immutable `start_ms` through `GameState::simulation_start_ms`. A slower
+/// difficulty stretches the period to its reaction delay.
+fn decision_schedule_ms(
+    game_state: &GameState,
+    difficulty: AiDifficulty,
+    now_ms: i64,
+) -> Option<(u64, u64)> {
     let tick_ms = game_state.properties.tick_duration_ms as u64;
     if tick_ms == 0 {
         return None;
     }
     let start_ms = game_state.simulation_start_ms()?;
-    let decision_ms = decision_interval_ms(game_state);
+    let decision_ms =
+        decision_interval_ms(game_state).max(u64::from(difficulty.profile().reaction_delay_ms));
     let elapsed_ms = (now_ms - start_ms).max(0) as u64;
     let decisions_elapsed = elapsed_ms / decision_ms;
     let next_decision_ms = start_ms + ((decisions_elapsed + 1) * decision_ms) as i64;
@@ -991,6 +1058,7 @@ async fn log_progress(idx: usize, mut status_rx: watch::Receiver<String>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::calculate_ai_command_at;
     use std::collections::HashMap;
     use tokio::net::TcpListener;
     use tokio_tungstenite::accept_async;
@@ -1051,10 +1119,16 @@ mod tests {
 
         // States from before the readiness protocol remain anchored to their
         // original start time.
-        assert_eq!(decision_schedule_ms(&game, 1_050), Some((100, 50)));
+        assert_eq!(
+            decision_schedule_ms(&game, AiDifficulty::Medium, 1_050),
+            Some((100, 50))
+        );
 
         game.arm_readiness_gate(9_000);
-        assert_eq!(decision_schedule_ms(&game, 8_000), None);
+        assert_eq!(
+            decision_schedule_ms(&game, AiDifficulty::Medium, 8_000),
+            None
+        );
 
         game.apply_event(
             GameEvent::MatchStartScheduled {
@@ -1065,8 +1139,20 @@ mod tests {
         // The old start was nine seconds earlier. A schedule based on it would
         // fire immediately here; the released epoch correctly waits until the
         // first 100 ms decision boundary after simulation begins.
-        assert_eq!(decision_schedule_ms(&game, 9_900), Some((100, 200)));
-        assert_eq!(decision_schedule_ms(&game, 10_050), Some((100, 50)));
+        assert_eq!(
+            decision_schedule_ms(&game, AiDifficulty::Medium, 9_900),
+            Some((100, 200))
+        );
+        assert_eq!(
+            decision_schedule_ms(&game, AiDifficulty::Medium, 10_050),
+            Some((100, 50))
+        );
+
+        // An easy bot reacts three times slower, on the same epoch.
+        assert_eq!(
+            decision_schedule_ms(&game, AiDifficulty::Easy, 10_050),
+            Some((300, 250))
+        );
     }
 
     #[tokio::test]
@@ -1084,7 +1170,7 @@ mod tests {
 
         // Model a timer installed from an earlier ungated snapshot. Receiving
         // the gated authoritative snapshot must remove it, not leave it live.
-        let mut tick_interval = build_interval(&state);
+        let mut tick_interval = build_interval(&state, AiDifficulty::Medium);
         assert!(tick_interval.is_some());
         state.arm_readiness_gate(now_ms + 15_000);
 
@@ -1110,6 +1196,7 @@ mod tests {
             &mut snake_id,
             &mut tick_interval,
             7,
+            AiDifficulty::Medium,
             &mut game_started,
             &mut game_completed,
             &mut hang_timer,
@@ -1147,6 +1234,7 @@ mod tests {
             &mut snake_id,
             &mut tick_interval,
             7,
+            AiDifficulty::Medium,
             &mut game_started,
             &mut game_completed,
             &mut hang_timer,
@@ -1171,6 +1259,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn policies_resolve_by_name() {
+        let mut game = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(5), 0);
+        let snake_id = game.add_player(41, None).unwrap().snake_id;
+        game.spawn_initial_food();
+        for difficulty in AiDifficulty::ALL {
+            let policy = resolve_policy(difficulty.as_str(), 30).unwrap();
+            let command = policy.decide(&game, snake_id);
+            assert!(command.is_some());
+            assert_eq!(
+                command,
+                calculate_ai_command_at(&game, snake_id, difficulty)
+            );
+        }
+        assert!(resolve_policy("Search", 30).is_ok());
+        assert!(resolve_policy("search", 0).is_err());
+        assert!(resolve_policy("minimax", 30).is_err());
+    }
+
     #[test]
     fn bot_activates_collected_boost_before_choosing_another_turn() {
         let mut game = GameState::new(
@@ -1201,7 +1308,7 @@ mod tests {
             pad.charge_ms
         );
         assert!(matches!(
-            calculate_ai_command(&game, player.snake_id),
+            calculate_ai_command_at(&game, player.snake_id, AiDifficulty::Medium),
             Some(GameCommand::ActivateBoost { snake_id }) if snake_id == player.snake_id
         ));
     }
@@ -1223,6 +1330,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if token == "test-token"
             ));
             send_ws(
@@ -1240,6 +1348,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     capabilities: Vec::new(),
                     socket_generation: 1,
+                    encoding: None,
                 },
             )
             .await
@@ -1322,6 +1431,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     capabilities: Vec::new(),
                     socket_generation: 1,
+                    encoding: None,
                 },
             )
             .await
@@ -5,6 +5,7 @@ mod tutorial;
 
 use common::{
     Direction, GameCommand, GameCommandMessage, GameEngine, GameEvent, GameEventMessage, GameState,
+    decode_game_event_frame,
 };
 use wasm_bindgen::prelude::*;
 
@@ -15,6 +16,42 @@ pub struct GameClient {
     engine: GameEngine,
 }
 
+/// A server frame as JavaScript received it: the text of a JSON frame, or the
+/// bytes of a binary MessagePack one, which the server sends game events as
+/// once `Authenticate` asked for `WireEncoding::MessagePack`.
+enum ServerFrame {
+    Json(String),
+    MessagePack(Vec<u8>),
+}
+
+impl ServerFrame {
+    fn from_js(frame: &JsValue) -> Result<Self, String> {
+        if let Some(text) = frame.as_string() {
+            return Ok(Self::Json(text));
+        }
+        if frame.is_instance_of::<js_sys::Uint8Array>()
+            || frame.is_instance_of::<js_sys::ArrayBuffer>()
+        {
+            return Ok(Self::MessagePack(js_sys::Uint8Array::new(frame).to_vec()));
+        }
+        Err("expected a frame string, Uint8Array or ArrayBuffer".to_string())
+    }
+
+    fn into_event_message(self) -> Result<GameEventMessage, String> {
+        match self {
+            Self::Json(text) => {
+                let frame: serde_json::Value =
+                    serde_json::from_str(&text).map_err(|e| e.to_string())?;
+                let inner = frame
+                    .get("GameEvent")
+                    .ok_or_else(|| "expected a GameEvent frame".to_string())?;
+                serde_json::from_value(inner.clone()).map_err(|e| e.to_string())
+            }
+            Self::MessagePack(bytes) => decode_game_event_frame(&bytes).map_err(|e| e.to_string()),
+        }
+    }
+}
+
 fn game_client_from_snapshot_frame(game_id: u32, frame_json: &str) -> Result<GameClient, String> {
     let frame: serde_json::Value = serde_json::from_str(frame_json).map_err(|e| e.to_string())?;
     let game_state_val = frame
@@ -25,13 +62,46 @@ fn game_client_from_snapshot_frame(game_id: u32, frame_json: &str) -> Result<Gam
         .ok_or_else(|| "expected a GameEvent Snapshot frame".to_string())?;
     let game_state: GameState =
         serde_json::from_value(game_state_val.clone()).map_err(|e| e.to_string())?;
+    game_client_from_snapshot_state(game_id, game_state)
+}
+
+fn game_client_from_server_frame(game_id: u32, frame: ServerFrame) -> Result<GameClient, String> {
+    match frame {
+        ServerFrame::Json(text) => game_client_from_snapshot_frame(game_id, &text),
+        ServerFrame::MessagePack(bytes) => {
+            let event = decode_game_event_frame(&bytes)
+                .map_err(|e| e.to_string())?
+                .event;
+            let GameEvent::Snapshot { game_state } = event else {
+                return Err("expected a GameEvent Snapshot frame".to_string());
+            };
+            game_client_from_snapshot_state(game_id, game_state)
+        }
+    }
+}
 
+fn game_client_from_snapshot_state(
+    game_id: u32,
+    game_state: GameState,
+) -> Result<GameClient, String> {
     Ok(GameClient {
         engine: GameEngine::try_new_from_snapshot_state(game_id, game_state)
             .map_err(|e| e.to_string())?,
     })
 }
 
+/// The JSON text of a binary MessagePack frame, so the socket layer can route
+/// it and hand its payload to listeners exactly like a text frame. Only the
+/// engine needs the original bytes: pass those to `processServerFrame` or
+/// `newFromSnapshotFrame`, since u64 fields in the JSON lose precision in
+/// `JSON.parse`.
+#[wasm_bindgen(js_name = decodeServerFrame)]
+pub fn decode_server_frame(frame: &[u8]) -> Result<String, JsValue> {
+    let message = decode_game_event_frame(frame).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_json::to_string(&serde_json::json!({ "GameEvent": message }))
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}
+
 #[wasm_bindgen]
 impl GameClient {
     /// Creates a new game client instance
@@ -196,22 +266,20 @@ impl GameClient {
             .map_err(|e| JsValue::from_str(&e.to_string()))
     }
 
-    /// Apply a raw server WSMessage frame (`{"GameEvent": <GameEventMessage>}`).
+    /// Apply a raw server WSMessage frame (`{"GameEvent": <GameEventMessage>}`),
+    /// given as the frame's JSON text or as the bytes of a binary MessagePack
+    /// frame.
     ///
-    /// The `GameEventMessage` is deserialized from the raw frame text entirely
-    /// in Rust, so full-range `u64` fields survive intact. Passing the parsed
+    /// The `GameEventMessage` is deserialized from the raw frame entirely in
+    /// Rust, so full-range `u64` fields survive intact. Passing the parsed
     /// JS object here instead would have already lost precision: `JSON.parse`
     /// widens every number to an f64, corrupting `TickHash.hash` (an unmasked
     /// 64-bit digest) and breaking divergence detection with false mismatches.
     #[wasm_bindgen(js_name = processServerFrame)]
-    pub fn process_server_frame(&mut self, frame_json: &str) -> Result<(), JsValue> {
-        let frame: serde_json::Value =
-            serde_json::from_str(frame_json).map_err(|e| JsValue::from_str(&e.to_string()))?;
-        let inner = frame
-            .get("GameEvent")
-            .ok_or_else(|| JsValue::from_str("expected a GameEvent frame"))?;
-        let event_message: GameEventMessage =
-            serde_json::from_value(inner.clone()).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    pub fn process_server_frame(&mut self, frame: JsValue) -> Result<(), JsValue> {
+        let event_message = ServerFrame::from_js(&frame)
+            .and_then(ServerFrame::into_event_message)
+            .map_err(|error| JsValue::from_str(&error))?;
 
         self.engine
             .process_server_event(&event_message)
@@ -219,14 +287,16 @@ impl GameClient {
     }
 
     /// Build a client from a raw server WSMessage frame carrying a Snapshot
-    /// (`{"GameEvent": { ..., "event": { "Snapshot": { "game_state": ... } } }}`).
+    /// (`{"GameEvent": { ..., "event": { "Snapshot": { "game_state": ... } } }}`),
+    /// as JSON text or MessagePack bytes.
     ///
-    /// Deserializing the `GameState` from the raw text in Rust keeps its u64
+    /// Deserializing the `GameState` from the raw frame in Rust keeps its u64
     /// fields (notably `rng.state`) intact, matching `processServerFrame`.
     #[wasm_bindgen(js_name = newFromSnapshotFrame)]
-    pub fn new_from_snapshot_frame(game_id: u32, frame_json: &str) -> Result<GameClient, JsValue> {
+    pub fn new_from_snapshot_frame(game_id: u32, frame: JsValue) -> Result<GameClient, JsValue> {
         console_error_panic_hook::set_once();
-        game_client_from_snapshot_frame(game_id, frame_json)
+        ServerFrame::from_js(&frame)
+            .and_then(|frame| game_client_from_server_frame(game_id, frame))
             .map_err(|error| JsValue::from_str(&error))
     }
 
@@ -370,6 +440,13 @@ impl GameClient {
         self.engine.committed_sync_hash().to_string()
     }
 
+    /// Tick of the last snapshot applied, which a resync request
+    /// acknowledges so the server may answer with a delta against it
+    #[wasm_bindgen(js_name = getSnapshotBaseTick)]
+    pub fn get_snapshot_base_tick(&self) -> Option<u32> {
+        self.engine.snapshot_base_tick()
+    }
+
     /// Clear the needs_resync flag once a resync request has been sent
     #[wasm_bindgen(js_name = clearNeedsResync)]
     pub fn clear_needs_resync(&mut self) {
@@ -500,7 +577,11 @@ mod tests {
             GameType::Solo => 1,
             GameType::TeamMatch { per_team } => u32::from(*per_team) * 2,
             GameType::FreeForAll { max_players } => u32::from(*max_players),
-            GameType::Custom { .. } => unreachable!("custom is not a legacy Boost mode"),
+            GameType::Custom { .. }
+            | GameType::KingOfTheHill { .. }
+            | GameType::CaptureTheFlag { .. } => {
+                unreachable!("not a legacy Boost mode")
+            }
         };
         for user_id in 1..=player_count {
             state.add_player(user_id, None).unwrap();
@@ -647,6 +728,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn message_pack_frames_feed_the_engine_like_json_frames() {
+        let hash: u64 = 0xFEDC_BA98_7654_3210;
+        let tick_hash = GameEventMessage {
+            game_id: 1,
+            tick: 5,
+            sequence: 9,
+            stream_seq: 9,
+            user_id: None,
+            event: GameEvent::TickHash {
+                hash,
+                server_ts_ms: 123,
+            },
+        };
+        let frame = common::encode_game_event_frame(&tick_hash).unwrap();
+        let decoded = ServerFrame::MessagePack(frame.clone())
+            .into_event_message()
+            .unwrap();
+        assert!(matches!(decoded.event, GameEvent::TickHash { hash: got, .. } if got == hash));
+        let json = decode_server_frame(&frame).unwrap();
+        let reparsed = ServerFrame::Json(json).into_event_message().unwrap();
+        assert!(matches!(reparsed.event, GameEvent::TickHash { hash: got, .. } if got == hash));
+
+        let completed = legacy_timed_boost_duel_state();
+        let snapshot = GameEventMessage {
+            game_id: 42,
+            tick: completed.tick,
+            sequence: completed.event_sequence,
+            stream_seq: 0,
+            user_id: None,
+            event: GameEvent::Snapshot {
+                game_state: completed.clone(),
+            },
+        };
+        let binary = common::encode_game_event_frame(&snapshot).unwrap();
+        let from_binary =
+            game_client_from_server_frame(42, ServerFrame::MessagePack(binary)).unwrap();
+        let from_json =
+            game_client_from_server_frame(42, ServerFrame::Json(snapshot_frame(42, &completed)))
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(from_binary.engine.committed_state()).unwrap(),
+            serde_json::to_value(from_json.engine.committed_state()).unwrap()
+        );
+        assert!(
+            game_client_from_server_frame(
+                42,
+                ServerFrame::MessagePack(common::encode_game_event_frame(&tick_hash).unwrap())
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn activate_boost_wire_command_contains_only_snake_identity() {
         let command = GameCommand::ActivateBoost { snake_id: 17 };
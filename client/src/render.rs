@@ -2,7 +2,7 @@ use crate::skin::{
     PaintCtx, SkinColors, SkinIdentity, SnakePose, SnakeSkin, paint_alive_with_occlusion,
     skin_registry,
 };
-use common::{BoostPad, GameState, Position};
+use common::{BoostPad, GameState, ObstacleBlock, Position, PowerUpKind, TeamId, ZoneBounds};
 use wasm_bindgen::prelude::*;
 
 /// Transform coordinates based on rotation angle
@@ -67,6 +67,69 @@ fn transformed_pad_bounds(
     Some((min_x, min_y, max_x - min_x + 1.0, max_y - min_y + 1.0))
 }
 
+/// Screen-space bounding box of a map obstacle in grid cells. A rectangle
+/// stays a rectangle under quarter turns, so its two corner cells suffice.
+fn transformed_obstacle_bounds(
+    block: &ObstacleBlock,
+    game_width: f64,
+    game_height: f64,
+    rotation: i32,
+) -> (f64, f64, f64, f64) {
+    let (x0, y0) = transform_coords(
+        block.position.x as f64,
+        block.position.y as f64,
+        game_width,
+        game_height,
+        rotation,
+    );
+    let (x1, y1) = transform_coords(
+        block.position.x as f64 + f64::from(block.width) - 1.0,
+        block.position.y as f64 + f64::from(block.height) - 1.0,
+        game_width,
+        game_height,
+        rotation,
+    );
+    (
+        x0.min(x1),
+        y0.min(y1),
+        (x1 - x0).abs() + 1.0,
+        (y1 - y0).abs() + 1.0,
+    )
+}
+
+/// Screen-space bounding box of the battle royale zone in grid cells, or
+/// `None` once the zone has closed and no cell of the field is safe.
+fn transformed_zone_bounds(
+    zone: &ZoneBounds,
+    game_width: f64,
+    game_height: f64,
+    rotation: i32,
+) -> Option<(f64, f64, f64, f64)> {
+    if zone.is_closed() {
+        return None;
+    }
+    let (x0, y0) = transform_coords(
+        zone.min_x as f64,
+        zone.min_y as f64,
+        game_width,
+        game_height,
+        rotation,
+    );
+    let (x1, y1) = transform_coords(
+        zone.max_x as f64 - 1.0,
+        zone.max_y as f64 - 1.0,
+        game_width,
+        game_height,
+        rotation,
+    );
+    Some((
+        x0.min(x1),
+        y0.min(y1),
+        (x1 - x0).abs() + 1.0,
+        (y1 - y0).abs() + 1.0,
+    ))
+}
+
 fn transformed_active_pad_bounds(
     pads: &[BoostPad],
     game_width: f64,
@@ -92,6 +155,24 @@ const NOS_ORANGE: &str = "#ff641e";
 /// by painting this before the skin paints anything.
 const ARENA_FIELD_COLOR: &str = "#ffffff";
 
+/// Map obstacles are neutral terrain, so they take one fixed colour instead
+/// of a side of the viewer's base theme.
+const ARENA_OBSTACLE_COLOR: &str = NOS_STEEL_DARK;
+
+/// The battle royale zone is a hazard, not a side, so it is the same for
+/// every viewer: a red wash over closed cells and a solid edge on the ring.
+const ZONE_CLOSED_FILL: &str = "rgba(220, 38, 38, 0.16)";
+const ZONE_EDGE_COLOR: &str = "#dc2626";
+
+/// The king-of-the-hill square takes its holder's base colour. Unclaimed it is
+/// a neutral wash; the edge turns to the hazard red while both teams stand on
+/// it, so a stalled count reads at a glance.
+const HILL_UNCLAIMED_FILL: &str = "rgba(15, 23, 42, 0.08)";
+const HILL_EDGE_COLOR: &str = "#f59e0b";
+
+/// Capture-the-flag pennants fly their team's wall colour from a dark pole.
+const FLAG_POLE_COLOR: &str = "#334155";
+
 const NOS_REGULAR_WIDTH_RATIO: f64 = 0.50;
 const NOS_REGULAR_HEIGHT_RATIO: f64 = 0.88;
 const NOS_FULL_WIDTH_RATIO: f64 = 0.68;
@@ -384,6 +465,86 @@ fn draw_regular_nos_canister(
     )
 }
 
+/// Fill and mark of each power-up badge. The mark is a single glyph so the
+/// three kinds stay distinct for players who cannot tell the fills apart.
+fn power_up_palette(kind: PowerUpKind) -> (&'static str, &'static str, &'static str) {
+    match kind {
+        PowerUpKind::Shield => ("#3f8fd6", "#1f5a8f", "S"),
+        PowerUpKind::Ghost => ("#b9b4d6", "#6f6a96", "G"),
+        PowerUpKind::Magnet => ("#d65a5a", "#8f2f2f", "M"),
+    }
+}
+
+fn draw_power_up(
+    ctx: &web_sys::CanvasRenderingContext2d,
+    kind: PowerUpKind,
+    left: f64,
+    top: f64,
+    cell_size: f64,
+) -> Result<(), JsValue> {
+    let (fill, rim, mark) = power_up_palette(kind);
+    let center_x = left + cell_size / 2.0;
+    let center_y = top + cell_size / 2.0;
+    let radius = cell_size / 2.0;
+
+    ctx.set_fill_style_str(rim);
+    ctx.begin_path();
+    ctx.arc(
+        center_x,
+        center_y,
+        radius + 1.0,
+        0.0,
+        2.0 * std::f64::consts::PI,
+    )?;
+    ctx.fill();
+    ctx.set_fill_style_str(fill);
+    ctx.begin_path();
+    ctx.arc(center_x, center_y, radius, 0.0, 2.0 * std::f64::consts::PI)?;
+    ctx.fill();
+
+    ctx.save();
+    ctx.set_text_align("center");
+    ctx.set_text_baseline("middle");
+    ctx.set_font(&format!(
+        "bold {}px sans-serif",
+        (cell_size * 0.7).round().max(6.0)
+    ));
+    ctx.set_fill_style_str("#ffffff");
+    ctx.fill_text(mark, center_x, center_y)?;
+    ctx.restore();
+    Ok(())
+}
+
+/// A pennant on a pole, filling one cell. Drawn over snakes, so a carried
+/// flag rides on its carrier's head.
+fn draw_flag(
+    ctx: &web_sys::CanvasRenderingContext2d,
+    fill: &str,
+    left: f64,
+    top: f64,
+    cell_size: f64,
+) -> Result<(), JsValue> {
+    let pole_x = left + cell_size * 0.25;
+    ctx.save();
+    ctx.set_stroke_style_str(FLAG_POLE_COLOR);
+    ctx.set_line_width((cell_size * 0.12).max(1.0));
+    ctx.begin_path();
+    ctx.move_to(pole_x, top + cell_size * 0.1);
+    ctx.line_to(pole_x, top + cell_size * 0.95);
+    ctx.stroke();
+
+    ctx.set_fill_style_str(fill);
+    ctx.begin_path();
+    ctx.move_to(pole_x, top + cell_size * 0.1);
+    ctx.line_to(left + cell_size * 0.95, top + cell_size * 0.3);
+    ctx.line_to(pole_x, top + cell_size * 0.55);
+    ctx.close_path();
+    ctx.fill();
+    ctx.stroke();
+    ctx.restore();
+    Ok(())
+}
+
 fn draw_full_nos_canister(
     ctx: &web_sys::CanvasRenderingContext2d,
     left: f64,
@@ -1709,6 +1870,72 @@ pub fn render_game_state(
         }
     }
 
+    // Closed battle royale cells are washed over the grid but under food and
+    // snakes, so everything caught outside the ring stays readable.
+    let zone_rect = arena
+        .zone
+        .as_ref()
+        .map(|zone| transformed_zone_bounds(zone, game_width, game_height, rotation_int));
+    if let Some(zone_rect) = zone_rect {
+        ctx.set_fill_style_str(ZONE_CLOSED_FILL);
+        match zone_rect {
+            Some((x, y, zone_width, zone_height)) => {
+                let right = x + zone_width;
+                let bottom = y + zone_height;
+                ctx.fill_rect(0.0, 0.0, width * cell_size, y * cell_size);
+                ctx.fill_rect(
+                    0.0,
+                    bottom * cell_size,
+                    width * cell_size,
+                    (height - bottom) * cell_size,
+                );
+                ctx.fill_rect(0.0, y * cell_size, x * cell_size, zone_height * cell_size);
+                ctx.fill_rect(
+                    right * cell_size,
+                    y * cell_size,
+                    (width - right) * cell_size,
+                    zone_height * cell_size,
+                );
+            }
+            None => ctx.fill_rect(0.0, 0.0, width * cell_size, height * cell_size),
+        }
+    }
+
+    // The hill sits with the zone wash: over the grid, under food and snakes.
+    if let (Some(config), Some(control)) = (&state.properties.hill, &state.hill)
+        && let Some((x, y, hill_width, hill_height)) = transformed_zone_bounds(
+            &config.bounds(arena.width, arena.height),
+            game_width,
+            game_height,
+            rotation_int,
+        )
+    {
+        let fill = match control.holder {
+            Some(TeamId(0)) => base_sides.left_zone,
+            Some(_) => base_sides.right_zone,
+            None => HILL_UNCLAIMED_FILL,
+        };
+        ctx.set_fill_style_str(fill);
+        ctx.fill_rect(
+            x * cell_size,
+            y * cell_size,
+            hill_width * cell_size,
+            hill_height * cell_size,
+        );
+        ctx.set_stroke_style_str(if control.contested {
+            ZONE_EDGE_COLOR
+        } else {
+            HILL_EDGE_COLOR
+        });
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(
+            x * cell_size,
+            y * cell_size,
+            hill_width * cell_size,
+            hill_height * cell_size,
+        );
+    }
+
     // Draw endzone text after dots but before walls and snakes
     // This ensures text is visible over dots but under snakes
     if let Some(team_zone_config) = team_zone_config_data {
@@ -2077,6 +2304,19 @@ pub fn render_game_state(
         }
     }
 
+    // Power-ups sit on the same layer as Boost packets: under every snake, so
+    // a head arriving on one covers it exactly as it is collected.
+    for pickup in &arena.power_ups {
+        let (tx, ty) = transform_coords(
+            pickup.position.x as f64,
+            pickup.position.y as f64,
+            game_width,
+            game_height,
+            rotation_int,
+        );
+        draw_power_up(&ctx, pickup.kind, tx * cell_size, ty * cell_size, cell_size)?;
+    }
+
     // JavaScript owns score-effect animation, but the scoring snake must stay
     // above it. Temporarily return the canvas to its public, un-translated
     // coordinate system so the callback can use the same 1px-padded positions
@@ -2112,6 +2352,26 @@ pub fn render_game_state(
     let mut carried_food_labels: Vec<CarriedFoodLabel> = Vec::new();
     for (index, snake) in snakes.iter().enumerate() {
         let is_alive = snake.is_alive;
+        // On a wraparound field a snake crossing a seam is painted once per
+        // field-sized translation that reaches the field, each copy clipped
+        // to it, so the body leaves one edge and comes back in at the other.
+        let seam_offsets = arena.seam_offsets(snake);
+        let clip_to_field = seam_offsets.len() > 1;
+        let cells_at = |(dx, dy): (i16, i16)| -> Vec<(f64, f64)> {
+            snake
+                .body
+                .iter()
+                .map(|point| {
+                    transform_coords(
+                        (point.x + dx) as f64,
+                        (point.y + dy) as f64,
+                        game_width,
+                        game_height,
+                        rotation_int,
+                    )
+                })
+                .collect()
+        };
 
         if is_alive {
             // Choose snake color based on perspective in team games
@@ -2133,48 +2393,82 @@ pub fn render_game_state(
 
             // Hand the shared skin painter a rotation-resolved body. The roster
             // glyph calls the very same routine, so the two can never drift.
-            let cells: Vec<(f64, f64)> = snake
-                .body
-                .iter()
-                .map(|point| {
-                    transform_coords(
-                        point.x as f64,
-                        point.y as f64,
-                        game_width,
-                        game_height,
-                        rotation_int,
-                    )
-                })
-                .collect();
-
-            let pose = SnakePose {
-                cells: &cells,
-                cell_size,
-                boost_active: snake.boost().active,
-                anim_ms,
-                reduced_motion,
-                detail_scale: crate::skin::arena_detail_scale(cell_size),
-            };
-            paint_alive_with_occlusion(
-                &mut PaintCtx::web(&ctx),
-                skin,
-                &pose,
-                &identity,
-                Some(ARENA_FIELD_COLOR),
-            )?;
+            let cells = cells_at((0, 0));
+            // A ghost is drawn see-through, which is what it is to the others.
+            let ghost = snake.power_ups.ghost();
+            for &offset in &seam_offsets {
+                let copy_cells;
+                let pose = SnakePose {
+                    cells: if offset == (0, 0) {
+                        &cells
+                    } else {
+                        copy_cells = cells_at(offset);
+                        &copy_cells
+                    },
+                    cell_size,
+                    boost_active: snake.boost().active,
+                    anim_ms,
+                    reduced_motion,
+                    detail_scale: crate::skin::arena_detail_scale(cell_size),
+                };
+                if ghost || clip_to_field {
+                    ctx.save();
+                }
+                if clip_to_field {
+                    ctx.begin_path();
+                    ctx.rect(0.0, 0.0, width * cell_size, height * cell_size);
+                    ctx.clip();
+                }
+                if ghost {
+                    ctx.set_global_alpha(0.45);
+                }
+                paint_alive_with_occlusion(
+                    &mut PaintCtx::web(&ctx),
+                    skin,
+                    &pose,
+                    &identity,
+                    Some(ARENA_FIELD_COLOR),
+                )?;
+                if ghost || clip_to_field {
+                    ctx.restore();
+                }
+            }
+            // A held shield rings the head until a crash spends it.
+            if snake.power_ups.shield
+                && let Some(&(head_x, head_y)) = cells.first()
+            {
+                let (fill, _, _) = power_up_palette(PowerUpKind::Shield);
+                ctx.save();
+                ctx.set_stroke_style_str(fill);
+                ctx.set_line_width((cell_size * 0.15).max(1.0));
+                ctx.begin_path();
+                ctx.arc(
+                    head_x * cell_size + cell_size / 2.0,
+                    head_y * cell_size + cell_size / 2.0,
+                    cell_size * 0.85,
+                    0.0,
+                    2.0 * std::f64::consts::PI,
+                )?;
+                ctx.stroke();
+                ctx.restore();
+            }
 
             // Queue the carried-food readout. It rides a couple of cells behind
             // the head, clear of the dark head core and on the stretch of body
             // the head gradient lightens most. A snake carrying nothing queues
-            // nothing.
+            // nothing, and nothing is carried where there is no goal to bank
+            // in, as on the hill or in capture the flag.
             let carried_food = state.carried_food(snake);
             if carried_food > 0
+                && state.properties.hill.is_none()
+                && state.properties.flags.is_none()
                 && let Some(anchor) = body_cell_behind_head(&snake.body, CARRIED_LABEL_OFFSET_CELLS)
                 && Some(&anchor.cell) != snake.body.first()
             {
+                let anchor_cell = arena.wrap_position(anchor.cell);
                 let (anchor_tx, anchor_ty) = transform_coords(
-                    anchor.cell.x as f64,
-                    anchor.cell.y as f64,
+                    anchor_cell.x as f64,
+                    anchor_cell.y as f64,
                     game_width,
                     game_height,
                     rotation_int,
@@ -2192,27 +2486,54 @@ pub fn render_game_state(
                 });
             }
         } else {
-            let cells: Vec<(f64, f64)> = snake
-                .body
-                .iter()
-                .map(|point| {
-                    transform_coords(
-                        point.x as f64,
-                        point.y as f64,
-                        game_width,
-                        game_height,
-                        rotation_int,
-                    )
-                })
-                .collect();
-            let pose = SnakePose::still(&cells, cell_size, false);
-            // Death is identity-erasing by default, but it goes through the
-            // wearer's skin so the decision lives in one place and a future
-            // override has somewhere to land.
-            snake_skins[index].paint_dead(&mut PaintCtx::web(&ctx), &pose)?;
+            for &offset in &seam_offsets {
+                let cells = cells_at(offset);
+                let pose = SnakePose::still(&cells, cell_size, false);
+                if clip_to_field {
+                    ctx.save();
+                    ctx.begin_path();
+                    ctx.rect(0.0, 0.0, width * cell_size, height * cell_size);
+                    ctx.clip();
+                }
+                // Death is identity-erasing by default, but it goes through the
+                // wearer's skin so the decision lives in one place and a future
+                // override has somewhere to land.
+                snake_skins[index].paint_dead(&mut PaintCtx::web(&ctx), &pose)?;
+                if clip_to_field {
+                    ctx.restore();
+                }
+            }
         }
     }
 
+    // Flags stand over the snakes: one at home or on the ground stays in view
+    // as snakes cross it, and a carried one marks its carrier's head.
+    for flag in &state.flags {
+        let position = match flag.carrier() {
+            Some(snake_id) => arena
+                .snakes
+                .get(snake_id as usize)
+                .and_then(|snake| snake.head().ok())
+                .map(|head| arena.wrap_position(*head)),
+            None => flag.position(arena),
+        };
+        let Some(position) = position else {
+            continue;
+        };
+        let (tx, ty) = transform_coords(
+            position.x as f64,
+            position.y as f64,
+            game_width,
+            game_height,
+            rotation_int,
+        );
+        let fill = match flag.team_id {
+            TeamId(0) => base_sides.left_wall,
+            _ => base_sides.right_wall,
+        };
+        draw_flag(&ctx, fill, tx * cell_size, ty * cell_size, cell_size)?;
+    }
+
     // Draw walls AFTER snakes so dead snakes appear behind walls
     if let Some(team_zone_config) = team_zone_config_data {
         let end_zone_depth = team_zone_config.end_zone_depth as f64;
@@ -2384,6 +2705,37 @@ pub fn render_game_state(
         }
     }
 
+    // Map obstacles share the walls' layer: above dead snakes, below labels.
+    if let Some(map) = &arena.map {
+        ctx.set_fill_style_str(ARENA_OBSTACLE_COLOR);
+        for block in &map.obstacles {
+            let (x, y, block_width, block_height) =
+                transformed_obstacle_bounds(block, game_width, game_height, rotation_int);
+            ctx.fill_rect(
+                x * cell_size,
+                y * cell_size,
+                block_width * cell_size,
+                block_height * cell_size,
+            );
+        }
+    }
+
+    // The zone's edge is drawn on the walls' layer so a snake lying across it
+    // never hides where the safe area ends. A full-field zone has no edge to
+    // show yet.
+    if let Some(Some((x, y, zone_width, zone_height))) = zone_rect
+        && (zone_width < width || zone_height < height)
+    {
+        ctx.set_stroke_style_str(ZONE_EDGE_COLOR);
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(
+            x * cell_size,
+            y * cell_size,
+            zone_width * cell_size,
+            zone_height * cell_size,
+        );
+    }
+
     // Carried-food readouts, painted after every snake and the walls.
     // `font`, `textAlign`, `textBaseline` and `lineJoin` are the one class of
     // canvas state nothing else in this file resets, so the pass is wrapped to
@@ -8,7 +8,7 @@
 use crate::render;
 use common::{
     DeathCause, GameEvent, GameState, HighlightClip, LoadedScenario, Position, RecordedGameMessage,
-    ScenarioAddons, ScenarioCamera, ScenarioPlayback, ScenarioPlaybackSegment,
+    ReplayKeyframe, ScenarioAddons, ScenarioCamera, ScenarioPlayback, ScenarioPlaybackSegment,
     ScenarioPresentation, ScenarioScript, SnakeCrash, TeamGoal,
     advance_and_apply_replicated_message,
 };
@@ -233,6 +233,10 @@ struct HighlightPlayback {
     initial_state: GameState,
     state: GameState,
     messages: Vec<RecordedGameMessage>,
+    /// Checkpoints inside the window, verified against the clip's end hash
+    /// when it loaded. Seeks restore from the nearest one instead of
+    /// replaying from the window start.
+    keyframes: Vec<ReplayKeyframe>,
     next_message: usize,
     start_tick: u32,
     end_tick: u32,
@@ -245,26 +249,12 @@ impl HighlightPlayback {
         // This is both the compatibility gate and the mandatory end-hash
         // assertion. Never let a version-skewed or incomplete clip render as
         // if it were authoritative.
-        clip.replay_and_verify()
+        let recording = clip
+            .indexed_recording()
             .map_err(|error| error.to_string())?;
-
-        let mut initial_state = clip.anchor.clone();
-        for message in clip
-            .messages
-            .iter()
-            .filter(|message| message.tick <= clip.window.start_tick)
-        {
-            initial_state = advance_and_apply_replicated_message(
-                &initial_state,
-                &message.envelope(clip.game_id),
-            )
+        let initial_state = recording
+            .state_at_tick(clip.window.start_tick)
             .map_err(|error| error.to_string())?;
-        }
-        while initial_state.tick < clip.window.start_tick && !initial_state.is_complete() {
-            initial_state
-                .tick_forward(true)
-                .map_err(|error| error.to_string())?;
-        }
 
         let messages = clip
             .messages
@@ -272,11 +262,17 @@ impl HighlightPlayback {
             .filter(|message| message.tick > clip.window.start_tick)
             .cloned()
             .collect();
+        let keyframes = recording
+            .keyframes
+            .into_iter()
+            .filter(|keyframe| keyframe.tick > clip.window.start_tick)
+            .collect();
         Ok(Self {
             game_id: clip.game_id,
             state: initial_state.clone(),
             initial_state,
             messages,
+            keyframes,
             next_message: 0,
             start_tick: clip.window.start_tick,
             end_tick: clip.window.end_tick,
@@ -291,6 +287,30 @@ impl HighlightPlayback {
         self.cursor_tick = self.start_tick;
         self.events.clear();
     }
+
+    /// Rewind or skip ahead to the latest keyframe at or before
+    /// `target_tick`, falling back to the window start. Stays put when the
+    /// cursor is already the closer place to replay from.
+    fn restore_before(&mut self, target_tick: u32) {
+        let keyframe = self.keyframes[..self
+            .keyframes
+            .partition_point(|keyframe| keyframe.tick <= target_tick)]
+            .last();
+        let restore_tick = keyframe.map_or(self.start_tick, |keyframe| keyframe.tick);
+        if target_tick >= self.cursor_tick && restore_tick <= self.cursor_tick {
+            return;
+        }
+        let Some(keyframe) = keyframe else {
+            self.reset();
+            return;
+        };
+        self.state = keyframe.state.clone();
+        self.next_message = self.messages.partition_point(|message| {
+            (message.tick, message.sequence) <= (keyframe.tick, keyframe.sequence)
+        });
+        self.cursor_tick = keyframe.tick;
+        self.events.clear();
+    }
 }
 
 impl ScenarioTimeline for HighlightPlayback {
@@ -338,9 +358,7 @@ impl ScenarioTimeline for HighlightPlayback {
 
     fn seek_to_tick(&mut self, target_tick: u32) -> anyhow::Result<u32> {
         let target_tick = target_tick.clamp(self.start_tick, self.end_tick);
-        if target_tick < self.cursor_tick {
-            self.reset();
-        }
+        self.restore_before(target_tick);
         while self.cursor_tick < target_tick && self.advance_one()? {}
         Ok(self.state.tick)
     }
@@ -1067,6 +1085,8 @@ mod tests {
                         combo_chain: 0,
                         combo_remaining_ms: 0,
                         driver: ScenarioDriver::Scripted,
+                        team_strategy: None,
+                        policy: None,
                     },
                     ScenarioSnakePose {
                         user_id: 2,
@@ -1081,16 +1101,20 @@ mod tests {
                         combo_chain: 0,
                         combo_remaining_ms: 0,
                         driver: ScenarioDriver::Scripted,
+                        team_strategy: None,
+                        policy: None,
                     },
                 ],
                 food: vec![Position { x: 20, y: 20 }],
                 team_scores: Vec::new(),
                 start_tick: 0,
+                captured: None,
             },
             commands: vec![ScenarioCommand {
                 at_tick: 4,
                 user_id: 1,
                 command: ScenarioCommandKind::Turn(Direction::Down),
+                scheduled_at: None,
             }],
             run_ticks: 12,
             presentation: ScenarioPresentation {
@@ -1316,6 +1340,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn highlight_seeks_restore_from_verified_keyframes() {
+        let mut script =
+            ScenarioScript::from_json(include_str!("../web/scenarios/team-bank.json")).unwrap();
+        // Long enough for the 2.5 s keyframe cadence to lay down checkpoints;
+        // the fixture's expectations describe its shorter cut.
+        script.run_ticks = 120;
+        script.expect.clear();
+        let loaded = script.load().unwrap();
+        let run = loaded.run().unwrap();
+        let messages = run
+            .events
+            .iter()
+            .filter(|(_, _, event)| !matches!(event, GameEvent::Snapshot { .. }))
+            .enumerate()
+            .map(|(index, (tick, _, event))| RecordedGameMessage {
+                tick: *tick,
+                sequence: index as u64 + 1,
+                event: event.clone(),
+            })
+            .collect();
+        let end_tick = run.final_state.tick;
+        let clip = HighlightClip {
+            clip_format_version: common::HIGHLIGHT_CLIP_FORMAT_VERSION,
+            gameplay_version: common::GAMEPLAY_REPLAY_VERSION,
+            game_id: 72,
+            star_user_id: 1,
+            star_snake_id: 0,
+            star_name: "YOU".into(),
+            reason: common::HighlightReason::FeedingFrenzy { pickups: 8 },
+            score: 120,
+            breakdown: common::HighlightScoreBreakdown::default(),
+            window: common::HighlightWindow {
+                start_tick: 0,
+                end_tick,
+                focus_tick: end_tick / 2,
+            },
+            anchor: loaded.initial_state.clone(),
+            messages,
+            end_sync_hash: run.final_state.sync_hash(),
+            presentation: common::HighlightPresentation {
+                rotation: 0,
+                follow_snake_id: 0,
+                segments: Vec::new(),
+            },
+            config: common::HighlightConfig::default(),
+        };
+        let fresh = HighlightPlayback::new(&clip).unwrap();
+        let keyframe_tick = fresh.keyframes.first().expect("clip is indexed").tick;
+
+        let mut stepped = fresh.clone();
+        let mut hashes = vec![stepped.state().sync_hash()];
+        while stepped.advance_one().unwrap() {
+            hashes.push(stepped.state().sync_hash());
+        }
+        assert_eq!(hashes.last(), Some(&clip.end_sync_hash));
+
+        let mut player = fresh;
+        player.seek_to_tick(end_tick).unwrap();
+        // Nothing is replayed when the target is the keyframe itself.
+        player.seek_to_tick(keyframe_tick).unwrap();
+        assert!(player.events().is_empty());
+        assert_eq!(player.state().sync_hash(), hashes[keyframe_tick as usize]);
+        for target in (0..=end_tick).rev().chain(0..=end_tick) {
+            player.seek_to_tick(target).unwrap();
+            assert_eq!(
+                player.state().sync_hash(),
+                hashes[target as usize],
+                "tick {target}"
+            );
+        }
+    }
+
     #[test]
     fn fixed_and_track_cameras_clamp_and_interpolate_in_rotated_space() {
         let mut player = ScenarioCore::from_json(&script_json()).unwrap();
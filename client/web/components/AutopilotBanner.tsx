@@ -0,0 +1,49 @@
+import React, { useId } from 'react';
+
+export interface AutopilotBannerProps {
+  onConfirmActivity: () => void;
+}
+
+/**
+ * Shown to a player whose snake the AI took over after they went quiet. Any
+ * move hands it back; the button is for a player who would rather not steer
+ * the snake out of wherever the AI left it.
+ */
+const AutopilotBanner: React.FC<AutopilotBannerProps> = ({ onConfirmActivity }) => {
+  const titleId = useId();
+  const descriptionId = useId();
+
+  return (
+    <section
+      className="game-idle-warning is-autopilot"
+      aria-labelledby={titleId}
+      aria-describedby={descriptionId}
+      data-testid="autopilot-banner"
+    >
+      <span className="game-idle-warning__flag" aria-hidden="true">A</span>
+
+      <span className="game-idle-warning__copy">
+        <strong id={titleId}>Autopilot</strong>
+        <span id={descriptionId}>
+          The AI is covering for you. This match earns you no XP or rating.
+        </span>
+      </span>
+
+      <button
+        type="button"
+        className="game-idle-warning__action"
+        onClick={onConfirmActivity}
+        data-testid="autopilot-take-back"
+      >
+        I&rsquo;m here
+      </button>
+
+      <span className="sr-only" role="status">
+        Autopilot is steering your snake. Make a move or use the I&rsquo;m here
+        button to take back control.
+      </span>
+    </section>
+  );
+};
+
+export default AutopilotBanner;
@@ -32,6 +32,10 @@ function CustomGameCreator() {
     allowJoin: true,
     allowSpectators: true,
     snakeStartLength: 3,
+    powerUps: false,
+    shrinkingZone: false,
+    wraparound: false,
+    deathFood: false,
   });
 
   // Navigate to lobby when game is created
@@ -95,6 +99,10 @@ function CustomGameCreator() {
         is_private: !settings.allowJoin,
         allow_spectators: settings.allowSpectators,
         snake_start_length: settings.snakeStartLength,
+        power_ups: settings.powerUps,
+        shrinking_zone: settings.gameMode === 'freeForAll' && settings.shrinkingZone,
+        edge_policy: settings.wraparound ? 'Wrap' : 'Solid',
+        death_food: settings.deathFood,
       };
 
       createCustomGame(serverSettings);
@@ -274,6 +282,48 @@ function CustomGameCreator() {
               />
               <span className="text-sm font-bold uppercase tracking-1">Allow anyone to Spectate</span>
             </label>
+
+            <label className="flex items-center gap-3 cursor-pointer">
+              <input
+                type="checkbox"
+                checked={settings.powerUps}
+                onChange={(e) => handleSettingChange('powerUps', e.target.checked)}
+                className="w-4 h-4 border border-black-70"
+              />
+              <span className="text-sm font-bold uppercase tracking-1">Power-ups (shield, ghost, magnet)</span>
+            </label>
+
+            <label className="flex items-center gap-3 cursor-pointer">
+              <input
+                type="checkbox"
+                checked={settings.wraparound}
+                onChange={(e) => handleSettingChange('wraparound', e.target.checked)}
+                className="w-4 h-4 border border-black-70"
+              />
+              <span className="text-sm font-bold uppercase tracking-1">Wraparound edges</span>
+            </label>
+
+            <label className="flex items-center gap-3 cursor-pointer">
+              <input
+                type="checkbox"
+                checked={settings.deathFood}
+                onChange={(e) => handleSettingChange('deathFood', e.target.checked)}
+                className="w-4 h-4 border border-black-70"
+              />
+              <span className="text-sm font-bold uppercase tracking-1">Dead snakes drop food</span>
+            </label>
+
+            {settings.gameMode === 'freeForAll' && (
+              <label className="flex items-center gap-3 cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={settings.shrinkingZone}
+                  onChange={(e) => handleSettingChange('shrinkingZone', e.target.checked)}
+                  className="w-4 h-4 border border-black-70"
+                />
+                <span className="text-sm font-bold uppercase tracking-1">Battle royale (shrinking zone)</span>
+              </label>
+            )}
           </div>
 
           {/* Action Buttons */}
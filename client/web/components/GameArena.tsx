@@ -20,6 +20,7 @@ import GameHudShell from './GameHudShell';
 import GameControlsHint from './GameControlsHint';
 import IdleKickDialog from './IdleKickDialog';
 import IdleWarningBanner from './IdleWarningBanner';
+import AutopilotBanner from './AutopilotBanner';
 import LoadingScreen from './LoadingScreen';
 import TutorialModal from './TutorialModal';
 import { readSkinPreference } from '../utils/skinPreference';
@@ -334,8 +335,8 @@ export default function GameArena() {
     return admitted;
   }, [sendGameCommand]);
 
-  const handleRequestResync = useCallback(() => {
-    sendRequestResync(gameId);
+  const handleRequestResync = useCallback((baseTick?: number) => {
+    sendRequestResync(gameId, baseTick);
   }, [sendRequestResync, gameId]);
 
   // Use game engine for client-side prediction (call unconditionally to keep hook order stable)
@@ -796,7 +797,10 @@ export default function GameArena() {
 
     // Use the actual team_id from the snake when available; fall back to snake_id parity
     const teamId = snake?.team_id ?? (snakeId % 2);
-    const isTeamGame = typeof state.game_type === 'object' && 'TeamMatch' in state.game_type;
+    const isTeamGame = typeof state.game_type === 'object'
+      && ('TeamMatch' in state.game_type
+        || 'KingOfTheHill' in state.game_type
+        || 'CaptureTheFlag' in state.game_type);
     const forceUnrotated = isTeamGame && isShortWideScreen;
 
     const desiredRotation: ArenaRotation = forceUnrotated
@@ -1059,7 +1063,7 @@ export default function GameArena() {
                 }
               }
             }
-            if (processed && 'Snapshot' in event) {
+            if (processed && ('Snapshot' in event || 'SnapshotDelta' in event)) {
               const snapshotGameId = parseU32GameId(queued.message.game_id);
               if (snapshotGameId !== null) {
                 acknowledgeGameSnapshot(snapshotGameId);
@@ -1308,6 +1312,11 @@ export default function GameArena() {
     !gameOver
       ? localIdle.warning
       : null;
+  const showAutopilotBanner =
+    localIdle.isOnAutopilot &&
+    isGameInteractionActive &&
+    !connectionStale &&
+    !gameOver;
   const showIdleKickDialog = localWasIdleKicked && !isGameComplete && !gameOver;
 
   // The help screen is the same modal surface as the briefing: it suppresses
@@ -1621,6 +1630,10 @@ export default function GameArena() {
         return { TeamMatch: { per_team: 2 } };
       case 'ffa':
         return { FreeForAll: { max_players: 4 } };
+      case 'hill':
+        return { KingOfTheHill: { per_team: 1 } };
+      case 'ctf':
+        return { CaptureTheFlag: { per_team: 1 } };
       case 'solo':
       default:
         return 'Solo';
@@ -1896,6 +1909,10 @@ export default function GameArena() {
                 />
               )}
 
+              {showAutopilotBanner && (
+                <AutopilotBanner onConfirmActivity={handleConfirmActivity} />
+              )}
+
               {/* Countdown Overlay */}
               {showCountdown && countdownState && (
                 <div
@@ -245,8 +245,12 @@ const GameOverCard: React.FC<GameOverCardProps> = ({
   }
 
   const current = presentation.currentPlayer;
+  // A best-of-N match is settled on rounds, so that is the line to report.
+  const [leftSide, rightSide] = presentation.sides;
   const scoreline = presentation.isTeamGame
-    ? `${presentation.sides[0]?.score ?? 0}–${presentation.sides[1]?.score ?? 0}`
+    ? (leftSide?.roundWins != null && rightSide?.roundWins != null
+      ? `${leftSide.roundWins}–${rightSide.roundWins}`
+      : `${leftSide?.score ?? 0}–${rightSide?.score ?? 0}`)
     : current?.score.toString() ?? presentation.soloScore.toString();
 
 
@@ -202,7 +202,9 @@ export const GameStartForm: React.FC<GameStartFormProps> = ({
     { id: 'duel', label: 'DUEL' },
     { id: '2v2', label: '2V2' },
     { id: 'solo', label: 'SOLO' },
-    { id: 'ffa', label: 'FFA' }
+    { id: 'ffa', label: 'FFA' },
+    { id: 'hill', label: 'HILL' },
+    { id: 'ctf', label: 'CTF' }
   ];
 
   const toggleMode = (mode: LobbyGameMode) => {
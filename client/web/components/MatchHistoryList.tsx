@@ -72,6 +72,12 @@ const HistoryPlayerRow: React.FC<{
           <dd>{player.teamScore.toLocaleString()}</dd>
         </div>
       )}
+      {typeof player.roundsWon === 'number' && (
+        <div>
+          <dt>Rounds</dt>
+          <dd>{player.roundsWon}</dd>
+        </div>
+      )}
       <div>
         <dt>XP</dt>
         <dd className="is-positive">{signed(player.xpGained)}</dd>
@@ -155,6 +155,10 @@ export const NewHome: React.FC<NewHomeProps> = ({ onOpenAuth, onOpenAccount }) =
           return { TeamMatch: { per_team: 2 } };
         } else if (mode === 'ffa') {
           return { FreeForAll: { max_players: 4 } };
+        } else if (mode === 'hill') {
+          return { KingOfTheHill: { per_team: 1 } };
+        } else if (mode === 'ctf') {
+          return { CaptureTheFlag: { per_team: 1 } };
         } else {
           return 'Solo';
         }
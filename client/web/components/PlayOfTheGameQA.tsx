@@ -156,6 +156,7 @@ const presentation: MatchPresentation = {
       label: 'Your team',
       color: '#3b82f6',
       score: 25,
+      roundWins: null,
       players: [basePlayers[0]],
       isCurrentSide: true,
       isWinner: true,
@@ -165,6 +166,7 @@ const presentation: MatchPresentation = {
       label: 'Opponents',
       color: '#ef4444',
       score: 19,
+      roundWins: null,
       players: [basePlayers[1]],
       isCurrentSide: false,
       isWinner: false,
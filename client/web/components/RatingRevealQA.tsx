@@ -191,6 +191,7 @@ const presentation: MatchPresentation = {
     label: index === 0 ? 'Your team' : 'Opponents',
     color: index === 0 ? '#3C8DDE' : '#EF5A5A',
     score: player.score,
+    roundWins: null,
     players: [player],
     isCurrentSide: index === 0,
     isWinner: index === 0,
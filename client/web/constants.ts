@@ -1,4 +1,5 @@
 import type { ClientDistribution } from './types/generated/ClientDistribution';
+import type { WireEncoding } from './types/generated/WireEncoding';
 import type { WSMessage } from './types/generated/WSMessage';
 
 // Game tick interval constants (matching Rust constants)
@@ -8,6 +9,11 @@ export const DEFAULT_TICK_INTERVAL_MS = 100;
 export const EXECUTOR_POLL_INTERVAL_MS = 10;
 export const DEFAULT_CUSTOM_GAME_TICK_MS = 100;
 // Gameplay protocol version. Predictive simulation requires an exact match:
+// Protocol 15 catches up with the modes that reached the wire unversioned:
+// best-of-N rounds, power-ups, the shrinking zone, wraparound, king of the
+// hill, capture the flag, death food, AI seats and autopilot, and spectating.
+// Protocol 14 lets RequestResync acknowledge a snapshot, answered by a delta.
+// Protocol 13 lets Authenticate ask for game events as binary MessagePack.
 // Protocol 12 adds the rematch opt-in on the results card and its live roster.
 // Protocol 11 adds the social layer — the per-region online-player roster and
 // player-to-player challenges, both pushed by the server.
@@ -17,8 +23,9 @@ export const DEFAULT_CUSTOM_GAME_TICK_MS = 100;
 // carries both — deterministic death attribution on SnakeDied events, and
 // per-session distribution routing for server-owned advertisement policy.
 // (Protocol 8 changed scoring and physical growth.)
-// Tracks WS_PROTOCOL_VERSION in server/src/lifecycle.rs.
-export const GAMEPLAY_PROTOCOL_VERSION = 12;
+// Tracks WS_PROTOCOL_VERSION in common/src/wire.rs and
+// GAMEPLAY_REPLAY_VERSION in common/src/highlight.rs.
+export const GAMEPLAY_PROTOCOL_VERSION = 15;
 export const isGameplayProtocolCompatible = (serverVersion: unknown): boolean =>
   Number(serverVersion) === GAMEPLAY_PROTOCOL_VERSION;
 export const GAMEPLAY_UPDATE_REQUIRED_PREFIX = 'Gameplay update required';
@@ -53,13 +60,20 @@ export const CLIENT_DISTRIBUTION: ClientDistribution = resolveClientDistribution
 
 // `anon_id` is additive and optional on both ends: an older server ignores the
 // unknown field, and this server defaults it to absent for older clients, so it
-// is deliberately NOT part of the version gate above.
-export const buildGameplayAuthentication = (token: string, anonId?: string) => ({
+// is deliberately NOT part of the version gate above. `encoding` is omitted for
+// JSON, the server's default, so only a client that can decode MessagePack
+// frames ever asks for them.
+export const buildGameplayAuthentication = (
+  token: string,
+  anonId?: string,
+  encoding?: WireEncoding,
+) => ({
   Authenticate: {
     token,
     protocol_version: GAMEPLAY_PROTOCOL_VERSION,
     distribution: CLIENT_DISTRIBUTION,
     ...(anonId ? { anon_id: anonId } : {}),
+    ...(encoding && encoding !== 'Json' ? { encoding } : {}),
   },
 } as const satisfies WSMessage);
 // Game speed mappings
@@ -17,9 +17,12 @@ import {
 } from '../types';
 import {
   OutboundMessage,
+  RawServerFrame,
   WSMessageTag,
   TypedMessage,
 } from '../types/protocol';
+import type { WireEncoding } from '../types/generated/WireEncoding';
+import { getWasm } from '../wasm';
 import { clockSync } from '../utils/clockSync';
 import { record as recordTrace } from '../utils/syncTrace';
 import {
@@ -86,9 +89,9 @@ interface SocketSlot {
   authenticated: boolean;
   capabilities: string[];
   authTokenSent: string | null;
-  // Retains the exact frame text alongside the parsed object so a buffered
+  // Retains the exact frame alongside the parsed object so a buffered
   // message replayed later still carries `raw` for the u64-safe WASM path.
-  bufferedMessages: Array<{ rawMessage: any; rawText: string }>;
+  bufferedMessages: Array<{ rawMessage: any; rawText: RawServerFrame }>;
   expectedLobbyCode: string | null;
   expectedGameId: number | null;
   lobbyReady: boolean;
@@ -135,7 +138,7 @@ export const lobbyStorageKeyForIdentity = (
 const MAX_CHAT_HISTORY = 200;
 /** Stable empty inbox so an un-populated social layer never re-renders consumers. */
 const EMPTY_CHALLENGE_INBOX: ChallengeInbox = { incoming: [], outgoing: [] };
-const VALID_LOBBY_MODES: LobbyGameMode[] = ['duel', '2v2', 'solo', 'ffa'];
+const VALID_LOBBY_MODES: LobbyGameMode[] = ['duel', '2v2', 'solo', 'ffa', 'hill', 'ctf'];
 const VALID_LOBBY_STATES: LobbyState[] = ['waiting', 'ad_break', 'queued', 'matched'];
 const MAX_RECOVERY_METRIC_MS = 5 * 60 * 1000;
 // RFC close codes in the 1001-2999 range are reserved for the protocol and
@@ -177,6 +180,28 @@ const authoritativeLobbyState = (rawMessage: any): string | null => {
   return typeof state === 'string' ? state.trim().toLowerCase() : null;
 };
 
+// Binary game events are decoded by the WASM module, so a socket asks for
+// them only once it has loaded; until then it stays on JSON.
+const gameEventEncoding = (): WireEncoding | undefined =>
+  getWasm() ? 'MessagePack' : undefined;
+
+/**
+ * The JSON text to route a frame by, and the frame itself for the engine. A
+ * binary frame is a MessagePack game event; its JSON form is only for
+ * routing, since the engine must get the original bytes.
+ */
+const readServerFrame = (data: unknown): { text: string; raw: RawServerFrame } => {
+  if (typeof data === 'string') {
+    return { text: data, raw: data };
+  }
+  const wasm = getWasm();
+  if (!(data instanceof ArrayBuffer) || !wasm) {
+    throw new Error('binary WebSocket frame received without a MessagePack decoder');
+  }
+  const raw = new Uint8Array(data);
+  return { text: wasm.decodeServerFrame(raw), raw };
+};
+
 const clearAuthenticationTimeout = (slot: SocketSlot) => {
   if (slot.authTimeoutId !== null) {
     clearTimeout(slot.authTimeoutId);
@@ -645,7 +670,7 @@ export const WebSocketProvider: React.FC<WebSocketProviderProps> = ({ children }
     );
   }, []);
 
-  const dispatchRawMessage = useCallback((slot: SocketSlot, rawMessage: any, rawText: string) => {
+  const dispatchRawMessage = useCallback((slot: SocketSlot, rawMessage: any, rawText: RawServerFrame) => {
     if (activeSlotRef.current !== slot || slot.role !== 'active') {
       return;
     }
@@ -1004,7 +1029,7 @@ export const WebSocketProvider: React.FC<WebSocketProviderProps> = ({ children }
     }
   }, []);
 
-  const handleParsedMessage = useCallback((slot: SocketSlot, rawMessage: any, rawText: string) => {
+  const handleParsedMessage = useCallback((slot: SocketSlot, rawMessage: any, rawText: RawServerFrame) => {
     if (slot.role === 'retired') {
       return;
     }
@@ -1400,7 +1425,9 @@ export const WebSocketProvider: React.FC<WebSocketProviderProps> = ({ children }
         slot.authStartedAtMs = Date.now();
         slot.authTokenSent = token;
         slot.socket.send(
-          JSON.stringify(buildGameplayAuthentication(token, getOrCreateAnonId()))
+          JSON.stringify(
+            buildGameplayAuthentication(token, getOrCreateAnonId(), gameEventEncoding()),
+          )
         );
         lastAuthTokenRef.current = token;
         armAuthenticationTimeout(slot, token);
@@ -1432,7 +1459,8 @@ export const WebSocketProvider: React.FC<WebSocketProviderProps> = ({ children }
           return;
         }
         try {
-          handleParsedMessage(slot, JSON.parse(event.data), event.data);
+          const { text, raw } = readServerFrame(event.data);
+          handleParsedMessage(slot, JSON.parse(text), raw);
         } catch (error) {
           console.error('Failed to parse WebSocket message:', error);
         }
@@ -1508,6 +1536,7 @@ export const WebSocketProvider: React.FC<WebSocketProviderProps> = ({ children }
 
   const createSlot = useCallback((url: string, role: SocketRole): SocketSlot => {
     const socket = new WebSocket(url);
+    socket.binaryType = 'arraybuffer';
     return {
       socket,
       generation: ++nextGenerationRef.current,
@@ -1805,7 +1834,9 @@ export const WebSocketProvider: React.FC<WebSocketProviderProps> = ({ children }
       slot.authTokenSent = token;
       try {
         slot.socket.send(
-          JSON.stringify(buildGameplayAuthentication(token, getOrCreateAnonId()))
+          JSON.stringify(
+            buildGameplayAuthentication(token, getOrCreateAnonId(), gameEventEncoding()),
+          )
         );
       } catch (error) {
         slot.authStartedAtMs = null;
@@ -10,7 +10,8 @@ interface UseGameEngineProps {
   gameId: string;
   playerId: number;
   onCommandReady?: (commandMessage: GameCommandMessage) => boolean;
-  onRequestResync?: () => void;
+  /** `baseTick` is the snapshot the engine holds, when it holds one. */
+  onRequestResync?: (baseTick?: number) => void;
   latencyMs?: number;
 }
 
@@ -229,7 +230,7 @@ export const useGameEngine = ({
         const latestSync = prevSyncStatusRef.current;
         if (latestSync?.needs_resync && nowMs - lastResyncSentAtRef.current >= RESYNC_DEBOUNCE_MS) {
           lastResyncSentAtRef.current = nowMs;
-          onRequestResyncRef.current?.();
+          onRequestResyncRef.current?.(engineRef.current.getSnapshotBaseTick());
           engineRef.current.clearNeedsResync();
           recordTrace({ Note: { ts_ms: nowMs, note: 'resync requested (needs_resync)' } });
         }
@@ -265,7 +266,7 @@ export const useGameEngine = ({
             });
           }
           if (nowMs >= watchdogNextSendAtRef.current) {
-            onRequestResyncRef.current?.();
+            onRequestResyncRef.current?.(engineRef.current?.getSnapshotBaseTick());
             recordTrace({
               Note: {
                 ts_ms: nowMs,
@@ -450,9 +451,10 @@ export const useGameEngine = ({
 
   // Process server event for reconciliation. `queued.message` is the
   // JS-parsed GameEventMessage envelope, used only for routing (game_id is
-  // u32, the event kind is structural). `queued.raw` is the exact frame text,
-  // handed to the engine so full-range u64 fields (e.g. TickHash.hash) are
-  // parsed in Rust rather than corrupted by a JS JSON round-trip.
+  // u32, the event kind is structural). `queued.raw` is the exact frame, text
+  // or bytes, handed to the engine so full-range u64 fields (e.g.
+  // TickHash.hash) are parsed in Rust rather than corrupted by a JS JSON
+  // round-trip.
   const processServerEvent = useCallback(async (queued: QueuedGameEvent) => {
     try {
       const fullEventMessage = queued.message;
@@ -482,6 +484,10 @@ export const useGameEngine = ({
 
       const snapshotState = 'Snapshot' in event ? event.Snapshot.game_state : null;
       const isSnapshot = snapshotState !== null;
+      // A delta is rebuilt from the snapshot the current engine holds, so it
+      // must go through that engine rather than a fresh one. Once applied it
+      // replaces committed state just like a full snapshot.
+      const isSnapshotDelta = 'SnapshotDelta' in event;
       if (isSnapshot) {
         // A reconnect Snapshot replaces both committed and predicted state. Applying it to an
         // existing client only replaces committed state, which can leave pre-disconnect
@@ -556,6 +562,10 @@ export const useGameEngine = ({
         // Forwarding the raw frame (rather than JSON.stringify(fullEventMessage))
         // keeps full-range u64 fields intact end-to-end.
         engineRef.current.processServerFrame(queued.raw);
+        if (isSnapshotDelta) {
+          engineEpochRef.current += 1;
+          engineBaselineTickRef.current = fullEventMessage.tick;
+        }
         // Reconcile immediately, even when wall-clock time has not crossed a
         // tick boundary. The arena's next paint must see corrections (and
         // retract invalid crash effects) in the same visual frame.
@@ -564,7 +574,7 @@ export const useGameEngine = ({
           BigInt(Date.now() + Math.round(serverClockOffsetMs)),
         );
 
-        if (isSnapshot) {
+        if (isSnapshot || isSnapshotDelta) {
           // Synchronize React state before the caller dismisses its awaiting-snapshot overlay.
           // This prevents a reconnect or retry from briefly revealing the stale pre-reconnect
           // arena between receipt and the next animation frame.
@@ -3,17 +3,19 @@ import { useNavigate } from 'react-router-dom';
 import { useWebSocket } from '../contexts/WebSocketContext';
 import { useAuth } from '../contexts/AuthContext';
 import { GameState, GameType, GameCommand, GameEventMessage, Command, CustomGameSettings, GameLoadFailure } from '../types';
+import type { RawServerFrame } from '../types/protocol';
 import { DEFAULT_TICK_INTERVAL_MS } from '../constants';
 import { INVALID_GAME_ID_REASON, parseU32GameId } from '../utils/gameId';
 
 /**
- * A queued inbound game event. `raw` is the exact `{"GameEvent": ...}` frame
- * text, forwarded to the WASM engine (processServerFrame / newFromSnapshotFrame)
- * so full-range u64 fields survive; `message` is the JS-parsed envelope used for
- * routing and metadata only (game_id is u32, the event kind is structural).
+ * A queued inbound game event. `raw` is the exact `{"GameEvent": ...}` frame,
+ * JSON text or MessagePack bytes, forwarded to the WASM engine
+ * (processServerFrame / newFromSnapshotFrame) so full-range u64 fields survive;
+ * `message` is the JS-parsed envelope used for routing and metadata only
+ * (game_id is u32, the event kind is structural).
  */
 export interface QueuedGameEvent {
-  raw: string;
+  raw: RawServerFrame;
   message: GameEventMessage;
 }
 import {
@@ -58,7 +60,7 @@ interface UseGameWebSocketReturn {
   startCustomGame: () => void;
   spectateGame: (gameId: string, gameCode?: string | null) => void;
   sendGameCommand: (command: GameCommand) => boolean;
-  sendRequestResync: (gameId: string) => void;
+  sendRequestResync: (gameId: string, baseTick?: number) => void;
   sendPlayerReady: (gameId: string) => void;
   connected: boolean;
 }
@@ -123,8 +125,9 @@ export const useGameWebSocket = (): UseGameWebSocketReturn => {
     // Game events (including game state updates). `message.data` is the parsed
     // GameEventMessage, used here only for routing/outbox reconciliation
     // (game_id is u32, the event kind is structural). `message.raw` is the
-    // exact frame text, queued for the engine so full-range u64 fields (e.g.
-    // TickHash.hash) are never corrupted by a JS JSON.parse round-trip.
+    // exact frame, text or bytes, queued for the engine so full-range u64
+    // fields (e.g. TickHash.hash) are never corrupted by a JS JSON.parse
+    // round-trip.
     unsubscribers.push(
       onMessage('GameEvent', (message) => {
         const eventMessage = message.data;
@@ -157,7 +160,7 @@ export const useGameWebSocket = (): UseGameWebSocketReturn => {
         if (
           eventGameId !== null &&
           serverAssignedGameRef.current === eventGameId &&
-          'Snapshot' in event
+          ('Snapshot' in event || 'SnapshotDelta' in event)
         ) {
           serverAssignedGameRef.current = null;
           setMatchmakingStatus('idle');
@@ -656,8 +659,10 @@ export const useGameWebSocket = (): UseGameWebSocketReturn => {
   }, [isConnected, isSessionAuthenticated, sendMessage, serverCapabilities, user]);
 
   // Ask the game executor for a fresh snapshot when the engine detects
-  // a stream gap / hash divergence, matching WSMessage::RequestResync
-  const sendRequestResync = useCallback((gameId: string) => {
+  // a stream gap / hash divergence, matching WSMessage::RequestResync.
+  // `baseTick` names the snapshot the engine holds, so the answer may be a
+  // SnapshotDelta against it.
+  const sendRequestResync = useCallback((gameId: string, baseTick?: number) => {
     const numericGameId = parseInt(gameId, 10);
     if (!Number.isFinite(numericGameId)) {
       console.error('Cannot request resync for invalid game ID:', gameId);
@@ -665,7 +670,7 @@ export const useGameWebSocket = (): UseGameWebSocketReturn => {
     }
     console.log('Sending RequestResync for game', numericGameId);
     sendMessage({
-      RequestResync: { game_id: numericGameId }
+      RequestResync: { game_id: numericGameId, base_tick: baseTick }
     });
   }, [sendMessage]);
 
@@ -17,7 +17,7 @@ import type { CheckUsernameResponse } from '../types/generated';
 import { getOrCreateAnonId } from '../utils/anonId';
 import type { PlayerLobbyResponse } from '../types/generated';
 import type { NewsTickerResponse } from '../types/generated';
-import type { HighlightClip } from '../types/generated';
+import type { HighlightClip, HighlightMoment } from '../types/generated';
 import type { PublicGameResponse } from '../types/generated';
 
 /** Error thrown by `API.request` for a non-2xx response. */
@@ -78,6 +78,16 @@ export type GameHighlightResponse =
   | { status: 'pending' }
   | { status: 'ready'; play_of_the_game: HighlightClip }
   | { status: 'unavailable' };
+
+export type GameHighlightMomentsResponse =
+  | { status: 'pending' }
+  | { status: 'ready'; moments: HighlightMoment[] }
+  | { status: 'unavailable' };
+
+export type PlayerHighlightResponse =
+  | { status: 'pending' }
+  | { status: 'ready'; moment: HighlightMoment }
+  | { status: 'unavailable' };
 export interface AdminHistoryFilters {
   cursor?: string | null;
   limit?: number;
@@ -328,6 +338,30 @@ class API {
     );
   }
 
+  /** The match's ranked moments, Play of the Game first. Each is a window
+   * into the game's replay rather than a clip. */
+  async getGameHighlightMoments(
+    gameId: string,
+    signal?: AbortSignal,
+  ): Promise<GameHighlightMomentsResponse> {
+    return this.request<GameHighlightMomentsResponse>(
+      `/api/games/${encodeURIComponent(gameId)}/highlight/moments`,
+      { signal },
+    );
+  }
+
+  /** One player's best moment of the match, when they had one. */
+  async getPlayerHighlight(
+    gameId: string,
+    userId: number,
+    signal?: AbortSignal,
+  ): Promise<PlayerHighlightResponse> {
+    return this.request<PlayerHighlightResponse>(
+      `/api/games/${encodeURIComponent(gameId)}/highlight/players/${userId}`,
+      { signal },
+    );
+  }
+
   /**
    * The permanent public summary of a finished match. Anonymous by design —
    * this is what a shared link resolves to, and it must work for someone who
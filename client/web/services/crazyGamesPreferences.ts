@@ -53,7 +53,7 @@ const lobbyPreferences = (
   if (!Array.isArray(rawModes)) {
     return undefined;
   }
-  const validModes = new Set(['solo', 'duel', '2v2', 'ffa']);
+  const validModes = new Set(['solo', 'duel', '2v2', 'ffa', 'hill', 'ctf']);
   const selectedModes = [...new Set(rawModes
     .filter((mode): mode is string => typeof mode === 'string')
     .map((mode) => mode.trim().toLowerCase())
@@ -166,14 +166,20 @@ export function activeGameIdFromLocation(
   return activeGameIdFromPath(hashPath) ?? activeGameIdFromPath(location.pathname);
 }
 
+// A SnapshotDelta is a whole snapshot sent as changes from one the client
+// already holds; once applied it anchors the stream exactly like a Snapshot.
+function isSnapshotEvent(event: any): boolean {
+  return Boolean(
+    event &&
+    typeof event === 'object' &&
+    (Object.prototype.hasOwnProperty.call(event, 'Snapshot') ||
+      Object.prototype.hasOwnProperty.call(event, 'SnapshotDelta')),
+  );
+}
+
 export function isSnapshotForGame(rawMessage: any, gameId: number): boolean {
   const event = rawMessage?.GameEvent;
-  return (
-    Number(event?.game_id) === gameId &&
-    event?.event &&
-    typeof event.event === 'object' &&
-    Object.prototype.hasOwnProperty.call(event.event, 'Snapshot')
-  );
+  return Number(event?.game_id) === gameId && isSnapshotEvent(event?.event);
 }
 
 export function isTerminalSnapshotForGame(rawMessage: any, gameId: number): boolean {
@@ -225,11 +231,7 @@ export function advanceCandidateGameWatermark(
   if (!Number.isSafeInteger(streamSequence) || streamSequence < 0) {
     return current;
   }
-  if (
-    event?.event &&
-    typeof event.event === 'object' &&
-    Object.prototype.hasOwnProperty.call(event.event, 'Snapshot')
-  ) {
+  if (isSnapshotEvent(event?.event)) {
     return streamSequence;
   }
   if (current === null || streamSequence <= current) {
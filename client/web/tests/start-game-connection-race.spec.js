@@ -50,7 +50,7 @@ test('an early Start Game click waits for the authenticated regional socket', as
           socket.send(JSON.stringify({
             Authenticated: {
               task_boot_id: 'start-race-test',
-              protocol_version: 12,
+              protocol_version: 15,
               capabilities: REQUIRED_CAPABILITIES,
               socket_generation: 1,
             },
@@ -163,7 +163,7 @@ test('an early Start Game click waits for the authenticated regional socket', as
       {
         Authenticate: {
           token: 'guest-race-token',
-          protocol_version: 12,
+          protocol_version: 15,
           distribution: 'web',
         },
       },
@@ -152,6 +152,7 @@ test('terminal modes surface every supported elimination label and hide absent h
   assert.equal(buildDeathAttribution('OutOfBounds', false, resolveSnakeName), 'Eliminated by boundary');
   assert.equal(buildDeathAttribution('EnemyBase', false, resolveSnakeName), 'Eliminated by enemy base');
   assert.equal(buildDeathAttribution('SelfCollision', false, resolveSnakeName), 'Eliminated by yourself');
+  assert.equal(buildDeathAttribution('Zone', false, resolveSnakeName), 'Eliminated by the zone');
   assert.equal(buildDeathAttribution('EnemyBase', true, resolveSnakeName), 'Demolished by enemy base');
   assert.equal(buildDeathAttribution('SelfCollision', true, resolveSnakeName), 'Demolished by yourself');
   assert.equal(buildDeathAttribution('Banked', false, resolveSnakeName), null);
@@ -204,6 +205,23 @@ test('a teammate winning produces Victory and retains the current player XP/stat
   assert.equal(formatPerMinuteRate(presentation.actionsPerMinute), '600.0');
 });
 
+test('a best-of-three match names the round and reports each side\'s round wins', () => {
+  const state = duelState();
+  state.rounds = {
+    rounds_to_win: 2,
+    current_round: 3,
+    rounds_completed: 3,
+    round_wins: { 0: 2, 1: 1 },
+  };
+  const presentation = buildMatchPresentation(state, 7);
+
+  assert.equal(presentation.timeLabel, 'Round 3 · First to 50');
+  assert.deepEqual(presentation.sides.map((side) => side.roundWins), [2, 1]);
+
+  const singleRound = buildMatchPresentation(duelState(), 7);
+  assert.deepEqual(singleRound.sides.map((side) => side.roundWins), [null, null]);
+});
+
 test('result artwork is selected from outcome state rather than display copy', () => {
   const defeat = buildMatchPresentation(duelState(), 8);
   assert.equal(defeat.resultTitle, 'Defeat');
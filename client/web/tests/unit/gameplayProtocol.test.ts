@@ -14,23 +14,23 @@ test('gameplay authentication reports the protocol version and build distributio
     process.env.CRAZYGAMES_BUILD === 'true',
     process.env.ITCH_BUILD === 'true',
   );
-  assert.equal(GAMEPLAY_PROTOCOL_VERSION, 12);
+  assert.equal(GAMEPLAY_PROTOCOL_VERSION, 15);
   assert.equal(CLIENT_DISTRIBUTION, expectedDistribution);
   assert.deepEqual(buildGameplayAuthentication('test-token'), {
     Authenticate: {
       token: 'test-token',
-      protocol_version: 12,
+      protocol_version: 15,
       distribution: expectedDistribution,
     },
   });
 });
 
 test('predictive gameplay requires an exact protocol match', () => {
-  assert.equal(isGameplayProtocolCompatible(12), true);
-  assert.equal(isGameplayProtocolCompatible(11), false);
-  assert.equal(isGameplayProtocolCompatible(13), false);
+  assert.equal(isGameplayProtocolCompatible(15), true);
+  assert.equal(isGameplayProtocolCompatible(14), false);
+  assert.equal(isGameplayProtocolCompatible(16), false);
   assert.equal(isGameplayProtocolCompatible(undefined), false);
-  assert.equal(isGameplayProtocolCompatible('12'), true);
+  assert.equal(isGameplayProtocolCompatible('15'), true);
   assert.equal(
     isGameplayUpdateRequiredReason('Gameplay update required: client protocol 9'),
     true,
@@ -70,3 +70,14 @@ test('omitting the anon id leaves the frame unchanged', () => {
   assert.equal('anon_id' in buildGameplayAuthentication('t').Authenticate, false);
   assert.equal('anon_id' in buildGameplayAuthentication('t', '').Authenticate, false);
 });
+
+// Only a client that can decode binary frames asks for them; JSON is the
+// server's default and is never spelled out.
+test('gameplay authentication asks for MessagePack only when requested', () => {
+  assert.equal(
+    buildGameplayAuthentication('t', undefined, 'MessagePack').Authenticate.encoding,
+    'MessagePack',
+  );
+  assert.equal('encoding' in buildGameplayAuthentication('t').Authenticate, false);
+  assert.equal('encoding' in buildGameplayAuthentication('t', undefined, 'Json').Authenticate, false);
+});
@@ -1,5 +1,6 @@
 import assert from 'node:assert/strict';
 import test from 'node:test';
+import { GAMEPLAY_PROTOCOL_VERSION } from '../../constants.ts';
 import type { HighlightClip } from '../../types/generated/HighlightClip.ts';
 import {
   canAutoplayHighlight,
@@ -19,7 +20,7 @@ const eligibleAutoplayGate = {
 
 const clipFixture = (): HighlightClip => ({
   clip_format_version: 1,
-  gameplay_version: 12,
+  gameplay_version: GAMEPLAY_PROTOCOL_VERSION,
   game_id: 7,
   star_user_id: 11,
   star_snake_id: 0,
@@ -38,6 +39,13 @@ const clipFixture = (): HighlightClip => ({
     demolition_points: 180,
     banking_points: 0,
     combo_points: 0,
+    contested_captures: 0,
+    flag_captures: 0,
+    comeback_deficit: 0,
+    buzzer_beater_points: 0,
+    survival_seconds: 0,
+    trap_escapes: 0,
+    clutch_points: 0,
   },
   window: { start_tick: 100, end_tick: 190, focus_tick: 160 },
   anchor: {
@@ -81,6 +89,30 @@ test('highlight captions are human and plural-aware', () => {
     formatHighlightReason({ ComboFrenzy: { max_chain: 6 } }),
     'Combo frenzy — 6× chain',
   );
+  assert.equal(
+    formatHighlightReason({ ContestedCapture: { captures: 2 } }),
+    'Hill taken — 2 captures',
+  );
+  assert.equal(
+    formatHighlightReason({ FlagCapture: { captures: 1 } }),
+    'Flag run — 1 capture',
+  );
+  assert.equal(
+    formatHighlightReason({ Comeback: { deficit: 9 } }),
+    'Comeback — up from 9 points down',
+  );
+  assert.equal(
+    formatHighlightReason({ BuzzerBeater: { points: 1 } }),
+    'Buzzer beater — 1 point',
+  );
+  assert.equal(
+    formatHighlightReason({ SurvivalStreak: { seconds: 74 } }),
+    'Survivor — 74s unbroken',
+  );
+  assert.equal(
+    formatHighlightReason({ TrapEscape: { escapes: 2 } }),
+    'Great escape — 2 traps slipped',
+  );
 });
 
 test('canonical speed ramp puts the focus payoff eight seconds into playback', () => {
@@ -4,6 +4,7 @@ import type { GameState } from '../../types/index.ts';
 import {
   buildPlayerIdlePresentation,
   getIdleKickedUserIds,
+  isPlayerOnAutopilot,
   wasCompletedByInactivity,
   wasPlayerIdleKicked,
   type IdleStateSnapshot,
@@ -34,6 +35,7 @@ test('idle warning begins at the configured authoritative threshold', () => {
   const atThreshold = buildPlayerIdlePresentation(startedState({ tick: 500 }), 7);
   assert.deepEqual(atThreshold, {
     isKicked: false,
+    isOnAutopilot: false,
     warning: {
       deadlineTick: 600,
       remainingMs: 10_000,
@@ -59,6 +61,7 @@ test('server-provided twenty-second policy drives both warning boundaries', () =
     buildPlayerIdlePresentation({ ...serverConfiguredState, tick: 100 }, 7),
     {
       isKicked: false,
+      isOnAutopilot: false,
       warning: {
         deadlineTick: 200,
         remainingMs: 10_000,
@@ -187,6 +190,10 @@ test('eliminated field players are quiet while team players awaiting respawn are
           is_private: true,
           allow_spectators: true,
           snake_start_length: 2,
+          power_ups: false,
+          shrinking_zone: false,
+          edge_policy: 'Solid',
+          death_food: false,
         },
       },
     },
@@ -202,10 +209,29 @@ test('kicked users are normalized and suppress their warning', () => {
   assert.equal(wasPlayerIdleKicked(state, 7), true);
   assert.deepEqual(buildPlayerIdlePresentation(state, 7), {
     isKicked: true,
+    isOnAutopilot: false,
     warning: null,
   });
 });
 
+test('a player on autopilot sees no countdown until they take back control', () => {
+  const state = startedState({
+    tick: 600,
+    autopilot_players: { 7: { difficulty: 'Medium', next_decision_tick: 600 } },
+  });
+
+  assert.equal(isPlayerOnAutopilot(state, 7), true);
+  assert.equal(isPlayerOnAutopilot(state, 8), false);
+  assert.deepEqual(buildPlayerIdlePresentation(state, 7), {
+    isKicked: false,
+    isOnAutopilot: true,
+    warning: null,
+  });
+
+  const returned = startedState({ tick: 600, autopilot_players: {} });
+  assert.equal(buildPlayerIdlePresentation(returned, 7).isOnAutopilot, false);
+});
+
 test('inactivity completion is explicit rather than inferred from a draw', () => {
   assert.equal(wasCompletedByInactivity(startedState()), false);
   assert.equal(wasCompletedByInactivity(startedState({ completed_by_inactivity: true })), true);
@@ -58,6 +58,10 @@ test('custom games get no tutorial rather than a wrong one', () => {
         is_private: true,
         allow_spectators: true,
         snake_start_length: 4,
+        power_ups: false,
+        shrinking_zone: false,
+        edge_policy: 'Solid',
+        death_food: false,
       },
     },
   } as unknown as GameType;
@@ -171,6 +171,20 @@ test('game restoration only accepts a matching fresh snapshot', () => {
   assert.equal(isFreshSnapshotForGame(snapshot, 42, 8), true);
 });
 
+test('a snapshot delta anchors the stream like a full snapshot', () => {
+  const snapshotDelta = {
+    GameEvent: {
+      game_id: 42,
+      stream_seq: 30,
+      event: { SnapshotDelta: { base_tick: 12, changes: [], hash: 5 } },
+    },
+  };
+  assert.equal(isSnapshotForGame(snapshotDelta, 42), true);
+  assert.equal(isTerminalSnapshotForGame(snapshotDelta, 42), false);
+  assert.equal(isFreshSnapshotForGame(snapshotDelta, 42, 30), true);
+  assert.equal(advanceCandidateGameWatermark(90, snapshotDelta, 42), 30);
+});
+
 test('planned handoff waits when the old socket advances after the candidate snapshot', () => {
   const snapshotAt10 = {
     GameEvent: {
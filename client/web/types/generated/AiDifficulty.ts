@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How strong an AI opponent plays.
+ *
+ * Every tier runs the same policy; they differ only in the knobs of
+ * [`AiProfile`]. `Medium` is what [`calculate_ai_move`] and
+ * [`calculate_ai_command`] play.
+ */
+export type AiDifficulty = "Easy" | "Medium" | "Hard";
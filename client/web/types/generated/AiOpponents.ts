@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiDifficulty } from "./AiDifficulty";
+
+/**
+ * A request to fill seats with AI opponents of one tier.
+ */
+export type AiOpponents = { count: number, difficulty: AiDifficulty, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiDifficulty } from "./AiDifficulty";
+
+/**
+ * An AI-driven player in a running game.
+ *
+ * The seat lives in `GameState` and is driven by the simulation itself, so
+ * the server, a replay and a client's prediction all make the same decision
+ * on the same tick.
+ */
+export type AiSeat = { difficulty: AiDifficulty,
+/**
+ * First tick at which the seat may decide again.
+ */
+next_decision_tick: number, };
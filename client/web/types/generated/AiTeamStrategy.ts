@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How a team-match snake decides to take what it carries home.
+ */
+export type AiTeamStrategy = "Threshold" | "Adaptive";
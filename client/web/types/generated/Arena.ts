@@ -1,7 +1,33 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ArenaMap } from "./ArenaMap";
 import type { BoostPad } from "./BoostPad";
+import type { EdgePolicy } from "./EdgePolicy";
 import type { Position } from "./Position";
+import type { PowerUpPickup } from "./PowerUpPickup";
 import type { Snake } from "./Snake";
 import type { TeamZoneConfig } from "./TeamZoneConfig";
+import type { ZoneBounds } from "./ZoneBounds";
 
-export type Arena = { width: number, height: number, snakes: Array<Snake>, food: Array<Position>, boost_pads: Array<BoostPad>, team_zone_config: TeamZoneConfig | null, };
+export type Arena = { width: number, height: number, snakes: Array<Snake>, food: Array<Position>, boost_pads: Array<BoostPad>, team_zone_config: TeamZoneConfig | null,
+/**
+ * The match's map when it is not the implicit one described by the
+ * dimensions and `team_zone_config` alone (see `ArenaMap::implicit`).
+ * Classic matches leave this empty, so their snapshots and fingerprints
+ * are exactly those from before maps existed.
+ */
+map?: ArenaMap | null,
+/**
+ * Uncollected power-ups, in spawn order. Always empty in modes without
+ * power-ups, and omitted when empty.
+ */
+power_ups?: Array<PowerUpPickup>,
+/**
+ * The battle royale safe area. Present exactly when the match has a zone
+ * schedule, and always the schedule's bounds for the current tick.
+ */
+zone?: ZoneBounds | null,
+/**
+ * What the field's edge does to a snake that reaches it. Omitted for the
+ * usual solid edge, so those snapshots are unchanged.
+ */
+edges?: EdgePolicy, };
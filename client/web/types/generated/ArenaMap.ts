@@ -0,0 +1,25 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MapBoostPlacement } from "./MapBoostPlacement";
+import type { ObstacleBlock } from "./ObstacleBlock";
+import type { SpawnPoint } from "./SpawnPoint";
+import type { TeamZoneConfig } from "./TeamZoneConfig";
+
+/**
+ * A versioned, self-contained arena description. See the module docs.
+ */
+export type ArenaMap = { format_version: number, id: string,
+/**
+ * Content revision. Any geometry change to a published map bumps it so a
+ * persisted match can always be matched to the layout it was played on.
+ */
+revision: number, width: number, height: number,
+/**
+ * End zones and their goal walls. Present exactly on team maps.
+ */
+team_zones?: TeamZoneConfig | null, obstacles?: Array<ObstacleBlock>,
+/**
+ * Starting cells in assignment order. Empty means the engine's computed
+ * formation; a match with more snakes than spawns also falls back to it
+ * for the extra snakes.
+ */
+spawn_points?: Array<SpawnPoint>, boost: MapBoostPlacement, };
@@ -1,4 +1,28 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiOpponents } from "./AiOpponents";
+import type { EdgePolicy } from "./EdgePolicy";
 import type { GameMode } from "./GameMode";
 
-export type CustomGameSettings = { arena_width: number, arena_height: number, tick_duration_ms: number, food_spawn_rate: number, max_players: number, game_mode: GameMode, is_private: boolean, allow_spectators: boolean, snake_start_length: number, };
+export type CustomGameSettings = { arena_width: number, arena_height: number, tick_duration_ms: number, food_spawn_rate: number, max_players: number, game_mode: GameMode, is_private: boolean, allow_spectators: boolean, snake_start_length: number,
+/**
+ * Spawn shields, ghosts and magnets on the field. Lobbies created before
+ * power-ups existed play without them.
+ */
+power_ups: boolean,
+/**
+ * Battle royale: the field contracts until the last snake standing wins.
+ * Only honoured in free-for-all lobbies.
+ */
+shrinking_zone: boolean,
+/**
+ * Lobbies created before wraparound existed have solid edges.
+ */
+edge_policy: EdgePolicy,
+/**
+ * A fallen snake's body turns into food where it lay.
+ */
+death_food: boolean,
+/**
+ * Seats the host wants filled with AI opponents.
+ */
+ai_opponents?: AiOpponents, };
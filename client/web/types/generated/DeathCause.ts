@@ -4,4 +4,4 @@
  * Deterministic attribution for a snake death. This records information the
  * collision pass already knows; it does not change collision semantics.
  */
-export type DeathCause = "Unknown" | "Wall" | "OutOfBounds" | "EnemyBase" | "SelfCollision" | { "SnakeBody": { killer_snake_id: number, } } | { "HeadToHead": { other_snake_id: number, } } | "Banked";
+export type DeathCause = "Unknown" | "Wall" | "OutOfBounds" | "EnemyBase" | "SelfCollision" | { "SnakeBody": { killer_snake_id: number, } } | { "HeadToHead": { other_snake_id: number, } } | "Banked" | "Zone";
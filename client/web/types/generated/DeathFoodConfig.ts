@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Death food rules snapshotted into a match so simulation, prediction and
+ * replay never depend on process configuration.
+ */
+export type DeathFoodConfig = {
+/**
+ * Body cells per pellet: 1 turns every cell into food, 2 every other one.
+ */
+cells_per_pellet: number,
+/**
+ * Pellets a single death may leave, however long the snake was.
+ */
+max_per_death: number,
+/**
+ * Food on the field above which a death leaves nothing more.
+ */
+max_on_field: number, rules_version: number, };
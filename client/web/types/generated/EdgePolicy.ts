@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * What the edge of the field does to a snake that runs into it.
+ */
+export type EdgePolicy = "Solid" | "Wrap";
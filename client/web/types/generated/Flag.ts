@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FlagPlacement } from "./FlagPlacement";
+import type { TeamId } from "./TeamId";
+
+export type Flag = {
+/**
+ * The team the flag belongs to, and defends.
+ */
+team_id: TeamId, placement: FlagPlacement, };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Flag rules snapshotted into a match so simulation, prediction and replay
+ * never depend on process configuration.
+ */
+export type FlagConfig = {
+/**
+ * Simulation time a dropped flag lies untouched before it goes home.
+ */
+return_ms: number, rules_version: number, };
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Position } from "./Position";
+
+/**
+ * Where a flag is.
+ */
+export type FlagPlacement = "Home" | { "Carried": { snake_id: number, } } | { "Dropped": { position: Position, at_tick: number, } };
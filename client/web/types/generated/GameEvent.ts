@@ -7,7 +7,10 @@ import type { GameCommandMessage } from "./GameCommandMessage";
 import type { GameState } from "./GameState";
 import type { GameStatus } from "./GameStatus";
 import type { Position } from "./Position";
+import type { PowerUpKind } from "./PowerUpKind";
+import type { StateChange } from "./StateChange";
 import type { TeamId } from "./TeamId";
+import type { ZoneBounds } from "./ZoneBounds";
 
 export type GameEvent = { "SnakeTurned": { snake_id: number, direction: Direction, } } | { "SnakeDied": { snake_id: number,
 /**
@@ -16,7 +19,7 @@ export type GameEvent = { "SnakeTurned": { snake_id: number, direction: Directio
  * `Unknown`; current authoritative simulation always emits a
  * concrete cause.
  */
-cause: DeathCause, } } | { "PlayerIdleKicked": { user_id: number, snake_id: number, } } | { "FoodSpawned": { position: Position, } } | { "FoodEaten": { snake_id: number, position: Position,
+cause: DeathCause, } } | { "PlayerIdleKicked": { user_id: number, snake_id: number, } } | { "PlayerAutopilotChanged": { user_id: number, snake_id: number, enabled: boolean, } } | { "FoodSpawned": { position: Position, } } | { "FoodEaten": { snake_id: number, position: Position,
 /**
  * Exact score and physical growth awarded by this pickup.
  */
@@ -32,7 +35,7 @@ combo_remaining_ms_before: number,
 /**
  * Whether authoritative Boost was active at the instant of pickup.
  */
-boost_active: boolean, } } | { "BoostPacketCollected": { pad_id: number, snake_id: number, charge_ms_after: number, respawn_at_tick: number, } } | { "Snapshot": { game_state: GameState, } } | { "CommandScheduled": { command_message: GameCommandMessage, } } | { "CommandScheduledV2": { command_id: ClientCommandIdentityV2, command_message: GameCommandMessage,
+boost_active: boolean, } } | { "BoostPacketCollected": { pad_id: number, snake_id: number, charge_ms_after: number, respawn_at_tick: number, } } | { "PowerUpSpawned": { kind: PowerUpKind, position: Position, } } | { "PowerUpCollected": { snake_id: number, kind: PowerUpKind, position: Position, } } | { "ShieldBroken": { snake_id: number, } } | { "ZoneContracted": { bounds: ZoneBounds, } } | { "HillCaptured": { team_id: TeamId, previous_holder: TeamId | null, } } | { "FlagPickedUp": { team_id: TeamId, snake_id: number, } } | { "FlagDropped": { team_id: TeamId, snake_id: number, position: Position, at_tick: number, } } | { "FlagReturned": { team_id: TeamId, } } | { "FlagCaptured": { team_id: TeamId, snake_id: number, } } | { "Snapshot": { game_state: GameState, } } | { "SnapshotDelta": { base_tick: number, changes: Array<StateChange>, hash: number, } } | { "CommandScheduled": { command_message: GameCommandMessage, } } | { "CommandScheduledV2": { command_id: ClientCommandIdentityV2, command_message: GameCommandMessage,
 /**
  * True when the executor is returning a previously recorded outcome;
  * replicas must not schedule the same logical command again.
@@ -49,4 +52,4 @@ command_id_client?: CommandId | null,
  * or above this sequence have the same terminal rejection. Exact
  * outcomes and the contiguous resolved watermark take precedence.
  */
-session_rejected_from?: number, } } | { "StatusUpdated": { status: GameStatus, } } | { "PlayerReady": { user_id: number, } } | { "MatchStartScheduled": { simulation_epoch_ms: number, } } | { "ScoreUpdated": { snake_id: number, score: number, } } | { "TeamScoreUpdated": { team_id: TeamId, score: number, } } | { "SnakeRespawned": { snake_id: number, position: Position, direction: Direction, } } | { "XPAwarded": { player_xp: { [key in number]?: number }, } } | { "TickHash": { hash: number, server_ts_ms: number, } };
+session_rejected_from?: number, } } | { "StatusUpdated": { status: GameStatus, } } | { "PlayerReady": { user_id: number, } } | { "MatchStartScheduled": { simulation_epoch_ms: number, } } | { "ScoreUpdated": { snake_id: number, score: number, } } | { "TeamScoreUpdated": { team_id: TeamId, score: number, } } | { "RoundEnded": { round_number: number, winning_team_id: TeamId | null, } } | { "RoundStarted": { round_number: number, } } | { "SnakeRespawned": { snake_id: number, position: Position, direction: Direction, } } | { "XPAwarded": { player_xp: { [key in number]?: number }, } } | { "TickHash": { hash: number, server_ts_ms: number, } };
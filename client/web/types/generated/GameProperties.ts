@@ -1,6 +1,12 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiDifficulty } from "./AiDifficulty";
 import type { BoostConfig } from "./BoostConfig";
 import type { ComboConfig } from "./ComboConfig";
+import type { DeathFoodConfig } from "./DeathFoodConfig";
+import type { FlagConfig } from "./FlagConfig";
+import type { HillConfig } from "./HillConfig";
+import type { PowerUpConfig } from "./PowerUpConfig";
+import type { ZoneConfig } from "./ZoneConfig";
 
 export type GameProperties = { available_food_target: number, tick_duration_ms: number, time_limit_ms: number | null,
 /**
@@ -18,4 +24,35 @@ combo: ComboConfig,
  * Match inactivity policy, snapshotted with the game so every executor
  * and client resolves the same deadline even across failover.
  */
-player_idle_timeout_ms: number, player_idle_warning_ms: number, };
+player_idle_timeout_ms: number, player_idle_warning_ms: number,
+/**
+ * Difficulty the AI drives an idle or disconnected team player at, in
+ * place of removing them, as long as a teammate is still playing. `None`
+ * removes them as before.
+ */
+idle_autopilot?: AiDifficulty,
+/**
+ * Power-up spawn rules, present only for modes that opt in (see
+ * `power_up_config_for`). Absent from every other snapshot.
+ */
+power_ups?: PowerUpConfig | null,
+/**
+ * Battle royale zone schedule, present only for modes that opt in (see
+ * `zone_config_for`). Absent from every other snapshot.
+ */
+zone?: ZoneConfig | null,
+/**
+ * King-of-the-hill rules, present only in that mode (see
+ * `hill_config_for`). Absent from every other snapshot.
+ */
+hill?: HillConfig | null,
+/**
+ * Capture-the-flag rules, present only in that mode (see
+ * `flag_config_for`). Absent from every other snapshot.
+ */
+flags?: FlagConfig | null,
+/**
+ * Death food rules, present only for modes that opt in (see
+ * `death_food_config_for`). Absent from every other snapshot.
+ */
+death_food?: DeathFoodConfig | null, };
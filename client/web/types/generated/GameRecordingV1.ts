@@ -1,9 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { RecordedGameMessage } from "./RecordedGameMessage";
 import type { ReplayAnchor } from "./ReplayAnchor";
+import type { ReplayKeyframe } from "./ReplayKeyframe";
 import type { ReplayVisibility } from "./ReplayVisibility";
 
 /**
  * Complete replay source persisted for every real production match.
  */
-export type GameRecordingV1 = { format_version: number, gameplay_version: number, game_id: number, visibility: ReplayVisibility, anchors: Array<ReplayAnchor>, messages: Array<RecordedGameMessage>, end_tick: number, end_sync_hash: string, };
+export type GameRecordingV1 = { format_version: number, gameplay_version: number, game_id: number, visibility: ReplayVisibility, anchors: Array<ReplayAnchor>, messages: Array<RecordedGameMessage>, end_tick: number, end_sync_hash: string,
+/**
+ * Optional seek index laid down by [`Self::index_keyframes`]. Archives
+ * written before it existed, and bounded selection views, carry none.
+ */
+keyframes?: Array<ReplayKeyframe>, };
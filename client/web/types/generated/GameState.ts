@@ -1,12 +1,15 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Arena } from "./Arena";
 import type { DeathCause } from "./DeathCause";
+import type { Flag } from "./Flag";
 import type { GameProperties } from "./GameProperties";
 import type { GameStatus } from "./GameStatus";
 import type { GameType } from "./GameType";
+import type { HillControl } from "./HillControl";
 import type { MatchReadiness } from "./MatchReadiness";
 import type { Player } from "./Player";
 import type { QueueMode } from "./QueueMode";
+import type { RoundState } from "./RoundState";
 import type { SnakeCrash } from "./SnakeCrash";
 import type { TeamGoal } from "./TeamGoal";
 
@@ -80,4 +83,37 @@ readiness: MatchReadiness | null,
  * holding the match (in which case nothing may advance at all) or the
  * match never had a gate.
  */
-simulation_epoch_ms: number | null, };
+simulation_epoch_ms: number | null,
+/**
+ * Best-of-N progress for team matches played over several rounds.
+ * `None` is a single-round match — every Quickmatch, every other mode,
+ * and every state written before rounds existed — which ends the moment
+ * a team reaches the score limit, exactly as it always has.
+ */
+rounds?: RoundState | null,
+/**
+ * Who holds the hill, present exactly when `properties.hill` is.
+ */
+hill?: HillControl | null,
+/**
+ * Both teams' flags, in team order, present exactly when
+ * `properties.flags` is.
+ */
+flags?: Array<Flag>,
+/**
+ * Players the simulation drives itself, by user_id. Their snakes are
+ * steered inside `tick_forward`, so every engine that simulates the match
+ * makes the same decisions; they are never idle-kicked and earn no XP.
+ */
+ai_players?: Record<number, AiSeat>,
+/**
+ * Absent players whose snakes the AI is driving until they send input
+ * again, by user_id. Unlike `ai_players` these are people, so a match
+ * with them stays rated.
+ */
+autopilot_players?: Record<number, AiSeat>,
+/**
+ * Every player the AI has driven at some point this match, sorted. They
+ * earn no XP or rating from it, even after taking back control.
+ */
+autopiloted_user_ids?: Array<number>, };
@@ -1,3 +1,33 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type HighlightConfig = { rules_version: number, minimum_score: number, elimination: number, mutual_trade: number, boosted_kill: number, cutoff: number, proximity: number, trap: number, laden_per_point: number, laden_cap: number, teammate_kill: number, banked_per_point: number, big_carry: number, combo_step: number, nick_of_time: number, boosted_pickup: number, feeding_frenzy: number, death: number, };
+export type HighlightConfig = { rules_version: number, minimum_score: number, elimination: number, mutual_trade: number, boosted_kill: number, cutoff: number, proximity: number, trap: number, laden_per_point: number, laden_cap: number, teammate_kill: number, banked_per_point: number, big_carry: number, combo_step: number, nick_of_time: number, boosted_pickup: number, feeding_frenzy: number, death: number,
+/**
+ * Taking the hill from the other team with the star's own head on it.
+ * Configs persisted before king of the hill read the default weight.
+ */
+contested_capture: number,
+/**
+ * Carrying the enemy flag home. A capture decides a match the way a kill
+ * decides a duel, so it weighs the same as an elimination.
+ */
+flag_capture: number,
+/**
+ * The star's score putting their team ahead after trailing by at least
+ * `COMEBACK_MIN_DEFICIT_PERCENT` of the score limit. Like the weights
+ * below, configs persisted before rules version 3 read the default.
+ */
+comeback: number,
+/**
+ * The star's score that took the lead for good in the match's last
+ * `BUZZER_BEATER_MS`.
+ */
+buzzer_beater: number,
+/**
+ * A rival dying while the star's current life is at least
+ * `SURVIVAL_STREAK_MS` old.
+ */
+survival_streak: number,
+/**
+ * Breaking out of a pocket a rival had closed around the star.
+ */
+trap_escape: number, };
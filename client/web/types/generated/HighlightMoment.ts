@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HighlightReason } from "./HighlightReason";
+import type { HighlightScoreBreakdown } from "./HighlightScoreBreakdown";
+import type { HighlightWindow } from "./HighlightWindow";
+
+/**
+ * A scored window of a match without the replay payload a clip carries.
+ * Clients play one by seeking the match's replay to `window`.
+ */
+export type HighlightMoment = { star_user_id: number, star_snake_id: number, star_name: string, reason: HighlightReason, score: number, breakdown: HighlightScoreBreakdown, window: HighlightWindow, };
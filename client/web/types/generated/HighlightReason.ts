@@ -1,3 +1,3 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type HighlightReason = { "BoostedCutoff": { kills: number, } } | { "TrapKill": { kills: number, } } | { "Demolition": { kills: number, } } | { "GoalRun": { points: number, } } | { "ComboFrenzy": { max_chain: number, } } | { "FeedingFrenzy": { pickups: number, } };
+export type HighlightReason = { "BoostedCutoff": { kills: number, } } | { "TrapKill": { kills: number, } } | { "Demolition": { kills: number, } } | { "GoalRun": { points: number, } } | { "ComboFrenzy": { max_chain: number, } } | { "FeedingFrenzy": { pickups: number, } } | { "ContestedCapture": { captures: number, } } | { "FlagCapture": { captures: number, } } | { "Comeback": { deficit: number, } } | { "BuzzerBeater": { points: number, } } | { "SurvivalStreak": { seconds: number, } } | { "TrapEscape": { escapes: number, } };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { HighlightMoment } from "./HighlightMoment";
+
+export type HighlightReel = {
+/**
+ * The Play of the Game first when the match has one, then the rest by
+ * score. No two windows overlap.
+ */
+moments: Array<HighlightMoment>,
+/**
+ * Each participant's own best moment, ordered by user id. A player with
+ * nothing above `minimum_score` has no entry.
+ */
+players: Array<HighlightMoment>, };
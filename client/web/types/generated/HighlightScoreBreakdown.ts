@@ -1,3 +1,19 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
-export type HighlightScoreBreakdown = { total: number, focus_tick: number, kills: number, boosted_cutoff_kills: number, trap_kills: number, banked_points: number, max_chain: number, pickups: number, demolition_points: number, banking_points: number, combo_points: number, };
+export type HighlightScoreBreakdown = { total: number, focus_tick: number, kills: number, boosted_cutoff_kills: number, trap_kills: number, banked_points: number, max_chain: number, pickups: number, demolition_points: number, banking_points: number, combo_points: number, contested_captures: number, flag_captures: number,
+/**
+ * The largest deficit a lead the star scored overturned.
+ */
+comeback_deficit: number,
+/**
+ * Points in the star's match-winning score, when it beat the buzzer.
+ */
+buzzer_beater_points: number,
+/**
+ * Age of the star's life when it outlasted a rival, at the longest.
+ */
+survival_seconds: number, trap_escapes: number,
+/**
+ * Comebacks, buzzer beaters, survival streaks and trap escapes.
+ */
+clutch_points: number, };
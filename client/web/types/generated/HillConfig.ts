@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Hill rules snapshotted into a match so simulation, prediction and replay
+ * never depend on process configuration.
+ */
+export type HillConfig = {
+/**
+ * Side of the square hill in cells, centred on the arena.
+ */
+size: number,
+/**
+ * Simulation time a team must hold the hill alone to earn one point.
+ */
+point_interval_ms: number, rules_version: number, };
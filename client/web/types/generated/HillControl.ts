@@ -0,0 +1,21 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TeamId } from "./TeamId";
+
+/**
+ * Who holds the hill and how far they are toward their next point.
+ */
+export type HillControl = {
+/**
+ * The last team to stand on the hill alone. Holding survives a hill left
+ * empty or contested; only the other team standing on it alone takes it.
+ */
+holder: TeamId | null,
+/**
+ * Uncontested hold time banked toward the holder's next point. Always
+ * below the config's `point_interval_ms`, and reset by every capture.
+ */
+held_ms: number,
+/**
+ * Whether both teams had a head on the hill on the last tick.
+ */
+contested: boolean, };
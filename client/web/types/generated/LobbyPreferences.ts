@@ -1,6 +1,12 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiOpponents } from "./AiOpponents";
 
 /**
  * Host-selected matchmaking preferences for a lobby
  */
-export type LobbyPreferences = { selected_modes: Array<string>, competitive: boolean, };
+export type LobbyPreferences = { selected_modes: Array<string>, competitive: boolean,
+/**
+ * AI opponents to practise against when the lobby plays solo or a
+ * custom game.
+ */
+ai_opponents?: AiOpponents, };
@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Position } from "./Position";
+
+/**
+ * One explicitly placed Boost pad. A 2x2 pad holds a full tank and a 1x1
+ * packet a quarter, matching the convention of the drawn layouts.
+ */
+export type MapBoostPad = {
+/**
+ * Top-left cell of the square footprint.
+ */
+position: Position, size_cells: number, };
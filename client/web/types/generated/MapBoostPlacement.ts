@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { MapBoostPad } from "./MapBoostPad";
+
+/**
+ * How a map provides Boost pickups.
+ */
+export type MapBoostPlacement = { "kind": "none" } | { "kind": "layout", spot_layout_version: number, } | { "kind": "pads", pads: Array<MapBoostPad>, };
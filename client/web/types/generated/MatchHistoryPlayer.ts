@@ -5,4 +5,9 @@
  * intentionally contain only result data; the substantially larger final
  * `GameState` remains on the completed-game item for bounded snapshot reloads.
  */
-export type MatchHistoryPlayer = { userId: number, username: string, teamId: number | null, score: number, teamScore: number | null, xpGained: number, mmrDelta: number | null, outcome: string, };
+export type MatchHistoryPlayer = { userId: number, username: string, teamId: number | null, score: number, teamScore: number | null,
+/**
+ * Rounds the player's team took in a best-of-N match. Single-round
+ * matches, and rows written before rounds existed, have none.
+ */
+roundsWon: number | null, xpGained: number, mmrDelta: number | null, outcome: string, };
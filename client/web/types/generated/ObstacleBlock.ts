@@ -0,0 +1,12 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Position } from "./Position";
+
+/**
+ * A solid rectangle of cells. Thin blocks are walls; there is no separate
+ * wall primitive because collision treats both identically.
+ */
+export type ObstacleBlock = {
+/**
+ * Top-left cell of the block.
+ */
+position: Position, width: number, height: number, };
@@ -0,0 +1,24 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PowerUpKind } from "./PowerUpKind";
+
+/**
+ * Power-up rules snapshotted into a match so simulation, prediction and
+ * replay never depend on process configuration.
+ */
+export type PowerUpConfig = {
+/**
+ * Kinds that may spawn, in the order the spawn roll indexes them.
+ */
+kinds: Array<PowerUpKind>,
+/**
+ * Wall-clock cadence of spawn attempts.
+ */
+spawn_interval_ms: number,
+/**
+ * Uncollected pickups allowed on the field at once.
+ */
+max_on_field: number, ghost_duration_ms: number, magnet_duration_ms: number,
+/**
+ * Chebyshev distance from the head at which a magnet collects food.
+ */
+magnet_radius: number, rules_version: number, };
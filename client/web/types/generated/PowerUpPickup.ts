@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Position } from "./Position";
+import type { PowerUpKind } from "./PowerUpKind";
+
+/**
+ * An uncollected power-up lying on one cell of the field.
+ */
+export type PowerUpPickup = { kind: PowerUpKind, position: Position, };
@@ -0,0 +1,14 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameState } from "./GameState";
+
+/**
+ * Seek checkpoint derived from the archive when it is finalized. Unlike an
+ * anchor it carries no authority of its own: `verify_end_hash` replays
+ * through every keyframe and rejects one the messages do not reproduce.
+ */
+export type ReplayKeyframe = { tick: number,
+/**
+ * Highest recorder sequence already reflected in `state`, as on an
+ * anchor.
+ */
+sequence: number, sync_hash: string, state: GameState, };
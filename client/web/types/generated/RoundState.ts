@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Round bookkeeping for a best-of-N team match.
+ *
+ * A round ends when a team reaches the score limit; the field is then reset
+ * and the next round starts from zero. Ending and starting a round are two
+ * separate replicated events, and the two counters here are what make each of
+ * them idempotent: a round can only end while it is the one being played, and
+ * the next one can only start after it has ended.
+ */
+export type RoundState = { rounds_to_win: number,
+/**
+ * One-based number of the round being played, or of the round that just
+ * ended while the next has not started yet.
+ */
+current_round: number,
+/**
+ * Rounds that have ended, drawn ones included. Equal to `current_round`
+ * between a round's end and the next one's start, one less otherwise.
+ */
+rounds_completed: number,
+/**
+ * Rounds won by each team. A drawn round is completed but won by nobody.
+ */
+round_wins: Record<number, number>, };
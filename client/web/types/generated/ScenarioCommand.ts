@@ -1,4 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ScenarioCommandKind } from "./ScenarioCommandKind";
 
-export type ScenarioCommand = { at_tick: number, user_id: number, command: ScenarioCommandKind, };
+export type ScenarioCommand = { at_tick: number, user_id: number, command: ScenarioCommandKind,
+/**
+ * Tick the command reaches the queue, no later than `at_tick`. Commands
+ * without one are queued when the scenario loads. Queueing restarts the
+ * player's idle clock, so a captured script keeps the recorded arrival.
+ */
+scheduled_at?: number, };
@@ -1,4 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { Direction } from "./Direction";
 
-export type ScenarioCommandKind = { "Turn": Direction } | "ActivateBoost" | "DeactivateBoost";
+export type ScenarioCommandKind = { "Turn": Direction } | "ActivateBoost" | "DeactivateBoost" | "PlayerActivity";
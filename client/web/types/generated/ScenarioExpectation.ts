@@ -1,3 +1,9 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DeathCause } from "./DeathCause";
 
-export type ScenarioExpectation = { "SnakeDead": { snake_id: number, at_tick: number, } } | { "FinalSyncHash": string };
+/**
+ * An outcome a scenario promises. A tick is the one a simulation step lands
+ * on, which is also the tick [`ScenarioRun::events`] files that step's
+ * events under; state is checked as it stands once that tick is reached.
+ */
+export type ScenarioExpectation = { "SnakeDead": { snake_id: number, at_tick: number, } } | { "FinalSyncHash": string } | { "Score": { snake_id: number, at_tick: number, score: number, } } | { "TeamScore": { team_id: number, at_tick: number, score: number, } } | { "SnakeLength": { snake_id: number, at_tick: number, length: number, } } | { "FoodEaten": { snake_id: number, at_tick: number, count: number, } } | { "BoostCharge": { snake_id: number, at_tick: number, charge_ms: number, } } | { "Event": { kind: string, from_tick: number, to_tick: number, snake_id?: number | null, count?: number | null, } } | { "DeathCause": { snake_id: number, cause: DeathCause, at_tick?: number | null, } };
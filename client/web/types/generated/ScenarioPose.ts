@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { GameState } from "./GameState";
 import type { Position } from "./Position";
 import type { ScenarioSnakePose } from "./ScenarioSnakePose";
 
@@ -6,4 +7,11 @@ export type ScenarioPose = { snakes: Array<ScenarioSnakePose>, food: Array<Posit
 /**
  * Stable JSON representation: `[[team_id, score], ...]`.
  */
-team_scores: Array<[number, number]>, start_tick: number, };
+team_scores: Array<[number, number]>, start_tick: number,
+/**
+ * The exact state a pose taken from a recorded game was captured from.
+ * The scenario then starts from it instead of a freshly built arena, so
+ * RNG, scores and timers carry over; the fields above must still
+ * describe it, and the world's overrides must be empty.
+ */
+captured?: GameState, };
@@ -1,6 +1,17 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AiTeamStrategy } from "./AiTeamStrategy";
 import type { Direction } from "./Direction";
 import type { Position } from "./Position";
 import type { ScenarioDriver } from "./ScenarioDriver";
 
-export type ScenarioSnakePose = { user_id: number, name: string, body: Array<Position>, direction: Direction, food: number, team_id: number | null, is_alive: boolean | null, boost_charge_ms: number, boost_active: boolean, combo_chain: number, combo_remaining_ms: number, driver: ScenarioDriver, };
+export type ScenarioSnakePose = { user_id: number, name: string, body: Array<Position>, direction: Direction, food: number, team_id: number | null, is_alive: boolean | null, boost_charge_ms: number, boost_active: boolean, combo_chain: number, combo_remaining_ms: number, driver: ScenarioDriver,
+/**
+ * Team strategy an `Ai` driver plays with in place of the default one,
+ * so an evaluation can pit two strategies against each other.
+ */
+team_strategy?: AiTeamStrategy,
+/**
+ * Registered [`BotPolicy`] an `Ai` driver plays by in place of the
+ * scenario's own turn-only AI.
+ */
+policy?: string, };
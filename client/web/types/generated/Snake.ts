@@ -3,6 +3,7 @@ import type { Direction } from "./Direction";
 import type { Position } from "./Position";
 import type { SnakeBoost } from "./SnakeBoost";
 import type { SnakeCombo } from "./SnakeCombo";
+import type { SnakePowerUps } from "./SnakePowerUps";
 import type { TeamId } from "./TeamId";
 
 export type Snake = {
@@ -24,4 +25,9 @@ movement_credit: number, boost: SnakeBoost,
  * Current per-life combo meter. Historical snapshots deserialize as an
  * inactive combo, which is the only safe state to infer without events.
  */
-combo: SnakeCombo, };
+combo: SnakeCombo,
+/**
+ * Power-up effects held by this life. Always inactive in modes without
+ * power-ups, and omitted when inactive so those snapshots are unchanged.
+ */
+power_ups?: SnakePowerUps, };
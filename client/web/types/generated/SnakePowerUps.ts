@@ -0,0 +1,19 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Effects one snake currently holds. The default is the single inactive
+ * representation; a death or respawn always returns to it.
+ */
+export type SnakePowerUps = {
+/**
+ * Absorbs the next crash, then breaks. Shields do not stack.
+ */
+shield: boolean,
+/**
+ * Simulation time left passing through other snakes.
+ */
+ghost_remaining_ms: number,
+/**
+ * Simulation time left collecting food within the configured radius.
+ */
+magnet_remaining_ms: number, };
@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Direction } from "./Direction";
+import type { Position } from "./Position";
+import type { TeamId } from "./TeamId";
+
+/**
+ * Where a snake's head starts a life, and which way it faces. The body is
+ * laid out straight behind the head.
+ */
+export type SpawnPoint = { position: Position, direction: Direction,
+/**
+ * Restricts the spawn to one team. `None` spawns anybody, which is what
+ * teamless maps use.
+ */
+team_id?: TeamId | null, };
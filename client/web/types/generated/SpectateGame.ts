@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Client -> server request to watch a live game without a seat. Any
+ * authenticated identity, guests included, may watch a public game; a
+ * private game also needs its `game_code`. Answered with `SpectatorJoined`,
+ * after which the game's events stream as for a player.
+ */
+export type SpectateGame = { game_id: number, game_code?: string, };
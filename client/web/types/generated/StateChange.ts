@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * One edit to the serialized state. `path` is a JSON Pointer (RFC 6901);
+ * the empty path is the whole state.
+ */
+export type StateChange = { "Set": { path: string, value: unknown, } } | { "Remove": { path: string, } };
@@ -1,5 +1,6 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { AdBreakResolution } from "./AdBreakResolution";
+import type { AiOpponents } from "./AiOpponents";
 import type { ChallengeInbox } from "./ChallengeInbox";
 import type { ClientAdsConfig } from "./ClientAdsConfig";
 import type { ClientCommandIdentityV2 } from "./ClientCommandIdentityV2";
@@ -17,10 +18,32 @@ import type { QueueMode } from "./QueueMode";
 import type { RegionRoster } from "./RegionRoster";
 import type { RematchState } from "./RematchState";
 import type { SessionCommandRejectionFence } from "./SessionCommandRejectionFence";
+import type { SpectateGame } from "./SpectateGame";
+import type { WireEncoding } from "./WireEncoding";
 
 export type WSMessage = { "Token": string } | { "Authenticate": { token: string, protocol_version: number,
 /**
  * Session build channel. A missing value resolves to a disabled ad
  * policy because the client's available SDK is unknown.
  */
-distribution?: ClientDistribution | null, } } | { "JoinGame": number } | "LeaveGame" | { "GameCommandV2": { command_id: ClientCommandIdentityV2, command: GameCommandMessage, } } | { "GameEvent": GameEventMessage } | { "CommandOutcomes": { game_id: number, client_game_session_id: string, contiguous_through: number, outcomes: { [key in number]?: CommandOutcome }, rejection_fence?: SessionCommandRejectionFence | null, } } | { "CommandOutcomesComplete": { game_id: number, terminal_rejection_reason?: string | null, } } | { "Chat": string } | { "LobbyChatMessage": { lobby_code: string, message_id: string, user_id: number, username: string, message: string, timestamp_ms: number, } } | { "GameChatMessage": { game_id: number, message_id: string, user_id: number, username: string, message: string, timestamp_ms: number, } } | { "LobbyChatHistory": { lobby_code: string, messages: Array<LobbyChatBroadcast>, } } | { "GameChatHistory": { game_id: number, messages: Array<GameChatBroadcast>, } } | { "Authenticated": { task_boot_id: string, protocol_version: number, capabilities: Array<string>, socket_generation: number, } } | { "AdConfiguration": ClientAdsConfig } | { "PlayerReady": { game_id: number, } } | { "RequestResync": { game_id: number, } } | { "Ping": { client_time: number, } } | { "Pong": { client_time: number, server_time: number, } } | { "QueueForMatch": { game_type: GameType, queue_mode: QueueMode, } } | { "QueueForMatchMulti": { game_types: Array<GameType>, queue_mode: QueueMode, } } | "LeaveQueue" | { "MatchFound": { game_id: number, } } | { "QueueUpdate": { position: number, estimated_wait_seconds: number, } } | "QueueLeft" | { "AdBreakResolved": { break_id: string, resolution: AdBreakResolution, } } | { "UpdateNickname": { nickname: string, } } | "SpectatorJoined" | { "AccessDenied": { reason: string, } } | { "GameLoadFailed": { game_id: number, reason: string, } } | { "GameWarming": { game_id: number, retry_after_ms: number, } } | { "SoloGameCreated": { game_id: number, } } | { "Drain": { task_boot_id: string, deadline_unix_ms: number, } } | { "UserCountUpdate": { region_counts: { [key in string]?: number }, } } | "CreateLobby" | { "LobbyCreated": { lobby_code: string, } } | { "JoinLobby": { lobby_code: string, preferences: LobbyPreferences | null, } } | { "JoinedLobby": { lobby_code: string, } } | "LeaveLobby" | "LeftLobby" | { "LobbyUpdate": { lobby_code: string, members: Array<LobbyMember>, host_user_id: number, state: string, preferences: LobbyPreferences, ad_break?: LobbyAdBreakView | null, } } | { "UpdateLobbyPreferences": { selected_modes: Array<string>, competitive: boolean, } } | { "LobbyRegionMismatch": { target_region: string, ws_url: string, lobby_code: string, } } | { "OnlinePlayers": RegionRoster } | { "ChallengePlayer": { user_id: number, } } | { "RespondToChallenge": { challenge_id: string, accept: boolean, } } | { "CancelChallenge": { challenge_id: string, } } | { "Challenges": ChallengeInbox } | { "ChallengeAccepted": { challenge_id: string, lobby_code: string, } } | { "ChallengeFailed": { reason: string, } } | { "SetRematchIntent": { game_id: number, opt_in: boolean, } } | { "Rematch": RematchState };
+distribution?: ClientDistribution | null,
+/**
+ * How the client wants `GameEvent` frames encoded. Absent means JSON.
+ */
+encoding?: WireEncoding, } } | { "JoinGame": number } | { "SpectateGame": SpectateGame } | "LeaveGame" | { "GameCommandV2": { command_id: ClientCommandIdentityV2, command: GameCommandMessage, } } | { "GameEvent": GameEventMessage } | { "CommandOutcomes": { game_id: number, client_game_session_id: string, contiguous_through: number, outcomes: { [key in number]?: CommandOutcome }, rejection_fence?: SessionCommandRejectionFence | null, } } | { "CommandOutcomesComplete": { game_id: number, terminal_rejection_reason?: string | null, } } | { "Chat": string } | { "LobbyChatMessage": { lobby_code: string, message_id: string, user_id: number, username: string, message: string, timestamp_ms: number, } } | { "GameChatMessage": { game_id: number, message_id: string, user_id: number, username: string, message: string, timestamp_ms: number, } } | { "LobbyChatHistory": { lobby_code: string, messages: Array<LobbyChatBroadcast>, } } | { "GameChatHistory": { game_id: number, messages: Array<GameChatBroadcast>, } } | { "Authenticated": { task_boot_id: string, protocol_version: number, capabilities: Array<string>, socket_generation: number,
+/**
+ * The encoding `GameEvent` frames will use from here on, echoed only
+ * to a client that asked for one.
+ */
+encoding?: WireEncoding, } } | { "AdConfiguration": ClientAdsConfig } | { "PlayerReady": { game_id: number, } } | { "RequestResync": { game_id: number,
+/**
+ * Tick of the last snapshot the client applied. When the gateway
+ * still holds that snapshot, the fresh one may arrive as a
+ * `SnapshotDelta` against it.
+ */
+base_tick?: number, } } | { "Ping": { client_time: number, } } | { "Pong": { client_time: number, server_time: number, } } | { "QueueForMatch": { game_type: GameType, queue_mode: QueueMode, } } | { "QueueForMatchMulti": { game_types: Array<GameType>, queue_mode: QueueMode, } } | "LeaveQueue" | { "MatchFound": { game_id: number, } } | { "QueueUpdate": { position: number, estimated_wait_seconds: number, } } | "QueueLeft" | { "AdBreakResolved": { break_id: string, resolution: AdBreakResolution, } } | { "UpdateNickname": { nickname: string, } } | "SpectatorJoined" | { "AccessDenied": { reason: string, } } | { "GameLoadFailed": { game_id: number, reason: string, } } | { "GameWarming": { game_id: number, retry_after_ms: number, } } | { "SoloGameCreated": { game_id: number, } } | { "Drain": { task_boot_id: string, deadline_unix_ms: number, } } | { "UserCountUpdate": { region_counts: { [key in string]?: number }, } } | "CreateLobby" | { "LobbyCreated": { lobby_code: string, } } | { "JoinLobby": { lobby_code: string, preferences: LobbyPreferences | null, } } | { "JoinedLobby": { lobby_code: string, } } | "LeaveLobby" | "LeftLobby" | { "LobbyUpdate": { lobby_code: string, members: Array<LobbyMember>, host_user_id: number, state: string, preferences: LobbyPreferences, ad_break?: LobbyAdBreakView | null, } } | { "UpdateLobbyPreferences": { selected_modes: Array<string>, competitive: boolean,
+/**
+ * AI opponents for solo practice or a custom game. Absent leaves the
+ * lobby without.
+ */
+ai_opponents?: AiOpponents, } } | { "LobbyRegionMismatch": { target_region: string, ws_url: string, lobby_code: string, } } | { "OnlinePlayers": RegionRoster } | { "ChallengePlayer": { user_id: number, } } | { "RespondToChallenge": { challenge_id: string, accept: boolean, } } | { "CancelChallenge": { challenge_id: string, } } | { "Challenges": ChallengeInbox } | { "ChallengeAccepted": { challenge_id: string, lobby_code: string, } } | { "ChallengeFailed": { reason: string, } } | { "SetRematchIntent": { game_id: number, opt_in: boolean, } } | { "Rematch": RematchState };
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * How the server encodes `GameEvent` frames for one connection.
+ */
+export type WireEncoding = "Json" | "MessagePack";
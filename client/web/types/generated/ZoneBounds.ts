@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * The playable rectangle, as half-open cell ranges `[min_x, max_x)` and
+ * `[min_y, max_y)`. Once either range is empty the zone has closed and no
+ * cell is safe.
+ */
+export type ZoneBounds = { min_x: number, min_y: number, max_x: number, max_y: number, };
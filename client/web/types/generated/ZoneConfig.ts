@@ -0,0 +1,15 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Zone schedule snapshotted into a match so simulation, prediction and
+ * replay never depend on process configuration.
+ */
+export type ZoneConfig = {
+/**
+ * Simulation time the whole field stays open.
+ */
+start_delay_ms: number,
+/**
+ * Simulation time between two contractions once the zone is moving.
+ */
+shrink_interval_ms: number, rules_version: number, };
@@ -1,6 +1,11 @@
 // AUTO-GENERATED by scripts/gen-types.sh. Do not edit.
 export * from './AdBreakResolution';
+export * from './AiDifficulty';
+export * from './AiOpponents';
+export * from './AiSeat';
+export * from './AiTeamStrategy';
 export * from './Arena';
+export * from './ArenaMap';
 export * from './AuthResponse';
 export * from './BannerAdsConfig';
 export * from './BoostConfig';
@@ -18,7 +23,12 @@ export * from './CommandOutcome';
 export * from './CreateGuestResponse';
 export * from './CustomGameSettings';
 export * from './DeathCause';
+export * from './DeathFoodConfig';
 export * from './Direction';
+export * from './EdgePolicy';
+export * from './Flag';
+export * from './FlagConfig';
+export * from './FlagPlacement';
 export * from './GameChatBroadcast';
 export * from './GameCommand';
 export * from './GameCommandMessage';
@@ -35,11 +45,15 @@ export * from './HealthResponse';
 export * from './HighScoreEntryResponse';
 export * from './HighlightClip';
 export * from './HighlightConfig';
+export * from './HighlightMoment';
 export * from './HighlightPresentation';
 export * from './HighlightReason';
+export * from './HighlightReel';
 export * from './HighlightScoreBreakdown';
 export * from './HighlightSpeedSegment';
 export * from './HighlightWindow';
+export * from './HillConfig';
+export * from './HillControl';
 export * from './LeaderboardEntry';
 export * from './LeaderboardEntryResponse';
 export * from './LeaderboardResponse';
@@ -47,6 +61,8 @@ export * from './LobbyAdBreakView';
 export * from './LobbyChatBroadcast';
 export * from './LobbyMember';
 export * from './LobbyPreferences';
+export * from './MapBoostPad';
+export * from './MapBoostPlacement';
 export * from './MatchHistoryPage';
 export * from './MatchHistoryPlayer';
 export * from './MatchHistorySummary';
@@ -57,11 +73,15 @@ export * from './NewsTickerCtaAction';
 export * from './NewsTickerItem';
 export * from './NewsTickerKind';
 export * from './NewsTickerResponse';
+export * from './ObstacleBlock';
 export * from './OnlinePlayer';
 export * from './Player';
 export * from './PlayerLobbyResponse';
 export * from './PlayerLobbyStatus';
 export * from './Position';
+export * from './PowerUpConfig';
+export * from './PowerUpKind';
+export * from './PowerUpPickup';
 export * from './PresenceActivity';
 export * from './PublicGamePlayer';
 export * from './PublicGameResponse';
@@ -74,7 +94,9 @@ export * from './RegionRoster';
 export * from './RematchParticipant';
 export * from './RematchState';
 export * from './ReplayAnchor';
+export * from './ReplayKeyframe';
 export * from './ReplayVisibility';
+export * from './RoundState';
 export * from './RuntimeAdsConfig';
 export * from './RuntimeAdsDistributionsConfig';
 export * from './RuntimeAnnouncementConfig';
@@ -106,6 +128,10 @@ export * from './Snake';
 export * from './SnakeBoost';
 export * from './SnakeCombo';
 export * from './SnakeCrash';
+export * from './SnakePowerUps';
+export * from './SpawnPoint';
+export * from './SpectateGame';
+export * from './StateChange';
 export * from './SyncStatus';
 export * from './TeamGoal';
 export * from './TeamId';
@@ -115,3 +141,6 @@ export * from './UserInfo';
 export * from './UserRankingResponse';
 export * from './VideoAdsConfig';
 export * from './WSMessage';
+export * from './WireEncoding';
+export * from './ZoneBounds';
+export * from './ZoneConfig';
@@ -153,7 +153,7 @@ export interface ChatMessage {
 }
 
 export type LobbyState = 'waiting' | 'ad_break' | 'queued' | 'matched';
-export type LobbyGameMode = 'duel' | '2v2' | 'solo' | 'ffa';
+export type LobbyGameMode = 'duel' | '2v2' | 'solo' | 'ffa' | 'hill' | 'ctf';
 export type MatchmakingStatus = 'idle' | 'queued' | 'joining';
 
 // Client-side lobby preferences (camelCase UI shape). The wire equivalent is
@@ -383,6 +383,10 @@ export interface UIGameSettings {
   allowJoin: boolean;
   allowSpectators: boolean;
   snakeStartLength: number;
+  powerUps: boolean;
+  shrinkingZone: boolean;
+  wraparound: boolean;
+  deathFood: boolean;
 }
 
 // Arena rotation angles (in degrees)
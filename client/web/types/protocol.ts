@@ -36,16 +36,23 @@ export type PayloadOf<K extends WSMessageTag> = K extends keyof TaggedPayloadMap
   ? TaggedPayloadMap[K]
   : null;
 
+/**
+ * A server frame exactly as it arrived: the text of a JSON frame, or the bytes
+ * of a binary MessagePack one (game events on a connection that asked for
+ * `WireEncoding` MessagePack). The WASM engine accepts either.
+ */
+export type RawServerFrame = string | Uint8Array;
+
 /**
  * Envelope handed to `onMessage` handlers: the tag, its typed payload, and the
- * original raw frame text. `raw` lets the game-event path forward the exact
- * bytes to the WASM engine (via GameClient.processServerFrame) so full-range
- * u64 fields are never widened to f64 by a JS `JSON.parse`.
+ * original raw frame. `raw` lets the game-event path forward the exact bytes
+ * to the WASM engine (via GameClient.processServerFrame) so full-range u64
+ * fields are never widened to f64 by a JS `JSON.parse`.
  */
 export interface TypedMessage<K extends WSMessageTag = WSMessageTag> {
   type: K;
   data: PayloadOf<K>;
-  raw: string;
+  raw: RawServerFrame;
 }
 
 /**
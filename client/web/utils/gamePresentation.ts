@@ -49,6 +49,8 @@ export interface MatchSidePresentation {
   label: string;
   color: string;
   score: number;
+  /** Rounds this side has taken in a best-of-N match; null for one round. */
+  roundWins: number | null;
   players: MatchPlayerPresentation[];
   isCurrentSide: boolean;
   isWinner: boolean;
@@ -71,7 +73,7 @@ export interface MatchPresentation {
   /**
    * The caption above the match clock. Team matches race to a score with no
    * clock, so the caption carries the target ("First to 25") and the clock
-   * below it simply counts up.
+   * below it simply counts up. A best-of-N match also names the round.
    */
   timeLabel: string;
   timeValue: string;
@@ -169,6 +171,22 @@ const modeDetails = (gameState: GameState, queueMode: QueueMode) => {
       isTeam: true,
     };
   }
+  if ('KingOfTheHill' in gameType) {
+    const size = gameType.KingOfTheHill.per_team === 1 ? 'Hill' : 'Hill 2v2';
+    return {
+      label: queueMode === 'Competitive' ? `Competitive ${size}` : `Quick ${size}`,
+      isSolo: false,
+      isTeam: true,
+    };
+  }
+  if ('CaptureTheFlag' in gameType) {
+    const size = gameType.CaptureTheFlag.per_team === 1 ? 'CTF' : 'CTF 2v2';
+    return {
+      label: queueMode === 'Competitive' ? `Competitive ${size}` : `Quick ${size}`,
+      isSolo: false,
+      isTeam: true,
+    };
+  }
   if ('FreeForAll' in gameType) {
     return {
       label: queueMode === 'Competitive' ? 'Competitive FFA' : 'Free for all',
@@ -219,6 +237,8 @@ export const buildDeathAttribution = (
       return `${action} by enemy base`;
     case 'SelfCollision':
       return `${action} by yourself`;
+    case 'Zone':
+      return `${action} by the zone`;
   }
 
   return null;
@@ -319,6 +339,7 @@ export const buildMatchPresentation = (
     };
   });
 
+  const rounds = gameState.rounds ?? null;
   const sides: MatchSidePresentation[] = mode.isTeam
     ? orderedTeamIds.slice(0, 2).map((teamId, index) => ({
       teamId,
@@ -327,6 +348,7 @@ export const buildMatchPresentation = (
         : (teamId === currentTeamId ? 'Your team' : 'Opponents'),
       color: index === 0 ? GAME_SHELL_COLORS.blue : GAME_SHELL_COLORS.red,
       score: valueAt(gameState.team_scores, teamId),
+      roundWins: rounds === null ? null : valueAt(rounds.round_wins, teamId),
       players: players.filter((player) => player.teamId === teamId),
       isCurrentSide: teamId === currentTeamId,
       isWinner: winningTeamId === teamId,
@@ -396,7 +418,9 @@ export const buildMatchPresentation = (
     isSoloGame: mode.isSolo,
     isComplete: isCompleteGameState(gameState),
     timeLabel: scoreLimit !== null
-      ? `First to ${scoreLimit}`
+      ? (rounds === null
+        ? `First to ${scoreLimit}`
+        : `Round ${rounds.current_round} · First to ${scoreLimit}`)
       : timeLimitMs === null ? 'Time' : 'Time left',
     timeValue,
     scoreLimit,
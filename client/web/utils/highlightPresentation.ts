@@ -93,6 +93,24 @@ export const formatHighlightReason = (reason: HighlightReason): string => {
   if ('ComboFrenzy' in reason) {
     return `Combo frenzy — ${reason.ComboFrenzy.max_chain}× chain`;
   }
+  if ('ContestedCapture' in reason) {
+    return `Hill taken — ${countLabel(reason.ContestedCapture.captures, 'capture')}`;
+  }
+  if ('FlagCapture' in reason) {
+    return `Flag run — ${countLabel(reason.FlagCapture.captures, 'capture')}`;
+  }
+  if ('Comeback' in reason) {
+    return `Comeback — up from ${countLabel(reason.Comeback.deficit, 'point')} down`;
+  }
+  if ('BuzzerBeater' in reason) {
+    return `Buzzer beater — ${countLabel(reason.BuzzerBeater.points, 'point')}`;
+  }
+  if ('SurvivalStreak' in reason) {
+    return `Survivor — ${reason.SurvivalStreak.seconds}s unbroken`;
+  }
+  if ('TrapEscape' in reason) {
+    return `Great escape — ${countLabel(reason.TrapEscape.escapes, 'trap')} slipped`;
+  }
   return `Feeding frenzy — ${countLabel(reason.FeedingFrenzy.pickups, 'pickup')}`;
 };
 
@@ -19,6 +19,7 @@ export interface IdleStateSnapshot {
   };
   player_last_activity_ticks?: { [key: number]: number | undefined };
   idle_kicked_user_ids?: readonly number[];
+  autopilot_players?: { [key: number]: unknown };
   completed_by_inactivity?: boolean;
 }
 
@@ -32,6 +33,7 @@ export interface IdleWarningPresentation {
 
 export interface PlayerIdlePresentation {
   isKicked: boolean;
+  isOnAutopilot: boolean;
   warning: IdleWarningPresentation | null;
 }
 
@@ -53,7 +55,9 @@ const isEliminatedFieldPlayer = (
     (
       state.game_type &&
       typeof state.game_type === 'object' &&
-      'TeamMatch' in state.game_type
+      ('TeamMatch' in state.game_type ||
+        'KingOfTheHill' in state.game_type ||
+        'CaptureTheFlag' in state.game_type)
     ) || state.team_scores,
   );
   if (isTeamMatch) {
@@ -90,6 +94,18 @@ export const wasPlayerIdleKicked = (
   getIdleKickedUserIds(state).includes(userId)
 );
 
+/**
+ * The AI is steering this player's snake until they send input again.
+ */
+export const isPlayerOnAutopilot = (
+  state: Pick<IdleStateSnapshot, 'autopilot_players'> | null | undefined,
+  userId: number | null | undefined,
+): boolean => (
+  userId !== null &&
+  userId !== undefined &&
+  Boolean(state?.autopilot_players?.[userId])
+);
+
 export const wasCompletedByInactivity = (
   state: Pick<IdleStateSnapshot, 'completed_by_inactivity'> | null | undefined,
 ): boolean => state?.completed_by_inactivity === true;
@@ -99,6 +115,7 @@ export const buildPlayerIdlePresentation = (
   userId: number | null | undefined,
 ): PlayerIdlePresentation => {
   const isKicked = wasPlayerIdleKicked(state, userId);
+  const isOnAutopilot = isPlayerOnAutopilot(state, userId);
   const playerCount = state?.players
     ? Object.values(state.players).filter(Boolean).length
     : 0;
@@ -107,11 +124,12 @@ export const buildPlayerIdlePresentation = (
     userId === null ||
     userId === undefined ||
     isKicked ||
+    isOnAutopilot ||
     !isStarted(state.status) ||
     playerCount < 2 ||
     isEliminatedFieldPlayer(state, userId)
   ) {
-    return { isKicked, warning: null };
+    return { isKicked, isOnAutopilot, warning: null };
   }
 
   const tick = finiteNonNegativeInteger(state.tick);
@@ -132,14 +150,14 @@ export const buildPlayerIdlePresentation = (
     configuredWarningMs === 0 ||
     lastActivityTick === null
   ) {
-    return { isKicked, warning: null };
+    return { isKicked, isOnAutopilot, warning: null };
   }
 
   const warningMs = Math.min(configuredWarningMs, timeoutMs);
   const elapsedTicks = Math.max(0, tick - lastActivityTick);
   const elapsedMs = elapsedTicks * tickDurationMs;
   if (elapsedMs < timeoutMs - warningMs || elapsedMs >= timeoutMs) {
-    return { isKicked, warning: null };
+    return { isKicked, isOnAutopilot, warning: null };
   }
 
   // The server expires on the first whole simulation quantum whose elapsed
@@ -154,6 +172,7 @@ export const buildPlayerIdlePresentation = (
 
   return {
     isKicked,
+    isOnAutopilot,
     warning: {
       deadlineTick,
       remainingMs,
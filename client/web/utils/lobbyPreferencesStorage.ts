@@ -3,7 +3,7 @@ import { gameStorage } from '../services/gameStorage.ts';
 
 const LAST_LOBBY_PREFERENCES_KEY = 'lastLobbyPreferences';
 const LEGACY_LAST_MODES_KEY = 'lastSelectedGameModes';
-const VALID_GAME_MODES: LobbyGameMode[] = ['duel', '2v2', 'solo', 'ffa'];
+const VALID_GAME_MODES: LobbyGameMode[] = ['duel', '2v2', 'solo', 'ffa', 'hill', 'ctf'];
 
 const sanitizeModes = (raw: unknown): LobbyGameMode[] => {
   if (!Array.isArray(raw)) {
@@ -0,0 +1,118 @@
+//! Tick cost with long snakes.
+//!
+//! Four snakes lie folded into serpentines on a large Custom arena, so every
+//! body has hundreds of turns and collision checks cannot get away with a few
+//! segments. `tick` measures one authoritative tick with the occupancy grid
+//! current, as it is between ticks of a running match. `tick_after_snapshot`
+//! starts from an empty grid, the cost paid once after a snapshot is loaded.
+//! `ai_move` measures one AI decision on the same field.
+//!
+//! Run with `cargo bench -p common --bench tick`.
+
+use common::{
+    CustomGameSettings, Direction, GameMode, GameState, GameStatus, GameType, OccupancyGrid,
+    Position, QueueMode, calculate_ai_move,
+};
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const ARENA: u16 = 160;
+/// Cells per serpentine row; the columns to the right stay open for the heads.
+const ROW: i16 = 128;
+const BAND_ROWS: i16 = 40;
+const LENGTHS: [usize; 4] = [64, 256, 1024, 4096];
+
+/// `length` cells folded back and forth inside the band starting at row
+/// `top`, head first, with the head at the right end of the top row so it
+/// has open field ahead.
+fn serpentine(top: i16, length: usize) -> Vec<Position> {
+    let cells: Vec<Position> = (0..length as i16)
+        .map(|i| {
+            let (row, step) = (i / ROW, i % ROW);
+            let x = if row % 2 == 0 { ROW - step } else { 1 + step };
+            Position { x, y: top + row }
+        })
+        .collect();
+    // Keep the ends and every turn: the compressed form `Snake::body` uses.
+    let mut body = vec![cells[0]];
+    for window in cells.windows(3) {
+        let straight = (window[0].x == window[2].x) || (window[0].y == window[2].y);
+        if !straight {
+            body.push(window[1]);
+        }
+    }
+    body.push(cells[cells.len() - 1]);
+    body
+}
+
+fn crowded_game(length: usize) -> GameState {
+    let settings = CustomGameSettings {
+        arena_width: ARENA,
+        arena_height: ARENA,
+        max_players: 4,
+        game_mode: GameMode::FreeForAll { max_players: 4 },
+        ..CustomGameSettings::default()
+    };
+    let mut game = GameState::new(
+        ARENA,
+        ARENA,
+        GameType::Custom { settings },
+        QueueMode::Quickmatch,
+        Some(7),
+        0,
+    );
+    for user_id in 1..=4 {
+        game.add_player(user_id, None).expect("add bench player");
+    }
+    for (band, snake) in game.arena.snakes.iter_mut().enumerate() {
+        snake.body = serpentine(band as i16 * BAND_ROWS, length);
+        snake.direction = Direction::Right;
+    }
+    game.status = GameStatus::Started { server_id: 1 };
+    game.arena.sync_occupancy();
+    game
+}
+
+fn tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick");
+    for length in LENGTHS {
+        let game = crowded_game(length);
+        group.bench_with_input(BenchmarkId::from_parameter(length), &game, |b, game| {
+            b.iter_batched(
+                || game.clone(),
+                |mut game| game.tick_forward(false).expect("bench tick"),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn tick_after_snapshot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tick_after_snapshot");
+    for length in LENGTHS {
+        let mut game = crowded_game(length);
+        game.arena.occupancy = OccupancyGrid::default();
+        group.bench_with_input(BenchmarkId::from_parameter(length), &game, |b, game| {
+            b.iter_batched(
+                || game.clone(),
+                |mut game| game.tick_forward(false).expect("bench tick"),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn ai_move(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ai_move");
+    for length in LENGTHS {
+        let game = crowded_game(length);
+        group.bench_with_input(BenchmarkId::from_parameter(length), &game, |b, game| {
+            b.iter(|| calculate_ai_move(game, 0, Direction::Right))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, tick, tick_after_snapshot, ai_move);
+criterion_main!(benches);
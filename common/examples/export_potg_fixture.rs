@@ -38,16 +38,19 @@ fn main() -> Result<()> {
             at_tick: 28,
             user_id: 1,
             command: ScenarioCommandKind::Turn(Direction::Right),
+            scheduled_at: None,
         },
         ScenarioCommand {
             at_tick: 38,
             user_id: 1,
             command: ScenarioCommandKind::Turn(Direction::Down),
+            scheduled_at: None,
         },
         ScenarioCommand {
             at_tick: 66,
             user_id: 1,
             command: ScenarioCommandKind::Turn(Direction::Left),
+            scheduled_at: None,
         },
     ];
 
@@ -92,6 +95,7 @@ fn main() -> Result<()> {
         messages,
         end_tick: run.final_state.tick,
         end_sync_hash: run.final_state.sync_hash(),
+        keyframes: Vec::new(),
     };
     recording.verify_end_hash()?;
     let clip = select_highlight(&recording, &HighlightConfig::default())?
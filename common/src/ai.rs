@@ -1,19 +1,219 @@
-use crate::{Direction, GameCommand, GameState, GameType, Position, Snake, TeamId};
+use crate::{
+    Direction, FlagPlacement, GameCommand, GameState, OccupancyGrid, Position, Snake, TeamId,
+};
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+mod policy;
+mod search;
+
+pub use policy::*;
+pub use search::*;
+
+/// How many user ids are reserved for AI players, counting down from
+/// `u32::MAX`.
+pub const AI_PLAYER_SLOTS: u32 = 11;
 
 /// Determines if a user_id represents an AI player
 pub fn is_ai_player(user_id: u32) -> bool {
     // AI players have user_ids starting from u32::MAX and counting down
-    user_id >= u32::MAX - 10
+    user_id > u32::MAX - AI_PLAYER_SLOTS
+}
+
+/// The reserved user id of the `index`th AI player, or `None` once the
+/// reserved range is used up.
+pub fn ai_user_id(index: u32) -> Option<u32> {
+    (index < AI_PLAYER_SLOTS).then(|| u32::MAX - index)
+}
+
+/// How strong an AI opponent plays.
+///
+/// Every tier runs the same policy; they differ only in the knobs of
+/// [`AiProfile`]. `Medium` is what [`calculate_ai_move`] and
+/// [`calculate_ai_command`] play.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum AiDifficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl AiDifficulty {
+    pub const ALL: [AiDifficulty; 3] =
+        [AiDifficulty::Easy, AiDifficulty::Medium, AiDifficulty::Hard];
+
+    pub fn profile(self) -> AiProfile {
+        match self {
+            AiDifficulty::Easy => AiProfile {
+                reaction_delay_ms: 300,
+                lookahead_depth: 0,
+                boost: AiBoostUsage::Never,
+                team_strategy: AiTeamStrategy::Adaptive,
+            },
+            AiDifficulty::Medium => AiProfile {
+                reaction_delay_ms: 100,
+                lookahead_depth: 1,
+                boost: AiBoostUsage::WhenCharged,
+                team_strategy: AiTeamStrategy::Adaptive,
+            },
+            AiDifficulty::Hard => AiProfile {
+                reaction_delay_ms: 50,
+                lookahead_depth: 6,
+                boost: AiBoostUsage::OnClearRun,
+                team_strategy: AiTeamStrategy::Adaptive,
+            },
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AiDifficulty::Easy => "easy",
+            AiDifficulty::Medium => "medium",
+            AiDifficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for AiDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AiDifficulty {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        AiDifficulty::ALL
+            .into_iter()
+            .find(|difficulty| value.trim().eq_ignore_ascii_case(difficulty.as_str()))
+            .ok_or_else(|| {
+                anyhow!("unknown AI difficulty '{value}' (expected easy, medium or hard)")
+            })
+    }
+}
+
+/// The knobs a difficulty tier turns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AiProfile {
+    /// Least time between two decisions. A snake can turn at most once per
+    /// step anyway, so anything at or below the tick changes nothing.
+    pub reaction_delay_ms: u32,
+    /// Steps past the next cell searched for room to keep moving. At 0 the
+    /// snake only avoids running straight into something; at 1 it also
+    /// refuses cells with no way out.
+    pub lookahead_depth: u8,
+    pub boost: AiBoostUsage,
+    pub team_strategy: AiTeamStrategy,
+}
+
+impl AiProfile {
+    /// Ticks between two decisions at `tick_duration_ms`, never less than one.
+    pub fn decision_interval_ticks(&self, tick_duration_ms: u32) -> u32 {
+        self.reaction_delay_ms
+            .div_ceil(tick_duration_ms.max(1))
+            .max(1)
+    }
+}
+
+/// When an AI snake spends its Boost charge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiBoostUsage {
+    Never,
+    /// As soon as there is any charge, before choosing a turn.
+    WhenCharged,
+    /// Only when it is about to keep its heading and the cells ahead are
+    /// free as far as it looks.
+    OnClearRun,
+}
+
+/// How a team-match snake decides to take what it carries home.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum AiTeamStrategy {
+    /// Bank at a fixed carry, sooner only when the score race forces it.
+    /// The policy `Adaptive` replaced, kept as the baseline it is measured
+    /// against.
+    Threshold,
+    /// Weigh the carry against the trip home and the stage of the match.
+    /// See `specs/team-ai-upgrade.md`.
+    #[default]
+    Adaptive,
+}
+
+/// A request to fill seats with AI opponents of one tier.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct AiOpponents {
+    pub count: u8,
+    pub difficulty: AiDifficulty,
+}
+
+/// An AI-driven player in a running game.
+///
+/// The seat lives in `GameState` and is driven by the simulation itself, so
+/// the server, a replay and a client's prediction all make the same decision
+/// on the same tick.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct AiSeat {
+    pub difficulty: AiDifficulty,
+    /// First tick at which the seat may decide again.
+    #[serde(default)]
+    pub next_decision_tick: u32,
 }
 
 /// Basic AI that tries to reach food while avoiding obstacles
-/// In team games, also decides when to return to base to score points
+/// In team games, also decides when to return to base to score points,
+/// in king of the hill makes for the hill, and in capture the flag raids the
+/// enemy base and defends its own flag
 pub fn calculate_ai_move(
     game_state: &GameState,
     snake_id: u32,
     current_direction: Direction,
 ) -> Option<Direction> {
+    calculate_ai_move_at(
+        game_state,
+        snake_id,
+        current_direction,
+        AiDifficulty::default(),
+    )
+}
+
+/// [`calculate_ai_move`] at a chosen difficulty. Only the lookahead depth
+/// and team strategy apply here; reaction delay is the caller's cadence.
+pub fn calculate_ai_move_at(
+    game_state: &GameState,
+    snake_id: u32,
+    current_direction: Direction,
+    difficulty: AiDifficulty,
+) -> Option<Direction> {
+    calculate_ai_move_for(
+        game_state,
+        snake_id,
+        current_direction,
+        &difficulty.profile(),
+    )
+}
+
+/// [`calculate_ai_move`] with every knob spelled out, for harnesses that
+/// compare one knob at a time.
+pub fn calculate_ai_move_for(
+    game_state: &GameState,
+    snake_id: u32,
+    current_direction: Direction,
+    profile: &AiProfile,
+) -> Option<Direction> {
+    let lookahead_depth = profile.lookahead_depth;
     let snake = game_state.arena.snakes.get(snake_id as usize)?;
     if !snake.is_alive {
         return None;
@@ -22,6 +222,7 @@ pub fn calculate_ai_move(
     let head = snake.head().ok()?;
     let arena_width = game_state.arena.width as i16;
     let arena_height = game_state.arena.height as i16;
+    let occupancy = game_state.arena.occupancy();
 
     // Get all possible directions (always excluding opposite of current direction)
     let mut possible_directions = vec![
@@ -35,16 +236,42 @@ pub fn calculate_ai_move(
     possible_directions.retain(|&d| !current_direction.is_opposite(&d));
 
     // Determine target based on game mode and strategy
-    let target = if is_team_game(game_state)
+    let target = if let Some(config) = &game_state.properties.hill {
+        // King of the hill scores only on the hill, so head for its centre
+        // and circle there once arrived.
+        let hill = config.bounds(game_state.arena.width, game_state.arena.height);
+        Position {
+            x: (hill.min_x + hill.max_x) / 2,
+            y: (hill.min_y + hill.max_y) / 2,
+        }
+    } else if game_state.properties.flags.is_some()
+        && let Some(team_id) = snake.team_id
+    {
+        capture_the_flag_target(
+            game_state,
+            snake_id,
+            head,
+            team_id,
+            arena_width,
+            arena_height,
+        )?
+    } else if is_team_game(game_state)
         && let Some(team_id) = snake.team_id
     {
         // Decide whether to return to base or collect more food
-        if should_return_to_base(game_state, snake, team_id) {
+        let trip = match profile.team_strategy {
+            AiTeamStrategy::Threshold => should_return_to_base(game_state, snake, team_id)
+                .then(|| find_path_to_base(game_state, head, team_id, arena_width, arena_height))
+                .flatten(),
+            AiTeamStrategy::Adaptive => {
+                adaptive_return_target(game_state, occupancy, snake, team_id)
+            }
+        };
+        match trip {
             // RETURN_TO_BASE mode: Navigate to goal
-            find_path_to_base(game_state, head, team_id, arena_width, arena_height)?
-        } else {
+            Some(goal) => goal,
             // COLLECT_FOOD mode: Find nearest food
-            find_nearest_food(game_state, head, arena_width, arena_height)?
+            None => find_nearest_food(game_state, head, arena_width, arena_height)?,
         }
     } else {
         // Non-team game: always collect food
@@ -59,15 +286,14 @@ pub fn calculate_ai_move(
     let mut best_score = i32::MIN;
 
     for direction in possible_directions {
-        let new_pos = get_new_position(head, direction);
-
         // Skip if out of bounds
-        if !is_within_bounds(&new_pos, arena_width, arena_height) {
+        let Some(new_pos) = next_position(game_state, head, direction, arena_width, arena_height)
+        else {
             continue;
-        }
+        };
 
         // Skip if collision with any snake
-        if would_collide_with_snake(game_state, &new_pos) {
+        if would_collide_with_snake(game_state, occupancy, &new_pos) {
             continue;
         }
 
@@ -75,27 +301,58 @@ pub fn calculate_ai_move(
         let mut score = 0;
 
         // Prefer moving toward food
-        let food_distance = manhattan_distance(&new_pos, &nearest_food);
+        let food_distance = field_distance(game_state, &new_pos, &nearest_food);
         score -= food_distance as i32 * 10;
 
-        // Prefer staying in center of arena
-        let center_x = arena_width / 2;
-        let center_y = arena_height / 2;
-        let center_distance = manhattan_distance(
-            &new_pos,
-            &Position {
-                x: center_x,
-                y: center_y,
-            },
-        );
-        score -= center_distance as i32;
+        // Prefer staying in center of arena. A wrapping field has no edge to
+        // be pinned against, so there the center is no safer than anywhere.
+        if !game_state.arena.wraps() {
+            let center_x = arena_width / 2;
+            let center_y = arena_height / 2;
+            let center_distance = manhattan_distance(
+                &new_pos,
+                &Position {
+                    x: center_x,
+                    y: center_y,
+                },
+            );
+            score -= center_distance as i32;
+        }
 
-        // Check if this move would trap us (simple lookahead)
-        let escape_routes = count_escape_routes(game_state, &new_pos, arena_width, arena_height);
-        if escape_routes == 0 {
-            continue; // Skip moves that would trap us
+        // Check if this move would trap us. One step ahead counts the ways
+        // out of the new cell; a deeper search counts the room around it,
+        // scaled so it weighs about as much against food as one step does.
+        match lookahead_depth {
+            0 => {}
+            1 => {
+                let escape_routes =
+                    count_escape_routes(game_state, occupancy, &new_pos, arena_width, arena_height);
+                if escape_routes == 0 {
+                    continue; // Skip moves that would trap us
+                }
+                score += escape_routes as i32 * 5;
+            }
+            depth => {
+                let room = count_reachable_cells(
+                    game_state,
+                    occupancy,
+                    head,
+                    &new_pos,
+                    depth,
+                    arena_width,
+                    arena_height,
+                );
+                if room == 0 {
+                    continue;
+                }
+                // Fewer free cells than steps searched is a pocket the snake
+                // would have to die in; take it only if nothing else is left.
+                if room < depth as u32 {
+                    score -= 1_000;
+                }
+                score += (room * 5 / depth as u32) as i32;
+            }
         }
-        score += escape_routes as i32 * 5;
 
         if score > best_score {
             best_score = score;
@@ -117,23 +374,68 @@ pub fn calculate_ai_move(
 /// the exact same Boost-first behavior as the networked bot binary. The
 /// caller owns cadence and transport identity, just as the live bot does.
 pub fn calculate_ai_command(game_state: &GameState, snake_id: u32) -> Option<GameCommand> {
+    calculate_ai_command_at(game_state, snake_id, AiDifficulty::default())
+}
+
+/// [`calculate_ai_command`] at a chosen difficulty, which decides whether and
+/// when Boost is spent as well as how far the turn looks ahead.
+pub fn calculate_ai_command_at(
+    game_state: &GameState,
+    snake_id: u32,
+    difficulty: AiDifficulty,
+) -> Option<GameCommand> {
+    calculate_ai_command_for(game_state, snake_id, &difficulty.profile())
+}
+
+/// [`calculate_ai_command`] with every knob spelled out.
+pub fn calculate_ai_command_for(
+    game_state: &GameState,
+    snake_id: u32,
+    profile: &AiProfile,
+) -> Option<GameCommand> {
     let snake = game_state.arena.snakes.get(snake_id as usize)?;
     if !snake.is_alive {
         return None;
     }
-    if game_state.properties.boost.is_some() && snake.boost().charge_ms > 0 && !snake.boost().active
-    {
+    let can_boost = game_state.properties.boost.is_some()
+        && snake.boost().charge_ms > 0
+        && !snake.boost().active;
+    if can_boost && profile.boost == AiBoostUsage::WhenCharged {
         return Some(GameCommand::ActivateBoost { snake_id });
     }
 
-    let direction =
-        calculate_ai_move(game_state, snake_id, snake.direction).unwrap_or(snake.direction);
+    let direction = calculate_ai_move_for(game_state, snake_id, snake.direction, profile)
+        .unwrap_or(snake.direction);
+    if can_boost
+        && profile.boost == AiBoostUsage::OnClearRun
+        && direction == snake.direction
+        && has_clear_run(game_state, snake, profile.lookahead_depth)
+    {
+        return Some(GameCommand::ActivateBoost { snake_id });
+    }
     Some(GameCommand::Turn {
         snake_id,
         direction,
     })
 }
 
+/// Whether the `cells` cells straight ahead of `snake` are all free.
+fn has_clear_run(game_state: &GameState, snake: &Snake, cells: u8) -> bool {
+    let Ok(mut pos) = snake.head().copied() else {
+        return false;
+    };
+    let width = game_state.arena.width as i16;
+    let height = game_state.arena.height as i16;
+    let occupancy = game_state.arena.occupancy();
+    for _ in 0..cells.max(1) {
+        match next_position(game_state, &pos, snake.direction, width, height) {
+            Some(next) if !would_collide_with_snake(game_state, occupancy, &next) => pos = next,
+            _ => return false,
+        }
+    }
+    true
+}
+
 fn get_new_position(pos: &Position, direction: Direction) -> Position {
     match direction {
         Direction::Up => Position {
@@ -159,9 +461,35 @@ fn is_within_bounds(pos: &Position, width: i16, height: i16) -> bool {
     pos.x >= 0 && pos.x < width && pos.y >= 0 && pos.y < height
 }
 
-fn would_collide_with_snake(game_state: &GameState, pos: &Position) -> bool {
+/// The cell one step from `pos`, or `None` when that step leaves a solid
+/// field. On a wrapping field the step comes back in on the far side.
+fn next_position(
+    game_state: &GameState,
+    pos: &Position,
+    direction: Direction,
+    width: i16,
+    height: i16,
+) -> Option<Position> {
+    let new_pos = get_new_position(pos, direction);
+    if game_state.arena.wraps() {
+        Some(game_state.arena.wrap_position(new_pos))
+    } else {
+        is_within_bounds(&new_pos, width, height).then_some(new_pos)
+    }
+}
+
+/// Whether a living snake covers `pos`, looked up in `occupancy` when the
+/// caller has a current grid.
+fn would_collide_with_snake(
+    game_state: &GameState,
+    occupancy: Option<&OccupancyGrid>,
+    pos: &Position,
+) -> bool {
+    if let Some(occupancy) = occupancy {
+        return occupancy.live_snake_at(&game_state.arena.snakes, pos);
+    }
     for snake in &game_state.arena.snakes {
-        if snake.is_alive && snake.contains_point(pos, false) {
+        if snake.is_alive && game_state.arena.snake_contains(snake, pos, false) {
             return true;
         }
     }
@@ -172,6 +500,19 @@ fn manhattan_distance(p1: &Position, p2: &Position) -> u16 {
     ((p1.x - p2.x).abs() + (p1.y - p2.y).abs()) as u16
 }
 
+/// Steps between two cells, taking the short way across the seam when the
+/// field wraps.
+fn field_distance(game_state: &GameState, p1: &Position, p2: &Position) -> u16 {
+    if !game_state.arena.wraps() {
+        return manhattan_distance(p1, p2);
+    }
+    let width = game_state.arena.width as i16;
+    let height = game_state.arena.height as i16;
+    let dx = (p1.x - p2.x).rem_euclid(width);
+    let dy = (p1.y - p2.y).rem_euclid(height);
+    (dx.min(width - dx) + dy.min(height - dy)) as u16
+}
+
 fn find_nearest_food(
     game_state: &GameState,
     start: &Position,
@@ -205,9 +546,9 @@ fn find_nearest_food(
             Direction::Left,
             Direction::Right,
         ] {
-            let new_pos = get_new_position(&pos, *direction);
-
-            if is_within_bounds(&new_pos, width, height) && !visited.contains(&new_pos) {
+            if let Some(new_pos) = next_position(game_state, &pos, *direction, width, height)
+                && !visited.contains(&new_pos)
+            {
                 visited.insert(new_pos);
                 queue.push_back(new_pos);
             }
@@ -218,7 +559,13 @@ fn find_nearest_food(
     game_state.arena.food.first().copied()
 }
 
-fn count_escape_routes(game_state: &GameState, pos: &Position, width: i16, height: i16) -> u8 {
+fn count_escape_routes(
+    game_state: &GameState,
+    occupancy: Option<&OccupancyGrid>,
+    pos: &Position,
+    width: i16,
+    height: i16,
+) -> u8 {
     let mut count = 0;
 
     for direction in &[
@@ -227,10 +574,8 @@ fn count_escape_routes(game_state: &GameState, pos: &Position, width: i16, heigh
         Direction::Left,
         Direction::Right,
     ] {
-        let new_pos = get_new_position(pos, *direction);
-
-        if is_within_bounds(&new_pos, width, height)
-            && !would_collide_with_snake(game_state, &new_pos)
+        if let Some(new_pos) = next_position(game_state, pos, *direction, width, height)
+            && !would_collide_with_snake(game_state, occupancy, &new_pos)
         {
             count += 1;
         }
@@ -239,13 +584,50 @@ fn count_escape_routes(game_state: &GameState, pos: &Position, width: i16, heigh
     count
 }
 
+/// Free cells reachable from `start` in at most `depth` steps, `start`
+/// excluded. `head` is where the snake is now, which it will have left.
+fn count_reachable_cells(
+    game_state: &GameState,
+    occupancy: Option<&OccupancyGrid>,
+    head: &Position,
+    start: &Position,
+    depth: u8,
+    width: i16,
+    height: i16,
+) -> u32 {
+    let mut visited = HashSet::from([*head, *start]);
+    let mut frontier = vec![*start];
+    let mut count = 0;
+    for _ in 0..depth {
+        let mut next_frontier = Vec::new();
+        for pos in &frontier {
+            for direction in [
+                Direction::Up,
+                Direction::Down,
+                Direction::Left,
+                Direction::Right,
+            ] {
+                if let Some(new_pos) = next_position(game_state, pos, direction, width, height)
+                    && visited.insert(new_pos)
+                    && !would_collide_with_snake(game_state, occupancy, &new_pos)
+                {
+                    count += 1;
+                    next_frontier.push(new_pos);
+                }
+            }
+        }
+        frontier = next_frontier;
+    }
+    count
+}
+
 // ============================================================================
 // TEAM GAME AI HELPERS
 // ============================================================================
 
-/// Check if the current game is a team match
+/// Check if the current game is played between two teams
 fn is_team_game(game_state: &GameState) -> bool {
-    matches!(game_state.game_type, GameType::TeamMatch { .. })
+    game_state.game_type.is_team_game()
 }
 
 /// A team is this close to the target before its next bank is treated as
@@ -330,9 +712,9 @@ fn find_path_to_base(
             Direction::Left,
             Direction::Right,
         ] {
-            let new_pos = get_new_position(&pos, *direction);
-
-            if is_within_bounds(&new_pos, width, height) && !visited.contains(&new_pos) {
+            if let Some(new_pos) = next_position(game_state, &pos, *direction, width, height)
+                && !visited.contains(&new_pos)
+            {
                 visited.insert(new_pos);
                 queue.push_back(new_pos);
             }
@@ -346,7 +728,58 @@ fn find_path_to_base(
     })
 }
 
-/// Determine if the snake should return to base to score points
+/// Where a capture-the-flag snake should head, in order of urgency: a carrier
+/// runs home, a defender goes after its own flag when it has left its stand,
+/// and everyone else goes for the enemy flag. The enemy base is only open
+/// through its goal, so a snake outside it aims for the goal mouth first and
+/// a carrier inside it leaves the same way. With the enemy flag already in a
+/// teammate's hands there is nothing left to take, so the snake eats.
+fn capture_the_flag_target(
+    game_state: &GameState,
+    snake_id: u32,
+    head: &Position,
+    team_id: TeamId,
+    width: i16,
+    height: i16,
+) -> Option<Position> {
+    let enemy_team = if team_id.0 == 0 { TeamId(1) } else { TeamId(0) };
+    let arena = &game_state.arena;
+    let flag_of = |team| game_state.flags.iter().find(|flag| flag.team_id == team);
+    let in_enemy_base = arena.is_in_team_base(head, enemy_team);
+
+    let enemy_flag = flag_of(enemy_team)?;
+    if enemy_flag.carrier() == Some(snake_id) {
+        let goal = if in_enemy_base { enemy_team } else { team_id };
+        return find_path_to_base(game_state, head, goal, width, height);
+    }
+
+    if let Some(own_flag) = flag_of(team_id) {
+        match own_flag.placement {
+            FlagPlacement::Home => {}
+            FlagPlacement::Carried { snake_id: carrier } => {
+                if let Some(carrier_head) = arena
+                    .snakes
+                    .get(carrier as usize)
+                    .and_then(|snake| snake.head().ok())
+                {
+                    return Some(*carrier_head);
+                }
+            }
+            FlagPlacement::Dropped { position, .. } => return Some(position),
+        }
+    }
+
+    match enemy_flag.position(arena) {
+        Some(flag) if arena.is_in_team_base(&flag, enemy_team) && !in_enemy_base => {
+            find_path_to_base(game_state, head, enemy_team, width, height)
+        }
+        Some(flag) => Some(flag),
+        None => find_nearest_food(game_state, head, width, height),
+    }
+}
+
+/// Determine if the snake should return to base to score points, the
+/// [`AiTeamStrategy::Threshold`] way
 fn should_return_to_base(game_state: &GameState, snake: &Snake, team_id: TeamId) -> bool {
     let carried_points = game_state.carried_food(snake);
 
@@ -383,3 +816,551 @@ fn should_return_to_base(game_state: &GameState, snake: &Snake, team_id: TeamId)
     // Decision threshold: >= 40 means return to base
     total_return_score >= 40
 }
+
+// ============================================================================
+// ADAPTIVE BANKING (specs/team-ai-upgrade.md)
+// ============================================================================
+
+/// Return score at which a snake stops eating and heads home.
+const RETURN_THRESHOLD: i32 = 40;
+
+/// What a trip home is worth when the base is right there. Every step of the
+/// way costs two points of it, so the carry worth the trip grows with the
+/// distance: three food at the door, five from ten steps out, and from across
+/// the field only a haul. A snake that respawns at home after banking should
+/// bank on its way past, not walk back for it.
+const TRIP_SCORE_NEAR_BASE: i32 = 15;
+
+/// The cell to step to on the way home when the snake should bank now, or
+/// `None` to keep eating.
+fn adaptive_return_target(
+    game_state: &GameState,
+    occupancy: Option<&OccupancyGrid>,
+    snake: &Snake,
+    team_id: TeamId,
+) -> Option<Position> {
+    let carried_points = game_state.carried_food(snake);
+    if carried_points == 0 {
+        return None;
+    }
+    let head = snake.head().ok()?;
+    if game_state.arena.is_in_team_base(head, team_id) {
+        return None; // Already home, go back out
+    }
+
+    // With every way home blocked, keep eating until one opens up.
+    let (next_step, distance) = path_home(game_state, occupancy, snake, team_id)?;
+    (return_score(game_state, team_id, carried_points, distance) >= RETURN_THRESHOLD)
+        .then_some(next_step)
+}
+
+/// How much a snake carrying `carried_points`, `distance` steps from home,
+/// wants to bank them now.
+///
+/// Nearby enemies deliberately play no part: the snake stays greedy and
+/// leaves danger to the per-move collision checks rather than running home
+/// at the first sight of a rival.
+fn return_score(
+    game_state: &GameState,
+    team_id: TeamId,
+    carried_points: u32,
+    distance: u32,
+) -> i32 {
+    let base_food_score = i32::try_from(carried_points)
+        .unwrap_or(i32::MAX)
+        .saturating_mul(10);
+    let trip_score = TRIP_SCORE_NEAR_BASE.saturating_sub(
+        i32::try_from(distance)
+            .unwrap_or(i32::MAX)
+            .saturating_mul(2),
+    );
+    let phase_score = match_phase_pressure(game_state);
+    let race_pressure_score = score_race_pressure(game_state, team_id, carried_points);
+
+    // Take more risk when losing badly: only a bigger carry can catch up.
+    let score_differential_bonus = if get_score_differential(game_state, team_id) < -10 {
+        -20
+    } else {
+        0
+    };
+
+    base_food_score
+        .saturating_add(trip_score)
+        .saturating_add(phase_score + race_pressure_score + score_differential_bonus)
+}
+
+/// Urgency from how far the match has run.
+///
+/// Team matches end on a score target rather than a clock, so the leader's
+/// share of the target stands in for time remaining: early on a snake fills
+/// up, midway it banks a little sooner, and in the closing stretch any carry
+/// is worth securing.
+fn match_phase_pressure(game_state: &GameState) -> i32 {
+    let Some(score_limit) = game_state.properties.score_limit.filter(|limit| *limit > 0) else {
+        return 0;
+    };
+    let leader = game_state
+        .team_scores
+        .as_ref()
+        .and_then(|scores| scores.values().copied().max())
+        .unwrap_or(0);
+    match leader.saturating_mul(100) / score_limit {
+        0..40 => 0,
+        40..80 => 10,
+        _ => 50,
+    }
+}
+
+/// Shortest way into the team's base around walls and every living body, as
+/// the first cell to step to and the number of steps. The snake cannot
+/// reverse, so the cell behind its head is never a first step.
+fn path_home(
+    game_state: &GameState,
+    occupancy: Option<&OccupancyGrid>,
+    snake: &Snake,
+    team_id: TeamId,
+) -> Option<(Position, u32)> {
+    let head = *snake.head().ok()?;
+    let arena = &game_state.arena;
+    let width = arena.width as i16;
+    let height = arena.height as i16;
+    let index = |pos: &Position| pos.y as usize * arena.width as usize + pos.x as usize;
+
+    // For every reached cell, the first step taken on the way to it.
+    let mut first_step: Vec<Option<Position>> =
+        vec![None; arena.width as usize * arena.height as usize];
+    let mut queue = VecDeque::new();
+    for direction in [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ] {
+        if snake.direction.is_opposite(&direction) {
+            continue;
+        }
+        if let Some(next) = next_position(game_state, &head, direction, width, height)
+            && !arena.is_wall_position(&next)
+            && !would_collide_with_snake(game_state, occupancy, &next)
+        {
+            first_step[index(&next)] = Some(next);
+            queue.push_back((next, 1));
+        }
+    }
+
+    while let Some((pos, distance)) = queue.pop_front() {
+        let step = first_step[index(&pos)]?;
+        if arena.is_in_team_base(&pos, team_id) {
+            return Some((step, distance));
+        }
+        for direction in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            if let Some(next) = next_position(game_state, &pos, direction, width, height)
+                && next != head
+                && first_step[index(&next)].is_none()
+                && !arena.is_wall_position(&next)
+                && !would_collide_with_snake(game_state, occupancy, &next)
+            {
+                first_step[index(&next)] = Some(step);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CommandId, CustomGameSettings, DeathCause, GameCommandMessage, GameEvent, GameStatus,
+        GameType, QueueMode,
+    };
+    use std::collections::HashMap;
+
+    /// Snake 0 heads left along the top edge toward food at the end of a
+    /// four-cell dead end walled in by snake 1; the way down is open field.
+    fn pocket_game() -> GameState {
+        let mut game = GameState::new(
+            20,
+            20,
+            GameType::Custom {
+                settings: CustomGameSettings::default(),
+            },
+            QueueMode::Quickmatch,
+            Some(3),
+            0,
+        );
+        game.add_player(1, None).unwrap();
+        game.add_player(2, None).unwrap();
+        game.arena.snakes[0].body = vec![Position { x: 4, y: 0 }, Position { x: 7, y: 0 }];
+        game.arena.snakes[0].direction = Direction::Left;
+        game.arena.snakes[1].body = vec![Position { x: 0, y: 1 }, Position { x: 3, y: 1 }];
+        game.arena.snakes[1].direction = Direction::Left;
+        game.arena.food = vec![Position { x: 0, y: 0 }];
+        game.status = GameStatus::Started { server_id: 1 };
+        game.arena.sync_occupancy();
+        game
+    }
+
+    #[test]
+    fn difficulties_parse_from_their_names() {
+        for difficulty in AiDifficulty::ALL {
+            assert_eq!(
+                difficulty.to_string().parse::<AiDifficulty>().unwrap(),
+                difficulty
+            );
+        }
+        assert_eq!(
+            " HARD ".parse::<AiDifficulty>().unwrap(),
+            AiDifficulty::Hard
+        );
+        assert!("insane".parse::<AiDifficulty>().is_err());
+        assert!(is_ai_player(ai_user_id(0).unwrap()));
+        assert!(is_ai_player(ai_user_id(AI_PLAYER_SLOTS - 1).unwrap()));
+        assert!(!is_ai_player(u32::MAX - AI_PLAYER_SLOTS));
+        assert_eq!(ai_user_id(AI_PLAYER_SLOTS), None);
+    }
+
+    #[test]
+    fn only_a_deep_lookahead_stays_out_of_the_dead_end() {
+        let game = pocket_game();
+        for difficulty in [AiDifficulty::Easy, AiDifficulty::Medium] {
+            assert_eq!(
+                calculate_ai_move_at(&game, 0, Direction::Left, difficulty),
+                Some(Direction::Left),
+                "{difficulty} follows the food in"
+            );
+        }
+        assert_eq!(
+            calculate_ai_move_at(&game, 0, Direction::Left, AiDifficulty::Hard),
+            Some(Direction::Down)
+        );
+        assert_eq!(
+            calculate_ai_move(&game, 0, Direction::Left),
+            calculate_ai_move_at(&game, 0, Direction::Left, AiDifficulty::Medium)
+        );
+    }
+
+    #[test]
+    fn tiers_spend_boost_differently() {
+        let mut game = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(5), 0);
+        game.add_player(1, None).unwrap();
+        game.status = GameStatus::Started { server_id: 1 };
+        assert!(game.arena.snakes[0].boost().charge_ms > 0);
+
+        assert!(matches!(
+            calculate_ai_command_at(&game, 0, AiDifficulty::Easy),
+            Some(GameCommand::Turn { .. })
+        ));
+        assert_eq!(
+            calculate_ai_command_at(&game, 0, AiDifficulty::Medium),
+            Some(GameCommand::ActivateBoost { snake_id: 0 })
+        );
+        assert_eq!(
+            calculate_ai_command(&game, 0),
+            calculate_ai_command_at(&game, 0, AiDifficulty::Medium)
+        );
+
+        // Hard boosts only down a clear straight; facing the wall it turns.
+        let snake = &mut game.arena.snakes[0];
+        snake.body = vec![Position { x: 38, y: 20 }, Position { x: 35, y: 20 }];
+        snake.direction = Direction::Right;
+        game.arena.sync_occupancy();
+        assert!(matches!(
+            calculate_ai_command_at(&game, 0, AiDifficulty::Hard),
+            Some(GameCommand::Turn { direction, .. }) if direction != Direction::Right
+        ));
+    }
+
+    fn duel_game() -> GameState {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(11),
+            0,
+        );
+        game.add_player(1, None).unwrap();
+        game.add_player(2, None).unwrap();
+        game.status = GameStatus::Started { server_id: 1 };
+        game
+    }
+
+    #[test]
+    fn adaptive_banking_weighs_the_carry_against_the_trip() {
+        let mut game = duel_game();
+        let home = |carry, distance, game: &GameState| {
+            return_score(game, TeamId(0), carry, distance) >= RETURN_THRESHOLD
+        };
+
+        // Early on, three food is worth banking at the door but not from
+        // across the field, where only a haul is.
+        assert!(home(3, 0, &game));
+        assert!(!home(3, 20, &game));
+        assert!(!home(6, 20, &game));
+        assert!(home(7, 20, &game));
+
+        // Midway a snake banks a little sooner; in the closing stretch a single
+        // food close to home is worth securing.
+        game.team_scores = Some(HashMap::from([(TeamId(0), 10), (TeamId(1), 0)]));
+        assert!(home(6, 20, &game));
+        assert!(!home(1, 5, &game));
+        game.team_scores = Some(HashMap::from([(TeamId(0), 20), (TeamId(1), 0)]));
+        assert!(home(1, 5, &game));
+
+        // When this bank ends the match, any distance is worth it.
+        game.team_scores = Some(HashMap::from([(TeamId(0), 3), (TeamId(1), 0)]));
+        assert!(!home(4, 40, &game));
+        game.team_scores = Some(HashMap::from([(TeamId(0), 21), (TeamId(1), 0)]));
+        assert!(home(4, 40, &game));
+    }
+
+    #[test]
+    fn the_way_home_goes_around_bodies_and_gives_up_when_walled_in() {
+        let mut game = duel_game();
+        game.arena.food.clear();
+        // Snake 0 heads left toward its goal at x=9; snake 1 lies right in
+        // front of the whole goal mouth.
+        game.arena.snakes[0].body = vec![Position { x: 12, y: 20 }, Position { x: 15, y: 20 }];
+        game.arena.snakes[0].direction = Direction::Left;
+        game.arena.snakes[0].food = 5;
+        game.arena.snakes[1].body = vec![Position { x: 10, y: 10 }, Position { x: 10, y: 30 }];
+        game.arena.sync_occupancy();
+
+        let snake = &game.arena.snakes[0];
+        let occupancy = game.arena.occupancy();
+        assert!(path_home(&game, occupancy, snake, TeamId(0)).is_none());
+        assert_eq!(
+            adaptive_return_target(&game, occupancy, snake, TeamId(0)),
+            None,
+            "with no way in, the snake keeps eating"
+        );
+
+        // Leave the lower half of the mouth open and the way in is found.
+        game.arena.snakes[1].body = vec![Position { x: 10, y: 10 }, Position { x: 10, y: 19 }];
+        game.arena.sync_occupancy();
+        let snake = &game.arena.snakes[0];
+        assert_eq!(
+            path_home(&game, game.arena.occupancy(), snake, TeamId(0)),
+            Some((Position { x: 11, y: 20 }, 3))
+        );
+    }
+
+    /// Head-to-head banking evaluations: one side plays `Adaptive`, the other
+    /// the `Threshold` policy it replaced, on the real engine.
+    const BANKING_EVALUATIONS: [&str; 2] = [
+        include_str!("../scenarios/ai-eval/duel-banking.json"),
+        include_str!("../scenarios/ai-eval/2v2-banking.json"),
+    ];
+
+    #[test]
+    fn adaptive_banking_beats_the_threshold_it_replaced() {
+        let swap = |strategy: AiTeamStrategy| match strategy {
+            AiTeamStrategy::Adaptive => AiTeamStrategy::Threshold,
+            AiTeamStrategy::Threshold => AiTeamStrategy::Adaptive,
+        };
+        for fixture in BANKING_EVALUATIONS {
+            let authored = crate::ScenarioScript::from_json(fixture).unwrap();
+            let mut scripts = vec![authored.clone()];
+            // Other food layouts, and each one with the sides swapped, so the
+            // result owes nothing to one lucky seed or the better spawn.
+            for seed in 1..=3 {
+                let mut script = authored.clone();
+                script.world.rng_seed = Some(seed);
+                script.expect.clear();
+                let mut swapped = script.clone();
+                for pose in &mut swapped.pose.snakes {
+                    pose.team_strategy = pose.team_strategy.map(swap);
+                }
+                scripts.extend([script, swapped]);
+            }
+
+            for script in scripts {
+                let adaptive_team = script
+                    .pose
+                    .snakes
+                    .iter()
+                    .find(|pose| pose.team_strategy == Some(AiTeamStrategy::Adaptive))
+                    .and_then(|pose| pose.team_id)
+                    .map(TeamId)
+                    .unwrap();
+                let seed = script.world.rng_seed;
+                let run = script.load().unwrap().run().unwrap();
+                let scores = run.final_state.team_scores.as_ref().unwrap();
+                let limit = run.final_state.properties.score_limit.unwrap();
+                let adaptive = scores[&adaptive_team];
+                let threshold = scores[&TeamId(1 - adaptive_team.0)];
+                assert!(
+                    adaptive >= limit && adaptive > threshold,
+                    "{} seed {seed:?}: adaptive {adaptive}, threshold {threshold}",
+                    authored.id
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn the_search_stays_out_of_the_dead_end_too() {
+        let game = pocket_game();
+        assert_eq!(
+            calculate_search_command(&game, 0, &SearchProfile::default()),
+            Some(GameCommand::Turn {
+                snake_id: 0,
+                direction: Direction::Down,
+            })
+        );
+    }
+
+    #[test]
+    fn the_search_keeps_to_its_budget_and_falls_back_when_it_has_none() {
+        let game = duel_game();
+        let hard = calculate_ai_command_at(&game, 0, AiDifficulty::Hard);
+        let mut profile = SearchProfile::default();
+
+        profile.budget.max_ticks = 5;
+        let starved = search_ai_command(&game, 0, &profile);
+        assert_eq!((starved.rounds, starved.ticks), (0, 0));
+        assert_eq!(starved.command, hard);
+
+        profile.budget.max_ticks = 1_000;
+        let timed_out = search_ai_command(
+            &game,
+            0,
+            &SearchProfile {
+                budget: SearchBudget {
+                    max_ticks: 1_000,
+                    time: Some(std::time::Duration::ZERO),
+                },
+                ..profile
+            },
+        );
+        assert_eq!((timed_out.rounds, timed_out.command), (0, hard));
+
+        let searched = search_ai_command(&game, 0, &profile);
+        assert!(searched.rounds >= 1);
+        assert!(searched.ticks > 0 && searched.ticks <= 1_000);
+        assert!(searched.command.is_some());
+    }
+
+    /// One 2v2 on the real engine with every seat but `seat` on `Hard`, and
+    /// `seat` searching or not. Returns whether its team won and how often
+    /// it died.
+    fn play_2v2(seed: u64, seat: u32, search: bool) -> (bool, u32) {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 2 },
+            QueueMode::Quickmatch,
+            Some(seed),
+            0,
+        );
+        for user_id in 1..=4 {
+            game.add_player(user_id, None).unwrap();
+        }
+        game.spawn_initial_food();
+        game.status = GameStatus::Started { server_id: 1 };
+        let user_id = seat + 1;
+        for other in (1..=4).filter(|other| *other != user_id) {
+            game.ai_players.insert(
+                other,
+                AiSeat {
+                    difficulty: AiDifficulty::Hard,
+                    next_decision_tick: 0,
+                },
+            );
+        }
+
+        let profile = SearchProfile::default();
+        let mut deaths = 0;
+        let mut sequence = 0;
+        while !game.is_complete() && game.tick < 6_000 {
+            if game.tick.is_multiple_of(2) {
+                let command = if search {
+                    calculate_search_command(&game, seat, &profile)
+                } else {
+                    calculate_ai_command_at(&game, seat, AiDifficulty::Hard)
+                };
+                if let Some(command) = command {
+                    sequence += 1;
+                    let id = CommandId {
+                        tick: game.tick,
+                        user_id,
+                        sequence_number: sequence,
+                    };
+                    game.schedule_command(&GameCommandMessage {
+                        command_id_client: id.clone(),
+                        command_id_server: Some(id),
+                        command,
+                    });
+                }
+            }
+            for (_, event) in game.tick_forward(false).unwrap() {
+                if matches!(
+                    event,
+                    GameEvent::SnakeDied { snake_id, cause }
+                        if snake_id == seat && cause != DeathCause::Banked
+                ) {
+                    deaths += 1;
+                }
+            }
+        }
+        let team = game.arena.snakes[seat as usize].team_id.unwrap();
+        let scores = game.team_scores.as_ref().unwrap();
+        let won = scores
+            .iter()
+            .all(|(other, score)| *other == team || *score < scores[&team]);
+        (won, deaths)
+    }
+
+    #[test]
+    fn the_search_carries_a_2v2_the_heuristic_loses() {
+        // Seat 1's team loses both of these with every seat on `Hard`.
+        // Searching in that one seat turns them around without a death.
+        for seed in [2, 3] {
+            assert!(!play_2v2(seed, 1, false).0, "seed {seed}");
+            assert_eq!(play_2v2(seed, 1, true), (true, 0), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn bot_policies_are_found_by_name() {
+        let registry = BotPolicyRegistry::builtin();
+        assert_eq!(
+            registry.names().collect::<Vec<_>>(),
+            ["easy", "hard", "medium", "medium-threshold", "search"]
+        );
+        let game = pocket_game();
+        assert_eq!(
+            registry.get(DEFAULT_BOT_POLICY).unwrap().decide(&game, 0),
+            calculate_ai_command(&game, 0)
+        );
+        assert_eq!(
+            registry.get(" Search ").unwrap().decide(&game, 0),
+            calculate_search_command(&game, 0, &SearchProfile::default())
+        );
+        let error = registry.get("minimax").err().unwrap().to_string();
+        assert!(error.contains("minimax") && error.contains("medium-threshold"));
+
+        // Registering under a taken name replaces what was there.
+        let mut registry = registry;
+        assert!(registry.register("medium", AiDifficulty::Hard).is_some());
+        assert_eq!(
+            registry.get("medium").unwrap().decide(&game, 0),
+            calculate_ai_command_at(&game, 0, AiDifficulty::Hard)
+        );
+    }
+
+    #[test]
+    fn slower_tiers_decide_less_often() {
+        let ticks = |difficulty: AiDifficulty| difficulty.profile().decision_interval_ticks(50);
+        assert_eq!(ticks(AiDifficulty::Hard), 1);
+        assert_eq!(ticks(AiDifficulty::Medium), 2);
+        assert_eq!(ticks(AiDifficulty::Easy), 6);
+    }
+}
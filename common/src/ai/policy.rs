@@ -0,0 +1,113 @@
+//! Bot policies behind one trait, picked by name.
+//!
+//! The bot binary, the scenario runner and the highlight corpus all take a
+//! policy name, so two strategies can be played against each other by
+//! registering both rather than by forking a code path.
+
+use super::{
+    AiDifficulty, AiProfile, AiTeamStrategy, SearchProfile, calculate_ai_command_at,
+    calculate_ai_command_for, calculate_search_command,
+};
+use crate::{GameCommand, GameState};
+use anyhow::{Result, anyhow};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Decides what an AI-driven snake does next.
+///
+/// A policy sees the same `GameState` every engine simulates and answers
+/// with one command. It owns no cadence: callers decide how often to ask,
+/// the way the live bot asks once per decision period.
+pub trait BotPolicy: Send + Sync {
+    /// The command `snake_id` issues now, or `None` when it has nothing to
+    /// do, such as while it is dead.
+    fn decide(&self, game_state: &GameState, snake_id: u32) -> Option<GameCommand>;
+}
+
+impl BotPolicy for AiDifficulty {
+    fn decide(&self, game_state: &GameState, snake_id: u32) -> Option<GameCommand> {
+        calculate_ai_command_at(game_state, snake_id, *self)
+    }
+}
+
+impl BotPolicy for AiProfile {
+    fn decide(&self, game_state: &GameState, snake_id: u32) -> Option<GameCommand> {
+        calculate_ai_command_for(game_state, snake_id, self)
+    }
+}
+
+impl BotPolicy for SearchProfile {
+    fn decide(&self, game_state: &GameState, snake_id: u32) -> Option<GameCommand> {
+        calculate_search_command(game_state, snake_id, self)
+    }
+}
+
+/// The name [`BotPolicyRegistry::builtin`] gives the policy the live bot
+/// plays by default, [`calculate_ai_command`](super::calculate_ai_command).
+pub const DEFAULT_BOT_POLICY: &str = "medium";
+
+/// Bot policies by name. Names are matched without regard to case.
+#[derive(Clone, Default)]
+pub struct BotPolicyRegistry {
+    policies: BTreeMap<String, Arc<dyn BotPolicy>>,
+}
+
+impl BotPolicyRegistry {
+    /// Every policy this crate ships:
+    ///
+    /// - `easy`, `medium` and `hard`: the heuristic at each difficulty;
+    /// - `medium-threshold`: `medium` banking by the `Threshold` strategy it
+    ///   replaced;
+    /// - `search`: [`calculate_search_command`] on its default, tick-only
+    ///   budget, so it is reproducible wherever it runs.
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        for difficulty in AiDifficulty::ALL {
+            registry.register(difficulty.as_str(), difficulty);
+        }
+        registry.register(
+            "medium-threshold",
+            AiProfile {
+                team_strategy: AiTeamStrategy::Threshold,
+                ..AiDifficulty::Medium.profile()
+            },
+        );
+        registry.register("search", SearchProfile::default());
+        registry
+    }
+
+    /// Add `policy` under `name`, replacing and returning whatever was
+    /// registered there before.
+    pub fn register(
+        &mut self,
+        name: &str,
+        policy: impl BotPolicy + 'static,
+    ) -> Option<Arc<dyn BotPolicy>> {
+        self.policies
+            .insert(name.trim().to_ascii_lowercase(), Arc::new(policy))
+    }
+
+    pub fn get(&self, name: &str) -> Result<Arc<dyn BotPolicy>> {
+        self.policies
+            .get(&name.trim().to_ascii_lowercase())
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "unknown bot policy '{name}' (expected one of {})",
+                    self.names().collect::<Vec<_>>().join(", ")
+                )
+            })
+    }
+
+    /// Registered names, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.policies.keys().map(String::as_str)
+    }
+}
+
+impl fmt::Debug for BotPolicyRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
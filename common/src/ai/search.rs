@@ -0,0 +1,501 @@
+//! A policy that looks ahead by playing the real simulation forward.
+//!
+//! Each candidate command is tried on a clone of the match, ticked forward a
+//! few movement steps with every other snake on the heuristic policy. The
+//! opponents closest to the searching snake also try each of their own first
+//! moves, and a candidate is worth its worst reply. Kills, cutoffs and traps
+//! are read off the clone with the same predicates the highlight scorer uses
+//! on a recording, so the bot goes after the plays a highlight would show.
+
+use super::{AiDifficulty, AiSeat, calculate_ai_command_at, field_distance, next_position};
+use crate::highlight::{encloses, lies_across_path, reachable_cells};
+use crate::{
+    CommandId, DeathCause, Direction, GameCommand, GameCommandMessage, GameEvent, GameState,
+    Position,
+};
+use std::time::{Duration, Instant};
+
+/// Opponents that try each of their replies. Every one more triples the
+/// lines a decision plays.
+const MAX_REPLYING_OPPONENTS: usize = 2;
+/// Cells of room the searching snake counts around its head at the end of
+/// a line, and the room below which it counts itself shut in. The figures
+/// are the highlight scorer's trap bounds.
+const ROOM_LIMIT: usize = 64;
+const SHUT_IN_ROOM: usize = 15;
+
+const LOSS: i64 = -100_000;
+const KILL: i64 = 900;
+const TRAP: i64 = 300;
+const CUTOFF: i64 = 60;
+const POINT: i64 = 60;
+const ROOM_CELL: i64 = 2;
+const SHUT_IN: i64 = 2_000;
+/// How much better than the heuristic's own choice another candidate has to
+/// look before the search overrides it. The lines end long before most trips
+/// home do, so small differences are mostly noise about where food is.
+const OVERRIDE_MARGIN: i64 = 150;
+
+/// How much one search decision may spend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchBudget {
+    /// Simulated ticks across every line of one decision. This bound is
+    /// deterministic, so a corpus or replay played under it reproduces.
+    pub max_ticks: u32,
+    /// Wall-clock time for one decision, on top of `max_ticks`. Native
+    /// callers only: reading the clock panics on `wasm32-unknown-unknown`.
+    pub time: Option<Duration>,
+}
+
+/// The knobs of [`calculate_search_command`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchProfile {
+    /// Movement steps each line is played forward.
+    pub horizon_steps: u8,
+    /// Opponents whose head is at most this many steps away try each of
+    /// their replies.
+    pub reply_radius: u16,
+    /// How everyone else is expected to play once a line is under way.
+    pub opponent_model: AiDifficulty,
+    pub budget: SearchBudget,
+}
+
+impl Default for SearchProfile {
+    fn default() -> Self {
+        Self {
+            horizon_steps: 8,
+            reply_radius: 6,
+            opponent_model: AiDifficulty::Medium,
+            budget: SearchBudget {
+                max_ticks: 1_000,
+                time: None,
+            },
+        }
+    }
+}
+
+impl SearchProfile {
+    /// The default search, capped at `time` per decision as well.
+    pub fn timed(time: Duration) -> Self {
+        let mut profile = Self::default();
+        profile.budget.time = Some(time);
+        profile
+    }
+}
+
+/// Choose a command by searching a few steps ahead on clones of
+/// `game_state`.
+///
+/// The search is anytime: every candidate is first played against the
+/// opponent model alone, then against each set of replies in turn, and only
+/// complete rounds count. When the budget does not cover even the first
+/// round the decision falls back to [`calculate_ai_command_at`] at `Hard`,
+/// so a decision is never skipped.
+pub fn calculate_search_command(
+    game_state: &GameState,
+    snake_id: u32,
+    profile: &SearchProfile,
+) -> Option<GameCommand> {
+    search_ai_command(game_state, snake_id, profile).command
+}
+
+/// What one search decision chose and what it cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchDecision {
+    pub command: Option<GameCommand>,
+    /// Reply rounds every candidate was played through; 0 when the budget
+    /// ran out first and the heuristic decided.
+    pub rounds: usize,
+    /// Ticks simulated across every line.
+    pub ticks: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Candidate {
+    Turn(Direction),
+    Boost,
+}
+
+impl Candidate {
+    fn command(self, snake_id: u32) -> GameCommand {
+        match self {
+            Candidate::Turn(direction) => GameCommand::Turn {
+                snake_id,
+                direction,
+            },
+            Candidate::Boost => GameCommand::ActivateBoost { snake_id },
+        }
+    }
+}
+
+/// The ticks and wall clock one decision has left.
+struct Allowance {
+    ticks: u32,
+    deadline: Option<Instant>,
+}
+
+impl Allowance {
+    fn affords(&self, ticks: u32) -> bool {
+        self.ticks >= ticks
+            && self
+                .deadline
+                .is_none_or(|deadline| Instant::now() < deadline)
+    }
+}
+
+/// [`calculate_search_command`], reporting how far the search got.
+pub fn search_ai_command(
+    game_state: &GameState,
+    snake_id: u32,
+    profile: &SearchProfile,
+) -> SearchDecision {
+    let mut allowance = Allowance {
+        ticks: profile.budget.max_ticks,
+        deadline: profile.budget.time.map(|time| Instant::now() + time),
+    };
+    let fallback = |ticks| SearchDecision {
+        command: calculate_ai_command_at(game_state, snake_id, AiDifficulty::Hard),
+        rounds: 0,
+        ticks,
+    };
+    let Some(snake) = game_state
+        .arena
+        .snakes
+        .get(snake_id as usize)
+        .filter(|snake| snake.is_alive)
+    else {
+        return fallback(0);
+    };
+    let Ok(head) = snake.head().copied() else {
+        return fallback(0);
+    };
+
+    // The heuristic's own choice goes first so that it wins every tie.
+    let mut candidates = Vec::with_capacity(5);
+    if let Some(command) = calculate_ai_command_at(game_state, snake_id, AiDifficulty::Hard) {
+        match command {
+            GameCommand::Turn { direction, .. } => candidates.push(Candidate::Turn(direction)),
+            GameCommand::ActivateBoost { .. } => candidates.push(Candidate::Boost),
+            _ => {}
+        }
+    }
+    for direction in legal_directions(game_state, snake_id) {
+        if !candidates.contains(&Candidate::Turn(direction)) {
+            candidates.push(Candidate::Turn(direction));
+        }
+    }
+    let can_boost = game_state.properties.boost.is_some()
+        && snake.boost().charge_ms > 0
+        && !snake.boost().active;
+    if can_boost && !candidates.contains(&Candidate::Boost) {
+        candidates.push(Candidate::Boost);
+    }
+
+    let step_ticks = game_state
+        .normal_movement_interval_ms()
+        .div_ceil(game_state.properties.tick_duration_ms.max(1))
+        .max(1);
+    let horizon_ticks = step_ticks * u32::from(profile.horizon_steps.max(1));
+
+    // Round 0 leaves every opponent on the model; each later round pins the
+    // first move of the nearest opponents to one combination.
+    let opponents = replying_opponents(game_state, snake_id, head, profile.reply_radius);
+    let mut combinations: Vec<Vec<(u32, Direction)>> = vec![Vec::new()];
+    for &opponent in &opponents {
+        let replies = legal_directions(game_state, opponent);
+        combinations = combinations
+            .into_iter()
+            .flat_map(|pinned| {
+                replies.iter().map(move |&direction| {
+                    let mut pinned = pinned.clone();
+                    pinned.push((opponent, direction));
+                    pinned
+                })
+            })
+            .collect();
+    }
+    let mut rounds = vec![Vec::new()];
+    if !opponents.is_empty() {
+        rounds.extend(combinations);
+    }
+
+    let mut values: Option<Vec<i64>> = None;
+    let mut completed = 0;
+    let mut spent = 0;
+    'rounds: for replies in &rounds {
+        let mut round = Vec::with_capacity(candidates.len());
+        for &candidate in &candidates {
+            if !allowance.affords(horizon_ticks) {
+                break 'rounds;
+            }
+            let (value, ticks) = play_line(
+                game_state,
+                snake_id,
+                candidate,
+                replies,
+                horizon_ticks,
+                profile.opponent_model,
+            );
+            allowance.ticks -= ticks;
+            spent += ticks;
+            round.push(value);
+        }
+        values = Some(match values {
+            None => round,
+            Some(worst) => worst
+                .into_iter()
+                .zip(round)
+                .map(|(worst, value)| worst.min(value))
+                .collect(),
+        });
+        completed += 1;
+    }
+
+    let Some(values) = values else {
+        return fallback(spent);
+    };
+    let mut best = 0;
+    for (index, value) in values.iter().enumerate().skip(1) {
+        let bar = if best == 0 {
+            values[0] + OVERRIDE_MARGIN
+        } else {
+            values[best]
+        };
+        if *value > bar {
+            best = index;
+        }
+    }
+    SearchDecision {
+        command: Some(candidates[best].command(snake_id)),
+        rounds: completed,
+        ticks: spent,
+    }
+}
+
+/// Directions `snake_id` may turn to without reversing or leaving a solid
+/// field.
+fn legal_directions(game_state: &GameState, snake_id: u32) -> Vec<Direction> {
+    let Some(snake) = game_state.arena.snakes.get(snake_id as usize) else {
+        return Vec::new();
+    };
+    let Ok(head) = snake.head() else {
+        return Vec::new();
+    };
+    let width = game_state.arena.width as i16;
+    let height = game_state.arena.height as i16;
+    [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ]
+    .into_iter()
+    .filter(|direction| {
+        !snake.direction.is_opposite(direction)
+            && !snake.travel_direction().is_opposite(direction)
+            && next_position(game_state, head, *direction, width, height).is_some()
+    })
+    .collect()
+}
+
+/// Living opponents whose head is within `radius` steps of `head`, nearest
+/// first.
+fn replying_opponents(
+    game_state: &GameState,
+    snake_id: u32,
+    head: Position,
+    radius: u16,
+) -> Vec<u32> {
+    let team_id = game_state.arena.snakes[snake_id as usize].team_id;
+    let mut opponents = game_state
+        .arena
+        .snakes
+        .iter()
+        .enumerate()
+        .filter(|(id, snake)| {
+            *id as u32 != snake_id
+                && snake.is_alive
+                && (team_id.is_none() || snake.team_id != team_id)
+        })
+        .filter_map(|(id, snake)| {
+            let distance = field_distance(game_state, &head, snake.head().ok()?);
+            (distance <= radius).then_some((distance, id as u32))
+        })
+        .collect::<Vec<_>>();
+    opponents.sort_unstable();
+    opponents
+        .into_iter()
+        .take(MAX_REPLYING_OPPONENTS)
+        .map(|(_, id)| id)
+        .collect()
+}
+
+/// Play `candidate` forward on a clone of `game_state` and score where it
+/// leads. Returns the value and the ticks simulated.
+fn play_line(
+    game_state: &GameState,
+    snake_id: u32,
+    candidate: Candidate,
+    replies: &[(u32, Direction)],
+    horizon_ticks: u32,
+    opponent_model: AiDifficulty,
+) -> (i64, u32) {
+    let mut state = game_state.clone();
+    let start_lead = lead(&state, snake_id);
+
+    // Everyone plays as an AI seat. The snakes whose first move this line
+    // fixes hold it until they have made it, then join in.
+    let mut pinned = Vec::with_capacity(replies.len() + 1);
+    let mut players = state
+        .players
+        .iter()
+        .map(|(user_id, player)| (*user_id, player.snake_id))
+        .collect::<Vec<_>>();
+    players.sort_unstable();
+    for (user_id, id) in players {
+        let difficulty = if id == snake_id {
+            AiDifficulty::Hard
+        } else {
+            opponent_model
+        };
+        let seat = AiSeat {
+            difficulty,
+            next_decision_tick: state.tick,
+        };
+        let first_move = if id == snake_id {
+            Some(candidate)
+        } else {
+            replies
+                .iter()
+                .find(|(replier, _)| *replier == id)
+                .map(|(_, direction)| Candidate::Turn(*direction))
+        };
+        let Some(first_move) = first_move else {
+            state.ai_players.insert(user_id, seat);
+            continue;
+        };
+        state.ai_players.remove(&user_id);
+        match first_move {
+            Candidate::Turn(direction) => state.arena.snakes[id as usize].direction = direction,
+            Candidate::Boost => {
+                let command_id = CommandId {
+                    tick: state.tick,
+                    user_id,
+                    sequence_number: u32::MAX,
+                };
+                state.schedule_command(&GameCommandMessage {
+                    command_id_client: command_id.clone(),
+                    command_id_server: Some(command_id),
+                    command: GameCommand::ActivateBoost { snake_id: id },
+                });
+            }
+        }
+        let head = state.arena.snakes[id as usize].head().ok().copied();
+        pinned.push((user_id, id, head, seat));
+    }
+
+    let mut kills = 0;
+    let mut ticks = 0;
+    while ticks < horizon_ticks && !state.is_complete() {
+        let Ok(events) = state.tick_forward(false) else {
+            break;
+        };
+        ticks += 1;
+        for (_, event) in &events {
+            let GameEvent::SnakeDied {
+                snake_id: victim,
+                cause,
+            } = event
+            else {
+                continue;
+            };
+            if *victim == snake_id {
+                if !matches!(cause, DeathCause::Banked) {
+                    return (LOSS + i64::from(ticks) * 10, ticks);
+                }
+                continue;
+            }
+            let killer = match cause {
+                DeathCause::SnakeBody { killer_snake_id } => Some(*killer_snake_id),
+                DeathCause::HeadToHead { other_snake_id } => Some(*other_snake_id),
+                _ => None,
+            };
+            if killer == Some(snake_id) {
+                kills += if same_team(&state, snake_id, *victim) {
+                    -1
+                } else {
+                    1
+                };
+            }
+        }
+        pinned.retain(|(user_id, id, head, seat)| {
+            let snake = &state.arena.snakes[*id as usize];
+            if snake.is_alive && snake.head().ok().copied() == *head {
+                return true;
+            }
+            state.ai_players.insert(*user_id, *seat);
+            false
+        });
+    }
+
+    let mut value = kills * KILL + (lead(&state, snake_id) - start_lead) * POINT;
+    for (id, snake) in state.arena.snakes.iter().enumerate() {
+        let id = id as u32;
+        if id == snake_id || !snake.is_alive || same_team(&state, snake_id, id) {
+            continue;
+        }
+        if encloses(&state, snake_id, id) {
+            value += TRAP;
+        } else if lies_across_path(&state, snake_id, id) {
+            value += CUTOFF;
+        }
+    }
+    let room = reachable_cells(&state, snake_id, ROOM_LIMIT).len();
+    value += room as i64 * ROOM_CELL;
+    if room < SHUT_IN_ROOM {
+        value -= SHUT_IN;
+    }
+    (value, ticks)
+}
+
+/// How far `snake_id`'s side is ahead of the best of the others.
+fn lead(state: &GameState, snake_id: u32) -> i64 {
+    let best_other = (0..state.arena.snakes.len() as u32)
+        .filter(|id| *id != snake_id && !same_team(state, snake_id, *id))
+        .map(|id| side_points(state, id))
+        .max()
+        .unwrap_or_default();
+    side_points(state, snake_id) - best_other
+}
+
+/// What a snake's side has to show: its team's banked score plus what the
+/// team's living snakes carry, or the snake's own score outside team play.
+fn side_points(state: &GameState, snake_id: u32) -> i64 {
+    let Some(snake) = state.arena.snakes.get(snake_id as usize) else {
+        return 0;
+    };
+    match (&state.team_scores, snake.team_id) {
+        (Some(team_scores), Some(team_id)) => {
+            let carried: u32 = state
+                .arena
+                .snakes
+                .iter()
+                .filter(|snake| snake.is_alive && snake.team_id == Some(team_id))
+                .map(|snake| state.carried_food(snake))
+                .sum();
+            i64::from(team_scores.get(&team_id).copied().unwrap_or_default()) + i64::from(carried)
+        }
+        _ => i64::from(state.scores.get(&snake_id).copied().unwrap_or_default()),
+    }
+}
+
+fn same_team(state: &GameState, left: u32, right: u32) -> bool {
+    let team = |id: u32| {
+        state
+            .arena
+            .snakes
+            .get(id as usize)
+            .and_then(|snake| snake.team_id)
+    };
+    team(left).is_some() && team(left) == team(right)
+}
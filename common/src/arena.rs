@@ -1 +1,436 @@
+//! Data-driven arena maps.
+//!
+//! An `ArenaMap` describes everything about a playing field that is fixed for
+//! the whole match: its size, the team end-zone walls, interior obstacle
+//! blocks, spawn points and where Boost pads sit. Maps are plain JSON so a new
+//! rotation is a data change, not an engine change; the built-in set lives in
+//! `common/maps/` and is compiled in so the server, WASM client and offline
+//! tools always agree on the exact geometry a map id names.
+//!
+//! The two classic maps predate this format. Their geometry is what every
+//! historical snapshot already encodes implicitly (dimensions plus
+//! `team_zone_config`), so `Arena` stores a map only when it differs from that
+//! implicit one. That keeps every pre-map snapshot, recording and fingerprint
+//! byte-identical while still letting `boost_config_for` key on a map.
 
+use crate::constants::{
+    ARENA_MAP_FORMAT_VERSION, BOOST_SPOT_LAYOUT_VERSION_FIELD, BOOST_SPOT_LAYOUT_VERSION_TEAM,
+};
+use crate::game_state::{GameType, TeamId, TeamZoneConfig};
+use crate::snake::{Direction, Position};
+use anyhow::{Context, Result, ensure};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+/// Source of every built-in map, in registry order.
+const BUILTIN_ARENA_MAP_SOURCES: &[&str] = &[
+    include_str!("../maps/classic-team.json"),
+    include_str!("../maps/classic-field.json"),
+    include_str!("../maps/crossroads.json"),
+];
+
+/// Id of the open map synthesized for dimensions no built-in map covers.
+pub const OPEN_ARENA_MAP_ID: &str = "open";
+
+/// A spawn's body is laid out behind its head at this length when a map is
+/// validated. Custom games may start longer; their spawns are still checked
+/// against the real length when a snake is placed.
+const MAP_VALIDATION_SNAKE_LENGTH: i16 = 4;
+
+/// A solid rectangle of cells. Thin blocks are walls; there is no separate
+/// wall primitive because collision treats both identically.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct ObstacleBlock {
+    /// Top-left cell of the block.
+    pub position: Position,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl ObstacleBlock {
+    pub fn contains(&self, position: &Position) -> bool {
+        let x = i32::from(position.x) - i32::from(self.position.x);
+        let y = i32::from(position.y) - i32::from(self.position.y);
+        x >= 0 && y >= 0 && x < i32::from(self.width) && y < i32::from(self.height)
+    }
+
+    /// Every covered cell in stable row-major order.
+    pub fn cells(&self) -> Vec<Position> {
+        let mut cells = Vec::with_capacity(usize::from(self.width) * usize::from(self.height));
+        for y_offset in 0..self.height {
+            for x_offset in 0..self.width {
+                let x = i32::from(self.position.x) + i32::from(x_offset);
+                let y = i32::from(self.position.y) + i32::from(y_offset);
+                if let (Ok(x), Ok(y)) = (i16::try_from(x), i16::try_from(y)) {
+                    cells.push(Position { x, y });
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Where a snake's head starts a life, and which way it faces. The body is
+/// laid out straight behind the head.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct SpawnPoint {
+    pub position: Position,
+    pub direction: Direction,
+    /// Restricts the spawn to one team. `None` spawns anybody, which is what
+    /// teamless maps use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<TeamId>,
+}
+
+/// One explicitly placed Boost pad. A 2x2 pad holds a full tank and a 1x1
+/// packet a quarter, matching the convention of the drawn layouts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct MapBoostPad {
+    /// Top-left cell of the square footprint.
+    pub position: Position,
+    pub size_cells: u8,
+}
+
+/// How a map provides Boost pickups.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MapBoostPlacement {
+    /// No pickups. Collectible Boost modes play without Boost here; Solo's
+    /// unlimited tank needs no pads and is unaffected.
+    #[default]
+    None,
+    /// One of the procedurally drawn canonical layouts, by layout version.
+    Layout { spot_layout_version: u16 },
+    /// Pads at exactly these cells, in pad-id order
+    /// (`BOOST_SPOT_LAYOUT_VERSION_MAP`).
+    Pads { pads: Vec<MapBoostPad> },
+}
+
+/// A versioned, self-contained arena description. See the module docs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+#[serde(deny_unknown_fields)]
+pub struct ArenaMap {
+    pub format_version: u16,
+    pub id: String,
+    /// Content revision. Any geometry change to a published map bumps it so a
+    /// persisted match can always be matched to the layout it was played on.
+    pub revision: u16,
+    pub width: u16,
+    pub height: u16,
+    /// End zones and their goal walls. Present exactly on team maps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team_zones: Option<TeamZoneConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub obstacles: Vec<ObstacleBlock>,
+    /// Starting cells in assignment order. Empty means the engine's computed
+    /// formation; a match with more snakes than spawns also falls back to it
+    /// for the extra snakes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawn_points: Vec<SpawnPoint>,
+    #[serde(default)]
+    pub boost: MapBoostPlacement,
+}
+
+impl ArenaMap {
+    /// Parse and validate a map document.
+    pub fn from_json(source: &str) -> Result<Self> {
+        let map: ArenaMap = serde_json::from_str(source).context("invalid arena map JSON")?;
+        map.validate()
+            .with_context(|| format!("invalid arena map {:?}", map.id))?;
+        Ok(map)
+    }
+
+    /// Every built-in map, in registry order.
+    pub fn builtins() -> &'static [ArenaMap] {
+        static BUILTINS: OnceLock<Vec<ArenaMap>> = OnceLock::new();
+        BUILTINS.get_or_init(|| {
+            BUILTIN_ARENA_MAP_SOURCES
+                .iter()
+                .map(|source| {
+                    ArenaMap::from_json(source).expect("built-in arena map must be valid")
+                })
+                .collect()
+        })
+    }
+
+    pub fn builtin(id: &str) -> Option<&'static ArenaMap> {
+        Self::builtins().iter().find(|map| map.id == id)
+    }
+
+    /// The map a mode gets on the given dimensions when nobody picked one:
+    /// the classic map if these are its dimensions, an open field otherwise.
+    /// `GameState::new` uses this, so callers that only know a size keep the
+    /// exact arenas they always had.
+    pub fn default_for(game_type: &GameType, width: u16, height: u16) -> ArenaMap {
+        Self::implicit(width, height, default_team_zones(game_type, height))
+    }
+
+    /// The map a snapshot without an explicit map describes. A built-in map
+    /// with no obstacles or spawns is fully determined by its size and zones,
+    /// which is exactly what such a snapshot records.
+    pub fn implicit(width: u16, height: u16, team_zones: Option<TeamZoneConfig>) -> ArenaMap {
+        Self::builtins()
+            .iter()
+            .find(|map| {
+                map.width == width
+                    && map.height == height
+                    && map.team_zones == team_zones
+                    && map.obstacles.is_empty()
+                    && map.spawn_points.is_empty()
+            })
+            .cloned()
+            .unwrap_or_else(|| ArenaMap {
+                format_version: ARENA_MAP_FORMAT_VERSION,
+                id: OPEN_ARENA_MAP_ID.to_string(),
+                revision: 1,
+                width,
+                height,
+                team_zones,
+                obstacles: Vec::new(),
+                spawn_points: Vec::new(),
+                boost: MapBoostPlacement::None,
+            })
+    }
+
+    /// Whether `Arena` can represent this map without storing it.
+    pub fn is_implicit(&self) -> bool {
+        *self == Self::implicit(self.width, self.height, self.team_zones.clone())
+    }
+
+    pub fn is_obstacle_position(&self, position: &Position) -> bool {
+        self.obstacles.iter().any(|block| block.contains(position))
+    }
+
+    fn in_bounds(&self, position: &Position) -> bool {
+        position.x >= 0
+            && position.y >= 0
+            && i32::from(position.x) < i32::from(self.width)
+            && i32::from(position.y) < i32::from(self.height)
+    }
+
+    /// Structural checks that need nothing but the map itself. Whether a
+    /// drawn Boost layout actually fits is answered by `GameState` validation,
+    /// which owns the layout geometry.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.format_version == ARENA_MAP_FORMAT_VERSION,
+            "unsupported arena map format {}, expected {}",
+            self.format_version,
+            ARENA_MAP_FORMAT_VERSION
+        );
+        ensure!(!self.id.is_empty(), "arena map id must not be empty");
+        ensure!(
+            (1..=i16::MAX as u16).contains(&self.width)
+                && (1..=i16::MAX as u16).contains(&self.height),
+            "arena map dimensions {}x{} are out of range",
+            self.width,
+            self.height
+        );
+        if let Some(zones) = &self.team_zones {
+            ensure!(
+                zones.end_zone_depth > 0
+                    && u32::from(zones.end_zone_depth) * 2 < u32::from(self.width),
+                "team end zones must leave a main field"
+            );
+            ensure!(
+                zones.goal_width > 0 && zones.goal_width <= self.height,
+                "goal width must fit the arena height"
+            );
+        }
+
+        for block in &self.obstacles {
+            ensure!(
+                block.width > 0 && block.height > 0,
+                "obstacle blocks must cover at least one cell"
+            );
+            let cells = block.cells();
+            ensure!(
+                cells.len() == usize::from(block.width) * usize::from(block.height)
+                    && cells.iter().all(|cell| self.in_bounds(cell)),
+                "obstacle block at ({}, {}) leaves the arena",
+                block.position.x,
+                block.position.y
+            );
+        }
+
+        for spawn in &self.spawn_points {
+            let (dx, dy) = match spawn.direction {
+                Direction::Up => (0, 1),
+                Direction::Down => (0, -1),
+                Direction::Left => (1, 0),
+                Direction::Right => (-1, 0),
+            };
+            for offset in 0..MAP_VALIDATION_SNAKE_LENGTH {
+                let cell = Position {
+                    x: spawn.position.x.saturating_add(dx * offset),
+                    y: spawn.position.y.saturating_add(dy * offset),
+                };
+                ensure!(
+                    self.in_bounds(&cell) && !self.is_obstacle_position(&cell),
+                    "spawn at ({}, {}) does not fit a starting snake",
+                    spawn.position.x,
+                    spawn.position.y
+                );
+            }
+            if let Some(team_id) = spawn.team_id {
+                ensure!(
+                    self.team_zones.is_some() && team_id.0 < 2,
+                    "team spawns need a team map with teams 0 and 1"
+                );
+            }
+        }
+
+        match &self.boost {
+            MapBoostPlacement::None => {}
+            MapBoostPlacement::Layout {
+                spot_layout_version,
+            } => ensure!(
+                matches!(
+                    *spot_layout_version,
+                    BOOST_SPOT_LAYOUT_VERSION_TEAM | BOOST_SPOT_LAYOUT_VERSION_FIELD
+                ),
+                "arena maps may only draw Boost layout {} or {}, got {}",
+                BOOST_SPOT_LAYOUT_VERSION_TEAM,
+                BOOST_SPOT_LAYOUT_VERSION_FIELD,
+                spot_layout_version
+            ),
+            MapBoostPlacement::Pads { pads } => {
+                ensure!(
+                    !pads.is_empty() && pads.len() <= usize::from(u8::MAX),
+                    "placed Boost layouts need between 1 and {} pads",
+                    u8::MAX
+                );
+                let mut footprint = HashSet::new();
+                for pad in pads {
+                    ensure!(
+                        matches!(pad.size_cells, 1 | 2),
+                        "placed Boost pads must be 1x1 or 2x2"
+                    );
+                    for y in 0..i16::from(pad.size_cells) {
+                        for x in 0..i16::from(pad.size_cells) {
+                            let cell = Position {
+                                x: pad.position.x.saturating_add(x),
+                                y: pad.position.y.saturating_add(y),
+                            };
+                            ensure!(
+                                self.in_bounds(&cell) && !self.is_obstacle_position(&cell),
+                                "Boost pad at ({}, {}) must sit on open floor",
+                                pad.position.x,
+                                pad.position.y
+                            );
+                            ensure!(footprint.insert(cell), "placed Boost pads must not overlap");
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// End zones a mode gets on an arena of this height. Team matches score by
+/// carrying food home, so they always have zones. King of the hill plays on
+/// the same field, with the zones as each side's spawn, and capture the flag
+/// keeps each side's flag in them; no other mode has them.
+pub fn default_team_zones(game_type: &GameType, height: u16) -> Option<TeamZoneConfig> {
+    match game_type {
+        GameType::TeamMatch { .. }
+        | GameType::KingOfTheHill { .. }
+        | GameType::CaptureTheFlag { .. } => {
+            // Calculate goal width as 20% of arena height
+            let goal_width = ((height as f32 * 0.2).round() as u16).max(3);
+            // Make sure it's odd for symmetry
+            let goal_width = if goal_width.is_multiple_of(2) {
+                goal_width + 1
+            } else {
+                goal_width
+            };
+
+            Some(TeamZoneConfig {
+                end_zone_depth: 10,
+                goal_width,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_maps_parse_and_validate() {
+        let ids: Vec<&str> = ArenaMap::builtins()
+            .iter()
+            .map(|map| map.id.as_str())
+            .collect();
+        assert_eq!(ids, ["classic-team", "classic-field", "crossroads"]);
+    }
+
+    #[test]
+    fn canonical_dimensions_resolve_to_the_classic_maps() {
+        let team = ArenaMap::default_for(&GameType::TeamMatch { per_team: 1 }, 60, 40);
+        assert_eq!(team.id, "classic-team");
+        assert!(team.is_implicit());
+        let field = ArenaMap::default_for(&GameType::FreeForAll { max_players: 4 }, 40, 40);
+        assert_eq!(field.id, "classic-field");
+        // The same square arena with end zones is not the field map.
+        let open = ArenaMap::default_for(&GameType::TeamMatch { per_team: 1 }, 40, 40);
+        assert_eq!(open.id, OPEN_ARENA_MAP_ID);
+        assert_eq!(open.boost, MapBoostPlacement::None);
+        assert!(open.is_implicit());
+        assert!(!ArenaMap::builtin("crossroads").unwrap().is_implicit());
+    }
+
+    #[test]
+    fn validation_rejects_spawns_inside_obstacles() {
+        let mut map = ArenaMap::builtin("crossroads").unwrap().clone();
+        map.spawn_points[0].position = Position { x: 9, y: 9 };
+        assert!(map.validate().is_err());
+    }
+
+    #[test]
+    fn validation_rejects_pads_on_obstacles_and_overlaps() {
+        let mut map = ArenaMap::builtin("crossroads").unwrap().clone();
+        let MapBoostPlacement::Pads { pads } = &mut map.boost else {
+            panic!("crossroads places its pads explicitly");
+        };
+        pads[4].position = Position { x: 8, y: 8 };
+        assert!(map.validate().is_err());
+
+        let mut map = ArenaMap::builtin("crossroads").unwrap().clone();
+        let MapBoostPlacement::Pads { pads } = &mut map.boost else {
+            unreachable!();
+        };
+        pads[4].position = Position { x: 4, y: 4 };
+        assert!(map.validate().is_err());
+    }
+
+    #[test]
+    fn unknown_fields_and_formats_are_rejected() {
+        assert!(
+            ArenaMap::from_json(
+                r#"{"format_version":1,"id":"x","revision":1,"width":10,"height":10,"lava":[]}"#
+            )
+            .is_err()
+        );
+        assert!(
+            ArenaMap::from_json(
+                r#"{"format_version":9,"id":"x","revision":1,"width":10,"height":10}"#
+            )
+            .is_err()
+        );
+    }
+}
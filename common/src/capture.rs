@@ -0,0 +1,447 @@
+//! Turning recorded games into scenario scripts.
+//!
+//! A [`GameRecordingV1`] or a sync trace holds everything needed to play a
+//! match again. [`scenario_from_recording`] poses the match as it stood at a
+//! chosen tick and scripts the commands players sent after it, so a report
+//! or a highlight can be replayed and edited with the scenario tooling. The
+//! script is run before it is returned and must end on the recording's own
+//! `FinalSyncHash`.
+
+use crate::replay::{require_current_trace_format, trace_side};
+use crate::trace::{TraceRecord, TraceSide};
+use crate::{
+    GAME_RECORDING_FORMAT_VERSION, GAMEPLAY_REPLAY_VERSION, GameCommand, GameEvent,
+    GameRecordingV1, GameState, RecordedGameMessage, ReplayAnchor, ReplayVisibility,
+    SCENARIO_FORMAT_VERSION, ScenarioCommand, ScenarioCommandKind, ScenarioExpectation,
+    ScenarioOverrides, ScenarioPose, ScenarioPresentation, ScenarioScript, ScenarioWorld,
+};
+use anyhow::{Context, Result, bail, ensure};
+use std::collections::BTreeMap;
+
+/// Script `recording` from `at_tick` to its end, as scenario `id`.
+pub fn scenario_from_recording(
+    recording: &GameRecordingV1,
+    at_tick: u32,
+    id: &str,
+) -> Result<ScenarioScript> {
+    recording.validate()?;
+    ensure!(
+        at_tick < recording.end_tick,
+        "capture tick {at_tick} must be before the recording's end tick {}",
+        recording.end_tick
+    );
+    let anchor = recording
+        .anchors
+        .iter()
+        .rev()
+        .find(|anchor| anchor.tick <= at_tick)
+        .context("no replay anchor at or before the capture tick")?;
+    let first = recording.messages.partition_point(|message| {
+        (message.tick, message.sequence) <= (anchor.tick, anchor.sequence)
+    });
+    let commands = recorded_commands(&recording.messages[first..], recording.end_tick)?;
+
+    // Reach the capture tick by simulating from the anchor rather than by
+    // applying replicated events: only the simulation carries the RNG and
+    // timers forward the way the server did.
+    let mut playback = captured_script(id, &anchor.state, &commands, recording)
+        .load()
+        .context("recording anchor cannot be played as a scenario")?
+        .playback();
+    playback.seek_to_tick(at_tick)?;
+    ensure!(
+        playback.state().tick == at_tick,
+        "the game ended at tick {} before the capture tick {at_tick}",
+        playback.state().tick
+    );
+
+    let script = captured_script(id, playback.state(), &commands, recording);
+    script
+        .clone()
+        .load()?
+        .run()
+        .context("captured scenario does not reproduce the recording's FinalSyncHash")?;
+    Ok(script)
+}
+
+/// Rebuild a recording from a sync trace: its first `State` is the anchor,
+/// the messages it published (server) or received (client) are the stream,
+/// and its last `Fingerprint` is the end the replay must reach.
+pub fn recording_from_trace(records: &[TraceRecord]) -> Result<GameRecordingV1> {
+    require_current_trace_format(records)?;
+    let side = trace_side(records).context("trace has no Meta record")?;
+    let game_id = records
+        .iter()
+        .find_map(|record| match record {
+            TraceRecord::Meta { game_id, .. } => Some(*game_id),
+            _ => None,
+        })
+        .context("trace has no Meta record")?;
+
+    let mut anchor: Option<ReplayAnchor> = None;
+    let mut applied_sequence = 0;
+    let mut messages = BTreeMap::new();
+    let mut end = None;
+    for record in records {
+        let message = match (record, side) {
+            (TraceRecord::State { state, .. }, _) => {
+                if anchor.is_none() {
+                    anchor = Some(ReplayAnchor {
+                        tick: state.tick,
+                        sequence: applied_sequence,
+                        state: (**state).clone(),
+                    });
+                }
+                continue;
+            }
+            (TraceRecord::Fingerprint { tick, hash, .. }, _) if anchor.is_some() => {
+                end = Some((*tick, *hash));
+                continue;
+            }
+            (TraceRecord::EventOut { msg, .. }, TraceSide::Server)
+            | (TraceRecord::EventIn { msg, .. }, TraceSide::Client) => msg,
+            _ => continue,
+        };
+        match &message.event {
+            GameEvent::Snapshot { game_state } => {
+                if anchor.is_none() && side == TraceSide::Client {
+                    anchor = Some(ReplayAnchor {
+                        tick: game_state.tick,
+                        sequence: message.sequence,
+                        state: game_state.clone(),
+                    });
+                }
+            }
+            GameEvent::SnapshotDelta { .. } => {}
+            event if anchor.is_some() => {
+                // Per-user copies and redeliveries share one stream position.
+                messages
+                    .entry((message.tick, message.sequence))
+                    .or_insert_with(|| event.clone());
+            }
+            _ => applied_sequence = applied_sequence.max(message.sequence),
+        }
+    }
+    let anchor = anchor.context("trace has no State record to anchor the recording")?;
+    let (end_tick, end_sync_hash) =
+        end.context("trace has no Fingerprint after its anchor to end the recording on")?;
+    ensure!(
+        end_tick >= anchor.tick,
+        "trace's last Fingerprint at tick {end_tick} precedes its anchor at tick {}",
+        anchor.tick
+    );
+
+    let recording = GameRecordingV1 {
+        format_version: GAME_RECORDING_FORMAT_VERSION,
+        gameplay_version: GAMEPLAY_REPLAY_VERSION,
+        game_id,
+        visibility: ReplayVisibility::Private,
+        messages: messages
+            .into_iter()
+            .filter(|((tick, _), _)| *tick >= anchor.tick && *tick <= end_tick)
+            .map(|((tick, sequence), event)| RecordedGameMessage {
+                tick,
+                sequence,
+                event,
+            })
+            .collect(),
+        anchors: vec![anchor],
+        end_tick,
+        end_sync_hash,
+        keyframes: Vec::new(),
+    };
+    recording.validate()?;
+    Ok(recording)
+}
+
+/// Player commands in `messages`, each scheduled at the tick it reached the
+/// server, in the order the command queue runs them.
+fn recorded_commands(
+    messages: &[RecordedGameMessage],
+    end_tick: u32,
+) -> Result<Vec<ScenarioCommand>> {
+    let mut commands = Vec::new();
+    for message in messages {
+        let command_message = match &message.event {
+            GameEvent::CommandScheduled { command_message }
+            | GameEvent::CommandScheduledV2 {
+                command_message,
+                deduplicated_replay: false,
+                ..
+            } => command_message,
+            _ => continue,
+        };
+        let id = command_message.id();
+        let command = match &command_message.command {
+            GameCommand::Turn { direction, .. } => ScenarioCommandKind::Turn(*direction),
+            GameCommand::ActivateBoost { .. } => ScenarioCommandKind::ActivateBoost,
+            GameCommand::DeactivateBoost { .. } => ScenarioCommandKind::DeactivateBoost,
+            GameCommand::PlayerActivity { .. } => ScenarioCommandKind::PlayerActivity,
+            // Status updates only mark a failover and change no gameplay.
+            GameCommand::UpdateStatus { .. } => continue,
+        };
+        // A command queued for a tick the recording never reaches cannot
+        // change how it ends.
+        if id.tick >= end_tick {
+            continue;
+        }
+        if id.tick < message.tick {
+            bail!(
+                "command {id:?} was scheduled at tick {}, after its own tick",
+                message.tick
+            );
+        }
+        commands.push((
+            id.clone(),
+            ScenarioCommand {
+                at_tick: id.tick,
+                user_id: id.user_id,
+                command,
+                scheduled_at: Some(message.tick),
+            },
+        ));
+    }
+    // The loader numbers commands in the order given, so keep the queue's.
+    commands.sort_by(|(left, _), (right, _)| left.cmp(right));
+    Ok(commands.into_iter().map(|(_, command)| command).collect())
+}
+
+/// A script playing the recording's commands from `state` to its end.
+fn captured_script(
+    id: &str,
+    state: &GameState,
+    commands: &[ScenarioCommand],
+    recording: &GameRecordingV1,
+) -> ScenarioScript {
+    ScenarioScript {
+        format_version: SCENARIO_FORMAT_VERSION,
+        id: id.to_string(),
+        world: ScenarioWorld {
+            game_type: state.game_type.clone(),
+            queue_mode: state.queue_mode.clone(),
+            rng_seed: None,
+            arena_width: Some(state.arena.width),
+            arena_height: Some(state.arena.height),
+            overrides: ScenarioOverrides::default(),
+        },
+        pose: ScenarioPose::capture(state),
+        // Anything queued earlier is already in the captured state.
+        commands: commands
+            .iter()
+            .filter(|command| {
+                command.scheduled_at.unwrap_or(command.at_tick) >= state.tick
+                    && state.players.contains_key(&command.user_id)
+            })
+            .cloned()
+            .collect(),
+        run_ticks: recording.end_tick - state.tick,
+        presentation: ScenarioPresentation::default(),
+        expect: vec![ScenarioExpectation::FinalSyncHash(format!(
+            "{:#x}",
+            recording.end_sync_hash
+        ))],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CommandId, Direction, GameCommandMessage, GameEventMessage, GameType, QueueMode};
+
+    /// Play a seeded free-for-all the way the server does, recording every
+    /// command as it is scheduled and every event the tick produces.
+    fn recorded_game() -> GameRecordingV1 {
+        let mut state = GameState::new(
+            40,
+            40,
+            GameType::FreeForAll { max_players: 2 },
+            QueueMode::Quickmatch,
+            Some(41),
+            0,
+        );
+        for user_id in 1..=2 {
+            state.add_player(user_id, None).unwrap();
+        }
+        state.status = crate::GameStatus::Started { server_id: 1 };
+        let anchor = ReplayAnchor {
+            tick: 0,
+            sequence: 0,
+            state: state.clone(),
+        };
+
+        let turns = [
+            (6, 1, Direction::Down),
+            (9, 2, Direction::Left),
+            (20, 1, Direction::Left),
+            (31, 2, Direction::Up),
+            (44, 1, Direction::Up),
+            (57, 2, Direction::Right),
+            (70, 1, Direction::Right),
+        ];
+        let mut messages = Vec::new();
+        let mut sequence = 0;
+        let mut record = |messages: &mut Vec<_>, tick, event| {
+            sequence += 1;
+            messages.push(RecordedGameMessage {
+                tick,
+                sequence,
+                event,
+            });
+        };
+        while state.tick < 90 && !state.is_complete() {
+            for &(at_tick, user_id, direction) in &turns {
+                // Each turn reaches the server two ticks before it runs.
+                if at_tick != state.tick + 2 {
+                    continue;
+                }
+                let snake_id = state.players[&user_id].snake_id;
+                let id = CommandId {
+                    tick: at_tick,
+                    user_id,
+                    sequence_number: at_tick,
+                };
+                let command_message = GameCommandMessage {
+                    command_id_client: id.clone(),
+                    command_id_server: Some(id),
+                    command: GameCommand::Turn {
+                        snake_id,
+                        direction,
+                    },
+                };
+                state.schedule_command(&command_message);
+                record(
+                    &mut messages,
+                    state.tick,
+                    GameEvent::CommandScheduled { command_message },
+                );
+            }
+            for (_, event) in state.tick_forward(false).unwrap() {
+                record(&mut messages, state.tick, event);
+            }
+        }
+        GameRecordingV1 {
+            format_version: GAME_RECORDING_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: 5,
+            visibility: ReplayVisibility::Public,
+            anchors: vec![anchor],
+            messages,
+            end_tick: state.tick,
+            end_sync_hash: state.sync_hash(),
+            keyframes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn captured_scenarios_end_on_the_recordings_hash() {
+        let recording = recorded_game();
+        assert!(
+            recording
+                .messages
+                .iter()
+                .any(|message| matches!(message.event, GameEvent::FoodSpawned { .. })),
+            "the recording should exercise the RNG"
+        );
+        for at_tick in [0, 19, 50] {
+            let script = scenario_from_recording(&recording, at_tick, "captured").unwrap();
+            assert_eq!(script.pose.start_tick, at_tick);
+            assert_eq!(script.run_ticks, recording.end_tick - at_tick);
+            assert!(
+                script
+                    .commands
+                    .iter()
+                    .all(|command| command.scheduled_at >= Some(at_tick))
+            );
+
+            // The script survives the trip through JSON and still reproduces.
+            let json = serde_json::to_string(&script).unwrap();
+            let run = ScenarioScript::from_json(&json)
+                .unwrap()
+                .load()
+                .unwrap()
+                .run()
+                .unwrap();
+            assert_eq!(run.final_state.sync_hash(), recording.end_sync_hash);
+        }
+
+        let mut drifted = recording.clone();
+        drifted.end_sync_hash ^= 1;
+        assert!(scenario_from_recording(&drifted, 19, "captured").is_err());
+    }
+
+    #[test]
+    fn a_captured_pose_must_still_describe_its_state() {
+        let recording = recorded_game();
+        let script = scenario_from_recording(&recording, 30, "captured").unwrap();
+
+        let mut moved = script.clone();
+        moved.pose.snakes[0].body[0].x += 1;
+        assert!(moved.load().is_err());
+
+        let mut overridden = script.clone();
+        overridden.world.overrides.player_idle_timeout_ms = Some(1_000);
+        assert!(overridden.load().is_err());
+
+        // Names and drivers are free to change.
+        let mut renamed = script;
+        renamed.pose.snakes[0].name = "STAR".into();
+        renamed.load().unwrap().run().unwrap();
+    }
+
+    #[test]
+    fn server_traces_convert_through_a_recording() {
+        let recording = recorded_game();
+        let mut records = vec![
+            TraceRecord::Meta {
+                version: crate::trace::TRACE_FORMAT_VERSION,
+                side: TraceSide::Server,
+                game_id: recording.game_id,
+                session: "server".into(),
+                ts_ms: 0,
+                build: "test".into(),
+                tick_duration_ms: recording.anchors[0].state.properties.tick_duration_ms,
+            },
+            TraceRecord::State {
+                ts_ms: 0,
+                tick: 0,
+                state: Box::new(recording.anchors[0].state.clone()),
+            },
+        ];
+        records.extend(
+            recording
+                .messages
+                .iter()
+                .map(|message| TraceRecord::EventOut {
+                    ts_ms: i64::from(message.tick),
+                    msg: Box::new(GameEventMessage {
+                        stream_seq: message.sequence,
+                        ..message.envelope(recording.game_id)
+                    }),
+                }),
+        );
+        records.push(TraceRecord::Fingerprint {
+            ts_ms: i64::from(recording.end_tick),
+            tick: recording.end_tick,
+            hash: recording.end_sync_hash,
+        });
+
+        let from_trace = recording_from_trace(&records).unwrap();
+        // Snapshots re-anchor a stream rather than advance it.
+        assert_eq!(
+            from_trace.messages.len(),
+            recording
+                .messages
+                .iter()
+                .filter(|message| !matches!(message.event, GameEvent::Snapshot { .. }))
+                .count()
+        );
+        let script = scenario_from_recording(&from_trace, 25, "from-trace").unwrap();
+        assert_eq!(
+            script.expect,
+            vec![ScenarioExpectation::FinalSyncHash(format!(
+                "{:#x}",
+                recording.end_sync_hash
+            ))]
+        );
+    }
+}
@@ -24,8 +24,10 @@ pub const DEFAULT_BOOST_PAD_RESPAWN_MS: u32 = 8_000;
 ///
 /// v3 — the canonical 60x40 team map, drawn inside the end-zone-inset field.
 /// v4 — the teamless 40x40 free-for-all map, drawn on the whole arena.
+/// v5 — pads placed explicitly by the match's `ArenaMap` rather than drawn.
 pub const BOOST_SPOT_LAYOUT_VERSION_TEAM: u16 = 3;
 pub const BOOST_SPOT_LAYOUT_VERSION_FIELD: u16 = 4;
+pub const BOOST_SPOT_LAYOUT_VERSION_MAP: u16 = 5;
 /// Layout carried by an unlimited tank, which has no pickups to place.
 pub const BOOST_SPOT_LAYOUT_VERSION_NONE: u16 = 0;
 pub const BOOST_RULES_VERSION: u16 = 2;
@@ -49,6 +51,10 @@ pub const DEFAULT_PLAYER_IDLE_TIMEOUT_MS: u32 = 60_000;
 /// never use this value as a UI fallback.
 pub const DEFAULT_PLAYER_IDLE_WARNING_MS: u32 = 10_000;
 
+/// Serialized `ArenaMap` schema. Bumped only when an existing field changes
+/// meaning; a map's own content is versioned separately by its `revision`.
+pub const ARENA_MAP_FORMAT_VERSION: u16 = 1;
+
 /// Default available food target
 pub const DEFAULT_FOOD_TARGET: usize = 10;
 
@@ -68,3 +74,60 @@ pub const MATCH_READY_WINDOW_MS: i64 = 15_000;
 /// A team wins the moment its banked score reaches its queue's target.
 pub const DEFAULT_QUICKMATCH_TEAM_SCORE_LIMIT: u32 = 25;
 pub const DEFAULT_COMPETITIVE_TEAM_SCORE_LIMIT: u32 = 50;
+
+/// Rounds a team must take to win a competitive team match: best of three.
+/// Each round is raced to the queue's score target above, and the field is
+/// reset between rounds, so a side that loses the first round can still take
+/// the match. Quickmatch stays a single round.
+pub const DEFAULT_COMPETITIVE_TEAM_ROUNDS_TO_WIN: u32 = 2;
+
+/// Power-up rules snapshotted into every match that opts in. Pickups appear on
+/// a wall-clock cadence, never more than a few at a time, so they stay a
+/// detour worth taking rather than a carpet over the field.
+pub const DEFAULT_POWER_UP_SPAWN_INTERVAL_MS: u32 = 8_000;
+pub const DEFAULT_POWER_UP_MAX_ON_FIELD: u8 = 2;
+pub const DEFAULT_GHOST_DURATION_MS: u32 = 5_000;
+pub const DEFAULT_MAGNET_DURATION_MS: u32 = 8_000;
+/// Chebyshev distance from the head at which a magnet collects food.
+pub const DEFAULT_MAGNET_RADIUS: u8 = 2;
+/// Timed effects may not outlast this, so a snapshot cannot smuggle in a
+/// permanent ghost.
+pub const MAX_POWER_UP_DURATION_MS: u32 = 60_000;
+pub const MAX_MAGNET_RADIUS: u8 = 5;
+pub const POWER_UP_RULES_VERSION: u16 = 1;
+
+/// Battle royale zone schedule. The field stays open for the first half
+/// minute, then loses a ring every three seconds; a 40x40 field is closed a
+/// minute after that, the same 90 seconds free-for-all used to be timed at.
+pub const DEFAULT_ZONE_START_DELAY_MS: u32 = 30_000;
+pub const DEFAULT_ZONE_SHRINK_INTERVAL_MS: u32 = 3_000;
+pub const MAX_ZONE_START_DELAY_MS: u32 = 600_000;
+/// Faster than this and a snake crossing the field could not outrun the wall.
+pub const MIN_ZONE_SHRINK_INTERVAL_MS: u32 = 500;
+pub const ZONE_RULES_VERSION: u16 = 1;
+
+/// King of the hill. A point a second for the team holding the hill alone,
+/// raced to the queue's target like banked food.
+pub const DEFAULT_HILL_SIZE: u16 = 6;
+pub const DEFAULT_HILL_POINT_INTERVAL_MS: u32 = 1_000;
+pub const MAX_HILL_POINT_INTERVAL_MS: u32 = 60_000;
+pub const DEFAULT_QUICKMATCH_HILL_SCORE_LIMIT: u32 = 30;
+pub const DEFAULT_COMPETITIVE_HILL_SCORE_LIMIT: u32 = 60;
+pub const HILL_RULES_VERSION: u16 = 1;
+
+/// Capture the flag. A point per flag carried home; a dropped flag goes home
+/// by itself once it has lain untouched this long.
+pub const DEFAULT_FLAG_RETURN_MS: u32 = 15_000;
+pub const MAX_FLAG_RETURN_MS: u32 = 120_000;
+pub const DEFAULT_QUICKMATCH_FLAG_SCORE_LIMIT: u32 = 3;
+pub const DEFAULT_COMPETITIVE_FLAG_SCORE_LIMIT: u32 = 5;
+pub const FLAG_RULES_VERSION: u16 = 1;
+
+/// Death food. A fallen snake leaves a pellet on every other cell of its body,
+/// at most a feast's worth per death, and never tops the field up past the cap.
+pub const DEFAULT_DEATH_FOOD_CELLS_PER_PELLET: u8 = 2;
+pub const DEFAULT_DEATH_FOOD_MAX_PER_DEATH: u16 = 16;
+pub const DEFAULT_DEATH_FOOD_MAX_ON_FIELD: u16 = 64;
+/// Above this a single death could bury a small custom arena in pellets.
+pub const MAX_DEATH_FOOD_ON_FIELD: u16 = 512;
+pub const DEATH_FOOD_RULES_VERSION: u16 = 1;
@@ -0,0 +1,183 @@
+//! Death food.
+//!
+//! Normally a snake that dies simply vanishes from the field. With death food
+//! its body turns into pellets where it lay, so a kill in free-for-all is also
+//! a meal for whoever gets there first.
+//!
+//! Pellets are placed in the authoritative path of `GameState::tick_forward`,
+//! drawn from the match's `PseudoRandom`, and reach replicas as ordinary
+//! `FoodSpawned` events. Prediction never invents food, so `GameEngine` and the
+//! executor agree on every pellet the moment the event lands. A death only
+//! fills the field up to `max_on_field`, and never with more than
+//! `max_per_death` pellets, so a pile-up cannot flood a small arena.
+//!
+//! Only Custom games can opt in (see `death_food_config_for`). Every other
+//! match carries no config, so its snapshots, recordings and fingerprints are
+//! byte-identical to the ones written before death food existed.
+
+use crate::constants::{
+    DEATH_FOOD_RULES_VERSION, DEFAULT_DEATH_FOOD_CELLS_PER_PELLET, DEFAULT_DEATH_FOOD_MAX_ON_FIELD,
+    DEFAULT_DEATH_FOOD_MAX_PER_DEATH, MAX_DEATH_FOOD_ON_FIELD,
+};
+use crate::game_state::GameType;
+use crate::snake::Position;
+use crate::util::PseudoRandom;
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+/// Death food rules snapshotted into a match so simulation, prediction and
+/// replay never depend on process configuration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct DeathFoodConfig {
+    /// Body cells per pellet: 1 turns every cell into food, 2 every other one.
+    pub cells_per_pellet: u8,
+    /// Pellets a single death may leave, however long the snake was.
+    pub max_per_death: u16,
+    /// Food on the field above which a death leaves nothing more.
+    pub max_on_field: u16,
+    pub rules_version: u16,
+}
+
+impl Default for DeathFoodConfig {
+    fn default() -> Self {
+        Self {
+            cells_per_pellet: DEFAULT_DEATH_FOOD_CELLS_PER_PELLET,
+            max_per_death: DEFAULT_DEATH_FOOD_MAX_PER_DEATH,
+            max_on_field: DEFAULT_DEATH_FOOD_MAX_ON_FIELD,
+            rules_version: DEATH_FOOD_RULES_VERSION,
+        }
+    }
+}
+
+impl DeathFoodConfig {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.cells_per_pellet > 0,
+            "death food cells_per_pellet must be positive"
+        );
+        ensure!(
+            self.max_per_death > 0,
+            "death food max_per_death must be positive"
+        );
+        ensure!(
+            (1..=MAX_DEATH_FOOD_ON_FIELD).contains(&self.max_on_field),
+            "death food max_on_field must be in 1..={MAX_DEATH_FOOD_ON_FIELD}, got {}",
+            self.max_on_field
+        );
+        ensure!(
+            self.rules_version == DEATH_FOOD_RULES_VERSION,
+            "unsupported death food rules version {}, expected {}",
+            self.rules_version,
+            DEATH_FOOD_RULES_VERSION
+        );
+        Ok(())
+    }
+
+    /// Pick the cells a body of `body_len` cells leaves food on, out of the
+    /// `candidates` still free for it, when the field holds `food_on_field`
+    /// pellets.
+    ///
+    /// The draw is a partial Fisher-Yates shuffle, so the result depends only
+    /// on the candidate order and the generator. Callers pass candidates head
+    /// to tail and the match's own generator.
+    pub fn place(
+        &self,
+        body_len: usize,
+        mut candidates: Vec<Position>,
+        food_on_field: usize,
+        rng: &mut PseudoRandom,
+    ) -> Vec<Position> {
+        let count = body_len
+            .div_ceil(usize::from(self.cells_per_pellet))
+            .min(usize::from(self.max_per_death))
+            .min(usize::from(self.max_on_field).saturating_sub(food_on_field))
+            .min(candidates.len());
+        for i in 0..count {
+            let j = i + rng.next_u32() as usize % (candidates.len() - i);
+            candidates.swap(i, j);
+        }
+        candidates.truncate(count);
+        candidates
+    }
+}
+
+/// Which death food rules a mode plays with, if any.
+///
+/// Only Custom games can opt in, through their lobby settings. Matchmade modes
+/// are ranked or feed leaderboards, so they keep exactly the rules players
+/// already know.
+pub fn death_food_config_for(game_type: &GameType) -> Option<DeathFoodConfig> {
+    match game_type {
+        GameType::Custom { settings } if settings.death_food => Some(DeathFoodConfig::default()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CustomGameSettings;
+
+    fn row(len: i16) -> Vec<Position> {
+        (0..len).map(|x| Position { x, y: 3 }).collect()
+    }
+
+    #[test]
+    fn only_custom_games_that_ask_for_death_food_get_it() {
+        assert_eq!(
+            death_food_config_for(&GameType::Custom {
+                settings: CustomGameSettings {
+                    death_food: true,
+                    ..CustomGameSettings::default()
+                }
+            }),
+            Some(DeathFoodConfig::default())
+        );
+        for ranked in [
+            GameType::Solo,
+            GameType::TeamMatch { per_team: 1 },
+            GameType::FreeForAll { max_players: 4 },
+            GameType::Custom {
+                settings: CustomGameSettings::default(),
+            },
+        ] {
+            assert_eq!(death_food_config_for(&ranked), None, "{ranked:?}");
+        }
+        DeathFoodConfig::default()
+            .validate()
+            .expect("default is valid");
+    }
+
+    #[test]
+    fn placement_is_a_pure_function_of_the_generator() {
+        let config = DeathFoodConfig::default();
+        let first = config.place(10, row(10), 0, &mut PseudoRandom::new(7));
+        let second = config.place(10, row(10), 0, &mut PseudoRandom::new(7));
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 5);
+
+        let mut unique = first.clone();
+        unique.sort_by_key(|position| position.x);
+        unique.dedup();
+        assert_eq!(unique.len(), first.len());
+        assert!(first.iter().all(|position| row(10).contains(position)));
+    }
+
+    #[test]
+    fn placement_respects_every_cap() {
+        let config = DeathFoodConfig::default();
+        let mut rng = PseudoRandom::new(1);
+        let per_death = usize::from(config.max_per_death);
+        assert_eq!(config.place(200, row(200), 0, &mut rng).len(), per_death);
+
+        let field = usize::from(config.max_on_field);
+        assert_eq!(config.place(40, row(40), field - 3, &mut rng).len(), 3);
+        assert!(config.place(40, row(40), field, &mut rng).is_empty());
+        assert!(config.place(40, row(40), field + 10, &mut rng).is_empty());
+
+        // A body mostly lying on food or walls leaves only what fits.
+        assert_eq!(config.place(40, row(2), 0, &mut rng).len(), 2);
+    }
+}
@@ -28,7 +28,14 @@
 //!   genuinely disagree is still caught, because `tick` is hashed and the two
 //!   sides would start simulating at different times.
 
-use crate::game_state::{GameCommand, GameState, GameStatus};
+use crate::ai::AiDifficulty;
+use crate::arena::{ArenaMap, MapBoostPlacement};
+use crate::death_food::DeathFoodConfig;
+use crate::flag::{FlagConfig, FlagPlacement};
+use crate::game_state::{GameCommand, GameState, GameStatus, RoundState};
+use crate::hill::HillConfig;
+use crate::power_up::{PowerUpConfig, PowerUpKind};
+use crate::zone::ZoneConfig;
 
 /// FNV-1a 64-bit, hand-rolled so both native and WASM builds hash identically
 /// with no dependencies. All multi-byte values are hashed little-endian.
@@ -118,6 +125,12 @@ impl GameState {
         } else {
             h.write_u8(0);
         }
+        // Only an explicit map contributes: an implicit one is fully described
+        // by the dimensions and zones above, and writing nothing for it keeps
+        // every classic fingerprint identical to the pre-map format.
+        if let Some(map) = &self.arena.map {
+            write_arena_map(&mut h, map);
+        }
 
         // Snakes: Vec index is the snake id, so order is canonical already.
         h.write_u32(self.arena.snakes.len() as u32);
@@ -173,6 +186,17 @@ impl GameState {
             h.write_i16(x);
             h.write_i16(y);
         }
+        // Only a mode with power-ups contributes, which keeps every other
+        // fingerprint identical to the format from before they existed.
+        if let Some(config) = &self.properties.power_ups {
+            write_power_ups(&mut h, self, config);
+        }
+        if let Some(config) = &self.properties.zone {
+            write_zone(&mut h, self, config);
+        }
+        if self.arena.wraps() {
+            h.write_u8(0xC2);
+        }
 
         // HashMaps are hashed in sorted key order for determinism.
         let mut players: Vec<(u32, u32)> = self
@@ -220,6 +244,29 @@ impl GameState {
             }
             None => h.write_u8(0),
         }
+        // Single-round matches carry no round state and contribute nothing,
+        // so their fingerprints are unchanged from before rounds existed.
+        if let Some(rounds) = &self.rounds {
+            write_round_state(&mut h, rounds);
+        }
+        // Likewise only king of the hill has a hill.
+        if let Some(config) = &self.properties.hill {
+            write_hill(&mut h, self, config);
+        }
+        if let Some(config) = &self.properties.flags {
+            write_flags(&mut h, self, config);
+        }
+        if let Some(config) = &self.properties.death_food {
+            write_death_food(&mut h, config);
+        }
+        // A game without AI seats hashes exactly as it did before they existed.
+        if !self.ai_players.is_empty() {
+            write_ai_players(&mut h, self);
+        }
+        // Likewise a game without the autopilot option.
+        if self.properties.idle_autopilot.is_some() || !self.autopiloted_user_ids.is_empty() {
+            write_autopilot(&mut h, self);
+        }
 
         let mut xp: Vec<(u32, u32)> = self.player_xp.iter().map(|(k, v)| (*k, *v)).collect();
         xp.sort_unstable();
@@ -344,6 +391,236 @@ fn direction_tag(direction: &crate::Direction) -> u8 {
     }
 }
 
+fn write_round_state(h: &mut SyncHasher, rounds: &RoundState) {
+    h.write_u8(0xB0);
+    h.write_u32(rounds.rounds_to_win);
+    h.write_u32(rounds.current_round);
+    h.write_u32(rounds.rounds_completed);
+    let mut wins: Vec<(u8, u32)> = rounds
+        .round_wins
+        .iter()
+        .map(|(team, wins)| (team.0, *wins))
+        .collect();
+    wins.sort_unstable();
+    h.write_u32(wins.len() as u32);
+    for (team, count) in wins {
+        h.write_u8(team);
+        h.write_u32(count);
+    }
+}
+
+/// Rules, field pickups (as a set, like food) and every snake's effects.
+fn write_power_ups(h: &mut SyncHasher, state: &GameState, config: &PowerUpConfig) {
+    h.write_u8(0xC0);
+    h.write_u32(config.kinds.len() as u32);
+    for kind in &config.kinds {
+        h.write_u8(power_up_kind_tag(kind));
+    }
+    h.write_u32(config.spawn_interval_ms);
+    h.write_u8(config.max_on_field);
+    h.write_u32(config.ghost_duration_ms);
+    h.write_u32(config.magnet_duration_ms);
+    h.write_u8(config.magnet_radius);
+    h.write_u16(config.rules_version);
+
+    let mut pickups: Vec<(i16, i16, u8)> = state
+        .arena
+        .power_ups
+        .iter()
+        .map(|pickup| {
+            (
+                pickup.position.x,
+                pickup.position.y,
+                power_up_kind_tag(&pickup.kind),
+            )
+        })
+        .collect();
+    pickups.sort_unstable();
+    h.write_u32(pickups.len() as u32);
+    for (x, y, kind) in pickups {
+        h.write_i16(x);
+        h.write_i16(y);
+        h.write_u8(kind);
+    }
+
+    for snake in &state.arena.snakes {
+        h.write_u8(snake.power_ups.shield as u8);
+        h.write_u32(snake.power_ups.ghost_remaining_ms);
+        h.write_u32(snake.power_ups.magnet_remaining_ms);
+    }
+}
+
+fn write_zone(h: &mut SyncHasher, state: &GameState, config: &ZoneConfig) {
+    h.write_u8(0xC1);
+    h.write_u32(config.start_delay_ms);
+    h.write_u32(config.shrink_interval_ms);
+    h.write_u16(config.rules_version);
+    match &state.arena.zone {
+        Some(zone) => {
+            h.write_u8(1);
+            h.write_i16(zone.min_x);
+            h.write_i16(zone.min_y);
+            h.write_i16(zone.max_x);
+            h.write_i16(zone.max_y);
+        }
+        None => h.write_u8(0),
+    }
+}
+
+fn write_hill(h: &mut SyncHasher, state: &GameState, config: &HillConfig) {
+    h.write_u8(0xC3);
+    h.write_u16(config.size);
+    h.write_u32(config.point_interval_ms);
+    h.write_u16(config.rules_version);
+    match &state.hill {
+        Some(control) => {
+            h.write_u8(1);
+            match control.holder {
+                Some(team_id) => {
+                    h.write_u8(1);
+                    h.write_u8(team_id.0);
+                }
+                None => h.write_u8(0),
+            }
+            h.write_u32(control.held_ms);
+            h.write_u8(control.contested as u8);
+        }
+        None => h.write_u8(0),
+    }
+}
+
+fn write_flags(h: &mut SyncHasher, state: &GameState, config: &FlagConfig) {
+    h.write_u8(0xC4);
+    h.write_u32(config.return_ms);
+    h.write_u16(config.rules_version);
+    let mut flags = state.flags.clone();
+    flags.sort_unstable_by_key(|flag| flag.team_id);
+    h.write_u32(flags.len() as u32);
+    for flag in flags {
+        h.write_u8(flag.team_id.0);
+        match flag.placement {
+            FlagPlacement::Home => h.write_u8(0),
+            FlagPlacement::Carried { snake_id } => {
+                h.write_u8(1);
+                h.write_u32(snake_id);
+            }
+            FlagPlacement::Dropped { position, at_tick } => {
+                h.write_u8(2);
+                h.write_i16(position.x);
+                h.write_i16(position.y);
+                h.write_u32(at_tick);
+            }
+        }
+    }
+}
+
+fn write_death_food(h: &mut SyncHasher, config: &DeathFoodConfig) {
+    h.write_u8(0xC5);
+    h.write_u8(config.cells_per_pellet);
+    h.write_u16(config.max_per_death);
+    h.write_u16(config.max_on_field);
+    h.write_u16(config.rules_version);
+}
+
+fn write_ai_players(h: &mut SyncHasher, state: &GameState) {
+    h.write_u8(0xC6);
+    let mut seats: Vec<_> = state.ai_players.iter().collect();
+    seats.sort_unstable_by_key(|(user_id, _)| **user_id);
+    h.write_u32(seats.len() as u32);
+    for (user_id, seat) in seats {
+        h.write_u32(*user_id);
+        h.write_u8(match seat.difficulty {
+            AiDifficulty::Easy => 0,
+            AiDifficulty::Medium => 1,
+            AiDifficulty::Hard => 2,
+        });
+        h.write_u32(seat.next_decision_tick);
+    }
+}
+
+fn write_autopilot(h: &mut SyncHasher, state: &GameState) {
+    h.write_u8(0xC7);
+    h.write_u8(match state.properties.idle_autopilot {
+        None => 0,
+        Some(AiDifficulty::Easy) => 1,
+        Some(AiDifficulty::Medium) => 2,
+        Some(AiDifficulty::Hard) => 3,
+    });
+    h.write_u32(state.autopiloted_user_ids.len() as u32);
+    for user_id in &state.autopiloted_user_ids {
+        h.write_u32(*user_id);
+        match state.autopilot_players.get(user_id) {
+            Some(seat) => {
+                h.write_u8(1);
+                h.write_u8(match seat.difficulty {
+                    AiDifficulty::Easy => 0,
+                    AiDifficulty::Medium => 1,
+                    AiDifficulty::Hard => 2,
+                });
+                h.write_u32(seat.next_decision_tick);
+            }
+            None => h.write_u8(0),
+        }
+    }
+}
+
+fn power_up_kind_tag(kind: &PowerUpKind) -> u8 {
+    match kind {
+        PowerUpKind::Shield => 0,
+        PowerUpKind::Ghost => 1,
+        PowerUpKind::Magnet => 2,
+    }
+}
+
+/// Identity plus every piece of map geometry the simulation reads. The
+/// identity is included so two revisions of one map can never be confused,
+/// even when a revision only moved something no snake has touched yet.
+fn write_arena_map(h: &mut SyncHasher, map: &ArenaMap) {
+    h.write_u8(0xA7);
+    h.write_u16(map.format_version);
+    h.write_u32(map.id.len() as u32);
+    h.write_bytes(map.id.as_bytes());
+    h.write_u16(map.revision);
+    h.write_u32(map.obstacles.len() as u32);
+    for block in &map.obstacles {
+        h.write_i16(block.position.x);
+        h.write_i16(block.position.y);
+        h.write_u16(block.width);
+        h.write_u16(block.height);
+    }
+    h.write_u32(map.spawn_points.len() as u32);
+    for spawn in &map.spawn_points {
+        h.write_i16(spawn.position.x);
+        h.write_i16(spawn.position.y);
+        h.write_u8(direction_tag(&spawn.direction));
+        match spawn.team_id {
+            Some(team) => {
+                h.write_u8(1);
+                h.write_u8(team.0);
+            }
+            None => h.write_u8(0),
+        }
+    }
+    match &map.boost {
+        MapBoostPlacement::None => h.write_u8(0),
+        MapBoostPlacement::Layout {
+            spot_layout_version,
+        } => {
+            h.write_u8(1);
+            h.write_u16(*spot_layout_version);
+        }
+        MapBoostPlacement::Pads { pads } => {
+            h.write_u8(2);
+            h.write_u32(pads.len() as u32);
+            for pad in pads {
+                h.write_i16(pad.position.x);
+                h.write_i16(pad.position.y);
+                h.write_u8(pad.size_cells);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -558,4 +835,247 @@ mod tests {
         pad_changed.arena.boost_pads[0].size_cells = 1;
         assert_ne!(baseline.sync_hash(), pad_changed.sync_hash());
     }
+
+    #[test]
+    fn hash_covers_explicit_maps_and_ignores_implicit_ones() {
+        let baseline = test_state();
+        let mut implicit = baseline.clone();
+        implicit.arena.map = None;
+        assert_eq!(baseline.sync_hash(), implicit.sync_hash());
+
+        let map = crate::ArenaMap::builtin("crossroads").unwrap().clone();
+        let on_map = |map: &crate::ArenaMap| {
+            GameState::new_on_map(
+                map,
+                GameType::FreeForAll { max_players: 4 },
+                QueueMode::Quickmatch,
+                Some(42),
+                1_000,
+            )
+            .expect("crossroads hosts free-for-all")
+        };
+        let state = on_map(&map);
+
+        let mut obstacle_moved = map.clone();
+        obstacle_moved.obstacles[0].position.x += 1;
+        assert_ne!(state.sync_hash(), on_map(&obstacle_moved).sync_hash());
+
+        let mut spawn_turned = map.clone();
+        spawn_turned.spawn_points[2].direction = Direction::Up;
+        spawn_turned.spawn_points[2].position.y += 3;
+        assert_ne!(state.sync_hash(), on_map(&spawn_turned).sync_hash());
+
+        let mut revised = map;
+        revised.revision += 1;
+        assert_ne!(state.sync_hash(), on_map(&revised).sync_hash());
+    }
+
+    #[test]
+    fn hash_detects_round_progress() {
+        let baseline = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Competitive,
+            Some(42),
+            1_000,
+        );
+        assert!(baseline.rounds.is_some());
+
+        let mut ended = baseline.clone();
+        ended.rounds.as_mut().unwrap().rounds_completed = 1;
+        assert_ne!(baseline.sync_hash(), ended.sync_hash());
+
+        let mut won = ended.clone();
+        won.rounds
+            .as_mut()
+            .unwrap()
+            .round_wins
+            .insert(crate::TeamId(1), 1);
+        assert_ne!(ended.sync_hash(), won.sync_hash());
+
+        let mut next = won.clone();
+        next.rounds.as_mut().unwrap().current_round = 2;
+        assert_ne!(won.sync_hash(), next.sync_hash());
+    }
+
+    #[test]
+    fn hash_covers_power_ups_only_where_the_mode_offers_them() {
+        let custom = |power_ups: bool| {
+            let mut state = GameState::new(
+                40,
+                40,
+                GameType::Custom {
+                    settings: crate::CustomGameSettings {
+                        power_ups,
+                        ..Default::default()
+                    },
+                },
+                QueueMode::Quickmatch,
+                Some(42),
+                1_000,
+            );
+            state.add_player(1, None).unwrap();
+            state
+        };
+        let plain = custom(false);
+        let baseline = custom(true);
+        assert_ne!(plain.sync_hash(), baseline.sync_hash());
+
+        let mut spawned = baseline.clone();
+        spawned.arena.power_ups.push(crate::PowerUpPickup {
+            kind: crate::PowerUpKind::Ghost,
+            position: Position { x: 5, y: 5 },
+        });
+        assert_ne!(baseline.sync_hash(), spawned.sync_hash());
+
+        let mut other_kind = spawned.clone();
+        other_kind.arena.power_ups[0].kind = crate::PowerUpKind::Magnet;
+        assert_ne!(spawned.sync_hash(), other_kind.sync_hash());
+
+        let mut shielded = baseline.clone();
+        shielded.arena.snakes[0].power_ups.shield = true;
+        assert_ne!(baseline.sync_hash(), shielded.sync_hash());
+
+        let mut ghosting = baseline.clone();
+        ghosting.arena.snakes[0].power_ups.ghost_remaining_ms = 500;
+        assert_ne!(baseline.sync_hash(), ghosting.sync_hash());
+
+        let mut magnet = baseline.clone();
+        magnet.arena.snakes[0].power_ups.magnet_remaining_ms = 500;
+        assert_ne!(baseline.sync_hash(), magnet.sync_hash());
+    }
+
+    #[test]
+    fn hash_covers_the_zone_only_where_the_mode_has_one() {
+        let custom = |shrinking_zone: bool| {
+            GameState::new(
+                40,
+                40,
+                GameType::Custom {
+                    settings: crate::CustomGameSettings {
+                        shrinking_zone,
+                        ..Default::default()
+                    },
+                },
+                QueueMode::Quickmatch,
+                Some(42),
+                1_000,
+            )
+        };
+        let plain = custom(false);
+        let open = custom(true);
+        assert_ne!(plain.sync_hash(), open.sync_hash());
+
+        let mut contracted = open.clone();
+        contracted.arena.zone = Some(crate::ZoneBounds::inset(40, 40, 1));
+        assert_ne!(open.sync_hash(), contracted.sync_hash());
+    }
+
+    #[test]
+    fn hash_covers_hill_control_in_king_of_the_hill() {
+        let mut hill = GameState::new(
+            60,
+            40,
+            GameType::KingOfTheHill { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(42),
+            1_000,
+        );
+        let team = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(42),
+            1_000,
+        );
+        let unheld = hill.sync_hash();
+        assert_ne!(unheld, team.sync_hash());
+
+        hill.hill.as_mut().unwrap().holder = Some(crate::TeamId(1));
+        let held = hill.sync_hash();
+        assert_ne!(unheld, held);
+        hill.hill.as_mut().unwrap().held_ms = 500;
+        assert_ne!(held, hill.sync_hash());
+    }
+
+    #[test]
+    fn hash_covers_flags_in_capture_the_flag() {
+        let mut ctf = GameState::new(
+            60,
+            40,
+            GameType::CaptureTheFlag { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(42),
+            1_000,
+        );
+        let team = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(42),
+            1_000,
+        );
+        let home = ctf.sync_hash();
+        assert_ne!(home, team.sync_hash());
+
+        ctf.flags[0].placement = crate::FlagPlacement::Carried { snake_id: 1 };
+        let carried = ctf.sync_hash();
+        assert_ne!(home, carried);
+        ctf.flags[0].placement = crate::FlagPlacement::Dropped {
+            position: crate::Position { x: 30, y: 20 },
+            at_tick: 7,
+        };
+        assert_ne!(carried, ctf.sync_hash());
+    }
+
+    #[test]
+    fn hash_covers_wraparound_edges_only_where_the_lobby_asked_for_them() {
+        let custom = |edge_policy: crate::EdgePolicy| {
+            GameState::new(
+                40,
+                40,
+                GameType::Custom {
+                    settings: crate::CustomGameSettings {
+                        edge_policy,
+                        ..Default::default()
+                    },
+                },
+                QueueMode::Quickmatch,
+                Some(42),
+                1_000,
+            )
+        };
+        let solid = custom(crate::EdgePolicy::Solid);
+        let mut wrapping = custom(crate::EdgePolicy::Wrap);
+        assert_ne!(solid.sync_hash(), wrapping.sync_hash());
+        wrapping.arena.edges = crate::EdgePolicy::Solid;
+        assert_eq!(solid.sync_hash(), wrapping.sync_hash());
+    }
+
+    #[test]
+    fn hash_covers_death_food_only_where_the_lobby_asked_for_it() {
+        let custom = |death_food: bool| {
+            GameState::new(
+                40,
+                40,
+                GameType::Custom {
+                    settings: crate::CustomGameSettings {
+                        death_food,
+                        ..Default::default()
+                    },
+                },
+                QueueMode::Quickmatch,
+                Some(42),
+                1_000,
+            )
+        };
+        let plain = custom(false);
+        let mut feast = custom(true);
+        assert_ne!(plain.sync_hash(), feast.sync_hash());
+        feast.properties.death_food = None;
+        assert_eq!(plain.sync_hash(), feast.sync_hash());
+    }
 }
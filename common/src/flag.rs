@@ -0,0 +1,191 @@
+//! Capture the flag.
+//!
+//! A capture-the-flag match is played on the team arena. Each team's flag
+//! stands in the middle of its end zone. Snakes may enter the enemy base,
+//! which every other team mode punishes with death, to take the enemy flag
+//! by running a head over it. The carrier scores by bringing it into its own
+//! base: the flag goes home and the team gains a point, reported through the
+//! same `team_scores` and `TeamScoreUpdated` as banked food. The first team to
+//! the queue's target wins.
+//!
+//! A carrier that dies drops the flag on the last cell its head safely
+//! occupied. An enemy head there picks it up again, a friendly head returns
+//! it home, and a flag nobody touches for `return_ms` goes home by itself.
+//!
+//! Like the hill, flags move only in the movement path of
+//! `GameState::tick_forward`, from positions and the tick alone. Replicas
+//! therefore pick up, drop and capture on the same tick as the server.
+//!
+//! Every other mode carries no config and no flags, so its snapshots,
+//! recordings and fingerprints are byte-identical to the ones written before
+//! flags existed.
+
+use crate::constants::{
+    DEFAULT_COMPETITIVE_FLAG_SCORE_LIMIT, DEFAULT_FLAG_RETURN_MS,
+    DEFAULT_QUICKMATCH_FLAG_SCORE_LIMIT, FLAG_RULES_VERSION, MAX_FLAG_RETURN_MS,
+};
+use crate::game_state::{Arena, GameType, QueueMode, TeamId};
+use crate::snake::Position;
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+/// Flag rules snapshotted into a match so simulation, prediction and replay
+/// never depend on process configuration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct FlagConfig {
+    /// Simulation time a dropped flag lies untouched before it goes home.
+    pub return_ms: u32,
+    pub rules_version: u16,
+}
+
+impl Default for FlagConfig {
+    fn default() -> Self {
+        Self {
+            return_ms: DEFAULT_FLAG_RETURN_MS,
+            rules_version: FLAG_RULES_VERSION,
+        }
+    }
+}
+
+impl FlagConfig {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            (1..=MAX_FLAG_RETURN_MS).contains(&self.return_ms),
+            "flag return_ms must be in 1..={MAX_FLAG_RETURN_MS}, got {}",
+            self.return_ms
+        );
+        ensure!(
+            self.rules_version == FLAG_RULES_VERSION,
+            "unsupported flag rules version {}, expected {}",
+            self.rules_version,
+            FLAG_RULES_VERSION
+        );
+        Ok(())
+    }
+
+    /// The tick on which a flag dropped on `dropped_at_tick` goes home.
+    pub fn return_tick(&self, dropped_at_tick: u32, tick_duration_ms: u32) -> u32 {
+        dropped_at_tick.saturating_add(self.return_ms.div_ceil(tick_duration_ms.max(1)))
+    }
+}
+
+/// Where a flag is.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum FlagPlacement {
+    /// On its stand in the middle of its team's end zone.
+    Home,
+    /// On the head of an enemy snake.
+    Carried { snake_id: u32 },
+    /// Where its last carrier died.
+    Dropped { position: Position, at_tick: u32 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct Flag {
+    /// The team the flag belongs to, and defends.
+    pub team_id: TeamId,
+    pub placement: FlagPlacement,
+}
+
+impl Flag {
+    pub fn carrier(&self) -> Option<u32> {
+        match self.placement {
+            FlagPlacement::Carried { snake_id } => Some(snake_id),
+            _ => None,
+        }
+    }
+
+    /// The cell a head must reach to touch the flag, or `None` while it is
+    /// carried.
+    pub fn position(&self, arena: &Arena) -> Option<Position> {
+        match self.placement {
+            FlagPlacement::Home => flag_home(arena, self.team_id),
+            FlagPlacement::Carried { .. } => None,
+            FlagPlacement::Dropped { position, .. } => Some(position),
+        }
+    }
+}
+
+/// The cell a team's flag stands on: the middle of the open part of its end
+/// zone, clear of the goal wall.
+pub fn flag_home(arena: &Arena, team_id: TeamId) -> Option<Position> {
+    let (min_x, max_x, min_y, max_y) = arena.team_zone_bounds(team_id)?;
+    // The zone's inner column is the goal wall, so the stand centres on the
+    // columns in front of it.
+    let (min_x, max_x) = if team_id.0 == 0 {
+        (min_x, max_x - 1)
+    } else {
+        (min_x + 1, max_x)
+    };
+    Some(Position {
+        x: (min_x + max_x) / 2,
+        y: (min_y + max_y + 1) / 2,
+    })
+}
+
+/// Which flag rules a mode plays with, if any. Only capture the flag has one.
+pub fn flag_config_for(game_type: &GameType) -> Option<FlagConfig> {
+    match game_type {
+        GameType::CaptureTheFlag { .. } => Some(FlagConfig::default()),
+        _ => None,
+    }
+}
+
+/// Captures a team needs to win, by queue.
+pub fn flag_score_limit(queue_mode: &QueueMode) -> u32 {
+    match queue_mode {
+        QueueMode::Quickmatch => DEFAULT_QUICKMATCH_FLAG_SCORE_LIMIT,
+        QueueMode::Competitive => DEFAULT_COMPETITIVE_FLAG_SCORE_LIMIT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameState;
+
+    #[test]
+    fn only_capture_the_flag_matches_have_flags() {
+        for per_team in [1, 2] {
+            assert_eq!(
+                flag_config_for(&GameType::CaptureTheFlag { per_team }),
+                Some(FlagConfig::default())
+            );
+        }
+        for other in [
+            GameType::Solo,
+            GameType::TeamMatch { per_team: 1 },
+            GameType::KingOfTheHill { per_team: 1 },
+            GameType::FreeForAll { max_players: 4 },
+        ] {
+            assert_eq!(flag_config_for(&other), None, "{other:?}");
+        }
+        FlagConfig::default().validate().expect("default is valid");
+    }
+
+    #[test]
+    fn flags_stand_mirrored_inside_their_own_base() {
+        let game = GameState::new(
+            60,
+            40,
+            GameType::CaptureTheFlag { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(3),
+            0,
+        );
+        let arena = &game.arena;
+        let left = flag_home(arena, TeamId(0)).unwrap();
+        let right = flag_home(arena, TeamId(1)).unwrap();
+        assert!(arena.is_in_team_base(&left, TeamId(0)));
+        assert!(arena.is_in_team_base(&right, TeamId(1)));
+        assert!(!arena.is_wall_position(&left) && !arena.is_wall_position(&right));
+        assert_eq!(left.x, arena.width as i16 - 1 - right.x);
+        assert_eq!(left.y, right.y);
+    }
+}
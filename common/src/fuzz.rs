@@ -0,0 +1,888 @@
+//! Determinism fuzzing for the shared engine.
+//!
+//! Netcode assumes every path to a tick lands on the same `GameState`: the
+//! server simulating it, a client restoring a snapshot of it from the wire,
+//! and a client or recording replaying its events onto an earlier anchor.
+//! [`fuzz`] generates random [`ScenarioScript`]s, plays each one live, and
+//! checks the other two paths against the live sync hash at every tick. A
+//! failing script is shrunk to a minimal one that still diverges, ready to be
+//! saved as a scenario file.
+
+use crate::scenario::{
+    SCENARIO_FORMAT_VERSION, ScenarioCommand, ScenarioCommandKind, ScenarioDriver,
+    ScenarioOverrides, ScenarioPose, ScenarioPresentation, ScenarioScript, ScenarioSnakePose,
+    ScenarioWorld,
+};
+use crate::{
+    AiDifficulty, ArenaMap, BOOST_TICK_INTERVAL_MS, CustomGameSettings, Direction, EdgePolicy,
+    GameCommandMessage, GameEngine, GameEvent, GameEventMessage, GameMode, GameState, GameStatus,
+    GameType, Position, PseudoRandom, QueueMode, advance_and_apply_replicated_message,
+    boost_config_for, decode_game_event_frame, encode_game_event_frame,
+};
+use anyhow::{Context, Result, ensure};
+use std::fmt;
+
+/// Longest scenario [`FuzzConfig::default`] generates, in ticks.
+pub const DEFAULT_FUZZ_MAX_RUN_TICKS: u32 = 150;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzConfig {
+    /// Case `n` is generated from `seed + n`, so any case can be rerun alone.
+    pub seed: u64,
+    pub cases: u32,
+    pub max_run_ticks: u32,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            seed: 0x5eed_f00d,
+            cases: 32,
+            max_run_ticks: DEFAULT_FUZZ_MAX_RUN_TICKS,
+        }
+    }
+}
+
+/// The path that disagreed with the live simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayPath {
+    /// A snapshot sent as a JSON frame and restored into a `GameEngine`.
+    JsonSnapshot { from_tick: u32 },
+    /// The same, sent as a MessagePack frame.
+    MessagePackSnapshot { from_tick: u32 },
+    /// Every replicated event applied, in order, to the starting pose.
+    EventReplay,
+}
+
+impl fmt::Display for ReplayPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayPath::JsonSnapshot { from_tick } => {
+                write!(f, "JSON snapshot restored at tick {from_tick}")
+            }
+            ReplayPath::MessagePackSnapshot { from_tick } => {
+                write!(f, "MessagePack snapshot restored at tick {from_tick}")
+            }
+            ReplayPath::EventReplay => f.write_str("event replay"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub path: ReplayPath,
+    /// The first tick the path disagreed at.
+    pub tick: u32,
+    pub reason: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} diverged at tick {}: {}",
+            self.path, self.tick, self.reason
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FuzzFailure {
+    pub case: u32,
+    pub original: ScenarioScript,
+    /// The smallest script [`shrink`] found that still diverges.
+    pub minimal: ScenarioScript,
+    /// How `minimal` diverges.
+    pub divergence: Divergence,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FuzzReport {
+    pub cases_checked: u32,
+    /// Generated scripts the loader rejected, such as food on a Boost pad.
+    pub cases_discarded: u32,
+    pub failure: Option<FuzzFailure>,
+}
+
+/// Check `config.cases` generated scripts, stopping at the first that
+/// diverges and shrinking it.
+pub fn fuzz(config: &FuzzConfig) -> Result<FuzzReport> {
+    let mut report = FuzzReport::default();
+    for case in 0..config.cases {
+        let script = generate_script(
+            config.seed.wrapping_add(u64::from(case)),
+            config.max_run_ticks,
+        );
+        if script.clone().load().is_err() {
+            report.cases_discarded += 1;
+            continue;
+        }
+        report.cases_checked += 1;
+        if let Some(divergence) = check_determinism(&script)? {
+            let (minimal, divergence) = shrink(script.clone(), divergence);
+            report.failure = Some(FuzzFailure {
+                case,
+                original: script,
+                minimal,
+                divergence,
+            });
+            break;
+        }
+    }
+    Ok(report)
+}
+
+/// A random, fully scripted scenario. The same seed always gives the same
+/// script.
+///
+/// Half the scripts pose snakes by hand on a mode's default arena. The other
+/// half start from a match built the way the server builds one, on a
+/// built-in map, with the mode's own rules, AI seats and the idle autopilot,
+/// captured as the pose.
+pub fn generate_script(seed: u64, max_run_ticks: u32) -> ScenarioScript {
+    // Xorshift's first outputs from neighbouring seeds are nearly equal, so
+    // spread the seed first or consecutive cases would all pick one mode.
+    let mut rng = PseudoRandom::new(seed.wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    let built = if rng.next_u32().is_multiple_of(2) {
+        built_match(&mut rng)
+    } else {
+        None
+    };
+    let (world, pose, humans) = built.unwrap_or_else(|| authored_pose(&mut rng));
+
+    let run_ticks = 20 + rng.next_u32() % max_run_ticks.saturating_sub(19).max(1);
+    let mut commands: Vec<ScenarioCommand> = (0..rng.next_u32() % (run_ticks / 4 + 1))
+        .map(|_| {
+            let at_tick = pose.start_tick + rng.next_u32() % run_ticks;
+            ScenarioCommand {
+                at_tick,
+                user_id: humans[(rng.next_u32() % humans.len() as u32) as usize],
+                command: match rng.next_u32() % 10 {
+                    0 => ScenarioCommandKind::ActivateBoost,
+                    1 => ScenarioCommandKind::DeactivateBoost,
+                    // Hands a snake back from the autopilot, and otherwise
+                    // only restarts the idle clock.
+                    2 => ScenarioCommandKind::PlayerActivity,
+                    turn => ScenarioCommandKind::Turn(match turn % 4 {
+                        0 => Direction::Up,
+                        1 => Direction::Down,
+                        2 => Direction::Left,
+                        _ => Direction::Right,
+                    }),
+                },
+                // Half arrive on the tick they apply at, as live input does,
+                // and half are queued up front.
+                scheduled_at: rng.next_u32().is_multiple_of(2).then_some(at_tick),
+            }
+        })
+        .collect();
+    // With the autopilot on, the first player keeps playing so that their
+    // idle teammate, the third, is taken over rather than removed, and that
+    // teammate comes back late in the run. Input only restarts the idle
+    // clock when it arrives, so each one is scheduled at its own tick.
+    if pose
+        .captured
+        .as_ref()
+        .is_some_and(|state| state.properties.idle_autopilot.is_some())
+    {
+        commands.extend((0..run_ticks).step_by(10).map(|tick| ScenarioCommand {
+            at_tick: pose.start_tick + tick,
+            user_id: humans[0],
+            command: ScenarioCommandKind::PlayerActivity,
+            scheduled_at: Some(pose.start_tick + tick),
+        }));
+        let returns_at = pose.start_tick + run_ticks / 2 + rng.next_u32() % run_ticks.div_ceil(2);
+        commands.push(ScenarioCommand {
+            at_tick: returns_at,
+            user_id: humans[2],
+            command: ScenarioCommandKind::PlayerActivity,
+            scheduled_at: Some(returns_at),
+        });
+    }
+
+    ScenarioScript {
+        format_version: SCENARIO_FORMAT_VERSION,
+        id: format!("fuzz-{seed:#x}"),
+        world,
+        pose,
+        commands,
+        run_ticks,
+        presentation: ScenarioPresentation::default(),
+        expect: Vec::new(),
+    }
+}
+
+/// Snakes posed by hand in one of the modes the authored pose supports, with
+/// the user ids that may send commands.
+fn authored_pose(rng: &mut PseudoRandom) -> (ScenarioWorld, ScenarioPose, Vec<u32>) {
+    let game_type = match rng.next_u32() % 3 {
+        0 => GameType::Solo,
+        1 => GameType::FreeForAll {
+            max_players: 2 + (rng.next_u32() % 3) as u8,
+        },
+        _ => GameType::TeamMatch {
+            per_team: 1 + (rng.next_u32() % 2) as u8,
+        },
+    };
+    let snake_count = match &game_type {
+        GameType::Solo => 1,
+        GameType::FreeForAll { max_players } => u32::from(*max_players),
+        GameType::TeamMatch { per_team } => u32::from(*per_team) * 2,
+        _ => unreachable!("never generated"),
+    };
+    let (width, height) = if game_type.is_team_game() {
+        (60_i16, 40_i16)
+    } else {
+        (40, 40)
+    };
+    let boost = boost_config_for(
+        &game_type,
+        &ArenaMap::default_for(&game_type, width as u16, height as u16),
+    );
+
+    // One snake to a row, on odd rows, leaves the even rows free for food.
+    let snakes = (0..snake_count)
+        .map(|index| {
+            let y = 5 + index as i16 * 8;
+            let length = 2 + (rng.next_u32() % 4) as i16;
+            let head_x = 8 + (rng.next_u32() % (width as u32 - 16)) as i16;
+            let direction = if rng.next_u32().is_multiple_of(2) {
+                Direction::Right
+            } else {
+                Direction::Left
+            };
+            let tail_x = match direction {
+                Direction::Right => head_x - length,
+                _ => head_x + length,
+            };
+            let boost_charge_ms = match &boost {
+                Some(config) if !config.unlimited => {
+                    let steps = config.capacity_ms / BOOST_TICK_INTERVAL_MS;
+                    (rng.next_u32() % (steps + 1)) * BOOST_TICK_INTERVAL_MS
+                }
+                _ => 0,
+            };
+            ScenarioSnakePose {
+                user_id: index + 1,
+                name: format!("FUZZ{}", index + 1),
+                body: vec![Position { x: head_x, y }, Position { x: tail_x, y }],
+                direction,
+                food: 0,
+                team_id: None,
+                is_alive: None,
+                boost_charge_ms,
+                boost_active: false,
+                combo_chain: 0,
+                combo_remaining_ms: 0,
+                driver: ScenarioDriver::Scripted,
+                team_strategy: None,
+                policy: None,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut food: Vec<Position> = Vec::new();
+    for _ in 0..rng.next_u32() % 6 {
+        let position = Position {
+            x: (rng.next_u32() % width as u32) as i16,
+            y: ((rng.next_u32() % (height as u32 / 2)) * 2) as i16,
+        };
+        if !food.contains(&position) {
+            food.push(position);
+        }
+    }
+
+    // A short idle timeout now and then puts kicks and forfeits in play.
+    let player_idle_timeout_ms = idle_timeout_ms(rng);
+
+    let world = ScenarioWorld {
+        game_type,
+        queue_mode: QueueMode::Quickmatch,
+        rng_seed: Some(rng.next_u64()),
+        arena_width: None,
+        arena_height: None,
+        overrides: ScenarioOverrides {
+            player_idle_timeout_ms,
+            ..Default::default()
+        },
+    };
+    let pose = ScenarioPose {
+        snakes,
+        food,
+        team_scores: Vec::new(),
+        start_tick: 0,
+        captured: None,
+    };
+    (world, pose, (1..=snake_count).collect())
+}
+
+/// A match built like the server builds one, on a built-in map that fits its
+/// mode, captured as the pose, with the user ids of its human players. `None`
+/// when the map cannot host the mode.
+fn built_match(rng: &mut PseudoRandom) -> Option<(ScenarioWorld, ScenarioPose, Vec<u32>)> {
+    let per_team = 1 + (rng.next_u32() % 2) as u8;
+    let mut game_type = match rng.next_u32() % 6 {
+        0 => GameType::Solo,
+        1 => GameType::FreeForAll {
+            max_players: 2 + (rng.next_u32() % 3) as u8,
+        },
+        2 => GameType::TeamMatch { per_team },
+        3 => GameType::KingOfTheHill { per_team },
+        4 => GameType::CaptureTheFlag { per_team },
+        _ => {
+            let max_players = 2 + (rng.next_u32() % 3) as u8;
+            GameType::Custom {
+                settings: CustomGameSettings {
+                    max_players,
+                    game_mode: GameMode::FreeForAll { max_players },
+                    power_ups: rng.next_u32().is_multiple_of(2),
+                    shrinking_zone: rng.next_u32().is_multiple_of(2),
+                    edge_policy: if rng.next_u32().is_multiple_of(2) {
+                        EdgePolicy::Wrap
+                    } else {
+                        EdgePolicy::Solid
+                    },
+                    death_food: rng.next_u32().is_multiple_of(2),
+                    ..Default::default()
+                },
+            }
+        }
+    };
+    let maps: Vec<&ArenaMap> = ArenaMap::builtins()
+        .iter()
+        .filter(|map| map.team_zones.is_some() == game_type.is_team_game())
+        .collect();
+    let map = maps[(rng.next_u32() % maps.len() as u32) as usize];
+    if let GameType::Custom { settings } = &mut game_type {
+        settings.arena_width = map.width;
+        settings.arena_height = map.height;
+    }
+    // Team modes in the competitive queue are played as rounds.
+    let queue_mode = if game_type.is_team_game() && rng.next_u32().is_multiple_of(3) {
+        QueueMode::Competitive
+    } else {
+        QueueMode::Quickmatch
+    };
+    let seats = match &game_type {
+        GameType::Solo => 1,
+        GameType::FreeForAll { max_players } => u32::from(*max_players),
+        GameType::Custom { settings } => u32::from(settings.max_players),
+        GameType::TeamMatch { per_team }
+        | GameType::KingOfTheHill { per_team }
+        | GameType::CaptureTheFlag { per_team } => u32::from(*per_team) * 2,
+    };
+    // The autopilot only takes over a team player while a teammate still
+    // plays in person, so it needs two humans on one team: teams are filled
+    // in turn, which puts the first and third together.
+    let autopilot = game_type.is_team_game() && seats == 4 && rng.next_u32().is_multiple_of(2);
+    let ai_seats = if autopilot {
+        rng.next_u32() % 2
+    } else {
+        rng.next_u32() % seats
+    };
+    let humans: Vec<u32> = (1..=seats - ai_seats).collect();
+
+    let mut state =
+        GameState::new_on_map(map, game_type, queue_mode, Some(rng.next_u64()), 0).ok()?;
+    for &user_id in &humans {
+        state
+            .add_player(user_id, Some(format!("FUZZ{user_id}")))
+            .ok()?;
+    }
+    for _ in 0..ai_seats {
+        let difficulty = AiDifficulty::ALL[(rng.next_u32() % 3) as usize];
+        state.add_ai_player(difficulty, None).ok()?;
+    }
+    let idle_timeout_ms = if autopilot {
+        state.properties.idle_autopilot = Some(AiDifficulty::ALL[(rng.next_u32() % 3) as usize]);
+        Some(1_000 * (1 + rng.next_u32() % 3))
+    } else {
+        idle_timeout_ms(rng)
+    };
+    if let Some(timeout) = idle_timeout_ms {
+        state.properties.player_idle_timeout_ms = timeout;
+        state.properties.player_idle_warning_ms =
+            state.properties.player_idle_warning_ms.min(timeout - 1);
+    }
+    state.spawn_initial_food();
+    state.status = GameStatus::Started { server_id: 0 };
+
+    let world = ScenarioWorld {
+        game_type: state.game_type.clone(),
+        queue_mode: state.queue_mode.clone(),
+        rng_seed: None,
+        arena_width: Some(state.arena.width),
+        arena_height: Some(state.arena.height),
+        overrides: ScenarioOverrides::default(),
+    };
+    Some((world, ScenarioPose::capture(&state), humans))
+}
+
+/// An idle timeout short enough to expire within a run, a quarter of the
+/// time.
+fn idle_timeout_ms(rng: &mut PseudoRandom) -> Option<u32> {
+    rng.next_u32()
+        .is_multiple_of(4)
+        .then(|| 1_000 * (1 + rng.next_u32() % 5))
+}
+
+/// Play `script` live and check snapshot restore and event replay against
+/// it. `Ok(None)` means every path agreed at every tick. The script may only
+/// use scripted drivers, so that every input lives in the state's command
+/// queue and travels with a snapshot.
+pub fn check_determinism(script: &ScenarioScript) -> Result<Option<Divergence>> {
+    ensure!(
+        script
+            .pose
+            .snakes
+            .iter()
+            .all(|snake| snake.driver == ScenarioDriver::Scripted),
+        "determinism checks need every snake scripted"
+    );
+    let loaded = script.clone().load()?;
+    let mut playback = loaded.playback();
+    let start_tick = playback.start_tick();
+    let mut hashes = vec![playback.state().sync_hash()];
+    let mut restore_points = Vec::new();
+    let restore_ticks = [
+        start_tick,
+        start_tick + script.run_ticks / 3,
+        start_tick + script.run_ticks * 2 / 3,
+    ];
+    loop {
+        if restore_ticks.contains(&playback.state().tick) {
+            restore_points.push(playback.state().clone());
+        }
+        if !playback.advance_one()? {
+            break;
+        }
+        hashes.push(playback.state().sync_hash());
+    }
+    let end_tick = playback.state().tick;
+
+    for state in &restore_points {
+        let from_tick = state.tick;
+        for path in [
+            ReplayPath::JsonSnapshot { from_tick },
+            ReplayPath::MessagePackSnapshot { from_tick },
+        ] {
+            let divergence = match restore_snapshot(state, path) {
+                Ok(restored) => simulate_against(
+                    restored,
+                    loaded.deferred_commands(),
+                    start_tick,
+                    end_tick,
+                    &hashes,
+                    path,
+                ),
+                Err(error) => Some(Divergence {
+                    path,
+                    tick: from_tick,
+                    reason: format!("snapshot rejected: {error:#}"),
+                }),
+            };
+            if divergence.is_some() {
+                return Ok(divergence);
+            }
+        }
+    }
+
+    Ok(replay_events_against(
+        &loaded.initial_state,
+        playback.events(),
+        loaded.deferred_commands(),
+        start_tick,
+        end_tick,
+        &hashes,
+    ))
+}
+
+/// Send `state` through the wire as a snapshot and restore it the way a
+/// joining client does.
+fn restore_snapshot(state: &GameState, path: ReplayPath) -> Result<GameState> {
+    let message = GameEventMessage {
+        game_id: 0,
+        tick: state.tick,
+        sequence: 0,
+        stream_seq: 0,
+        user_id: None,
+        event: GameEvent::Snapshot {
+            game_state: state.clone(),
+        },
+    };
+    let received = match path {
+        ReplayPath::JsonSnapshot { .. } => {
+            let json = serde_json::to_string(&message).context("encode JSON snapshot")?;
+            serde_json::from_str::<GameEventMessage>(&json).context("decode JSON snapshot")?
+        }
+        ReplayPath::MessagePackSnapshot { .. } => {
+            decode_game_event_frame(&encode_game_event_frame(&message)?)?
+        }
+        ReplayPath::EventReplay => unreachable!("not a snapshot path"),
+    };
+    let GameEvent::Snapshot { game_state } = received.event else {
+        anyhow::bail!("snapshot decoded as another event");
+    };
+    let engine = GameEngine::try_new_from_snapshot_state(0, game_state)?;
+    Ok(engine.committed_state().clone())
+}
+
+/// Run `state` to `end_tick` the way the live playback does, comparing each
+/// tick with `hashes` (indexed from `start_tick`).
+fn simulate_against(
+    mut state: GameState,
+    deferred_commands: &[(u32, GameCommandMessage)],
+    start_tick: u32,
+    end_tick: u32,
+    hashes: &[u64],
+    path: ReplayPath,
+) -> Option<Divergence> {
+    loop {
+        let tick = state.tick;
+        if let Some(divergence) = compare_hash(&state, start_tick, hashes, path) {
+            return Some(divergence);
+        }
+        if tick >= end_tick || state.is_complete() {
+            break;
+        }
+        // Input that had not arrived when the snapshot was taken reaches the
+        // restored state on the tick it reached live play.
+        for (_, command) in deferred_commands
+            .iter()
+            .filter(|(scheduled_at, _)| *scheduled_at == tick)
+        {
+            state.schedule_command(command);
+        }
+        if let Err(error) = state.tick_forward(false) {
+            return Some(Divergence {
+                path,
+                tick: tick + 1,
+                reason: format!("tick failed: {error:#}"),
+            });
+        }
+    }
+    (state.tick != end_tick).then(|| Divergence {
+        path,
+        tick: state.tick,
+        reason: format!(
+            "stopped at tick {}, live play ran to {end_tick}",
+            state.tick
+        ),
+    })
+}
+
+fn replay_events_against(
+    initial_state: &GameState,
+    events: &[(u32, u64, GameEvent)],
+    deferred_commands: &[(u32, GameCommandMessage)],
+    start_tick: u32,
+    end_tick: u32,
+    hashes: &[u64],
+) -> Option<Divergence> {
+    let path = ReplayPath::EventReplay;
+    let mut state = initial_state.clone();
+    let mut pending = events
+        .iter()
+        .filter(|(_, _, event)| !matches!(event, GameEvent::Snapshot { .. }))
+        .peekable();
+    for tick in start_tick + 1..=end_tick {
+        while let Some((_, sequence, event)) = pending.next_if(|(at, _, _)| *at == tick) {
+            let message = GameEventMessage {
+                game_id: 0,
+                tick,
+                sequence: *sequence,
+                stream_seq: 0,
+                user_id: None,
+                event: event.clone(),
+            };
+            match advance_and_apply_replicated_message(&state, &message) {
+                Ok(next) => state = next,
+                Err(error) => {
+                    return Some(Divergence {
+                        path,
+                        tick,
+                        reason: format!("event rejected: {error:#}"),
+                    });
+                }
+            }
+        }
+        while state.tick < tick {
+            if let Err(error) = state.tick_forward(true) {
+                return Some(Divergence {
+                    path,
+                    tick,
+                    reason: format!("movement failed: {error:#}"),
+                });
+            }
+        }
+        if let Some(divergence) = compare_hash(&state, start_tick, hashes, path) {
+            return Some(divergence);
+        }
+        // The playback queues input that arrives mid-run without publishing
+        // it. The server publishes each as `CommandScheduled` once the tick
+        // it arrives on is done, so it counts from the next one. Input from a
+        // player removed for inactivity is refused and never published.
+        for (_, command) in deferred_commands
+            .iter()
+            .filter(|(scheduled_at, _)| *scheduled_at == tick)
+        {
+            let user_id = command.command_id_client.user_id;
+            if !state.players.contains_key(&user_id) || state.is_player_idle_kicked(user_id) {
+                continue;
+            }
+            let message = GameEventMessage {
+                game_id: 0,
+                tick,
+                sequence: 0,
+                stream_seq: 0,
+                user_id: None,
+                event: GameEvent::CommandScheduled {
+                    command_message: command.clone(),
+                },
+            };
+            match advance_and_apply_replicated_message(&state, &message) {
+                Ok(next) => state = next,
+                Err(error) => {
+                    return Some(Divergence {
+                        path,
+                        tick,
+                        reason: format!("command rejected: {error:#}"),
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+fn compare_hash(
+    state: &GameState,
+    start_tick: u32,
+    hashes: &[u64],
+    path: ReplayPath,
+) -> Option<Divergence> {
+    let Some(live) = state
+        .tick
+        .checked_sub(start_tick)
+        .and_then(|offset| hashes.get(offset as usize))
+    else {
+        return Some(Divergence {
+            path,
+            tick: state.tick,
+            reason: "reached a tick live play never did".into(),
+        });
+    };
+    let actual = state.sync_hash();
+    (actual != *live).then(|| Divergence {
+        path,
+        tick: state.tick,
+        reason: format!("sync hash {actual:#018x}, live play had {live:#018x}"),
+    })
+}
+
+/// Greedily cut `script` down while it still diverges: a shorter run, fewer
+/// commands, snakes, and food, and no optional overrides.
+pub fn shrink(script: ScenarioScript, divergence: Divergence) -> (ScenarioScript, Divergence) {
+    shrink_with(script, divergence, |candidate| {
+        check_determinism(candidate).ok().flatten()
+    })
+}
+
+fn shrink_with(
+    mut script: ScenarioScript,
+    mut divergence: Divergence,
+    diverges: impl Fn(&ScenarioScript) -> Option<Divergence>,
+) -> (ScenarioScript, Divergence) {
+    'shrinking: loop {
+        for candidate in shrink_candidates(&script, &divergence) {
+            if candidate.validate().is_err() {
+                continue;
+            }
+            if let Some(found) = diverges(&candidate) {
+                script = candidate;
+                divergence = found;
+                continue 'shrinking;
+            }
+        }
+        return (script, divergence);
+    }
+}
+
+/// Every one-step reduction of `script`, most aggressive first.
+fn shrink_candidates(script: &ScenarioScript, divergence: &Divergence) -> Vec<ScenarioScript> {
+    let mut candidates = Vec::new();
+    let run_to_divergence = divergence
+        .tick
+        .saturating_sub(script.pose.start_tick)
+        .max(1);
+    if run_to_divergence < script.run_ticks {
+        let mut shorter = script.clone();
+        shorter.run_ticks = run_to_divergence;
+        shorter
+            .commands
+            .retain(|command| command.at_tick < run_to_divergence);
+        candidates.push(shorter);
+    }
+
+    let half = script.commands.len() / 2;
+    if half > 0 {
+        for kept in [
+            script.commands[..half].to_vec(),
+            script.commands[half..].to_vec(),
+        ] {
+            let mut fewer = script.clone();
+            fewer.commands = kept;
+            candidates.push(fewer);
+        }
+    }
+    for index in 0..script.commands.len() {
+        let mut fewer = script.clone();
+        fewer.commands.remove(index);
+        candidates.push(fewer);
+    }
+
+    // A captured pose must describe its state exactly, so only its commands
+    // and run length can shrink.
+    if script.pose.captured.is_some() {
+        return candidates;
+    }
+    if script.pose.snakes.len() > 1 {
+        for index in 0..script.pose.snakes.len() {
+            let mut fewer = script.clone();
+            let removed = fewer.pose.snakes.remove(index).user_id;
+            fewer.commands.retain(|command| command.user_id != removed);
+            candidates.push(fewer);
+        }
+    }
+    for index in 0..script.pose.food.len() {
+        let mut fewer = script.clone();
+        fewer.pose.food.remove(index);
+        candidates.push(fewer);
+    }
+    for index in 0..script.pose.snakes.len() {
+        if script.pose.snakes[index].boost_charge_ms > 0 {
+            let mut plainer = script.clone();
+            plainer.pose.snakes[index].boost_charge_ms = 0;
+            candidates.push(plainer);
+        }
+    }
+    if script.world.overrides.player_idle_timeout_ms.is_some() {
+        let mut plainer = script.clone();
+        plainer.world.overrides.player_idle_timeout_ms = None;
+        candidates.push(plainer);
+    }
+    if script.world.rng_seed.is_some() {
+        let mut plainer = script.clone();
+        plainer.world.rng_seed = None;
+        candidates.push(plainer);
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_generates_the_same_loadable_script() {
+        let mut loaded = 0;
+        for seed in 0..40 {
+            let script = generate_script(seed, DEFAULT_FUZZ_MAX_RUN_TICKS);
+            // Compared as values: a captured state's maps serialize in
+            // hash order.
+            assert_eq!(
+                serde_json::to_value(&script).unwrap(),
+                serde_json::to_value(generate_script(seed, DEFAULT_FUZZ_MAX_RUN_TICKS)).unwrap()
+            );
+            assert!(script.run_ticks <= DEFAULT_FUZZ_MAX_RUN_TICKS);
+            if script.load().is_ok() {
+                loaded += 1;
+            }
+        }
+        assert!(loaded >= 30, "only {loaded} of 40 generated scripts load");
+    }
+
+    #[test]
+    fn shrinking_keeps_only_what_the_failure_needs() {
+        let script = (0..)
+            .map(|seed| generate_script(seed, DEFAULT_FUZZ_MAX_RUN_TICKS))
+            .find(|script| {
+                script.pose.captured.is_none()
+                    && script.pose.snakes.len() > 2
+                    && script.commands.len() > 4
+                    && script.clone().load().is_ok()
+            })
+            .unwrap();
+        let culprit = script.commands[script.commands.len() / 2].clone();
+        // Stand-in for an engine bug that one command provokes.
+        let diverges = |candidate: &ScenarioScript| {
+            candidate.commands.contains(&culprit).then(|| Divergence {
+                path: ReplayPath::EventReplay,
+                tick: culprit.at_tick + 1,
+                reason: "stand-in".into(),
+            })
+        };
+        let first = diverges(&script).unwrap();
+
+        let (minimal, divergence) = shrink_with(script, first, diverges);
+        assert_eq!(minimal.commands, vec![culprit.clone()]);
+        assert_eq!(minimal.pose.snakes.len(), 1);
+        assert_eq!(minimal.pose.snakes[0].user_id, culprit.user_id);
+        assert!(minimal.pose.food.is_empty());
+        assert_eq!(minimal.world.rng_seed, None);
+        assert_eq!(minimal.run_ticks, culprit.at_tick + 1);
+        assert_eq!(divergence.tick, culprit.at_tick + 1);
+    }
+
+    #[test]
+    fn every_path_reports_the_first_tick_it_disagrees_at() {
+        let script = (0..)
+            .map(|seed| generate_script(seed, DEFAULT_FUZZ_MAX_RUN_TICKS))
+            .find(|script| script.run_ticks > 30 && script.clone().load().is_ok())
+            .unwrap();
+        assert_eq!(check_determinism(&script).unwrap(), None);
+
+        let loaded = script.load().unwrap();
+        let mut playback = loaded.playback();
+        let start_tick = playback.start_tick();
+        let mut hashes = vec![playback.state().sync_hash()];
+        while playback.advance_one().unwrap() {
+            hashes.push(playback.state().sync_hash());
+        }
+        let end_tick = playback.state().tick;
+        // Pretend live play reached a different state at tick 20.
+        hashes[20] ^= 1;
+
+        let replayed = replay_events_against(
+            &loaded.initial_state,
+            playback.events(),
+            loaded.deferred_commands(),
+            start_tick,
+            end_tick,
+            &hashes,
+        )
+        .unwrap();
+        assert_eq!(replayed.path, ReplayPath::EventReplay);
+        assert_eq!(replayed.tick, start_tick + 20);
+
+        let path = ReplayPath::MessagePackSnapshot {
+            from_tick: start_tick,
+        };
+        let restored = restore_snapshot(&loaded.initial_state, path).unwrap();
+        let resumed = simulate_against(
+            restored,
+            loaded.deferred_commands(),
+            start_tick,
+            end_tick,
+            &hashes,
+            path,
+        )
+        .unwrap();
+        assert_eq!(resumed.tick, start_tick + 20);
+        assert!(
+            resumed
+                .to_string()
+                .contains("MessagePack snapshot restored at tick")
+        );
+    }
+}
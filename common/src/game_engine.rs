@@ -1,8 +1,8 @@
 use crate::{
     BoostLifecycleTransition, CommandId, DEFAULT_TICK_INTERVAL_MS, GameCommand, GameCommandMessage,
-    GameEvent, GameEventMessage, GameState, GameType, QueueMode,
+    GameEvent, GameEventMessage, GameState, GameType, QueueMode, apply_snapshot_delta,
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::Serialize;
 
 /// How far past its last authoritative anchor the predicted state may free-run,
@@ -27,10 +27,21 @@ fn validate_client_snapshot_state(game_state: &GameState) -> Result<()> {
 /// This is the single movement-only catch-up contract for live clients,
 /// recorded highlights, and offline scoring. A malformed delta can never
 /// commit only its pre-event movement or one half of an atomic event.
+///
+/// A `SnapshotDelta` is refused: it describes a change from the snapshot at
+/// its base tick, not from `state`, which events or prediction may have moved
+/// past that base. Expand it first with [`expand_snapshot_delta`].
 pub fn advance_and_apply_replicated_message(
     state: &GameState,
     event_message: &GameEventMessage,
 ) -> Result<GameState> {
+    if let GameEvent::SnapshotDelta { base_tick, .. } = &event_message.event {
+        return Err(anyhow::anyhow!(
+            "snapshot delta from tick {base_tick} must be expanded against that snapshot, not applied to the state at tick {}",
+            state.tick
+        ));
+    }
+
     let is_snapshot = matches!(&event_message.event, GameEvent::Snapshot { .. });
     if let GameEvent::Snapshot { game_state } = &event_message.event {
         if game_state.tick != event_message.tick {
@@ -58,6 +69,32 @@ pub fn advance_and_apply_replicated_message(
     Ok(candidate)
 }
 
+/// Rebuild a `SnapshotDelta` message into the full `Snapshot` it stands for,
+/// from `snapshot_base`: the last authoritative snapshot the receiver applied,
+/// which must be the one at the delta's base tick.
+pub fn expand_snapshot_delta(
+    snapshot_base: &GameState,
+    event_message: &GameEventMessage,
+) -> Result<GameEventMessage> {
+    let GameEvent::SnapshotDelta {
+        base_tick,
+        changes,
+        hash,
+    } = &event_message.event
+    else {
+        return Err(anyhow::anyhow!("expected a snapshot delta"));
+    };
+    let game_state = apply_snapshot_delta(snapshot_base, *base_tick, changes, *hash)?;
+    Ok(GameEventMessage {
+        game_id: event_message.game_id,
+        tick: event_message.tick,
+        sequence: event_message.sequence,
+        stream_seq: event_message.stream_seq,
+        user_id: event_message.user_id,
+        event: GameEvent::Snapshot { game_state },
+    })
+}
+
 /// Client-side synchronization health, updated on every processed server
 /// message. Exposed to the UI so it can detect divergence (hash mismatches),
 /// message loss (stream gaps), and trigger a resync instead of drifting.
@@ -116,6 +153,10 @@ pub struct GameEngine {
     /// than the speculative execution tick.
     speculative_commands: Vec<GameCommandMessage>,
     sync_status: SyncStatus,
+    /// The last snapshot applied, full or rebuilt from a delta. A resync
+    /// request names its tick so the server can answer with a delta against
+    /// it; `None` until a snapshot arrives and after a delta fails.
+    snapshot_base: Option<GameState>,
     /// Authoritative input changed since the last prediction replay. A replay
     /// must happen even when wall-clock time has not crossed another tick so
     /// visual prediction (including crash cues) retracts in the same frame.
@@ -149,6 +190,7 @@ impl GameEngine {
             local_command_seq: 0,
             speculative_commands: Vec::new(),
             sync_status: SyncStatus::default(),
+            snapshot_base: None,
             prediction_needs_rebuild: false,
         }
     }
@@ -175,7 +217,9 @@ impl GameEngine {
                 settings.arena_height,
                 settings.tick_duration_ms,
             ),
-            GameType::TeamMatch { .. } => (60, 40, DEFAULT_TICK_INTERVAL_MS),
+            GameType::TeamMatch { .. }
+            | GameType::KingOfTheHill { .. }
+            | GameType::CaptureTheFlag { .. } => (60, 40, DEFAULT_TICK_INTERVAL_MS),
             _ => (40, 40, DEFAULT_TICK_INTERVAL_MS),
         };
 
@@ -204,6 +248,7 @@ impl GameEngine {
             local_command_seq: 0,
             speculative_commands: Vec::new(),
             sync_status: SyncStatus::default(),
+            snapshot_base: None,
             prediction_needs_rebuild: false,
         }
     }
@@ -269,6 +314,7 @@ impl GameEngine {
             local_command_seq: 0,
             speculative_commands: Vec::new(),
             sync_status: SyncStatus::default(),
+            snapshot_base: None,
             prediction_needs_rebuild: false,
         }
     }
@@ -377,6 +423,26 @@ impl GameEngine {
 
     /// Process a server event and reconcile with local predictions
     pub fn process_server_event(&mut self, event_message: &GameEventMessage) -> Result<()> {
+        if matches!(event_message.event, GameEvent::SnapshotDelta { .. }) {
+            // Expanded against the last authoritative snapshot, never the
+            // committed or predicted state, which have moved on since.
+            let expanded = self
+                .snapshot_base
+                .as_ref()
+                .context("received a snapshot delta without a base snapshot")
+                .and_then(|base| expand_snapshot_delta(base, event_message));
+            return match expanded {
+                Ok(snapshot) => self.process_server_event(&snapshot),
+                Err(error) => {
+                    // The base is no longer trusted: the resync this asks for
+                    // must fetch a full snapshot.
+                    self.snapshot_base = None;
+                    self.sync_status.needs_resync = true;
+                    Err(error)
+                }
+            };
+        }
+
         let is_snapshot = matches!(&event_message.event, GameEvent::Snapshot { .. });
         if let GameEvent::Snapshot { game_state } = &event_message.event {
             if game_state.tick != event_message.tick {
@@ -504,6 +570,7 @@ impl GameEngine {
             self.speculative_commands.clear();
             self.sync_status.needs_resync = false;
             self.sync_status.consecutive_hash_mismatches = 0;
+            self.snapshot_base = Some(self.committed_state.clone());
         }
 
         Ok(())
@@ -842,6 +909,12 @@ impl GameEngine {
         self.committed_state.sync_hash()
     }
 
+    /// Tick of the snapshot a `SnapshotDelta` may be computed against, for
+    /// the resync request to acknowledge.
+    pub fn snapshot_base_tick(&self) -> Option<u32> {
+        self.snapshot_base.as_ref().map(|base| base.tick)
+    }
+
     /// Call after a resync request has been issued so it isn't re-triggered
     /// every frame while the snapshot is in flight.
     pub fn clear_needs_resync(&mut self) {
@@ -1425,6 +1498,157 @@ mod tests {
         assert_eq!(predicted.speed_milli, NORMAL_SNAKE_SPEED_MILLI);
     }
 
+    fn snapshot_message(stream_seq: u64, event: GameEvent, tick: u32) -> GameEventMessage {
+        GameEventMessage {
+            game_id: 99,
+            tick,
+            sequence: 0,
+            stream_seq,
+            user_id: None,
+            event,
+        }
+    }
+
+    fn running_game() -> GameState {
+        let mut state = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(5),
+            0,
+        );
+        state.add_player(7, None).unwrap();
+        state.add_player(8, None).unwrap();
+        state.spawn_initial_food();
+        state.status = GameStatus::Started { server_id: 1 };
+        state
+    }
+
+    #[test]
+    fn snapshot_delta_rebuilds_against_the_last_snapshot() {
+        let base = running_game();
+        let mut engine = GameEngine::new(99, 0);
+        assert_eq!(engine.snapshot_base_tick(), None);
+        engine
+            .process_server_event(&snapshot_message(
+                3,
+                GameEvent::Snapshot {
+                    game_state: base.clone(),
+                },
+                base.tick,
+            ))
+            .unwrap();
+        assert_eq!(engine.snapshot_base_tick(), Some(base.tick));
+
+        let mut target = base.clone();
+        for _ in 0..20 {
+            target.tick_forward(false).unwrap();
+        }
+        let delta = crate::snapshot_delta(&base, &target).unwrap();
+        engine
+            .process_server_event(&snapshot_message(40, delta, target.tick))
+            .unwrap();
+
+        assert_eq!(engine.committed_sync_hash(), target.sync_hash());
+        assert_eq!(engine.snapshot_base_tick(), Some(target.tick));
+        // The delta re-anchors the stream exactly like a full snapshot.
+        assert_eq!(engine.sync_status().last_stream_seq, 40);
+        assert!(!engine.sync_status().needs_resync);
+    }
+
+    #[test]
+    fn snapshot_delta_expands_against_the_base_while_prediction_runs_ahead() {
+        let base = running_game();
+        let mut engine = GameEngine::new(99, 0);
+        engine
+            .process_server_event(&snapshot_message(
+                3,
+                GameEvent::Snapshot {
+                    game_state: base.clone(),
+                },
+                base.tick,
+            ))
+            .unwrap();
+        let start_ms = engine.committed_state().simulation_start_ms().unwrap();
+        let tick_ms = base.properties.tick_duration_ms as i64;
+        engine
+            .rebuild_predicted_state(start_ms + 10 * tick_ms)
+            .unwrap();
+        let predicted_tick = engine.predicted_state().unwrap().current_tick();
+        assert!(predicted_tick > base.tick);
+
+        let mut target = base.clone();
+        for _ in 0..20 {
+            target.tick_forward(false).unwrap();
+        }
+        let delta = snapshot_message(
+            40,
+            crate::snapshot_delta(&base, &target).unwrap(),
+            target.tick,
+        );
+
+        // The predicted state is past the base, so the delta cannot apply to it.
+        assert!(
+            advance_and_apply_replicated_message(engine.predicted_state().unwrap(), &delta)
+                .is_err()
+        );
+
+        engine.process_server_event(&delta).unwrap();
+        assert_eq!(engine.committed_sync_hash(), target.sync_hash());
+        assert_eq!(engine.snapshot_base_tick(), Some(target.tick));
+        assert!(!engine.sync_status().needs_resync);
+    }
+
+    #[test]
+    fn snapshot_delta_that_fails_its_hash_drops_the_base() {
+        let base = running_game();
+        let mut engine = GameEngine::new(99, 0);
+        engine
+            .process_server_event(&snapshot_message(
+                3,
+                GameEvent::Snapshot {
+                    game_state: base.clone(),
+                },
+                base.tick,
+            ))
+            .unwrap();
+        let committed_hash = engine.committed_sync_hash();
+
+        let mut target = base.clone();
+        target.tick_forward(false).unwrap();
+        let GameEvent::SnapshotDelta {
+            base_tick,
+            changes,
+            hash,
+        } = crate::snapshot_delta(&base, &target).unwrap()
+        else {
+            panic!("expected a snapshot delta");
+        };
+        let forged = GameEvent::SnapshotDelta {
+            base_tick,
+            changes,
+            hash: hash.wrapping_add(1),
+        };
+
+        assert!(
+            engine
+                .process_server_event(&snapshot_message(4, forged, target.tick))
+                .is_err()
+        );
+        assert_eq!(engine.committed_sync_hash(), committed_hash);
+        assert!(engine.sync_status().needs_resync);
+        assert_eq!(engine.snapshot_base_tick(), None);
+
+        // Without a base even a well-formed delta asks for a full snapshot.
+        let delta = crate::snapshot_delta(&base, &target).unwrap();
+        assert!(
+            engine
+                .process_server_event(&snapshot_message(5, delta, target.tick))
+                .is_err()
+        );
+    }
+
     #[test]
     fn malformed_boost_snapshot_is_rejected_before_it_can_run_forever() {
         let mut state = GameState::new(
@@ -1462,7 +1686,11 @@ mod tests {
             GameType::Solo => 1,
             GameType::TeamMatch { per_team } => u32::from(per_team) * 2,
             GameType::FreeForAll { max_players } => u32::from(max_players),
-            GameType::Custom { .. } => unreachable!("custom games are not legacy Boost modes"),
+            GameType::Custom { .. }
+            | GameType::KingOfTheHill { .. }
+            | GameType::CaptureTheFlag { .. } => {
+                unreachable!("{game_type:?} is not a legacy Boost mode")
+            }
         };
         for user_id in 1..=player_count {
             state
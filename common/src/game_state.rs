@@ -1,16 +1,24 @@
+use crate::arena::{ArenaMap, MapBoostPlacement};
+use crate::occupancy::body_cells;
 use crate::util::PseudoRandom;
 use crate::{
-    BOOST_RULES_VERSION, BOOST_SPOT_LAYOUT_VERSION_FIELD, BOOST_SPOT_LAYOUT_VERSION_NONE,
-    BOOST_SPOT_LAYOUT_VERSION_TEAM, BOOST_TICK_INTERVAL_MS, BoostResolution,
-    DEFAULT_BOOST_CAPACITY_MS, DEFAULT_BOOST_PACKET_CHARGE_MS, DEFAULT_BOOST_PAD_RESPAWN_MS,
-    DEFAULT_BOOST_SPEED_MILLI, DEFAULT_COMPETITIVE_TEAM_SCORE_LIMIT, DEFAULT_CUSTOM_GAME_TICK_MS,
-    DEFAULT_FOOD_TARGET, DEFAULT_PLAYER_IDLE_TIMEOUT_MS, DEFAULT_PLAYER_IDLE_WARNING_MS,
-    DEFAULT_QUICKMATCH_TEAM_SCORE_LIMIT, DEFAULT_TICK_INTERVAL_MS, Direction,
-    MAX_BOOST_SPEED_MILLI, NORMAL_SNAKE_SPEED_MILLI, Player, Position, Snake, SnakeBoost,
-    SnakeCombo,
+    AiDifficulty, AiOpponents, AiSeat, BOOST_RULES_VERSION, BOOST_SPOT_LAYOUT_VERSION_FIELD,
+    BOOST_SPOT_LAYOUT_VERSION_MAP, BOOST_SPOT_LAYOUT_VERSION_NONE, BOOST_SPOT_LAYOUT_VERSION_TEAM,
+    BOOST_TICK_INTERVAL_MS, BoostResolution, DEFAULT_BOOST_CAPACITY_MS,
+    DEFAULT_BOOST_PACKET_CHARGE_MS, DEFAULT_BOOST_PAD_RESPAWN_MS, DEFAULT_BOOST_SPEED_MILLI,
+    DEFAULT_COMPETITIVE_TEAM_ROUNDS_TO_WIN, DEFAULT_COMPETITIVE_TEAM_SCORE_LIMIT,
+    DEFAULT_CUSTOM_GAME_TICK_MS, DEFAULT_FOOD_TARGET, DEFAULT_PLAYER_IDLE_TIMEOUT_MS,
+    DEFAULT_PLAYER_IDLE_WARNING_MS, DEFAULT_QUICKMATCH_TEAM_SCORE_LIMIT, DEFAULT_TICK_INTERVAL_MS,
+    DeathFoodConfig, Direction, Flag, FlagConfig, FlagPlacement, HillConfig, HillControl,
+    MAX_BOOST_SPEED_MILLI, NORMAL_SNAKE_SPEED_MILLI, OccupancyGrid, Player, Position,
+    PowerUpConfig, PowerUpKind, PowerUpPickup, Snake, SnakeBoost, SnakeCombo, SnakePowerUps,
+    StateChange, ZoneBounds, ZoneConfig, ai_user_id, apply_snapshot_delta, calculate_ai_command_at,
+    death_food_config_for, flag_config_for, flag_score_limit, hill_config_for, hill_score_limit,
+    power_up_config_for, zone_config_for,
 };
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, ensure};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
@@ -59,7 +67,7 @@ enum FoodAxisDistribution {
 /// and 2v2 therefore keep their existing center pressure only on `x`, while
 /// every non-team mode distributes food uniformly on both axes.
 fn food_axis_distributions(game_type: &GameType) -> (FoodAxisDistribution, FoodAxisDistribution) {
-    if matches!(game_type, GameType::TeamMatch { .. }) {
+    if game_type.is_team_game() {
         (
             FoodAxisDistribution::CenterBiased,
             FoodAxisDistribution::Uniform,
@@ -203,6 +211,14 @@ pub enum GameEvent {
         user_id: u32,
         snake_id: u32,
     },
+    /// The AI took over an absent player's snake (`enabled`), or handed it
+    /// back because the player is sending input again. Only matches with
+    /// `properties.idle_autopilot` set emit it.
+    PlayerAutopilotChanged {
+        user_id: u32,
+        snake_id: u32,
+        enabled: bool,
+    },
     FoodSpawned {
         position: Position,
     },
@@ -228,9 +244,71 @@ pub enum GameEvent {
         charge_ms_after: u32,
         respawn_at_tick: u32,
     },
+    /// A power-up appeared on the field. Only the authoritative simulation
+    /// rolls these, exactly like food; replicas learn of them from this event.
+    PowerUpSpawned {
+        kind: PowerUpKind,
+        position: Position,
+    },
+    /// A snake's head reached a power-up and took its effect.
+    PowerUpCollected {
+        snake_id: u32,
+        kind: PowerUpKind,
+        position: Position,
+    },
+    /// A shield absorbed a crash. The snake keeps its life and stays where it
+    /// was before the move that would have killed it; only the shield is gone.
+    ShieldBroken {
+        snake_id: u32,
+    },
+    /// The battle royale zone lost a ring. Every simulation derives this from
+    /// the tick, so replicas have usually drawn it already when it arrives.
+    ZoneContracted {
+        bounds: ZoneBounds,
+    },
+    /// A team became the only side on the hill while the other held it, or
+    /// while nobody did. Like the zone, every simulation derives this from
+    /// positions, so replicas have usually drawn it already when it arrives.
+    HillCaptured {
+        team_id: TeamId,
+        previous_holder: Option<TeamId>,
+    },
+    /// An enemy head reached a team's flag, at home or where it was dropped.
+    FlagPickedUp {
+        team_id: TeamId,
+        snake_id: u32,
+    },
+    /// A flag's carrier died or left the match, and the flag fell on the last
+    /// cell its head held. `at_tick` starts the flag's return countdown.
+    FlagDropped {
+        team_id: TeamId,
+        snake_id: u32,
+        position: Position,
+        at_tick: u32,
+    },
+    /// A dropped flag went back to its stand, touched by a defender or left
+    /// alone for the config's `return_ms`.
+    FlagReturned {
+        team_id: TeamId,
+    },
+    /// A carrier brought the enemy flag into its own base. The flag goes home;
+    /// the point follows as `TeamScoreUpdated`.
+    FlagCaptured {
+        team_id: TeamId,
+        snake_id: u32,
+    },
     Snapshot {
         game_state: GameState,
     },
+    /// A snapshot sent as the changes from an earlier one the client
+    /// acknowledged, at `base_tick`. `hash` is the `sync_hash` of the state
+    /// the changes produce; see `apply_snapshot_delta`.
+    SnapshotDelta {
+        base_tick: u32,
+        changes: Vec<StateChange>,
+        #[cfg_attr(feature = "ts-gen", ts(type = "number"))]
+        hash: u64,
+    },
     CommandScheduled {
         command_message: GameCommandMessage,
     },
@@ -286,6 +364,23 @@ pub enum GameEvent {
         team_id: TeamId,
         score: u32,
     },
+    /// A round of a best-of-N team match was decided. `winning_team_id` is
+    /// `None` when both teams crossed the score limit level on the same tick;
+    /// that round counts as played but is won by nobody. Team scores are left
+    /// as the round finished so the result stays readable, and on the deciding
+    /// round they are the final ones.
+    RoundEnded {
+        round_number: u32,
+        winning_team_id: Option<TeamId>,
+    },
+    /// The next round begins. Team scores return to zero, food and pad
+    /// cooldowns are cleared, and every snake still in the match is put back
+    /// on its starting cell with a fresh life. The reset is derived entirely
+    /// from state, so this one event carries it to every replica; the food
+    /// for the new round follows as ordinary `FoodSpawned` events.
+    RoundStarted {
+        round_number: u32,
+    },
 
     SnakeRespawned {
         snake_id: u32,
@@ -333,6 +428,8 @@ pub enum DeathCause {
     },
     /// Team-mode banking intentionally resets and respawns the scoring snake.
     Banked,
+    /// The battle royale zone closed over the snake's head.
+    Zone,
 }
 
 fn default_food_eaten_points() -> u32 {
@@ -455,6 +552,15 @@ impl BoostConfig {
         }
     }
 
+    /// The collectible tank on a map that places its own pads. Same balance
+    /// as the team default.
+    pub fn placed() -> Self {
+        Self {
+            spot_layout_version: BOOST_SPOT_LAYOUT_VERSION_MAP,
+            ..Self::default()
+        }
+    }
+
     /// A tank that never empties and has nothing to collect.
     pub fn unlimited() -> Self {
         Self {
@@ -498,15 +604,17 @@ impl BoostConfig {
             self.spot_layout_version,
             BOOST_SPOT_LAYOUT_VERSION_TEAM
                 | BOOST_SPOT_LAYOUT_VERSION_FIELD
+                | BOOST_SPOT_LAYOUT_VERSION_MAP
                 | BOOST_SPOT_LAYOUT_VERSION_NONE
         );
         if !known_layout {
             return Err(anyhow::anyhow!(
-                "unsupported Boost spot layout version {}, expected one of {}, {} or {}",
+                "unsupported Boost spot layout version {}, expected one of {}, {}, {} or {}",
                 self.spot_layout_version,
                 BOOST_SPOT_LAYOUT_VERSION_NONE,
                 BOOST_SPOT_LAYOUT_VERSION_TEAM,
-                BOOST_SPOT_LAYOUT_VERSION_FIELD
+                BOOST_SPOT_LAYOUT_VERSION_FIELD,
+                BOOST_SPOT_LAYOUT_VERSION_MAP
             ));
         }
         // The padless layout and the unlimited tank imply one another: pickups
@@ -593,6 +701,29 @@ pub struct Arena {
     #[serde(default)]
     pub boost_pads: Vec<BoostPad>,
     pub team_zone_config: Option<TeamZoneConfig>, // New field - minimal state
+    /// The match's map when it is not the implicit one described by the
+    /// dimensions and `team_zone_config` alone (see `ArenaMap::implicit`).
+    /// Classic matches leave this empty, so their snapshots and fingerprints
+    /// are exactly those from before maps existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<ArenaMap>,
+    /// Uncollected power-ups, in spawn order. Always empty in modes without
+    /// power-ups, and omitted when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub power_ups: Vec<PowerUpPickup>,
+    /// The battle royale safe area. Present exactly when the match has a zone
+    /// schedule, and always the schedule's bounds for the current tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<ZoneBounds>,
+    /// What the field's edge does to a snake that reaches it. Omitted for the
+    /// usual solid edge, so those snapshots are unchanged.
+    #[serde(default, skip_serializing_if = "EdgePolicy::is_solid")]
+    pub edges: EdgePolicy,
+    /// Which snakes cover which cells, kept up to date as snakes step (see
+    /// `Arena::occupancy`). Derived from `snakes`, so never serialized.
+    #[serde(skip)]
+    #[cfg_attr(feature = "ts-gen", ts(skip))]
+    pub occupancy: OccupancyGrid,
 }
 
 impl Arena {
@@ -609,6 +740,182 @@ impl Arena {
         self.boost_pads.iter().any(|pad| pad.contains(position))
     }
 
+    pub fn wraps(&self) -> bool {
+        self.edges == EdgePolicy::Wrap
+    }
+
+    /// The field cell `position` lands on. On a wrapping field every
+    /// coordinate is taken modulo the field's size; a solid field leaves
+    /// positions as they are, so an off-field cell stays recognisably off it.
+    pub fn wrap_position(&self, position: Position) -> Position {
+        if !self.wraps() {
+            return position;
+        }
+        Position {
+            x: position.x.rem_euclid(self.width.max(1) as i16),
+            y: position.y.rem_euclid(self.height.max(1) as i16),
+        }
+    }
+
+    /// Whole-field translations at which `snake` is (partly) visible.
+    ///
+    /// A snake on a wrapping field keeps its body as one unbroken run of
+    /// cells, with only the head held on the field (see
+    /// `Snake::step_forward_wrapping`). The part of the body that has crossed
+    /// a seam lies off the field and shows up again shifted by the field's
+    /// width or height. A solid field, or a snake clear of every seam, has the
+    /// single translation `(0, 0)`.
+    pub fn seam_offsets(&self, snake: &Snake) -> Vec<(i16, i16)> {
+        if !self.wraps() || snake.body.is_empty() {
+            return vec![(0, 0)];
+        }
+        let (width, height) = (i32::from(self.width.max(1)), i32::from(self.height.max(1)));
+        let min_x = snake.body.iter().map(|p| i32::from(p.x)).min().unwrap_or(0);
+        let max_x = snake.body.iter().map(|p| i32::from(p.x)).max().unwrap_or(0);
+        let min_y = snake.body.iter().map(|p| i32::from(p.y)).min().unwrap_or(0);
+        let max_y = snake.body.iter().map(|p| i32::from(p.y)).max().unwrap_or(0);
+        // Copy k spans [min + k * size, max + k * size]; keep the copies
+        // that overlap [0, size).
+        let copies = |min: i32, max: i32, size: i32| {
+            -max.div_euclid(size)..=(size - 1 - min).div_euclid(size)
+        };
+        let mut offsets = Vec::new();
+        for kx in copies(min_x, max_x, width) {
+            for ky in copies(min_y, max_y, height) {
+                offsets.push(((kx * width) as i16, (ky * height) as i16));
+            }
+        }
+        offsets
+    }
+
+    /// Whether `snake` covers the field cell `point`, honouring the edge
+    /// policy. This is the occupancy test every collision, spawn and AI check
+    /// goes through; `Snake::contains_point` alone only knows about the cells
+    /// the body literally lists.
+    pub fn snake_contains(&self, snake: &Snake, point: &Position, skip_head: bool) -> bool {
+        if !self.wraps() {
+            return snake.contains_point(point, skip_head);
+        }
+        self.seam_offsets(snake).into_iter().any(|(dx, dy)| {
+            let unshifted = Position {
+                x: point.x - dx,
+                y: point.y - dy,
+            };
+            // Only the on-field copy holds the head.
+            snake.contains_point(&unshifted, skip_head && (dx, dy) == (0, 0))
+        })
+    }
+
+    /// Every field cell `snake` covers, head to tail, with the parts past a
+    /// seam brought back onto the field.
+    pub fn body_cells(&self, snake: &Snake) -> Vec<Position> {
+        body_cells(&snake.body)
+            .map(|cell| self.wrap_position(cell))
+            .collect()
+    }
+
+    /// The occupancy grid, if it describes the snakes as they are now.
+    ///
+    /// Checking costs one comparison of every compressed body, so callers
+    /// about to ask many questions fetch the grid once and fall back to
+    /// `snake_contains` when it is stale.
+    pub fn occupancy(&self) -> Option<&OccupancyGrid> {
+        self.occupancy
+            .is_current(self.width, self.height, self.wraps(), &self.snakes)
+            .then_some(&self.occupancy)
+    }
+
+    /// Bring the occupancy grid up to date with the snakes, reindexing only
+    /// the ones whose bodies changed since it last saw them.
+    pub fn sync_occupancy(&mut self) {
+        let wraps = self.wraps();
+        self.occupancy
+            .sync(self.width, self.height, wraps, &self.snakes);
+    }
+
+    /// Move `snake_id` one cell under the field's edge policy, updating the
+    /// occupancy grid by the cells it entered and left.
+    pub fn step_snake(&mut self, snake_id: usize) {
+        let wraps = self.wraps();
+        let (width, height) = (self.width, self.height);
+        let Some(snake) = self.snakes.get_mut(snake_id) else {
+            return;
+        };
+        let held = self.occupancy.holds(snake_id, &snake.body);
+        let old_tail = snake.body.last().copied();
+        if wraps {
+            snake.step_forward_wrapping(width, height);
+        } else {
+            snake.step_forward();
+        }
+        if let Some(old_tail) = old_tail
+            && held
+            && !self.occupancy.holds(snake_id, &snake.body)
+        {
+            self.occupancy.step(snake_id, old_tail, &snake.body);
+        }
+    }
+
+    /// Whether the battle royale zone has closed over `position`. Always false
+    /// in a match without a zone.
+    pub fn is_outside_zone(&self, position: &Position) -> bool {
+        self.zone.is_some_and(|zone| !zone.contains(position))
+    }
+
+    pub fn power_up_at(&self, position: &Position) -> Option<&PowerUpPickup> {
+        self.power_ups
+            .iter()
+            .find(|pickup| pickup.position == *position)
+    }
+
+    /// The map this arena was built from, whether stored or implicit.
+    pub fn map(&self) -> Cow<'_, ArenaMap> {
+        match &self.map {
+            Some(map) => Cow::Borrowed(map),
+            None => Cow::Owned(ArenaMap::implicit(
+                self.width,
+                self.height,
+                self.team_zone_config.clone(),
+            )),
+        }
+    }
+
+    /// Whether a map obstacle covers this cell. Implicit maps have none, so
+    /// this is free on every classic arena.
+    pub fn is_obstacle_position(&self, position: &Position) -> bool {
+        self.map
+            .as_ref()
+            .is_some_and(|map| map.is_obstacle_position(position))
+    }
+
+    /// A stored map must describe this very arena: the simulation reads
+    /// dimensions and zones from the arena and obstacles from the map, so a
+    /// disagreement would make two readers of one snapshot see two fields.
+    fn validate_map(&self) -> Result<()> {
+        let Some(map) = &self.map else {
+            return Ok(());
+        };
+        map.validate()?;
+        if map.width != self.width
+            || map.height != self.height
+            || map.team_zones != self.team_zone_config
+        {
+            return Err(anyhow::anyhow!(
+                "arena map {:?} does not match the {}x{} arena it is attached to",
+                map.id,
+                self.width,
+                self.height
+            ));
+        }
+        if map.is_implicit() {
+            return Err(anyhow::anyhow!(
+                "implicit arena map {:?} must not be stored explicitly",
+                map.id
+            ));
+        }
+        Ok(())
+    }
+
     /// The playable span a Boost layout is drawn inside, by layout version.
     ///
     /// Team layouts inset the field by the end zones so no pad can sit in a
@@ -634,6 +941,14 @@ impl Arena {
                 (self.team_zone_config.is_none() && self.width == 40 && self.height == 40)
                     .then_some((0, self.width as i16 - 1))
             }
+            BOOST_SPOT_LAYOUT_VERSION_MAP => {
+                // Placed pads obey the same zone rule as drawn ones; balancing
+                // their positions is the map author's job.
+                matches!(self.map().boost, MapBoostPlacement::Pads { .. }).then(|| {
+                    self.main_field_bounds()
+                        .unwrap_or((0, self.width as i16 - 1))
+                })
+            }
             _ => None,
         }
     }
@@ -653,6 +968,26 @@ impl Arena {
         let Some((left, right)) = self.boost_field_bounds(config.spot_layout_version) else {
             return Vec::new();
         };
+        if config.spot_layout_version == BOOST_SPOT_LAYOUT_VERSION_MAP {
+            let MapBoostPlacement::Pads { pads } = &self.map().boost else {
+                return Vec::new();
+            };
+            return pads
+                .iter()
+                .enumerate()
+                .map(|(id, pad)| BoostPad {
+                    id: id as u8,
+                    position: pad.position,
+                    charge_ms: if pad.size_cells == 2 {
+                        config.capacity_ms
+                    } else {
+                        config.packet_charge_ms
+                    },
+                    size_cells: pad.size_cells,
+                    respawn_at_tick: None,
+                })
+                .collect();
+        }
 
         let field_width = right - left + 1;
         let arena_bottom = self.height as i16 - 1;
@@ -856,8 +1191,13 @@ impl Arena {
         })
     }
 
-    /// Check if a position is within a wall (not in goal opening)
+    /// Check if a position is within a wall (not in goal opening) or a map
+    /// obstacle. Both kill a snake the same way, so collision, food placement
+    /// and highlight analysis all ask this one question.
     pub fn is_wall_position(&self, pos: &Position) -> bool {
+        if self.is_obstacle_position(pos) {
+            return true;
+        }
         if let Some(config) = &self.team_zone_config {
             // Check if at zone boundary
             let at_team_a_boundary = pos.x == config.end_zone_depth as i16 - 1;
@@ -977,6 +1317,32 @@ pub struct GameProperties {
     pub player_idle_timeout_ms: u32,
     #[serde(default = "default_player_idle_warning_ms")]
     pub player_idle_warning_ms: u32,
+    /// Difficulty the AI drives an idle or disconnected team player at, in
+    /// place of removing them, as long as a teammate is still playing. `None`
+    /// removes them as before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub idle_autopilot: Option<AiDifficulty>,
+    /// Power-up spawn rules, present only for modes that opt in (see
+    /// `power_up_config_for`). Absent from every other snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_ups: Option<PowerUpConfig>,
+    /// Battle royale zone schedule, present only for modes that opt in (see
+    /// `zone_config_for`). Absent from every other snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<ZoneConfig>,
+    /// King-of-the-hill rules, present only in that mode (see
+    /// `hill_config_for`). Absent from every other snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hill: Option<HillConfig>,
+    /// Capture-the-flag rules, present only in that mode (see
+    /// `flag_config_for`). Absent from every other snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flags: Option<FlagConfig>,
+    /// Death food rules, present only for modes that opt in (see
+    /// `death_food_config_for`). Absent from every other snapshot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub death_food: Option<DeathFoodConfig>,
 }
 
 fn default_player_idle_timeout_ms() -> u32 {
@@ -987,6 +1353,34 @@ fn default_player_idle_warning_ms() -> u32 {
     DEFAULT_PLAYER_IDLE_WARNING_MS
 }
 
+/// What the edge of the field does to a snake that runs into it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum EdgePolicy {
+    /// The edge is deadly: leaving the field is `DeathCause::OutOfBounds`.
+    #[default]
+    Solid,
+    /// Classic wraparound. A snake leaving one side comes back in on the
+    /// opposite side, and so does everything that looks for it there.
+    Wrap,
+}
+
+impl EdgePolicy {
+    pub fn is_solid(&self) -> bool {
+        *self == EdgePolicy::Solid
+    }
+}
+
+/// The edge policy a mode plays with. Only Custom lobbies can choose to wrap;
+/// matchmade modes, and team matches with their end zones, keep solid edges.
+pub fn edge_policy_for(game_type: &GameType) -> EdgePolicy {
+    match game_type {
+        GameType::Custom { settings } => settings.edge_policy,
+        _ => EdgePolicy::Solid,
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-gen", ts(export))]
@@ -1000,6 +1394,24 @@ pub struct CustomGameSettings {
     pub is_private: bool,
     pub allow_spectators: bool,
     pub snake_start_length: u8,
+    /// Spawn shields, ghosts and magnets on the field. Lobbies created before
+    /// power-ups existed play without them.
+    #[serde(default)]
+    pub power_ups: bool,
+    /// Battle royale: the field contracts until the last snake standing wins.
+    /// Only honoured in free-for-all lobbies.
+    #[serde(default)]
+    pub shrinking_zone: bool,
+    /// Lobbies created before wraparound existed have solid edges.
+    #[serde(default)]
+    pub edge_policy: EdgePolicy,
+    /// A fallen snake's body turns into food where it lay.
+    #[serde(default)]
+    pub death_food: bool,
+    /// Seats the host wants filled with AI opponents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub ai_opponents: Option<AiOpponents>,
 }
 
 impl Default for CustomGameSettings {
@@ -1014,6 +1426,11 @@ impl Default for CustomGameSettings {
             is_private: true,
             allow_spectators: true,
             snake_start_length: 4,
+            power_ups: false,
+            shrinking_zone: false,
+            edge_policy: EdgePolicy::Solid,
+            death_food: false,
+            ai_opponents: None,
         }
     }
 }
@@ -1045,24 +1462,71 @@ pub fn team_score_limit(queue_mode: &QueueMode) -> u32 {
     }
 }
 
+/// Rounds a team must win to take the match, by queue. Each round is raced to
+/// `team_score_limit`; a single-round queue needs no round bookkeeping at all,
+/// so its matches carry no `RoundState`.
+pub fn team_rounds_to_win(queue_mode: &QueueMode) -> u32 {
+    match queue_mode {
+        QueueMode::Quickmatch => 1,
+        QueueMode::Competitive => DEFAULT_COMPETITIVE_TEAM_ROUNDS_TO_WIN,
+    }
+}
+
+/// The team score that ends a match (or, best of N, a round). Only the team
+/// modes race to a score; Solo, free-for-all and Custom games end when every
+/// snake is dead, so a target there would be a win condition nothing in the
+/// engine ever tests.
+pub fn score_limit_for(game_type: &GameType, queue_mode: &QueueMode) -> Option<u32> {
+    match game_type {
+        GameType::TeamMatch { .. } => Some(team_score_limit(queue_mode)),
+        GameType::KingOfTheHill { .. } => Some(hill_score_limit(queue_mode)),
+        GameType::CaptureTheFlag { .. } => Some(flag_score_limit(queue_mode)),
+        _ => None,
+    }
+}
+
 /// Which Boost fuel model a mode gets on a given map, if any.
 ///
-/// The two collectible models place a fixed pad geometry, and a geometry only
-/// exists for that mode's canonical map — so a match on any other size gets no
-/// Boost rather than a half-drawn one. That keeps `GameState::new` total: it is
-/// called with arbitrary dimensions by tests, previews and Custom games, and
-/// none of those should fail to construct.
+/// The collectible models need pads, and the map decides where those are: a
+/// classic map names its drawn layout, a newer map places pads itself, and a
+/// map that offers neither (including the open field synthesized for any
+/// non-canonical size) gets no Boost rather than a half-drawn one. That keeps
+/// `GameState::new` total: it is called with arbitrary dimensions by tests,
+/// previews and Custom games, and none of those should fail to construct.
+///
+/// A drawn layout only serves the mode it was drawn for, and placed pads only
+/// serve a map whose zones fit the mode.
 ///
 /// Solo is unlimited because a lone runner has nobody to contest pads with, so
 /// a meter would only ration a mechanic that exists to feel fast. Needing no
 /// geometry, it works on every map.
-pub fn boost_config_for(game_type: &GameType, width: u16, height: u16) -> Option<BoostConfig> {
-    match game_type {
-        GameType::TeamMatch { per_team: 1 | 2 } if (width, height) == (60, 40) => {
-            Some(BoostConfig::default())
+pub fn boost_config_for(game_type: &GameType, map: &ArenaMap) -> Option<BoostConfig> {
+    let team_map = map.team_zones.is_some();
+    match (game_type, &map.boost) {
+        (GameType::Solo, _) => Some(BoostConfig::unlimited()),
+        (
+            GameType::TeamMatch { per_team: 1 | 2 }
+            | GameType::KingOfTheHill { per_team: 1 | 2 }
+            | GameType::CaptureTheFlag { per_team: 1 | 2 },
+            MapBoostPlacement::Layout {
+                spot_layout_version: BOOST_SPOT_LAYOUT_VERSION_TEAM,
+            },
+        ) => Some(BoostConfig::default()),
+        (
+            GameType::FreeForAll { .. },
+            MapBoostPlacement::Layout {
+                spot_layout_version: BOOST_SPOT_LAYOUT_VERSION_FIELD,
+            },
+        ) => Some(BoostConfig::field()),
+        (
+            GameType::TeamMatch { per_team: 1 | 2 }
+            | GameType::KingOfTheHill { per_team: 1 | 2 }
+            | GameType::CaptureTheFlag { per_team: 1 | 2 },
+            MapBoostPlacement::Pads { .. },
+        ) if team_map => Some(BoostConfig::placed()),
+        (GameType::FreeForAll { .. }, MapBoostPlacement::Pads { .. }) if !team_map => {
+            Some(BoostConfig::placed())
         }
-        GameType::FreeForAll { .. } if (width, height) == (40, 40) => Some(BoostConfig::field()),
-        GameType::Solo => Some(BoostConfig::unlimited()),
         _ => None,
     }
 }
@@ -1076,9 +1540,10 @@ pub fn boost_config_for(game_type: &GameType, width: u16, height: u16) -> Option
 /// per-mode value is decided.
 pub fn food_target_for(game_type: &GameType) -> usize {
     match game_type {
-        GameType::TeamMatch { per_team: 2 } | GameType::FreeForAll { .. } => {
-            DEFAULT_FOOD_TARGET * 2
-        }
+        GameType::TeamMatch { per_team: 2 }
+        | GameType::KingOfTheHill { per_team: 2 }
+        | GameType::CaptureTheFlag { per_team: 2 }
+        | GameType::FreeForAll { .. } => DEFAULT_FOOD_TARGET * 2,
         _ => DEFAULT_FOOD_TARGET,
     }
 }
@@ -1088,9 +1553,40 @@ pub fn food_target_for(game_type: &GameType) -> usize {
 #[cfg_attr(feature = "ts-gen", ts(export))]
 pub enum GameType {
     Solo,
-    TeamMatch { per_team: u8 },
-    FreeForAll { max_players: u8 },
-    Custom { settings: CustomGameSettings },
+    TeamMatch {
+        per_team: u8,
+    },
+    FreeForAll {
+        max_players: u8,
+    },
+    Custom {
+        settings: CustomGameSettings,
+    },
+    /// Two teams race to a score earned by holding the centre of the field
+    /// instead of banking food (see `hill_config_for`).
+    KingOfTheHill {
+        per_team: u8,
+    },
+    /// Two teams score by carrying the enemy flag out of its base and home
+    /// into their own (see `flag_config_for`).
+    CaptureTheFlag {
+        per_team: u8,
+    },
+}
+
+impl GameType {
+    /// Whether two teams share the match: snakes carry a `TeamId`, the arena
+    /// has end zones to spawn and respawn in, and the result is decided by
+    /// `team_scores`. Team matches bank food for their score; king of the
+    /// hill earns it on the hill, and capture the flag with flags.
+    pub fn is_team_game(&self) -> bool {
+        matches!(
+            self,
+            GameType::TeamMatch { .. }
+                | GameType::KingOfTheHill { .. }
+                | GameType::CaptureTheFlag { .. }
+        )
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -1381,6 +1877,95 @@ pub struct GameState {
     #[serde(default)]
     #[cfg_attr(feature = "ts-gen", ts(type = "number | null"))]
     pub simulation_epoch_ms: Option<i64>,
+
+    /// Best-of-N progress for team matches played over several rounds.
+    /// `None` is a single-round match — every Quickmatch, every other mode,
+    /// and every state written before rounds existed — which ends the moment
+    /// a team reaches the score limit, exactly as it always has.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<RoundState>,
+
+    /// Who holds the hill, present exactly when `properties.hill` is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hill: Option<HillControl>,
+
+    /// Both teams' flags, in team order, present exactly when
+    /// `properties.flags` is.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flags: Vec<Flag>,
+
+    /// Players the simulation drives itself, by user_id. Their snakes are
+    /// steered inside `tick_forward`, so every engine that simulates the match
+    /// makes the same decisions; they are never idle-kicked and earn no XP.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[cfg_attr(feature = "ts-gen", ts(type = "Record<number, AiSeat>"))]
+    pub ai_players: HashMap<u32, AiSeat>,
+
+    /// Absent players whose snakes the AI is driving until they send input
+    /// again, by user_id. Unlike `ai_players` these are people, so a match
+    /// with them stays rated.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[cfg_attr(feature = "ts-gen", ts(type = "Record<number, AiSeat>"))]
+    pub autopilot_players: HashMap<u32, AiSeat>,
+
+    /// Every player the AI has driven at some point this match, sorted. They
+    /// earn no XP or rating from it, even after taking back control.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub autopiloted_user_ids: Vec<u32>,
+}
+
+/// Round bookkeeping for a best-of-N team match.
+///
+/// A round ends when a team reaches the score limit; the field is then reset
+/// and the next round starts from zero. Ending and starting a round are two
+/// separate replicated events, and the two counters here are what make each of
+/// them idempotent: a round can only end while it is the one being played, and
+/// the next one can only start after it has ended.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct RoundState {
+    pub rounds_to_win: u32,
+    /// One-based number of the round being played, or of the round that just
+    /// ended while the next has not started yet.
+    pub current_round: u32,
+    /// Rounds that have ended, drawn ones included. Equal to `current_round`
+    /// between a round's end and the next one's start, one less otherwise.
+    pub rounds_completed: u32,
+    /// Rounds won by each team. A drawn round is completed but won by nobody.
+    #[cfg_attr(feature = "ts-gen", ts(type = "Record<number, number>"))]
+    pub round_wins: HashMap<TeamId, u32>,
+}
+
+impl RoundState {
+    fn new(rounds_to_win: u32) -> Self {
+        RoundState {
+            rounds_to_win,
+            current_round: 1,
+            rounds_completed: 0,
+            round_wins: HashMap::from([(TeamId(0), 0), (TeamId(1), 0)]),
+        }
+    }
+
+    pub fn wins(&self, team_id: TeamId) -> u32 {
+        self.round_wins.get(&team_id).copied().unwrap_or(0)
+    }
+
+    /// The team that has taken enough rounds to win the match, if any.
+    pub fn match_winner(&self) -> Option<TeamId> {
+        let mut winners: Vec<TeamId> = self
+            .round_wins
+            .iter()
+            .filter(|(_, wins)| **wins >= self.rounds_to_win)
+            .map(|(team_id, _)| *team_id)
+            .collect();
+        winners.sort_unstable();
+        winners.first().copied()
+    }
+
+    fn is_between_rounds(&self) -> bool {
+        self.rounds_completed == self.current_round
+    }
 }
 
 /// The pre-match readiness gate: who still has to confirm, and when the match
@@ -1478,7 +2063,40 @@ impl GameState {
         rng_seed: Option<u64>,
         start_ms: i64,
     ) -> Self {
-        let boost = boost_config_for(&game_type, width, height);
+        // `new` predates fallible match construction. Keep its API stable but
+        // fail closed if an eligible map cannot materialize a complete valid
+        // Boost layout; production builders use canonical dimensions.
+        Self::new_on_map(
+            &ArenaMap::default_for(&game_type, width, height),
+            game_type,
+            queue_mode,
+            rng_seed,
+            start_ms,
+        )
+        .expect("new game must satisfy Boost invariants")
+    }
+
+    /// Construct a match on an explicit map. The map fixes the arena's size,
+    /// zones, obstacles, spawns and Boost pads; `boost_config_for` decides
+    /// from it whether this mode plays with Boost at all.
+    pub fn new_on_map(
+        map: &ArenaMap,
+        game_type: GameType,
+        queue_mode: QueueMode,
+        rng_seed: Option<u64>,
+        start_ms: i64,
+    ) -> Result<Self> {
+        map.validate()
+            .with_context(|| format!("invalid arena map {:?}", map.id))?;
+        if map.team_zones.is_some() != game_type.is_team_game() {
+            return Err(anyhow::anyhow!(
+                "arena map {:?} does not fit {:?}: team matches need end zones and no other mode has them",
+                map.id,
+                game_type
+            ));
+        }
+        let (width, height) = (map.width, map.height);
+        let boost = boost_config_for(&game_type, map);
         debug_assert!(boost.as_ref().is_none_or(|c| c.validate().is_ok()));
 
         // Team matches are raced to a score, never against a clock: no time
@@ -1492,15 +2110,12 @@ impl GameState {
         } else {
             DEFAULT_TICK_INTERVAL_MS
         };
-        let (tick_duration_ms, time_limit_ms, score_limit) = match &game_type {
-            GameType::Custom { settings } => (settings.tick_duration_ms, None, None),
-            GameType::TeamMatch { .. } => {
-                (boost_tick_ms, None, Some(team_score_limit(&queue_mode)))
-            }
-            // Solo and free-for-all run until every snake is dead: no clock,
-            // and no score target to race to.
-            _ => (boost_tick_ms, None, None),
+        let tick_duration_ms = match &game_type {
+            GameType::Custom { settings } => settings.tick_duration_ms,
+            _ => boost_tick_ms,
         };
+        let time_limit_ms = None;
+        let score_limit = score_limit_for(&game_type, &queue_mode);
 
         let properties = GameProperties {
             available_food_target: food_target_for(&game_type),
@@ -1511,29 +2126,15 @@ impl GameState {
             combo: ComboConfig::default(),
             player_idle_timeout_ms: DEFAULT_PLAYER_IDLE_TIMEOUT_MS,
             player_idle_warning_ms: DEFAULT_PLAYER_IDLE_WARNING_MS,
+            idle_autopilot: None,
+            power_ups: power_up_config_for(&game_type),
+            zone: zone_config_for(&game_type),
+            hill: hill_config_for(&game_type),
+            flags: flag_config_for(&game_type),
+            death_food: death_food_config_for(&game_type),
         };
 
-        // Set up team zones for team-based games
-        let team_zone_config = match &game_type {
-            GameType::TeamMatch { .. } => {
-                // Calculate goal width as 20% of arena height
-                let goal_width = ((height as f32 * 0.2).round() as u16).max(3);
-                // Make sure it's odd for symmetry
-                let goal_width = if goal_width.is_multiple_of(2) {
-                    goal_width + 1
-                } else {
-                    goal_width
-                };
-
-                Some(TeamZoneConfig {
-                    end_zone_depth: 10,
-                    goal_width,
-                })
-            }
-            _ => None,
-        };
-
-        let team_scores = if matches!(&game_type, GameType::TeamMatch { .. }) {
+        let team_scores = if game_type.is_team_game() {
             let mut scores = HashMap::new();
             scores.insert(TeamId(0), 0);
             scores.insert(TeamId(1), 0);
@@ -1541,6 +2142,12 @@ impl GameState {
         } else {
             None
         };
+        let rounds = match &game_type {
+            GameType::TeamMatch { .. } if team_rounds_to_win(&queue_mode) > 1 => {
+                Some(RoundState::new(team_rounds_to_win(&queue_mode)))
+            }
+            _ => None,
+        };
 
         let mut arena = Arena {
             width,
@@ -1548,12 +2155,34 @@ impl GameState {
             snakes: Vec::new(),
             food: Vec::new(),
             boost_pads: Vec::new(),
-            team_zone_config,
+            team_zone_config: map.team_zones.clone(),
+            map: (!map.is_implicit()).then(|| map.clone()),
+            power_ups: Vec::new(),
+            zone: properties
+                .zone
+                .as_ref()
+                .map(|_| ZoneBounds::full(width, height)),
+            edges: edge_policy_for(&game_type),
+            occupancy: OccupancyGrid::default(),
         };
         if let Some(config) = properties.boost.as_ref() {
             arena.boost_pads = arena.boost_pad_layout(config);
         }
 
+        let hill = properties.hill.as_ref().map(|_| HillControl::default());
+        let flags = match properties.flags {
+            Some(_) => vec![
+                Flag {
+                    team_id: TeamId(0),
+                    placement: FlagPlacement::Home,
+                },
+                Flag {
+                    team_id: TeamId(1),
+                    placement: FlagPlacement::Home,
+                },
+            ],
+            None => Vec::new(),
+        };
         let state = GameState {
             tick: 0,
             status: GameStatus::Stopped,
@@ -1586,15 +2215,16 @@ impl GameState {
             completed_by_inactivity: false,
             readiness: None,
             simulation_epoch_ms: None,
+            rounds,
+            hill,
+            flags,
+            ai_players: HashMap::new(),
+            autopilot_players: HashMap::new(),
+            autopiloted_user_ids: Vec::new(),
         };
 
-        // `new` predates fallible match construction. Keep its API stable but
-        // fail closed if an eligible map cannot materialize a complete valid
-        // Boost layout; production builders use canonical dimensions.
-        state
-            .validate_boost_invariants()
-            .expect("new game must satisfy Boost invariants");
-        state
+        state.validate_boost_invariants()?;
+        Ok(state)
     }
 
     /// Authoritative simulated match duration represented by this snapshot.
@@ -1615,6 +2245,24 @@ impl GameState {
         self.idle_kicked_user_ids.binary_search(&user_id).is_ok()
     }
 
+    /// Whether the AI is driving this player's snake while they are away.
+    pub fn is_on_autopilot(&self, user_id: u32) -> bool {
+        self.autopilot_players.contains_key(&user_id)
+    }
+
+    /// Whether the AI has driven this player's snake at any point this match.
+    pub fn was_on_autopilot(&self, user_id: u32) -> bool {
+        self.autopiloted_user_ids.binary_search(&user_id).is_ok()
+    }
+
+    /// Players who take no XP or rating from this match: removed for
+    /// inactivity, seated as AI, or carried by the autopilot.
+    pub fn earns_no_credit(&self, user_id: u32) -> bool {
+        self.is_player_idle_kicked(user_id)
+            || self.is_ai_driven(user_id)
+            || self.was_on_autopilot(user_id)
+    }
+
     /// Record presence only for an authenticated player's own gameplay
     /// command. Callers invoke this at first scheduling, never at execution,
     /// so deferred turns and transport retries cannot extend the deadline.
@@ -1680,6 +2328,25 @@ impl GameState {
         rng_seed: Option<u64>,
         start_ms: i64,
         boost_config: BoostConfig,
+    ) -> Result<Self> {
+        Self::new_on_map_with_boost_config(
+            &ArenaMap::default_for(&game_type, width, height),
+            game_type,
+            queue_mode,
+            rng_seed,
+            start_ms,
+            boost_config,
+        )
+    }
+
+    /// `new_with_boost_config` on an explicit map.
+    pub fn new_on_map_with_boost_config(
+        map: &ArenaMap,
+        game_type: GameType,
+        queue_mode: QueueMode,
+        rng_seed: Option<u64>,
+        start_ms: i64,
+        boost_config: BoostConfig,
     ) -> Result<Self> {
         boost_config.validate()?;
         if !matches!(
@@ -1691,7 +2358,7 @@ impl GameState {
             ));
         }
 
-        let mut state = Self::new(width, height, game_type, queue_mode, rng_seed, start_ms);
+        let mut state = Self::new_on_map(map, game_type, queue_mode, rng_seed, start_ms)?;
         state.properties.boost = Some(boost_config);
         state.arena.boost_pads = state
             .arena
@@ -1724,8 +2391,7 @@ impl GameState {
                 GameType::Solo | GameType::FreeForAll { .. } => None,
                 _ => return false,
             };
-            return boost_config_for(&self.game_type, self.arena.width, self.arena.height)
-                .is_some()
+            return boost_config_for(&self.game_type, &self.arena.map()).is_some()
                 && self.properties.available_food_target == DEFAULT_FOOD_TARGET
                 && self.properties.tick_duration_ms == DEFAULT_TICK_INTERVAL_MS
                 && self.properties.time_limit_ms == legacy_time_limit_ms
@@ -1757,28 +2423,389 @@ impl GameState {
         migrated.validate_boost_invariants().is_ok()
     }
 
-    /// The tank size to hand a snake starting a new life, in modes whose Boost
-    /// never empties. `None` everywhere else, where a new life starts dry and
-    /// the player refuels from the map.
-    fn unlimited_boost_capacity_ms(&self) -> Option<u32> {
-        self.properties
-            .boost
-            .as_ref()
-            .filter(|config| config.unlimited)
-            .map(|config| config.capacity_ms)
+    /// Round progress must belong to a multi-round team queue and be one the
+    /// round events could have produced. A missing `rounds` is always valid:
+    /// it is how single-round matches and pre-round snapshots look.
+    fn validate_rounds(&self) -> Result<()> {
+        let Some(rounds) = &self.rounds else {
+            return Ok(());
+        };
+        ensure!(
+            matches!(self.game_type, GameType::TeamMatch { .. }),
+            "only team matches are played over rounds, got {:?}",
+            self.game_type
+        );
+        let expected = team_rounds_to_win(&self.queue_mode);
+        ensure!(
+            expected > 1 && rounds.rounds_to_win == expected,
+            "{:?} team matches need {expected} round wins, got {}",
+            self.queue_mode,
+            rounds.rounds_to_win
+        );
+        ensure!(
+            rounds.current_round >= 1
+                && (rounds.rounds_completed == rounds.current_round
+                    || rounds.rounds_completed + 1 == rounds.current_round),
+            "round {} cannot follow {} completed rounds",
+            rounds.current_round,
+            rounds.rounds_completed
+        );
+        let mut total_wins = 0u32;
+        for (team_id, wins) in &rounds.round_wins {
+            ensure!(
+                self.team_scores
+                    .as_ref()
+                    .is_some_and(|scores| scores.contains_key(team_id)),
+                "round wins reference missing team {}",
+                team_id.0
+            );
+            ensure!(
+                *wins <= rounds.rounds_to_win,
+                "team {} has {wins} round wins in a first-to-{} match",
+                team_id.0,
+                rounds.rounds_to_win
+            );
+            total_wins = total_wins.saturating_add(*wins);
+        }
+        ensure!(
+            total_wins <= rounds.rounds_completed,
+            "{total_wins} round wins from only {} completed rounds",
+            rounds.rounds_completed
+        );
+        Ok(())
     }
 
-    /// Validate every cross-field Boost invariant at a serialized tick
-    /// boundary. This is used for snapshot/recovery admission as well as match
-    /// creation, preventing malformed state from producing permanent speed or
-    /// movement divergence.
-    pub fn validate_boost_invariants(&self) -> Result<()> {
-        self.properties.combo.validate()?;
-        for (snake_id, snake) in self.arena.snakes.iter().enumerate() {
-            if snake.combo.remaining_ms > self.properties.combo.window_ms {
-                return Err(anyhow::anyhow!(
-                    "snake {snake_id} Combo remaining_ms exceeds the configured window"
-                ));
+    /// Power-ups are a property of the mode, so a state cannot claim them in a
+    /// mode that never offers them, and without a config there can be nothing
+    /// on the field or on any snake.
+    fn validate_power_ups(&self) -> Result<()> {
+        ensure!(
+            self.properties.power_ups == power_up_config_for(&self.game_type),
+            "{:?} requires power-up config {:?}, got {:?}",
+            self.game_type,
+            power_up_config_for(&self.game_type),
+            self.properties.power_ups
+        );
+        let Some(config) = &self.properties.power_ups else {
+            ensure!(
+                self.arena.power_ups.is_empty(),
+                "a match without power-ups cannot contain power-up pickups"
+            );
+            ensure!(
+                self.arena
+                    .snakes
+                    .iter()
+                    .all(|snake| snake.power_ups.is_inactive()),
+                "a match without power-ups cannot have snakes holding their effects"
+            );
+            return Ok(());
+        };
+        config.validate()?;
+        ensure!(
+            self.arena.power_ups.len() <= usize::from(config.max_on_field),
+            "{} power-ups on the field, at most {} allowed",
+            self.arena.power_ups.len(),
+            config.max_on_field
+        );
+        let mut cells = HashSet::new();
+        for pickup in &self.arena.power_ups {
+            ensure!(
+                config.allows(pickup.kind),
+                "power-up {:?} is not offered by this match",
+                pickup.kind
+            );
+            ensure!(
+                cells.insert(pickup.position),
+                "two power-ups share cell {:?}",
+                pickup.position
+            );
+            self.validate_power_up_cell(&pickup.position)?;
+        }
+        for (snake_id, snake) in self.arena.snakes.iter().enumerate() {
+            let effects = &snake.power_ups;
+            ensure!(
+                snake.is_alive || effects.is_inactive(),
+                "dead snake {snake_id} must hold no power-up effects"
+            );
+            ensure!(
+                effects.ghost_remaining_ms <= config.ghost_duration_ms
+                    && effects.magnet_remaining_ms <= config.magnet_duration_ms,
+                "snake {snake_id} power-up timers exceed the configured durations"
+            );
+        }
+        Ok(())
+    }
+
+    /// The zone is a property of the mode and a function of the tick, so a
+    /// state either has none or has exactly the one its schedule dictates.
+    fn validate_zone(&self) -> Result<()> {
+        ensure!(
+            self.properties.zone == zone_config_for(&self.game_type),
+            "{:?} requires zone config {:?}, got {:?}",
+            self.game_type,
+            zone_config_for(&self.game_type),
+            self.properties.zone
+        );
+        let expected = self.scheduled_zone(self.tick);
+        ensure!(
+            self.arena.zone == expected,
+            "zone at tick {} must be {:?}, got {:?}",
+            self.tick,
+            expected,
+            self.arena.zone
+        );
+        if let Some(config) = &self.properties.zone {
+            config.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Hill rules belong to king of the hill alone, and control must be a
+    /// state the per-tick accounting could have reached.
+    fn validate_hill(&self) -> Result<()> {
+        ensure!(
+            self.properties.hill == hill_config_for(&self.game_type),
+            "{:?} requires hill config {:?}, got {:?}",
+            self.game_type,
+            hill_config_for(&self.game_type),
+            self.properties.hill
+        );
+        ensure!(
+            self.hill.is_some() == self.properties.hill.is_some(),
+            "hill control must be present exactly when the match has a hill"
+        );
+        if let (Some(config), Some(control)) = (&self.properties.hill, &self.hill) {
+            config.validate()?;
+            ensure!(
+                control.held_ms < config.point_interval_ms,
+                "hill hold {}ms should already have scored at {}ms",
+                control.held_ms,
+                config.point_interval_ms
+            );
+            ensure!(
+                control.holder.is_some() || control.held_ms == 0,
+                "an unheld hill cannot bank hold time"
+            );
+            if let Some(holder) = control.holder {
+                ensure!(
+                    self.team_scores
+                        .as_ref()
+                        .is_some_and(|scores| scores.contains_key(&holder)),
+                    "hill holder references missing team {}",
+                    holder.0
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Flag rules belong to capture the flag alone. Each team has exactly one
+    /// flag, carried only by a living enemy and dropped only inside the arena.
+    fn validate_flags(&self) -> Result<()> {
+        ensure!(
+            self.properties.flags == flag_config_for(&self.game_type),
+            "{:?} requires flag config {:?}, got {:?}",
+            self.game_type,
+            flag_config_for(&self.game_type),
+            self.properties.flags
+        );
+        let Some(config) = &self.properties.flags else {
+            ensure!(
+                self.flags.is_empty(),
+                "flags must be absent when the match has no flag rules"
+            );
+            return Ok(());
+        };
+        config.validate()?;
+        let teams: Vec<TeamId> = self.flags.iter().map(|flag| flag.team_id).collect();
+        ensure!(
+            teams == [TeamId(0), TeamId(1)],
+            "capture the flag needs one flag per team in team order, got {teams:?}"
+        );
+        let mut carriers = HashSet::new();
+        for flag in &self.flags {
+            match flag.placement {
+                FlagPlacement::Home => {}
+                FlagPlacement::Carried { snake_id } => {
+                    let carrier = self
+                        .arena
+                        .snakes
+                        .get(snake_id as usize)
+                        .with_context(|| format!("flag carrier {snake_id} does not exist"))?;
+                    ensure!(
+                        carrier.is_alive,
+                        "team {} flag is carried by dead snake {snake_id}",
+                        flag.team_id.0
+                    );
+                    ensure!(
+                        carrier
+                            .team_id
+                            .is_some_and(|team_id| team_id != flag.team_id),
+                        "team {} flag is carried by its own snake {snake_id}",
+                        flag.team_id.0
+                    );
+                    ensure!(
+                        carriers.insert(snake_id),
+                        "snake {snake_id} carries more than one flag"
+                    );
+                }
+                FlagPlacement::Dropped { position, at_tick } => {
+                    ensure!(
+                        position.x >= 0
+                            && position.y >= 0
+                            && position.x < self.arena.width as i16
+                            && position.y < self.arena.height as i16,
+                        "team {} flag dropped outside the arena at {position:?}",
+                        flag.team_id.0
+                    );
+                    ensure!(
+                        at_tick <= self.tick,
+                        "team {} flag dropped on future tick {at_tick}",
+                        flag.team_id.0
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn place_flag(&mut self, team_id: TeamId, placement: FlagPlacement) {
+        if let Some(flag) = self.flags.iter_mut().find(|flag| flag.team_id == team_id) {
+            flag.placement = placement;
+        }
+    }
+
+    fn require_flag(&self, team_id: TeamId) -> Result<()> {
+        ensure!(
+            self.flags.iter().any(|flag| flag.team_id == team_id),
+            "replicated flag event references missing team {} flag",
+            team_id.0
+        );
+        Ok(())
+    }
+
+    /// Death food belongs to the modes that opt in, with a config the
+    /// simulation can honour.
+    fn validate_death_food(&self) -> Result<()> {
+        ensure!(
+            self.properties.death_food == death_food_config_for(&self.game_type),
+            "{:?} requires death food config {:?}, got {:?}",
+            self.game_type,
+            death_food_config_for(&self.game_type),
+            self.properties.death_food
+        );
+        if let Some(config) = &self.properties.death_food {
+            config.validate()?;
+        }
+        Ok(())
+    }
+
+    /// The flag `snake_id` is carrying, if any, falls where its head is.
+    /// Called before the snake dies or leaves, while the head still marks the
+    /// last cell it safely held.
+    fn drop_carried_flag(&mut self, snake_id: u32, out: &mut Vec<(u64, GameEvent)>) {
+        let Some(team_id) = self
+            .flags
+            .iter()
+            .find(|flag| flag.carrier() == Some(snake_id))
+            .map(|flag| flag.team_id)
+        else {
+            return;
+        };
+        let Some(position) = self
+            .arena
+            .snakes
+            .get(snake_id as usize)
+            .and_then(|snake| snake.head().ok().copied())
+        else {
+            return;
+        };
+        self.apply_event(
+            GameEvent::FlagDropped {
+                team_id,
+                snake_id,
+                position,
+                at_tick: self.tick.saturating_add(1),
+            },
+            Some(out),
+        );
+    }
+
+    /// Turn the bodies of snakes that died this tick into food. Bodies are
+    /// taken in snake-id order and every pellet is drawn from the match's
+    /// generator, so a replayed match drops the same pellets.
+    fn drop_death_food(&mut self, fallen: Vec<Vec<Position>>, out: &mut Vec<(u64, GameEvent)>) {
+        let Some(config) = self.properties.death_food.clone() else {
+            return;
+        };
+        self.arena.sync_occupancy();
+        for body in fallen {
+            let (width, height) = (self.arena.width as i16, self.arena.height as i16);
+            let mut candidates: Vec<Position> = Vec::with_capacity(body.len());
+            for position in &body {
+                if position.x >= 0
+                    && position.y >= 0
+                    && position.x < width
+                    && position.y < height
+                    && !candidates.contains(position)
+                    && !self.arena.food.contains(position)
+                    && !self.arena.is_boost_pad_position(position)
+                    && !self.arena.is_wall_position(position)
+                    && !self.arena.is_outside_zone(position)
+                    && !self
+                        .arena
+                        .occupancy
+                        .live_snake_at(&self.arena.snakes, position)
+                {
+                    candidates.push(*position);
+                }
+            }
+            let food_on_field = self.arena.food.len();
+            let Some(rng) = self.rng.as_mut() else {
+                return;
+            };
+            for position in config.place(body.len(), candidates, food_on_field, rng) {
+                self.apply_event(GameEvent::FoodSpawned { position }, Some(out));
+            }
+        }
+    }
+
+    /// A pickup must lie on an open cell of the arena.
+    fn validate_power_up_cell(&self, position: &Position) -> Result<()> {
+        ensure!(
+            position.x >= 0
+                && position.y >= 0
+                && position.x < self.arena.width as i16
+                && position.y < self.arena.height as i16
+                && !self.arena.is_wall_position(position)
+                && !self.arena.is_obstacle_position(position),
+            "power-up at {position:?} is not on an open cell"
+        );
+        Ok(())
+    }
+
+    /// The tank size to hand a snake starting a new life, in modes whose Boost
+    /// never empties. `None` everywhere else, where a new life starts dry and
+    /// the player refuels from the map.
+    fn unlimited_boost_capacity_ms(&self) -> Option<u32> {
+        self.properties
+            .boost
+            .as_ref()
+            .filter(|config| config.unlimited)
+            .map(|config| config.capacity_ms)
+    }
+
+    /// Validate every cross-field Boost invariant at a serialized tick
+    /// boundary. This is used for snapshot/recovery admission as well as match
+    /// creation, preventing malformed state from producing permanent speed or
+    /// movement divergence.
+    pub fn validate_boost_invariants(&self) -> Result<()> {
+        self.properties.combo.validate()?;
+        for (snake_id, snake) in self.arena.snakes.iter().enumerate() {
+            if snake.combo.remaining_ms > self.properties.combo.window_ms {
+                return Err(anyhow::anyhow!(
+                    "snake {snake_id} Combo remaining_ms exceeds the configured window"
+                ));
             }
             let inactive = snake.combo.remaining_ms == 0;
             if inactive != (snake.combo.chain_count == 0) {
@@ -1813,7 +2840,8 @@ impl GameState {
                 | DeathCause::OutOfBounds
                 | DeathCause::EnemyBase
                 | DeathCause::SelfCollision
-                | DeathCause::Banked => None,
+                | DeathCause::Banked
+                | DeathCause::Zone => None,
             };
             if attributed_snake_id
                 .is_some_and(|attributed| attributed as usize >= self.arena.snakes.len())
@@ -1828,8 +2856,21 @@ impl GameState {
         // A mode is Boost-eligible only on a map that can host its layout, so
         // this asks exactly the question construction answered. Custom games
         // are player-defined and never carry Boost.
-        let expected_config =
-            boost_config_for(&self.game_type, self.arena.width, self.arena.height);
+        self.arena.validate_map()?;
+        self.validate_rounds()?;
+        self.validate_power_ups()?;
+        self.validate_zone()?;
+        self.validate_hill()?;
+        self.validate_flags()?;
+        self.validate_death_food()?;
+        ensure!(
+            self.arena.edges == edge_policy_for(&self.game_type),
+            "{:?} requires {:?} edges, got {:?}",
+            self.game_type,
+            edge_policy_for(&self.game_type),
+            self.arena.edges
+        );
+        let expected_config = boost_config_for(&self.game_type, &self.arena.map());
         let eligible = expected_config.is_some();
         if !matches!(self.game_type, GameType::Custom { .. }) {
             let expected_food_target = food_target_for(&self.game_type);
@@ -1884,10 +2925,7 @@ impl GameState {
                 // Only team matches race to a score. Solo and free-for-all end
                 // when every snake is dead, so a target there would be a win
                 // condition nothing in the engine ever tests.
-                let expected_score_limit = match &self.game_type {
-                    GameType::TeamMatch { .. } => Some(team_score_limit(&self.queue_mode)),
-                    _ => None,
-                };
+                let expected_score_limit = score_limit_for(&self.game_type, &self.queue_mode);
                 if self.properties.score_limit != expected_score_limit {
                     return Err(anyhow::anyhow!(
                         "Boost match score limit must be exactly {:?} for {:?} in {:?}, got {:?}",
@@ -1909,9 +2947,18 @@ impl GameState {
                     .iter()
                     .map(|pad| (pad.id, pad.position, pad.charge_ms, pad.size_cells))
                     .collect();
-                // An unlimited tank places no pads; every collectible layout
-                // places exactly the canonical twelve.
-                let expected_pad_count = if config.unlimited { 0 } else { 12 };
+                // An unlimited tank places no pads; a map-placed layout places
+                // exactly the map's pads; every drawn layout places exactly
+                // the canonical twelve.
+                let expected_pad_count = match &self.arena.map().boost {
+                    _ if config.unlimited => 0,
+                    MapBoostPlacement::Pads { pads }
+                        if config.spot_layout_version == BOOST_SPOT_LAYOUT_VERSION_MAP =>
+                    {
+                        pads.len()
+                    }
+                    _ => 12,
+                };
                 if expected.len() != expected_pad_count || actual != expected {
                     return Err(anyhow::anyhow!(
                         "Boost layout v{} requires {} canonical value/footprint pads, got {} (expected geometry produced {})",
@@ -2098,6 +3145,26 @@ impl GameState {
                 ));
             }
         }
+        if self
+            .autopiloted_user_ids
+            .windows(2)
+            .any(|pair| pair[0] >= pair[1])
+        {
+            return Err(anyhow::anyhow!(
+                "autopiloted player IDs must be sorted and unique"
+            ));
+        }
+        for user_id in self.autopilot_players.keys() {
+            if !self.players.contains_key(user_id)
+                || self.is_ai_driven(*user_id)
+                || self.is_player_idle_kicked(*user_id)
+                || !self.was_on_autopilot(*user_id)
+            {
+                return Err(anyhow::anyhow!(
+                    "autopilot user {user_id} must be a present, recorded player"
+                ));
+            }
+        }
         for (user_id, activity_tick) in &self.player_last_activity_ticks {
             if !self.players.contains_key(user_id) || *activity_tick > self.tick {
                 return Err(anyhow::anyhow!(
@@ -2128,6 +3195,13 @@ impl GameState {
 
         match event {
             GameEvent::Snapshot { game_state } => game_state.validate_boost_invariants(),
+            GameEvent::SnapshotDelta {
+                base_tick,
+                changes,
+                hash,
+            } => {
+                apply_snapshot_delta(self, *base_tick, changes, *hash)?.validate_boost_invariants()
+            }
             GameEvent::SnakeTurned { snake_id, .. }
             | GameEvent::ScoreUpdated { snake_id, .. }
             | GameEvent::SnakeRespawned { snake_id, .. } => {
@@ -2148,7 +3222,8 @@ impl GameState {
                     | DeathCause::OutOfBounds
                     | DeathCause::EnemyBase
                     | DeathCause::SelfCollision
-                    | DeathCause::Banked => {}
+                    | DeathCause::Banked
+                    | DeathCause::Zone => {}
                 }
                 Ok(())
             }
@@ -2176,9 +3251,14 @@ impl GameState {
                 // it is still present, however, every telemetry field must
                 // describe this exact pre-pickup state.
                 if self.has_food(position) {
-                    if !snake.is_alive || snake.head().ok() != Some(position) {
+                    let head = snake.head().ok();
+                    let within_magnet_reach = snake.power_ups.magnet()
+                        && self.properties.power_ups.as_ref().is_some_and(|config| {
+                            head.is_some_and(|head| config.magnet_reaches(head, position))
+                        });
+                    if !snake.is_alive || (head != Some(position) && !within_magnet_reach) {
                         return Err(anyhow::anyhow!(
-                            "food event snake {snake_id} is not alive on the pellet"
+                            "food event snake {snake_id} is not alive on or magnetically near the pellet"
                         ));
                     }
                     let expected = self.combo_pickup_context(snake);
@@ -2216,6 +3296,31 @@ impl GameState {
                 }
                 Ok(())
             }
+            GameEvent::PlayerAutopilotChanged {
+                user_id,
+                snake_id,
+                enabled,
+            } => {
+                require_snake(*snake_id)?;
+                let player = self.players.get(user_id).with_context(|| {
+                    format!("autopilot event references missing player {user_id}")
+                })?;
+                if player.snake_id != *snake_id {
+                    return Err(anyhow::anyhow!(
+                        "autopilot event snake {snake_id} is not owned by player {user_id}"
+                    ));
+                }
+                if *enabled
+                    && (self.properties.idle_autopilot.is_none()
+                        || self.is_ai_driven(*user_id)
+                        || self.is_player_idle_kicked(*user_id))
+                {
+                    return Err(anyhow::anyhow!(
+                        "autopilot cannot take over player {user_id}"
+                    ));
+                }
+                Ok(())
+            }
             GameEvent::BoostPacketCollected {
                 pad_id,
                 snake_id,
@@ -2283,6 +3388,138 @@ impl GameState {
                     )),
                 }
             }
+            GameEvent::PowerUpSpawned { kind, position } => {
+                let config = self
+                    .properties
+                    .power_ups
+                    .as_ref()
+                    .context("power-up spawn in a match without power-ups")?;
+                if !config.allows(*kind) {
+                    return Err(anyhow::anyhow!(
+                        "replicated power-up spawn of {kind:?}, which this match does not offer"
+                    ));
+                }
+                match self.arena.power_up_at(position) {
+                    // Re-delivery of a spawn this state already holds.
+                    Some(existing) if existing.kind == *kind => Ok(()),
+                    Some(existing) => Err(anyhow::anyhow!(
+                        "replicated power-up spawn of {kind:?} on a cell holding {:?}",
+                        existing.kind
+                    )),
+                    None => {
+                        self.validate_power_up_cell(position)?;
+                        if self.arena.power_ups.len() >= usize::from(config.max_on_field) {
+                            return Err(anyhow::anyhow!(
+                                "replicated power-up spawn exceeds the {} allowed on the field",
+                                config.max_on_field
+                            ));
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            GameEvent::PowerUpCollected {
+                snake_id,
+                kind,
+                position,
+            } => {
+                self.properties
+                    .power_ups
+                    .as_ref()
+                    .context("power-up collection in a match without power-ups")?;
+                let snake = require_snake(*snake_id)?;
+                match self.arena.power_up_at(position) {
+                    // Movement is derived before its confirming event arrives,
+                    // so a pickup that is already gone was already collected.
+                    None => Ok(()),
+                    Some(existing) if existing.kind != *kind => Err(anyhow::anyhow!(
+                        "replicated collection of {kind:?} on a cell holding {:?}",
+                        existing.kind
+                    )),
+                    Some(_) if !snake.is_alive || snake.head().ok() != Some(position) => {
+                        Err(anyhow::anyhow!(
+                            "power-up collection snake {snake_id} is not alive on the pickup"
+                        ))
+                    }
+                    Some(_) => Ok(()),
+                }
+            }
+            GameEvent::ShieldBroken { snake_id } => {
+                self.properties
+                    .power_ups
+                    .as_ref()
+                    .context("shield event in a match without power-ups")?;
+                require_snake(*snake_id)?;
+                Ok(())
+            }
+            GameEvent::ZoneContracted { bounds } => {
+                let config = self
+                    .properties
+                    .zone
+                    .as_ref()
+                    .context("zone contraction in a match without a zone")?;
+                let current = self
+                    .arena
+                    .zone
+                    .context("zone contraction in a state without a zone")?;
+                // Replicas derive every ring from the tick, so the event names
+                // either the ring this state already drew or one it passed.
+                let rings = (0..=u64::from(self.arena.width.min(self.arena.height).div_ceil(2)))
+                    .find(|rings| {
+                        ZoneBounds::inset(self.arena.width, self.arena.height, *rings) == *bounds
+                    });
+                if rings.is_none() || !bounds.encloses(&current) {
+                    return Err(anyhow::anyhow!(
+                        "replicated zone {bounds:?} is not on the schedule {config:?} at or before tick {} (zone {current:?})",
+                        self.tick
+                    ));
+                }
+                Ok(())
+            }
+            GameEvent::HillCaptured { team_id, .. } => {
+                ensure!(
+                    self.hill.is_some(),
+                    "hill capture in a match without a hill"
+                );
+                ensure!(
+                    self.team_scores
+                        .as_ref()
+                        .is_some_and(|scores| scores.contains_key(team_id)),
+                    "replicated hill capture references missing team {}",
+                    team_id.0
+                );
+                Ok(())
+            }
+            GameEvent::FlagPickedUp { team_id, snake_id }
+            | GameEvent::FlagCaptured { team_id, snake_id } => {
+                self.require_flag(*team_id)?;
+                let snake = require_snake(*snake_id)?;
+                ensure!(
+                    snake
+                        .team_id
+                        .is_some_and(|snake_team| snake_team != *team_id),
+                    "snake {snake_id} cannot carry its own team's flag"
+                );
+                Ok(())
+            }
+            GameEvent::FlagDropped {
+                team_id,
+                snake_id,
+                position,
+                ..
+            } => {
+                self.require_flag(*team_id)?;
+                require_snake(*snake_id)?;
+                ensure!(
+                    position.x >= 0
+                        && position.y >= 0
+                        && position.x < self.arena.width as i16
+                        && position.y < self.arena.height as i16,
+                    "replicated flag drop at {position:?} is outside the arena"
+                );
+                Ok(())
+            }
+            GameEvent::FlagReturned { team_id } => self.require_flag(*team_id),
             GameEvent::CommandScheduled { command_message }
             | GameEvent::CommandScheduledV2 {
                 command_message, ..
@@ -2309,6 +3546,47 @@ impl GameState {
                 }
                 Ok(())
             }
+            // Round transitions must follow the match's own sequence. A
+            // re-delivered transition is a no-op in `apply_event`, so only an
+            // event from a round this state has not reached is refused.
+            GameEvent::RoundEnded {
+                round_number,
+                winning_team_id,
+            } => {
+                let rounds = self
+                    .rounds
+                    .as_ref()
+                    .context("round event for a single-round match")?;
+                if *round_number == 0 || *round_number > rounds.current_round {
+                    return Err(anyhow::anyhow!(
+                        "replicated end of round {round_number} while round {} is in play",
+                        rounds.current_round
+                    ));
+                }
+                if let Some(team_id) = winning_team_id
+                    && !rounds.round_wins.contains_key(team_id)
+                {
+                    return Err(anyhow::anyhow!(
+                        "replicated round result references missing team {}",
+                        team_id.0
+                    ));
+                }
+                Ok(())
+            }
+            GameEvent::RoundStarted { round_number } => {
+                let rounds = self
+                    .rounds
+                    .as_ref()
+                    .context("round event for a single-round match")?;
+                let next_round = rounds.rounds_completed.saturating_add(1);
+                if *round_number < 2 || *round_number > next_round {
+                    return Err(anyhow::anyhow!(
+                        "replicated start of round {round_number} after {} completed rounds",
+                        rounds.rounds_completed
+                    ));
+                }
+                Ok(())
+            }
             // A replicated readiness confirmation must name a real player of
             // this match. Accepting an arbitrary user id would let a corrupted
             // or spoofed delta pad the ready set and release the gate early.
@@ -2344,7 +3622,7 @@ impl GameState {
         self.tick
     }
 
-    fn normal_movement_interval_ms(&self) -> u32 {
+    pub(crate) fn normal_movement_interval_ms(&self) -> u32 {
         match &self.game_type {
             GameType::Custom { settings } => settings.tick_duration_ms.max(1),
             _ => DEFAULT_TICK_INTERVAL_MS,
@@ -2359,6 +3637,39 @@ impl GameState {
         elapsed_before / interval_ms != elapsed_after / interval_ms
     }
 
+    /// Roll one power-up spawn: a kind from the configured list and a cell
+    /// sampled like food. A roll that lands on anything occupied is dropped
+    /// rather than retried, again like food, so the RNG draws a fixed amount.
+    fn power_up_spawn_event(&mut self) -> Option<GameEvent> {
+        let kinds = self.properties.power_ups.as_ref()?.kinds.clone();
+        self.arena.sync_occupancy();
+        let rng = self.rng.as_mut()?;
+        let kind = kinds[rng.next_u32() as usize % kinds.len()];
+        let position = sample_food_position(rng, &self.game_type, &self.arena);
+        let occupied = self.arena.food.contains(&position)
+            || self.arena.power_up_at(&position).is_some()
+            || self.arena.is_boost_pad_position(&position)
+            || self.arena.is_obstacle_position(&position)
+            || self.arena.is_outside_zone(&position)
+            || self
+                .arena
+                .occupancy
+                .live_snake_at(&self.arena.snakes, &position);
+        (!occupied).then_some(GameEvent::PowerUpSpawned { kind, position })
+    }
+
+    /// The zone the schedule dictates at `tick`, for modes that have one.
+    fn scheduled_zone(&self, tick: u32) -> Option<ZoneBounds> {
+        self.properties.zone.as_ref().map(|config| {
+            config.bounds_at(
+                self.arena.width,
+                self.arena.height,
+                tick,
+                self.properties.tick_duration_ms,
+            )
+        })
+    }
+
     fn food_refill_due(&self) -> bool {
         // Boost halves the authoritative quantum, but food balance remains a
         // roughly 100 ms wall-clock opportunity. Every other mode retains the
@@ -2524,7 +3835,7 @@ impl GameState {
     }
 
     fn calculate_team_starting_positions(&self) -> Vec<(Position, Direction)> {
-        if !matches!(self.game_type, GameType::TeamMatch { .. }) {
+        if !self.game_type.is_team_game() {
             return self.calculate_starting_positions(self.players.len());
         }
 
@@ -2626,12 +3937,44 @@ impl GameState {
             .collect()
     }
 
-    fn apply_starting_positions(&mut self, player_count: usize) {
-        let starting_positions = if matches!(self.game_type, GameType::TeamMatch { .. }) {
+    /// Starting cells for every snake: the mode's computed formation, with
+    /// any spawn points of a stored map taking precedence.
+    fn starting_positions(&self, player_count: usize) -> Vec<(Position, Direction)> {
+        let mut positions = if self.game_type.is_team_game() {
             self.calculate_team_starting_positions()
         } else {
             self.calculate_starting_positions(player_count)
         };
+        self.assign_map_spawn_points(&mut positions);
+        positions
+    }
+
+    /// Hand each snake, in id order, the first unused map spawn open to its
+    /// team. Snakes left over once the spawns run out keep their computed
+    /// formation slot.
+    fn assign_map_spawn_points(&self, positions: &mut [(Position, Direction)]) {
+        let Some(map) = &self.arena.map else {
+            return;
+        };
+        let mut used = vec![false; map.spawn_points.len()];
+        for (snake_id, slot) in positions.iter_mut().enumerate() {
+            let team_id = self
+                .arena
+                .snakes
+                .get(snake_id)
+                .and_then(|snake| snake.team_id);
+            let spawn = map.spawn_points.iter().enumerate().find(|(index, spawn)| {
+                !used[*index] && (spawn.team_id.is_none() || spawn.team_id == team_id)
+            });
+            if let Some((index, spawn)) = spawn {
+                used[index] = true;
+                *slot = (spawn.position, spawn.direction);
+            }
+        }
+    }
+
+    fn apply_starting_positions(&mut self, player_count: usize) {
+        let starting_positions = self.starting_positions(player_count);
 
         let snake_length = self.starting_snake_length();
 
@@ -2685,11 +4028,7 @@ impl GameState {
     }
 
     fn respawn_event_for_snake(&self, snake_id: u32) -> Option<GameEvent> {
-        let starting_positions = if matches!(self.game_type, GameType::TeamMatch { .. }) {
-            self.calculate_team_starting_positions()
-        } else {
-            self.calculate_starting_positions(self.players.len())
-        };
+        let starting_positions = self.starting_positions(self.players.len());
         let position_idx = snake_id as usize;
 
         let mut candidate_positions: Vec<(Position, Direction)> = Vec::new();
@@ -2703,7 +4042,7 @@ impl GameState {
                 .arena
                 .snakes
                 .iter()
-                .any(|s| s.is_alive && s.contains_point(&pos, false));
+                .any(|s| s.is_alive && self.arena.snake_contains(s, &pos, false));
             if !occupied {
                 return Some(GameEvent::SnakeRespawned {
                     snake_id,
@@ -2742,9 +4081,9 @@ impl GameState {
         }
 
         // Determine team assignment for team games
-        let team_id = match (&self.game_type, team_override) {
-            (GameType::TeamMatch { .. }, Some(team)) => Some(team),
-            (GameType::TeamMatch { .. }, None) => {
+        let team_id = match (self.game_type.is_team_game(), team_override) {
+            (true, Some(team)) => Some(team),
+            (true, None) => {
                 // Assign teams alternately: A, B, A, B...
                 let existing_player_count = self.players.len();
                 let team_index = (existing_player_count % 2) as u8;
@@ -2769,6 +4108,7 @@ impl GameState {
                 ..Default::default()
             },
             combo: SnakeCombo::default(),
+            power_ups: SnakePowerUps::default(),
         };
 
         let snake_id = self.arena.add_snake(snake)?;
@@ -2787,16 +4127,115 @@ impl GameState {
         self.add_player_with_team(user_id, username, None)
     }
 
-    /// Record which skin a player is wearing.
-    ///
-    /// The caller is responsible for having checked the id against the
-    /// catalogue — this is cosmetic state, and an unknown id renders as the
-    /// classic look rather than failing, so the engine does not need to guard
-    /// it. Passing `None` clears any previous choice.
-    pub fn set_player_skin(&mut self, user_id: u32, skin_ref: Option<String>) {
-        match skin_ref {
-            Some(skin_ref) => {
-                self.skins.insert(user_id, skin_ref);
+    /// Seat `request.count` AI players, taking the next free reserved ids.
+    /// Like any player they can only join before the first tick.
+    pub fn add_ai_opponents(&mut self, request: &AiOpponents) -> Result<Vec<Player>> {
+        (0..request.count)
+            .map(|_| {
+                self.add_ai_player(request.difficulty, None)
+                    .with_context(|| format!("could not seat {} AI opponents", request.count))
+            })
+            .collect()
+    }
+
+    /// Seat one AI player on `team`, or the next team in turn when `None`,
+    /// under the first free reserved id.
+    pub fn add_ai_player(
+        &mut self,
+        difficulty: crate::AiDifficulty,
+        team: Option<TeamId>,
+    ) -> Result<Player> {
+        let user_id = (0..)
+            .map_while(ai_user_id)
+            .find(|user_id| !self.players.contains_key(user_id))
+            .context("no reserved AI ids left")?;
+        let label = match difficulty {
+            crate::AiDifficulty::Easy => "Easy",
+            crate::AiDifficulty::Medium => "Medium",
+            crate::AiDifficulty::Hard => "Hard",
+        };
+        let name = format!("{label} AI {}", self.ai_players.len() + 1);
+        let player = self.add_player_with_team(user_id, Some(name), team)?;
+        self.ai_players.insert(
+            user_id,
+            AiSeat {
+                difficulty,
+                next_decision_tick: 0,
+            },
+        );
+        Ok(player)
+    }
+
+    /// Whether the simulation drives this player.
+    pub fn is_ai_driven(&self, user_id: u32) -> bool {
+        self.ai_players.contains_key(&user_id)
+    }
+
+    /// Decide for every AI seat whose snake moves this step and whose
+    /// reaction delay has passed, in user_id order. Runs after player turns,
+    /// so a seat never overrides a turn already taken this step.
+    fn drive_ai_players(
+        &mut self,
+        movers: &HashSet<u32>,
+        turned_snake_ids: &mut HashSet<u32>,
+        out: &mut Vec<(u64, GameEvent)>,
+    ) {
+        if self.ai_players.is_empty() && self.autopilot_players.is_empty() {
+            return;
+        }
+        let mut seats: Vec<(u32, AiSeat)> = self
+            .ai_players
+            .iter()
+            .chain(&self.autopilot_players)
+            .map(|(user_id, seat)| (*user_id, *seat))
+            .collect();
+        seats.sort_unstable_by_key(|(user_id, _)| *user_id);
+        let tick_duration_ms = self.properties.tick_duration_ms;
+        for (user_id, seat) in seats {
+            if seat.next_decision_tick > self.tick || self.is_player_idle_kicked(user_id) {
+                continue;
+            }
+            let Some(snake_id) = self.players.get(&user_id).map(|player| player.snake_id) else {
+                continue;
+            };
+            if !movers.contains(&snake_id) || turned_snake_ids.contains(&snake_id) {
+                continue;
+            }
+            let Some(command) = calculate_ai_command_at(self, snake_id, seat.difficulty) else {
+                continue;
+            };
+            if let Some(seat) = self
+                .ai_players
+                .get_mut(&user_id)
+                .or_else(|| self.autopilot_players.get_mut(&user_id))
+            {
+                seat.next_decision_tick = self.tick
+                    + seat
+                        .difficulty
+                        .profile()
+                        .decision_interval_ticks(tick_duration_ms);
+            }
+            if let Ok(events) = self.exec_command(command) {
+                for (_, event) in &events {
+                    if let GameEvent::SnakeTurned { snake_id, .. } = event {
+                        turned_snake_ids.insert(*snake_id);
+                    }
+                }
+                out.extend(events);
+            }
+        }
+    }
+
+    /// Record which skin a player is wearing.
+    ///
+    /// The caller is responsible for having checked the id against the
+    /// catalogue — this is cosmetic state, and an unknown id renders as the
+    /// classic look rather than failing, so the engine does not need to guard
+    /// it. Passing `None` clears any previous choice.
+    pub fn set_player_skin(&mut self, user_id: u32, skin_ref: Option<String>) {
+        match skin_ref {
+            Some(skin_ref) => {
+                self.skins.insert(user_id, skin_ref);
             }
             None => {
                 self.skins.remove(&user_id);
@@ -2839,8 +4278,9 @@ impl GameState {
             .is_some_and(|readiness| readiness.ready_user_ids.contains(&user_id))
     }
 
-    /// Players who have not yet confirmed. Spectators are never included: they
-    /// have no snake and must not be able to hold a match.
+    /// Players who have not yet confirmed. Spectators and AI seats are never
+    /// included: neither has anyone to read a briefing, so neither may hold a
+    /// match.
     pub fn players_pending_ready(&self) -> Vec<u32> {
         let Some(readiness) = self.readiness.as_ref() else {
             return Vec::new();
@@ -2849,6 +4289,7 @@ impl GameState {
             .players
             .keys()
             .copied()
+            .filter(|user_id| !self.is_ai_driven(*user_id))
             .filter(|user_id| !readiness.ready_user_ids.contains(user_id))
             .collect();
         pending.sort_unstable();
@@ -2884,12 +4325,20 @@ impl GameState {
 
     /// Spawns initial food items when the game starts
     pub fn spawn_initial_food(&mut self) {
+        self.fill_food_to_target(None);
+    }
+
+    /// Top the field up to its food target in one go, as at the start of a
+    /// match or a round. Each pellet is applied as a `FoodSpawned` event so a
+    /// mid-match refill can be replicated like any other spawn.
+    fn fill_food_to_target(&mut self, mut out: Option<&mut Vec<(u64, GameEvent)>>) {
         if self.rng.is_none() {
             return; // Can't spawn food without RNG
         }
 
         let target_food = self.properties.available_food_target;
         let mut attempts = 0;
+        self.arena.sync_occupancy();
         const MAX_ATTEMPTS: usize = 1000; // Prevent infinite loop
 
         while self.arena.food.len() < target_food && attempts < MAX_ATTEMPTS {
@@ -2901,13 +4350,14 @@ impl GameState {
                 // Check if position is valid (not occupied by food or snake)
                 if !self.arena.food.contains(&position)
                     && !self.arena.is_boost_pad_position(&position)
+                    && !self.arena.is_obstacle_position(&position)
+                    && !self.arena.is_outside_zone(&position)
                     && !self
                         .arena
-                        .snakes
-                        .iter()
-                        .any(|s| s.is_alive && s.contains_point(&position, false))
+                        .occupancy
+                        .live_snake_at(&self.arena.snakes, &position)
                 {
-                    self.arena.food.push(position);
+                    self.apply_event(GameEvent::FoodSpawned { position }, out.as_deref_mut());
                 }
             }
         }
@@ -2958,10 +4408,11 @@ impl GameState {
     }
 
     fn player_is_idle_contender(&self, user_id: u32, player: &Player) -> bool {
-        if self.is_player_idle_kicked(user_id) {
+        // A team carried only by the autopilot has nobody left playing for it.
+        if self.is_player_idle_kicked(user_id) || self.is_on_autopilot(user_id) {
             return false;
         }
-        if matches!(self.game_type, GameType::TeamMatch { .. }) || self.team_scores.is_some() {
+        if self.game_type.is_team_game() || self.team_scores.is_some() {
             return true;
         }
         self.arena
@@ -2970,6 +4421,42 @@ impl GameState {
             .is_some_and(|snake| snake.is_alive)
     }
 
+    /// How long `user_id` will have gone without input by `post_tick`.
+    fn idle_ms_at(&self, user_id: u32, post_tick: u32) -> u32 {
+        let last_tick = self
+            .player_last_activity_ticks
+            .get(&user_id)
+            .copied()
+            .unwrap_or(self.tick);
+        post_tick
+            .saturating_sub(last_tick)
+            .saturating_mul(self.properties.tick_duration_ms.max(1))
+    }
+
+    /// Whether someone on `snake_id`'s team is still playing in person and is
+    /// not among the `leaving`.
+    fn has_present_teammate(&self, snake_id: u32, leaving: &[(u32, u32)]) -> bool {
+        let Some(team) = self
+            .arena
+            .snakes
+            .get(snake_id as usize)
+            .and_then(|snake| snake.team_id)
+        else {
+            return false;
+        };
+        self.players.iter().any(|(user_id, player)| {
+            player.snake_id != snake_id
+                && !self.is_ai_driven(*user_id)
+                && !leaving.iter().any(|(left, _)| left == user_id)
+                && self.player_is_idle_contender(*user_id, player)
+                && self
+                    .arena
+                    .snakes
+                    .get(player.snake_id as usize)
+                    .is_some_and(|snake| snake.team_id == Some(team))
+        })
+    }
+
     fn inactivity_winning_snake_id(&self) -> Option<Option<u32>> {
         let active_players: Vec<(u32, &Player)> = self
             .players
@@ -2978,7 +4465,7 @@ impl GameState {
             .map(|(user_id, player)| (*user_id, player))
             .collect();
 
-        if matches!(self.game_type, GameType::TeamMatch { .. }) || self.team_scores.is_some() {
+        if self.game_type.is_team_game() || self.team_scores.is_some() {
             let mut active_teams: Vec<TeamId> = active_players
                 .iter()
                 .filter_map(|(_, player)| {
@@ -3028,7 +4515,7 @@ impl GameState {
         self.players
             .iter()
             .map(|(user_id, player)| {
-                if self.is_player_idle_kicked(*user_id) {
+                if self.earns_no_credit(*user_id) {
                     return (*user_id, 0);
                 }
                 let pickups = self
@@ -3069,28 +4556,58 @@ impl GameState {
         self.ensure_player_idle_tracking();
         let tick_duration_ms = self.properties.tick_duration_ms.max(1);
         let timeout_ms = self.properties.player_idle_timeout_ms.max(tick_duration_ms);
+        // Input since the takeover means the player is back at the keys.
+        let mut returned: Vec<(u32, u32)> = self
+            .autopilot_players
+            .keys()
+            .filter(|user_id| self.idle_ms_at(**user_id, post_tick) < timeout_ms)
+            .filter_map(|user_id| Some((*user_id, self.players.get(user_id)?.snake_id)))
+            .collect();
+        returned.sort_unstable_by_key(|(user_id, _)| *user_id);
+        for (user_id, snake_id) in returned {
+            self.apply_event(
+                GameEvent::PlayerAutopilotChanged {
+                    user_id,
+                    snake_id,
+                    enabled: false,
+                },
+                Some(out),
+            );
+        }
+
         let mut expired: Vec<(u32, u32)> = self
             .players
             .iter()
             .filter(|(user_id, player)| self.player_is_idle_contender(**user_id, player))
-            .filter_map(|(user_id, player)| {
-                let last_tick = self
-                    .player_last_activity_ticks
-                    .get(user_id)
-                    .copied()
-                    .unwrap_or(self.tick);
-                let idle_ms = post_tick
-                    .saturating_sub(last_tick)
-                    .saturating_mul(tick_duration_ms);
-                (idle_ms >= timeout_ms).then_some((*user_id, player.snake_id))
-            })
+            .filter(|(user_id, _)| !self.is_ai_driven(**user_id))
+            .filter(|(user_id, _)| self.idle_ms_at(**user_id, post_tick) >= timeout_ms)
+            .map(|(user_id, player)| (*user_id, player.snake_id))
             .collect();
         expired.sort_unstable_by_key(|(user_id, _)| *user_id);
-        if expired.is_empty() {
+
+        // Decide every takeover against the roster before any of them, so
+        // teammates expiring together are removed together whatever the order.
+        let (carried, removed): (Vec<_>, Vec<_>) =
+            expired.iter().copied().partition(|(_, snake_id)| {
+                self.properties.idle_autopilot.is_some()
+                    && self.has_present_teammate(*snake_id, &expired)
+            });
+        for (user_id, snake_id) in carried {
+            self.apply_event(
+                GameEvent::PlayerAutopilotChanged {
+                    user_id,
+                    snake_id,
+                    enabled: true,
+                },
+                Some(out),
+            );
+        }
+        if removed.is_empty() {
             return false;
         }
 
-        for (user_id, snake_id) in expired {
+        for (user_id, snake_id) in removed {
+            self.drop_carried_flag(snake_id, out);
             self.apply_event(GameEvent::PlayerIdleKicked { user_id, snake_id }, Some(out));
         }
 
@@ -3159,6 +4676,16 @@ impl GameState {
         // for this quantum's movement and collection phase.
         let post_tick = self.tick.saturating_add(1);
 
+        // The zone is drawn from the tick alone, in every simulation, so a
+        // client's prediction closes each ring on exactly the server's tick.
+        // It contracts before anything moves: a snake has to be inside the
+        // new ring at the end of the step that draws it.
+        if let Some(bounds) = self.scheduled_zone(post_tick)
+            && self.arena.zone != Some(bounds)
+        {
+            self.apply_event(GameEvent::ZoneContracted { bounds }, Some(&mut out));
+        }
+
         if self.resolve_player_inactivity(post_tick, &mut out) {
             self.tick = post_tick;
             return Ok(out);
@@ -3170,6 +4697,7 @@ impl GameState {
         // fresh meter is therefore not charged for the quantum that earned it.
         for snake in &mut self.arena.snakes {
             snake.drain_combo(tick_duration_ms);
+            snake.power_ups.drain(tick_duration_ms);
         }
 
         for pad in &mut self.arena.boost_pads {
@@ -3270,6 +4798,8 @@ impl GameState {
             }
         }
 
+        self.drive_ai_players(&movers, &mut turned_snake_ids, &mut out);
+
         for mut command_message in deferred_commands {
             // Reschedule one tick later on whichever id `CommandQueue` orders
             // by (the server id when present). The original sequence number is
@@ -3287,13 +4817,17 @@ impl GameState {
         let old_snakes = self.arena.snakes.clone();
 
         // Move snakes
-        for (snake_id, snake) in self.arena.snakes.iter_mut().enumerate() {
+        self.arena.sync_occupancy();
+        for snake_id in 0..self.arena.snakes.len() {
             if movers.contains(&(snake_id as u32)) {
-                snake.step_forward()
+                self.arena.step_snake(snake_id);
             }
         }
 
-        // Check for collisions
+        // Check for collisions. Every step above kept the occupancy grid
+        // current, so each head is looked up rather than tested against every
+        // body.
+        let occupancy = &self.arena.occupancy;
         let mut crashed_snakes: HashMap<u32, (Position, DeathCause)> = HashMap::new();
         let width = self.arena.width as i16;
         let height = self.arena.height as i16;
@@ -3306,8 +4840,10 @@ impl GameState {
                     continue 'main_snake_loop;
                 }
 
-                // Entering the enemy base kills the snake
+                // Entering the enemy base kills the snake, except in capture
+                // the flag, where the enemy flag stands inside it.
                 if let Some(team_id) = snake.team_id
+                    && self.properties.flags.is_none()
                     && self.arena.is_in_enemy_base(head, team_id)
                 {
                     crashed_snakes.insert(snake_id, (*head, DeathCause::EnemyBase));
@@ -3321,9 +4857,17 @@ impl GameState {
                 }
 
                 // If crashed with other snake
-                for (other_snake_id, other_snake) in self.iter_snakes() {
+                for other_snake_id in occupancy.snakes_at(head) {
+                    let other_snake = &self.arena.snakes[other_snake_id as usize];
                     let is_self = snake_id == other_snake_id;
-                    if other_snake.is_alive && other_snake.contains_point(head, is_self) {
+                    // A ghost and the snakes around it pass through one
+                    // another; only its own body stays solid to it.
+                    if !is_self && (snake.power_ups.ghost() || other_snake.power_ups.ghost()) {
+                        continue;
+                    }
+                    if other_snake.is_alive
+                        && occupancy.covers(&self.arena.snakes, other_snake_id, head, is_self)
+                    {
                         let cause = if is_self {
                             DeathCause::SelfCollision
                         } else if other_snake.is_head(head)
@@ -3349,6 +4893,20 @@ impl GameState {
             }
         }
 
+        // The zone catches every living snake whose head it has closed over,
+        // moving or not, unless something else already killed it this step.
+        if self.arena.zone.is_some() {
+            for (snake_id, snake) in self.iter_snakes() {
+                let head = snake.head()?;
+                if snake.is_alive
+                    && self.arena.is_outside_zone(head)
+                    && !crashed_snakes.contains_key(&snake_id)
+                {
+                    crashed_snakes.insert(snake_id, (*head, DeathCause::Zone));
+                }
+            }
+        }
+
         // Rollback and kill snakes that crashed.
         // Sorted order is required for determinism: HashSet iteration order
         // differs between processes (native server vs WASM client), and
@@ -3357,8 +4915,16 @@ impl GameState {
         let mut crashed_snakes: Vec<(u32, (Position, DeathCause))> =
             crashed_snakes.into_iter().collect();
         crashed_snakes.sort_unstable_by_key(|(snake_id, _)| *snake_id);
+        let mut fallen_bodies: Vec<Vec<Position>> = Vec::new();
         for (snake_id, (attempted_head, cause)) in crashed_snakes {
             self.arena.snakes[snake_id as usize] = old_snakes[snake_id as usize].clone();
+            // A shield spends itself on the crash instead of the life. The
+            // rollback above already holds the snake where it was, so the
+            // player gets one movement step to steer clear.
+            if self.arena.snakes[snake_id as usize].power_ups.shield {
+                self.apply_event(GameEvent::ShieldBroken { snake_id }, Some(&mut out));
+                continue;
+            }
             let crash_position = Position {
                 x: attempted_head.x.clamp(0, width.saturating_sub(1)),
                 y: attempted_head.y.clamp(0, height.saturating_sub(1)),
@@ -3369,9 +4935,13 @@ impl GameState {
                 position: crash_position,
                 cause: cause.clone(),
             });
+            self.drop_carried_flag(snake_id, &mut out);
+            if self.properties.death_food.is_some() {
+                fallen_bodies.push(self.arena.body_cells(&self.arena.snakes[snake_id as usize]));
+            }
             self.apply_event(GameEvent::SnakeDied { snake_id, cause }, Some(&mut out));
 
-            if let GameType::TeamMatch { .. } = &self.game_type
+            if self.game_type.is_team_game()
                 && let Some(event) = self.respawn_event_for_snake(snake_id)
             {
                 self.apply_event(event, Some(&mut out));
@@ -3379,7 +4949,7 @@ impl GameState {
         }
 
         // Eat food
-        let mut food_eaten_events: Vec<GameEvent> = Vec::new();
+        let mut food_claims: Vec<(u32, Position)> = Vec::new();
         let mut claimed_food_positions: HashSet<Position> = HashSet::new();
         for (snake_id, snake) in self.iter_snakes() {
             let head = snake.head()?;
@@ -3387,23 +4957,43 @@ impl GameState {
                 && self.arena.food.contains(head)
                 && claimed_food_positions.insert(*head)
             {
-                let (points, combo_chain, combo_remaining_ms_before, boost_active) =
-                    self.combo_pickup_context(snake);
-                food_eaten_events.push(GameEvent::FoodEaten {
-                    snake_id,
-                    position: *head,
-                    points,
-                    combo_chain,
-                    combo_remaining_ms_before,
-                    boost_active,
-                });
+                food_claims.push((snake_id, *head));
             }
         }
-        for event in food_eaten_events {
-            let GameEvent::FoodEaten { snake_id, .. } = &event else {
-                unreachable!("food collection only creates FoodEaten events")
+        // Magnets pull in a second pass, so a pellet under another snake's
+        // head is always that snake's. Food is visited in sorted order because
+        // replicas may hold the same set in a different order.
+        if let Some(config) = self.properties.power_ups.as_ref() {
+            let mut food = self.arena.food.clone();
+            food.sort_unstable_by_key(|position| (position.x, position.y));
+            for (snake_id, snake) in self.iter_snakes() {
+                if !snake.is_alive || !snake.power_ups.magnet() {
+                    continue;
+                }
+                let head = snake.head()?;
+                for position in &food {
+                    if config.magnet_reaches(head, position)
+                        && claimed_food_positions.insert(*position)
+                    {
+                        food_claims.push((snake_id, *position));
+                    }
+                }
+            }
+        }
+        // Each pickup's Combo context is read after the previous one applied,
+        // so a magnet eating several pellets in one step chains them exactly
+        // as if they had been eaten one after another.
+        for (snake_id, position) in food_claims {
+            let (points, combo_chain, combo_remaining_ms_before, boost_active) =
+                self.combo_pickup_context(&self.arena.snakes[snake_id as usize]);
+            let event = GameEvent::FoodEaten {
+                snake_id,
+                position,
+                points,
+                combo_chain,
+                combo_remaining_ms_before,
+                boost_active,
             };
-            let snake_id = *snake_id;
             let old_score = self.scores.get(&snake_id).copied().unwrap_or(0);
             self.apply_event(event, Some(&mut out));
             let score = self.scores.get(&snake_id).copied().unwrap_or(0);
@@ -3412,6 +5002,31 @@ impl GameState {
             }
         }
 
+        // Power-ups resolve after food, in spawn order. Like Boost packets, a
+        // contested pickup goes to the lowest snake ID.
+        if self.properties.power_ups.is_some() {
+            let pickups = self.arena.power_ups.clone();
+            for pickup in pickups {
+                let collector = self
+                    .iter_snakes()
+                    .filter(|(_, snake)| {
+                        snake.is_alive && snake.head().is_ok_and(|head| *head == pickup.position)
+                    })
+                    .map(|(snake_id, _)| snake_id)
+                    .min();
+                if let Some(snake_id) = collector {
+                    self.apply_event(
+                        GameEvent::PowerUpCollected {
+                            snake_id,
+                            kind: pickup.kind,
+                            position: pickup.position,
+                        },
+                        Some(&mut out),
+                    );
+                }
+            }
+        }
+
         // Resolve packets after collision and food from stable pad/snake IDs.
         // Collection stores fuel only; activation remains an explicit command.
         if let Some(boost_config) = self.properties.boost.clone() {
@@ -3466,6 +5081,148 @@ impl GameState {
             }
         }
 
+        // King of the hill: a team alone on the hill holds it, and every full
+        // interval it stays there alone is a point. This is accounted from
+        // positions in the movement path, so replicas score on the same ticks
+        // as the server and its events only confirm what they already drew.
+        if let (Some(config), Some(control)) = (self.properties.hill.clone(), self.hill)
+            && matches!(self.status, GameStatus::Started { .. })
+        {
+            let hill = config.bounds(self.arena.width, self.arena.height);
+            let mut present: Vec<TeamId> = self
+                .arena
+                .snakes
+                .iter()
+                .filter(|snake| snake.is_alive)
+                .filter_map(|snake| Some((snake.team_id?, *snake.head().ok()?)))
+                .filter(|(_, head)| hill.contains(head))
+                .map(|(team_id, _)| team_id)
+                .collect();
+            present.sort_unstable();
+            present.dedup();
+
+            if let [team_id] = present[..]
+                && control.holder != Some(team_id)
+            {
+                self.apply_event(
+                    GameEvent::HillCaptured {
+                        team_id,
+                        previous_holder: control.holder,
+                    },
+                    Some(&mut out),
+                );
+            }
+            let mut scored = None;
+            if let Some(control) = self.hill.as_mut() {
+                control.contested = present.len() > 1;
+                if let ([team_id], Some(holder)) = (&present[..], control.holder)
+                    && *team_id == holder
+                {
+                    control.held_ms += self.properties.tick_duration_ms;
+                    if control.held_ms >= config.point_interval_ms {
+                        control.held_ms -= config.point_interval_ms;
+                        scored = Some(holder);
+                    }
+                }
+            }
+            if let Some(team_id) = scored {
+                let score = self
+                    .team_scores
+                    .as_ref()
+                    .and_then(|scores| scores.get(&team_id).copied())
+                    .unwrap_or(0);
+                self.apply_event(
+                    GameEvent::TeamScoreUpdated {
+                        team_id,
+                        score: score + 1,
+                    },
+                    Some(&mut out),
+                );
+            }
+        }
+
+        // Capture the flag, in team order. A dropped flag left alone long
+        // enough goes home; otherwise the lowest enemy snake ID with its head
+        // on a flag takes it, and a defender's head on its own dropped flag
+        // returns it. A carrier scores the moment its head is in its own base.
+        // Like the hill this is read from positions and the tick alone.
+        if let Some(config) = self.properties.flags.clone()
+            && matches!(self.status, GameStatus::Started { .. })
+        {
+            for flag in self.flags.clone() {
+                let team_id = flag.team_id;
+                if let FlagPlacement::Carried { snake_id } = flag.placement {
+                    let Some(carrier) = self.arena.snakes.get(snake_id as usize) else {
+                        continue;
+                    };
+                    let Some(carrier_team) = carrier.team_id else {
+                        continue;
+                    };
+                    if carrier.is_alive
+                        && carrier
+                            .head()
+                            .is_ok_and(|head| self.arena.is_in_team_base(head, carrier_team))
+                    {
+                        self.apply_event(
+                            GameEvent::FlagCaptured { team_id, snake_id },
+                            Some(&mut out),
+                        );
+                        let score = self
+                            .team_scores
+                            .as_ref()
+                            .and_then(|scores| scores.get(&carrier_team).copied())
+                            .unwrap_or(0);
+                        self.apply_event(
+                            GameEvent::TeamScoreUpdated {
+                                team_id: carrier_team,
+                                score: score + 1,
+                            },
+                            Some(&mut out),
+                        );
+                    }
+                    continue;
+                }
+
+                if let FlagPlacement::Dropped { at_tick, .. } = flag.placement
+                    && post_tick >= config.return_tick(at_tick, tick_duration_ms)
+                {
+                    self.apply_event(GameEvent::FlagReturned { team_id }, Some(&mut out));
+                    continue;
+                }
+                let Some(position) = flag.position(&self.arena) else {
+                    continue;
+                };
+                let touching: Vec<(u32, Option<TeamId>)> = self
+                    .iter_snakes()
+                    .filter(|(_, snake)| {
+                        snake.is_alive && snake.head().is_ok_and(|head| *head == position)
+                    })
+                    .map(|(snake_id, snake)| (snake_id, snake.team_id))
+                    .collect();
+                let taker = touching
+                    .iter()
+                    .filter(|(_, team)| team.is_some_and(|team| team != team_id))
+                    .map(|(snake_id, _)| *snake_id)
+                    .min();
+                if let Some(snake_id) = taker {
+                    self.apply_event(
+                        GameEvent::FlagPickedUp { team_id, snake_id },
+                        Some(&mut out),
+                    );
+                } else if matches!(flag.placement, FlagPlacement::Dropped { .. })
+                    && touching.iter().any(|(_, team)| *team == Some(team_id))
+                {
+                    self.apply_event(GameEvent::FlagReturned { team_id }, Some(&mut out));
+                }
+            }
+        }
+
+        // Fallen snakes leave their bodies as food before the regular refill,
+        // which then only tops the field up to its usual target.
+        if !movement_only && !fallen_bodies.is_empty() {
+            self.drop_death_food(fallen_bodies, &mut out);
+        }
+
         // Spawn new food
         if !movement_only
             && self.food_refill_due()
@@ -3473,22 +5230,35 @@ impl GameState {
         {
             // The client will not have rng so it won't be able to spawn food.
             // This is by design as there's no reason for the client to spawn food.
+            self.arena.sync_occupancy();
             if let Some(rng) = &mut self.rng {
                 let position = sample_food_position(rng, &self.game_type, &self.arena);
 
                 if !self.arena.food.contains(&position)
                     && !self.arena.is_boost_pad_position(&position)
+                    && !self.arena.is_obstacle_position(&position)
+                    && !self.arena.is_outside_zone(&position)
                     && !self
                         .arena
-                        .snakes
-                        .iter()
-                        .any(|s| s.is_alive && s.contains_point(&position, false))
+                        .occupancy
+                        .live_snake_at(&self.arena.snakes, &position)
                 {
                     self.apply_event(GameEvent::FoodSpawned { position }, Some(&mut out));
                 }
             }
         }
 
+        // Spawn power-ups, on their own wall-clock cadence and only where the
+        // mode has opted in, so no other match ever draws from the RNG here.
+        if !movement_only
+            && let Some(config) = self.properties.power_ups.as_ref()
+            && self.arena.power_ups.len() < usize::from(config.max_on_field)
+            && self.wall_clock_interval_due(config.spawn_interval_ms)
+            && let Some(event) = self.power_up_spawn_event()
+        {
+            self.apply_event(event, Some(&mut out));
+        }
+
         // Cosmetic goal cues are recorded here, in the movement path, rather
         // than inside the authoritative scoring block below. A client advances
         // its committed state with `movement_only` and receives the score and
@@ -3636,7 +5406,9 @@ impl GameState {
 
             if matches!(self.status, GameStatus::Started { .. }) {
                 match &self.game_type {
-                    GameType::TeamMatch { .. } => {
+                    GameType::TeamMatch { .. }
+                    | GameType::KingOfTheHill { .. }
+                    | GameType::CaptureTheFlag { .. } => {
                         // The match runs until a team banks the target score.
                         // Scoring for this tick has already been applied above,
                         // so the comparison sees the crossing on the tick it
@@ -3644,12 +5416,18 @@ impl GameState {
                         // both teams can bank on the same tick, so the test is
                         // `>=` and the winner is the higher score among those
                         // that crossed — equal scores at the target are a draw.
+                        //
+                        // In a best-of-N match that only settles the round.
+                        // The match ends once a team has taken enough rounds;
+                        // until then the field is reset and the next round
+                        // starts on this same tick. A drawn round is played
+                        // again without either side gaining a win.
                         if let Some(score_limit) = self.properties.score_limit {
                             let reached = self.team_scores.as_ref().is_some_and(|scores| {
                                 scores.values().any(|score| *score >= score_limit)
                             });
-                            if reached {
-                                let winning_team = self.team_scores.as_ref().and_then(|scores| {
+                            let winning_team = if reached {
+                                self.team_scores.as_ref().and_then(|scores| {
                                     let max_score = scores.values().copied().max()?;
                                     let mut leaders = scores
                                         .iter()
@@ -3657,8 +5435,39 @@ impl GameState {
                                         .map(|(team_id, _)| *team_id);
                                     let leader = leaders.next()?;
                                     leaders.next().is_none().then_some(leader)
-                                });
-
+                                })
+                            } else {
+                                None
+                            };
+                            let match_decided = match self.rounds.as_ref() {
+                                Some(rounds) if reached => {
+                                    let round_number = rounds.current_round;
+                                    self.apply_event(
+                                        GameEvent::RoundEnded {
+                                            round_number,
+                                            winning_team_id: winning_team,
+                                        },
+                                        Some(&mut out),
+                                    );
+                                    let decided = self
+                                        .rounds
+                                        .as_ref()
+                                        .and_then(RoundState::match_winner)
+                                        .is_some();
+                                    if !decided {
+                                        self.apply_event(
+                                            GameEvent::RoundStarted {
+                                                round_number: round_number + 1,
+                                            },
+                                            Some(&mut out),
+                                        );
+                                        self.fill_food_to_target(Some(&mut out));
+                                    }
+                                    decided
+                                }
+                                _ => reached,
+                            };
+                            if match_decided {
                                 let winning_snake_id = winning_team
                                     .and_then(|team_id| {
                                         self.arena
@@ -3683,7 +5492,7 @@ impl GameState {
 
                                 let mut player_xp_awards = HashMap::new();
                                 for (user_id, player) in &self.players {
-                                    if self.is_player_idle_kicked(*user_id) {
+                                    if self.earns_no_credit(*user_id) {
                                         player_xp_awards.insert(*user_id, 0);
                                         continue;
                                     }
@@ -3719,12 +5528,32 @@ impl GameState {
                         }
                     }
                     _ => {
-                        if alive_snakes.is_empty() {
-                            let winning_snake_id = None;
+                        // A battle royale is won by outlasting everyone else,
+                        // so it ends as soon as one snake is left. Alone on
+                        // the field there is nobody to outlast, and the zone
+                        // plays out like any other solo run.
+                        let last_standing = self.properties.zone.is_some()
+                            && self.arena.snakes.len() > 1
+                            && alive_snakes.len() == 1;
+                        // AI opponents are there for the players; once no
+                        // player is left alive there is nobody to play for.
+                        let only_ai_left = !self.ai_players.is_empty()
+                            && self.players.len() > self.ai_players.len()
+                            && self
+                                .players
+                                .iter()
+                                .filter(|(user_id, _)| !self.is_ai_driven(**user_id))
+                                .all(|(_, player)| !alive_snakes.contains(&player.snake_id));
+                        if alive_snakes.is_empty() || last_standing || only_ai_left {
+                            let winning_snake_id = if last_standing {
+                                alive_snakes.first().copied()
+                            } else {
+                                None
+                            };
 
                             let mut player_xp_awards = HashMap::new();
                             for (user_id, player) in &self.players {
-                                if self.is_player_idle_kicked(*user_id) {
+                                if self.earns_no_credit(*user_id) {
                                     player_xp_awards.insert(*user_id, 0);
                                     continue;
                                 }
@@ -3735,7 +5564,12 @@ impl GameState {
                                     .copied()
                                     .unwrap_or(0);
                                 let base_xp = pickups.saturating_mul(10);
-                                player_xp_awards.insert(*user_id, base_xp + 10);
+                                let bonus_xp = if winning_snake_id == Some(player.snake_id) {
+                                    50
+                                } else {
+                                    10
+                                };
+                                player_xp_awards.insert(*user_id, base_xp + bonus_xp);
                             }
 
                             self.apply_event(
@@ -3760,6 +5594,10 @@ impl GameState {
         // Increment tick
         self.tick += 1;
 
+        // Leave the grid current for whoever reads this tick's state, such as
+        // the AI deciding its next move.
+        self.arena.sync_occupancy();
+
         Ok(out)
     }
 
@@ -3878,6 +5716,18 @@ impl GameState {
                 *self = game_state;
             }
 
+            // A delta only fits the snapshot it was computed against, and
+            // the hash check means it can never half-apply anywhere else.
+            GameEvent::SnapshotDelta {
+                base_tick,
+                changes,
+                hash,
+            } => {
+                if let Ok(game_state) = apply_snapshot_delta(self, base_tick, &changes, hash) {
+                    *self = game_state;
+                }
+            }
+
             GameEvent::SnakeTurned {
                 snake_id,
                 direction,
@@ -3896,11 +5746,32 @@ impl GameState {
                     snake.is_alive = false;
                     snake.reset_boost_and_movement(None);
                     snake.reset_combo();
+                    snake.reset_power_ups();
                 }
                 self.command_queue
                     .discard_player_commands_for_snake(snake_id);
             }
 
+            GameEvent::PlayerAutopilotChanged {
+                user_id, enabled, ..
+            } => {
+                if enabled {
+                    let difficulty = self.properties.idle_autopilot.unwrap_or_default();
+                    // A replica that resolved the takeover in its own step
+                    // has already driven the seat; the server's copy of the
+                    // event must not rewind its next decision.
+                    self.autopilot_players.entry(user_id).or_insert(AiSeat {
+                        difficulty,
+                        next_decision_tick: self.tick,
+                    });
+                    if let Err(index) = self.autopiloted_user_ids.binary_search(&user_id) {
+                        self.autopiloted_user_ids.insert(index, user_id);
+                    }
+                } else {
+                    self.autopilot_players.remove(&user_id);
+                }
+            }
+
             GameEvent::PlayerIdleKicked { user_id, snake_id } => {
                 if let Err(index) = self.idle_kicked_user_ids.binary_search(&user_id) {
                     self.idle_kicked_user_ids.insert(index, user_id);
@@ -3911,6 +5782,7 @@ impl GameState {
                     // life to hand a full unlimited tank to.
                     snake.reset_boost_and_movement(None);
                     snake.reset_combo();
+                    snake.reset_power_ups();
                     // Ordinary team deaths preserve a physically-held Boost
                     // intent for respawn. An idle removal is permanent, so no
                     // input latch may survive on the dead snake.
@@ -3986,6 +5858,87 @@ impl GameState {
                 }
             }
 
+            GameEvent::PowerUpSpawned { kind, position } => {
+                if self.properties.power_ups.is_some()
+                    && self.arena.power_up_at(&position).is_none()
+                {
+                    self.arena.power_ups.push(PowerUpPickup { kind, position });
+                }
+            }
+
+            GameEvent::PowerUpCollected {
+                snake_id,
+                kind,
+                position,
+            } => {
+                // A replica that already collected this pickup while advancing
+                // movement has nothing left to remove, and must not re-grant.
+                let index = self
+                    .arena
+                    .power_ups
+                    .iter()
+                    .position(|pickup| pickup.position == position && pickup.kind == kind);
+                if let Some(index) = index
+                    && let Some(config) = self.properties.power_ups.clone()
+                    && let Some(snake) = self.arena.snakes.get_mut(snake_id as usize)
+                {
+                    snake.power_ups.grant(kind, &config);
+                    self.arena.power_ups.remove(index);
+                }
+            }
+
+            GameEvent::ShieldBroken { snake_id } => {
+                if let Ok(snake) = self.get_snake_mut(snake_id) {
+                    snake.power_ups.shield = false;
+                }
+            }
+
+            // The zone only ever shrinks, so a ring this state has already
+            // passed is a no-op rather than a step back out.
+            GameEvent::ZoneContracted { bounds } => {
+                if let Some(zone) = &mut self.arena.zone
+                    && zone.encloses(&bounds)
+                {
+                    *zone = bounds;
+                }
+            }
+
+            GameEvent::HillCaptured { team_id, .. } => {
+                // A replica that derived this capture already holds the hill
+                // for the team and keeps its progress toward the next point.
+                if let Some(control) = &mut self.hill
+                    && control.holder != Some(team_id)
+                {
+                    control.holder = Some(team_id);
+                    control.held_ms = 0;
+                }
+            }
+
+            // Flag events name where the flag ends up, so a replica that
+            // derived the same move already holds that placement.
+            GameEvent::FlagPickedUp { team_id, snake_id } => {
+                self.place_flag(team_id, FlagPlacement::Carried { snake_id });
+            }
+
+            // Only the carrier's flag falls; a replica may already have seen
+            // it picked up again or returned on the same tick.
+            GameEvent::FlagDropped {
+                team_id,
+                snake_id,
+                position,
+                at_tick,
+            } => {
+                if let Some(flag) = self.flags.iter_mut().find(|flag| flag.team_id == team_id)
+                    && flag.carrier() == Some(snake_id)
+                {
+                    flag.placement = FlagPlacement::Dropped { position, at_tick };
+                }
+            }
+
+            GameEvent::FlagReturned { team_id } | GameEvent::FlagCaptured { team_id, .. } => {
+                self.place_flag(team_id, FlagPlacement::Home);
+            }
+
             GameEvent::CommandScheduled { command_message } => {
                 self.record_player_activity_for_command(&command_message, self.tick);
                 self.command_queue.push(command_message);
@@ -4046,45 +5999,35 @@ impl GameState {
                 }
             }
 
-            GameEvent::SnakeRespawned {
-                snake_id,
-                position,
-                direction,
+            GameEvent::RoundEnded {
+                round_number,
+                winning_team_id,
             } => {
-                let snake_length = self.starting_snake_length() as i16;
-
-                // Build compressed snake body: just head and tail for a straight snake
-                let tail_pos = match direction {
-                    Direction::Left => Position {
-                        x: position.x + snake_length - 1,
-                        y: position.y,
-                    },
-                    Direction::Right => Position {
-                        x: position.x - snake_length + 1,
-                        y: position.y,
-                    },
-                    Direction::Up => Position {
-                        x: position.x,
-                        y: position.y + snake_length - 1,
-                    },
-                    Direction::Down => Position {
-                        x: position.x,
-                        y: position.y - snake_length + 1,
-                    },
-                };
+                if let Some(rounds) = self.rounds.as_mut()
+                    && rounds.current_round == round_number
+                    && !rounds.is_between_rounds()
+                {
+                    rounds.rounds_completed = round_number;
+                    if let Some(team_id) = winning_team_id {
+                        *rounds.round_wins.entry(team_id).or_default() += 1;
+                    }
+                }
+            }
 
-                // Now update the snake
-                let refill = self.unlimited_boost_capacity_ms();
-                if let Ok(snake) = self.get_snake_mut(snake_id) {
-                    snake.body = vec![position, tail_pos];
-                    snake.direction = direction;
-                    snake.is_alive = true;
-                    snake.food = 0;
-                    snake.reset_boost_and_movement(refill);
-                    snake.reset_combo();
+            GameEvent::RoundStarted { round_number } => {
+                if self.rounds.as_ref().is_some_and(|rounds| {
+                    rounds.is_between_rounds() && rounds.current_round + 1 == round_number
+                }) {
+                    self.reset_for_round(round_number);
                 }
-                self.command_queue
-                    .discard_player_commands_for_snake(snake_id);
+            }
+
+            GameEvent::SnakeRespawned {
+                snake_id,
+                position,
+                direction,
+            } => {
+                self.respawn_snake(snake_id, position, direction);
             }
 
             GameEvent::XPAwarded { player_xp } => {
@@ -4095,6 +6038,82 @@ impl GameState {
             GameEvent::TickHash { .. } => {}
         }
     }
+
+    /// Put a snake back on the field with a fresh life: a straight body of the
+    /// starting length headed at `position`, no carried food, and a reset
+    /// Boost and Combo.
+    fn respawn_snake(&mut self, snake_id: u32, position: Position, direction: Direction) {
+        let snake_length = self.starting_snake_length() as i16;
+
+        // Build compressed snake body: just head and tail for a straight snake
+        let tail_pos = match direction {
+            Direction::Left => Position {
+                x: position.x + snake_length - 1,
+                y: position.y,
+            },
+            Direction::Right => Position {
+                x: position.x - snake_length + 1,
+                y: position.y,
+            },
+            Direction::Up => Position {
+                x: position.x,
+                y: position.y + snake_length - 1,
+            },
+            Direction::Down => Position {
+                x: position.x,
+                y: position.y - snake_length + 1,
+            },
+        };
+
+        // Now update the snake
+        let refill = self.unlimited_boost_capacity_ms();
+        if let Ok(snake) = self.get_snake_mut(snake_id) {
+            snake.body = vec![position, tail_pos];
+            snake.direction = direction;
+            snake.is_alive = true;
+            snake.food = 0;
+            snake.reset_boost_and_movement(refill);
+            snake.reset_combo();
+            snake.reset_power_ups();
+        }
+        self.command_queue
+            .discard_player_commands_for_snake(snake_id);
+    }
+
+    /// Clear the field for the next round of a best-of-N match. Everything a
+    /// round accumulates on the field goes — banked score, food, carried food,
+    /// pad cooldowns — while match-long history (per-snake scores, pickups,
+    /// round wins) stays. Players removed for inactivity stay out.
+    fn reset_for_round(&mut self, round_number: u32) {
+        if let Some(rounds) = self.rounds.as_mut() {
+            rounds.current_round = round_number;
+        }
+        if let Some(team_scores) = self.team_scores.as_mut() {
+            for score in team_scores.values_mut() {
+                *score = 0;
+            }
+        }
+        self.arena.food.clear();
+        self.arena.power_ups.clear();
+        for pad in &mut self.arena.boost_pads {
+            pad.respawn_at_tick = None;
+        }
+
+        let removed_snakes: HashSet<u32> = self
+            .players
+            .iter()
+            .filter(|(user_id, _)| self.is_player_idle_kicked(**user_id))
+            .map(|(_, player)| player.snake_id)
+            .collect();
+        let starting_positions = self.starting_positions(self.players.len());
+        for (snake_id, (position, direction)) in starting_positions.into_iter().enumerate() {
+            let snake_id = snake_id as u32;
+            if snake_id as usize >= self.arena.snakes.len() || removed_snakes.contains(&snake_id) {
+                continue;
+            }
+            self.respawn_snake(snake_id, position, direction);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -4313,7 +6332,7 @@ mod readiness_tests {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::SnakeBoost;
+    use crate::{SnakeBoost, flag_home};
     use std::collections::BinaryHeap;
 
     fn create_command_id(tick: u32, user_id: u32, seq: u32) -> CommandId {
@@ -4389,6 +6408,7 @@ mod tests {
             movement_credit: 0,
             boost: Default::default(),
             combo: Default::default(),
+            power_ups: Default::default(),
         });
 
         let events = game
@@ -4418,6 +6438,7 @@ mod tests {
             movement_credit: 0,
             boost: Default::default(),
             combo: Default::default(),
+            power_ups: Default::default(),
         });
 
         // Solo runs a 50ms quantum, so the wall is reached on the quantum that
@@ -4469,6 +6490,140 @@ mod tests {
         );
     }
 
+    fn crossroads_game(players: u32) -> GameState {
+        let map = ArenaMap::builtin("crossroads").expect("built-in map");
+        let mut game = GameState::new_on_map(
+            map,
+            GameType::FreeForAll { max_players: 4 },
+            QueueMode::Quickmatch,
+            Some(7),
+            0,
+        )
+        .expect("crossroads hosts free-for-all");
+        for user_id in 1..=players {
+            game.add_player(user_id, None).expect("player joins");
+        }
+        game
+    }
+
+    #[test]
+    fn classic_matches_keep_their_map_implicit() {
+        let game = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            None,
+            0,
+        );
+        assert!(game.arena.map.is_none());
+        assert_eq!(game.arena.map().id, "classic-team");
+        let json = serde_json::to_value(&game).expect("serialize state");
+        assert!(json["arena"].get("map").is_none());
+        assert_eq!(
+            game.properties.boost,
+            boost_config_for(&game.game_type, &game.arena.map())
+        );
+    }
+
+    #[test]
+    fn map_obstacles_kill_like_walls() {
+        let mut game = crossroads_game(1);
+        let snake = &mut game.arena.snakes[0];
+        snake.body = vec![Position { x: 7, y: 9 }, Position { x: 4, y: 9 }];
+        snake.direction = Direction::Right;
+
+        let mut events = Vec::new();
+        while game.arena.snakes[0].is_alive {
+            assert!(game.current_tick() < 8, "snake should have hit the block");
+            events.extend(game.tick_forward(true).expect("tick"));
+        }
+        assert_death_cause(&events, 0, DeathCause::Wall);
+        assert_eq!(
+            game.arena.snakes[0].head().unwrap(),
+            &Position { x: 7, y: 9 }
+        );
+    }
+
+    #[test]
+    fn map_spawn_points_are_assigned_in_snake_order() {
+        let game = crossroads_game(3);
+        let spawns = &game.arena.map.as_ref().unwrap().spawn_points;
+        for (snake, spawn) in game.arena.snakes.iter().zip(spawns) {
+            assert_eq!(snake.head().unwrap(), &spawn.position);
+            assert_eq!(snake.direction, spawn.direction);
+            assert_eq!(snake.length(), DEFAULT_SNAKE_LENGTH);
+        }
+    }
+
+    #[test]
+    fn placed_boost_pads_come_from_the_map() {
+        let game = crossroads_game(0);
+        let config = game.properties.boost.as_ref().expect("collectible Boost");
+        assert_eq!(config.spot_layout_version, BOOST_SPOT_LAYOUT_VERSION_MAP);
+        assert_eq!(game.arena.boost_pads.len(), 8);
+        assert!(game.arena.boost_pads.iter().all(|pad| {
+            pad.charge_ms
+                == if pad.size_cells == 2 {
+                    config.capacity_ms
+                } else {
+                    config.packet_charge_ms
+                }
+        }));
+
+        // A map-placed layout does not fit a map that places nothing.
+        let mut swapped = game.clone();
+        swapped.arena.map.as_mut().unwrap().boost = MapBoostPlacement::None;
+        assert!(swapped.validate_boost_invariants().is_err());
+    }
+
+    #[test]
+    fn food_never_spawns_inside_an_obstacle() {
+        let mut game = crossroads_game(2);
+        game.properties.available_food_target = 400;
+        game.spawn_initial_food();
+        assert!(game.arena.food.len() > 100);
+        assert!(
+            game.arena
+                .food
+                .iter()
+                .all(|food| !game.arena.is_obstacle_position(food))
+        );
+    }
+
+    #[test]
+    fn stored_maps_must_describe_their_arena() {
+        let game = crossroads_game(2);
+        game.validate_boost_invariants().expect("valid map state");
+
+        let mut resized = game.clone();
+        resized.arena.map.as_mut().unwrap().width = 41;
+        assert!(resized.validate_boost_invariants().is_err());
+
+        let mut implicit = GameState::new(
+            40,
+            40,
+            GameType::FreeForAll { max_players: 4 },
+            QueueMode::Quickmatch,
+            None,
+            0,
+        );
+        implicit.arena.map = Some(implicit.arena.map().into_owned());
+        assert!(implicit.validate_boost_invariants().is_err());
+
+        assert!(
+            GameState::new_on_map(
+                ArenaMap::builtin("crossroads").unwrap(),
+                GameType::TeamMatch { per_team: 1 },
+                QueueMode::Quickmatch,
+                None,
+                0,
+            )
+            .is_err(),
+            "a teamless map cannot host a team match"
+        );
+    }
+
     #[test]
     fn snapshots_without_recent_crashes_remain_compatible() {
         let state = GameState::new(10, 10, GameType::Solo, QueueMode::Quickmatch, None, 0);
@@ -6696,6 +8851,11 @@ mod tests {
                     is_private: false,
                     allow_spectators: true,
                     snake_start_length: 4,
+                    power_ups: false,
+                    shrinking_zone: false,
+                    edge_policy: EdgePolicy::Solid,
+                    death_food: false,
+                    ai_opponents: None,
                 },
             },
             QueueMode::Quickmatch,
@@ -6722,6 +8882,11 @@ mod tests {
                     is_private: true,
                     allow_spectators: true,
                     snake_start_length: 4,
+                    power_ups: false,
+                    shrinking_zone: false,
+                    edge_policy: EdgePolicy::Solid,
+                    death_food: false,
+                    ai_opponents: None,
                 },
             },
             QueueMode::Quickmatch,
@@ -6886,7 +9051,7 @@ mod tests {
                 ..BoostConfig::default()
             },
             BoostConfig {
-                spot_layout_version: BOOST_SPOT_LAYOUT_VERSION_FIELD + 1,
+                spot_layout_version: BOOST_SPOT_LAYOUT_VERSION_MAP + 1,
                 ..BoostConfig::default()
             },
             BoostConfig {
@@ -7822,115 +9987,1271 @@ mod tests {
         ));
     }
 
-    #[test]
-    fn inactivity_fires_on_the_exact_authoritative_deadline() {
-        let mut game = started_inactivity_team_game(2);
-        let timeout_ticks = inactivity_timeout_ticks(&game);
-        let idle_snake_id = game.players[&1].snake_id;
-
-        game.tick = timeout_ticks - 2;
-        game.player_last_activity_ticks.insert(1, 0);
-        for user_id in [2, 3, 4] {
-            game.player_last_activity_ticks.insert(user_id, game.tick);
+    fn competitive_round_game() -> GameState {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Competitive,
+            Some(1234),
+            0,
+        );
+        for user_id in 1..=2 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add round test player");
         }
-
-        let before_deadline = game.tick_forward(true).expect("deadline minus one");
-        assert_eq!(game.tick, timeout_ticks - 1);
-        assert!(game.idle_kicked_user_ids.is_empty());
-        assert!(game.arena.snakes[idle_snake_id as usize].is_alive);
-        assert!(!before_deadline.iter().any(|(_, event)| matches!(
-            event,
-            GameEvent::PlayerIdleKicked { user_id: 1, snake_id }
-                if *snake_id == idle_snake_id
-        )));
-
-        let at_deadline = game.tick_forward(true).expect("exact deadline");
-        assert_eq!(game.tick, timeout_ticks);
-        assert_eq!(game.idle_kicked_user_ids, vec![1]);
-        assert!(!game.arena.snakes[idle_snake_id as usize].is_alive);
-        assert!(matches!(game.status, GameStatus::Started { .. }));
-        assert!(at_deadline.iter().any(|(_, event)| matches!(
-            event,
-            GameEvent::PlayerIdleKicked { user_id: 1, snake_id }
-                if *snake_id == idle_snake_id
-        )));
+        game.status = GameStatus::Started { server_id: 7 };
+        game
     }
 
-    #[test]
-    fn distinct_noop_and_player_activity_commands_reset_the_full_deadline() {
-        let mut game = started_inactivity_team_game(1);
-        let timeout_ticks = inactivity_timeout_ticks(&game);
-        game.tick = timeout_ticks - 1;
-        for user_id in [1, 2] {
-            game.player_last_activity_ticks.insert(user_id, 0);
-        }
-
-        let first_snake_id = game.players[&1].snake_id;
-        let current_direction = game.arena.snakes[first_snake_id as usize].direction;
-        schedule_inactivity_test_command(
-            &mut game,
-            1,
-            1,
-            GameCommand::Turn {
-                snake_id: first_snake_id,
-                direction: current_direction,
+    /// Bank enough for `team_id` to reach the limit and run the deciding tick.
+    fn win_round(game: &mut GameState, team_id: TeamId) -> Vec<(u64, GameEvent)> {
+        let score_limit = game.properties.score_limit.expect("team score limit");
+        game.apply_event(
+            GameEvent::TeamScoreUpdated {
+                team_id,
+                score: score_limit,
             },
+            None,
         );
-        let second_snake_id = game.players[&2].snake_id;
-        schedule_inactivity_test_command(
-            &mut game,
-            2,
-            1,
-            GameCommand::PlayerActivity {
-                snake_id: second_snake_id,
-            },
+        game.tick_forward(false).expect("round-deciding tick")
+    }
+
+    #[test]
+    fn competitive_team_matches_are_best_of_three() {
+        let mut game = competitive_round_game();
+        assert_eq!(
+            game.rounds.as_ref().map(|rounds| rounds.rounds_to_win),
+            Some(DEFAULT_COMPETITIVE_TEAM_ROUNDS_TO_WIN)
         );
 
-        assert_eq!(game.player_last_activity_ticks[&1], timeout_ticks - 1);
-        assert_eq!(game.player_last_activity_ticks[&2], timeout_ticks - 1);
-        game.tick_forward(true)
-            .expect("both commands reset activity before expiry");
-        assert!(matches!(game.status, GameStatus::Started { .. }));
-        assert!(game.idle_kicked_user_ids.is_empty());
+        win_round(&mut game, TeamId(1));
+        assert!(!game.is_complete(), "one round does not decide the match");
+        win_round(&mut game, TeamId(0));
+        assert!(!game.is_complete());
+        let rounds = game.rounds.as_ref().expect("round state");
+        assert_eq!((rounds.current_round, rounds.rounds_completed), (3, 2));
+        assert_eq!((rounds.wins(TeamId(0)), rounds.wins(TeamId(1))), (1, 1));
 
-        let renewed_deadline = (timeout_ticks - 1) + timeout_ticks;
-        game.tick = renewed_deadline - 2;
-        game.tick_forward(true).expect("renewed deadline minus one");
-        assert!(game.idle_kicked_user_ids.is_empty());
-        game.tick_forward(true).expect("renewed exact deadline");
-        assert_eq!(game.tick, renewed_deadline);
-        assert_eq!(game.idle_kicked_user_ids, vec![1, 2]);
+        // The side that dropped the first round comes back to take the match.
+        win_round(&mut game, TeamId(0));
+        let GameStatus::Complete { winning_snake_id } = game.status else {
+            panic!("the second round win must complete the match");
+        };
+        let winner = winning_snake_id.expect("the match has a winner");
+        assert_eq!(game.arena.snakes[winner as usize].team_id, Some(TeamId(0)));
+        assert_eq!(
+            game.rounds.as_ref().and_then(RoundState::match_winner),
+            Some(TeamId(0))
+        );
     }
 
     #[test]
-    fn duel_inactivity_awards_the_forfeit_to_the_active_opponent() {
-        let mut game = started_inactivity_team_game(1);
-        let timeout_ticks = inactivity_timeout_ticks(&game);
-        let active_snake_id = game.players[&1].snake_id;
-        let idle_snake_id = game.players[&2].snake_id;
-        game.tick = timeout_ticks - 1;
-        game.player_last_activity_ticks.insert(1, game.tick);
-        game.player_last_activity_ticks.insert(2, 0);
+    fn a_new_round_resets_the_field_but_keeps_match_history() {
+        let mut game = competitive_round_game();
+        let snake_id = game.players[&1].snake_id;
+        let start = game.arena.snakes[snake_id as usize].body.clone();
+        game.scores.insert(snake_id, 9);
+        game.arena.snakes[snake_id as usize].food = 4;
+        game.arena.snakes[snake_id as usize].body =
+            vec![Position { x: 30, y: 20 }, Position { x: 30, y: 23 }];
+        game.arena.food.clear();
 
-        let events = game.tick_forward(true).expect("duel forfeit");
+        let events = win_round(&mut game, TeamId(1));
 
-        assert_eq!(game.idle_kicked_user_ids, vec![2]);
-        assert!(game.completed_by_inactivity);
-        assert!(matches!(
-            game.status,
-            GameStatus::Complete {
-                winning_snake_id: Some(winner)
-            } if winner == active_snake_id
-        ));
-        assert!(game.arena.snakes[active_snake_id as usize].is_alive);
-        assert!(!game.arena.snakes[idle_snake_id as usize].is_alive);
         assert!(events.iter().any(|(_, event)| matches!(
             event,
-            GameEvent::StatusUpdated {
-                status: GameStatus::Complete {
-                    winning_snake_id: Some(winner)
-                }
-            } if *winner == active_snake_id
+            GameEvent::RoundEnded {
+                round_number: 1,
+                winning_team_id: Some(TeamId(1)),
+            }
+        )));
+        assert!(
+            events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::RoundStarted { round_number: 2 }))
+        );
+        assert_eq!(
+            game.team_scores,
+            Some(HashMap::from([(TeamId(0), 0), (TeamId(1), 0)]))
+        );
+        let snake = &game.arena.snakes[snake_id as usize];
+        assert!(snake.is_alive);
+        assert_eq!(snake.food, 0);
+        assert_eq!(
+            snake.body, start,
+            "every snake is back on its starting cell"
+        );
+        assert_eq!(game.scores.get(&snake_id), Some(&9));
+        assert_eq!(
+            game.arena.food.len(),
+            game.properties.available_food_target,
+            "the new round starts with a full field"
+        );
+    }
+
+    #[test]
+    fn a_drawn_round_is_replayed_without_a_winner() {
+        let mut game = competitive_round_game();
+        let score_limit = game.properties.score_limit.expect("team score limit");
+        for team_id in [TeamId(0), TeamId(1)] {
+            game.apply_event(
+                GameEvent::TeamScoreUpdated {
+                    team_id,
+                    score: score_limit,
+                },
+                None,
+            );
+        }
+        game.tick_forward(false).expect("level round");
+
+        assert!(!game.is_complete());
+        let rounds = game.rounds.as_ref().expect("round state");
+        assert_eq!((rounds.current_round, rounds.rounds_completed), (2, 1));
+        assert_eq!((rounds.wins(TeamId(0)), rounds.wins(TeamId(1))), (0, 0));
+    }
+
+    #[test]
+    fn replicas_reproduce_round_transitions_from_events() {
+        let mut game = competitive_round_game();
+        // Step past tick 0, whose opening snapshot would simply overwrite the
+        // replica with the server's pre-tick state.
+        game.tick_forward(false).expect("opening tick");
+        let mut replica = game.clone();
+        replica.rng = None;
+
+        let events = win_round(&mut game, TeamId(0));
+        replica.tick_forward(true).expect("replica movement");
+        for (_, event) in &events {
+            replica
+                .try_apply_replicated_event(event.clone())
+                .expect("authoritative round transition");
+        }
+        assert_eq!(replica.sync_hash(), game.sync_hash());
+
+        // Re-delivery is a no-op rather than a second win or a second reset.
+        for (_, event) in events {
+            if matches!(
+                event,
+                GameEvent::RoundEnded { .. } | GameEvent::RoundStarted { .. }
+            ) {
+                replica
+                    .try_apply_replicated_event(event)
+                    .expect("duplicate round transition");
+            }
+        }
+        assert_eq!(replica.sync_hash(), game.sync_hash());
+
+        assert!(
+            replica
+                .try_apply_replicated_event(GameEvent::RoundStarted { round_number: 4 })
+                .is_err(),
+            "a round this match has not reached must be refused"
+        );
+        let mut quickmatch = boost_test_game(2);
+        assert!(
+            quickmatch
+                .try_apply_replicated_event(GameEvent::RoundEnded {
+                    round_number: 1,
+                    winning_team_id: Some(TeamId(0)),
+                })
+                .is_err(),
+            "single-round matches have no rounds to end"
+        );
+    }
+
+    /// A two-snake Custom game with power-ups on and no food, both snakes
+    /// heading right along their own rows.
+    fn power_up_game() -> GameState {
+        let mut game = GameState::new(
+            40,
+            40,
+            GameType::Custom {
+                settings: CustomGameSettings {
+                    power_ups: true,
+                    ..CustomGameSettings::default()
+                },
+            },
+            QueueMode::Quickmatch,
+            Some(99),
+            0,
+        );
+        for user_id in 1..=2 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add power-up test player");
+        }
+        game.tick_forward(false).expect("opening tick");
+        game.properties.available_food_target = 0;
+        game.arena.food.clear();
+        for (snake_id, row) in [(0, 10), (1, 30)] {
+            let snake = &mut game.arena.snakes[snake_id];
+            snake.body = vec![Position { x: 10, y: row }, Position { x: 7, y: row }];
+            snake.direction = Direction::Right;
+        }
+        game.status = GameStatus::Started { server_id: 7 };
+        game
+    }
+
+    #[test]
+    fn power_ups_spawn_only_in_games_that_opt_in() {
+        let mut game = GameState::new(
+            40,
+            40,
+            GameType::Custom {
+                settings: CustomGameSettings {
+                    power_ups: true,
+                    ..CustomGameSettings::default()
+                },
+            },
+            QueueMode::Quickmatch,
+            Some(5),
+            0,
+        );
+        let config = game.properties.power_ups.clone().expect("opted in");
+        let mut spawned = 0;
+        for _ in 0..400 {
+            for (_, event) in game.tick_forward(false).expect("tick") {
+                if let GameEvent::PowerUpSpawned { kind, .. } = event {
+                    assert!(config.allows(kind));
+                    spawned += 1;
+                }
+            }
+            assert!(game.arena.power_ups.len() <= usize::from(config.max_on_field));
+        }
+        assert!(spawned > 0, "400 ticks cover several spawn intervals");
+        assert_eq!(spawned, game.arena.power_ups.len());
+        game.validate_boost_invariants()
+            .expect("valid power-up state");
+
+        let mut ranked = GameState::new(
+            60,
+            40,
+            GameType::FreeForAll { max_players: 4 },
+            QueueMode::Competitive,
+            Some(5),
+            0,
+        );
+        assert!(ranked.properties.power_ups.is_none());
+        for _ in 0..400 {
+            ranked.tick_forward(false).expect("tick");
+        }
+        assert!(ranked.arena.power_ups.is_empty());
+        assert!(
+            ranked
+                .try_apply_replicated_event(GameEvent::PowerUpSpawned {
+                    kind: PowerUpKind::Shield,
+                    position: Position { x: 5, y: 5 },
+                })
+                .is_err(),
+            "a ranked mode must refuse power-ups from the wire"
+        );
+        ranked.properties.power_ups = Some(PowerUpConfig::default());
+        assert!(ranked.validate_boost_invariants().is_err());
+    }
+
+    #[test]
+    fn collecting_a_power_up_grants_its_effect_until_it_runs_out() {
+        let mut game = power_up_game();
+        let pickup = Position { x: 11, y: 10 };
+        game.apply_event(
+            GameEvent::PowerUpSpawned {
+                kind: PowerUpKind::Ghost,
+                position: pickup,
+            },
+            None,
+        );
+
+        let events = game.tick_forward(false).expect("collecting tick");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::PowerUpCollected {
+                snake_id: 0,
+                kind: PowerUpKind::Ghost,
+                ..
+            }
+        )));
+        assert!(game.arena.power_ups.is_empty());
+        let duration_ms = game
+            .properties
+            .power_ups
+            .as_ref()
+            .unwrap()
+            .ghost_duration_ms;
+        assert_eq!(
+            game.arena.snakes[0].power_ups.ghost_remaining_ms,
+            duration_ms
+        );
+
+        let tick_ms = game.properties.tick_duration_ms;
+        for _ in 0..duration_ms / tick_ms {
+            game.tick_forward(false).expect("tick");
+            if game.arena.snakes[0].head().unwrap().x >= 35 {
+                game.arena.snakes[0].direction = Direction::Down;
+            }
+        }
+        assert!(game.arena.snakes[0].power_ups.is_inactive());
+    }
+
+    #[test]
+    fn a_shield_absorbs_one_crash_and_then_breaks() {
+        let mut game = power_up_game();
+        game.arena.snakes[0].body = vec![Position { x: 39, y: 10 }, Position { x: 36, y: 10 }];
+        game.arena.snakes[0].power_ups.shield = true;
+
+        let events = game.tick_forward(false).expect("crash tick");
+        assert!(
+            events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::ShieldBroken { snake_id: 0 }))
+        );
+        let snake = &game.arena.snakes[0];
+        assert!(snake.is_alive, "the shield took the crash");
+        assert!(!snake.power_ups.shield);
+        assert_eq!(snake.head().unwrap(), &Position { x: 39, y: 10 });
+
+        game.tick_forward(false).expect("second crash");
+        assert!(
+            !game.arena.snakes[0].is_alive,
+            "a broken shield saves nothing"
+        );
+    }
+
+    #[test]
+    fn a_ghost_passes_through_other_snakes_but_not_walls() {
+        let mut game = power_up_game();
+        // Snake 1 lies across snake 0's path.
+        game.arena.snakes[1].body = vec![Position { x: 12, y: 8 }, Position { x: 12, y: 12 }];
+        game.arena.snakes[1].direction = Direction::Up;
+        game.arena.snakes[0].power_ups.ghost_remaining_ms = 1_000;
+
+        for _ in 0..3 {
+            game.tick_forward(false).expect("tick");
+        }
+        assert!(game.arena.snakes[0].is_alive);
+        assert!(game.arena.snakes[1].is_alive);
+        assert_eq!(game.arena.snakes[0].head().unwrap().x, 13);
+
+        game.arena.snakes[0].body = vec![Position { x: 39, y: 10 }, Position { x: 36, y: 10 }];
+        game.tick_forward(false).expect("wall tick");
+        assert!(
+            !game.arena.snakes[0].is_alive,
+            "walls stay solid to a ghost"
+        );
+    }
+
+    #[test]
+    fn a_magnet_eats_nearby_food_as_one_combo_chain() {
+        let mut game = power_up_game();
+        game.arena.snakes[0].power_ups.magnet_remaining_ms = 1_000;
+        let near = [Position { x: 12, y: 12 }, Position { x: 9, y: 8 }];
+        let far = Position { x: 15, y: 10 };
+        for position in near.into_iter().chain([far]) {
+            game.apply_event(GameEvent::FoodSpawned { position }, None);
+        }
+        let mut replica = game.clone();
+        replica.rng = None;
+
+        let events = game.tick_forward(false).expect("magnet tick");
+        let chains: Vec<u32> = events
+            .iter()
+            .filter_map(|(_, event)| match event {
+                GameEvent::FoodEaten {
+                    snake_id: 0,
+                    combo_chain,
+                    ..
+                } => Some(*combo_chain),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(chains, vec![1, 2]);
+        assert_eq!(game.arena.food, vec![far]);
+        assert_eq!(game.food_pickups.get(&0), Some(&2));
+
+        // The pulled pellets are lawful deltas for a replica that has not yet
+        // simulated the step itself.
+        for (_, event) in events {
+            if matches!(event, GameEvent::FoodEaten { .. }) {
+                replica
+                    .try_apply_replicated_event(event)
+                    .expect("magnet pickup is a valid delta");
+            }
+        }
+        assert_eq!(replica.food_pickups.get(&0), Some(&2));
+    }
+
+    #[test]
+    fn replicas_reproduce_power_ups_from_movement_and_events() {
+        let mut game = power_up_game();
+        let mut replica = game.clone();
+        replica.rng = None;
+        game.apply_event(
+            GameEvent::PowerUpSpawned {
+                kind: PowerUpKind::Shield,
+                position: Position { x: 11, y: 10 },
+            },
+            None,
+        );
+        replica
+            .try_apply_replicated_event(GameEvent::PowerUpSpawned {
+                kind: PowerUpKind::Shield,
+                position: Position { x: 11, y: 10 },
+            })
+            .expect("spawn delta");
+
+        let events = game.tick_forward(false).expect("collecting tick");
+        replica.tick_forward(true).expect("replica movement");
+        assert!(
+            replica.arena.snakes[0].power_ups.shield,
+            "collected locally"
+        );
+        for (_, event) in events {
+            replica
+                .try_apply_replicated_event(event)
+                .expect("authoritative delta");
+        }
+        assert_eq!(replica.sync_hash(), game.sync_hash());
+
+        assert!(
+            replica
+                .try_apply_replicated_event(GameEvent::PowerUpCollected {
+                    snake_id: 1,
+                    kind: PowerUpKind::Magnet,
+                    position: Position { x: 20, y: 20 },
+                })
+                .is_ok(),
+            "a pickup that is already gone was already collected"
+        );
+        replica.apply_event(
+            GameEvent::PowerUpSpawned {
+                kind: PowerUpKind::Magnet,
+                position: Position { x: 20, y: 20 },
+            },
+            None,
+        );
+        assert!(
+            replica
+                .try_apply_replicated_event(GameEvent::PowerUpCollected {
+                    snake_id: 1,
+                    kind: PowerUpKind::Magnet,
+                    position: Position { x: 20, y: 20 },
+                })
+                .is_err(),
+            "a snake cannot collect a pickup its head is not on"
+        );
+    }
+
+    /// A three-snake battle royale one tick before its first ring closes.
+    fn battle_royale_before_first_ring() -> GameState {
+        let mut game = GameState::new(
+            40,
+            40,
+            GameType::Custom {
+                settings: CustomGameSettings {
+                    shrinking_zone: true,
+                    ..CustomGameSettings::default()
+                },
+            },
+            QueueMode::Quickmatch,
+            Some(17),
+            0,
+        );
+        for user_id in 1..=3 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add battle royale test player");
+        }
+        game.tick_forward(false).expect("opening tick");
+        game.properties.available_food_target = 0;
+        game.arena.food.clear();
+        for (snake_id, row) in [(0, 10), (1, 20), (2, 30)] {
+            let snake = &mut game.arena.snakes[snake_id];
+            snake.body = vec![Position { x: 10, y: row }, Position { x: 7, y: row }];
+            snake.direction = Direction::Right;
+        }
+        game.status = GameStatus::Started { server_id: 7 };
+
+        let config = game.properties.zone.clone().expect("opted in");
+        game.tick = config.start_delay_ms / game.properties.tick_duration_ms - 1;
+        game.arena.zone = game.scheduled_zone(game.tick);
+        for user_id in 1..=3 {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+        game.validate_boost_invariants()
+            .expect("a battle royale on schedule is valid");
+        game
+    }
+
+    #[test]
+    fn the_zone_closes_a_ring_on_schedule_and_kills_snakes_caught_outside() {
+        let mut game = battle_royale_before_first_ring();
+        // Snake 1 idles along the outermost column, which the first ring takes.
+        game.arena.snakes[1].body = vec![Position { x: 0, y: 20 }, Position { x: 0, y: 23 }];
+        game.arena.snakes[1].direction = Direction::Up;
+
+        let events = game.tick_forward(false).expect("first ring");
+        assert_eq!(game.arena.zone, Some(ZoneBounds::inset(40, 40, 1)));
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::ZoneContracted { bounds } if *bounds == ZoneBounds::inset(40, 40, 1)
+        )));
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::SnakeDied {
+                snake_id: 1,
+                cause: DeathCause::Zone
+            }
+        )));
+        assert!(game.arena.snakes[0].is_alive);
+        assert!(game.arena.snakes[2].is_alive);
+        assert!(matches!(game.status, GameStatus::Started { .. }));
+
+        // Nothing changes again until the next interval is up.
+        let events = game.tick_forward(false).expect("between rings");
+        assert!(
+            !events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::ZoneContracted { .. }))
+        );
+        game.validate_boost_invariants()
+            .expect("the zone stays on schedule");
+    }
+
+    #[test]
+    fn the_last_snake_inside_the_zone_wins_the_battle_royale() {
+        let mut game = battle_royale_before_first_ring();
+        for snake_id in [1, 2] {
+            let row = game.arena.snakes[snake_id].head().unwrap().y;
+            game.arena.snakes[snake_id].body =
+                vec![Position { x: 39, y: row }, Position { x: 39, y: row + 3 }];
+            game.arena.snakes[snake_id].direction = Direction::Up;
+        }
+
+        game.tick_forward(false).expect("first ring");
+        assert!(!game.arena.snakes[1].is_alive);
+        assert!(!game.arena.snakes[2].is_alive);
+        assert_eq!(
+            game.status,
+            GameStatus::Complete {
+                winning_snake_id: Some(0)
+            }
+        );
+        assert_eq!(game.last_death_causes.get(&1), Some(&DeathCause::Zone));
+    }
+
+    #[test]
+    fn replicas_draw_the_zone_themselves_and_accept_it_from_the_wire() {
+        let mut game = battle_royale_before_first_ring();
+        let mut replica = game.clone();
+        replica.rng = None;
+
+        let events = game.tick_forward(false).expect("first ring");
+        replica.tick_forward(true).expect("replica movement");
+        assert_eq!(replica.arena.zone, game.arena.zone, "predicted locally");
+        for (_, event) in events {
+            replica
+                .try_apply_replicated_event(event)
+                .expect("authoritative delta");
+        }
+        assert_eq!(replica.sync_hash(), game.sync_hash());
+
+        // A late copy of an earlier ring cannot reopen the field, and a ring
+        // from the future is not on this tick's schedule.
+        replica
+            .try_apply_replicated_event(GameEvent::ZoneContracted {
+                bounds: ZoneBounds::full(40, 40),
+            })
+            .expect("a ring already passed is a no-op");
+        assert_eq!(replica.arena.zone, Some(ZoneBounds::inset(40, 40, 1)));
+        assert!(
+            replica
+                .try_apply_replicated_event(GameEvent::ZoneContracted {
+                    bounds: ZoneBounds::inset(40, 40, 2),
+                })
+                .is_err()
+        );
+
+        let mut ranked = GameState::new(
+            60,
+            40,
+            GameType::FreeForAll { max_players: 4 },
+            QueueMode::Competitive,
+            Some(5),
+            0,
+        );
+        assert!(ranked.arena.zone.is_none());
+        assert!(
+            ranked
+                .try_apply_replicated_event(GameEvent::ZoneContracted {
+                    bounds: ZoneBounds::inset(60, 40, 1),
+                })
+                .is_err(),
+            "a ranked mode must refuse a zone from the wire"
+        );
+    }
+
+    /// Two snakes on a 40x40 wraparound custom field, one heading for the
+    /// right-hand seam and one climbing just inside it.
+    fn wrapping_duel() -> GameState {
+        let mut game = GameState::new(
+            40,
+            40,
+            GameType::Custom {
+                settings: CustomGameSettings {
+                    edge_policy: EdgePolicy::Wrap,
+                    ..CustomGameSettings::default()
+                },
+            },
+            QueueMode::Quickmatch,
+            Some(23),
+            0,
+        );
+        for user_id in 1..=2 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add wraparound test player");
+        }
+        game.tick_forward(false).expect("opening tick");
+        game.properties.available_food_target = 0;
+        game.arena.food.clear();
+        game.arena.snakes[0].body = vec![Position { x: 39, y: 10 }, Position { x: 36, y: 10 }];
+        game.arena.snakes[0].direction = Direction::Right;
+        game.arena.snakes[1].body = vec![Position { x: 38, y: 12 }, Position { x: 38, y: 15 }];
+        game.arena.snakes[1].direction = Direction::Up;
+        game.status = GameStatus::Started { server_id: 7 };
+        for user_id in 1..=2 {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+        game
+    }
+
+    #[test]
+    fn a_wrapping_field_carries_snakes_across_the_seam_and_keeps_their_tails_solid() {
+        let mut game = wrapping_duel();
+        assert_eq!(game.arena.edges, EdgePolicy::Wrap);
+
+        game.tick_forward(false).expect("cross the seam");
+        let crosser = &game.arena.snakes[0];
+        assert!(crosser.is_alive, "the far edge is not a wall");
+        assert_eq!(crosser.head().unwrap(), &Position { x: 0, y: 10 });
+        assert!(
+            game.arena
+                .snake_contains(crosser, &Position { x: 38, y: 10 }, false),
+            "the tail still covers the cells it left behind the seam"
+        );
+        assert!(
+            !game
+                .arena
+                .snake_contains(crosser, &Position { x: 35, y: 10 }, false)
+        );
+
+        // The climber runs into the part of the body still behind the seam.
+        let events = game.tick_forward(false).expect("hit the tail");
+        assert!(game.arena.snakes[0].is_alive);
+        assert!(!game.arena.snakes[1].is_alive);
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::SnakeDied {
+                snake_id: 1,
+                cause: DeathCause::SnakeBody { killer_snake_id: 0 }
+            }
+        )));
+        game.validate_boost_invariants()
+            .expect("a wrapped body is a valid state");
+    }
+
+    #[test]
+    fn replicas_wrap_snakes_themselves_and_the_ai_takes_the_short_way_round() {
+        let mut game = wrapping_duel();
+        let mut replica = game.clone();
+        replica.rng = None;
+
+        let events = game.tick_forward(false).expect("cross the seam");
+        replica.tick_forward(true).expect("replica movement");
+        assert_eq!(replica.arena.snakes[0].body, game.arena.snakes[0].body);
+        for (_, event) in events {
+            replica
+                .try_apply_replicated_event(event)
+                .expect("authoritative delta");
+        }
+        assert_eq!(replica.sync_hash(), game.sync_hash());
+
+        // Food two cells behind the seam is closer through it than across the
+        // field, and stepping off the edge is a legal move.
+        let mut game = wrapping_duel();
+        game.arena.food = vec![Position { x: 1, y: 10 }];
+        assert_eq!(
+            crate::calculate_ai_move(&game, 0, Direction::Right),
+            Some(Direction::Right)
+        );
+        game.arena.edges = EdgePolicy::Solid;
+        assert_ne!(
+            crate::calculate_ai_move(&game, 0, Direction::Right),
+            Some(Direction::Right),
+            "a solid edge is still avoided"
+        );
+    }
+
+    /// A king-of-the-hill duel: team 0 one cell short of the hill's left
+    /// edge and heading onto it, team 1 climbing the open field far away.
+    fn hill_duel() -> GameState {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::KingOfTheHill { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(29),
+            0,
+        );
+        for user_id in 1..=2 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add hill test player");
+        }
+        game.tick_forward(false).expect("opening tick");
+        game.arena.food.clear();
+        game.arena.snakes[0].body = vec![Position { x: 26, y: 20 }, Position { x: 23, y: 20 }];
+        game.arena.snakes[0].direction = Direction::Right;
+        game.arena.snakes[1].body = vec![Position { x: 45, y: 30 }, Position { x: 45, y: 33 }];
+        game.arena.snakes[1].direction = Direction::Up;
+        game.status = GameStatus::Started { server_id: 7 };
+        for user_id in 1..=2 {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+        game.validate_boost_invariants()
+            .expect("a fresh hill duel is valid");
+        game
+    }
+
+    #[test]
+    fn a_team_alone_on_the_hill_captures_it_and_scores_each_interval() {
+        let mut game = hill_duel();
+        let config = game.properties.hill.clone().expect("king of the hill");
+        let tick_ms = game.properties.tick_duration_ms;
+        assert_eq!(game.arena.map().id, "classic-team");
+        assert_eq!(game.hill, Some(HillControl::default()));
+
+        let events = game.tick_forward(false).expect("step onto the hill");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::HillCaptured {
+                team_id: TeamId(0),
+                previous_holder: None,
+            }
+        )));
+        assert_eq!(
+            game.hill,
+            Some(HillControl {
+                holder: Some(TeamId(0)),
+                held_ms: tick_ms,
+                contested: false,
+            }),
+            "the capturing tick already counts toward the first point"
+        );
+
+        game.hill.as_mut().unwrap().held_ms = config.point_interval_ms - tick_ms;
+        let events = game.tick_forward(false).expect("hold a full interval");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::TeamScoreUpdated {
+                team_id: TeamId(0),
+                score: 1,
+            }
+        )));
+        assert_eq!(game.hill.unwrap().held_ms, 0);
+        assert_eq!(game.team_scores.as_ref().unwrap()[&TeamId(0)], 1);
+        game.validate_boost_invariants()
+            .expect("hill control stays valid");
+    }
+
+    #[test]
+    fn a_contested_hill_freezes_the_count_and_keeps_its_holder() {
+        let mut game = hill_duel();
+        game.tick_forward(false).expect("team 0 captures");
+        let held_ms = game.hill.unwrap().held_ms;
+
+        game.arena.snakes[1].body = vec![Position { x: 30, y: 22 }, Position { x: 30, y: 25 }];
+        let events = game.tick_forward(false).expect("team 1 contests");
+        assert!(
+            !events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::HillCaptured { .. }))
+        );
+        assert_eq!(
+            game.hill,
+            Some(HillControl {
+                holder: Some(TeamId(0)),
+                held_ms,
+                contested: true,
+            })
+        );
+
+        // Once team 0 walks off, team 1 takes the hill and the count restarts.
+        game.arena.snakes[0].body = vec![Position { x: 10, y: 5 }, Position { x: 7, y: 5 }];
+        let events = game.tick_forward(false).expect("team 1 alone");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::HillCaptured {
+                team_id: TeamId(1),
+                previous_holder: Some(TeamId(0)),
+            }
+        )));
+        let control = game.hill.unwrap();
+        assert_eq!(control.holder, Some(TeamId(1)));
+        assert!(!control.contested);
+        assert_eq!(control.held_ms, game.properties.tick_duration_ms);
+    }
+
+    #[test]
+    fn replicas_keep_the_hill_themselves_and_the_score_limit_ends_the_match() {
+        let mut game = hill_duel();
+        game.tick_forward(false).expect("team 0 captures");
+        let limit = game
+            .properties
+            .score_limit
+            .expect("hill matches race to a limit");
+        assert_eq!(limit, hill_score_limit(&QueueMode::Quickmatch));
+        game.team_scores
+            .as_mut()
+            .unwrap()
+            .insert(TeamId(0), limit - 1);
+        let interval = game.properties.hill.as_ref().unwrap().point_interval_ms;
+        game.hill.as_mut().unwrap().held_ms = interval - game.properties.tick_duration_ms;
+
+        let mut replica = game.clone();
+        replica.rng = None;
+        let events = game.tick_forward(false).expect("winning point");
+        replica.tick_forward(true).expect("replica movement");
+        assert_eq!(replica.hill, game.hill, "predicted locally");
+        for (_, event) in events {
+            replica
+                .try_apply_replicated_event(event)
+                .expect("authoritative delta");
+        }
+        assert_eq!(replica.sync_hash(), game.sync_hash());
+        assert_eq!(
+            game.status,
+            GameStatus::Complete {
+                winning_snake_id: Some(0)
+            }
+        );
+
+        assert!(
+            hill_duel()
+                .try_apply_replicated_event(GameEvent::HillCaptured {
+                    team_id: TeamId(5),
+                    previous_holder: None,
+                })
+                .is_err(),
+            "a capture by a team that is not playing is refused"
+        );
+    }
+
+    /// A capture-the-flag duel: team 0 one cell short of team 1's flag deep in
+    /// team 1's base, team 1 out in the open field.
+    fn flag_duel() -> GameState {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::CaptureTheFlag { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(31),
+            0,
+        );
+        for user_id in 1..=2 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add flag test player");
+        }
+        game.tick_forward(false).expect("opening tick");
+        game.arena.food.clear();
+        let flag = flag_home(&game.arena, TeamId(1)).expect("team 1 flag");
+        game.arena.snakes[0].body = vec![
+            Position {
+                x: flag.x + 1,
+                y: flag.y,
+            },
+            Position {
+                x: flag.x + 3,
+                y: flag.y,
+            },
+        ];
+        game.arena.snakes[0].direction = Direction::Left;
+        game.arena.snakes[1].body = vec![Position { x: 40, y: 5 }, Position { x: 40, y: 2 }];
+        game.arena.snakes[1].direction = Direction::Down;
+        game.status = GameStatus::Started { server_id: 7 };
+        for user_id in 1..=2 {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+        game.validate_boost_invariants()
+            .expect("a fresh flag duel is valid");
+        game
+    }
+
+    #[test]
+    fn a_raider_takes_the_enemy_flag_from_its_base_and_scores_at_home() {
+        let mut game = flag_duel();
+        assert_eq!(game.arena.map().id, "classic-team");
+        assert_eq!(
+            game.flags
+                .iter()
+                .map(|flag| flag.placement)
+                .collect::<Vec<_>>(),
+            vec![FlagPlacement::Home, FlagPlacement::Home]
+        );
+
+        let events = game.tick_forward(false).expect("step onto the flag");
+        assert!(
+            !events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::SnakeDied { .. })),
+            "the enemy base is open in capture the flag"
+        );
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::FlagPickedUp {
+                team_id: TeamId(1),
+                snake_id: 0,
+            }
+        )));
+        assert_eq!(game.flags[1].carrier(), Some(0));
+
+        game.arena.snakes[0].body = vec![Position { x: 5, y: 20 }, Position { x: 8, y: 20 }];
+        let events = game.tick_forward(false).expect("carry it home");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::FlagCaptured {
+                team_id: TeamId(1),
+                snake_id: 0,
+            }
+        )));
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::TeamScoreUpdated {
+                team_id: TeamId(0),
+                score: 1,
+            }
+        )));
+        assert_eq!(game.flags[1].placement, FlagPlacement::Home);
+        game.validate_boost_invariants().expect("flags stay valid");
+    }
+
+    #[test]
+    fn a_dead_carrier_drops_the_flag_until_a_defender_or_the_clock_returns_it() {
+        let mut game = flag_duel();
+        game.flags[1].placement = FlagPlacement::Carried { snake_id: 0 };
+        game.arena.snakes[0].body = vec![Position { x: 30, y: 0 }, Position { x: 30, y: 3 }];
+        game.arena.snakes[0].direction = Direction::Up;
+        let drop_tick = game.tick + 1;
+
+        let events = game.tick_forward(false).expect("carrier leaves the field");
+        let dropped = Position { x: 30, y: 0 };
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::FlagDropped {
+                team_id: TeamId(1),
+                snake_id: 0,
+                position,
+                at_tick,
+            } if *position == dropped && *at_tick == drop_tick
+        )));
+        assert_eq!(
+            game.flags[1].placement,
+            FlagPlacement::Dropped {
+                position: dropped,
+                at_tick: drop_tick,
+            }
+        );
+        game.validate_boost_invariants()
+            .expect("a dropped flag is valid");
+
+        // A defender's head on the dropped flag sends it home.
+        game.arena.snakes[1].body = vec![Position { x: 31, y: 0 }, Position { x: 34, y: 0 }];
+        game.arena.snakes[1].direction = Direction::Left;
+        let returned = (0..4).any(|_| {
+            game.tick_forward(false)
+                .expect("defender returns it")
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::FlagReturned { team_id: TeamId(1) }))
+        });
+        assert!(returned);
+        assert_eq!(game.flags[1].placement, FlagPlacement::Home);
+
+        // Left alone, it goes home by itself once `return_ms` has passed.
+        let config = game.properties.flags.clone().expect("capture the flag");
+        let return_ticks = config.return_ms.div_ceil(game.properties.tick_duration_ms);
+        game.tick = return_ticks + 10;
+        for user_id in 1..=2 {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+        game.flags[1].placement = FlagPlacement::Dropped {
+            position: Position { x: 20, y: 30 },
+            at_tick: game.tick + 1 - return_ticks,
+        };
+        let events = game.tick_forward(false).expect("return timer runs out");
+        assert!(
+            events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::FlagReturned { team_id: TeamId(1) }))
+        );
+        assert_eq!(game.flags[1].placement, FlagPlacement::Home);
+    }
+
+    #[test]
+    fn replicas_move_flags_themselves_and_the_score_limit_ends_the_match() {
+        let mut game = flag_duel();
+        let limit = game
+            .properties
+            .score_limit
+            .expect("flag matches race to a limit");
+        assert_eq!(limit, flag_score_limit(&QueueMode::Quickmatch));
+        game.team_scores
+            .as_mut()
+            .unwrap()
+            .insert(TeamId(0), limit - 1);
+
+        let mut replica = game.clone();
+        replica.rng = None;
+        for step in ["pick up", "capture"] {
+            if step == "capture" {
+                for state in [&mut game, &mut replica] {
+                    state.arena.snakes[0].body =
+                        vec![Position { x: 5, y: 20 }, Position { x: 8, y: 20 }];
+                }
+            }
+            let events = game.tick_forward(false).expect(step);
+            replica.tick_forward(true).expect("replica movement");
+            assert_eq!(replica.flags, game.flags, "predicted locally: {step}");
+            for (_, event) in events {
+                replica
+                    .try_apply_replicated_event(event)
+                    .expect("authoritative delta");
+            }
+            assert_eq!(replica.sync_hash(), game.sync_hash(), "{step}");
+        }
+        assert_eq!(
+            game.status,
+            GameStatus::Complete {
+                winning_snake_id: Some(0)
+            }
+        );
+
+        assert!(
+            flag_duel()
+                .try_apply_replicated_event(GameEvent::FlagPickedUp {
+                    team_id: TeamId(0),
+                    snake_id: 0,
+                })
+                .is_err(),
+            "a snake cannot take its own team's flag"
+        );
+    }
+
+    /// A three-snake Custom game with an empty field. Snake 0 is twelve cells
+    /// long with its head against the right wall, so its next step kills it.
+    fn death_food_game(death_food: bool) -> GameState {
+        let mut game = GameState::new(
+            40,
+            40,
+            GameType::Custom {
+                settings: CustomGameSettings {
+                    death_food,
+                    ..CustomGameSettings::default()
+                },
+            },
+            QueueMode::Quickmatch,
+            Some(23),
+            0,
+        );
+        for user_id in 1..=3 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add death food test player");
+        }
+        game.tick_forward(false).expect("opening tick");
+        game.properties.available_food_target = 0;
+        game.arena.food.clear();
+        for (snake_id, row) in [(0, 10), (1, 20), (2, 30)] {
+            let snake = &mut game.arena.snakes[snake_id];
+            snake.body = vec![Position { x: 10, y: row }, Position { x: 7, y: row }];
+            snake.direction = Direction::Right;
+        }
+        game.arena.snakes[0].body = vec![Position { x: 39, y: 10 }, Position { x: 28, y: 10 }];
+        game.status = GameStatus::Started { server_id: 7 };
+        game
+    }
+
+    /// Step the server and a replica until snake 0 dies, checking they agree
+    /// after every tick. Returns the food the server spawned.
+    fn run_until_snake_zero_dies(game: &mut GameState) -> Vec<Position> {
+        let mut replica = game.clone();
+        replica.rng = None;
+        let mut spawned = Vec::new();
+        for _ in 0..10 {
+            let events = game.tick_forward(false).expect("server tick");
+            replica.tick_forward(true).expect("replica movement");
+            for (_, event) in events {
+                if let GameEvent::FoodSpawned { position } = event {
+                    spawned.push(position);
+                }
+                replica
+                    .try_apply_replicated_event(event)
+                    .expect("authoritative event");
+            }
+            assert_eq!(replica.sync_hash(), game.sync_hash());
+            if !game.arena.snakes[0].is_alive {
+                return spawned;
+            }
+        }
+        panic!("snake 0 should have hit the wall");
+    }
+
+    #[test]
+    fn a_fallen_snake_leaves_food_along_its_body_in_lockstep_with_replicas() {
+        let mut game = death_food_game(true);
+        let config = game.properties.death_food.clone().expect("opted in");
+        let dropped = run_until_snake_zero_dies(&mut game);
+
+        assert_eq!(
+            dropped.len(),
+            12usize.div_ceil(usize::from(config.cells_per_pellet))
+        );
+        assert!(
+            dropped
+                .iter()
+                .all(|position| position.y == 10 && (28..40).contains(&position.x))
+        );
+        assert_eq!(game.arena.food.len(), dropped.len());
+
+        // The same seed drops the same pellets.
+        let mut again = death_food_game(true);
+        assert_eq!(run_until_snake_zero_dies(&mut again), dropped);
+
+        let mut plain = death_food_game(false);
+        assert!(run_until_snake_zero_dies(&mut plain).is_empty());
+        assert!(plain.arena.food.is_empty());
+    }
+
+    #[test]
+    fn death_food_stops_at_the_field_cap() {
+        let mut game = death_food_game(true);
+        let config = game.properties.death_food.clone().expect("opted in");
+        let room = 2;
+        game.arena.food = (0..usize::from(config.max_on_field) - room)
+            .map(|i| Position {
+                x: (i % 40) as i16,
+                y: 33 + (i / 40) as i16,
+            })
+            .collect();
+
+        let dropped = run_until_snake_zero_dies(&mut game);
+        assert_eq!(dropped.len(), room);
+        assert_eq!(game.arena.food.len(), usize::from(config.max_on_field));
+    }
+
+    #[test]
+    fn inactivity_fires_on_the_exact_authoritative_deadline() {
+        let mut game = started_inactivity_team_game(2);
+        let timeout_ticks = inactivity_timeout_ticks(&game);
+        let idle_snake_id = game.players[&1].snake_id;
+
+        game.tick = timeout_ticks - 2;
+        game.player_last_activity_ticks.insert(1, 0);
+        for user_id in [2, 3, 4] {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+
+        let before_deadline = game.tick_forward(true).expect("deadline minus one");
+        assert_eq!(game.tick, timeout_ticks - 1);
+        assert!(game.idle_kicked_user_ids.is_empty());
+        assert!(game.arena.snakes[idle_snake_id as usize].is_alive);
+        assert!(!before_deadline.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::PlayerIdleKicked { user_id: 1, snake_id }
+                if *snake_id == idle_snake_id
+        )));
+
+        let at_deadline = game.tick_forward(true).expect("exact deadline");
+        assert_eq!(game.tick, timeout_ticks);
+        assert_eq!(game.idle_kicked_user_ids, vec![1]);
+        assert!(!game.arena.snakes[idle_snake_id as usize].is_alive);
+        assert!(matches!(game.status, GameStatus::Started { .. }));
+        assert!(at_deadline.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::PlayerIdleKicked { user_id: 1, snake_id }
+                if *snake_id == idle_snake_id
+        )));
+    }
+
+    #[test]
+    fn distinct_noop_and_player_activity_commands_reset_the_full_deadline() {
+        let mut game = started_inactivity_team_game(1);
+        let timeout_ticks = inactivity_timeout_ticks(&game);
+        game.tick = timeout_ticks - 1;
+        for user_id in [1, 2] {
+            game.player_last_activity_ticks.insert(user_id, 0);
+        }
+
+        let first_snake_id = game.players[&1].snake_id;
+        let current_direction = game.arena.snakes[first_snake_id as usize].direction;
+        schedule_inactivity_test_command(
+            &mut game,
+            1,
+            1,
+            GameCommand::Turn {
+                snake_id: first_snake_id,
+                direction: current_direction,
+            },
+        );
+        let second_snake_id = game.players[&2].snake_id;
+        schedule_inactivity_test_command(
+            &mut game,
+            2,
+            1,
+            GameCommand::PlayerActivity {
+                snake_id: second_snake_id,
+            },
+        );
+
+        assert_eq!(game.player_last_activity_ticks[&1], timeout_ticks - 1);
+        assert_eq!(game.player_last_activity_ticks[&2], timeout_ticks - 1);
+        game.tick_forward(true)
+            .expect("both commands reset activity before expiry");
+        assert!(matches!(game.status, GameStatus::Started { .. }));
+        assert!(game.idle_kicked_user_ids.is_empty());
+
+        let renewed_deadline = (timeout_ticks - 1) + timeout_ticks;
+        game.tick = renewed_deadline - 2;
+        game.tick_forward(true).expect("renewed deadline minus one");
+        assert!(game.idle_kicked_user_ids.is_empty());
+        game.tick_forward(true).expect("renewed exact deadline");
+        assert_eq!(game.tick, renewed_deadline);
+        assert_eq!(game.idle_kicked_user_ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn duel_inactivity_awards_the_forfeit_to_the_active_opponent() {
+        let mut game = started_inactivity_team_game(1);
+        let timeout_ticks = inactivity_timeout_ticks(&game);
+        let active_snake_id = game.players[&1].snake_id;
+        let idle_snake_id = game.players[&2].snake_id;
+        game.tick = timeout_ticks - 1;
+        game.player_last_activity_ticks.insert(1, game.tick);
+        game.player_last_activity_ticks.insert(2, 0);
+
+        let events = game.tick_forward(true).expect("duel forfeit");
+
+        assert_eq!(game.idle_kicked_user_ids, vec![2]);
+        assert!(game.completed_by_inactivity);
+        assert!(matches!(
+            game.status,
+            GameStatus::Complete {
+                winning_snake_id: Some(winner)
+            } if winner == active_snake_id
+        ));
+        assert!(game.arena.snakes[active_snake_id as usize].is_alive);
+        assert!(!game.arena.snakes[idle_snake_id as usize].is_alive);
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::StatusUpdated {
+                status: GameStatus::Complete {
+                    winning_snake_id: Some(winner)
+                }
+            } if *winner == active_snake_id
         )));
     }
 
@@ -8130,6 +11451,158 @@ mod tests {
         assert!(!game.arena.snakes[remaining_teammate_snake_id as usize].is_alive);
     }
 
+    #[test]
+    fn a_replica_applying_the_takeover_agrees_with_the_server() {
+        let mut game = started_inactivity_team_game(2);
+        game.properties.idle_autopilot = Some(AiDifficulty::Easy);
+        game.tick = inactivity_timeout_ticks(&game) - 1;
+        game.player_last_activity_ticks.insert(1, 0);
+        for user_id in [2, 3, 4] {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+        let mut replica = game.clone();
+
+        // The takeover step, then steps where the autopilot decides.
+        for _ in 0..10 {
+            let events = game.tick_forward(false).expect("server step");
+            for (sequence, event) in events {
+                let message = GameEventMessage {
+                    game_id: 0,
+                    tick: game.tick,
+                    sequence,
+                    stream_seq: 0,
+                    user_id: None,
+                    event,
+                };
+                replica = crate::advance_and_apply_replicated_message(&replica, &message)
+                    .expect("replicated event applies");
+            }
+            while replica.tick < game.tick {
+                replica.tick_forward(true).unwrap();
+            }
+            assert!(game.is_on_autopilot(1));
+            assert_eq!(replica.autopilot_players, game.autopilot_players);
+            assert_eq!(replica.sync_hash(), game.sync_hash());
+        }
+    }
+
+    #[test]
+    fn autopilot_carries_an_idle_teammate_until_they_return() {
+        let mut game = started_inactivity_team_game(2);
+        game.properties.idle_autopilot = Some(AiDifficulty::Easy);
+        let timeout_ticks = inactivity_timeout_ticks(&game);
+        let idle_snake_id = game.players[&1].snake_id;
+        game.tick = timeout_ticks - 1;
+        game.player_last_activity_ticks.insert(1, 0);
+        for user_id in [2, 3, 4] {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+
+        let events = game.tick_forward(true).expect("autopilot takeover");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::PlayerAutopilotChanged { user_id: 1, snake_id, enabled: true }
+                if *snake_id == idle_snake_id
+        )));
+        assert!(game.idle_kicked_user_ids.is_empty());
+        assert!(game.is_on_autopilot(1));
+        assert!(game.arena.snakes[idle_snake_id as usize].is_alive);
+
+        let takeover_tick = game.autopilot_players[&1].next_decision_tick;
+        let mut sequence_number = 1;
+        for _ in 0..10 {
+            for user_id in [2, 3, 4] {
+                let snake_id = game.players[&user_id].snake_id;
+                schedule_inactivity_test_command(
+                    &mut game,
+                    user_id,
+                    sequence_number,
+                    GameCommand::PlayerActivity { snake_id },
+                );
+                sequence_number += 1;
+            }
+            game.tick_forward(true).expect("autopiloted quantum");
+        }
+        assert!(game.is_on_autopilot(1));
+        assert!(game.autopilot_players[&1].next_decision_tick > takeover_tick);
+
+        let restored: GameState =
+            serde_json::from_value(serde_json::to_value(&game).unwrap()).unwrap();
+        assert_eq!(restored.autopilot_players, game.autopilot_players);
+        assert_eq!(restored.sync_hash(), game.sync_hash());
+        restored.validate_boost_invariants().unwrap();
+
+        schedule_inactivity_test_command(
+            &mut game,
+            1,
+            sequence_number,
+            GameCommand::PlayerActivity {
+                snake_id: idle_snake_id,
+            },
+        );
+        let events = game.tick_forward(true).expect("hand back");
+        assert!(events.iter().any(|(_, event)| matches!(
+            event,
+            GameEvent::PlayerAutopilotChanged {
+                user_id: 1,
+                enabled: false,
+                ..
+            }
+        )));
+        assert!(!game.is_on_autopilot(1));
+        assert!(game.was_on_autopilot(1));
+
+        let winning_snake_id = game.players[&2].snake_id;
+        let winning_team = game.arena.snakes[winning_snake_id as usize]
+            .team_id
+            .expect("team assignment");
+        let score_limit = game.properties.score_limit.expect("team score limit");
+        game.apply_event(
+            GameEvent::TeamScoreUpdated {
+                team_id: winning_team,
+                score: score_limit,
+            },
+            None,
+        );
+        game.tick_forward(false)
+            .expect("score completion after autopilot");
+
+        assert!(game.is_complete());
+        assert_eq!(game.player_xp.get(&1), Some(&0));
+        assert!(game.player_xp.get(&3).is_some_and(|xp| *xp > 0));
+    }
+
+    #[test]
+    fn teammates_idle_together_are_kicked_rather_than_autopiloted() {
+        let mut game = started_inactivity_team_game(2);
+        game.properties.idle_autopilot = Some(AiDifficulty::Easy);
+        let timeout_ticks = inactivity_timeout_ticks(&game);
+        let winning_snake_id = game.players[&2].snake_id;
+        game.tick = timeout_ticks - 1;
+        for user_id in [1, 3] {
+            game.player_last_activity_ticks.insert(user_id, 0);
+        }
+        for user_id in [2, 4] {
+            game.player_last_activity_ticks.insert(user_id, game.tick);
+        }
+
+        let events = game.tick_forward(true).expect("both teammates expire");
+        assert!(
+            !events
+                .iter()
+                .any(|(_, event)| matches!(event, GameEvent::PlayerAutopilotChanged { .. }))
+        );
+        assert_eq!(game.idle_kicked_user_ids, vec![1, 3]);
+        assert!(game.autopiloted_user_ids.is_empty());
+        assert!(game.completed_by_inactivity);
+        assert!(matches!(
+            game.status,
+            GameStatus::Complete {
+                winning_snake_id: Some(winner)
+            } if winner == winning_snake_id
+        ));
+    }
+
     #[test]
     fn player_kicked_mid_match_receives_no_xp_when_score_later_ends_match() {
         let mut game = started_inactivity_team_game(2);
@@ -8720,4 +12193,168 @@ mod tests {
             "isolated four-snake engine p99 {p99:?} exceeded half the 50 ms quantum"
         );
     }
+
+    /// Four AI snakes grown long on a small Custom arena with power-ups and
+    /// death food, so bodies crowd the field, ghosts pass through one another
+    /// and snakes keep crashing. Returns every tick's fingerprint folded into
+    /// one, with the tick the match ended on.
+    fn crowded_ai_match(edge_policy: EdgePolicy) -> (u64, u32) {
+        let settings = CustomGameSettings {
+            arena_width: 30,
+            arena_height: 30,
+            snake_start_length: 12,
+            power_ups: true,
+            death_food: true,
+            edge_policy,
+            ..CustomGameSettings::default()
+        };
+        let mut game = GameState::new(
+            30,
+            30,
+            GameType::Custom { settings },
+            QueueMode::Quickmatch,
+            Some(2024),
+            0,
+        );
+        for user_id in 1..=4 {
+            game.add_player(user_id, Some(format!("Player{user_id}")))
+                .expect("add crowded match player");
+        }
+        game.status = GameStatus::Started { server_id: 7 };
+
+        let mut fingerprint = crate::SyncHasher::new();
+        for _ in 0..400 {
+            for snake_id in 0..4 {
+                let current = game.arena.snakes[snake_id as usize].direction;
+                if let Some(direction) = crate::calculate_ai_move(&game, snake_id, current) {
+                    game.arena.snakes[snake_id as usize].direction = direction;
+                }
+            }
+            game.tick_forward(false).expect("crowded tick");
+            assert!(
+                game.arena.occupancy().is_some(),
+                "a tick leaves the grid current"
+            );
+            fingerprint.write_u64(game.sync_hash());
+            if matches!(game.status, GameStatus::Complete { .. }) {
+                break;
+            }
+        }
+        (fingerprint.finish(), game.tick)
+    }
+
+    /// Recorded before collisions moved to the occupancy grid. The grid is a
+    /// cache, so no match may ever play out differently because of it.
+    #[test]
+    fn the_occupancy_grid_leaves_every_fingerprint_unchanged() {
+        assert_eq!(
+            crowded_ai_match(EdgePolicy::Solid),
+            (3656975854154251899, 214)
+        );
+        assert_eq!(
+            crowded_ai_match(EdgePolicy::Wrap),
+            (16858194954619994701, 40)
+        );
+    }
+
+    #[test]
+    fn ai_seats_play_inside_the_simulation_for_the_players() {
+        let mut game = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(9), 0);
+        game.add_player(1, Some("human".to_owned())).unwrap();
+        let seated = game
+            .add_ai_opponents(&crate::AiOpponents {
+                count: 2,
+                difficulty: crate::AiDifficulty::Hard,
+            })
+            .unwrap();
+        assert_eq!(
+            seated
+                .iter()
+                .map(|player| player.user_id)
+                .collect::<Vec<_>>(),
+            vec![u32::MAX, u32::MAX - 1]
+        );
+        assert_eq!(game.usernames[&u32::MAX], "Hard AI 1");
+        assert!(game.is_ai_driven(u32::MAX) && !game.is_ai_driven(1));
+        let mut gated = game.clone();
+        gated.arm_readiness_gate(1_000);
+        assert_eq!(
+            gated.players_pending_ready(),
+            vec![1],
+            "seats never hold the gate"
+        );
+        game.spawn_initial_food();
+        game.status = GameStatus::Started { server_id: 1 };
+
+        // A snapshot carries the seats, so a replica simulating from it makes
+        // the same decisions as the authority.
+        let mut replica: GameState =
+            serde_json::from_value(serde_json::to_value(&game).unwrap()).unwrap();
+        let mut ai_turned = false;
+        while !game.is_complete() && game.tick < 2_000 {
+            for (_, event) in game.tick_forward(false).unwrap() {
+                ai_turned |= matches!(
+                    event,
+                    GameEvent::SnakeTurned { snake_id, .. } if snake_id != 0
+                );
+            }
+            replica.tick_forward(false).unwrap();
+            assert_eq!(replica.sync_hash(), game.sync_hash());
+        }
+        assert!(ai_turned, "the seats steer their snakes");
+
+        // The human never turned and ran into the wall; with nobody left to
+        // play for the match ends, and the seats earn nothing.
+        assert!(!game.arena.snakes[0].is_alive);
+        assert!(matches!(game.status, GameStatus::Complete { .. }));
+        assert!(game.idle_kicked_user_ids.is_empty());
+        assert_eq!(game.player_xp.get(&u32::MAX), Some(&0));
+        assert_eq!(game.player_xp.get(&(u32::MAX - 1)), Some(&0));
+
+        let mut plain = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(9), 0);
+        plain.add_player(1, None).unwrap();
+        let hash = plain.sync_hash();
+        plain.ai_players.insert(
+            1,
+            crate::AiSeat {
+                difficulty: crate::AiDifficulty::Easy,
+                next_decision_tick: 0,
+            },
+        );
+        assert_ne!(plain.sync_hash(), hash, "seats are fingerprinted");
+    }
+
+    #[test]
+    fn ai_players_can_fill_a_chosen_team() {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 2 },
+            QueueMode::Quickmatch,
+            Some(9),
+            0,
+        );
+        game.add_player_with_team(1, None, Some(TeamId(1))).unwrap();
+        let mut seated = Vec::new();
+        for team in [TeamId(1), TeamId(0), TeamId(0)] {
+            seated.push(
+                game.add_ai_player(crate::AiDifficulty::Easy, Some(team))
+                    .unwrap(),
+            );
+        }
+        let teams: Vec<_> = seated
+            .iter()
+            .map(|player| game.arena.snakes[player.snake_id as usize].team_id)
+            .collect();
+        assert_eq!(
+            teams,
+            vec![Some(TeamId(1)), Some(TeamId(0)), Some(TeamId(0))]
+        );
+        assert_eq!(game.usernames[&(u32::MAX - 2)], "Easy AI 3");
+        assert!(
+            seated
+                .iter()
+                .all(|player| game.is_ai_driven(player.user_id))
+        );
+    }
 }
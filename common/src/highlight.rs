@@ -1,12 +1,14 @@
 //! Durable replay archives and deterministic Play-of-the-Game selection.
 
 use crate::{
-    DeathCause, Direction, GameEvent, GameEventMessage, GameState, GameType, Position, TeamGoal,
-    advance_and_apply_replicated_message,
+    DeathCause, Direction, GameEvent, GameEventMessage, GameState, GameStatus, GameType, Position,
+    TeamGoal, TeamId, advance_and_apply_replicated_message,
 };
 use anyhow::{Context, Result, ensure};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Ordering;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 
 /// JSON has no lossless integer representation above 2^53. Replay hashes are
 /// arbitrary u64 values and cross a JavaScript boundary, so new payloads use
@@ -44,15 +46,29 @@ mod json_u64_string {
 pub const GAME_RECORDING_FORMAT_VERSION: u32 = 1;
 pub const HIGHLIGHT_CLIP_FORMAT_VERSION: u32 = 1;
 /// Simulation compatibility gate shared by archives and browser playback.
-/// Must equal `WS_PROTOCOL_VERSION` / `GAMEPLAY_PROTOCOL_VERSION`: the browser
+/// Must equal [`WS_PROTOCOL_VERSION`](crate::WS_PROTOCOL_VERSION): the browser
 /// rejects any clip whose `gameplay_version` differs from the protocol it
 /// speaks, so a clip written under a stale value is silently unplayable.
-pub const GAMEPLAY_REPLAY_VERSION: u16 = 12;
+pub const GAMEPLAY_REPLAY_VERSION: u16 = 15;
 /// Completion scoring is deliberately bounded. Longer archives remain fully
 /// replayable from S3, but an unexpectedly long or event-dense match degrades
 /// to the no-highlight/banner path instead of monopolizing its executor.
 pub const MAX_HIGHLIGHT_SELECTION_TICKS: u32 = 24_000;
 pub const MAX_HIGHLIGHT_SELECTION_MESSAGES: usize = 32_000;
+/// A lead only reads as a comeback once the team had trailed by this share of
+/// the score limit. One ordinary goal run is worth a fifth of a Quickmatch
+/// round, so a smaller deficit is just the lead trading hands.
+const COMEBACK_MIN_DEFICIT_PERCENT: u32 = 30;
+/// How close to the final whistle a match-winning goal has to land.
+const BUZZER_BEATER_MS: u32 = 5_000;
+/// An unbroken life this long turns outlasting a rival into a highlight.
+const SURVIVAL_STREAK_MS: u32 = 60_000;
+/// How long a snake shut in a pocket has to break out of it.
+const TRAP_ESCAPE_MS: u32 = 3_000;
+/// Finalized archives gain a keyframe wherever their anchors leave this much
+/// play uncovered, so a seek never replays more than this from a checkpoint.
+/// Half the recorder's anchor cadence puts one keyframe between live anchors.
+pub const REPLAY_KEYFRAME_INTERVAL_MS: u32 = 2_500;
 
 fn recording_format_version() -> u32 {
     GAME_RECORDING_FORMAT_VERSION
@@ -90,6 +106,40 @@ pub struct ReplayAnchor {
     pub state: GameState,
 }
 
+/// Seek checkpoint derived from the archive when it is finalized. Unlike an
+/// anchor it carries no authority of its own: `verify_end_hash` replays
+/// through every keyframe and rejects one the messages do not reproduce.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct ReplayKeyframe {
+    pub tick: u32,
+    /// Highest recorder sequence already reflected in `state`, as on an
+    /// anchor.
+    #[cfg_attr(feature = "ts-gen", ts(type = "number"))]
+    pub sequence: u64,
+    #[serde(with = "json_u64_string")]
+    #[cfg_attr(feature = "ts-gen", ts(type = "string"))]
+    pub sync_hash: u64,
+    pub state: GameState,
+}
+
+impl ReplayKeyframe {
+    /// Structural checks that need no replay: the embedded state is the one
+    /// the index names and hashes to the recorded value.
+    pub fn validate(&self) -> Result<()> {
+        ensure!(self.tick == self.state.tick, "keyframe tick/state mismatch");
+        ensure!(
+            self.state.sync_hash() == self.sync_hash,
+            "keyframe at tick {} does not match its sync hash",
+            self.tick
+        );
+        self.state
+            .validate_boost_invariants()
+            .context("recording keyframe violates engine invariants")
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-gen", ts(export))]
@@ -131,6 +181,11 @@ pub struct GameRecordingV1 {
     #[serde(with = "json_u64_string")]
     #[cfg_attr(feature = "ts-gen", ts(type = "string"))]
     pub end_sync_hash: u64,
+    /// Optional seek index laid down by [`Self::index_keyframes`]. Archives
+    /// written before it existed, and bounded selection views, carry none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "ts-gen", ts(optional, as = "Option<Vec<ReplayKeyframe>>"))]
+    pub keyframes: Vec<ReplayKeyframe>,
 }
 
 impl GameRecordingV1 {
@@ -186,6 +241,82 @@ impl GameRecordingV1 {
                 .all(|anchor| anchor.tick <= self.end_tick),
             "recording anchor lies beyond end_tick"
         );
+        ensure!(
+            self.keyframes
+                .windows(2)
+                .all(|pair| pair[0].tick < pair[1].tick),
+            "recording keyframes must be strictly tick ordered"
+        );
+        for keyframe in &self.keyframes {
+            ensure!(
+                keyframe.tick >= first_tick && keyframe.tick <= self.end_tick,
+                "recording keyframe lies outside the replay span"
+            );
+            keyframe.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Replace the keyframe index with one that leaves no more than
+    /// [`REPLAY_KEYFRAME_INTERVAL_MS`] of play between checkpoints. Building it
+    /// replays every anchor segment once, which also proves the archive ends
+    /// on `end_sync_hash`, so finalization need not verify separately.
+    pub fn index_keyframes(&mut self) -> Result<()> {
+        self.keyframes.clear();
+        self.validate()?;
+        let tick_duration_ms = self.anchors[0].state.properties.tick_duration_ms.max(1);
+        self.index_keyframes_every(REPLAY_KEYFRAME_INTERVAL_MS.div_ceil(tick_duration_ms))
+    }
+
+    fn index_keyframes_every(&mut self, interval_ticks: u32) -> Result<()> {
+        let interval_ticks = interval_ticks.max(1);
+        let mut keyframes = Vec::new();
+        let mut end_state = None;
+        for (index, anchor) in self.anchors.iter().enumerate() {
+            let segment_end = self
+                .anchors
+                .get(index + 1)
+                .map_or(self.end_tick, |next| next.tick);
+            let mut position = (anchor.tick, anchor.sequence);
+            let mut state = anchor.state.clone();
+            let mut tick = anchor.tick.saturating_add(interval_ticks);
+            while tick < segment_end {
+                state = self.replay_through(state, position, (tick, u64::MAX))?;
+                let applied = self
+                    .messages
+                    .partition_point(|message| message.tick <= tick);
+                let sequence = match applied.checked_sub(1).map(|last| &self.messages[last]) {
+                    Some(message) if (message.tick, message.sequence) > position => {
+                        message.sequence
+                    }
+                    _ => position.1,
+                };
+                position = (tick, sequence);
+                if state.tick != tick {
+                    // The match ended early; nothing later needs a checkpoint.
+                    break;
+                }
+                keyframes.push(ReplayKeyframe {
+                    tick,
+                    sequence,
+                    sync_hash: state.sync_hash(),
+                    state: state.clone(),
+                });
+                tick = tick.saturating_add(interval_ticks);
+            }
+            if index + 1 == self.anchors.len() {
+                end_state =
+                    Some(self.replay_through(state, position, (self.end_tick, u64::MAX))?);
+            }
+        }
+        let end_state = end_state.expect("validated recording has an anchor");
+        ensure!(
+            end_state.sync_hash() == self.end_sync_hash,
+            "recording end hash mismatch: expected {:#018x}, got {:#018x}",
+            self.end_sync_hash,
+            end_state.sync_hash()
+        );
+        self.keyframes = keyframes;
         Ok(())
     }
 
@@ -203,23 +334,67 @@ impl GameRecordingV1 {
             target_tick <= self.end_tick,
             "target tick is beyond recording end"
         );
-        let anchor = self
+        let anchor = self.anchors[..self
             .anchors
-            .iter()
-            .rev()
-            .find(|anchor| anchor.tick <= target_tick)
+            .partition_point(|anchor| anchor.tick <= target_tick)]
+            .last()
             .context("no replay anchor at or before target tick")?;
-        let mut state = anchor.state.clone();
-        let first = self.messages.partition_point(|message| {
-            (message.tick, message.sequence) <= (anchor.tick, anchor.sequence)
-        });
+        let keyframe = self.keyframes[..self
+            .keyframes
+            .partition_point(|keyframe| keyframe.tick <= target_tick)]
+            .last()
+            .filter(|keyframe| (keyframe.tick, keyframe.sequence) > (anchor.tick, anchor.sequence));
+        let (position, state) = match keyframe {
+            Some(keyframe) => ((keyframe.tick, keyframe.sequence), &keyframe.state),
+            None => ((anchor.tick, anchor.sequence), &anchor.state),
+        };
+        self.replay_through(state.clone(), position, (target_tick, u64::MAX))
+    }
+
+    /// Visit the state at every tick from `start_tick` through `end_tick`, in
+    /// order. Each tick is stepped to from the one before rather than sought,
+    /// so a window costs one replay however long it is. Ticks after the match
+    /// ended repeat its final state.
+    pub fn for_each_tick(
+        &self,
+        start_tick: u32,
+        end_tick: u32,
+        mut visit: impl FnMut(&GameState) -> Result<()>,
+    ) -> Result<()> {
+        self.validate()?;
+        ensure!(start_tick <= end_tick, "tick range ends before it starts");
+        ensure!(
+            end_tick <= self.end_tick,
+            "target tick is beyond recording end"
+        );
+        let mut state = self.state_at_tick_validated(start_tick)?;
+        visit(&state)?;
+        for tick in start_tick.saturating_add(1)..=end_tick {
+            let cursor = ((tick - 1, u64::MAX), state);
+            state = self.replay_from_cursor(Some(cursor), (tick, u64::MAX))?;
+            visit(&state)?;
+        }
+        Ok(())
+    }
+
+    /// Apply every message after `from` up to and including `through`, both
+    /// `(tick, sequence)` positions, then tick on to `through`'s tick.
+    fn replay_through(
+        &self,
+        mut state: GameState,
+        from: (u32, u64),
+        through: (u32, u64),
+    ) -> Result<GameState> {
+        let first = self
+            .messages
+            .partition_point(|message| (message.tick, message.sequence) <= from);
         let last = self
             .messages
-            .partition_point(|message| message.tick <= target_tick);
-        for message in &self.messages[first..last] {
+            .partition_point(|message| (message.tick, message.sequence) <= through);
+        for message in &self.messages[first..last.max(first)] {
             state = advance_and_apply_replicated_message(&state, &message.envelope(self.game_id))?;
         }
-        while state.tick < target_tick && !state.is_complete() {
+        while state.tick < through.0 && !state.is_complete() {
             state.tick_forward(true)?;
         }
         Ok(state)
@@ -229,9 +404,23 @@ impl GameRecordingV1 {
         self.verified_end_state().map(|_| ())
     }
 
+    /// Seeking trusts keyframes, so verification must not: it replays through
+    /// each one in order and checks that the messages reproduce its hash. An
+    /// anchor in between restarts the walk, exactly as a seek past it would.
     fn verified_end_state(&self) -> Result<GameState> {
         self.validate()?;
-        let state = self.state_at_tick_validated(self.end_tick)?;
+        let mut cursor = None;
+        for keyframe in &self.keyframes {
+            let state =
+                self.replay_from_cursor(cursor.take(), (keyframe.tick, keyframe.sequence))?;
+            ensure!(
+                state.sync_hash() == keyframe.sync_hash,
+                "recording keyframe at tick {} does not match its replay",
+                keyframe.tick
+            );
+            cursor = Some(((keyframe.tick, keyframe.sequence), state));
+        }
+        let state = self.replay_from_cursor(cursor, (self.end_tick, u64::MAX))?;
         ensure!(
             state.sync_hash() == self.end_sync_hash,
             "recording end hash mismatch: expected {:#018x}, got {:#018x}",
@@ -240,6 +429,25 @@ impl GameRecordingV1 {
         );
         Ok(state)
     }
+
+    fn replay_from_cursor(
+        &self,
+        cursor: Option<((u32, u64), GameState)>,
+        through: (u32, u64),
+    ) -> Result<GameState> {
+        let anchor = self.anchors[..self
+            .anchors
+            .partition_point(|anchor| (anchor.tick, anchor.sequence) <= through)]
+            .last()
+            .context("no replay anchor at or before target tick")?;
+        let (from, state) = match cursor {
+            Some((position, state)) if position >= (anchor.tick, anchor.sequence) => {
+                (position, state)
+            }
+            _ => ((anchor.tick, anchor.sequence), anchor.state.clone()),
+        };
+        self.replay_through(state, from, through)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -252,6 +460,12 @@ pub enum HighlightReason {
     GoalRun { points: u32 },
     ComboFrenzy { max_chain: u32 },
     FeedingFrenzy { pickups: u32 },
+    ContestedCapture { captures: u32 },
+    FlagCapture { captures: u32 },
+    Comeback { deficit: u32 },
+    BuzzerBeater { points: u32 },
+    SurvivalStreak { seconds: u32 },
+    TrapEscape { escapes: u32 },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -302,6 +516,54 @@ pub struct HighlightConfig {
     pub boosted_pickup: i32,
     pub feeding_frenzy: i32,
     pub death: i32,
+    /// Taking the hill from the other team with the star's own head on it.
+    /// Configs persisted before king of the hill read the default weight.
+    #[serde(default = "default_contested_capture")]
+    pub contested_capture: i32,
+    /// Carrying the enemy flag home. A capture decides a match the way a kill
+    /// decides a duel, so it weighs the same as an elimination.
+    #[serde(default = "default_flag_capture")]
+    pub flag_capture: i32,
+    /// The star's score putting their team ahead after trailing by at least
+    /// `COMEBACK_MIN_DEFICIT_PERCENT` of the score limit. Like the weights
+    /// below, configs persisted before rules version 3 read the default.
+    #[serde(default = "default_comeback")]
+    pub comeback: i32,
+    /// The star's score that took the lead for good in the match's last
+    /// `BUZZER_BEATER_MS`.
+    #[serde(default = "default_buzzer_beater")]
+    pub buzzer_beater: i32,
+    /// A rival dying while the star's current life is at least
+    /// `SURVIVAL_STREAK_MS` old.
+    #[serde(default = "default_survival_streak")]
+    pub survival_streak: i32,
+    /// Breaking out of a pocket a rival had closed around the star.
+    #[serde(default = "default_trap_escape")]
+    pub trap_escape: i32,
+}
+
+fn default_contested_capture() -> i32 {
+    45
+}
+
+fn default_flag_capture() -> i32 {
+    90
+}
+
+fn default_comeback() -> i32 {
+    70
+}
+
+fn default_buzzer_beater() -> i32 {
+    90
+}
+
+fn default_survival_streak() -> i32 {
+    100
+}
+
+fn default_trap_escape() -> i32 {
+    120
 }
 
 impl Default for HighlightConfig {
@@ -310,8 +572,11 @@ impl Default for HighlightConfig {
             // Calibration round 1 (200 deterministic authoritative bot
             // games) raised only the two cooperative-play weights below.
             // The resulting non-trivial production rate is 75%, with no
-            // category above 50.4% of winners.
-            rules_version: 2,
+            // category above 50.4% of winners. Round 2 added the four clutch
+            // weights at the end on the same corpus: 96.9% production, no
+            // category above 43.3%, and every new reason selected at least
+            // twice.
+            rules_version: 3,
             minimum_score: 120,
             elimination: 90,
             mutual_trade: 60,
@@ -329,6 +594,12 @@ impl Default for HighlightConfig {
             boosted_pickup: 5,
             feeding_frenzy: 20,
             death: -60,
+            contested_capture: default_contested_capture(),
+            flag_capture: default_flag_capture(),
+            comeback: default_comeback(),
+            buzzer_beater: default_buzzer_beater(),
+            survival_streak: default_survival_streak(),
+            trap_escape: default_trap_escape(),
         }
     }
 }
@@ -375,6 +646,21 @@ impl HighlightClip {
     }
 
     pub fn replay_and_verify(&self) -> Result<GameState> {
+        self.ensure_compatible()?;
+        self.recording().verified_end_state()
+    }
+
+    /// [`Self::recording`] with a keyframe index for seeking. Indexing replays
+    /// the whole clip and fails unless it ends on `end_sync_hash`, so this is
+    /// as strong a gate as [`Self::replay_and_verify`].
+    pub fn indexed_recording(&self) -> Result<GameRecordingV1> {
+        self.ensure_compatible()?;
+        let mut recording = self.recording();
+        recording.index_keyframes()?;
+        Ok(recording)
+    }
+
+    fn ensure_compatible(&self) -> Result<()> {
         ensure!(
             self.clip_format_version == HIGHLIGHT_CLIP_FORMAT_VERSION,
             "unsupported highlight clip version"
@@ -383,7 +669,13 @@ impl HighlightClip {
             self.gameplay_version == GAMEPLAY_REPLAY_VERSION,
             "highlight gameplay version mismatch"
         );
-        let recording = GameRecordingV1 {
+        Ok(())
+    }
+
+    /// The clip as a one-anchor recording that ends with its window, for
+    /// anything that replays recordings rather than clips.
+    pub fn recording(&self) -> GameRecordingV1 {
+        GameRecordingV1 {
             format_version: GAME_RECORDING_FORMAT_VERSION,
             gameplay_version: self.gameplay_version,
             game_id: self.game_id,
@@ -396,8 +688,8 @@ impl HighlightClip {
             messages: self.messages.clone(),
             end_tick: self.window.end_tick,
             end_sync_hash: self.end_sync_hash,
-        };
-        recording.verified_end_state()
+            keyframes: Vec::new(),
+        }
     }
 }
 
@@ -416,6 +708,24 @@ pub struct HighlightScoreBreakdown {
     pub demolition_points: i32,
     pub banking_points: i32,
     pub combo_points: i32,
+    #[serde(default)]
+    pub contested_captures: u32,
+    #[serde(default)]
+    pub flag_captures: u32,
+    /// The largest deficit a lead the star scored overturned.
+    #[serde(default)]
+    pub comeback_deficit: u32,
+    /// Points in the star's match-winning score, when it beat the buzzer.
+    #[serde(default)]
+    pub buzzer_beater_points: u32,
+    /// Age of the star's life when it outlasted a rival, at the longest.
+    #[serde(default)]
+    pub survival_seconds: u32,
+    #[serde(default)]
+    pub trap_escapes: u32,
+    /// Comebacks, buzzer beaters, survival streaks and trap escapes.
+    #[serde(default)]
+    pub clutch_points: i32,
     #[serde(skip)]
     #[cfg_attr(feature = "ts-gen", ts(skip))]
     focus_value: i32,
@@ -429,7 +739,39 @@ impl HighlightScoreBreakdown {
         }
     }
 
+    fn add_clutch(&mut self, tick: u32, points: i32) {
+        self.total += points;
+        self.clutch_points += points;
+        self.add_focus(tick, points);
+    }
+
     fn reason(&self) -> HighlightReason {
+        // Clutch plays only take the caption outright, so a window that
+        // scored the same under rules version 2 keeps its old reason.
+        if self.clutch_points
+            > self
+                .demolition_points
+                .max(self.banking_points)
+                .max(self.combo_points)
+        {
+            if self.buzzer_beater_points > 0 {
+                return HighlightReason::BuzzerBeater {
+                    points: self.buzzer_beater_points,
+                };
+            } else if self.comeback_deficit > 0 {
+                return HighlightReason::Comeback {
+                    deficit: self.comeback_deficit,
+                };
+            } else if self.trap_escapes > 0 {
+                return HighlightReason::TrapEscape {
+                    escapes: self.trap_escapes,
+                };
+            } else if self.survival_seconds > 0 {
+                return HighlightReason::SurvivalStreak {
+                    seconds: self.survival_seconds,
+                };
+            }
+        }
         if self.demolition_points >= self.banking_points
             && self.demolition_points >= self.combo_points
             && self.kills > 0
@@ -445,6 +787,14 @@ impl HighlightScoreBreakdown {
             HighlightReason::GoalRun {
                 points: self.banked_points,
             }
+        } else if self.banking_points >= self.combo_points && self.contested_captures > 0 {
+            HighlightReason::ContestedCapture {
+                captures: self.contested_captures,
+            }
+        } else if self.banking_points >= self.combo_points && self.flag_captures > 0 {
+            HighlightReason::FlagCapture {
+                captures: self.flag_captures,
+            }
         } else if self.max_chain > 1 {
             HighlightReason::ComboFrenzy {
                 max_chain: self.max_chain,
@@ -464,6 +814,79 @@ struct Observation {
     before: GameState,
 }
 
+/// Match-wide context the clutch categories judge a window against. A
+/// comeback or a survival streak reaches back further than any one scoring
+/// window, so this is built once per recording.
+#[derive(Debug, Default)]
+struct MatchTimeline {
+    lead_changes: Vec<LeadChange>,
+    /// Each snake's lives as `[start, end)` ticks; a banked respawn continues
+    /// the life it interrupted.
+    lives: HashMap<u32, Vec<(u32, Option<u32>)>>,
+    /// `(snake, tick)` for every pocket a snake broke out of.
+    escapes: Vec<(u32, u32)>,
+}
+
+#[derive(Debug)]
+struct LeadChange {
+    tick: u32,
+    team_id: TeamId,
+    /// The furthest the team had trailed since its round began.
+    deficit: u32,
+    /// Whether the lead held until the match completed, within
+    /// `BUZZER_BEATER_MS` of the final whistle.
+    buzzer_beater: bool,
+}
+
+/// How many moments [`select_match_highlights`] ranks for one match.
+pub const HIGHLIGHT_REEL_MOMENTS: usize = 5;
+
+/// A scored window of a match without the replay payload a clip carries.
+/// Clients play one by seeking the match's replay to `window`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct HighlightMoment {
+    pub star_user_id: u32,
+    pub star_snake_id: u32,
+    pub star_name: String,
+    pub reason: HighlightReason,
+    pub score: i32,
+    pub breakdown: HighlightScoreBreakdown,
+    pub window: HighlightWindow,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct HighlightReel {
+    /// The Play of the Game first when the match has one, then the rest by
+    /// score. No two windows overlap.
+    pub moments: Vec<HighlightMoment>,
+    /// Each participant's own best moment, ordered by user id. A player with
+    /// nothing above `minimum_score` has no entry.
+    pub players: Vec<HighlightMoment>,
+}
+
+impl HighlightReel {
+    pub fn is_empty(&self) -> bool {
+        self.moments.is_empty() && self.players.is_empty()
+    }
+
+    pub fn player(&self, user_id: u32) -> Option<&HighlightMoment> {
+        self.players
+            .iter()
+            .find(|moment| moment.star_user_id == user_id)
+    }
+}
+
+/// A match's Play of the Game and the reel around it.
+#[derive(Debug, Default, Clone)]
+pub struct MatchHighlights {
+    pub play_of_the_game: Option<HighlightClip>,
+    pub reel: HighlightReel,
+}
+
 /// Select and cut one deterministic highlight. Custom games remain ineligible
 /// at launch; every canonical queue/mode, including Solo, is eligible.
 pub fn select_highlight(
@@ -482,166 +905,328 @@ pub fn score_highlight_candidate(
     recording: &GameRecordingV1,
     config: &HighlightConfig,
 ) -> Result<Option<HighlightClip>> {
-    recording.validate()?;
-    ensure!(
-        recording.end_tick.saturating_sub(recording.anchors[0].tick)
-            <= MAX_HIGHLIGHT_SELECTION_TICKS,
-        "recording exceeds highlight tick budget"
-    );
-    ensure!(
-        recording.messages.len() <= MAX_HIGHLIGHT_SELECTION_MESSAGES,
-        "recording exceeds highlight message budget"
-    );
-    if matches!(
-        recording.anchors[0].state.game_type,
-        GameType::Custom { .. }
-    ) {
+    let Some(mut scorer) = HighlightScorer::new(recording, config)? else {
         return Ok(None);
+    };
+    let candidates = scorer.candidates()?;
+    let Some((star, raw)) = candidates.first() else {
+        return Ok(None);
+    };
+    let moment = scorer.moment(*star, raw)?;
+    scorer.clip(moment).map(Some)
+}
+
+/// Select the Play of the Game together with up to `limit` ranked moments and
+/// every participant's best moment, replaying the recording once for all of
+/// them. The Play of the Game is exactly what [`select_highlight`] returns.
+pub fn select_match_highlights(
+    recording: &GameRecordingV1,
+    config: &HighlightConfig,
+    limit: usize,
+) -> Result<MatchHighlights> {
+    let mut highlights = MatchHighlights::default();
+    let Some(mut scorer) = HighlightScorer::new(recording, config)? else {
+        return Ok(highlights);
+    };
+    let candidates = scorer.candidates()?;
+    let stars: HashSet<u32> = candidates.iter().map(|(star, _)| *star).collect();
+    let mut players = BTreeMap::<u32, HighlightMoment>::new();
+    let mut best_stars = HashSet::new();
+    for (index, (star, raw)) in candidates.iter().enumerate() {
+        // Candidates are ranked by raw score, so once one falls below the
+        // threshold every later one does too. The first is still cut, since
+        // re-centering can lift it over.
+        if index > 0 && raw.total < config.minimum_score {
+            break;
+        }
+        if highlights.reel.moments.len() >= limit && best_stars.len() == stars.len() {
+            break;
+        }
+        let window = scorer.window(raw.focus_tick);
+        let ranked = highlights.reel.moments.len() < limit
+            && !highlights
+                .reel
+                .moments
+                .iter()
+                .any(|moment| windows_overlap(&moment.window, &window));
+        if !ranked && best_stars.contains(star) {
+            continue;
+        }
+        let moment = scorer.moment(*star, raw)?;
+        if moment.score < config.minimum_score {
+            continue;
+        }
+        if index == 0 {
+            highlights.play_of_the_game = Some(scorer.clip(moment.clone())?);
+        }
+        best_stars.insert(*star);
+        match players.entry(moment.star_user_id) {
+            Entry::Vacant(entry) => {
+                entry.insert(moment.clone());
+            }
+            Entry::Occupied(mut entry) if moment.score > entry.get().score => {
+                entry.insert(moment.clone());
+            }
+            Entry::Occupied(_) => {}
+        }
+        if ranked {
+            highlights.reel.moments.push(moment);
+        }
     }
-    let observations = replay_observations(recording)?;
-    let anchor_state = &recording.anchors[0].state;
-    let tick_ms = anchor_state.properties.tick_duration_ms.max(1);
-    let window_ticks = (10_000 / tick_ms).max(1);
-    let step_ticks = (1_000 / tick_ms).max(1);
-    let lead_ticks = 6_000 / tick_ms;
-    let earliest = recording.anchors[0].tick;
-    let latest = recording.end_tick;
-    let mut snakes: Vec<u32> = anchor_state
-        .players
-        .values()
-        .map(|player| player.snake_id)
-        .collect();
-    snakes.sort_unstable();
-    snakes.dedup();
+    // Re-centering moves scores a little, so the moments after the Play of
+    // the Game are put back in the order a viewer would expect.
+    let moments = &mut highlights.reel.moments;
+    let settled = usize::from(highlights.play_of_the_game.is_some()).min(moments.len());
+    moments[settled..].sort_by(|left, right| {
+        right
+            .score
+            .cmp(&left.score)
+            .then(left.window.focus_tick.cmp(&right.window.focus_tick))
+    });
+    highlights.reel.players = players.into_values().collect();
+    Ok(highlights)
+}
+
+fn windows_overlap(left: &HighlightWindow, right: &HighlightWindow) -> bool {
+    left.start_tick <= right.end_tick && right.start_tick <= left.end_tick
+}
 
-    let mut raw_best: Option<(u32, HighlightScoreBreakdown)> = None;
+/// One recording replayed once, so any number of windows can be scored
+/// against it.
+struct HighlightScorer<'a> {
+    recording: &'a GameRecordingV1,
+    config: &'a HighlightConfig,
+    observations: Vec<Observation>,
+    timeline: MatchTimeline,
     // A scored event appears in several overlapping windows. Cache the
     // comparatively expensive flood-fill/resimulation result so the launch
     // scorer's work is proportional to unique deaths, not windows × deaths.
-    let mut trap_cache = HashMap::new();
-    for star in snakes {
-        let mut start = earliest;
-        loop {
-            let end = start
-                .saturating_add(window_ticks)
-                .min(latest.saturating_add(1));
-            let score = score_range(
-                recording,
-                &observations,
-                star,
-                start,
-                end,
-                config,
-                &mut trap_cache,
-            )?;
-            // The published clip is re-centered around the scored focus with a
-            // six-second lead-in. Keep the scoring domain to focus ticks that
-            // can actually retain that lead-in from the oldest anchor. An
-            // earlier (and even higher-scoring) event must not suppress a
-            // valid later candidate and make the whole match ineligible.
-            if score.focus_tick >= earliest.saturating_add(lead_ticks)
-                && better_candidate(star, &score, raw_best.as_ref())
-            {
-                raw_best = Some((star, score));
+    trap_cache: HashMap<(u32, u32, u32), bool>,
+    tick_ms: u32,
+}
+
+impl<'a> HighlightScorer<'a> {
+    /// `None` for recordings that are never highlighted.
+    fn new(recording: &'a GameRecordingV1, config: &'a HighlightConfig) -> Result<Option<Self>> {
+        recording.validate()?;
+        ensure!(
+            recording.end_tick.saturating_sub(recording.anchors[0].tick)
+                <= MAX_HIGHLIGHT_SELECTION_TICKS,
+            "recording exceeds highlight tick budget"
+        );
+        ensure!(
+            recording.messages.len() <= MAX_HIGHLIGHT_SELECTION_MESSAGES,
+            "recording exceeds highlight message budget"
+        );
+        if matches!(
+            recording.anchors[0].state.game_type,
+            GameType::Custom { .. }
+        ) {
+            return Ok(None);
+        }
+        let observations = replay_observations(recording)?;
+        let timeline = match_timeline(recording, &observations);
+        Ok(Some(Self {
+            recording,
+            config,
+            observations,
+            timeline,
+            trap_cache: HashMap::new(),
+            tick_ms: recording.anchors[0]
+                .state
+                .properties
+                .tick_duration_ms
+                .max(1),
+        }))
+    }
+
+    fn lead_ticks(&self) -> u32 {
+        6_000 / self.tick_ms
+    }
+
+    fn score(&mut self, star: u32, start: u32, end: u32) -> Result<HighlightScoreBreakdown> {
+        score_range(
+            self.recording,
+            &self.observations,
+            star,
+            start,
+            end,
+            self.config,
+            &self.timeline,
+            &mut self.trap_cache,
+        )
+    }
+
+    /// Every snake's best sliding window at each focus tick, best first.
+    fn candidates(&mut self) -> Result<Vec<(u32, HighlightScoreBreakdown)>> {
+        let anchor_state = &self.recording.anchors[0].state;
+        let window_ticks = (10_000 / self.tick_ms).max(1);
+        let step_ticks = (1_000 / self.tick_ms).max(1);
+        let earliest = self.recording.anchors[0].tick;
+        let latest = self.recording.end_tick;
+        let mut snakes: Vec<u32> = anchor_state
+            .players
+            .values()
+            .map(|player| player.snake_id)
+            .collect();
+        snakes.sort_unstable();
+        snakes.dedup();
+
+        let mut best = BTreeMap::<(u32, u32), HighlightScoreBreakdown>::new();
+        for star in snakes {
+            let mut start = earliest;
+            loop {
+                let end = start
+                    .saturating_add(window_ticks)
+                    .min(latest.saturating_add(1));
+                let score = self.score(star, start, end)?;
+                // The published clip is re-centered around the scored focus
+                // with a six-second lead-in. Keep the scoring domain to focus
+                // ticks that can actually retain that lead-in from the oldest
+                // anchor. An earlier (and even higher-scoring) event must not
+                // suppress a valid later candidate and make the whole match
+                // ineligible.
+                if score.focus_tick >= earliest.saturating_add(self.lead_ticks()) {
+                    let key = (star, score.focus_tick);
+                    if best.get(&key).is_none_or(|held| score.total > held.total) {
+                        best.insert(key, score);
+                    }
+                }
+                if end > latest || start.saturating_add(step_ticks) > latest {
+                    break;
+                }
+                start = start.saturating_add(step_ticks);
             }
-            if end > latest || start.saturating_add(step_ticks) > latest {
-                break;
+        }
+        let mut candidates: Vec<_> = best
+            .into_iter()
+            .map(|((star, _), score)| (star, score))
+            .collect();
+        candidates.sort_by(|left, right| {
+            if better_candidate(left.0, &left.1, Some(right)) {
+                Ordering::Less
+            } else if better_candidate(right.0, &right.1, Some(left)) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
             }
-            start = start.saturating_add(step_ticks);
+        });
+        Ok(candidates)
+    }
+
+    fn window(&self, focus_tick: u32) -> HighlightWindow {
+        HighlightWindow {
+            start_tick: focus_tick - self.lead_ticks(),
+            end_tick: focus_tick + 3_000 / self.tick_ms,
+            focus_tick,
         }
     }
-    let Some((star, raw)) = raw_best else {
-        return Ok(None);
-    };
-    let tail_ticks = 3_000 / tick_ms;
-    let start_tick = raw.focus_tick - lead_ticks;
-    let end_tick = raw.focus_tick + tail_ticks;
-    let rescored = score_range(
-        recording,
-        &observations,
-        star,
-        start_tick,
-        end_tick.saturating_add(1),
-        config,
-        &mut trap_cache,
-    )?;
-    let anchor = recording
-        .anchors
-        .iter()
-        .rev()
-        .find(|candidate| candidate.tick <= start_tick)
-        .context("highlight has no retained anchor")?;
-    let mut anchor_state = anchor.state.clone();
-    anchor_state.rng = None;
-    let messages = recording
-        .messages
-        .iter()
-        .filter(|message| {
-            (message.tick, message.sequence) > (anchor.tick, anchor.sequence)
-                && message.tick <= end_tick
+
+    fn anchor(&self, start_tick: u32) -> Result<&'a ReplayAnchor> {
+        self.recording
+            .anchors
+            .iter()
+            .rev()
+            .find(|candidate| candidate.tick <= start_tick)
+            .context("highlight has no retained anchor")
+    }
+
+    /// Re-center `raw` on its focus and score the window the clip would show.
+    fn moment(&mut self, star: u32, raw: &HighlightScoreBreakdown) -> Result<HighlightMoment> {
+        let mut window = self.window(raw.focus_tick);
+        let rescored = self.score(star, window.start_tick, window.end_tick.saturating_add(1))?;
+        window.focus_tick = rescored.focus_tick;
+        let anchor_state = &self.anchor(window.start_tick)?.state;
+        let star_user_id = anchor_state
+            .players
+            .iter()
+            .filter_map(|(user_id, player)| (player.snake_id == star).then_some(*user_id))
+            .min()
+            .context("highlight star has no player")?;
+        let star_name = anchor_state
+            .usernames
+            .get(&star_user_id)
+            .cloned()
+            .unwrap_or_else(|| format!("Player {star_user_id}"));
+        Ok(HighlightMoment {
+            star_user_id,
+            star_snake_id: star,
+            star_name,
+            reason: rescored.reason(),
+            score: rescored.total,
+            breakdown: rescored,
+            window,
         })
-        .cloned()
-        .collect();
-    // Terminal plays intentionally keep three seconds of post-roll on screen.
-    // Once a match is complete the replay engine holds the immutable final
-    // frame, so the padded clip remains deterministic without fake events.
-    let end_sync_hash = recording
-        .state_at_tick_validated(end_tick.min(recording.end_tick))?
-        .sync_hash();
-    let star_user_id = anchor_state
-        .players
-        .iter()
-        .filter_map(|(user_id, player)| (player.snake_id == star).then_some(*user_id))
-        .min()
-        .context("highlight star has no player")?;
-    let star_name = anchor_state
-        .usernames
-        .get(&star_user_id)
-        .cloned()
-        .unwrap_or_else(|| format!("Player {star_user_id}"));
-    let focus_tick = rescored.focus_tick;
-    let slow_start = focus_tick - (2_000 / tick_ms);
-    let slow_end = focus_tick + (1_500 / tick_ms);
-    let rotation = star_rotation(&anchor_state, star);
-
-    Ok(Some(HighlightClip {
-        clip_format_version: HIGHLIGHT_CLIP_FORMAT_VERSION,
-        gameplay_version: GAMEPLAY_REPLAY_VERSION,
-        game_id: recording.game_id,
-        star_user_id,
-        star_snake_id: star,
-        star_name,
-        reason: rescored.reason(),
-        score: rescored.total,
-        breakdown: rescored.clone(),
-        window: HighlightWindow {
+    }
+
+    /// Cut the replay payload for `moment`.
+    fn clip(&self, moment: HighlightMoment) -> Result<HighlightClip> {
+        let recording = self.recording;
+        let tick_ms = self.tick_ms;
+        let HighlightWindow {
             start_tick,
             end_tick,
             focus_tick,
-        },
-        anchor: anchor_state,
-        messages,
-        end_sync_hash,
-        presentation: HighlightPresentation {
-            rotation,
-            follow_snake_id: star,
-            segments: vec![
-                HighlightSpeedSegment {
-                    until_tick: slow_start,
-                    time_scale: 1.0,
-                },
-                HighlightSpeedSegment {
-                    until_tick: slow_end,
-                    time_scale: 0.5,
-                },
-                HighlightSpeedSegment {
-                    until_tick: end_tick,
-                    time_scale: 1.0,
-                },
-            ],
-        },
-        config: config.clone(),
-    }))
+        } = moment.window;
+        let anchor = self.anchor(start_tick)?;
+        let mut anchor_state = anchor.state.clone();
+        anchor_state.rng = None;
+        let messages = recording
+            .messages
+            .iter()
+            .filter(|message| {
+                (message.tick, message.sequence) > (anchor.tick, anchor.sequence)
+                    && message.tick <= end_tick
+            })
+            .cloned()
+            .collect();
+        // Terminal plays intentionally keep three seconds of post-roll on
+        // screen. Once a match is complete the replay engine holds the
+        // immutable final frame, so the padded clip remains deterministic
+        // without fake events.
+        let end_sync_hash = recording
+            .state_at_tick_validated(end_tick.min(recording.end_tick))?
+            .sync_hash();
+        let star = moment.star_snake_id;
+        let slow_start = focus_tick - (2_000 / tick_ms);
+        let slow_end = focus_tick + (1_500 / tick_ms);
+        let rotation = star_rotation(&anchor_state, star);
+
+        Ok(HighlightClip {
+            clip_format_version: HIGHLIGHT_CLIP_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: recording.game_id,
+            star_user_id: moment.star_user_id,
+            star_snake_id: star,
+            star_name: moment.star_name,
+            reason: moment.reason,
+            score: moment.score,
+            breakdown: moment.breakdown,
+            window: moment.window,
+            anchor: anchor_state,
+            messages,
+            end_sync_hash,
+            presentation: HighlightPresentation {
+                rotation,
+                follow_snake_id: star,
+                segments: vec![
+                    HighlightSpeedSegment {
+                        until_tick: slow_start,
+                        time_scale: 1.0,
+                    },
+                    HighlightSpeedSegment {
+                        until_tick: slow_end,
+                        time_scale: 0.5,
+                    },
+                    HighlightSpeedSegment {
+                        until_tick: end_tick,
+                        time_scale: 1.0,
+                    },
+                ],
+            },
+            config: self.config.clone(),
+        })
+    }
 }
 
 fn replay_observations(recording: &GameRecordingV1) -> Result<Vec<Observation>> {
@@ -666,6 +1251,139 @@ fn replay_observations(recording: &GameRecordingV1) -> Result<Vec<Observation>>
     Ok(observations)
 }
 
+fn match_timeline(recording: &GameRecordingV1, observations: &[Observation]) -> MatchTimeline {
+    let anchor = &recording.anchors[0];
+    let tick_ms = anchor.state.properties.tick_duration_ms.max(1);
+    let mut timeline = MatchTimeline::default();
+
+    let mut leader = None;
+    let mut deficits = HashMap::<TeamId, u32>::new();
+    let mut completed_at = None;
+    for (snake_id, snake) in anchor.state.arena.snakes.iter().enumerate() {
+        if snake.is_alive {
+            timeline
+                .lives
+                .entry(snake_id as u32)
+                .or_default()
+                .push((anchor.tick, None));
+        }
+    }
+    let mut dead = HashSet::new();
+    for observation in observations {
+        match &observation.event {
+            GameEvent::TeamScoreUpdated { team_id, score } => {
+                let mut scores = observation.before.team_scores.clone().unwrap_or_default();
+                scores.insert(*team_id, *score);
+                for (team, own) in &scores {
+                    let best_other = scores
+                        .iter()
+                        .filter(|(other, _)| *other != team)
+                        .map(|(_, score)| *score)
+                        .max()
+                        .unwrap_or_default();
+                    let deficit = deficits.entry(*team).or_default();
+                    *deficit = (*deficit).max(best_other.saturating_sub(*own));
+                }
+                let top = scores.values().copied().max().unwrap_or_default();
+                let mut leaders = scores.iter().filter(|(_, score)| **score == top);
+                let new_leader = match (leaders.next(), leaders.next()) {
+                    (Some((team, _)), None) if top > 0 => Some(*team),
+                    _ => None,
+                };
+                if let Some(team_id) = new_leader
+                    && new_leader != leader
+                {
+                    timeline.lead_changes.push(LeadChange {
+                        tick: observation.tick,
+                        team_id,
+                        deficit: deficits.get(&team_id).copied().unwrap_or_default(),
+                        buzzer_beater: false,
+                    });
+                }
+                leader = new_leader;
+            }
+            GameEvent::RoundStarted { .. } => {
+                leader = None;
+                deficits.clear();
+            }
+            GameEvent::StatusUpdated {
+                status: GameStatus::Complete { .. },
+            } if completed_at.is_none() => completed_at = Some(observation.tick),
+            GameEvent::SnakeDied { snake_id, cause } if !matches!(cause, DeathCause::Banked) => {
+                dead.insert(*snake_id);
+                if let Some(life) = timeline
+                    .lives
+                    .get_mut(snake_id)
+                    .and_then(|lives| lives.last_mut())
+                    .filter(|life| life.1.is_none())
+                {
+                    life.1 = Some(observation.tick);
+                }
+            }
+            GameEvent::SnakeRespawned { snake_id, .. } => {
+                let lives = timeline.lives.entry(*snake_id).or_default();
+                if dead.remove(snake_id) || lives.is_empty() {
+                    lives.push((observation.tick, None));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let (Some(completed_at), Some(leader), Some(last)) =
+        (completed_at, leader, timeline.lead_changes.last_mut())
+        && last.team_id == leader
+        && completed_at.saturating_sub(last.tick) <= BUZZER_BEATER_MS / tick_ms
+    {
+        last.buzzer_beater = true;
+    }
+
+    // A pocket can close and open again inside a second, but not inside a
+    // fifth of one, so that is as often as the flood fill needs to run.
+    let step_ticks = (200 / tick_ms).max(1);
+    let escape_ticks = TRAP_ESCAPE_MS / tick_ms;
+    let mut next_sample = anchor.tick;
+    let mut trapped_at = HashMap::<u32, u32>::new();
+    for observation in observations {
+        if let GameEvent::SnakeDied { snake_id, .. } = &observation.event {
+            trapped_at.remove(snake_id);
+        }
+        if observation.tick < next_sample {
+            continue;
+        }
+        next_sample = observation.tick.saturating_add(step_ticks);
+        let state = &observation.before;
+        for (snake_id, snake) in state.arena.snakes.iter().enumerate() {
+            let snake_id = snake_id as u32;
+            if !snake.is_alive {
+                trapped_at.remove(&snake_id);
+                continue;
+            }
+            let open = reachable_cells(state, snake_id, 15).len() >= 15;
+            match trapped_at.get(&snake_id).copied() {
+                Some(since) if open => {
+                    trapped_at.remove(&snake_id);
+                    if observation.tick.saturating_sub(since) <= escape_ticks {
+                        timeline.escapes.push((snake_id, observation.tick));
+                    }
+                }
+                None if !open => {
+                    let cornered = (0..state.arena.snakes.len() as u32).any(|rival| {
+                        rival != snake_id
+                            && !same_team(state, snake_id, rival)
+                            && state.arena.snakes[rival as usize].is_alive
+                            && encloses(state, rival, snake_id)
+                    });
+                    if cornered {
+                        trapped_at.insert(snake_id, observation.tick);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    timeline
+}
+
 fn better_candidate(
     star: u32,
     candidate: &HighlightScoreBreakdown,
@@ -680,6 +1398,7 @@ fn better_candidate(
                 || (candidate.focus_tick == current.focus_tick && star < *current_star)))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn score_range(
     recording: &GameRecordingV1,
     observations: &[Observation],
@@ -687,6 +1406,7 @@ fn score_range(
     start: u32,
     end: u32,
     config: &HighlightConfig,
+    timeline: &MatchTimeline,
     trap_cache: &mut HashMap<(u32, u32, u32), bool>,
 ) -> Result<HighlightScoreBreakdown> {
     let mut result = HighlightScoreBreakdown {
@@ -836,6 +1556,31 @@ fn score_range(
         result.add_focus(goal.tick, points);
     }
 
+    // Hill and flag captures share the objective bucket with banking: each
+    // mode scores its own way, so a match only ever feeds one of them.
+    for observation in in_window {
+        if let GameEvent::HillCaptured {
+            team_id,
+            previous_holder: Some(previous_holder),
+        } = &observation.event
+            && *previous_holder != *team_id
+            && star_on_hill(&observation.before, star, *team_id)
+        {
+            result.contested_captures += 1;
+            result.total += config.contested_capture;
+            result.banking_points += config.contested_capture;
+            result.add_focus(observation.tick, config.contested_capture);
+        }
+        if let GameEvent::FlagCaptured { snake_id, .. } = &observation.event
+            && *snake_id == star
+        {
+            result.flag_captures += 1;
+            result.total += config.flag_capture;
+            result.banking_points += config.flag_capture;
+            result.add_focus(observation.tick, config.flag_capture);
+        }
+    }
+
     let mut combo_focus_tick = start;
     let mut frenzy_focus_tick = start;
     for observation in in_window {
@@ -868,24 +1613,135 @@ fn score_range(
             result.add_focus(observation.tick, contribution);
         }
     }
-    if result.max_chain > 1 {
-        let points = (result.max_chain - 1) as i32 * config.combo_step;
-        result.total += points;
-        result.combo_points += points;
-        result.add_focus(combo_focus_tick, points);
+    if result.max_chain > 1 {
+        let points = (result.max_chain - 1) as i32 * config.combo_step;
+        result.total += points;
+        result.combo_points += points;
+        result.add_focus(combo_focus_tick, points);
+    }
+    if result.pickups >= 8 {
+        result.total += config.feeding_frenzy;
+        result.combo_points += config.feeding_frenzy;
+        result.add_focus(frenzy_focus_tick, config.feeding_frenzy);
+    }
+    score_clutch(
+        &mut result,
+        recording,
+        in_window,
+        star,
+        start,
+        end,
+        config,
+        timeline,
+        &goals,
+    );
+    for death_tick in star_death_ticks {
+        if !kill_ticks.contains(&death_tick) {
+            result.total += config.death;
+            result.demolition_points += config.death;
+        }
+    }
+    Ok(result)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn score_clutch(
+    result: &mut HighlightScoreBreakdown,
+    recording: &GameRecordingV1,
+    in_window: &[Observation],
+    star: u32,
+    start: u32,
+    end: u32,
+    config: &HighlightConfig,
+    timeline: &MatchTimeline,
+    goals: &HashMap<(u32, u32), TeamGoal>,
+) {
+    let anchor_state = &recording.anchors[0].state;
+    let star_team = anchor_state
+        .arena
+        .snakes
+        .get(star as usize)
+        .and_then(|snake| snake.team_id);
+    let min_deficit = anchor_state
+        .properties
+        .score_limit
+        .map_or(1, |limit| {
+            (limit * COMEBACK_MIN_DEFICIT_PERCENT).div_ceil(100)
+        })
+        .max(1);
+    // Only a lead the star scored themselves is theirs to claim: a goal run
+    // or a flag capture on the tick the lead changed hands.
+    for change in &timeline.lead_changes {
+        if change.tick < start || change.tick >= end || Some(change.team_id) != star_team {
+            continue;
+        }
+        let goal_points = goals
+            .get(&(change.tick, star))
+            .map(|goal| goal.points)
+            .unwrap_or_default();
+        let captured = in_window.iter().any(|observation| {
+            observation.tick == change.tick
+                && matches!(
+                    observation.event,
+                    GameEvent::FlagCaptured { snake_id, .. } if snake_id == star
+                )
+        });
+        if goal_points == 0 && !captured {
+            continue;
+        }
+        if change.deficit >= min_deficit {
+            result.comeback_deficit = result.comeback_deficit.max(change.deficit);
+            result.add_clutch(change.tick, config.comeback);
+        }
+        if change.buzzer_beater {
+            result.buzzer_beater_points = goal_points.max(1);
+            result.add_clutch(change.tick, config.buzzer_beater);
+        }
+    }
+
+    let tick_ms = anchor_state.properties.tick_duration_ms.max(1);
+    let streak_ticks = SURVIVAL_STREAK_MS / tick_ms;
+    let star_life = |tick: u32| {
+        timeline.lives.get(&star).and_then(|lives| {
+            lives
+                .iter()
+                .find(|(born, died)| *born <= tick && died.is_none_or(|died| tick < died))
+                .map(|(born, _)| *born)
+        })
+    };
+    let mut outlasted = None;
+    for observation in in_window {
+        if let GameEvent::SnakeDied { snake_id, cause } = &observation.event
+            && *snake_id != star
+            && !matches!(cause, DeathCause::Banked)
+            // The star's own kills already score as demolition.
+            && !matches!(
+                cause,
+                DeathCause::SnakeBody { killer_snake_id: killer }
+                    | DeathCause::HeadToHead { other_snake_id: killer }
+                    if *killer == star
+            )
+            && !same_team(&observation.before, star, *snake_id)
+            && let Some(born) = star_life(observation.tick)
+            && observation.tick - born >= streak_ticks
+        {
+            let seconds = (observation.tick - born) * tick_ms / 1_000;
+            if outlasted.is_none_or(|(_, best)| seconds > best) {
+                outlasted = Some((observation.tick, seconds));
+            }
+        }
     }
-    if result.pickups >= 8 {
-        result.total += config.feeding_frenzy;
-        result.combo_points += config.feeding_frenzy;
-        result.add_focus(frenzy_focus_tick, config.feeding_frenzy);
+    if let Some((tick, seconds)) = outlasted {
+        result.survival_seconds = seconds;
+        result.add_clutch(tick, config.survival_streak);
     }
-    for death_tick in star_death_ticks {
-        if !kill_ticks.contains(&death_tick) {
-            result.total += config.death;
-            result.demolition_points += config.death;
+
+    for (snake_id, tick) in &timeline.escapes {
+        if *snake_id == star && *tick >= start && *tick < end {
+            result.trap_escapes += 1;
+            result.add_clutch(*tick, config.trap_escape);
         }
     }
-    Ok(result)
 }
 
 /// Observations are validated in `(tick, sequence)` order, so binary slicing
@@ -927,6 +1783,21 @@ fn same_team(state: &GameState, left: u32, right: u32) -> bool {
     left.is_some() && left == right
 }
 
+/// Whether `star` plays for `team_id` and stands on the hill in `state`.
+fn star_on_hill(state: &GameState, star: u32, team_id: TeamId) -> bool {
+    let Some(hill) = &state.properties.hill else {
+        return false;
+    };
+    let bounds = hill.bounds(state.arena.width, state.arena.height);
+    state
+        .arena
+        .snakes
+        .get(star as usize)
+        .filter(|snake| snake.is_alive && snake.team_id == Some(team_id))
+        .and_then(|snake| snake.head().ok())
+        .is_some_and(|head| bounds.contains(head))
+}
+
 fn prior_kills_of_victim(
     observations: &[Observation],
     star: u32,
@@ -1009,9 +1880,12 @@ fn recent_cutoff(
             GameEvent::SnakeTurned { snake_id, .. } if snake_id == star
         )
     });
-    if !turned_recently {
-        return false;
-    }
+    turned_recently && lies_across_path(state, star, victim)
+}
+
+/// Whether `star`'s body lies across the next dozen cells of `victim`'s
+/// heading: the geometry of a cutoff, without the turn that makes it one.
+pub(crate) fn lies_across_path(state: &GameState, star: u32, victim: u32) -> bool {
     let Some(star_snake) = state.arena.snakes.get(star as usize) else {
         return false;
     };
@@ -1060,12 +1934,22 @@ fn trap_signature(
         .saturating_sub(2_000 / tick_ms)
         .max(recording.anchors[0].tick);
     let prior = recording.state_at_tick_validated(prior_tick)?;
-    let current_region = reachable_cells(current, victim, 64);
-    let current_reach = current_region.len();
+    let current_reach = reachable_cells(current, victim, 64).len();
     let prior_reach = reachable_cells(&prior, victim, 64).len();
-    if current_reach >= 15 || prior_reach == 0 || current_reach * 100 > prior_reach * 40 {
+    if prior_reach == 0 || current_reach * 100 > prior_reach * 40 {
         return Ok(false);
     }
+    Ok(encloses(current, star, victim))
+}
+
+/// Whether `victim` is shut in a pocket of fewer than 15 cells whose edge is
+/// at least 40% `star`'s body. A trap kill is one where the pocket also
+/// closed quickly; the search bot uses this half alone to judge a position.
+pub(crate) fn encloses(current: &GameState, star: u32, victim: u32) -> bool {
+    let current_region = reachable_cells(current, victim, 64);
+    if current_region.len() >= 15 {
+        return false;
+    }
     let star_cells = current
         .arena
         .snakes
@@ -1091,10 +1975,10 @@ fn trap_signature(
             }
         }
     }
-    Ok(frontier > 0 && star_frontier * 100 >= frontier * 40)
+    frontier > 0 && star_frontier * 100 >= frontier * 40
 }
 
-fn reachable_cells(state: &GameState, victim: u32, limit: usize) -> HashSet<Position> {
+pub(crate) fn reachable_cells(state: &GameState, victim: u32, limit: usize) -> HashSet<Position> {
     let Some(start) = state
         .arena
         .snakes
@@ -1226,6 +2110,11 @@ mod tests {
         CommandId, GameCommand, GameCommandMessage, GameStatus, QueueMode, ScenarioScript, TeamId,
     };
 
+    #[test]
+    fn replay_version_moves_with_the_protocol_version() {
+        assert_eq!(GAMEPLAY_REPLAY_VERSION, crate::WS_PROTOCOL_VERSION);
+    }
+
     fn recording_from_scenario(json: &str, game_id: u32) -> GameRecordingV1 {
         let loaded = ScenarioScript::from_json(json).unwrap().load().unwrap();
         let run = loaded.run().unwrap();
@@ -1255,6 +2144,7 @@ mod tests {
             messages,
             end_tick: run.final_state.tick,
             end_sync_hash: run.final_state.sync_hash(),
+            keyframes: Vec::new(),
         }
     }
 
@@ -1269,6 +2159,243 @@ mod tests {
         assert!(recording.verify_end_hash().is_err());
     }
 
+    #[test]
+    fn archive_replays_the_battle_royale_zone() {
+        let mut initial = GameState::new(
+            40,
+            40,
+            GameType::Custom {
+                settings: crate::CustomGameSettings {
+                    shrinking_zone: true,
+                    ..Default::default()
+                },
+            },
+            QueueMode::Quickmatch,
+            Some(11),
+            0,
+        );
+        for user_id in 1..=2 {
+            initial.add_player(user_id, None).unwrap();
+        }
+        initial.status = GameStatus::Started { server_id: 1 };
+        let zone = initial.properties.zone.clone().unwrap();
+        let first_ring_tick = zone.start_delay_ms / initial.properties.tick_duration_ms;
+        initial.tick = first_ring_tick - 2;
+        for user_id in 1..=2 {
+            initial
+                .player_last_activity_ticks
+                .insert(user_id, initial.tick);
+        }
+
+        let mut live = initial.clone();
+        let mut messages = Vec::new();
+        for _ in 0..4 {
+            for (sequence, event) in live.tick_forward(false).unwrap() {
+                messages.push(RecordedGameMessage {
+                    tick: live.tick,
+                    sequence,
+                    event,
+                });
+            }
+        }
+        assert!(
+            messages
+                .iter()
+                .any(|message| matches!(message.event, GameEvent::ZoneContracted { .. }))
+        );
+        let recording = GameRecordingV1 {
+            format_version: GAME_RECORDING_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: 9,
+            visibility: ReplayVisibility::Public,
+            anchors: vec![ReplayAnchor {
+                tick: initial.tick,
+                sequence: initial.event_sequence,
+                state: initial.clone(),
+            }],
+            messages,
+            end_tick: live.tick,
+            end_sync_hash: live.sync_hash(),
+            keyframes: Vec::new(),
+        };
+        recording.verify_end_hash().unwrap();
+        assert_eq!(
+            recording
+                .state_at_tick(first_ring_tick - 1)
+                .unwrap()
+                .arena
+                .zone,
+            Some(crate::ZoneBounds::full(40, 40))
+        );
+        assert_eq!(
+            recording.state_at_tick(first_ring_tick).unwrap().arena.zone,
+            Some(crate::ZoneBounds::inset(40, 40, 1))
+        );
+    }
+
+    #[test]
+    fn taking_the_hill_from_the_other_team_is_a_contested_capture() {
+        let mut initial = GameState::new(
+            60,
+            40,
+            GameType::KingOfTheHill { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(13),
+            0,
+        );
+        for user_id in 1..=2 {
+            initial.add_player(user_id, None).unwrap();
+        }
+        initial.status = GameStatus::Started { server_id: 1 };
+        initial.arena.snakes[0].body = vec![Position { x: 10, y: 5 }, Position { x: 7, y: 5 }];
+        initial.arena.snakes[0].direction = Direction::Right;
+        initial.arena.snakes[1].body = vec![Position { x: 30, y: 24 }, Position { x: 30, y: 27 }];
+        initial.arena.snakes[1].direction = Direction::Up;
+        initial.hill.as_mut().unwrap().holder = Some(crate::TeamId(0));
+        initial.tick = 20;
+        for user_id in 1..=2 {
+            initial
+                .player_last_activity_ticks
+                .insert(user_id, initial.tick);
+        }
+
+        let mut live = initial.clone();
+        let mut messages = Vec::new();
+        for _ in 0..8 {
+            for (sequence, event) in live.tick_forward(false).unwrap() {
+                messages.push(RecordedGameMessage {
+                    tick: live.tick,
+                    sequence,
+                    event,
+                });
+            }
+        }
+        let recording = GameRecordingV1 {
+            format_version: GAME_RECORDING_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: 12,
+            visibility: ReplayVisibility::Public,
+            anchors: vec![ReplayAnchor {
+                tick: initial.tick,
+                sequence: initial.event_sequence,
+                state: initial.clone(),
+            }],
+            messages,
+            end_tick: live.tick,
+            end_sync_hash: live.sync_hash(),
+            keyframes: Vec::new(),
+        };
+        recording.verify_end_hash().unwrap();
+
+        let observations = replay_observations(&recording).unwrap();
+        let config = HighlightConfig::default();
+        let score = |star| {
+            score_range(
+                &recording,
+                &observations,
+                star,
+                0,
+                recording.end_tick + 1,
+                &config,
+                &match_timeline(&recording, &observations),
+                &mut HashMap::new(),
+            )
+            .unwrap()
+        };
+        let taker = score(1);
+        assert_eq!(taker.contested_captures, 1);
+        assert_eq!(taker.total, config.contested_capture);
+        assert_eq!(
+            taker.reason(),
+            HighlightReason::ContestedCapture { captures: 1 }
+        );
+        assert_eq!(
+            score(0).contested_captures,
+            0,
+            "losing the hill is no highlight"
+        );
+    }
+
+    #[test]
+    fn carrying_the_enemy_flag_home_is_a_flag_capture() {
+        let mut initial = GameState::new(
+            60,
+            40,
+            GameType::CaptureTheFlag { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(13),
+            0,
+        );
+        for user_id in 1..=2 {
+            initial.add_player(user_id, None).unwrap();
+        }
+        initial.status = GameStatus::Started { server_id: 1 };
+        assert_eq!(initial.arena.snakes[0].team_id, Some(crate::TeamId(0)));
+        initial.arena.snakes[0].body = vec![Position { x: 12, y: 20 }, Position { x: 15, y: 20 }];
+        initial.arena.snakes[0].direction = Direction::Left;
+        initial.arena.snakes[1].body = vec![Position { x: 40, y: 5 }, Position { x: 43, y: 5 }];
+        initial.arena.snakes[1].direction = Direction::Left;
+        initial.flags[1].placement = crate::FlagPlacement::Carried { snake_id: 0 };
+        initial.tick = 20;
+        for user_id in 1..=2 {
+            initial
+                .player_last_activity_ticks
+                .insert(user_id, initial.tick);
+        }
+
+        let mut live = initial.clone();
+        let mut messages = Vec::new();
+        for _ in 0..16 {
+            for (sequence, event) in live.tick_forward(false).unwrap() {
+                messages.push(RecordedGameMessage {
+                    tick: live.tick,
+                    sequence,
+                    event,
+                });
+            }
+        }
+        let recording = GameRecordingV1 {
+            format_version: GAME_RECORDING_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: 14,
+            visibility: ReplayVisibility::Public,
+            anchors: vec![ReplayAnchor {
+                tick: initial.tick,
+                sequence: initial.event_sequence,
+                state: initial.clone(),
+            }],
+            messages,
+            end_tick: live.tick,
+            end_sync_hash: live.sync_hash(),
+            keyframes: Vec::new(),
+        };
+        recording.verify_end_hash().unwrap();
+
+        let observations = replay_observations(&recording).unwrap();
+        let config = HighlightConfig::default();
+        let score = |star| {
+            score_range(
+                &recording,
+                &observations,
+                star,
+                0,
+                recording.end_tick + 1,
+                &config,
+                &match_timeline(&recording, &observations),
+                &mut HashMap::new(),
+            )
+            .unwrap()
+        };
+        let carrier = score(0);
+        assert_eq!(carrier.flag_captures, 1);
+        assert_eq!(carrier.total, config.flag_capture);
+        assert_eq!(
+            carrier.reason(),
+            HighlightReason::FlagCapture { captures: 1 }
+        );
+        assert_eq!(score(1).flag_captures, 0, "losing the flag is no highlight");
+    }
+
     #[test]
     fn periodic_anchor_replays_only_later_same_tick_sequences() {
         let mut initial = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(7), 0);
@@ -1319,6 +2446,7 @@ mod tests {
             messages: vec![first, second],
             end_tick: expected.tick,
             end_sync_hash: expected.sync_hash(),
+            keyframes: Vec::new(),
         };
 
         recording.verify_end_hash().unwrap();
@@ -1328,6 +2456,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn keyframes_seek_to_the_state_a_full_replay_reaches() {
+        let plain = recording_from_scenario(
+            include_str!("../../client/web/scenarios/combo-frenzy.json"),
+            12,
+        );
+        // Scenario clips are shorter than the production cadence.
+        let interval_ticks = 4;
+        let mut indexed = plain.clone();
+        indexed.index_keyframes_every(interval_ticks).unwrap();
+        assert!(indexed.keyframes.len() > 2);
+        let mut previous = plain.anchors[0].tick;
+        for keyframe in &indexed.keyframes {
+            assert_eq!(keyframe.tick - previous, interval_ticks);
+            previous = keyframe.tick;
+        }
+
+        // Seeks must not depend on the keyframe surviving the archive's JSON.
+        let indexed: GameRecordingV1 =
+            serde_json::from_str(&serde_json::to_string(&indexed).unwrap()).unwrap();
+        indexed.verify_end_hash().unwrap();
+        for tick in plain.anchors[0].tick..=plain.end_tick {
+            assert_eq!(
+                indexed.state_at_tick(tick).unwrap().sync_hash(),
+                plain.state_at_tick(tick).unwrap().sync_hash(),
+                "tick {tick}"
+            );
+        }
+    }
+
+    #[test]
+    fn keyframes_fill_the_gaps_between_anchors() {
+        let mut recording = recording_from_scenario(
+            include_str!("../../client/web/scenarios/combo-frenzy.json"),
+            14,
+        );
+        let start = recording.anchors[0].tick;
+        let midpoint = start + 10;
+        let state = recording.state_at_tick(midpoint).unwrap();
+        let sequence = recording
+            .messages
+            .iter()
+            .take_while(|message| message.tick <= midpoint)
+            .last()
+            .map_or(0, |message| message.sequence);
+        recording.anchors.push(ReplayAnchor {
+            tick: midpoint,
+            sequence,
+            state,
+        });
+
+        recording.index_keyframes_every(4).unwrap();
+        let ticks: Vec<_> = recording
+            .keyframes
+            .iter()
+            .map(|keyframe| keyframe.tick - start)
+            .collect();
+        assert_eq!(ticks, [4, 8, 14, 18]);
+        recording.verify_end_hash().unwrap();
+    }
+
+    #[test]
+    fn stepping_through_ticks_matches_seeking_each_one() {
+        let mut recording = recording_from_scenario(
+            include_str!("../../client/web/scenarios/combo-frenzy.json"),
+            14,
+        );
+        // An anchor mid-window must restart the walk, as it restarts a seek.
+        let midpoint = recording.anchors[0].tick + 9;
+        let state = recording.state_at_tick(midpoint).unwrap();
+        let sequence = recording
+            .messages
+            .iter()
+            .take_while(|message| message.tick <= midpoint)
+            .last()
+            .map_or(0, |message| message.sequence);
+        recording.anchors.push(ReplayAnchor {
+            tick: midpoint,
+            sequence,
+            state,
+        });
+
+        let start = recording.anchors[0].tick + 2;
+        let mut visited = Vec::new();
+        recording
+            .for_each_tick(start, recording.end_tick, |state| {
+                visited.push(state.sync_hash());
+                Ok(())
+            })
+            .unwrap();
+        let sought: Vec<_> = (start..=recording.end_tick)
+            .map(|tick| recording.state_at_tick(tick).unwrap().sync_hash())
+            .collect();
+        assert_eq!(visited, sought);
+        assert_eq!(visited.last(), Some(&recording.end_sync_hash));
+        assert!(
+            recording
+                .for_each_tick(start, start - 1, |_| Ok(()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn keyframes_are_checked_against_their_hash_and_the_replay() {
+        let mut recording = recording_from_scenario(
+            include_str!("../../client/web/scenarios/combo-frenzy.json"),
+            13,
+        );
+        recording.index_keyframes_every(4).unwrap();
+        recording.keyframes[0].sync_hash ^= 1;
+        assert!(recording.validate().is_err());
+
+        // A self-consistent keyframe still has to be the one the messages
+        // produce, or a seek would land on a state the match never had.
+        recording.keyframes[0].state.arena.snakes[0].food += 1;
+        recording.keyframes[0].sync_hash = recording.keyframes[0].state.sync_hash();
+        recording.validate().unwrap();
+        assert!(recording.verify_end_hash().is_err());
+
+        recording.index_keyframes().unwrap();
+        recording.verify_end_hash().unwrap();
+        assert!(recording.keyframes.is_empty());
+        let legacy = serde_json::to_value(GameRecordingV1 {
+            keyframes: Vec::new(),
+            ..recording
+        })
+        .unwrap();
+        assert!(legacy.get("keyframes").is_none());
+    }
+
     #[test]
     fn replay_hashes_cross_json_losslessly_and_read_legacy_numbers() {
         let recording = recording_from_scenario(
@@ -1446,6 +2704,7 @@ mod tests {
             0,
             recording.end_tick + 1,
             &HighlightConfig::default(),
+            &match_timeline(&recording, &observations),
             &mut trap_cache,
         )
         .unwrap();
@@ -1468,6 +2727,7 @@ mod tests {
             0,
             recording.end_tick + 1,
             &HighlightConfig::default(),
+            &match_timeline(&recording, &observations),
             &mut trap_cache,
         )
         .unwrap();
@@ -1600,6 +2860,7 @@ mod tests {
             messages: Vec::new(),
             end_tick: state.tick,
             end_sync_hash: state.sync_hash(),
+            keyframes: Vec::new(),
         }
     }
 
@@ -1654,6 +2915,7 @@ mod tests {
             state.tick,
             state.tick + 1,
             &HighlightConfig::default(),
+            &MatchTimeline::default(),
             &mut active_cache,
         )
         .unwrap();
@@ -1665,6 +2927,7 @@ mod tests {
             state.tick,
             state.tick + 1,
             &HighlightConfig::default(),
+            &MatchTimeline::default(),
             &mut afk_cache,
         )
         .unwrap();
@@ -1690,6 +2953,7 @@ mod tests {
             state.tick,
             state.tick + 1,
             &HighlightConfig::default(),
+            &MatchTimeline::default(),
             &mut first_cache,
         )
         .unwrap();
@@ -1701,6 +2965,7 @@ mod tests {
             state.tick,
             state.tick + 1,
             &HighlightConfig::default(),
+            &MatchTimeline::default(),
             &mut second_cache,
         )
         .unwrap();
@@ -1712,6 +2977,7 @@ mod tests {
             state.tick,
             state.tick + 1,
             &HighlightConfig::default(),
+            &MatchTimeline::default(),
             &mut third_cache,
         )
         .unwrap();
@@ -1760,4 +3026,158 @@ mod tests {
         assert_eq!(goals.len(), 1);
         assert_eq!(goals[&(10, star)].points, 15);
     }
+
+    fn clutch_score(
+        recording: &GameRecordingV1,
+        observations: &[Observation],
+        star: u32,
+        start: u32,
+        end: u32,
+    ) -> HighlightScoreBreakdown {
+        score_range(
+            recording,
+            observations,
+            star,
+            start,
+            end,
+            &HighlightConfig::default(),
+            &match_timeline(recording, observations),
+            &mut HashMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn scoring_the_lead_from_behind_at_the_whistle_is_a_buzzer_beating_comeback() {
+        let (mut state, star, victim, _) = adversarial_kill_state();
+        let star_team = state.arena.snakes[star as usize].team_id.unwrap();
+        let victim_team = state.arena.snakes[victim as usize].team_id.unwrap();
+        state.team_scores = Some(HashMap::from([(star_team, 0), (victim_team, 0)]));
+        let recording = recording_at_state(&state);
+        let trailing = Observation {
+            tick: 10,
+            before: state.clone(),
+            event: GameEvent::TeamScoreUpdated {
+                team_id: victim_team,
+                score: 9,
+            },
+        };
+        state.tick = 40;
+        state.team_scores = Some(HashMap::from([(star_team, 0), (victim_team, 9)]));
+        state.recent_goals = vec![TeamGoal {
+            tick: 40,
+            team_id: star_team,
+            snake_id: star,
+            position: Position { x: 0, y: 0 },
+            points: 10,
+        }];
+        let goal = Observation {
+            tick: 40,
+            before: state.clone(),
+            event: GameEvent::TeamScoreUpdated {
+                team_id: star_team,
+                score: 10,
+            },
+        };
+        let whistle = Observation {
+            tick: 42,
+            before: state,
+            event: GameEvent::StatusUpdated {
+                status: GameStatus::Complete {
+                    winning_snake_id: Some(star),
+                },
+            },
+        };
+        let observations = [trailing, goal, whistle];
+
+        let score = clutch_score(&recording, &observations, star, 30, 45);
+        let config = HighlightConfig::default();
+        assert_eq!(score.comeback_deficit, 9);
+        assert_eq!(score.buzzer_beater_points, 10);
+        assert_eq!(score.clutch_points, config.comeback + config.buzzer_beater);
+        assert_eq!(score.focus_tick, 40);
+        assert_eq!(score.reason(), HighlightReason::BuzzerBeater { points: 10 });
+        // The early lead came from level, so the rival's goal is neither.
+        let early = clutch_score(&recording, &observations, victim, 0, 20);
+        assert_eq!(early.clutch_points, 0);
+    }
+
+    #[test]
+    fn a_rival_dying_late_in_the_stars_life_is_a_survival_streak() {
+        let (state, star, victim, _) = adversarial_kill_state();
+        let recording = recording_at_state(&state);
+        let tick_ms = state.properties.tick_duration_ms;
+        let streak_tick = state.tick + SURVIVAL_STREAK_MS / tick_ms;
+        let death = |tick| Observation {
+            tick,
+            before: state.clone(),
+            event: GameEvent::SnakeDied {
+                snake_id: victim,
+                cause: DeathCause::Wall,
+            },
+        };
+        let observations = [death(streak_tick - 1), death(streak_tick + 5)];
+
+        let early = clutch_score(&recording, &observations, star, 0, streak_tick);
+        assert_eq!(early.survival_seconds, 0);
+        let late = clutch_score(
+            &recording,
+            &observations,
+            star,
+            streak_tick,
+            streak_tick + 10,
+        );
+        assert_eq!(
+            late.survival_seconds,
+            (5 * tick_ms + SURVIVAL_STREAK_MS) / 1_000
+        );
+        assert_eq!(late.focus_tick, streak_tick + 5);
+        assert_eq!(
+            late.reason(),
+            HighlightReason::SurvivalStreak {
+                seconds: late.survival_seconds
+            }
+        );
+    }
+
+    #[test]
+    fn breaking_out_of_a_closing_pocket_is_a_trap_escape() {
+        let (mut state, star, victim, _) = adversarial_kill_state();
+        state.arena.snakes[star as usize].body =
+            vec![Position { x: 30, y: 20 }, Position { x: 30, y: 21 }];
+        // A ring around the star's 3×3 pocket.
+        state.arena.snakes[victim as usize].body = vec![
+            Position { x: 28, y: 18 },
+            Position { x: 32, y: 18 },
+            Position { x: 32, y: 22 },
+            Position { x: 28, y: 22 },
+            Position { x: 28, y: 19 },
+        ];
+        assert!(encloses(&state, victim, star));
+        let recording = recording_at_state(&state);
+        let trapped = state.clone();
+        state.tick += 1_000 / state.properties.tick_duration_ms;
+        state.arena.snakes[victim as usize].body =
+            vec![Position { x: 40, y: 10 }, Position { x: 44, y: 10 }];
+        let escape_tick = state.tick;
+        let heartbeat = |tick, before| Observation {
+            tick,
+            before,
+            event: GameEvent::TickHash {
+                hash: 0,
+                server_ts_ms: 0,
+            },
+        };
+        let observations = [
+            heartbeat(trapped.tick, trapped),
+            heartbeat(escape_tick, state),
+        ];
+
+        let score = clutch_score(&recording, &observations, star, 0, escape_tick + 1);
+        assert_eq!(score.trap_escapes, 1);
+        assert_eq!(score.focus_tick, escape_tick);
+        assert_eq!(score.reason(), HighlightReason::TrapEscape { escapes: 1 });
+        let trapper = clutch_score(&recording, &observations, victim, 0, escape_tick + 1);
+        assert_eq!(trapper.trap_escapes, 0);
+    }
 }
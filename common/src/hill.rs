@@ -0,0 +1,161 @@
+//! King of the hill.
+//!
+//! A king-of-the-hill match is a team match without banking. Two teams start
+//! in their end zones of the classic team arena, and a square hill sits in the
+//! middle of the field. A team scores by being the only side with a snake's
+//! head on the hill: every `point_interval_ms` of such uncontested hold is one
+//! point, reported through the same `team_scores` and `TeamScoreUpdated` as
+//! banked food. The first team to the queue's target wins.
+//!
+//! Control is accounted from the snakes' positions alone, in the movement
+//! path of `GameState::tick_forward`. Every replica therefore reaches the same
+//! holder and the same points on the same tick, and the authoritative events
+//! only confirm what the replica already drew.
+//!
+//! Every other mode carries no config and no control, so its snapshots,
+//! recordings and fingerprints are byte-identical to the ones written before
+//! the hill existed.
+
+use crate::constants::{
+    DEFAULT_COMPETITIVE_HILL_SCORE_LIMIT, DEFAULT_HILL_POINT_INTERVAL_MS, DEFAULT_HILL_SIZE,
+    DEFAULT_QUICKMATCH_HILL_SCORE_LIMIT, HILL_RULES_VERSION, MAX_HILL_POINT_INTERVAL_MS,
+};
+use crate::game_state::{GameType, QueueMode, TeamId};
+use crate::zone::ZoneBounds;
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+/// Hill rules snapshotted into a match so simulation, prediction and replay
+/// never depend on process configuration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct HillConfig {
+    /// Side of the square hill in cells, centred on the arena.
+    pub size: u16,
+    /// Simulation time a team must hold the hill alone to earn one point.
+    pub point_interval_ms: u32,
+    pub rules_version: u16,
+}
+
+impl Default for HillConfig {
+    fn default() -> Self {
+        Self {
+            size: DEFAULT_HILL_SIZE,
+            point_interval_ms: DEFAULT_HILL_POINT_INTERVAL_MS,
+            rules_version: HILL_RULES_VERSION,
+        }
+    }
+}
+
+impl HillConfig {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(self.size > 0, "hill size must be positive");
+        ensure!(
+            (1..=MAX_HILL_POINT_INTERVAL_MS).contains(&self.point_interval_ms),
+            "hill point_interval_ms must be in 1..={MAX_HILL_POINT_INTERVAL_MS}, got {}",
+            self.point_interval_ms
+        );
+        ensure!(
+            self.rules_version == HILL_RULES_VERSION,
+            "unsupported hill rules version {}, expected {}",
+            self.rules_version,
+            HILL_RULES_VERSION
+        );
+        Ok(())
+    }
+
+    /// The hill's cells on a `width` x `height` arena. An odd leftover cell
+    /// goes to the right and bottom, so both teams are equally far from it
+    /// on the canonical even-sized team arena.
+    pub fn bounds(&self, width: u16, height: u16) -> ZoneBounds {
+        let side_x = self.size.min(width);
+        let side_y = self.size.min(height);
+        let min_x = (width - side_x) / 2;
+        let min_y = (height - side_y) / 2;
+        ZoneBounds {
+            min_x: min_x as i16,
+            min_y: min_y as i16,
+            max_x: (min_x + side_x) as i16,
+            max_y: (min_y + side_y) as i16,
+        }
+    }
+}
+
+/// Who holds the hill and how far they are toward their next point.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct HillControl {
+    /// The last team to stand on the hill alone. Holding survives a hill left
+    /// empty or contested; only the other team standing on it alone takes it.
+    pub holder: Option<TeamId>,
+    /// Uncontested hold time banked toward the holder's next point. Always
+    /// below the config's `point_interval_ms`, and reset by every capture.
+    pub held_ms: u32,
+    /// Whether both teams had a head on the hill on the last tick.
+    pub contested: bool,
+}
+
+/// Which hill rules a mode plays with, if any. Only king of the hill has one.
+pub fn hill_config_for(game_type: &GameType) -> Option<HillConfig> {
+    match game_type {
+        GameType::KingOfTheHill { .. } => Some(HillConfig::default()),
+        _ => None,
+    }
+}
+
+/// Hill points a team needs to win, by queue. Quick Match is a sprint;
+/// Competitive asks for twice as long on top of the hill.
+pub fn hill_score_limit(queue_mode: &QueueMode) -> u32 {
+    match queue_mode {
+        QueueMode::Quickmatch => DEFAULT_QUICKMATCH_HILL_SCORE_LIMIT,
+        QueueMode::Competitive => DEFAULT_COMPETITIVE_HILL_SCORE_LIMIT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    #[test]
+    fn only_king_of_the_hill_matches_have_a_hill() {
+        for per_team in [1, 2] {
+            assert_eq!(
+                hill_config_for(&GameType::KingOfTheHill { per_team }),
+                Some(HillConfig::default())
+            );
+        }
+        for other in [
+            GameType::Solo,
+            GameType::TeamMatch { per_team: 2 },
+            GameType::FreeForAll { max_players: 4 },
+            GameType::Custom {
+                settings: Default::default(),
+            },
+        ] {
+            assert_eq!(hill_config_for(&other), None, "{other:?}");
+        }
+        HillConfig::default().validate().expect("default is valid");
+    }
+
+    #[test]
+    fn the_hill_is_centred_and_never_larger_than_the_arena() {
+        let hill = HillConfig::default().bounds(60, 40);
+        assert_eq!(
+            (hill.max_x - hill.min_x, hill.max_y - hill.min_y),
+            (DEFAULT_HILL_SIZE as i16, DEFAULT_HILL_SIZE as i16)
+        );
+        assert_eq!(hill.min_x, 60 - hill.max_x);
+        assert_eq!(hill.min_y, 40 - hill.max_y);
+        assert!(hill.contains(&Position { x: 30, y: 20 }));
+
+        let tiny = HillConfig {
+            size: 50,
+            ..HillConfig::default()
+        }
+        .bounds(8, 8);
+        assert_eq!(tiny, ZoneBounds::full(8, 8));
+    }
+}
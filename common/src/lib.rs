@@ -1,26 +1,48 @@
 mod ai;
 mod arena;
+pub mod capture;
 mod constants;
+mod death_food;
 mod fingerprint;
+mod flag;
+#[cfg(feature = "test-support")]
+pub mod fuzz;
 mod game_engine;
 mod game_state;
 pub mod highlight;
+mod hill;
+mod occupancy;
+mod power_up;
 pub mod replay;
 pub mod scenario;
 mod snake;
+mod snapshot_delta;
+mod spectate;
 pub mod trace;
+mod wire;
+mod zone;
 
 pub mod util;
 
 pub use ai::*;
+pub use arena::*;
 pub use constants::*;
+pub use death_food::*;
 pub use fingerprint::SyncHasher;
+pub use flag::*;
 pub use game_engine::*;
 pub use game_state::*;
 pub use highlight::*;
+pub use hill::*;
+pub use occupancy::OccupancyGrid;
+pub use power_up::*;
 pub use scenario::*;
 pub use snake::*;
+pub use snapshot_delta::*;
+pub use spectate::*;
 pub use util::PseudoRandom;
+pub use wire::*;
+pub use zone::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct ServerTimestamp(u64);
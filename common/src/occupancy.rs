@@ -0,0 +1,375 @@
+//! Which snakes cover which cells.
+//!
+//! Collision checks used to ask every snake whether its compressed body
+//! contains a cell, a walk over every body for every head, every food spawn
+//! and every cell an AI considers. The occupancy grid answers the same
+//! question with one lookup. It lives on `Arena`, is updated cell by cell as
+//! snakes step, and is rebuilt for a snake only when its body changed some
+//! other way: a respawn, a crash rollback, a snapshot, a test placing it.
+//!
+//! The grid is a cache. It is never serialized or hashed, and a grid that no
+//! longer matches the bodies is never consulted: `Arena::occupancy` only hands
+//! out a grid it has checked against every body, and callers without one fall
+//! back to `Arena::snake_contains`. Every answer is the one the bodies
+//! themselves give, so simulation, replays and fingerprints are unchanged.
+
+use crate::snake::{Position, Snake};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// No snake covers the cell.
+const EMPTY: u32 = u32::MAX;
+/// Several covers; the snake ids are in `OccupancyGrid::shared`.
+const SHARED: u32 = u32::MAX - 1;
+
+#[derive(Clone, Default)]
+pub struct OccupancyGrid {
+    width: u16,
+    height: u16,
+    wraps: bool,
+    /// Row-major: `EMPTY`, the id of the one snake covering the cell, or
+    /// `SHARED`.
+    cells: Vec<u32>,
+    /// Cells covered more than once, with every covering snake id in
+    /// ascending order, once per cover. Ghosts and a snake that has just run
+    /// into its own body are the only way to get here.
+    shared: BTreeMap<usize, Vec<u32>>,
+    /// The body each snake was indexed from, by snake id.
+    indexed: Vec<Vec<Position>>,
+}
+
+impl OccupancyGrid {
+    fn has_layout(&self, width: u16, height: u16, wraps: bool) -> bool {
+        self.width == width
+            && self.height == height
+            && self.wraps == wraps
+            && self.cells.len() == usize::from(width) * usize::from(height)
+    }
+
+    /// Whether the grid describes exactly these bodies on this field.
+    pub(crate) fn is_current(
+        &self,
+        width: u16,
+        height: u16,
+        wraps: bool,
+        snakes: &[Snake],
+    ) -> bool {
+        self.has_layout(width, height, wraps)
+            && self.indexed.len() == snakes.len()
+            && self
+                .indexed
+                .iter()
+                .zip(snakes)
+                .all(|(indexed, snake)| *indexed == snake.body)
+    }
+
+    /// Bring the grid up to date, reindexing only the snakes whose bodies
+    /// changed since they were last indexed.
+    pub(crate) fn sync(&mut self, width: u16, height: u16, wraps: bool, snakes: &[Snake]) {
+        if !self.has_layout(width, height, wraps) {
+            *self = Self {
+                width,
+                height,
+                wraps,
+                cells: vec![EMPTY; usize::from(width) * usize::from(height)],
+                shared: BTreeMap::new(),
+                indexed: Vec::with_capacity(snakes.len()),
+            };
+        }
+        while self.indexed.len() > snakes.len() {
+            let snake_id = self.indexed.len() - 1;
+            self.unindex(snake_id);
+            self.indexed.pop();
+        }
+        for (snake_id, snake) in snakes.iter().enumerate() {
+            if self.indexed.get(snake_id) != Some(&snake.body) {
+                self.unindex(snake_id);
+                self.index(snake_id, &snake.body);
+            }
+        }
+    }
+
+    /// Record one movement step of `snake_id`, whose tail was `old_tail`
+    /// before it. The caller must have checked that the grid held the snake's
+    /// body from before the step.
+    pub(crate) fn step(&mut self, snake_id: usize, old_tail: Position, body: &[Position]) {
+        if self.indexed[snake_id].len() < 2 || body.len() < 2 {
+            self.unindex(snake_id);
+            self.index(snake_id, body);
+            return;
+        }
+        if let Some(head) = self.cell_index(body[0]) {
+            self.add(head, snake_id as u32);
+        }
+        // A snake that is growing keeps its tail; otherwise the tail has left
+        // its old cell.
+        if self.wrap(old_tail) != self.wrap(body[body.len() - 1])
+            && let Some(tail) = self.cell_index(old_tail)
+        {
+            self.remove(tail, snake_id as u32);
+        }
+        let indexed = &mut self.indexed[snake_id];
+        indexed.clear();
+        indexed.extend_from_slice(body);
+    }
+
+    /// Whether the grid currently holds `body` for `snake_id`.
+    pub(crate) fn holds(&self, snake_id: usize, body: &[Position]) -> bool {
+        self.indexed
+            .get(snake_id)
+            .is_some_and(|indexed| indexed == body)
+    }
+
+    /// Every snake covering `position`, in ascending id order, once per
+    /// cover. Empty off the field.
+    pub fn occupants(&self, position: &Position) -> &[u32] {
+        let Some(index) = self.cell_index(*position) else {
+            return &[];
+        };
+        match self.cells[index] {
+            EMPTY => &[],
+            SHARED => &self.shared[&index],
+            _ => std::slice::from_ref(&self.cells[index]),
+        }
+    }
+
+    /// The distinct snakes covering `position`, in ascending id order.
+    pub fn snakes_at(&self, position: &Position) -> impl Iterator<Item = u32> + '_ {
+        let occupants = self.occupants(position);
+        occupants
+            .iter()
+            .enumerate()
+            .filter(move |(i, snake_id)| *i == 0 || occupants[i - 1] != **snake_id)
+            .map(|(_, snake_id)| *snake_id)
+    }
+
+    /// `Arena::snake_contains` for snake `snake_id`, answered from the grid.
+    pub fn covers(
+        &self,
+        snakes: &[Snake],
+        snake_id: u32,
+        position: &Position,
+        skip_head: bool,
+    ) -> bool {
+        let covers = self
+            .occupants(position)
+            .iter()
+            .filter(|occupant| **occupant == snake_id)
+            .count();
+        let head_skipped = skip_head
+            && snakes
+                .get(snake_id as usize)
+                .and_then(|snake| snake.body.first())
+                == Some(position);
+        covers > usize::from(head_skipped)
+    }
+
+    /// Whether any living snake covers `position`.
+    pub fn live_snake_at(&self, snakes: &[Snake], position: &Position) -> bool {
+        self.snakes_at(position)
+            .any(|snake_id| snakes.get(snake_id as usize).is_some_and(|s| s.is_alive))
+    }
+
+    fn wrap(&self, position: Position) -> Position {
+        if !self.wraps {
+            return position;
+        }
+        Position {
+            x: position.x.rem_euclid(self.width.max(1) as i16),
+            y: position.y.rem_euclid(self.height.max(1) as i16),
+        }
+    }
+
+    fn cell_index(&self, position: Position) -> Option<usize> {
+        let position = self.wrap(position);
+        (position.x >= 0
+            && position.y >= 0
+            && position.x < self.width as i16
+            && position.y < self.height as i16)
+            .then(|| position.y as usize * usize::from(self.width) + position.x as usize)
+    }
+
+    /// The cells a body covers, once per cover. A body of fewer than two
+    /// points covers nothing, as it does for `Snake::contains_point`.
+    fn body_cell_indices(&self, body: &[Position]) -> Vec<usize> {
+        if body.len() < 2 {
+            return Vec::new();
+        }
+        body_cells(body)
+            .filter_map(|cell| self.cell_index(cell))
+            .collect()
+    }
+
+    fn index(&mut self, snake_id: usize, body: &[Position]) {
+        if self.indexed.len() <= snake_id {
+            self.indexed.resize_with(snake_id + 1, Vec::new);
+        }
+        for cell in self.body_cell_indices(body) {
+            self.add(cell, snake_id as u32);
+        }
+        self.indexed[snake_id] = body.to_vec();
+    }
+
+    fn unindex(&mut self, snake_id: usize) {
+        let Some(body) = self.indexed.get_mut(snake_id).map(std::mem::take) else {
+            return;
+        };
+        for cell in self.body_cell_indices(&body) {
+            self.remove(cell, snake_id as u32);
+        }
+    }
+
+    fn add(&mut self, cell: usize, snake_id: u32) {
+        match self.cells[cell] {
+            EMPTY => self.cells[cell] = snake_id,
+            SHARED => {
+                let occupants = self.shared.entry(cell).or_default();
+                let at = occupants.partition_point(|occupant| *occupant <= snake_id);
+                occupants.insert(at, snake_id);
+            }
+            only => {
+                self.cells[cell] = SHARED;
+                self.shared
+                    .insert(cell, vec![only.min(snake_id), only.max(snake_id)]);
+            }
+        }
+    }
+
+    fn remove(&mut self, cell: usize, snake_id: u32) {
+        match self.cells[cell] {
+            SHARED => {
+                let Some(occupants) = self.shared.get_mut(&cell) else {
+                    return;
+                };
+                if let Some(at) = occupants.iter().position(|occupant| *occupant == snake_id) {
+                    occupants.remove(at);
+                }
+                if occupants.len() == 1 {
+                    self.cells[cell] = occupants[0];
+                    self.shared.remove(&cell);
+                }
+            }
+            only if only == snake_id => self.cells[cell] = EMPTY,
+            _ => {}
+        }
+    }
+}
+
+/// Arenas compare by their contents; the grid is derived from them.
+impl PartialEq for OccupancyGrid {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for OccupancyGrid {}
+
+impl fmt::Debug for OccupancyGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupancyGrid")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("snakes", &self.indexed.len())
+            .field("shared_cells", &self.shared.len())
+            .finish()
+    }
+}
+
+/// Every cell of a compressed body, head to tail, as the body lists them: a
+/// body that has crossed a seam runs on off the field.
+pub(crate) fn body_cells(body: &[Position]) -> impl Iterator<Item = Position> + '_ {
+    body.first()
+        .copied()
+        .into_iter()
+        .chain(body.windows(2).flat_map(|pair| {
+            let (from, to) = (pair[0], pair[1]);
+            let (dx, dy) = ((to.x - from.x).signum(), (to.y - from.y).signum());
+            let steps = (to.x - from.x).abs() + (to.y - from.y).abs();
+            (1..=steps).map(move |step| Position {
+                x: from.x + dx * step,
+                y: from.y + dy * step,
+            })
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Direction;
+
+    fn snake(body: Vec<Position>) -> Snake {
+        Snake::new(body, Direction::Right, true, 0, None)
+    }
+
+    fn at(x: i16, y: i16) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn the_grid_answers_exactly_what_the_bodies_do() {
+        let snakes = vec![
+            snake(vec![at(5, 2), at(5, 5), at(1, 5)]),
+            // Crosses snake 0, as a ghost may.
+            snake(vec![at(3, 7), at(3, 3)]),
+            // Runs into its own body.
+            snake(vec![at(8, 1), at(8, 3), at(6, 3), at(6, 1), at(9, 1)]),
+            snake(vec![at(0, 0)]),
+        ];
+        let mut grid = OccupancyGrid::default();
+        grid.sync(10, 10, false, &snakes);
+        assert!(grid.is_current(10, 10, false, &snakes));
+
+        for x in -1..11 {
+            for y in -1..11 {
+                let cell = at(x, y);
+                for (snake_id, snake) in snakes.iter().enumerate() {
+                    for skip_head in [false, true] {
+                        assert_eq!(
+                            grid.covers(&snakes, snake_id as u32, &cell, skip_head),
+                            snake.contains_point(&cell, skip_head),
+                            "snake {snake_id} at {cell:?}, skip_head {skip_head}"
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(grid.snakes_at(&at(3, 5)).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(grid.occupants(&at(8, 1)), &[2, 2]);
+    }
+
+    #[test]
+    fn steps_update_the_grid_in_place_and_other_changes_are_found_by_sync() {
+        let mut snakes = vec![
+            snake(vec![at(4, 4), at(1, 4)]),
+            snake(vec![at(4, 6), at(1, 6)]),
+        ];
+        let mut grid = OccupancyGrid::default();
+        grid.sync(8, 8, true, &snakes);
+
+        for _ in 0..6 {
+            let old_tail = snakes[0].body[1];
+            assert!(grid.holds(0, &snakes[0].body));
+            snakes[0].step_forward_wrapping(8, 8);
+            grid.step(0, old_tail, &snakes[0].body);
+        }
+        snakes[1].food = 2;
+        for _ in 0..3 {
+            let old_tail = snakes[1].body[1];
+            snakes[1].step_forward_wrapping(8, 8);
+            grid.step(1, old_tail, &snakes[1].body);
+        }
+        assert!(grid.is_current(8, 8, true, &snakes));
+
+        let mut rebuilt = OccupancyGrid::default();
+        rebuilt.sync(8, 8, true, &snakes);
+        assert_eq!(grid.cells, rebuilt.cells);
+        assert_eq!(grid.shared, rebuilt.shared);
+
+        snakes[1].body = vec![at(2, 2), at(2, 0)];
+        assert!(!grid.is_current(8, 8, true, &snakes));
+        grid.sync(8, 8, true, &snakes);
+        rebuilt.sync(8, 8, true, &snakes);
+        assert_eq!(grid.cells, rebuilt.cells);
+        assert_eq!(grid.occupants(&at(4, 6)), &[] as &[u32]);
+    }
+}
@@ -0,0 +1,248 @@
+//! Collectible power-ups.
+//!
+//! Boost packets are the one pickup every matchmade mode shares. Power-ups are
+//! a second, optional family: short-lived effects that bend a single rule for
+//! the snake that collects them.
+//!
+//! - **Shield** absorbs the next crash. The snake stays where it was, keeps
+//!   its life, and loses the shield.
+//! - **Ghost** lets the snake pass through other snakes, and them through it,
+//!   for a few seconds. Walls, obstacles and its own body still count.
+//! - **Magnet** eats every pellet within a small radius of the head.
+//!
+//! Power-ups are opt-in per `GameType` through `power_up_config_for`. Ranked
+//! modes never get a config, and every power-up field is empty without one, so
+//! their snapshots, recordings and fingerprints are byte-identical to the ones
+//! written before power-ups existed.
+
+use crate::constants::{
+    DEFAULT_GHOST_DURATION_MS, DEFAULT_MAGNET_DURATION_MS, DEFAULT_MAGNET_RADIUS,
+    DEFAULT_POWER_UP_MAX_ON_FIELD, DEFAULT_POWER_UP_SPAWN_INTERVAL_MS, MAX_MAGNET_RADIUS,
+    MAX_POWER_UP_DURATION_MS, POWER_UP_RULES_VERSION,
+};
+use crate::game_state::GameType;
+use crate::snake::Position;
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum PowerUpKind {
+    Shield,
+    Ghost,
+    Magnet,
+}
+
+/// Power-up rules snapshotted into a match so simulation, prediction and
+/// replay never depend on process configuration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct PowerUpConfig {
+    /// Kinds that may spawn, in the order the spawn roll indexes them.
+    pub kinds: Vec<PowerUpKind>,
+    /// Wall-clock cadence of spawn attempts.
+    pub spawn_interval_ms: u32,
+    /// Uncollected pickups allowed on the field at once.
+    pub max_on_field: u8,
+    pub ghost_duration_ms: u32,
+    pub magnet_duration_ms: u32,
+    /// Chebyshev distance from the head at which a magnet collects food.
+    pub magnet_radius: u8,
+    pub rules_version: u16,
+}
+
+impl Default for PowerUpConfig {
+    fn default() -> Self {
+        Self {
+            kinds: vec![PowerUpKind::Shield, PowerUpKind::Ghost, PowerUpKind::Magnet],
+            spawn_interval_ms: DEFAULT_POWER_UP_SPAWN_INTERVAL_MS,
+            max_on_field: DEFAULT_POWER_UP_MAX_ON_FIELD,
+            ghost_duration_ms: DEFAULT_GHOST_DURATION_MS,
+            magnet_duration_ms: DEFAULT_MAGNET_DURATION_MS,
+            magnet_radius: DEFAULT_MAGNET_RADIUS,
+            rules_version: POWER_UP_RULES_VERSION,
+        }
+    }
+}
+
+impl PowerUpConfig {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(!self.kinds.is_empty(), "power-up config offers no kinds");
+        let mut kinds = self.kinds.clone();
+        kinds.sort_unstable();
+        kinds.dedup();
+        ensure!(
+            kinds.len() == self.kinds.len(),
+            "power-up kinds must be unique, got {:?}",
+            self.kinds
+        );
+        ensure!(
+            self.spawn_interval_ms > 0,
+            "power-up spawn_interval_ms must be positive"
+        );
+        ensure!(
+            self.max_on_field > 0,
+            "power-up max_on_field must be positive"
+        );
+        for (name, duration_ms) in [
+            ("ghost_duration_ms", self.ghost_duration_ms),
+            ("magnet_duration_ms", self.magnet_duration_ms),
+        ] {
+            ensure!(
+                (1..=MAX_POWER_UP_DURATION_MS).contains(&duration_ms),
+                "power-up {name} must be in 1..={MAX_POWER_UP_DURATION_MS}, got {duration_ms}"
+            );
+        }
+        ensure!(
+            (1..=MAX_MAGNET_RADIUS).contains(&self.magnet_radius),
+            "power-up magnet_radius must be in 1..={MAX_MAGNET_RADIUS}, got {}",
+            self.magnet_radius
+        );
+        ensure!(
+            self.rules_version == POWER_UP_RULES_VERSION,
+            "unsupported power-up rules version {}, expected {}",
+            self.rules_version,
+            POWER_UP_RULES_VERSION
+        );
+        Ok(())
+    }
+
+    pub fn allows(&self, kind: PowerUpKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// Whether `food` is within magnet reach of `head`.
+    pub fn magnet_reaches(&self, head: &Position, food: &Position) -> bool {
+        let radius = i32::from(self.magnet_radius);
+        (i32::from(head.x) - i32::from(food.x)).abs() <= radius
+            && (i32::from(head.y) - i32::from(food.y)).abs() <= radius
+    }
+}
+
+/// An uncollected power-up lying on one cell of the field.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct PowerUpPickup {
+    pub kind: PowerUpKind,
+    pub position: Position,
+}
+
+/// Effects one snake currently holds. The default is the single inactive
+/// representation; a death or respawn always returns to it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct SnakePowerUps {
+    /// Absorbs the next crash, then breaks. Shields do not stack.
+    pub shield: bool,
+    /// Simulation time left passing through other snakes.
+    pub ghost_remaining_ms: u32,
+    /// Simulation time left collecting food within the configured radius.
+    pub magnet_remaining_ms: u32,
+}
+
+impl SnakePowerUps {
+    pub fn is_inactive(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn ghost(&self) -> bool {
+        self.ghost_remaining_ms > 0
+    }
+
+    pub fn magnet(&self) -> bool {
+        self.magnet_remaining_ms > 0
+    }
+
+    /// Start (or restart) the effect `kind` grants. Collecting a timed effect
+    /// that is already running refills it rather than adding to it.
+    pub(crate) fn grant(&mut self, kind: PowerUpKind, config: &PowerUpConfig) {
+        match kind {
+            PowerUpKind::Shield => self.shield = true,
+            PowerUpKind::Ghost => self.ghost_remaining_ms = config.ghost_duration_ms,
+            PowerUpKind::Magnet => self.magnet_remaining_ms = config.magnet_duration_ms,
+        }
+    }
+
+    /// Drain one authoritative simulation quantum from the timed effects.
+    pub(crate) fn drain(&mut self, elapsed_ms: u32) {
+        self.ghost_remaining_ms = self.ghost_remaining_ms.saturating_sub(elapsed_ms);
+        self.magnet_remaining_ms = self.magnet_remaining_ms.saturating_sub(elapsed_ms);
+    }
+}
+
+/// Which power-up rules a mode plays with, if any.
+///
+/// Only Custom games can opt in, through their lobby settings. Matchmade modes
+/// are ranked or feed leaderboards, so they keep exactly the rules players
+/// already know.
+pub fn power_up_config_for(game_type: &GameType) -> Option<PowerUpConfig> {
+    match game_type {
+        GameType::Custom { settings } if settings.power_ups => Some(PowerUpConfig::default()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CustomGameSettings;
+
+    #[test]
+    fn only_custom_games_that_ask_for_power_ups_get_them() {
+        let with_power_ups = GameType::Custom {
+            settings: CustomGameSettings {
+                power_ups: true,
+                ..CustomGameSettings::default()
+            },
+        };
+        assert_eq!(
+            power_up_config_for(&with_power_ups),
+            Some(PowerUpConfig::default())
+        );
+        assert_eq!(
+            power_up_config_for(&GameType::Custom {
+                settings: CustomGameSettings::default()
+            }),
+            None
+        );
+        for ranked in [
+            GameType::Solo,
+            GameType::TeamMatch { per_team: 1 },
+            GameType::TeamMatch { per_team: 2 },
+            GameType::FreeForAll { max_players: 4 },
+        ] {
+            assert_eq!(power_up_config_for(&ranked), None, "{ranked:?}");
+        }
+    }
+
+    #[test]
+    fn default_config_is_valid_and_rejects_duplicates_and_unbounded_effects() {
+        PowerUpConfig::default()
+            .validate()
+            .expect("default is valid");
+
+        let duplicated = PowerUpConfig {
+            kinds: vec![PowerUpKind::Ghost, PowerUpKind::Ghost],
+            ..PowerUpConfig::default()
+        };
+        assert!(duplicated.validate().is_err());
+
+        let endless_ghost = PowerUpConfig {
+            ghost_duration_ms: MAX_POWER_UP_DURATION_MS + 1,
+            ..PowerUpConfig::default()
+        };
+        assert!(endless_ghost.validate().is_err());
+    }
+
+    #[test]
+    fn magnet_reach_is_a_square_around_the_head() {
+        let config = PowerUpConfig::default();
+        let head = Position { x: 10, y: 10 };
+        assert!(config.magnet_reaches(&head, &Position { x: 12, y: 8 }));
+        assert!(!config.magnet_reaches(&head, &Position { x: 13, y: 10 }));
+    }
+}
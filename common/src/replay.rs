@@ -97,7 +97,7 @@ pub fn trace_side(records: &[TraceRecord]) -> Option<TraceSide> {
     })
 }
 
-fn require_current_trace_format(records: &[TraceRecord]) -> Result<()> {
+pub(crate) fn require_current_trace_format(records: &[TraceRecord]) -> Result<()> {
     let version = records
         .iter()
         .find_map(|record| match record {
@@ -178,10 +178,8 @@ impl ServerReplay {
         for record in records {
             // Only records at or after the anchor participate in the replay.
             match record {
-                TraceRecord::State { state, .. } => {
-                    if anchor.is_none() {
-                        anchor = Some(state);
-                    }
+                TraceRecord::State { state, .. } if anchor.is_none() => {
+                    anchor = Some(state);
                 }
                 TraceRecord::CmdIn { ts_ms, cmd } if anchor.is_some() => {
                     timeline.push(ServerItem::Cmd { ts_ms, cmd });
@@ -411,10 +409,8 @@ impl ClientReplay {
         let mut timeline = Vec::new();
         for record in records {
             match record {
-                TraceRecord::State { state, .. } => {
-                    if anchor.is_none() {
-                        anchor = Some(state);
-                    }
+                TraceRecord::State { state, .. } if anchor.is_none() => {
+                    anchor = Some(state);
                 }
                 TraceRecord::EventIn { msg, .. } => {
                     if anchor.is_some() {
@@ -781,11 +777,9 @@ pub fn diff_traces(server: &[TraceRecord], client: &[TraceRecord]) -> Divergence
 
     for record in client {
         match record {
-            TraceRecord::EventIn { msg, .. } => {
-                if msg.stream_seq > 0 {
-                    client_first_seq.get_or_insert(msg.stream_seq);
-                    client_seqs.insert(msg.stream_seq);
-                }
+            TraceRecord::EventIn { msg, .. } if msg.stream_seq > 0 => {
+                client_first_seq.get_or_insert(msg.stream_seq);
+                client_seqs.insert(msg.stream_seq);
             }
             TraceRecord::CmdOut {
                 ts_ms,
@@ -5,12 +5,15 @@
 //! fields can be overridden; outcomes are always produced by the real engine.
 
 use crate::{
-    CommandId, Direction, GameCommand, GameCommandMessage, GameEvent, GameState, GameStatus,
-    GameType, Position, QueueMode, SnakeCombo, TeamId, boost_config_for, calculate_ai_move,
+    AiDifficulty, AiProfile, AiTeamStrategy, ArenaMap, BotPolicy, BotPolicyRegistry, CommandId,
+    DeathCause, Direction, GameCommand, GameCommandMessage, GameEvent, GameState, GameStatus,
+    GameType, Position, QueueMode, SnakeCombo, TeamId, boost_config_for, calculate_ai_move_for,
 };
 use anyhow::{Context, Result, bail, ensure};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
 
 pub const SCENARIO_FORMAT_VERSION: u32 = 1;
 
@@ -30,7 +33,7 @@ fn default_camera_ease() -> f32 {
     0.16
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-gen", ts(export))]
 pub struct ScenarioScript {
@@ -96,7 +99,7 @@ pub struct ScenarioBoostOverride {
     pub capacity_ms: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-gen", ts(export))]
 pub struct ScenarioPose {
@@ -108,6 +111,13 @@ pub struct ScenarioPose {
     pub team_scores: Vec<(u8, u32)>,
     #[serde(default)]
     pub start_tick: u32,
+    /// The exact state a pose taken from a recorded game was captured from.
+    /// The scenario then starts from it instead of a freshly built arena, so
+    /// RNG, scores and timers carry over; the fields above must still
+    /// describe it, and the world's overrides must be empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub captured: Option<Box<GameState>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -134,6 +144,16 @@ pub struct ScenarioSnakePose {
     pub combo_remaining_ms: u32,
     #[serde(default)]
     pub driver: ScenarioDriver,
+    /// Team strategy an `Ai` driver plays with in place of the default one,
+    /// so an evaluation can pit two strategies against each other.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub team_strategy: Option<AiTeamStrategy>,
+    /// Registered [`BotPolicy`] an `Ai` driver plays by in place of the
+    /// scenario's own turn-only AI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub policy: Option<String>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -152,6 +172,12 @@ pub struct ScenarioCommand {
     pub at_tick: u32,
     pub user_id: u32,
     pub command: ScenarioCommandKind,
+    /// Tick the command reaches the queue, no later than `at_tick`. Commands
+    /// without one are queued when the scenario loads. Queueing restarts the
+    /// player's idle clock, so a captured script keeps the recorded arrival.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub scheduled_at: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -161,6 +187,7 @@ pub enum ScenarioCommandKind {
     Turn(Direction),
     ActivateBoost,
     DeactivateBoost,
+    PlayerActivity,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -254,19 +281,98 @@ pub struct ScenarioAddons {
     pub boost_meter: bool,
 }
 
+/// An outcome a scenario promises. A tick is the one a simulation step lands
+/// on, which is also the tick [`ScenarioRun::events`] files that step's
+/// events under; state is checked as it stands once that tick is reached.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-gen", ts(export))]
 pub enum ScenarioExpectation {
-    SnakeDead { snake_id: u32, at_tick: u32 },
+    SnakeDead {
+        snake_id: u32,
+        at_tick: u32,
+    },
     FinalSyncHash(String),
+    Score {
+        snake_id: u32,
+        at_tick: u32,
+        score: u32,
+    },
+    TeamScore {
+        team_id: u8,
+        at_tick: u32,
+        score: u32,
+    },
+    /// Cells occupied, as [`Snake::length`](crate::Snake::length) counts them.
+    SnakeLength {
+        snake_id: u32,
+        at_tick: u32,
+        length: u32,
+    },
+    /// Pellets picked up since the match began, whatever their combo value.
+    FoodEaten {
+        snake_id: u32,
+        at_tick: u32,
+        count: u32,
+    },
+    BoostCharge {
+        snake_id: u32,
+        at_tick: u32,
+        charge_ms: u32,
+    },
+    /// A `kind` event (its JSON tag, such as `"FoodEaten"`) in
+    /// `from_tick..=to_tick`, only counting events about `snake_id` when it
+    /// is given. At least one is expected, or exactly `count`.
+    Event {
+        kind: String,
+        from_tick: u32,
+        to_tick: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        snake_id: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        count: Option<u32>,
+    },
+    /// The snake died of `cause`, at `at_tick` when it is given.
+    DeathCause {
+        snake_id: u32,
+        cause: DeathCause,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        at_tick: Option<u32>,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub struct LoadedScenario {
     pub script: ScenarioScript,
     pub initial_state: GameState,
-    ai_snakes: Vec<(u32, u32)>, // (user_id, snake_id)
+    ai_snakes: Vec<(u32, u32, ScenarioAi)>, // (user_id, snake_id, driver)
+    deferred_commands: Vec<(u32, GameCommandMessage)>, // (scheduled_at, command)
+}
+
+/// How the playback steers one `Ai` snake.
+#[derive(Clone)]
+enum ScenarioAi {
+    /// The scenario's own AI, which only ever turns.
+    Turns(AiProfile),
+    /// A registered policy, by the name it was loaded under.
+    Policy(String, Arc<dyn BotPolicy>),
+}
+
+impl fmt::Debug for ScenarioAi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScenarioAi::Turns(profile) => f.debug_tuple("Turns").field(profile).finish(),
+            ScenarioAi::Policy(name, _) => f.debug_tuple("Policy").field(name).finish(),
+        }
+    }
+}
+
+impl LoadedScenario {
+    /// Commands that reach the queue after the scenario starts, as
+    /// `(scheduled_at, command)` in arrival order.
+    pub fn deferred_commands(&self) -> &[(u32, GameCommandMessage)] {
+        &self.deferred_commands
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -285,8 +391,10 @@ pub struct ScenarioRun {
 pub struct ScenarioPlayback {
     initial_state: GameState,
     state: GameState,
-    ai_snakes: Vec<(u32, u32)>,
+    ai_snakes: Vec<(u32, u32, ScenarioAi)>,
     ai_sequences: HashMap<u32, u32>,
+    deferred_commands: Vec<(u32, GameCommandMessage)>,
+    next_deferred: usize,
     end_tick: u32,
     events: Vec<(u32, u64, GameEvent)>,
 }
@@ -311,6 +419,7 @@ impl ScenarioPlayback {
     pub fn reset(&mut self) {
         self.state = self.initial_state.clone();
         self.ai_sequences.clear();
+        self.next_deferred = 0;
         self.events.clear();
     }
 
@@ -321,15 +430,38 @@ impl ScenarioPlayback {
             return Ok(false);
         }
 
-        for (user_id, snake_id) in &self.ai_snakes {
+        while let Some((scheduled_at, command)) = self.deferred_commands.get(self.next_deferred)
+            && *scheduled_at <= self.state.tick
+        {
+            self.state.schedule_command(command);
+            self.next_deferred += 1;
+        }
+
+        for (user_id, snake_id, driver) in &self.ai_snakes {
             let Some(snake) = self.state.arena.snakes.get(*snake_id as usize) else {
                 continue;
             };
             let direction = snake.direction;
-            let Some(next_direction) = calculate_ai_move(&self.state, *snake_id, direction) else {
-                continue;
+            let command = match driver {
+                ScenarioAi::Turns(profile) => {
+                    let Some(next_direction) =
+                        calculate_ai_move_for(&self.state, *snake_id, direction, profile)
+                    else {
+                        continue;
+                    };
+                    GameCommand::Turn {
+                        snake_id: *snake_id,
+                        direction: next_direction,
+                    }
+                }
+                ScenarioAi::Policy(_, policy) => {
+                    let Some(command) = policy.decide(&self.state, *snake_id) else {
+                        continue;
+                    };
+                    command
+                }
             };
-            if next_direction == direction {
+            if matches!(command, GameCommand::Turn { direction: next, .. } if next == direction) {
                 continue;
             }
             let sequence = self.ai_sequences.entry(*user_id).or_default();
@@ -342,10 +474,7 @@ impl ScenarioPlayback {
             self.state.schedule_command(&GameCommandMessage {
                 command_id_client: id.clone(),
                 command_id_server: Some(id),
-                command: GameCommand::Turn {
-                    snake_id: *snake_id,
-                    direction: next_direction,
-                },
+                command,
             });
         }
 
@@ -454,14 +583,191 @@ impl ScenarioScript {
             }
             ScenarioCamera::FullArena => {}
         }
+        for expected in &self.expect {
+            if let ScenarioExpectation::Event {
+                kind,
+                from_tick,
+                to_tick,
+                ..
+            } = expected
+            {
+                ensure!(!kind.trim().is_empty(), "expected event kind must be named");
+                ensure!(
+                    from_tick <= to_tick,
+                    "expected `{kind}` event range {from_tick}..={to_tick} is empty"
+                );
+            }
+        }
         Ok(())
     }
 
     pub fn load(self) -> Result<LoadedScenario> {
+        self.load_with_policies(&BotPolicyRegistry::builtin())
+    }
+
+    /// [`Self::load`], resolving `Ai` snakes' `policy` names in `policies`
+    /// rather than in the built-in registry.
+    pub fn load_with_policies(self, policies: &BotPolicyRegistry) -> Result<LoadedScenario> {
         self.validate()?;
         let (width, height) = scenario_dimensions(&self.world)?;
+        let mut state = match self.pose.captured.as_deref() {
+            Some(captured) => {
+                self.check_captured_pose(captured, width, height)?;
+                captured.clone()
+            }
+            None => self.fresh_state(width, height)?,
+        };
+
+        let mut users = HashSet::new();
+        let mut ai_users = Vec::new();
+        let mut snake_by_user = HashMap::new();
+        for pose in &self.pose.snakes {
+            ensure!(
+                users.insert(pose.user_id),
+                "duplicate scenario user_id {}",
+                pose.user_id
+            );
+            let snake_id = if self.pose.captured.is_some() {
+                state
+                    .players
+                    .get(&pose.user_id)
+                    .with_context(|| format!("captured state has no player {}", pose.user_id))?
+                    .snake_id
+            } else {
+                validate_body(pose, width, height)?;
+                let team_override = pose.team_id.map(TeamId);
+                if !state.game_type.is_team_game() && team_override.is_some() {
+                    bail!("team_id is only valid in a team scenario");
+                }
+                state
+                    .add_player_with_team(pose.user_id, Some(pose.name.clone()), team_override)?
+                    .snake_id
+            };
+            snake_by_user.insert(pose.user_id, snake_id);
+            if pose.driver == ScenarioDriver::Ai {
+                let driver = match &pose.policy {
+                    Some(name) => {
+                        ensure!(
+                            pose.team_strategy.is_none(),
+                            "snake {} has both a policy and a team_strategy",
+                            pose.user_id
+                        );
+                        let policy = policies
+                            .get(name)
+                            .with_context(|| format!("snake {}", pose.user_id))?;
+                        ScenarioAi::Policy(name.clone(), policy)
+                    }
+                    None => {
+                        let mut profile = AiDifficulty::default().profile();
+                        if let Some(team_strategy) = pose.team_strategy {
+                            profile.team_strategy = team_strategy;
+                        }
+                        ScenarioAi::Turns(profile)
+                    }
+                };
+                ai_users.push((pose.user_id, snake_id, driver));
+            } else {
+                ensure!(
+                    pose.team_strategy.is_none(),
+                    "snake {} has a team_strategy but no Ai driver",
+                    pose.user_id
+                );
+                ensure!(
+                    pose.policy.is_none(),
+                    "snake {} has a policy but no Ai driver",
+                    pose.user_id
+                );
+            }
+        }
+
+        // Adding a player recalculates every spawn, so apply authored poses
+        // only after the complete roster exists.
+        if self.pose.captured.is_none() {
+            self.apply_pose(&mut state, &snake_by_user)?;
+        }
 
-        let base_boost = boost_config_for(&self.world.game_type, width, height);
+        let end_tick = state.tick.saturating_add(self.run_ticks);
+        let mut sequence_by_user: HashMap<u32, u32> = HashMap::new();
+        let mut deferred_commands = Vec::new();
+        let mut commands = self.commands.clone();
+        commands.sort_by_key(|entry| (entry.at_tick, entry.user_id));
+        for command in commands {
+            ensure!(
+                command.at_tick >= state.tick && command.at_tick < end_tick,
+                "scenario command at tick {} is outside [{}, {})",
+                command.at_tick,
+                state.tick,
+                end_tick
+            );
+            if let Some(scheduled_at) = command.scheduled_at {
+                ensure!(
+                    scheduled_at >= state.tick && scheduled_at <= command.at_tick,
+                    "scenario command for tick {} is scheduled at {}, outside [{}, {}]",
+                    command.at_tick,
+                    scheduled_at,
+                    state.tick,
+                    command.at_tick
+                );
+            }
+            let player = state
+                .players
+                .get(&command.user_id)
+                .with_context(|| format!("command references unknown user {}", command.user_id))?;
+            let sequence = sequence_by_user.entry(command.user_id).or_default();
+            *sequence = sequence.saturating_add(1);
+            let id = CommandId {
+                tick: command.at_tick,
+                user_id: command.user_id,
+                sequence_number: *sequence,
+            };
+            let engine_command = match command.command {
+                ScenarioCommandKind::Turn(direction) => GameCommand::Turn {
+                    snake_id: player.snake_id,
+                    direction,
+                },
+                ScenarioCommandKind::ActivateBoost => GameCommand::ActivateBoost {
+                    snake_id: player.snake_id,
+                },
+                ScenarioCommandKind::DeactivateBoost => GameCommand::DeactivateBoost {
+                    snake_id: player.snake_id,
+                },
+                ScenarioCommandKind::PlayerActivity => GameCommand::PlayerActivity {
+                    snake_id: player.snake_id,
+                },
+            };
+            let message = GameCommandMessage {
+                command_id_client: id.clone(),
+                command_id_server: Some(id),
+                command: engine_command,
+            };
+            match command.scheduled_at {
+                Some(scheduled_at) if scheduled_at > state.tick => {
+                    deferred_commands.push((scheduled_at, message));
+                }
+                _ => state.schedule_command(&message),
+            }
+        }
+        deferred_commands.sort_by_key(|(scheduled_at, _)| *scheduled_at);
+
+        state
+            .validate_boost_invariants()
+            .context("scenario pose violates engine invariants")?;
+        let loaded = LoadedScenario {
+            script: self,
+            initial_state: state,
+            ai_snakes: ai_users,
+            deferred_commands,
+        };
+        validate_presentation_targets(&loaded)?;
+        Ok(loaded)
+    }
+
+    /// A new match for the scenario's world, with its overrides applied.
+    fn fresh_state(&self, width: u16, height: u16) -> Result<GameState> {
+        let base_boost = boost_config_for(
+            &self.world.game_type,
+            &ArenaMap::default_for(&self.world.game_type, width, height),
+        );
         let configured_boost = match (&self.world.overrides.boost, base_boost) {
             (None, existing) => existing,
             (Some(_), None) => bail!("Boost override is not valid for this mode and arena"),
@@ -509,31 +815,10 @@ impl ScenarioScript {
                 .player_idle_warning_ms
                 .min(timeout.saturating_sub(1));
         }
+        Ok(state)
+    }
 
-        let mut users = HashSet::new();
-        let mut ai_users = Vec::new();
-        let mut snake_by_user = HashMap::new();
-        for pose in &self.pose.snakes {
-            ensure!(
-                users.insert(pose.user_id),
-                "duplicate scenario user_id {}",
-                pose.user_id
-            );
-            validate_body(pose, width, height)?;
-            let team_override = pose.team_id.map(TeamId);
-            if !matches!(state.game_type, GameType::TeamMatch { .. }) && team_override.is_some() {
-                bail!("team_id is only valid in a team scenario");
-            }
-            let player =
-                state.add_player_with_team(pose.user_id, Some(pose.name.clone()), team_override)?;
-            snake_by_user.insert(pose.user_id, player.snake_id);
-            if pose.driver == ScenarioDriver::Ai {
-                ai_users.push((pose.user_id, player.snake_id));
-            }
-        }
-
-        // Adding a player recalculates every spawn, so apply authored poses
-        // only after the complete roster exists.
+    fn apply_pose(&self, state: &mut GameState, snake_by_user: &HashMap<u32, u32>) -> Result<()> {
         for pose in &self.pose.snakes {
             let player_snake_id = *snake_by_user
                 .get(&pose.user_id)
@@ -592,7 +877,7 @@ impl ScenarioScript {
             }
         }
 
-        validate_food(&self.pose.food, &state)?;
+        validate_food(&self.pose.food, state)?;
         state.arena.food = self.pose.food.clone();
         if !self.pose.team_scores.is_empty() {
             let scores = state
@@ -610,59 +895,120 @@ impl ScenarioScript {
         for tick in state.player_last_activity_ticks.values_mut() {
             *tick = state.tick;
         }
+        Ok(())
+    }
 
-        let end_tick = state.tick.saturating_add(self.run_ticks);
-        let mut sequence_by_user: HashMap<u32, u32> = HashMap::new();
-        let mut commands = self.commands.clone();
-        commands.sort_by_key(|entry| (entry.at_tick, entry.user_id));
-        for command in commands {
+    /// A captured state replaces the world and pose, so refuse one that the
+    /// script no longer describes rather than silently playing something else.
+    fn check_captured_pose(&self, captured: &GameState, width: u16, height: u16) -> Result<()> {
+        ensure!(
+            self.world.overrides == ScenarioOverrides::default(),
+            "a captured pose cannot be combined with world overrides"
+        );
+        ensure!(
+            captured.game_type == self.world.game_type
+                && (captured.arena.width, captured.arena.height) == (width, height),
+            "captured state does not match the scenario world"
+        );
+        ensure!(
+            captured.tick == self.pose.start_tick,
+            "captured state is at tick {}, not the pose's start_tick {}",
+            captured.tick,
+            self.pose.start_tick
+        );
+
+        let described = ScenarioPose::capture(captured);
+        ensure!(
+            self.pose.snakes.len() == described.snakes.len(),
+            "a captured pose must describe all {} players",
+            described.snakes.len()
+        );
+        for pose in &self.pose.snakes {
+            let observed = described
+                .snakes
+                .iter()
+                .find(|observed| observed.user_id == pose.user_id)
+                .with_context(|| format!("captured state has no player {}", pose.user_id))?;
+            // Names and drivers are the script's to choose.
+            let authored = ScenarioSnakePose {
+                name: observed.name.clone(),
+                is_alive: pose.is_alive.filter(|alive| !alive),
+                driver: observed.driver,
+                team_strategy: None,
+                policy: None,
+                ..pose.clone()
+            };
             ensure!(
-                command.at_tick >= state.tick && command.at_tick < end_tick,
-                "scenario command at tick {} is outside [{}, {})",
-                command.at_tick,
-                state.tick,
-                end_tick
+                authored == *observed,
+                "snake of user {} does not match its captured state",
+                pose.user_id
             );
-            let player = state
-                .players
-                .get(&command.user_id)
-                .with_context(|| format!("command references unknown user {}", command.user_id))?;
-            let sequence = sequence_by_user.entry(command.user_id).or_default();
-            *sequence = sequence.saturating_add(1);
-            let id = CommandId {
-                tick: command.at_tick,
-                user_id: command.user_id,
-                sequence_number: *sequence,
-            };
-            let engine_command = match command.command {
-                ScenarioCommandKind::Turn(direction) => GameCommand::Turn {
-                    snake_id: player.snake_id,
-                    direction,
-                },
-                ScenarioCommandKind::ActivateBoost => GameCommand::ActivateBoost {
-                    snake_id: player.snake_id,
-                },
-                ScenarioCommandKind::DeactivateBoost => GameCommand::DeactivateBoost {
-                    snake_id: player.snake_id,
-                },
-            };
-            state.schedule_command(&GameCommandMessage {
-                command_id_client: id.clone(),
-                command_id_server: Some(id),
-                command: engine_command,
-            });
         }
-
-        state
-            .validate_boost_invariants()
-            .context("scenario pose violates engine invariants")?;
-        let loaded = LoadedScenario {
-            script: self,
-            initial_state: state,
-            ai_snakes: ai_users,
+        let sorted_food = |food: &[Position]| {
+            let mut food = food.to_vec();
+            food.sort_unstable_by_key(|position| (position.x, position.y));
+            food
         };
-        validate_presentation_targets(&loaded)?;
-        Ok(loaded)
+        ensure!(
+            sorted_food(&self.pose.food) == sorted_food(&described.food),
+            "food does not match the captured state"
+        );
+        let mut team_scores = self.pose.team_scores.clone();
+        team_scores.sort_unstable();
+        ensure!(
+            team_scores == described.team_scores,
+            "team_scores do not match the captured state"
+        );
+        Ok(())
+    }
+}
+
+impl ScenarioPose {
+    /// Describe `state` as a pose, keeping the state itself as the pose's
+    /// `captured` anchor. Every player is posed as a `Scripted` snake.
+    pub fn capture(state: &GameState) -> Self {
+        let mut players: Vec<_> = state.players.values().collect();
+        players.sort_by_key(|player| player.user_id);
+        let snakes = players
+            .into_iter()
+            .filter_map(|player| {
+                let snake = state.arena.snakes.get(player.snake_id as usize)?;
+                Some(ScenarioSnakePose {
+                    user_id: player.user_id,
+                    name: state
+                        .usernames
+                        .get(&player.user_id)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Player {}", player.user_id)),
+                    body: snake.body.clone(),
+                    direction: snake.direction,
+                    food: snake.food,
+                    team_id: snake.team_id.map(|team| team.0),
+                    is_alive: (!snake.is_alive).then_some(false),
+                    boost_charge_ms: snake.boost.charge_ms,
+                    boost_active: snake.boost.active,
+                    combo_chain: snake.combo.chain_count,
+                    combo_remaining_ms: snake.combo.remaining_ms,
+                    driver: ScenarioDriver::Scripted,
+                    team_strategy: None,
+                    policy: None,
+                })
+            })
+            .collect();
+        let mut team_scores: Vec<(u8, u32)> = state
+            .team_scores
+            .iter()
+            .flatten()
+            .map(|(team, score)| (team.0, *score))
+            .collect();
+        team_scores.sort_unstable();
+        Self {
+            snakes,
+            food: state.arena.food.clone(),
+            team_scores,
+            start_tick: state.tick,
+            captured: Some(Box::new(state.clone())),
+        }
     }
 }
 
@@ -673,6 +1019,8 @@ impl LoadedScenario {
             state: self.initial_state.clone(),
             ai_snakes: self.ai_snakes.clone(),
             ai_sequences: HashMap::new(),
+            deferred_commands: self.deferred_commands.clone(),
+            next_deferred: 0,
             end_tick: self
                 .initial_state
                 .tick
@@ -689,11 +1037,17 @@ impl LoadedScenario {
         Ok(run)
     }
 
+    /// Check every expectation against `run`, which must be a run of this
+    /// scenario: state expectations replay it to their tick. All failures are
+    /// reported together, each with the tick it concerns.
     pub fn assert_expectations(&self, run: &ScenarioRun) -> Result<()> {
+        let mut playback = self.playback();
+        let (start_tick, end_tick) = (playback.start_tick(), playback.end_tick());
+        let mut failures = Vec::new();
         for expected in &self.script.expect {
-            match expected {
-                ScenarioExpectation::SnakeDead { snake_id, at_tick } => ensure!(
-                    run.events.iter().any(|(tick, _, event)| {
+            let failure = match expected {
+                ScenarioExpectation::SnakeDead { snake_id, at_tick } => {
+                    (!run.events.iter().any(|(tick, _, event)| {
                         *tick == *at_tick
                             && matches!(
                                 event,
@@ -702,26 +1056,235 @@ impl LoadedScenario {
                                     ..
                                 } if observed == snake_id
                             )
-                    }),
-                    "expected snake {snake_id} to die at tick {at_tick}"
-                ),
+                    }))
+                    .then(|| format!("tick {at_tick}: expected snake {snake_id} to die"))
+                }
                 ScenarioExpectation::FinalSyncHash(expected) => {
-                    let expected = parse_sync_hash(expected)?;
-                    ensure!(
-                        run.final_state.sync_hash() == expected,
-                        "final sync hash mismatch: expected {expected:#018x}, got {:#018x}",
-                        run.final_state.sync_hash()
-                    );
+                    let final_tick = run.final_state.tick;
+                    match parse_sync_hash(expected) {
+                        Err(error) => Some(format!("final tick {final_tick}: {error:#}")),
+                        Ok(expected) => {
+                            let actual = run.final_state.sync_hash();
+                            (actual != expected).then(|| {
+                                format!(
+                                    "final tick {final_tick}: final sync hash mismatch: \
+                                     expected {expected:#018x}, got {actual:#018x}"
+                                )
+                            })
+                        }
+                    }
                 }
-            }
+                ScenarioExpectation::Event {
+                    kind,
+                    from_tick,
+                    to_tick,
+                    snake_id,
+                    count,
+                } => event_failure(run, kind, *from_tick..=*to_tick, *snake_id, *count),
+                ScenarioExpectation::DeathCause {
+                    snake_id,
+                    cause,
+                    at_tick,
+                } => death_cause_failure(run, *snake_id, cause, *at_tick),
+                ScenarioExpectation::Score { at_tick, .. }
+                | ScenarioExpectation::TeamScore { at_tick, .. }
+                | ScenarioExpectation::SnakeLength { at_tick, .. }
+                | ScenarioExpectation::FoodEaten { at_tick, .. }
+                | ScenarioExpectation::BoostCharge { at_tick, .. } => {
+                    if (start_tick..=end_tick).contains(at_tick) {
+                        // A run that completes early keeps its final state.
+                        playback.seek_to_tick(*at_tick)?;
+                        state_failure(playback.state(), expected)
+                            .map(|failure| format!("tick {at_tick}: {failure}"))
+                    } else {
+                        Some(format!(
+                            "tick {at_tick}: outside the scenario's ticks {start_tick}..={end_tick}"
+                        ))
+                    }
+                }
+            };
+            failures.extend(failure);
         }
+        ensure!(
+            failures.is_empty(),
+            "{} of {} scenario expectations failed:\n  - {}",
+            failures.len(),
+            self.script.expect.len(),
+            failures.join("\n  - ")
+        );
         Ok(())
     }
 }
 
+/// Why a state expectation does not hold of `state`, if it does not.
+fn state_failure(state: &GameState, expected: &ScenarioExpectation) -> Option<String> {
+    let snake = |snake_id: u32| {
+        state
+            .arena
+            .snakes
+            .get(snake_id as usize)
+            .ok_or_else(|| format!("snake {snake_id} does not exist"))
+    };
+    let (what, expected, actual) = match *expected {
+        ScenarioExpectation::Score {
+            snake_id, score, ..
+        } => {
+            if let Err(missing) = snake(snake_id) {
+                return Some(missing);
+            }
+            let actual = state.scores.get(&snake_id).copied().unwrap_or(0);
+            (format!("snake {snake_id} score"), score, actual)
+        }
+        ScenarioExpectation::TeamScore { team_id, score, .. } => {
+            let Some(team_scores) = &state.team_scores else {
+                return Some("team scores are only kept in a team match".into());
+            };
+            let actual = team_scores.get(&TeamId(team_id)).copied().unwrap_or(0);
+            (format!("team {team_id} score"), score, actual)
+        }
+        ScenarioExpectation::SnakeLength {
+            snake_id, length, ..
+        } => match snake(snake_id) {
+            Ok(snake) => (
+                format!("snake {snake_id} length"),
+                length,
+                snake.length() as u32,
+            ),
+            Err(missing) => return Some(missing),
+        },
+        ScenarioExpectation::FoodEaten {
+            snake_id, count, ..
+        } => {
+            if let Err(missing) = snake(snake_id) {
+                return Some(missing);
+            }
+            let actual = state.food_pickups.get(&snake_id).copied().unwrap_or(0);
+            (format!("snake {snake_id} to have eaten"), count, actual)
+        }
+        ScenarioExpectation::BoostCharge {
+            snake_id,
+            charge_ms,
+            ..
+        } => match snake(snake_id) {
+            Ok(snake) => (
+                format!("snake {snake_id} Boost charge (ms)"),
+                charge_ms,
+                snake.boost.charge_ms,
+            ),
+            Err(missing) => return Some(missing),
+        },
+        _ => return None,
+    };
+    (actual != expected).then(|| format!("expected {what} {expected}, got {actual}"))
+}
+
+/// An event's JSON tag and, for variants with fields, those fields.
+fn event_kind(event: &GameEvent) -> Option<(String, Option<serde_json::Value>)> {
+    match serde_json::to_value(event).ok()? {
+        serde_json::Value::String(kind) => Some((kind, None)),
+        serde_json::Value::Object(tagged) => tagged
+            .into_iter()
+            .next()
+            .map(|(kind, fields)| (kind, Some(fields))),
+        _ => None,
+    }
+}
+
+fn event_failure(
+    run: &ScenarioRun,
+    kind: &str,
+    ticks: std::ops::RangeInclusive<u32>,
+    snake_id: Option<u32>,
+    count: Option<u32>,
+) -> Option<String> {
+    let mut matched = 0;
+    let mut seen = BTreeSet::new();
+    for (_, _, event) in run
+        .events
+        .iter()
+        .filter(|(tick, _, _)| ticks.contains(tick))
+    {
+        let Some((observed, fields)) = event_kind(event) else {
+            continue;
+        };
+        let about_snake = snake_id.is_none_or(|snake_id| {
+            fields
+                .as_ref()
+                .and_then(|fields| fields.get("snake_id"))
+                .and_then(serde_json::Value::as_u64)
+                == Some(u64::from(snake_id))
+        });
+        if observed == kind && about_snake {
+            matched += 1;
+        }
+        seen.insert(observed);
+    }
+
+    let range = format!("ticks {}..={}", ticks.start(), ticks.end());
+    let about = snake_id
+        .map(|snake_id| format!(" for snake {snake_id}"))
+        .unwrap_or_default();
+    match count {
+        Some(count) if matched != count => Some(format!(
+            "{range}: expected {count} `{kind}` event(s){about}, got {matched}"
+        )),
+        None if matched == 0 => Some(format!(
+            "{range}: expected a `{kind}` event{about}, saw {}",
+            if seen.is_empty() {
+                "no events".to_owned()
+            } else {
+                seen.into_iter().collect::<Vec<_>>().join(", ")
+            }
+        )),
+        _ => None,
+    }
+}
+
+fn death_cause_failure(
+    run: &ScenarioRun,
+    snake_id: u32,
+    cause: &DeathCause,
+    at_tick: Option<u32>,
+) -> Option<String> {
+    let deaths: Vec<(u32, &DeathCause)> = run
+        .events
+        .iter()
+        .filter_map(|(tick, _, event)| match event {
+            GameEvent::SnakeDied {
+                snake_id: observed,
+                cause,
+            } if *observed == snake_id && at_tick.is_none_or(|at_tick| at_tick == *tick) => {
+                Some((*tick, cause))
+            }
+            _ => None,
+        })
+        .collect();
+    if deaths.iter().any(|(_, observed)| *observed == cause) {
+        return None;
+    }
+
+    let when = at_tick
+        .map(|at_tick| format!("tick {at_tick}"))
+        .unwrap_or_else(|| "any tick".to_owned());
+    let outcome = if deaths.is_empty() {
+        "it did not die".to_owned()
+    } else {
+        deaths
+            .iter()
+            .map(|(tick, observed)| format!("it died of {observed:?} at tick {tick}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    Some(format!(
+        "{when}: expected snake {snake_id} to die of {cause:?}, but {outcome}"
+    ))
+}
+
 fn scenario_dimensions(world: &ScenarioWorld) -> Result<(u16, u16)> {
     let inferred = match &world.game_type {
-        GameType::TeamMatch { .. } => (60, 40),
+        GameType::TeamMatch { .. }
+        | GameType::KingOfTheHill { .. }
+        | GameType::CaptureTheFlag { .. } => (60, 40),
         GameType::Custom { settings } => (settings.arena_width, settings.arena_height),
         _ => (40, 40),
     };
@@ -854,6 +1417,8 @@ mod tests {
                         combo_chain: 0,
                         combo_remaining_ms: 0,
                         driver: ScenarioDriver::Scripted,
+                        team_strategy: None,
+                        policy: None,
                     },
                     ScenarioSnakePose {
                         user_id: 2,
@@ -868,16 +1433,20 @@ mod tests {
                         combo_chain: 0,
                         combo_remaining_ms: 0,
                         driver: ScenarioDriver::Scripted,
+                        team_strategy: None,
+                        policy: None,
                     },
                 ],
                 food: vec![Position { x: 20, y: 20 }],
                 team_scores: Vec::new(),
                 start_tick: 0,
+                captured: None,
             },
             commands: vec![ScenarioCommand {
                 at_tick: 4,
                 user_id: 1,
                 command: ScenarioCommandKind::Turn(Direction::Down),
+                scheduled_at: None,
             }],
             run_ticks: 12,
             presentation: ScenarioPresentation {
@@ -974,6 +1543,128 @@ mod tests {
         assert!(drifting.load().unwrap().run().is_err());
     }
 
+    #[test]
+    fn state_and_event_expectations_hold_at_their_tick() {
+        let script = base_script();
+        let mut playback = script.clone().load().unwrap().playback();
+        playback.seek_to_tick(8).unwrap();
+        let you = &playback.state().arena.snakes[0];
+        let (length, charge_ms) = (you.length() as u32, you.boost.charge_ms);
+
+        let mut expecting = script;
+        expecting.expect = vec![
+            ScenarioExpectation::Score {
+                snake_id: 0,
+                at_tick: 8,
+                score: 0,
+            },
+            ScenarioExpectation::SnakeLength {
+                snake_id: 0,
+                at_tick: 8,
+                length,
+            },
+            ScenarioExpectation::FoodEaten {
+                snake_id: 1,
+                at_tick: 12,
+                count: 0,
+            },
+            ScenarioExpectation::BoostCharge {
+                snake_id: 0,
+                at_tick: 8,
+                charge_ms,
+            },
+            ScenarioExpectation::Event {
+                kind: "SnakeTurned".into(),
+                from_tick: 0,
+                to_tick: 12,
+                snake_id: Some(0),
+                count: Some(1),
+            },
+        ];
+        expecting.load().unwrap().run().unwrap();
+    }
+
+    #[test]
+    fn every_failed_expectation_is_reported_with_its_tick() {
+        let mut script = base_script();
+        script.expect = vec![
+            ScenarioExpectation::Score {
+                snake_id: 0,
+                at_tick: 6,
+                score: 9,
+            },
+            ScenarioExpectation::TeamScore {
+                team_id: 0,
+                at_tick: 6,
+                score: 1,
+            },
+            ScenarioExpectation::SnakeLength {
+                snake_id: 7,
+                at_tick: 3,
+                length: 4,
+            },
+            ScenarioExpectation::FoodEaten {
+                snake_id: 0,
+                at_tick: 99,
+                count: 0,
+            },
+            ScenarioExpectation::Event {
+                kind: "SnakeTurned".into(),
+                from_tick: 0,
+                to_tick: 3,
+                snake_id: Some(0),
+                count: None,
+            },
+            ScenarioExpectation::DeathCause {
+                snake_id: 1,
+                cause: DeathCause::Wall,
+                at_tick: None,
+            },
+            ScenarioExpectation::Event {
+                kind: "SnakeTurned".into(),
+                from_tick: 0,
+                to_tick: 12,
+                snake_id: None,
+                count: None,
+            },
+        ];
+
+        let error = script.load().unwrap().run().unwrap_err().to_string();
+        for expected in [
+            "6 of 7 scenario expectations failed",
+            "tick 6: expected snake 0 score 9, got 0",
+            "tick 6: team scores are only kept in a team match",
+            "tick 3: snake 7 does not exist",
+            "tick 99: outside the scenario's ticks 0..=12",
+            "ticks 0..=3: expected a `SnakeTurned` event for snake 0, saw",
+            "any tick: expected snake 1 to die of Wall, but it did not die",
+        ] {
+            assert!(
+                error.contains(expected),
+                "{expected:?} missing from {error}"
+            );
+        }
+    }
+
+    #[test]
+    fn event_ranges_must_be_named_and_ordered() {
+        let mut script = base_script();
+        script.expect = vec![ScenarioExpectation::Event {
+            kind: "FoodEaten".into(),
+            from_tick: 5,
+            to_tick: 4,
+            snake_id: None,
+            count: None,
+        }];
+        assert!(
+            script
+                .validate()
+                .unwrap_err()
+                .to_string()
+                .contains("is empty")
+        );
+    }
+
     #[test]
     fn malformed_pose_and_command_are_rejected() {
         let mut duplicate = base_script();
@@ -997,6 +1688,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ai_snakes_can_play_by_a_named_policy() {
+        let mut script = base_script();
+        script.commands.clear();
+        script.run_ticks = 40;
+        script.pose.snakes[1].driver = ScenarioDriver::Ai;
+        let turn_only = script.clone().load().unwrap().run().unwrap();
+
+        script.pose.snakes[1].policy = Some("hard".into());
+        let hard = script.clone().load().unwrap().run().unwrap();
+        assert_eq!(
+            hard.final_state.sync_hash(),
+            script
+                .clone()
+                .load()
+                .unwrap()
+                .run()
+                .unwrap()
+                .final_state
+                .sync_hash()
+        );
+        assert_ne!(
+            hard.final_state.sync_hash(),
+            turn_only.final_state.sync_hash()
+        );
+
+        // A caller's registry decides what a name means.
+        let mut policies = BotPolicyRegistry::builtin();
+        policies.register("hard", AiDifficulty::Easy);
+        let renamed = script.clone().load_with_policies(&policies).unwrap();
+        assert_ne!(
+            renamed.run().unwrap().final_state.sync_hash(),
+            hard.final_state.sync_hash()
+        );
+
+        script.pose.snakes[1].policy = Some("minimax".into());
+        let error = format!("{:#}", script.clone().load().unwrap_err());
+        assert!(error.contains("unknown bot policy 'minimax'"), "{error}");
+        script.pose.snakes[1].policy = Some("hard".into());
+        script.pose.snakes[1].driver = ScenarioDriver::Scripted;
+        assert!(
+            script
+                .load()
+                .unwrap_err()
+                .to_string()
+                .contains("policy but no Ai driver")
+        );
+    }
+
     #[test]
     fn every_checked_in_scenario_runs_deterministically() {
         let fixtures = [
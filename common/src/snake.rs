@@ -1,6 +1,6 @@
 use crate::{
     BOOST_TICK_INTERVAL_MS, DEFAULT_BOOST_CAPACITY_MS, DEFAULT_BOOST_SPEED_MILLI,
-    MAX_BOOST_SPEED_MILLI, NORMAL_SNAKE_SPEED_MILLI, TeamId,
+    MAX_BOOST_SPEED_MILLI, NORMAL_SNAKE_SPEED_MILLI, SnakePowerUps, TeamId,
 };
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
@@ -121,6 +121,10 @@ pub struct Snake {
     /// inactive combo, which is the only safe state to infer without events.
     #[serde(default)]
     pub combo: SnakeCombo,
+    /// Power-up effects held by this life. Always inactive in modes without
+    /// power-ups, and omitted when inactive so those snapshots are unchanged.
+    #[serde(default, skip_serializing_if = "SnakePowerUps::is_inactive")]
+    pub power_ups: SnakePowerUps,
 }
 
 fn default_snake_speed_milli() -> u16 {
@@ -149,6 +153,7 @@ impl Snake {
             movement_credit: 0,
             boost: SnakeBoost::default(),
             combo: SnakeCombo::default(),
+            power_ups: SnakePowerUps::default(),
         }
     }
 
@@ -193,6 +198,7 @@ impl Snake {
                 intent: boost_active,
             },
             combo: SnakeCombo::default(),
+            power_ups: SnakePowerUps::default(),
         }
     }
 
@@ -422,6 +428,11 @@ impl Snake {
         self.combo = SnakeCombo::default();
     }
 
+    /// Power-ups belong to one life; a death or respawn drops them all.
+    pub(crate) fn reset_power_ups(&mut self) {
+        self.power_ups = SnakePowerUps::default();
+    }
+
     pub fn head(&self) -> Result<&Position> {
         self.body.first().context("Snake has no head")
     }
@@ -513,6 +524,28 @@ impl Snake {
         }
     }
 
+    /// `step_forward` on a wrapping `width` x `height` field. When the head
+    /// runs off one side, the whole body shifts by the field's size so the
+    /// head comes back in on the opposite side. The body stays one unbroken
+    /// run of cells, which keeps the compressed representation, growth and
+    /// tail movement exactly as on a solid field; the part that has not yet
+    /// crossed simply lies off the field (see `Arena::seam_offsets`).
+    pub fn step_forward_wrapping(&mut self, width: u16, height: u16) {
+        self.step_forward();
+        let Some(head) = self.body.first().copied() else {
+            return;
+        };
+        let (width, height) = (width.max(1) as i16, height.max(1) as i16);
+        let dx = head.x.rem_euclid(width) - head.x;
+        let dy = head.y.rem_euclid(height) - head.y;
+        if dx != 0 || dy != 0 {
+            for point in &mut self.body {
+                point.x += dx;
+                point.y += dy;
+            }
+        }
+    }
+
     pub fn contains_point(&self, point: &Position, skip_head: bool) -> bool {
         self.iter_body().enumerate().any(|(idx, (p1, p2))| {
             if skip_head && idx == 0 && *p1 == *point {
@@ -566,6 +599,7 @@ mod tests {
             movement_credit: 0,
             boost: SnakeBoost::default(),
             combo: SnakeCombo::default(),
+            power_ups: SnakePowerUps::default(),
         }
     }
 
@@ -868,4 +902,26 @@ mod tests {
         );
         assert_eq!(degenerate.travel_direction(), Direction::Down);
     }
+
+    #[test]
+    fn wrapping_step_brings_the_head_back_on_the_far_side_in_one_run() {
+        let mut snake = snake_with_body(
+            vec![Position { x: 39, y: 5 }, Position { x: 36, y: 5 }],
+            Direction::Right,
+        );
+        snake.step_forward_wrapping(40, 40);
+        assert_eq!(
+            snake.body,
+            vec![Position { x: 0, y: 5 }, Position { x: -3, y: 5 }]
+        );
+        assert_eq!(snake.length(), 4);
+        assert_eq!(snake.travel_direction(), Direction::Right);
+
+        snake.direction = Direction::Up;
+        for _ in 0..6 {
+            snake.step_forward_wrapping(40, 40);
+        }
+        assert_eq!(snake.head().unwrap(), &Position { x: 0, y: 39 });
+        assert_eq!(snake.length(), 4);
+    }
 }
@@ -0,0 +1,290 @@
+//! Snapshots sent as changes from an earlier snapshot.
+//!
+//! A resync used to cost a whole `GameState`, although a client that asks for
+//! one usually already holds a snapshot from a few seconds before. When the
+//! client names that snapshot's tick, the gateway can send
+//! `GameEvent::SnapshotDelta` instead: the JSON-level changes that turn the
+//! acknowledged snapshot into the new one, plus the new state's `sync_hash`.
+//!
+//! The changes are computed over the serialized state, the same shape every
+//! snapshot already crosses the wire in, so a field added to `GameState` needs
+//! nothing here. Objects are compared member by member and equal-length arrays
+//! element by element; anything else that differs is replaced whole. The
+//! result is checked against the hash before it replaces anything, so a delta
+//! applied to the wrong base is an error, never a quietly different game.
+
+use crate::game_state::{GameEvent, GameState};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One edit to the serialized state. `path` is a JSON Pointer (RFC 6901);
+/// the empty path is the whole state.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum StateChange {
+    /// Replace the value at `path`, or add it as a new object member.
+    Set {
+        path: String,
+        #[cfg_attr(feature = "ts-gen", ts(type = "unknown"))]
+        value: Value,
+    },
+    /// Remove the object member at `path`.
+    Remove { path: String },
+}
+
+/// Build the `SnapshotDelta` event that turns `base` into `target`.
+pub fn snapshot_delta(base: &GameState, target: &GameState) -> Result<GameEvent> {
+    let base_value = serde_json::to_value(base).context("failed to serialize the base snapshot")?;
+    let target_value =
+        serde_json::to_value(target).context("failed to serialize the target snapshot")?;
+    let mut changes = Vec::new();
+    diff_values(&mut String::new(), &base_value, &target_value, &mut changes);
+    Ok(GameEvent::SnapshotDelta {
+        base_tick: base.tick,
+        changes,
+        hash: target.sync_hash(),
+    })
+}
+
+/// Rebuild the state a `SnapshotDelta` describes from the snapshot it was
+/// computed against. Fails when `base` is not at `base_tick`, when a change
+/// does not fit the base, or when the result does not hash to `hash`.
+pub fn apply_snapshot_delta(
+    base: &GameState,
+    base_tick: u32,
+    changes: &[StateChange],
+    hash: u64,
+) -> Result<GameState> {
+    if base.tick != base_tick {
+        return Err(anyhow::anyhow!(
+            "snapshot delta is based on tick {base_tick}, but the base snapshot is at tick {}",
+            base.tick
+        ));
+    }
+    let mut value = serde_json::to_value(base).context("failed to serialize the base snapshot")?;
+    for change in changes {
+        change.apply(&mut value)?;
+    }
+    let state: GameState =
+        serde_json::from_value(value).context("snapshot delta produced a malformed state")?;
+    let actual = state.sync_hash();
+    if actual != hash {
+        return Err(anyhow::anyhow!(
+            "snapshot delta from tick {base_tick} produced hash {actual:#018x}, expected {hash:#018x}"
+        ));
+    }
+    Ok(state)
+}
+
+impl StateChange {
+    fn apply(&self, root: &mut Value) -> Result<()> {
+        let path = match self {
+            StateChange::Set { path, .. } | StateChange::Remove { path } => path,
+        };
+        if path.is_empty() {
+            return match self {
+                StateChange::Set { value, .. } => {
+                    *root = value.clone();
+                    Ok(())
+                }
+                StateChange::Remove { .. } => Err(anyhow::anyhow!("cannot remove the whole state")),
+            };
+        }
+        let (parent_path, token) = path
+            .rsplit_once('/')
+            .with_context(|| format!("snapshot delta path {path:?} is not a JSON Pointer"))?;
+        let key = unescape_token(token);
+        let parent = root
+            .pointer_mut(parent_path)
+            .with_context(|| format!("snapshot delta path {path:?} is missing from the base"))?;
+
+        match (self, parent) {
+            (StateChange::Set { value, .. }, Value::Object(members)) => {
+                members.insert(key, value.clone());
+            }
+            (StateChange::Remove { .. }, Value::Object(members)) => {
+                members
+                    .remove(&key)
+                    .with_context(|| format!("snapshot delta removes missing member {path:?}"))?;
+            }
+            (StateChange::Set { value, .. }, Value::Array(items)) => {
+                let slot = key
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| items.get_mut(index))
+                    .with_context(|| format!("snapshot delta sets missing element {path:?}"))?;
+                *slot = value.clone();
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "snapshot delta change at {path:?} does not fit the base"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn diff_values(path: &mut String, base: &Value, target: &Value, out: &mut Vec<StateChange>) {
+    if base == target {
+        return;
+    }
+    match (base, target) {
+        (Value::Object(base_members), Value::Object(target_members)) => {
+            for (key, base_member) in base_members {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&escape_token(key));
+                match target_members.get(key) {
+                    Some(target_member) => diff_values(path, base_member, target_member, out),
+                    None => out.push(StateChange::Remove { path: path.clone() }),
+                }
+                path.truncate(len);
+            }
+            for (key, target_member) in target_members {
+                if !base_members.contains_key(key) {
+                    out.push(StateChange::Set {
+                        path: format!("{path}/{}", escape_token(key)),
+                        value: target_member.clone(),
+                    });
+                }
+            }
+        }
+        (Value::Array(base_items), Value::Array(target_items))
+            if base_items.len() == target_items.len() =>
+        {
+            for (index, (base_item, target_item)) in base_items.iter().zip(target_items).enumerate()
+            {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&index.to_string());
+                diff_values(path, base_item, target_item, out);
+                path.truncate(len);
+            }
+        }
+        _ => out.push(StateChange::Set {
+            path: path.clone(),
+            value: target.clone(),
+        }),
+    }
+}
+
+fn escape_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameType, QueueMode};
+
+    fn started_game() -> GameState {
+        let mut game = GameState::new(
+            40,
+            40,
+            GameType::FreeForAll { max_players: 3 },
+            QueueMode::Quickmatch,
+            Some(11),
+            1_700_000_000_000,
+        );
+        game.add_player(1, Some("one".to_owned())).unwrap();
+        game.add_player(2, Some("two/~".to_owned())).unwrap();
+        game.spawn_initial_food();
+        game.status = crate::GameStatus::Started { server_id: 1 };
+        game
+    }
+
+    fn later(game: &GameState, ticks: u32) -> GameState {
+        let mut later = game.clone();
+        for _ in 0..ticks {
+            later.tick_forward(false).unwrap();
+        }
+        later
+    }
+
+    fn parts(event: GameEvent) -> (u32, Vec<StateChange>, u64) {
+        let GameEvent::SnapshotDelta {
+            base_tick,
+            changes,
+            hash,
+        } = event
+        else {
+            panic!("expected a snapshot delta");
+        };
+        (base_tick, changes, hash)
+    }
+
+    #[test]
+    fn a_delta_rebuilds_the_target_snapshot() {
+        let base = started_game();
+        let mut target = later(&base, 12);
+        target.usernames.remove(&2);
+        target.usernames.insert(3, "new~/name".to_owned());
+
+        let (base_tick, changes, hash) = parts(snapshot_delta(&base, &target).unwrap());
+        assert_eq!(base_tick, base.tick);
+        let rebuilt = apply_snapshot_delta(&base, base_tick, &changes, hash).unwrap();
+        assert_eq!(
+            serde_json::to_value(&rebuilt).unwrap(),
+            serde_json::to_value(&target).unwrap()
+        );
+
+        let delta_len = serde_json::to_vec(&changes).unwrap().len();
+        let full_len = serde_json::to_vec(&target).unwrap().len();
+        assert!(delta_len < full_len, "{delta_len} >= {full_len}");
+    }
+
+    #[test]
+    fn a_delta_for_an_unchanged_state_is_empty() {
+        let base = started_game();
+        let (_, changes, hash) = parts(snapshot_delta(&base, &base).unwrap());
+        assert!(changes.is_empty());
+        assert_eq!(hash, base.sync_hash());
+    }
+
+    #[test]
+    fn a_delta_only_applies_to_its_own_base() {
+        let base = started_game();
+        let target = later(&base, 6);
+        let (base_tick, changes, hash) = parts(snapshot_delta(&base, &target).unwrap());
+
+        let other_base = later(&base, 2);
+        assert!(apply_snapshot_delta(&other_base, base_tick, &changes, hash).is_err());
+        // A base that claims the right tick but holds different state is
+        // caught by the fingerprint.
+        let mut forged = base.clone();
+        forged.arena.width += 1;
+        assert!(apply_snapshot_delta(&forged, base_tick, &changes, hash).is_err());
+        assert!(apply_snapshot_delta(&base, base_tick, &changes, hash ^ 1).is_err());
+    }
+
+    #[test]
+    fn changes_that_do_not_fit_the_base_are_errors() {
+        let base = started_game();
+        let hash = base.sync_hash();
+        for change in [
+            StateChange::Remove {
+                path: "/no_such_field".to_owned(),
+            },
+            StateChange::Set {
+                path: "/arena/snakes/99".to_owned(),
+                value: Value::Null,
+            },
+            StateChange::Set {
+                path: "/arena/missing/child".to_owned(),
+                value: Value::Null,
+            },
+            StateChange::Remove {
+                path: String::new(),
+            },
+        ] {
+            assert!(apply_snapshot_delta(&base, base.tick, &[change], hash).is_err());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! The part of the WebSocket protocol a spectator speaks.
+//!
+//! A client that only watches games needs a handful of the server's messages,
+//! and should not have to link the server to get them. These enums mirror
+//! those messages exactly: same tags, same field names, same optional fields
+//! left out when unset. They name only the fields a spectator reads, which is
+//! enough because serde ignores the rest. The server's own tests check that
+//! every frame here means the same thing to its `WSMessage`.
+
+use crate::game_state::GameEventMessage;
+use crate::wire::WireEncoding;
+use serde::{Deserialize, Serialize};
+
+/// Advertised by servers that accept [`SpectateGame`].
+pub const SPECTATE_CAPABILITY: &str = "spectate-v1";
+
+/// Client -> server request to watch a live game without a seat. Any
+/// authenticated identity, guests included, may watch a public game; a
+/// private game also needs its `game_code`. Answered with `SpectatorJoined`,
+/// after which the game's events stream as for a player.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct SpectateGame {
+    pub game_id: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub game_code: Option<String>,
+}
+
+/// What a spectator sends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpectatorRequest {
+    Authenticate {
+        token: String,
+        protocol_version: u16,
+        /// How game events should arrive. Absent means JSON.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encoding: Option<WireEncoding>,
+    },
+    SpectateGame(SpectateGame),
+    /// Restart the event stream from a fresh snapshot, which may arrive as a
+    /// delta against the one at `base_tick`.
+    RequestResync {
+        game_id: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base_tick: Option<u32>,
+    },
+    Ping {
+        client_time: i64,
+    },
+}
+
+/// What a spectator acts on. Anything else the server sends fails to decode
+/// as one of these and can be ignored.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SpectatorUpdate {
+    Authenticated {
+        protocol_version: u16,
+        capabilities: Vec<String>,
+        /// The encoding game events will use, echoed only when one was asked
+        /// for.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        encoding: Option<WireEncoding>,
+    },
+    SpectatorJoined,
+    GameEvent(GameEventMessage),
+    Pong {
+        client_time: i64,
+        server_time: i64,
+    },
+    /// The game exists, but the server is not ready to stream it yet; ask
+    /// again after `retry_after_ms`.
+    GameWarming {
+        game_id: u32,
+        retry_after_ms: u64,
+    },
+    GameLoadFailed {
+        game_id: u32,
+        reason: String,
+    },
+    AccessDenied {
+        reason: String,
+    },
+    /// The server is shutting down; reconnect to the same URL.
+    Drain {
+        deadline_unix_ms: i64,
+    },
+}
@@ -0,0 +1,196 @@
+//! WebSocket frame encodings.
+//!
+//! Every frame starts out as JSON text. A client may ask for `MessagePack`
+//! while it authenticates, and from then on the server sends its `GameEvent`
+//! frames, snapshots included, as binary MessagePack. Those frames are almost
+//! all of a match's traffic, and a full `GameState` snapshot comes out about a
+//! third smaller. Control frames are small and stay JSON, as does everything the
+//! client sends.
+//!
+//! The binary frame has exactly the shape of the JSON one: the externally
+//! tagged `{"GameEvent": GameEventMessage}` envelope, with structs encoded as
+//! maps keyed by field name. Field names cost some bytes, but they keep
+//! `#[serde(default)]` and `skip_serializing_if` working, which a positional
+//! format like bincode cannot, so state fields can keep being added without a
+//! second encoding to version.
+
+use crate::game_state::GameEventMessage;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// The gameplay protocol spoken over the WebSocket. The server refuses any
+/// client that reports a different one.
+///
+/// Version 15 covers the game modes and match state that reached the wire
+/// without a bump of their own: best-of-N rounds, power-ups, the shrinking
+/// zone, wraparound arenas, king of the hill, capture the flag, death food, AI
+/// seats and autopilot, and spectating. The earliest of them, rounds, landed
+/// before versions 13 and 14, so a client built for 14 would pass the equality
+/// check against a server that sends events it has never heard of.
+///
+/// Version 14 adds `GameEvent::SnapshotDelta`. A `RequestResync` may name the
+/// tick of the snapshot the client holds, and the fresh snapshot can then
+/// arrive as the changes from that one.
+///
+/// Version 13 lets `Authenticate` ask for MessagePack game events. A connection
+/// that does gets every `GameEvent` frame as a binary frame; everything else
+/// stays JSON text.
+///
+/// Version 12 adds the rematch: an opt-in on the results card that converges
+/// everyone who ticks it onto one lobby, plus the live roster of who is still
+/// looking at that card.
+///
+/// Version 11 adds the social layer: a per-region online-player roster and
+/// player-to-player challenges, both server-pushed. It also makes finished
+/// matches publicly addressable, though that surface is HTTP and does not
+/// cross this socket.
+///
+/// Version 10 is the same kind of collision as version 7 above, and is
+/// resolved the same way. Two branches each shipped a wire change and each
+/// independently claimed 9; git merged the constants silently, so 9 would
+/// have named two mutually unintelligible protocols. Version 10 carries
+/// both: deterministic death causes on `SnakeDied` events, and the
+/// server-coordinated, provider-neutral lobby ad-break barrier with runtime
+/// advertisement configuration and per-session distribution routing.
+///
+/// Version 8 adds authoritative combo configuration/state and enriched food
+/// events so clients can predict growth and render the same food value.
+///
+/// Version 7 carries the pre-match readiness gate and the inactivity protocol
+/// together. Each shipped a wire change and each independently claimed version
+/// 6, so 6 would now name two mutually unintelligible protocols: a client
+/// built for either one would pass this hard-cutover equality check and then
+/// fail to understand half the messages it receives. This must stay in lockstep
+/// with `GAMEPLAY_PROTOCOL_VERSION` in client/web/constants.ts and with
+/// [`GAMEPLAY_REPLAY_VERSION`](crate::GAMEPLAY_REPLAY_VERSION); the server, bot
+/// and loadtest clients all read this constant so they cannot drift at all.
+pub const WS_PROTOCOL_VERSION: u16 = 15;
+
+/// How the server encodes `GameEvent` frames for one connection.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub enum WireEncoding {
+    /// JSON text frames, the only encoding before protocol 13.
+    #[default]
+    Json,
+    /// Binary MessagePack frames for game events.
+    MessagePack,
+}
+
+#[derive(Serialize)]
+enum OutboundFrame<'a> {
+    GameEvent(&'a GameEventMessage),
+}
+
+#[derive(Deserialize)]
+enum InboundFrame {
+    GameEvent(GameEventMessage),
+}
+
+/// Encode a game event as the binary frame a `MessagePack` connection gets.
+pub fn encode_game_event_frame(message: &GameEventMessage) -> Result<Vec<u8>> {
+    rmp_serde::to_vec_named(&OutboundFrame::GameEvent(message))
+        .context("failed to encode a MessagePack game event frame")
+}
+
+/// Encode a game event as the JSON text frame, the same bytes serializing the
+/// server's `WSMessage::GameEvent` produces, without cloning into one.
+pub fn encode_game_event_text(message: &GameEventMessage) -> Result<String> {
+    serde_json::to_string(&OutboundFrame::GameEvent(message))
+        .context("failed to encode a JSON game event frame")
+}
+
+/// Decode a binary frame produced by [`encode_game_event_frame`].
+pub fn decode_game_event_frame(frame: &[u8]) -> Result<GameEventMessage> {
+    let InboundFrame::GameEvent(message) =
+        rmp_serde::from_slice(frame).context("failed to decode a MessagePack game event frame")?;
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameEvent, GameState, GameType, QueueMode};
+
+    fn snapshot_message() -> GameEventMessage {
+        let mut game = GameState::new(
+            60,
+            40,
+            GameType::KingOfTheHill { per_team: 1 },
+            QueueMode::Competitive,
+            Some(u64::MAX - 7),
+            1_700_000_000_000,
+        );
+        game.add_player(1, Some("left".to_owned())).unwrap();
+        game.add_player(2, Some("right".to_owned())).unwrap();
+        game.spawn_initial_food();
+        GameEventMessage {
+            game_id: 42,
+            tick: game.tick,
+            sequence: u64::MAX,
+            stream_seq: 9,
+            user_id: Some(2),
+            event: GameEvent::Snapshot { game_state: game },
+        }
+    }
+
+    #[test]
+    fn binary_frames_decode_to_the_same_message_as_json() {
+        let message = snapshot_message();
+        let frame = encode_game_event_frame(&message).unwrap();
+        let decoded = decode_game_event_frame(&frame).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&message).unwrap()
+        );
+
+        let json = serde_json::to_string(&serde_json::json!({ "GameEvent": message })).unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&encode_game_event_text(&message).unwrap())
+                .unwrap(),
+            serde_json::json!({ "GameEvent": message })
+        );
+        assert!(
+            frame.len() < json.len(),
+            "{} bytes of MessagePack against {} of JSON",
+            frame.len(),
+            json.len()
+        );
+    }
+
+    #[test]
+    fn snapshot_deltas_survive_the_binary_frame() {
+        let message = snapshot_message();
+        let GameEvent::Snapshot { game_state: base } = &message.event else {
+            unreachable!();
+        };
+        let mut target = base.clone();
+        target.usernames.insert(3, "late".to_owned());
+        target.tick += 4;
+        let delta = GameEventMessage {
+            tick: target.tick,
+            event: crate::snapshot_delta(base, &target).unwrap(),
+            ..message.clone()
+        };
+
+        let decoded = decode_game_event_frame(&encode_game_event_frame(&delta).unwrap()).unwrap();
+        let GameEvent::SnapshotDelta {
+            base_tick,
+            changes,
+            hash,
+        } = decoded.event
+        else {
+            panic!("expected a snapshot delta");
+        };
+        let rebuilt = crate::apply_snapshot_delta(base, base_tick, &changes, hash).unwrap();
+        assert_eq!(rebuilt.sync_hash(), target.sync_hash());
+    }
+
+    #[test]
+    fn a_truncated_frame_is_an_error() {
+        let frame = encode_game_event_frame(&snapshot_message()).unwrap();
+        assert!(decode_game_event_frame(&frame[..frame.len() / 2]).is_err());
+        assert!(decode_game_event_frame(b"{\"GameEvent\":{}}").is_err());
+    }
+}
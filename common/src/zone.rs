@@ -0,0 +1,250 @@
+//! The shrinking zone of battle royale free-for-all.
+//!
+//! A battle royale match starts on the whole field. After a grace period the
+//! playable area loses one ring of cells from every side at a fixed cadence
+//! until nothing is left, and a snake whose head is outside the zone dies. The
+//! last snake alive wins.
+//!
+//! The zone is a pure function of the tick. `GameState::tick_forward` applies
+//! each contraction in the movement path, so the client's prediction in
+//! `GameEngine` draws the same ring on the same tick as the server without
+//! waiting for it, and a replayed recording redraws it exactly.
+//!
+//! Only Custom free-for-all lobbies can opt in (see `zone_config_for`). Every
+//! other match carries no config and no zone, so its snapshots, recordings and
+//! fingerprints are byte-identical to the ones written before the zone existed.
+
+use crate::constants::{
+    DEFAULT_ZONE_SHRINK_INTERVAL_MS, DEFAULT_ZONE_START_DELAY_MS, MAX_ZONE_START_DELAY_MS,
+    MIN_ZONE_SHRINK_INTERVAL_MS, ZONE_RULES_VERSION,
+};
+use crate::game_state::{GameMode, GameType};
+use crate::snake::Position;
+use anyhow::{Result, ensure};
+use serde::{Deserialize, Serialize};
+
+/// Zone schedule snapshotted into a match so simulation, prediction and
+/// replay never depend on process configuration.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct ZoneConfig {
+    /// Simulation time the whole field stays open.
+    pub start_delay_ms: u32,
+    /// Simulation time between two contractions once the zone is moving.
+    pub shrink_interval_ms: u32,
+    pub rules_version: u16,
+}
+
+impl Default for ZoneConfig {
+    fn default() -> Self {
+        Self {
+            start_delay_ms: DEFAULT_ZONE_START_DELAY_MS,
+            shrink_interval_ms: DEFAULT_ZONE_SHRINK_INTERVAL_MS,
+            rules_version: ZONE_RULES_VERSION,
+        }
+    }
+}
+
+impl ZoneConfig {
+    pub fn validate(&self) -> Result<()> {
+        ensure!(
+            self.start_delay_ms <= MAX_ZONE_START_DELAY_MS,
+            "zone start_delay_ms must be at most {MAX_ZONE_START_DELAY_MS}, got {}",
+            self.start_delay_ms
+        );
+        ensure!(
+            self.shrink_interval_ms >= MIN_ZONE_SHRINK_INTERVAL_MS,
+            "zone shrink_interval_ms must be at least {MIN_ZONE_SHRINK_INTERVAL_MS}, got {}",
+            self.shrink_interval_ms
+        );
+        ensure!(
+            self.rules_version == ZONE_RULES_VERSION,
+            "unsupported zone rules version {}, expected {}",
+            self.rules_version,
+            ZONE_RULES_VERSION
+        );
+        Ok(())
+    }
+
+    /// Rings the zone has lost after `elapsed_ms` of simulation. The first
+    /// ring goes the moment the grace period ends.
+    pub fn rings_closed_at(&self, elapsed_ms: u64) -> u64 {
+        let delay_ms = u64::from(self.start_delay_ms);
+        if elapsed_ms < delay_ms {
+            return 0;
+        }
+        (elapsed_ms - delay_ms) / u64::from(self.shrink_interval_ms.max(1)) + 1
+    }
+
+    /// The zone on a `width` x `height` field after `tick` quanta of
+    /// `tick_duration_ms`.
+    pub fn bounds_at(
+        &self,
+        width: u16,
+        height: u16,
+        tick: u32,
+        tick_duration_ms: u32,
+    ) -> ZoneBounds {
+        let elapsed_ms = u64::from(tick) * u64::from(tick_duration_ms.max(1));
+        ZoneBounds::inset(width, height, self.rings_closed_at(elapsed_ms))
+    }
+}
+
+/// The playable rectangle, as half-open cell ranges `[min_x, max_x)` and
+/// `[min_y, max_y)`. Once either range is empty the zone has closed and no
+/// cell is safe.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
+#[cfg_attr(feature = "ts-gen", ts(export))]
+pub struct ZoneBounds {
+    pub min_x: i16,
+    pub min_y: i16,
+    pub max_x: i16,
+    pub max_y: i16,
+}
+
+impl ZoneBounds {
+    /// The whole field.
+    pub fn full(width: u16, height: u16) -> Self {
+        Self::inset(width, height, 0)
+    }
+
+    /// The field with `rings` cells taken from every side. Rings stop counting
+    /// once the shorter side is used up, so a closed zone has exactly one
+    /// representation however long the match runs on.
+    pub fn inset(width: u16, height: u16, rings: u64) -> Self {
+        let max_rings = width.min(height).div_ceil(2);
+        let rings = rings.min(u64::from(max_rings)) as u16;
+        let min_x = rings.min(width / 2);
+        let min_y = rings.min(height / 2);
+        Self {
+            min_x: min_x as i16,
+            min_y: min_y as i16,
+            max_x: width.saturating_sub(rings).max(min_x) as i16,
+            max_y: height.saturating_sub(rings).max(min_y) as i16,
+        }
+    }
+
+    pub fn contains(&self, position: &Position) -> bool {
+        position.x >= self.min_x
+            && position.x < self.max_x
+            && position.y >= self.min_y
+            && position.y < self.max_y
+    }
+
+    /// Whether every cell of `other` is also inside `self`. A closed zone is
+    /// inside every zone.
+    pub fn encloses(&self, other: &ZoneBounds) -> bool {
+        other.is_closed()
+            || (self.min_x <= other.min_x
+                && self.min_y <= other.min_y
+                && self.max_x >= other.max_x
+                && self.max_y >= other.max_y)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.min_x >= self.max_x || self.min_y >= self.max_y
+    }
+}
+
+/// Which zone schedule a mode plays with, if any.
+///
+/// Only Custom free-for-all lobbies can opt in. A zone needs rivals to squeeze
+/// together, and matchmade modes are ranked or feed leaderboards, so they keep
+/// exactly the rules players already know.
+pub fn zone_config_for(game_type: &GameType) -> Option<ZoneConfig> {
+    match game_type {
+        GameType::Custom { settings }
+            if settings.shrinking_zone
+                && matches!(settings.game_mode, GameMode::FreeForAll { .. }) =>
+        {
+            Some(ZoneConfig::default())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CustomGameSettings;
+
+    #[test]
+    fn only_custom_free_for_all_lobbies_that_ask_for_a_zone_get_one() {
+        let battle_royale = CustomGameSettings {
+            shrinking_zone: true,
+            ..CustomGameSettings::default()
+        };
+        assert_eq!(
+            zone_config_for(&GameType::Custom {
+                settings: battle_royale.clone()
+            }),
+            Some(ZoneConfig::default())
+        );
+        assert_eq!(
+            zone_config_for(&GameType::Custom {
+                settings: CustomGameSettings {
+                    game_mode: GameMode::Duel,
+                    ..battle_royale
+                }
+            }),
+            None
+        );
+        for ranked in [
+            GameType::Solo,
+            GameType::TeamMatch { per_team: 1 },
+            GameType::FreeForAll { max_players: 4 },
+            GameType::Custom {
+                settings: CustomGameSettings::default(),
+            },
+        ] {
+            assert_eq!(zone_config_for(&ranked), None, "{ranked:?}");
+        }
+    }
+
+    #[test]
+    fn the_zone_holds_through_the_grace_period_then_loses_a_ring_per_interval() {
+        let config = ZoneConfig::default();
+        let tick_ms = 100;
+        let grace_ticks = config.start_delay_ms / tick_ms;
+        let interval_ticks = config.shrink_interval_ms / tick_ms;
+
+        assert_eq!(
+            config.bounds_at(40, 40, 0, tick_ms),
+            ZoneBounds::full(40, 40)
+        );
+        assert_eq!(
+            config.bounds_at(40, 40, grace_ticks - 1, tick_ms),
+            ZoneBounds::full(40, 40)
+        );
+        assert_eq!(
+            config.bounds_at(40, 40, grace_ticks, tick_ms),
+            ZoneBounds::inset(40, 40, 1)
+        );
+        assert_eq!(
+            config.bounds_at(40, 40, grace_ticks + interval_ticks, tick_ms),
+            ZoneBounds {
+                min_x: 2,
+                min_y: 2,
+                max_x: 38,
+                max_y: 38
+            }
+        );
+    }
+
+    #[test]
+    fn the_zone_closes_on_the_shorter_side_and_then_stops_changing() {
+        let last_open = ZoneBounds::inset(41, 20, 9);
+        assert!(!last_open.is_closed());
+        assert!(last_open.contains(&Position { x: 9, y: 9 }));
+        assert!(!last_open.contains(&Position { x: 9, y: 11 }));
+
+        let closed = ZoneBounds::inset(41, 20, 10);
+        assert!(closed.is_closed());
+        assert!(!closed.contains(&Position { x: 20, y: 10 }));
+        assert_eq!(ZoneBounds::inset(41, 20, 500), closed);
+        assert!(last_open.encloses(&closed));
+        assert!(!closed.encloses(&last_open));
+    }
+}
@@ -0,0 +1,57 @@
+//! Generated scenarios must reach the same state live, from a restored
+//! snapshot, and by event replay.
+//!
+//! `SNAKETRON_FUZZ_CASES` and `SNAKETRON_FUZZ_SEED` widen or move the search,
+//! for example `SNAKETRON_FUZZ_CASES=5000 cargo test -p common --test
+//! determinism_fuzz`. A failure is shrunk and written out as a scenario file
+//! that `ScenarioScript::from_json` loads, so it can be checked in as a
+//! regression test once the engine is fixed.
+
+use common::fuzz::{FuzzConfig, fuzz};
+use std::path::Path;
+
+fn env_number(name: &str) -> Option<u64> {
+    let value = std::env::var(name).ok()?;
+    let value = value.trim();
+    let parsed = match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    Some(parsed.unwrap_or_else(|_| panic!("{name} must be a number, got {value:?}")))
+}
+
+#[test]
+fn generated_scenarios_agree_across_live_play_restore_and_replay() {
+    let defaults = FuzzConfig::default();
+    let config = FuzzConfig {
+        seed: env_number("SNAKETRON_FUZZ_SEED").unwrap_or(defaults.seed),
+        cases: env_number("SNAKETRON_FUZZ_CASES").map_or(64, |cases| {
+            u32::try_from(cases).expect("case count fits u32")
+        }),
+        ..defaults
+    };
+    let report = fuzz(&config).expect("fuzz harness runs");
+
+    if let Some(failure) = report.failure {
+        let path = Path::new(env!("CARGO_TARGET_TMPDIR"))
+            .join(format!("{}-minimal.json", failure.original.id));
+        std::fs::write(
+            &path,
+            serde_json::to_string_pretty(&failure.minimal).expect("scenario serializes"),
+        )
+        .expect("write minimal scenario");
+        panic!(
+            "case {} (seed {:#x}) {}; minimal scenario written to {}",
+            failure.case,
+            config.seed.wrapping_add(u64::from(failure.case)),
+            failure.divergence,
+            path.display()
+        );
+    }
+    assert!(
+        report.cases_discarded * 4 <= config.cases,
+        "{} of {} generated scenarios failed to load",
+        report.cases_discarded,
+        config.cases
+    );
+}
@@ -906,6 +906,7 @@ pub async fn run_match_group(
     let preferences = LobbyPreferences {
         selected_modes: vec![settings.selected_mode.clone()],
         competitive: settings.competitive,
+        ai_opponents: None,
     };
     let lobby_code = sessions[0]
         .record
@@ -1089,6 +1090,7 @@ async fn hold_population_session_inner(
     let preferences = LobbyPreferences {
         selected_modes: vec![settings.selected_mode.clone()],
         competitive: settings.competitive,
+        ai_opponents: None,
     };
 
     loop {
@@ -1445,6 +1447,7 @@ async fn prepare_pre_game_candidate(
             protocol_version: CLIENT_PROTOCOL_VERSION,
             anon_id: None,
             distribution: None,
+            encoding: None,
         },
         cancellation,
     )
@@ -1467,6 +1470,7 @@ async fn prepare_pre_game_candidate(
                 protocol_version: CLIENT_PROTOCOL_VERSION,
                 capabilities,
                 socket_generation,
+                ..
             } => {
                 let capabilities: BTreeSet<_> = capabilities.into_iter().collect();
                 validate_required_server_capabilities(
@@ -1905,6 +1909,7 @@ async fn authenticate_initial_admission_attempt(
                 protocol_version: CLIENT_PROTOCOL_VERSION,
                 anon_id: None,
                 distribution: None,
+                encoding: None,
             },
             cancellation,
         ),
@@ -2252,6 +2257,7 @@ async fn prepare_lobby(
     let preferences = LobbyPreferences {
         selected_modes: vec![settings.selected_mode.clone()],
         competitive: settings.competitive,
+        ai_opponents: None,
     };
     send_preferences_with_recovery(
         &mut sessions[0],
@@ -2425,6 +2431,7 @@ fn preferences_message(preferences: &LobbyPreferences) -> WSMessage {
     WSMessage::UpdateLobbyPreferences {
         selected_modes: preferences.selected_modes.clone(),
         competitive: preferences.competitive,
+        ai_opponents: preferences.ai_opponents,
     }
 }
 
@@ -2873,7 +2880,13 @@ async fn enforce_game_sync_health(
     // engine while its authoritative replacement snapshot is in flight.
     runtime.engine.clear_needs_resync();
     if let Err(error) = session
-        .send_cancellable(WSMessage::RequestResync { game_id }, cancellation)
+        .send_cancellable(
+            WSMessage::RequestResync {
+                game_id,
+                base_tick: None,
+            },
+            cancellation,
+        )
         .await
     {
         let snapshot_complete = synchronize_game_runtime(
@@ -4056,6 +4069,7 @@ async fn prepare_planned_candidate(
             protocol_version: CLIENT_PROTOCOL_VERSION,
             anon_id: None,
             distribution: None,
+            encoding: None,
         },
         cancellation,
     )
@@ -4066,6 +4080,7 @@ async fn prepare_planned_candidate(
     let preferences = LobbyPreferences {
         selected_modes: vec![settings.selected_mode.clone()],
         competitive: settings.competitive,
+        ai_opponents: None,
     };
     let mut authenticated = false;
     let mut capabilities = BTreeSet::new();
@@ -4262,6 +4277,7 @@ async fn prepare_planned_candidate(
                         protocol_version: CLIENT_PROTOCOL_VERSION,
                         capabilities: candidate_capabilities,
                         socket_generation: candidate_socket_generation,
+                        ..
                     } => {
                         if authenticated {
                             continue;
@@ -4353,7 +4369,10 @@ async fn prepare_planned_candidate(
                                 send_candidate_message(
                                     session,
                                     &mut socket,
-                                    WSMessage::RequestResync { game_id },
+                                    WSMessage::RequestResync {
+                                        game_id,
+                                        base_tick: None,
+                                    },
                                     cancellation,
                                 )
                                 .await
@@ -4866,6 +4885,7 @@ async fn wait_for_match_with_recovery(
     let preferences = LobbyPreferences {
         selected_modes: vec![settings.selected_mode.clone()],
         competitive: settings.competitive,
+        ai_opponents: None,
     };
     let deadline = tokio::time::Instant::now() + timeout;
     let mut group_game_updates = group_game_id.subscribe();
@@ -5823,6 +5843,7 @@ impl LiveSession {
                 protocol_version: CLIENT_PROTOCOL_VERSION,
                 anon_id: None,
                 distribution: None,
+                encoding: None,
             },
             cancellation,
         )
@@ -5855,6 +5876,7 @@ impl LiveSession {
                     protocol_version,
                     capabilities,
                     socket_generation,
+                    ..
                 } => {
                     if protocol_version != CLIENT_PROTOCOL_VERSION {
                         return Err(anyhow!(
@@ -7774,7 +7796,7 @@ mod tests {
         let request = server_socket.next().await.unwrap().unwrap();
         assert!(matches!(
             serde_json::from_str::<WSMessage>(request.to_text().unwrap()).unwrap(),
-            WSMessage::RequestResync { game_id: 42 }
+            WSMessage::RequestResync { game_id: 42, .. }
         ));
 
         // Re-observing the same engine flag while the snapshot is in flight
@@ -8122,6 +8144,7 @@ mod tests {
         let preferences = LobbyPreferences {
             selected_modes: vec!["solo".to_owned()],
             competitive: false,
+            ai_opponents: None,
         };
         let server_preferences = preferences.clone();
         let recovery_server = tokio::spawn(async move {
@@ -8136,6 +8159,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if value == "test-token"
             ));
             socket
@@ -8148,6 +8172,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 2,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -8288,6 +8313,7 @@ mod tests {
         let preferences = LobbyPreferences {
             selected_modes: vec!["duel".to_owned()],
             competitive: false,
+            ai_opponents: None,
         };
         let server_preferences = preferences.clone();
         let server = tokio::spawn(async move {
@@ -8310,6 +8336,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if value == "test-token"
             ));
             replacement
@@ -8322,6 +8349,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 2,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -8480,6 +8508,7 @@ mod tests {
         let preferences = LobbyPreferences {
             selected_modes: vec!["duel".to_owned()],
             competitive: false,
+            ai_opponents: None,
         };
         let server_preferences = preferences.clone();
         let server = tokio::spawn(async move {
@@ -8511,6 +8540,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if value == "test-token"
             ));
             candidate_socket
@@ -8523,6 +8553,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 2,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -8676,6 +8707,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if value == "test-token"
             ));
             candidate_socket
@@ -8688,6 +8720,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 2,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -8775,6 +8808,7 @@ mod tests {
         let preferences = LobbyPreferences {
             selected_modes: vec!["2v2".to_owned()],
             competitive: false,
+            ai_opponents: None,
         };
         let server_preferences = preferences.clone();
         let server = tokio::spawn(async move {
@@ -8791,6 +8825,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if value == "test-token"
             ));
             candidate_socket
@@ -8803,6 +8838,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 2,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -8969,6 +9005,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if value == "test-token"
             ));
 
@@ -8999,6 +9036,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 2,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -9654,6 +9692,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if token == "stable-token"
             ));
             drop(first_socket);
@@ -9668,6 +9707,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if token == "stable-token"
             ));
             draining_socket
@@ -9680,6 +9720,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 1,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
@@ -9719,6 +9760,7 @@ mod tests {
                     protocol_version: CLIENT_PROTOCOL_VERSION,
                     anon_id: None,
                     distribution: None,
+                    encoding: None,
                 } if token == "stable-token"
             ));
             replacement_socket
@@ -9731,6 +9773,7 @@ mod tests {
                             .map(|capability| (*capability).to_owned())
                             .collect(),
                         socket_generation: 1,
+                        encoding: None,
                     })
                     .unwrap(),
                 ))
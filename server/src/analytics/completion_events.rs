@@ -159,6 +159,7 @@ mod tests {
             recording_canonical_bytes: None,
             recording_journal: None,
             play_of_the_game: None,
+            highlight_reel: None,
             final_state: state,
             effects,
         }
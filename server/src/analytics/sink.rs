@@ -661,6 +661,7 @@ mod tests {
 
     fn queued_lobby(game_types: Vec<GameType>, pool: MatchmakingPool) -> QueuedLobby {
         QueuedLobby {
+            ai_opponents: None,
             queue_identity_json: None,
             lobby_code: "ABCDEF".to_owned(),
             queue_token: "token".to_owned(),
@@ -704,6 +705,7 @@ mod tests {
             recording_canonical_bytes: None,
             recording_journal: None,
             play_of_the_game: None,
+            highlight_reel: None,
             final_state: state,
             effects: Vec::new(),
         };
@@ -428,6 +428,7 @@ mod tests {
             team_id: None,
             score: 10,
             team_score: None,
+            rounds_won: None,
             xp_gained: 5,
             mmr_delta: Some(2),
             outcome: outcome.to_string(),
@@ -719,6 +719,7 @@ mod tests {
             team_id,
             score,
             team_score,
+            rounds_won: None,
             xp_gained: 40,
             mmr_delta: Some(12),
             outcome: outcome.to_string(),
@@ -18,7 +18,7 @@ use common::{GameType, QueueMode};
 pub struct LeaderboardQuery {
     /// Queue mode: "quickmatch" or "competitive"
     pub queue_mode: String,
-    /// Game type: "solo", "duel", "2v2", "ffa"
+    /// Game type: "solo", "duel", "2v2", "ffa", "hill", "hill-2v2", "ctf", "ctf-2v2"
     pub game_type: String,
     /// Season (optional, defaults to current season)
     pub season: Option<Season>,
@@ -122,7 +122,7 @@ pub struct LeaderboardState {
 ///
 /// Query parameters:
 /// - queue_mode: "quickmatch" or "competitive"
-/// - game_type: "solo", "duel", "2v2", "ffa"
+/// - game_type: "solo", "duel", "2v2", "ffa", "hill", "hill-2v2", "ctf", "ctf-2v2"
 /// - season: optional, defaults to current season
 /// - limit: optional, defaults to 25, max 100
 /// - offset: optional, defaults to 0
@@ -156,6 +156,10 @@ pub async fn get_leaderboard(
         "duel" | "1v1" => GameType::TeamMatch { per_team: 1 },
         "2v2" => GameType::TeamMatch { per_team: 2 },
         "ffa" | "free-for-all" => GameType::FreeForAll { max_players: 8 },
+        "hill" | "koth" => GameType::KingOfTheHill { per_team: 1 },
+        "hill-2v2" => GameType::KingOfTheHill { per_team: 2 },
+        "ctf" => GameType::CaptureTheFlag { per_team: 1 },
+        "ctf-2v2" => GameType::CaptureTheFlag { per_team: 2 },
         _ => {
             warn!("Invalid game_type: {}, defaulting to Solo", query.game_type);
             GameType::Solo
@@ -464,6 +468,10 @@ async fn read_user_ranking(
         "duel" | "1v1" => GameType::TeamMatch { per_team: 1 },
         "2v2" => GameType::TeamMatch { per_team: 2 },
         "ffa" | "free-for-all" => GameType::FreeForAll { max_players: 8 },
+        "hill" | "koth" => GameType::KingOfTheHill { per_team: 1 },
+        "hill-2v2" => GameType::KingOfTheHill { per_team: 2 },
+        "ctf" => GameType::CaptureTheFlag { per_team: 1 },
+        "ctf-2v2" => GameType::CaptureTheFlag { per_team: 2 },
         _ => return Err(StatusCode::BAD_REQUEST),
     };
 
@@ -284,7 +284,10 @@ impl ModeKey {
             GameType::TeamMatch { per_team: 1 } => Some(Self::Duel),
             GameType::TeamMatch { per_team: 2 } => Some(Self::TwoVsTwo),
             GameType::FreeForAll { .. } => Some(Self::FreeForAll),
-            GameType::TeamMatch { .. } | GameType::Custom { .. } => None,
+            GameType::TeamMatch { .. }
+            | GameType::KingOfTheHill { .. }
+            | GameType::CaptureTheFlag { .. }
+            | GameType::Custom { .. } => None,
         }
     }
 
@@ -401,7 +404,9 @@ fn observations_from_games(games: &[Game], season: Season) -> Vec<MetricObservat
                     duration_ms,
                 ))
             }
-            GameType::Custom { .. } => {}
+            GameType::KingOfTheHill { .. }
+            | GameType::CaptureTheFlag { .. }
+            | GameType::Custom { .. } => {}
         }
 
         if duration_ms >= MIN_RATE_DURATION_MS {
@@ -1515,6 +1520,7 @@ mod tests {
             game_code: None,
             replay_object: None,
             play_of_the_game: None,
+            highlight_reel: None,
             news_eligible,
         }
     }
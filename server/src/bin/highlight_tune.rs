@@ -23,6 +23,8 @@ struct Cli {
     games: usize,
     seed: u64,
     review_count: usize,
+    policy: Option<String>,
+    search_budget_ticks: Option<u32>,
     recordings: Vec<PathBuf>,
 }
 
@@ -35,6 +37,8 @@ impl Cli {
             games: DEFAULT_CORPUS_GAME_COUNT,
             seed: DEFAULT_CORPUS_SEED,
             review_count: DEFAULT_REVIEW_COUNT,
+            policy: None,
+            search_budget_ticks: None,
             recordings: Vec::new(),
         };
         while let Some(arg) = args.next() {
@@ -53,6 +57,23 @@ impl Cli {
                     cli.review_count =
                         parse_usize(next_arg(&mut args, "--review-count")?, "--review-count")?
                 }
+                Some("--policy") => {
+                    let policy = next_arg(&mut args, "--policy")?;
+                    cli.policy = Some(
+                        policy
+                            .to_str()
+                            .context("--policy must be UTF-8")?
+                            .to_owned(),
+                    );
+                }
+                Some("--search-budget-ticks") => {
+                    let ticks = parse_usize(
+                        next_arg(&mut args, "--search-budget-ticks")?,
+                        "--search-budget-ticks",
+                    )?;
+                    cli.search_budget_ticks =
+                        Some(u32::try_from(ticks).context("--search-budget-ticks exceeds u32")?);
+                }
                 Some("--help" | "-h") => {
                     println!("{}", usage());
                     std::process::exit(0);
@@ -75,7 +96,7 @@ impl Cli {
 }
 
 fn usage() -> &'static str {
-    "Usage:\n  highlight_tune [--config CONFIG.json] RECORDING.json...\n  highlight_tune --bot-corpus-dir OUTPUT [--games 200] [--seed U64|0xHEX] [--review-count 20] [--config CONFIG.json]"
+    "Usage:\n  highlight_tune [--config CONFIG.json] RECORDING.json...\n  highlight_tune --bot-corpus-dir OUTPUT [--games 200] [--seed U64|0xHEX] [--review-count 20] [--policy NAME] [--search-budget-ticks N] [--config CONFIG.json]"
 }
 
 fn next_arg(args: &mut impl Iterator<Item = OsString>, flag: &str) -> Result<OsString> {
@@ -191,6 +212,8 @@ fn main() -> Result<()> {
                 games: cli.games,
                 seed_base: cli.seed,
                 review_count: cli.review_count,
+                policy: cli.policy,
+                search_budget_ticks: cli.search_budget_ticks,
             },
             &config,
         )?;
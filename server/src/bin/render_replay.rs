@@ -0,0 +1,133 @@
+//! Render a replay to an animated GIF or APNG on the CPU, no browser needed.
+//!
+//! Render a whole recording:
+//! `cargo run -p server --release --bin render_replay -- recording.json`
+//!
+//! Render a Play of the Game clip as an APNG:
+//! `cargo run -p server --release --bin render_replay -- clip.json --format apng --out potg.png`
+//!
+//! The input is a `GameRecordingV1` or a `HighlightClip` as JSON. A clip
+//! renders its window the way it is presented; a recording renders from its
+//! first anchor to its end unless `--start-tick`/`--end-tick` narrow it.
+
+use anyhow::{Context, Result, bail};
+use common::{GameRecordingV1, HighlightClip};
+use server::replay_render::{RenderFormat, RenderOptions, render_clip, render_recording};
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+struct Cli {
+    input: PathBuf,
+    output: Option<PathBuf>,
+    options: RenderOptions,
+    start_tick: Option<u32>,
+    end_tick: Option<u32>,
+}
+
+impl Cli {
+    fn parse() -> Result<Self> {
+        let mut args = std::env::args_os().skip(1);
+        let mut input = None;
+        let mut cli = Self {
+            input: PathBuf::new(),
+            output: None,
+            options: RenderOptions::default(),
+            start_tick: None,
+            end_tick: None,
+        };
+        while let Some(arg) = args.next() {
+            match arg.to_str() {
+                Some("--out") => cli.output = Some(PathBuf::from(next_arg(&mut args, "--out")?)),
+                Some("--format") => {
+                    let format = next_arg(&mut args, "--format")?;
+                    cli.options.format =
+                        RenderFormat::parse(format.to_str().context("--format must be UTF-8")?)?;
+                }
+                Some("--cell-px") => {
+                    cli.options.cell_px = Some(parse_number(
+                        next_arg(&mut args, "--cell-px")?,
+                        "--cell-px",
+                    )?)
+                }
+                Some("--max-frames") => {
+                    cli.options.max_frames =
+                        parse_number(next_arg(&mut args, "--max-frames")?, "--max-frames")?
+                }
+                Some("--start-tick") => {
+                    cli.start_tick = Some(parse_number(
+                        next_arg(&mut args, "--start-tick")?,
+                        "--start-tick",
+                    )?)
+                }
+                Some("--end-tick") => {
+                    cli.end_tick = Some(parse_number(
+                        next_arg(&mut args, "--end-tick")?,
+                        "--end-tick",
+                    )?)
+                }
+                Some("--help" | "-h") => {
+                    println!("{}", usage());
+                    std::process::exit(0);
+                }
+                Some(flag) if flag.starts_with('-') => bail!("unknown option {flag}\n{}", usage()),
+                _ if input.is_none() => input = Some(PathBuf::from(arg)),
+                _ => bail!("pass exactly one replay JSON file\n{}", usage()),
+            }
+        }
+        cli.input = input.with_context(|| format!("pass a replay JSON file\n{}", usage()))?;
+        Ok(cli)
+    }
+}
+
+fn usage() -> &'static str {
+    "Usage:\n  render_replay REPLAY.json [--format gif|apng] [--out PATH] [--cell-px N] [--max-frames N] [--start-tick N] [--end-tick N]"
+}
+
+fn next_arg(args: &mut impl Iterator<Item = OsString>, flag: &str) -> Result<OsString> {
+    args.next().with_context(|| format!("{flag} needs a value"))
+}
+
+fn parse_number<T: std::str::FromStr>(value: OsString, flag: &str) -> Result<T> {
+    value
+        .to_str()
+        .with_context(|| format!("{flag} must be UTF-8"))?
+        .parse()
+        .ok()
+        .with_context(|| format!("{flag} must be a non-negative integer"))
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse()?;
+    let bytes = std::fs::read(&cli.input)
+        .with_context(|| format!("failed to read {}", cli.input.display()))?;
+    let document: serde_json::Value = serde_json::from_slice(&bytes)
+        .with_context(|| format!("failed to parse {}", cli.input.display()))?;
+    // Clips carry their window; recordings do not.
+    let image = if document.get("window").is_some() {
+        if cli.start_tick.is_some() || cli.end_tick.is_some() {
+            bail!("a highlight clip always renders its own window");
+        }
+        let clip: HighlightClip = serde_json::from_value(document)
+            .with_context(|| format!("{} is not a highlight clip", cli.input.display()))?;
+        render_clip(&clip, &cli.options)
+    } else {
+        let recording: GameRecordingV1 = serde_json::from_value(document)
+            .with_context(|| format!("{} is not a game recording", cli.input.display()))?;
+        let start_tick = cli
+            .start_tick
+            .or_else(|| recording.anchors.first().map(|anchor| anchor.tick))
+            .unwrap_or_default();
+        let end_tick = cli.end_tick.unwrap_or(recording.end_tick);
+        render_recording(&recording, start_tick, end_tick, &cli.options)
+    }
+    .with_context(|| format!("failed to render {}", cli.input.display()))?;
+
+    let output = cli
+        .output
+        .unwrap_or_else(|| cli.input.with_extension(cli.options.format.extension()));
+    std::fs::write(&output, &image)
+        .with_context(|| format!("failed to write {}", output.display()))?;
+    println!("wrote {} ({} bytes)", output.display(), image.len());
+    Ok(())
+}
@@ -4,11 +4,15 @@
 //!   trace_rca <trace.jsonl>                       replay one trace
 //!   trace_rca <server.jsonl> <client.jsonl>       replay both + cross-diff
 //!   flags: --json (machine-readable), --emit-test <out.rs> (write a repro test)
+//!
+//!   trace_rca to-scenario <recording.json|trace.jsonl> --at-tick <N> [--id <id>] [--out <script.json>]
+//!     pose the game at tick N as a scenario script that ends on its FinalSyncHash
 
 use anyhow::{Context, Result, bail};
+use common::capture::{recording_from_trace, scenario_from_recording};
 use common::replay::{ClientReplay, ServerReplay, diff_traces, trace_side};
 use common::trace::{TraceRecord, TraceSide, read_trace};
-use common::{DeathCause, GameEvent};
+use common::{DeathCause, GameEvent, GameRecordingV1};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
@@ -18,6 +22,13 @@ struct Args {
     emit_test: Option<PathBuf>,
 }
 
+struct ScenarioArgs {
+    input: PathBuf,
+    at_tick: u32,
+    id: Option<String>,
+    out: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 struct TraceDeath {
     tick: u32,
@@ -88,16 +99,106 @@ fn parse_args() -> Result<Args> {
     })
 }
 
+fn parse_scenario_args(mut iter: impl Iterator<Item = String>) -> Result<ScenarioArgs> {
+    let mut input = None;
+    let mut at_tick = None;
+    let mut id = None;
+    let mut out = None;
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--at-tick" => {
+                let tick = iter.next().context("--at-tick requires a tick")?;
+                at_tick = Some(
+                    tick.parse()
+                        .with_context(|| format!("Invalid --at-tick: {}", tick))?,
+                );
+            }
+            "--id" => id = Some(iter.next().context("--id requires a scenario id")?),
+            "--out" => {
+                let path = iter.next().context("--out requires an output path")?;
+                out = Some(PathBuf::from(path));
+            }
+            "--help" | "-h" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            other if other.starts_with("--") => bail!("Unknown flag: {}", other),
+            path if input.is_none() => input = Some(PathBuf::from(path)),
+            path => bail!("Unexpected extra input: {}", path),
+        }
+    }
+
+    let Some(input) = input else {
+        print_usage();
+        bail!("to-scenario expects a recording or trace path");
+    };
+    Ok(ScenarioArgs {
+        input,
+        at_tick: at_tick.context("to-scenario requires --at-tick")?,
+        id,
+        out,
+    })
+}
+
 fn print_usage() {
     eprintln!(
         "Usage: trace_rca <server_trace.jsonl> [<client_trace.jsonl>] [--json] [--emit-test <out.rs>]\n\
          Replays captured sync traces deterministically through the real game engine,\n\
          reports the first divergence, and cross-diffs server vs client perspectives.\n\
+         \n\
+         Usage: trace_rca to-scenario <recording.json|trace.jsonl> --at-tick <N> [--id <id>] [--out <script.json>]\n\
+         Poses a recorded game at tick N as a scenario script whose commands replay\n\
+         the rest of the match, checked to end on the recording's FinalSyncHash.\n\
          See DEBUGGING.md for the full workflow."
     );
 }
 
+/// A `GameRecordingV1` is one JSON document; anything else is read as a
+/// sync trace.
+fn load_recording(path: &Path) -> Result<GameRecordingV1> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if let Ok(recording) = serde_json::from_slice(&bytes) {
+        return Ok(recording);
+    }
+    let records =
+        read_trace(path).with_context(|| format!("Failed to read trace {}", path.display()))?;
+    recording_from_trace(&records)
+        .with_context(|| format!("{} cannot be replayed as a recording", path.display()))
+}
+
+fn to_scenario(args: ScenarioArgs) -> Result<()> {
+    let recording = load_recording(&args.input)?;
+    let id = args
+        .id
+        .unwrap_or_else(|| format!("game-{}-tick-{}", recording.game_id, args.at_tick));
+    let script = scenario_from_recording(&recording, args.at_tick, &id)?;
+    let json = serde_json::to_string_pretty(&script)?;
+    match &args.out {
+        Some(out) => {
+            std::fs::write(out, json)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+            eprintln!(
+                "Wrote scenario {} to {}: {} commands over {} ticks, ending on FinalSyncHash {:#x}",
+                id,
+                out.display(),
+                script.commands.len(),
+                script.run_ticks,
+                recording.end_sync_hash
+            );
+        }
+        None => println!("{json}"),
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
+    let mut raw_args = std::env::args().skip(1).peekable();
+    if raw_args.peek().map(String::as_str) == Some("to-scenario") {
+        raw_args.next();
+        return to_scenario(parse_scenario_args(raw_args)?);
+    }
     let args = parse_args()?;
 
     let mut loaded = Vec::new();
@@ -15,7 +15,8 @@ use anyhow::{Result, anyhow};
 use chrono::{DateTime, Utc};
 use common::{
     GAME_RECORDING_FORMAT_VERSION, GAMEPLAY_REPLAY_VERSION, GameRecordingV1, GameState, GameStatus,
-    GameType, HIGHLIGHT_CLIP_FORMAT_VERSION, HighlightClip, QueueMode,
+    GameType, HIGHLIGHT_CLIP_FORMAT_VERSION, HIGHLIGHT_REEL_MOMENTS, HighlightClip, HighlightReel,
+    QueueMode,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -96,6 +97,10 @@ pub struct CompletionRecordV1 {
     /// Server-selected canonical clip delivered identically to every viewer.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub play_of_the_game: Option<HighlightClip>,
+    /// The match's other distinct moments and each player's best, selected
+    /// in the same scoring pass as `play_of_the_game`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight_reel: Option<HighlightReel>,
     pub final_state: GameState,
     pub effects: Vec<CompletionEffect>,
 }
@@ -204,10 +209,31 @@ impl CompletionRecordV1 {
                 return Err(anyhow!("completion highlight targets a different game"));
             }
         }
+        if let Some(reel) = &self.highlight_reel {
+            if reel.moments.len() > HIGHLIGHT_REEL_MOMENTS {
+                return Err(anyhow!("completion highlight reel has too many moments"));
+            }
+            if reel.moments.iter().chain(&reel.players).any(|moment| {
+                moment.window.start_tick > moment.window.focus_tick
+                    || moment.window.focus_tick > moment.window.end_tick
+            }) {
+                return Err(anyhow!("completion highlight reel has a malformed window"));
+            }
+            if !reel
+                .players
+                .windows(2)
+                .all(|pair| pair[0].star_user_id < pair[1].star_user_id)
+            {
+                return Err(anyhow!(
+                    "completion highlight reel must list each player once, by user id"
+                ));
+            }
+        }
         if self.final_state.is_stress_test
             && (self.recording.is_some()
                 || self.recording_journal.is_some()
-                || self.play_of_the_game.is_some())
+                || self.play_of_the_game.is_some()
+                || self.highlight_reel.is_some())
         {
             return Err(anyhow!(
                 "stress-test completion must not contain replay artifacts"
@@ -350,7 +376,13 @@ impl CompletionRecordV1 {
                     matches!(effect, CompletionEffect::InsertHighScore { user_id: effect_user, .. } if effect_user == user_id)
                 })
                 .count();
-            if matches!(self.final_state.game_type, GameType::Solo) {
+            if self.final_state.is_ai_driven(*user_id) {
+                if mmr_count != 0 || ranking_count != 0 || high_score_count != 0 {
+                    return Err(anyhow!(
+                        "completion cannot contain progression effects for AI seat {user_id}"
+                    ));
+                }
+            } else if matches!(self.final_state.game_type, GameType::Solo) {
                 if mmr_count != 0 || ranking_count != 0 || high_score_count != 1 {
                     return Err(anyhow!(
                         "completion has an incomplete solo effect set for user {user_id}"
@@ -425,6 +457,20 @@ pub fn match_history_summary(
             (name.clone(), name)
         }
         GameType::FreeForAll { .. } => ("ffa".to_string(), "Free for All".to_string()),
+        GameType::KingOfTheHill { per_team: 1 } => {
+            ("hill".to_string(), "King of the Hill".to_string())
+        }
+        GameType::KingOfTheHill { per_team } => (
+            format!("hill-{per_team}v{per_team}"),
+            format!("King of the Hill {per_team}v{per_team}"),
+        ),
+        GameType::CaptureTheFlag { per_team: 1 } => {
+            ("ctf".to_string(), "Capture the Flag".to_string())
+        }
+        GameType::CaptureTheFlag { per_team } => (
+            format!("ctf-{per_team}v{per_team}"),
+            format!("Capture the Flag {per_team}v{per_team}"),
+        ),
         GameType::Custom { .. } => ("custom".to_string(), "Custom".to_string()),
     };
     let queue_mode = match state.queue_mode {
@@ -523,6 +569,12 @@ pub fn match_history_summary(
                         .as_ref()
                         .and_then(|scores| scores.get(&common::TeamId(team_id)).copied())
                 }),
+                rounds_won: team_id.and_then(|team_id| {
+                    state
+                        .rounds
+                        .as_ref()
+                        .map(|rounds| rounds.wins(common::TeamId(team_id)))
+                }),
                 xp_gained: state.player_xp.get(&user_id).copied().unwrap_or(0),
                 mmr_delta: mmr_deltas.get(&user_id).copied(),
                 outcome: outcome.to_string(),
@@ -788,6 +840,10 @@ pub async fn materialize_completion(
         let region = get_region();
         if matches!(final_state.game_type, GameType::Solo) {
             for user_id in player_ids {
+                // Practice AI never reaches the leaderboard.
+                if final_state.is_ai_driven(user_id) {
+                    continue;
+                }
                 let player = final_state
                     .players
                     .get(&user_id)
@@ -855,6 +911,7 @@ pub async fn materialize_completion(
         recording_canonical_bytes: None,
         recording_journal: None,
         play_of_the_game: None,
+        highlight_reel: None,
         final_state,
         effects,
     };
@@ -978,6 +1035,7 @@ mod tests {
             recording_canonical_bytes: None,
             recording_journal: None,
             play_of_the_game: None,
+            highlight_reel: None,
             final_state: state,
             effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
         };
@@ -999,6 +1057,72 @@ mod tests {
             .expect("legacy completion remains valid");
     }
 
+    #[test]
+    fn highlight_reel_is_bounded_and_names_each_player_once() {
+        let mut state = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(1), 0);
+        state.status = GameStatus::Complete {
+            winning_snake_id: None,
+        };
+        let moment = |user_id: u32, focus_tick: u32| common::HighlightMoment {
+            star_user_id: user_id,
+            star_snake_id: 0,
+            star_name: format!("Player {user_id}"),
+            reason: common::HighlightReason::FeedingFrenzy { pickups: 8 },
+            score: 120,
+            breakdown: Default::default(),
+            window: common::HighlightWindow {
+                start_tick: focus_tick - 60,
+                end_tick: focus_tick + 30,
+                focus_tick,
+            },
+        };
+        let mut record = CompletionRecordV1 {
+            schema_version: COMPLETION_SCHEMA_VERSION,
+            game_id: 18,
+            partition_id: 8,
+            revision: Uuid::new_v4(),
+            ended_at_ms: 10,
+            server_id: 1,
+            season: None,
+            recording: None,
+            recording_canonical_bytes: None,
+            recording_journal: None,
+            play_of_the_game: None,
+            highlight_reel: Some(HighlightReel {
+                moments: (1..=HIGHLIGHT_REEL_MOMENTS as u32)
+                    .map(|index| moment(7, index * 100))
+                    .collect(),
+                players: vec![moment(7, 100)],
+            }),
+            final_state: state,
+            effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
+        };
+        record.validate().expect("a full reel is valid");
+
+        let mut crowded = record.clone();
+        crowded
+            .highlight_reel
+            .as_mut()
+            .unwrap()
+            .moments
+            .push(moment(7, 900));
+        assert!(crowded.validate().is_err());
+
+        record
+            .highlight_reel
+            .as_mut()
+            .unwrap()
+            .players
+            .push(moment(7, 200));
+        assert!(
+            record
+                .validate()
+                .unwrap_err()
+                .to_string()
+                .contains("each player once")
+        );
+    }
+
     #[test]
     fn stress_completion_rejects_player_progression_effects() {
         let mut state = GameState::new(
@@ -1030,6 +1154,7 @@ mod tests {
             recording_canonical_bytes: None,
             recording_journal: None,
             play_of_the_game: None,
+            highlight_reel: None,
             final_state: state,
             effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
         };
@@ -1092,6 +1217,7 @@ mod tests {
             recording_canonical_bytes: None,
             recording_journal: None,
             play_of_the_game: None,
+            highlight_reel: None,
             final_state: state,
             effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
         };
@@ -1103,5 +1229,57 @@ mod tests {
         assert_eq!(summary.players[0].score, 9);
         assert_eq!(summary.players[0].xp_gained, 25);
         assert_eq!(summary.players[0].outcome, "completed");
+        assert_eq!(summary.players[0].rounds_won, None);
+    }
+
+    #[test]
+    fn best_of_three_history_reports_each_teams_round_wins() {
+        let mut state = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Competitive,
+            Some(1),
+            1_000,
+        );
+        let winner = state
+            .add_player(7, Some("winner".into()))
+            .expect("first player should be added");
+        state
+            .add_player(8, Some("runner-up".into()))
+            .expect("second player should be added");
+        let rounds = state.rounds.as_mut().expect("competitive rounds");
+        rounds.round_wins.insert(common::TeamId(0), 2);
+        rounds.round_wins.insert(common::TeamId(1), 1);
+        rounds.current_round = 3;
+        rounds.rounds_completed = 3;
+        state.status = GameStatus::Complete {
+            winning_snake_id: Some(winner.snake_id),
+        };
+        let record = CompletionRecordV1 {
+            schema_version: COMPLETION_SCHEMA_VERSION,
+            game_id: 43,
+            partition_id: 3,
+            revision: Uuid::new_v4(),
+            ended_at_ms: 5_000,
+            server_id: 1,
+            season: Some(0),
+            recording: None,
+            recording_canonical_bytes: None,
+            recording_journal: None,
+            play_of_the_game: None,
+            highlight_reel: None,
+            final_state: state,
+            effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
+        };
+
+        let summary = match_history_summary(&record, 30).unwrap();
+        assert_eq!(summary.winner_user_ids, vec![7]);
+        let rounds_won: Vec<_> = summary
+            .players
+            .iter()
+            .map(|player| (player.user_id, player.rounds_won))
+            .collect();
+        assert_eq!(rounds_won, vec![(7, Some(2)), (8, Some(1))]);
     }
 }
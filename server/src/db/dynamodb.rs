@@ -118,17 +118,22 @@ const RECENT_COMPLETED_GAMES_PAGE_SIZE: usize = 100;
 const MAX_DYNAMODB_REPLAY_METADATA_BYTES: usize = 8 * 1024;
 const MAX_DYNAMODB_HIGHLIGHT_BYTES: usize = 256 * 1024;
 const SPLIT_HIGHLIGHT_SORT_KEY: &str = "HIGHLIGHT";
-/// Keep the two largest JSON attributes well below DynamoDB's 400 KiB item
-/// ceiling. The remaining 200 KiB covers attribute names and game metadata.
-/// A valid highlight that would cross this budget is written to a separate
-/// item by the same completion transaction.
+/// Keep the largest JSON attributes, the final state, the highlight reel and
+/// the highlight, well below DynamoDB's 400 KiB item ceiling. The remaining
+/// 200 KiB covers attribute names and game metadata. A valid highlight that
+/// would cross this budget is written to a separate item by the same
+/// completion transaction.
 const MAX_DYNAMODB_STATE_AND_HIGHLIGHT_BYTES: usize = 200 * 1024;
+/// Reels carry scores and windows but no replay payload, so one this large
+/// is malformed rather than a busy match.
+const MAX_DYNAMODB_HIGHLIGHT_REEL_BYTES: usize = 32 * 1024;
 
 #[derive(Debug)]
 struct PersistableReplayArtifacts {
     replay_object_json: Option<String>,
     play_of_the_game_json: Option<String>,
     split_play_of_the_game_json: Option<String>,
+    highlight_reel_json: Option<String>,
 }
 
 /// Materialize replay artifacts before the DynamoDB transaction. Object keys
@@ -199,9 +204,28 @@ async fn prepare_replay_artifacts(
         })
         .transpose()?;
 
+    let highlight_reel_json = match &completion.highlight_reel {
+        Some(reel) => {
+            let json = String::from_utf8(canonical_json_bytes(reel)?)
+                .context("Canonical highlight reel JSON was not UTF-8")?;
+            if json.len() > MAX_DYNAMODB_HIGHLIGHT_REEL_BYTES {
+                warn!(
+                    game_id = completion.game_id,
+                    reel_bytes = json.len(),
+                    max_reel_bytes = MAX_DYNAMODB_HIGHLIGHT_REEL_BYTES,
+                    "Omitting oversized highlight reel from DynamoDB"
+                );
+                None
+            } else {
+                Some(json)
+            }
+        }
+        None => None,
+    };
     let final_state_bytes = canonical_json_bytes(&completion.final_state)
         .context("Failed to size completed game state")?
-        .len();
+        .len()
+        .saturating_add(highlight_reel_json.as_ref().map_or(0, String::len));
     let (play_of_the_game_json, split_play_of_the_game_json) = match &completion.play_of_the_game {
         Some(clip) => {
             let json = String::from_utf8(canonical_json_bytes(clip)?)
@@ -236,6 +260,7 @@ async fn prepare_replay_artifacts(
         replay_object_json,
         play_of_the_game_json,
         split_play_of_the_game_json,
+        highlight_reel_json,
     })
 }
 
@@ -896,6 +921,10 @@ impl DynamoDatabase {
             common::GameType::TeamMatch { per_team: 2 } => "2v2".to_string(),
             common::GameType::TeamMatch { per_team } => format!("team-{}", per_team),
             common::GameType::FreeForAll { .. } => "ffa".to_string(),
+            common::GameType::KingOfTheHill { per_team: 1 } => "hill".to_string(),
+            common::GameType::KingOfTheHill { per_team } => format!("hill-{}", per_team),
+            common::GameType::CaptureTheFlag { per_team: 1 } => "ctf".to_string(),
+            common::GameType::CaptureTheFlag { per_team } => format!("ctf-{}", per_team),
             common::GameType::Custom { .. } => "custom".to_string(),
         }
     }
@@ -978,6 +1007,12 @@ impl DynamoDatabase {
                     .context("Invalid playOfTheGame JSON on game metadata row")
             })
             .transpose()?;
+        let highlight_reel = Self::extract_string(item, "highlightReel")
+            .map(|value| {
+                serde_json::from_str::<common::HighlightReel>(&value)
+                    .context("Invalid highlightReel JSON on game metadata row")
+            })
+            .transpose()?;
 
         Ok(Game {
             id: game_id,
@@ -999,6 +1034,7 @@ impl DynamoDatabase {
             game_code: Self::extract_string(item, "gameCode"),
             replay_object,
             play_of_the_game,
+            highlight_reel,
             news_eligible: Self::extract_bool(item, "newsEligible") == Some(true)
                 && Self::extract_bool(item, "isPrivate") == Some(false)
                 && Self::extract_string(item, "gameCode").is_none(),
@@ -4424,7 +4460,10 @@ impl Database for DynamoDatabase {
                     if replay_artifacts.split_play_of_the_game_json.is_some() {
                         expression.push_str(", playOfTheGameSplit=:play_of_the_game_split");
                     }
-                    let mut removals = Vec::with_capacity(3);
+                    if replay_artifacts.highlight_reel_json.is_some() {
+                        expression.push_str(", highlightReel=:highlight_reel");
+                    }
+                    let mut removals = Vec::with_capacity(4);
                     if replay_artifacts.replay_object_json.is_none() {
                         removals.push("replayObject");
                     }
@@ -4434,6 +4473,9 @@ impl Database for DynamoDatabase {
                     if replay_artifacts.split_play_of_the_game_json.is_none() {
                         removals.push("playOfTheGameSplit");
                     }
+                    if replay_artifacts.highlight_reel_json.is_none() {
+                        removals.push("highlightReel");
+                    }
                     if !removals.is_empty() {
                         expression.push_str(" REMOVE ");
                         expression.push_str(&removals.join(", "));
@@ -4505,6 +4547,12 @@ impl Database for DynamoDatabase {
                             Self::av_bool(true),
                         );
                     }
+                    if let Some(highlight_reel) = &replay_artifacts.highlight_reel_json {
+                        update = update.expression_attribute_values(
+                            ":highlight_reel",
+                            Self::av_s(highlight_reel),
+                        );
+                    }
                     let mut mutations = vec![
                         TransactWriteItem::builder()
                             .update(
@@ -6184,6 +6232,7 @@ mod tests {
             messages: Vec::new(),
             end_tick: final_state.tick,
             end_sync_hash: final_state.sync_hash(),
+            keyframes: Vec::new(),
         };
         let completion = CompletionRecordV1 {
             schema_version: crate::completion::COMPLETION_SCHEMA_VERSION,
@@ -6197,6 +6246,7 @@ mod tests {
             recording_canonical_bytes: None,
             recording_journal: None,
             play_of_the_game: None,
+            highlight_reel: None,
             final_state,
             effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
         };
@@ -6296,6 +6346,7 @@ mod tests {
         assert_eq!(game.season, None, "legacy rows have no proven season");
         assert!(game.replay_object.is_none());
         assert!(game.play_of_the_game.is_none());
+        assert!(game.highlight_reel.is_none());
         assert!(!game.news_eligible, "legacy rows must fail closed");
 
         item.insert("season".to_string(), DynamoDatabase::av_n(7));
@@ -6335,6 +6386,21 @@ mod tests {
         assert!(DynamoDatabase::game_from_item(123, &item).is_err());
     }
 
+    #[test]
+    fn game_from_item_parses_the_highlight_reel_strictly() {
+        let reel = common::HighlightReel::default();
+        let mut item = HashMap::new();
+        item.insert(
+            "highlightReel".to_string(),
+            DynamoDatabase::av_s(serde_json::to_string(&reel).unwrap()),
+        );
+        let game = DynamoDatabase::game_from_item(123, &item).unwrap();
+        assert_eq!(game.highlight_reel, Some(reel));
+
+        item.insert("highlightReel".to_string(), DynamoDatabase::av_s("[]"));
+        assert!(DynamoDatabase::game_from_item(123, &item).is_err());
+    }
+
     #[tokio::test]
     async fn replay_artifacts_upload_deterministically_before_metadata_persistence() {
         let completion = completion_with_recording(123);
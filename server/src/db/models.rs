@@ -173,6 +173,10 @@ pub struct Game {
     /// legitimately omit it when no candidate clears the scoring threshold.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub play_of_the_game: Option<common::HighlightClip>,
+    /// Ranked moments and each player's best, cut from the same selection as
+    /// `play_of_the_game`. Games completed before reels omit it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight_reel: Option<common::HighlightReel>,
     /// Internal proof that completion persistence verified this result as a
     /// public source. Legacy rows without proof fail closed for news.
     #[serde(default, skip_serializing)]
@@ -285,6 +289,10 @@ pub struct MatchHistoryPlayer {
     pub team_id: Option<u8>,
     pub score: u32,
     pub team_score: Option<u32>,
+    /// Rounds the player's team took in a best-of-N match. Single-round
+    /// matches, and rows written before rounds existed, have none.
+    #[serde(default)]
+    pub rounds_won: Option<u32>,
     pub xp_gained: u32,
     pub mmr_delta: Option<i32>,
     pub outcome: String,
@@ -2411,9 +2411,14 @@ impl GameBus {
                     deltas,
                     &final_state,
                 )?;
-                let recording = recorder
+                let mut recording = recorder
                     .finish(game_id, &final_state)?
                     .context("completion replay journal did not assemble a recording")?;
+                if reference.recording_sha256.is_some() {
+                    // Legacy digests bind the archive as it was written,
+                    // before finalization laid down a keyframe index.
+                    recording.keyframes.clear();
+                }
                 let recording_bytes = crate::completion::canonical_json_bytes(&recording)?;
                 if let (Some(expected_bytes), Some(expected_sha256)) =
                     (reference.recording_bytes, &reference.recording_sha256)
@@ -16,7 +16,7 @@ use anyhow::{Context, Result, bail};
 use common::{
     ClientCommandIdentityV2, CommandId, EXECUTOR_POLL_INTERVAL_MS, GAME_START_COUNTDOWN_MS,
     GameCommand, GameCommandMessage, GameEngine, GameEvent, GameEventMessage, GameStatus,
-    HighlightConfig, select_highlight,
+    HIGHLIGHT_REEL_MOMENTS, HighlightConfig, select_match_highlights,
 };
 use futures_util::FutureExt;
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -52,6 +52,9 @@ const DISPATCH_FANOUT_WINDOW: usize = 64;
 /// that immutable record is committed; an oversized play degrades to the
 /// banner path instead of poisoning completion retries.
 const MAX_COMPLETION_HIGHLIGHT_JSON_BYTES: usize = 128 * 1024;
+/// Reels are scores and windows without replay payloads; this fits every
+/// moment of a full match with room to spare.
+const MAX_COMPLETION_HIGHLIGHT_REEL_JSON_BYTES: usize = 32 * 1024;
 const POTG_SELECTION_TIMEOUT: Duration = Duration::from_millis(500);
 const POTG_SCORER_CONCURRENCY: usize = 2;
 pub const POTG_ENABLED_ENV: &str = "SNAKETRON_POTG_ENABLED";
@@ -90,55 +93,67 @@ fn is_retryable_checkpoint_error(error: &anyhow::Error) -> bool {
     })
 }
 
+/// Optional presentation artifacts selected together for one completion.
+#[derive(Debug, Default)]
+struct CompletionHighlights {
+    play_of_the_game: Option<common::HighlightClip>,
+    highlight_reel: Option<common::HighlightReel>,
+}
+
 /// Highlight selection is optional presentation work. A malformed archive,
 /// explicit CPU budget rejection, or scorer panic must never poison the
 /// completion record that carries XP, ranking, and the final game snapshot.
-fn select_completion_highlight_sync(
-    recording: &common::GameRecordingV1,
-) -> Option<common::HighlightClip> {
+fn select_completion_highlight_sync(recording: &common::GameRecordingV1) -> CompletionHighlights {
     if !potg_enabled() {
         info!(
             game_id = recording.game_id,
             config = POTG_ENABLED_ENV,
             "Play-of-the-Game disabled by server configuration"
         );
-        return None;
+        return CompletionHighlights::default();
     }
     match std::panic::catch_unwind(AssertUnwindSafe(|| {
-        select_highlight(recording, &HighlightConfig::default())
+        select_match_highlights(
+            recording,
+            &HighlightConfig::default(),
+            HIGHLIGHT_REEL_MOMENTS,
+        )
     })) {
-        Ok(Ok(highlight)) => {
-            let highlight = highlight.and_then(|clip| match serde_json::to_vec(&clip) {
-                Ok(bytes) if bytes.len() <= MAX_COMPLETION_HIGHLIGHT_JSON_BYTES => {
-                    if let Err(error) = clip.replay_and_verify() {
-                        warn!(
-                            game_id = recording.game_id,
-                            %error,
-                            "Play-of-the-Game clip verification degraded to unavailable"
-                        );
-                        None
-                    } else {
-                        Some(clip)
-                    }
-                }
-                Ok(bytes) => {
-                    warn!(
-                        game_id = recording.game_id,
-                        highlight_bytes = bytes.len(),
-                        max_highlight_bytes = MAX_COMPLETION_HIGHLIGHT_JSON_BYTES,
-                        "Play-of-the-Game exceeded its completion payload budget"
-                    );
-                    None
-                }
-                Err(error) => {
-                    warn!(
-                        game_id = recording.game_id,
-                        %error,
-                        "Play-of-the-Game could not be serialized"
-                    );
-                    None
-                }
-            });
+        Ok(Ok(highlights)) => {
+            let highlight =
+                highlights
+                    .play_of_the_game
+                    .and_then(|clip| match serde_json::to_vec(&clip) {
+                        Ok(bytes) if bytes.len() <= MAX_COMPLETION_HIGHLIGHT_JSON_BYTES => {
+                            if let Err(error) = clip.replay_and_verify() {
+                                warn!(
+                                    game_id = recording.game_id,
+                                    %error,
+                                    "Play-of-the-Game clip verification degraded to unavailable"
+                                );
+                                None
+                            } else {
+                                Some(clip)
+                            }
+                        }
+                        Ok(bytes) => {
+                            warn!(
+                                game_id = recording.game_id,
+                                highlight_bytes = bytes.len(),
+                                max_highlight_bytes = MAX_COMPLETION_HIGHLIGHT_JSON_BYTES,
+                                "Play-of-the-Game exceeded its completion payload budget"
+                            );
+                            None
+                        }
+                        Err(error) => {
+                            warn!(
+                                game_id = recording.game_id,
+                                %error,
+                                "Play-of-the-Game could not be serialized"
+                            );
+                            None
+                        }
+                    });
             if let Some(clip) = &highlight {
                 info!(
                     game_id = recording.game_id,
@@ -147,7 +162,34 @@ fn select_completion_highlight_sync(
                     "Play-of-the-Game selected"
                 );
             }
-            highlight
+            // The reel only points into the replay, so it stands on its own
+            // even when the clip above was dropped.
+            let reel = Some(highlights.reel)
+                .filter(|reel| !reel.is_empty())
+                .filter(|reel| match serde_json::to_vec(reel) {
+                    Ok(bytes) if bytes.len() <= MAX_COMPLETION_HIGHLIGHT_REEL_JSON_BYTES => true,
+                    Ok(bytes) => {
+                        warn!(
+                            game_id = recording.game_id,
+                            reel_bytes = bytes.len(),
+                            max_reel_bytes = MAX_COMPLETION_HIGHLIGHT_REEL_JSON_BYTES,
+                            "Highlight reel exceeded its completion payload budget"
+                        );
+                        false
+                    }
+                    Err(error) => {
+                        warn!(
+                            game_id = recording.game_id,
+                            %error,
+                            "Highlight reel could not be serialized"
+                        );
+                        false
+                    }
+                });
+            CompletionHighlights {
+                play_of_the_game: highlight,
+                highlight_reel: reel,
+            }
         }
         Ok(Err(error)) => {
             warn!(
@@ -155,14 +197,14 @@ fn select_completion_highlight_sync(
                 %error,
                 "Play-of-the-Game selection degraded to unavailable"
             );
-            None
+            CompletionHighlights::default()
         }
         Err(_) => {
             error!(
                 game_id = recording.game_id,
                 "Play-of-the-Game scorer panicked; completion remains available"
             );
-            None
+            CompletionHighlights::default()
         }
     }
 }
@@ -176,9 +218,9 @@ async fn select_completion_highlight_with_budget<F>(
     slots: Arc<Semaphore>,
     budget: Duration,
     score: F,
-) -> Option<common::HighlightClip>
+) -> CompletionHighlights
 where
-    F: FnOnce(&common::GameRecordingV1) -> Option<common::HighlightClip> + Send + 'static,
+    F: FnOnce(&common::GameRecordingV1) -> CompletionHighlights + Send + 'static,
 {
     let game_id = recording.game_id;
     let deadline = Instant::now() + budget;
@@ -186,7 +228,7 @@ where
         Ok(Ok(permit)) => permit,
         Ok(Err(_)) => {
             error!(game_id, "Play-of-the-Game scorer gate closed");
-            return None;
+            return CompletionHighlights::default();
         }
         Err(_) => {
             warn!(
@@ -194,7 +236,7 @@ where
                 timeout_ms = budget.as_millis(),
                 "Play-of-the-Game scorer capacity timed out"
             );
-            return None;
+            return CompletionHighlights::default();
         }
     };
     let worker = tokio::task::spawn_blocking(move || {
@@ -202,10 +244,10 @@ where
         score(&recording)
     });
     match tokio::time::timeout_at(deadline, worker).await {
-        Ok(Ok(highlight)) => highlight,
+        Ok(Ok(highlights)) => highlights,
         Ok(Err(error)) => {
             error!(game_id, %error, "Play-of-the-Game scorer task failed");
-            None
+            CompletionHighlights::default()
         }
         Err(_) => {
             warn!(
@@ -213,14 +255,12 @@ where
                 timeout_ms = budget.as_millis(),
                 "Play-of-the-Game scorer exceeded its wall-clock budget"
             );
-            None
+            CompletionHighlights::default()
         }
     }
 }
 
-async fn select_completion_highlight(
-    recording: common::GameRecordingV1,
-) -> Option<common::HighlightClip> {
+async fn select_completion_highlight(recording: common::GameRecordingV1) -> CompletionHighlights {
     select_completion_highlight_with_budget(
         recording,
         Arc::clone(&POTG_SCORER_SLOTS),
@@ -1990,23 +2030,25 @@ impl GameActor {
                     "Play-of-the-Game selection ring was truncated"
                 );
             }
-            record.play_of_the_game = match self
+            let highlights = match self
                 .replay_recording
                 .finish_potg_selection(self.game_id, &final_state)
             {
                 Ok(Some(selection_recording)) => {
                     select_completion_highlight(selection_recording).await
                 }
-                Ok(None) => None,
+                Ok(None) => CompletionHighlights::default(),
                 Err(error) => {
                     warn!(
                         game_id = self.game_id,
                         %error,
                         "Play-of-the-Game ring could not be replayed; completion remains available"
                     );
-                    None
+                    CompletionHighlights::default()
                 }
             };
+            record.play_of_the_game = highlights.play_of_the_game;
+            record.highlight_reel = highlights.highlight_reel;
             // The budgeted scorer structurally validates its bounded ring and
             // verifies the selected clip before returning it. Do not replay
             // either artifact again on the partition actor.
@@ -3556,8 +3598,11 @@ mod tests {
             messages: Vec::new(),
             end_tick: 0,
             end_sync_hash: 0,
+            keyframes: Vec::new(),
         };
-        assert!(select_completion_highlight_sync(&recording).is_none());
+        let highlights = select_completion_highlight_sync(&recording);
+        assert!(highlights.play_of_the_game.is_none());
+        assert!(highlights.highlight_reel.is_none());
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -3571,6 +3616,7 @@ mod tests {
             messages: Vec::new(),
             end_tick: 0,
             end_sync_hash: 0,
+            keyframes: Vec::new(),
         };
         let slots = Arc::new(Semaphore::new(1));
         let held = Arc::clone(&slots)
@@ -3594,13 +3640,14 @@ mod tests {
                 worker_started.store(true, Ordering::Release);
                 std::thread::sleep(Duration::from_millis(300));
                 worker_finished.store(true, Ordering::Release);
-                None
+                CompletionHighlights::default()
             },
         )
         .await;
         let elapsed = began.elapsed();
 
-        assert!(selected.is_none());
+        assert!(selected.play_of_the_game.is_none());
+        assert!(selected.highlight_reel.is_none());
         assert!(
             started.load(Ordering::Acquire),
             "worker should consume the remainder of the shared deadline"
@@ -5820,6 +5867,7 @@ mod tests {
                 recording_canonical_bytes: None,
                 recording_journal: Some(recording_journal),
                 play_of_the_game: None,
+                highlight_reel: None,
                 final_state,
                 effects: vec![CompletionEffect::PersistGame { id: "game".into() }],
             };
@@ -9677,6 +9725,60 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn a_human_beating_an_ai_backfilled_team_is_rated_and_the_ai_is_not() -> Result<()> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut state = GameState::new(
+            40,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(7),
+            now - 1_000,
+        );
+        let human = state.add_player(101, Some("human".into()))?;
+        let ai = state.add_ai_player(common::AiDifficulty::Medium, Some(common::TeamId(1)))?;
+        let ai_user_id = ai.user_id;
+        let team_scores = state
+            .team_scores
+            .as_mut()
+            .context("team match keeps team scores")?;
+        team_scores.insert(common::TeamId(0), 5);
+        team_scores.insert(common::TeamId(1), 1);
+        state.status = GameStatus::Complete {
+            winning_snake_id: Some(human.snake_id),
+        };
+
+        let db = UnusedDatabase::fail_mmr_reads(0);
+        let record = materialize_completion(&db, 17, 7, 1, state, now).await?;
+        let human_delta = record.effects.iter().find_map(|effect| match effect {
+            CompletionEffect::AddMmr {
+                user_id: 101,
+                delta,
+                ..
+            } => Some(*delta),
+            _ => None,
+        });
+        assert!(
+            human_delta.is_some_and(|delta| delta > 0),
+            "the winning human gains MMR, got {human_delta:?}"
+        );
+        assert!(record.effects.iter().any(|effect| matches!(
+            effect,
+            CompletionEffect::UpdateRanking {
+                user_id: 101,
+                won: true,
+                ..
+            }
+        )));
+        assert!(!record.effects.iter().any(|effect| matches!(
+            effect,
+            CompletionEffect::AddMmr { user_id, .. } | CompletionEffect::UpdateRanking { user_id, .. }
+                if *user_id == ai_user_id
+        )));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn pending_completion_failure_does_not_starve_later_ids() {
         let mut attempted = Vec::new();
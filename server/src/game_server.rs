@@ -59,7 +59,7 @@ use crate::resilience_metrics;
 use crate::resilience_metrics::spawn_resilience_metrics;
 use crate::{
     db::{Database, ServerRegistration},
-    matchmaking::{run_game_created_outbox_loop, run_matchmaking_loop},
+    matchmaking::{AiBackfillConfig, run_game_created_outbox_loop, run_matchmaking_loop},
     redis_keys::RedisKeys,
     replication::GameEventRouter,
     ws_server::JwtVerifier,
@@ -261,6 +261,9 @@ pub struct GameServerConfig {
     pub player_idle_config: PlayerIdleConfig,
     /// Maximum time a new match waits for every player to confirm readiness.
     pub match_ready_window_ms: i64,
+    /// When and how thin quickmatch queues are filled with AI players; `None`
+    /// leaves every seat to people.
+    pub ai_backfill: Option<AiBackfillConfig>,
     /// Enables integration-test lifecycle behavior: readiness is still fully
     /// exercised, but startup omits a fixed grace sleep and shutdown uses
     /// bounded cancellation instead of production traffic/lease handoff.
@@ -437,6 +440,7 @@ impl GameServer {
             hosted_services,
             player_idle_config,
             match_ready_window_ms,
+            ai_backfill,
             test_mode,
             ads_config,
         } = config;
@@ -683,7 +687,9 @@ impl GameServer {
         handles.push(tokio::spawn(async move {
             let mm = match_matchmaking_manager.lock().await.clone();
             drop(match_matchmaking_manager); // Drop the lock
-            let result = run_matchmaking_loop(mm, match_token, match_lobby_manager, match_db).await;
+            let result =
+                run_matchmaking_loop(mm, match_token, match_lobby_manager, match_db, ai_backfill)
+                    .await;
             if !match_exit_token.is_cancelled() {
                 match_lifecycle.mark_critical_failure();
                 let reason = match result {
@@ -1614,6 +1620,7 @@ pub async fn start_test_server_with_grpc_options(
         boost_config: BoostConfig::default(),
         player_idle_config: PlayerIdleConfig::default(),
         match_ready_window_ms,
+        ai_backfill: None,
         test_mode,
         ads_config,
     };
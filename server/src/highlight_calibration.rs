@@ -1,18 +1,18 @@
 //! Deterministic offline corpus generation for Play-of-the-Game calibration.
 //!
 //! This runs the authoritative `GameState` simulation with the exact decision
-//! function used by the networked bot. It deliberately avoids the HTTP and
-//! persistence layers: a fixed seed and corpus index are enough to reproduce
+//! function used by the networked bot, or any other registered bot policy. It
+//! deliberately avoids the HTTP and persistence layers: a fixed seed and corpus index are enough to reproduce
 //! every accepted command, engine event, replay hash, and highlight result.
 
 use anyhow::{Context, Result, bail, ensure};
 use common::{
-    ClientCommandIdentityV2, CommandId, DeathCause, GAME_RECORDING_FORMAT_VERSION,
-    GAMEPLAY_REPLAY_VERSION, GameCommand, GameCommandMessage, GameEvent, GameRecordingV1,
-    GameState, GameStatus, GameType, HighlightClip, HighlightConfig, HighlightReason,
-    HighlightScoreBreakdown, HighlightWindow, MAX_HIGHLIGHT_SELECTION_TICKS, QueueMode,
-    RecordedGameMessage, ReplayAnchor, ReplayVisibility, calculate_ai_command,
-    score_highlight_candidate,
+    BotPolicy, BotPolicyRegistry, ClientCommandIdentityV2, CommandId, DEFAULT_BOT_POLICY,
+    DeathCause, GAME_RECORDING_FORMAT_VERSION, GAMEPLAY_REPLAY_VERSION, GameCommand,
+    GameCommandMessage, GameEvent, GameRecordingV1, GameState, GameStatus, GameType, HighlightClip,
+    HighlightConfig, HighlightReason, HighlightScoreBreakdown, HighlightWindow,
+    MAX_HIGHLIGHT_SELECTION_TICKS, QueueMode, RecordedGameMessage, ReplayAnchor, ReplayVisibility,
+    SearchBudget, SearchProfile, score_highlight_candidate,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
@@ -38,6 +38,10 @@ pub enum HighlightCategory {
     Banking,
     Combo,
     Frenzy,
+    /// Comebacks and buzzer beaters: scores that swung the match.
+    Clutch,
+    /// Survival streaks and trap escapes: staying alive against the odds.
+    Survival,
 }
 
 impl HighlightCategory {
@@ -46,9 +50,15 @@ impl HighlightCategory {
             HighlightReason::BoostedCutoff { .. }
             | HighlightReason::TrapKill { .. }
             | HighlightReason::Demolition { .. } => Self::Demolition,
-            HighlightReason::GoalRun { .. } => Self::Banking,
+            HighlightReason::GoalRun { .. }
+            | HighlightReason::ContestedCapture { .. }
+            | HighlightReason::FlagCapture { .. } => Self::Banking,
             HighlightReason::ComboFrenzy { .. } => Self::Combo,
             HighlightReason::FeedingFrenzy { .. } => Self::Frenzy,
+            HighlightReason::Comeback { .. } | HighlightReason::BuzzerBeater { .. } => Self::Clutch,
+            HighlightReason::SurvivalStreak { .. } | HighlightReason::TrapEscape { .. } => {
+                Self::Survival
+            }
         }
     }
 }
@@ -58,6 +68,15 @@ pub struct BotCorpusSpec {
     pub games: usize,
     pub seed_base: u64,
     pub review_count: usize,
+    /// Play every bot by this `BotPolicyRegistry::builtin` policy instead of
+    /// the live bot's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+    /// Give the `search` policy this many simulated ticks per decision. The
+    /// budget is counted in ticks rather than time so the corpus still
+    /// reproduces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub search_budget_ticks: Option<u32>,
 }
 
 impl Default for BotCorpusSpec {
@@ -66,6 +85,8 @@ impl Default for BotCorpusSpec {
             games: DEFAULT_CORPUS_GAME_COUNT,
             seed_base: DEFAULT_CORPUS_SEED,
             review_count: DEFAULT_REVIEW_COUNT,
+            policy: None,
+            search_budget_ticks: None,
         }
     }
 }
@@ -241,15 +262,25 @@ struct ReviewCandidate {
 /// The latter supplies the PRD's two-minute cohort because the stock bot
 /// finishes every sampled match in under two minutes. Score limits, engine
 /// ticks, commands, collisions, and replay events remain canonical.
+/// A named `policy` swaps every bot's policy and names the corpus after it.
 pub fn run_bot_corpus(spec: &BotCorpusSpec, config: &HighlightConfig) -> Result<CalibrationRun> {
     ensure!(spec.games > 0, "bot corpus needs at least one game");
     ensure!(spec.review_count > 0, "review count needs to be positive");
+    ensure!(
+        spec.search_budget_ticks != Some(0),
+        "search budget needs to be positive"
+    );
+    let mut registry = BotPolicyRegistry::builtin();
+    if let Some(ticks) = spec.search_budget_ticks {
+        registry.register("search", corpus_search_profile(ticks));
+    }
+    let policy = registry.get(spec.policy.as_deref().unwrap_or(DEFAULT_BOT_POLICY))?;
 
     let mut games = Vec::with_capacity(spec.games);
     let mut review_candidates = Vec::new();
     for corpus_index in 0..spec.games {
         let seed = corpus_seed(spec.seed_base, corpus_index);
-        let generated = generate_game(corpus_index, seed, config)?;
+        let generated = generate_game(corpus_index, seed, config, policy.as_ref())?;
         if let Some(clip) = generated.clip {
             review_candidates.push(ReviewCandidate {
                 summary: generated.summary.clone(),
@@ -274,6 +305,8 @@ pub fn run_bot_corpus(spec: &BotCorpusSpec, config: &HighlightConfig) -> Result<
         HighlightCategory::Banking,
         HighlightCategory::Combo,
         HighlightCategory::Frenzy,
+        HighlightCategory::Clutch,
+        HighlightCategory::Survival,
     ] {
         category_counts.insert(category, 0);
     }
@@ -297,8 +330,21 @@ pub fn run_bot_corpus(spec: &BotCorpusSpec, config: &HighlightConfig) -> Result<
     let category_balance_pass =
         team_mode_winners > 0 && largest_category_share_bps <= MAX_CATEGORY_SHARE_BPS;
 
+    let (policy_id, policy) = match (spec.policy.as_deref(), spec.search_budget_ticks) {
+        (None, _) => (String::new(), "common::calculate_ai_command".to_owned()),
+        (Some(name), ticks) => {
+            let name = name.trim().to_ascii_lowercase();
+            match ticks.filter(|_| name == "search") {
+                Some(ticks) => (
+                    format!("{name}-{ticks}-"),
+                    format!("bot policy `{name}` ({ticks} simulated ticks per decision)"),
+                ),
+                None => (format!("{name}-"), format!("bot policy `{name}`")),
+            }
+        }
+    };
     let corpus_id = format!(
-        "bot-v1-{}-{seed:016x}-rules-{}",
+        "bot-v1-{policy_id}{}-{seed:016x}-rules-{}",
         spec.games,
         config.rules_version,
         seed = spec.seed_base
@@ -309,7 +355,7 @@ pub fn run_bot_corpus(spec: &BotCorpusSpec, config: &HighlightConfig) -> Result<
     let summary = CalibrationSummary {
         schema_version: CALIBRATION_SCHEMA_VERSION,
         corpus_id: corpus_id.clone(),
-        generator: "authoritative GameState + common::calculate_ai_command at live 100 ms cadence (80% immediate; 20% declared 60 s presence-only prelude for long-form cohort)".into(),
+        generator: format!("authoritative GameState + {policy} at live 100 ms cadence (80% immediate; 20% declared 60 s presence-only prelude for long-form cohort)"),
         config: config.clone(),
         games_requested: spec.games,
         games_generated: games.len(),
@@ -351,6 +397,16 @@ pub fn run_bot_corpus(spec: &BotCorpusSpec, config: &HighlightConfig) -> Result<
     })
 }
 
+fn corpus_search_profile(max_ticks: u32) -> SearchProfile {
+    SearchProfile {
+        budget: SearchBudget {
+            max_ticks,
+            time: None,
+        },
+        ..SearchProfile::default()
+    }
+}
+
 fn corpus_seed(seed_base: u64, corpus_index: usize) -> u64 {
     // SplitMix64's output transform gives neighboring corpus indices unrelated
     // engine streams while staying stable across Rust/rand releases.
@@ -365,6 +421,7 @@ fn generate_game(
     corpus_index: usize,
     seed: u64,
     config: &HighlightConfig,
+    policy: &dyn BotPolicy,
 ) -> Result<GeneratedGame> {
     let (per_team, queue_mode, ai_start_delay_ms) = match corpus_index % 5 {
         0 => (1, QueueMode::Quickmatch, 0),
@@ -434,7 +491,7 @@ fn generate_game(
                                 })
                             })
                     } else {
-                        calculate_ai_command(&state, *snake_id)
+                        policy.decide(&state, *snake_id)
                     };
                     command.map(|command| (*user_id, *snake_id, command))
                 })
@@ -505,6 +562,7 @@ fn generate_game(
         messages,
         end_tick,
         end_sync_hash: state.sync_hash(),
+        keyframes: Vec::new(),
     };
     recording
         .verify_end_hash()
@@ -849,6 +907,7 @@ pub fn assert_automatic_acceptance(summary: &CalibrationSummary) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{AiDifficulty, HIGHLIGHT_REEL_MOMENTS, select_match_highlights};
 
     #[test]
     fn seed_derivation_is_stable_and_distinct() {
@@ -865,6 +924,8 @@ mod tests {
             games: 1,
             seed_base: DEFAULT_CORPUS_SEED,
             review_count: 1,
+            policy: None,
+            search_budget_ticks: None,
         };
         let run = run_bot_corpus(&spec, &HighlightConfig::default()).unwrap();
         assert!(
@@ -883,8 +944,20 @@ mod tests {
     #[test]
     fn a_fixed_bot_game_replays_and_scores_identically() {
         let config = HighlightConfig::default();
-        let left = generate_game(0, corpus_seed(DEFAULT_CORPUS_SEED, 0), &config).unwrap();
-        let right = generate_game(0, corpus_seed(DEFAULT_CORPUS_SEED, 0), &config).unwrap();
+        let left = generate_game(
+            0,
+            corpus_seed(DEFAULT_CORPUS_SEED, 0),
+            &config,
+            &AiDifficulty::Medium,
+        )
+        .unwrap();
+        let right = generate_game(
+            0,
+            corpus_seed(DEFAULT_CORPUS_SEED, 0),
+            &config,
+            &AiDifficulty::Medium,
+        )
+        .unwrap();
         assert_eq!(left.summary, right.summary);
         assert_eq!(left.recording.end_sync_hash, right.recording.end_sync_hash);
         assert_eq!(
@@ -893,10 +966,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn a_searching_bot_game_replays_identically_and_names_its_policy() {
+        let config = HighlightConfig::default();
+        let search = corpus_search_profile(160);
+        let seed = corpus_seed(DEFAULT_CORPUS_SEED, 0);
+        let left = generate_game(0, seed, &config, &search).unwrap();
+        let right = generate_game(0, seed, &config, &search).unwrap();
+        assert_eq!(left.summary, right.summary);
+        assert_eq!(left.recording.end_sync_hash, right.recording.end_sync_hash);
+        let heuristic = generate_game(0, seed, &config, &AiDifficulty::Medium).unwrap();
+        assert_ne!(
+            left.recording.end_sync_hash,
+            heuristic.recording.end_sync_hash
+        );
+
+        let run = run_bot_corpus(
+            &BotCorpusSpec {
+                games: 1,
+                seed_base: DEFAULT_CORPUS_SEED,
+                review_count: 1,
+                policy: Some("Search".to_owned()),
+                search_budget_ticks: Some(160),
+            },
+            &config,
+        )
+        .unwrap();
+        assert!(run.summary.corpus_id.starts_with("bot-v1-search-160-1-"));
+        assert!(run.summary.generator.contains("bot policy `search`"));
+        assert_eq!(run.summary.games, vec![left.summary]);
+
+        let unknown = BotCorpusSpec {
+            policy: Some("minimax".to_owned()),
+            ..BotCorpusSpec::default()
+        };
+        assert!(run_bot_corpus(&unknown, &config).is_err());
+    }
+
     #[test]
     fn declared_long_form_cohort_is_completed_active_and_non_trivial() {
         let config = HighlightConfig::default();
-        let game = generate_game(4, corpus_seed(DEFAULT_CORPUS_SEED, 4), &config).unwrap();
+        let game = generate_game(
+            4,
+            corpus_seed(DEFAULT_CORPUS_SEED, 4),
+            &config,
+            &AiDifficulty::Medium,
+        )
+        .unwrap();
         let interesting = game
             .recording
             .messages
@@ -923,4 +1039,63 @@ mod tests {
         let run = run_bot_corpus(&BotCorpusSpec::default(), &HighlightConfig::default()).unwrap();
         assert_automatic_acceptance(&run.summary).unwrap();
     }
+
+    #[test]
+    fn bot_game_reels_lead_with_the_play_of_the_game_and_never_overlap() {
+        let config = HighlightConfig::default();
+        let mut reels_with_several_moments = 0;
+        for corpus_index in 0..5 {
+            let game = generate_game(
+                corpus_index,
+                corpus_seed(DEFAULT_CORPUS_SEED, corpus_index),
+                &config,
+                &AiDifficulty::Medium,
+            )
+            .unwrap();
+            let highlights =
+                select_match_highlights(&game.recording, &config, HIGHLIGHT_REEL_MOMENTS).unwrap();
+            let reel = &highlights.reel;
+            assert_eq!(
+                highlights.play_of_the_game.as_ref().map(|clip| (
+                    clip.star_snake_id,
+                    &clip.window,
+                    clip.end_sync_hash
+                )),
+                game.clip.as_ref().map(|clip| (
+                    clip.star_snake_id,
+                    &clip.window,
+                    clip.end_sync_hash
+                )),
+                "one pass must select the same Play of the Game as select_highlight"
+            );
+            if let Some(clip) = &highlights.play_of_the_game {
+                assert_eq!(reel.moments[0].window, clip.window);
+                assert_eq!(reel.moments[0].score, clip.score);
+            }
+            assert!(reel.moments.len() <= HIGHLIGHT_REEL_MOMENTS);
+            for (index, moment) in reel.moments.iter().enumerate() {
+                assert!(moment.score >= config.minimum_score);
+                for other in &reel.moments[index + 1..] {
+                    assert!(
+                        other.window.end_tick < moment.window.start_tick
+                            || moment.window.end_tick < other.window.start_tick,
+                        "moments {:?} and {:?} overlap",
+                        moment.window,
+                        other.window
+                    );
+                }
+                let best = reel
+                    .player(moment.star_user_id)
+                    .expect("every star in the reel has a best moment");
+                assert!(best.score >= moment.score);
+            }
+            assert!(
+                reel.players
+                    .windows(2)
+                    .all(|pair| pair[0].star_user_id < pair[1].star_user_id)
+            );
+            reels_with_several_moments += usize::from(reel.moments.len() > 1);
+        }
+        assert!(reels_with_several_moments > 0);
+    }
 }
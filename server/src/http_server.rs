@@ -3,15 +3,18 @@ use async_trait::async_trait;
 use axum::{
     Json, Router,
     body::Body,
-    extract::{Path, State, ws::WebSocketUpgrade},
+    extract::{Path, Query, State, ws::WebSocketUpgrade},
     http::{HeaderMap, Request, StatusCode, header},
     middleware,
     response::{IntoResponse, Response},
     routing::{get, options, post, put},
 };
-use common::{GAMEPLAY_REPLAY_VERSION, HIGHLIGHT_CLIP_FORMAT_VERSION, HighlightClip};
+use common::{
+    GAMEPLAY_REPLAY_VERSION, HIGHLIGHT_CLIP_FORMAT_VERSION, HIGHLIGHT_REEL_MOMENTS, HighlightClip,
+    HighlightMoment,
+};
 use serde::Serialize;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, LazyLock, RwLock};
 use std::time::Instant;
 use tokio::net::TcpListener;
 use tokio::task::JoinHandle;
@@ -43,8 +46,9 @@ use crate::lobby_manager::LobbyManager;
 use crate::redis_keys::RedisKeys;
 use crate::region_cache::RegionCache;
 use crate::replay_cache::{ReplayCacheConfig, ValkeyReplayCache};
+use crate::replay_render::{RenderFormat, RenderOptions, render_clip};
 use crate::replay_repository::{ReplayLoadSource, ReplayRepository};
-use crate::replay_store::{ReplayStoreConfig, S3ReplayStore};
+use crate::replay_store::{ReplayObjectMetadata, ReplayStoreConfig, S3ReplayStore};
 use crate::replication::GameEventRouter;
 use crate::user_cache::UserCache;
 use crate::ws_server::{JwtVerifier, handle_websocket};
@@ -52,11 +56,17 @@ use crate::ws_server::{JwtVerifier, handle_websocket};
 use crate::redis_utils::RedisConnection;
 use redis::AsyncCommands;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Semaphore;
 
 const ACTIVE_SERVER_METRIC_TTL_MS: u64 = 10_000;
 const REPLAY_ROUTE_REQUEST_BODY_LIMIT: usize = 1024;
 const MAX_REPLAY_RESPONSE_BYTES: usize = 8 * 1024 * 1024;
 const MAX_HIGHLIGHT_RESPONSE_BYTES: usize = 256 * 1024;
+/// Highlight renders re-simulate the clip on a blocking thread. Beyond this
+/// many at once the route sheds load instead of queueing CPU work.
+const HIGHLIGHT_RENDER_CONCURRENCY: usize = 2;
+static HIGHLIGHT_RENDER_SLOTS: LazyLock<Arc<Semaphore>> =
+    LazyLock::new(|| Arc::new(Semaphore::new(HIGHLIGHT_RENDER_CONCURRENCY)));
 
 /// A dependency-free listener that serves liveness immediately and installs
 /// the full application router once Redis-dependent bootstrap has converged.
@@ -492,6 +502,14 @@ enum HighlightApiResponse {
     Ready {
         play_of_the_game: Box<HighlightClip>,
     },
+    #[serde(rename = "ready")]
+    Moments {
+        moments: Vec<HighlightMoment>,
+    },
+    #[serde(rename = "ready")]
+    PlayerMoment {
+        moment: Box<HighlightMoment>,
+    },
     Unavailable,
 }
 
@@ -514,7 +532,23 @@ fn build_replay_routes(
 fn replay_route_template() -> Router<ReplayApiState> {
     Router::new()
         .route("/api/games/:game_id/highlight", get(get_game_highlight))
+        .route(
+            "/api/games/:game_id/highlight/moments",
+            get(get_game_highlight_moments),
+        )
+        .route(
+            "/api/games/:game_id/highlight/render",
+            get(get_game_highlight_render),
+        )
+        .route(
+            "/api/games/:game_id/highlight/players/:user_id",
+            get(get_player_highlight),
+        )
         .route("/api/games/:game_id/replay", get(get_game_replay))
+        .route(
+            "/api/games/:game_id/replay/keyframe",
+            get(get_game_replay_keyframe),
+        )
         .layer(axum::extract::DefaultBodyLimit::max(
             REPLAY_ROUTE_REQUEST_BODY_LIMIT,
         ))
@@ -577,6 +611,46 @@ fn highlight_api_response(payload: HighlightApiResponse) -> Response {
     response
 }
 
+/// Load the game row behind a highlight route. `None` means the highlight is
+/// still pending.
+async fn completed_highlight_game(state: &ReplayApiState, game_id: i32) -> Result<Option<Game>> {
+    // The terminal snapshot is committed to Valkey before PersistGame creates
+    // the DynamoDB META row. A first post-match poll therefore commonly races
+    // this read. Keep a missing row pending; the bounded client poll will
+    // settle to unavailable if the game truly does not exist or persistence
+    // never completes.
+    Ok(state
+        .games
+        .get_game(game_id)
+        .await?
+        .filter(|game| game.status == "complete"))
+}
+
+fn highlight_metadata_error(game_id: i32, error: anyhow::Error) -> Response {
+    warn!(game_id, %error, "Failed to load game highlight metadata");
+    json_api_error(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "highlight metadata unavailable",
+    )
+}
+
+fn clip_shape_is_compatible(clip: &HighlightClip, game_id: i32) -> bool {
+    clip.game_id == game_id as u32
+        && clip.clip_format_version == HIGHLIGHT_CLIP_FORMAT_VERSION
+        && clip.gameplay_version == GAMEPLAY_REPLAY_VERSION
+        && clip.anchor.tick <= clip.window.start_tick
+        && clip.window.start_tick <= clip.window.focus_tick
+        && clip.window.focus_tick <= clip.window.end_tick
+        && clip
+            .messages
+            .windows(2)
+            .all(|pair| (pair[0].tick, pair[0].sequence) < (pair[1].tick, pair[1].sequence))
+        && clip
+            .messages
+            .iter()
+            .all(|message| message.tick >= clip.anchor.tick && message.tick <= clip.window.end_tick)
+}
+
 async fn get_game_highlight(
     State(state): State<ReplayApiState>,
     Path(raw_game_id): Path<String>,
@@ -584,42 +658,15 @@ async fn get_game_highlight(
     let Some(game_id) = parse_public_game_id(&raw_game_id) else {
         return json_api_error(StatusCode::BAD_REQUEST, "invalid game id");
     };
-    let game = match state.games.get_game(game_id).await {
+    let game = match completed_highlight_game(&state, game_id).await {
         Ok(Some(game)) => game,
-        // The terminal snapshot is committed to Valkey before PersistGame
-        // creates the DynamoDB META row. A first post-match poll therefore
-        // commonly races this read. Keep a missing row pending; the bounded
-        // client poll will settle to unavailable if the game truly does not
-        // exist or persistence never completes.
         Ok(None) => return highlight_api_response(HighlightApiResponse::Pending),
-        Err(error) => {
-            warn!(game_id, %error, "Failed to load game highlight metadata");
-            return json_api_error(
-                StatusCode::SERVICE_UNAVAILABLE,
-                "highlight metadata unavailable",
-            );
-        }
+        Err(error) => return highlight_metadata_error(game_id, error),
     };
-    if game.status != "complete" {
-        return highlight_api_response(HighlightApiResponse::Pending);
-    }
     let Some(clip) = game.play_of_the_game else {
         return highlight_api_response(HighlightApiResponse::Unavailable);
     };
-    let shape_is_compatible = clip.game_id == game_id as u32
-        && clip.clip_format_version == HIGHLIGHT_CLIP_FORMAT_VERSION
-        && clip.gameplay_version == GAMEPLAY_REPLAY_VERSION
-        && clip.anchor.tick <= clip.window.start_tick
-        && clip.window.start_tick <= clip.window.focus_tick
-        && clip.window.focus_tick <= clip.window.end_tick
-        && clip
-            .messages
-            .windows(2)
-            .all(|pair| (pair[0].tick, pair[0].sequence) < (pair[1].tick, pair[1].sequence))
-        && clip.messages.iter().all(|message| {
-            message.tick >= clip.anchor.tick && message.tick <= clip.window.end_tick
-        });
-    if !shape_is_compatible {
+    if !clip_shape_is_compatible(&clip, game_id) {
         warn!(
             game_id,
             clip_game_id = clip.game_id,
@@ -651,40 +698,249 @@ async fn get_game_highlight(
     }
 }
 
-async fn get_game_replay(
+#[derive(serde::Deserialize)]
+struct HighlightRenderQuery {
+    /// `gif` (the default) or `apng`.
+    format: Option<String>,
+}
+
+/// The Play of the Game as an animated GIF or APNG, for share previews that
+/// cannot run the browser renderer. A clip's render never changes once the
+/// completion is committed, so it is cacheable indefinitely.
+async fn get_game_highlight_render(
     State(state): State<ReplayApiState>,
     Path(raw_game_id): Path<String>,
-    headers: HeaderMap,
+    Query(query): Query<HighlightRenderQuery>,
 ) -> Response {
     let Some(game_id) = parse_public_game_id(&raw_game_id) else {
         return json_api_error(StatusCode::BAD_REQUEST, "invalid game id");
     };
+    let format = match query.format.as_deref().map(RenderFormat::parse) {
+        None => RenderFormat::Gif,
+        Some(Ok(format)) => format,
+        Some(Err(_)) => return json_api_error(StatusCode::BAD_REQUEST, "invalid render format"),
+    };
+    let clip = match completed_highlight_game(&state, game_id).await {
+        Ok(game) => game.and_then(|game| game.play_of_the_game),
+        Err(error) => return highlight_metadata_error(game_id, error),
+    };
+    let Some(clip) = clip else {
+        return json_api_error(StatusCode::NOT_FOUND, "highlight unavailable");
+    };
+    if !clip_shape_is_compatible(&clip, game_id) {
+        warn!(
+            game_id,
+            clip_game_id = clip.game_id,
+            "Rejecting incompatible highlight metadata"
+        );
+        return json_api_error(StatusCode::NOT_FOUND, "highlight unavailable");
+    }
+    let Ok(permit) = HIGHLIGHT_RENDER_SLOTS.clone().try_acquire_owned() else {
+        let mut response = json_api_error(StatusCode::SERVICE_UNAVAILABLE, "highlight render busy");
+        response.headers_mut().insert(
+            header::RETRY_AFTER,
+            axum::http::HeaderValue::from_static("2"),
+        );
+        return response;
+    };
+    let options = RenderOptions {
+        format,
+        ..RenderOptions::default()
+    };
+    let rendered = tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        render_clip(&clip, &options)
+    })
+    .await
+    .context("highlight render task failed")
+    .and_then(|rendered| rendered);
+    match rendered {
+        Ok(image) => (
+            [
+                (header::CONTENT_TYPE, format.content_type()),
+                (header::CACHE_CONTROL, "public, max-age=86400, immutable"),
+            ],
+            image,
+        )
+            .into_response(),
+        Err(error) => {
+            warn!(game_id, %error, "Failed to render highlight");
+            json_api_error(StatusCode::INTERNAL_SERVER_ERROR, "highlight render failed")
+        }
+    }
+}
+
+fn moment_window_is_compatible(moment: &HighlightMoment) -> bool {
+    moment.window.start_tick <= moment.window.focus_tick
+        && moment.window.focus_tick <= moment.window.end_tick
+}
+
+/// The match's ranked, non-overlapping moments. Each one is a window into
+/// the game's replay rather than a clip of its own, so clients play it
+/// through `/replay`.
+async fn get_game_highlight_moments(
+    State(state): State<ReplayApiState>,
+    Path(raw_game_id): Path<String>,
+) -> Response {
+    let Some(game_id) = parse_public_game_id(&raw_game_id) else {
+        return json_api_error(StatusCode::BAD_REQUEST, "invalid game id");
+    };
+    let game = match completed_highlight_game(&state, game_id).await {
+        Ok(Some(game)) => game,
+        Ok(None) => return highlight_api_response(HighlightApiResponse::Pending),
+        Err(error) => return highlight_metadata_error(game_id, error),
+    };
+    let Some(reel) = game.highlight_reel.filter(|reel| !reel.moments.is_empty()) else {
+        return highlight_api_response(HighlightApiResponse::Unavailable);
+    };
+    if reel.moments.len() > HIGHLIGHT_REEL_MOMENTS
+        || !reel.moments.iter().all(moment_window_is_compatible)
+    {
+        warn!(game_id, "Rejecting incompatible highlight reel metadata");
+        return highlight_api_response(HighlightApiResponse::Unavailable);
+    }
+    highlight_api_response(HighlightApiResponse::Moments {
+        moments: reel.moments,
+    })
+}
+
+/// One participant's best moment of the match. A player whose play never
+/// cleared the highlight threshold has none.
+async fn get_player_highlight(
+    State(state): State<ReplayApiState>,
+    Path((raw_game_id, raw_user_id)): Path<(String, String)>,
+) -> Response {
+    let Some(game_id) = parse_public_game_id(&raw_game_id) else {
+        return json_api_error(StatusCode::BAD_REQUEST, "invalid game id");
+    };
+    // User ids share the database's positive id range.
+    let Some(user_id) = parse_public_game_id(&raw_user_id) else {
+        return json_api_error(StatusCode::BAD_REQUEST, "invalid user id");
+    };
+    let game = match completed_highlight_game(&state, game_id).await {
+        Ok(Some(game)) => game,
+        Ok(None) => return highlight_api_response(HighlightApiResponse::Pending),
+        Err(error) => return highlight_metadata_error(game_id, error),
+    };
+    let moment = game
+        .highlight_reel
+        .and_then(|reel| {
+            reel.players
+                .into_iter()
+                .find(|moment| moment.star_user_id == user_id as u32)
+        })
+        .filter(moment_window_is_compatible);
+    match moment {
+        Some(moment) => highlight_api_response(HighlightApiResponse::PlayerMoment {
+            moment: Box::new(moment),
+        }),
+        None => highlight_api_response(HighlightApiResponse::Unavailable),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ReplayKeyframeQuery {
+    tick: u32,
+}
+
+/// The stored recording of a completed game and the repository serving it,
+/// or the error response for a game that has none.
+async fn completed_replay_object(
+    state: ReplayApiState,
+    raw_game_id: &str,
+) -> Result<(i32, ReplayObjectMetadata, Arc<ReplayRepository>), Response> {
+    let Some(game_id) = parse_public_game_id(raw_game_id) else {
+        return Err(json_api_error(StatusCode::BAD_REQUEST, "invalid game id"));
+    };
     let game = match state.games.get_game(game_id).await {
         Ok(Some(game)) => game,
-        Ok(None) => return json_api_error(StatusCode::NOT_FOUND, "replay unavailable"),
+        Ok(None) => return Err(json_api_error(StatusCode::NOT_FOUND, "replay unavailable")),
         Err(error) => {
             warn!(game_id, %error, "Failed to load game replay metadata");
-            return json_api_error(
+            return Err(json_api_error(
                 StatusCode::SERVICE_UNAVAILABLE,
                 "replay metadata unavailable",
-            );
+            ));
         }
     };
     if game.status != "complete" {
-        return json_api_error(StatusCode::NOT_FOUND, "replay unavailable");
+        return Err(json_api_error(StatusCode::NOT_FOUND, "replay unavailable"));
     }
     let Some(metadata) = game.replay_object else {
-        return json_api_error(StatusCode::NOT_FOUND, "replay unavailable");
+        return Err(json_api_error(StatusCode::NOT_FOUND, "replay unavailable"));
     };
     let Some(repository) = state.repository else {
         warn!(
             game_id,
             "Replay metadata exists but replay storage is disabled"
         );
-        return json_api_error(
+        return Err(json_api_error(
             StatusCode::SERVICE_UNAVAILABLE,
             "replay storage unavailable",
-        );
+        ));
+    };
+    Ok((game_id, metadata, repository))
+}
+
+/// The latest verified keyframe at or before `tick`, range-read from the
+/// archive. A player seeking a long recording starts there and replays only
+/// the messages after it.
+async fn get_game_replay_keyframe(
+    State(state): State<ReplayApiState>,
+    Path(raw_game_id): Path<String>,
+    Query(query): Query<ReplayKeyframeQuery>,
+) -> Response {
+    let (game_id, metadata, repository) = match completed_replay_object(state, &raw_game_id).await {
+        Ok(found) => found,
+        Err(response) => return response,
+    };
+    let loaded = match repository.get_keyframe(&metadata, query.tick).await {
+        Ok(Some(loaded)) => loaded,
+        Ok(None) => return json_api_error(StatusCode::NOT_FOUND, "replay keyframe unavailable"),
+        Err(error) => {
+            warn!(game_id, %error, "Verified replay keyframe lookup failed");
+            return json_api_error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "replay storage unavailable",
+            );
+        }
+    };
+    let bytes = match serde_json::to_vec(&loaded.keyframe) {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            warn!(game_id, %error, "Failed to encode replay keyframe");
+            return json_api_error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "replay keyframe unavailable",
+            );
+        }
+    };
+    let source = match loaded.source {
+        ReplayLoadSource::Cache => "cache",
+        ReplayLoadSource::ObjectStore => "object_store",
+    };
+    (
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (header::CACHE_CONTROL, "public, max-age=300"),
+            (
+                axum::http::HeaderName::from_static("x-snaketron-replay-source"),
+                source,
+            ),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+async fn get_game_replay(
+    State(state): State<ReplayApiState>,
+    Path(raw_game_id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let (game_id, metadata, repository) = match completed_replay_object(state, &raw_game_id).await {
+        Ok(found) => found,
+        Err(response) => return response,
     };
     let total_bytes = match repository.recording_length(&metadata).await {
         Ok(Some(total)) => total,
@@ -1169,7 +1425,8 @@ mod deferred_http_tests {
     use common::{
         GAME_RECORDING_FORMAT_VERSION, GAMEPLAY_REPLAY_VERSION, GameRecordingV1, GameState,
         GameStatus, GameType, HighlightConfig, HighlightPresentation, HighlightReason,
-        HighlightScoreBreakdown, HighlightWindow, QueueMode, ReplayAnchor, ReplayVisibility,
+        HighlightScoreBreakdown, HighlightWindow, QueueMode, ReplayAnchor, ReplayKeyframe,
+        ReplayVisibility,
     };
     use std::collections::HashMap;
     use std::time::Duration;
@@ -1213,6 +1470,7 @@ mod deferred_http_tests {
             messages: Vec::new(),
             end_tick: state.tick,
             end_sync_hash: state.sync_hash(),
+            keyframes: Vec::new(),
         }
     }
 
@@ -1262,6 +1520,7 @@ mod deferred_http_tests {
             game_code: None,
             replay_object: None,
             play_of_the_game: None,
+            highlight_reel: None,
             news_eligible: false,
         }
     }
@@ -1362,6 +1621,70 @@ mod deferred_http_tests {
         assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
     }
 
+    #[tokio::test]
+    async fn moment_and_player_highlight_routes_serve_the_persisted_reel() {
+        let moment = |user_id: u32, focus_tick: u32| HighlightMoment {
+            star_user_id: user_id,
+            star_snake_id: user_id - 1,
+            star_name: format!("Player {user_id}"),
+            reason: common::HighlightReason::ComboFrenzy { max_chain: 5 },
+            score: 160,
+            breakdown: Default::default(),
+            window: common::HighlightWindow {
+                start_tick: focus_tick - 60,
+                end_tick: focus_tick + 30,
+                focus_tick,
+            },
+        };
+        let waiting = replay_test_game(1, "playing");
+        let legacy = replay_test_game(2, "complete");
+        let mut ready = replay_test_game(3, "complete");
+        ready.highlight_reel = Some(common::HighlightReel {
+            moments: vec![moment(1, 400), moment(2, 100)],
+            players: vec![moment(1, 400), moment(2, 100)],
+        });
+        let app = replay_test_app(
+            [(1, waiting), (2, legacy), (3, ready)]
+                .into_iter()
+                .collect(),
+            None,
+        );
+
+        let pending = get_path(app.clone(), "/api/games/1/highlight/moments").await;
+        assert_eq!(pending.headers()[header::RETRY_AFTER], "1");
+        assert_eq!(response_json(pending).await["status"], "pending");
+        let missing = get_path(app.clone(), "/api/games/999/highlight/players/1").await;
+        assert_eq!(response_json(missing).await["status"], "pending");
+
+        for path in [
+            "/api/games/2/highlight/moments",
+            "/api/games/2/highlight/players/1",
+            "/api/games/3/highlight/players/3",
+        ] {
+            let unavailable = get_path(app.clone(), path).await;
+            assert_eq!(unavailable.status(), StatusCode::OK);
+            assert_eq!(response_json(unavailable).await["status"], "unavailable");
+        }
+
+        let moments = get_path(app.clone(), "/api/games/3/highlight/moments").await;
+        assert_eq!(
+            moments.headers()[header::CACHE_CONTROL],
+            "public, max-age=300"
+        );
+        let moments = response_json(moments).await;
+        assert_eq!(moments["status"], "ready");
+        assert_eq!(moments["moments"][0]["star_user_id"], 1);
+        assert_eq!(moments["moments"][1]["window"]["focus_tick"], 100);
+
+        let player =
+            response_json(get_path(app.clone(), "/api/games/3/highlight/players/2").await).await;
+        assert_eq!(player["status"], "ready");
+        assert_eq!(player["moment"]["star_user_id"], 2);
+
+        let invalid = get_path(app, "/api/games/3/highlight/players/0").await;
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn highlight_route_uses_precommit_verification_without_request_path_resimulation() {
         let recording = replay_test_recording(4);
@@ -1380,6 +1703,63 @@ mod deferred_http_tests {
         assert_eq!(response_json(response).await["status"], "ready");
     }
 
+    #[tokio::test]
+    async fn highlight_render_route_serves_a_verified_animation_in_either_format() {
+        let recording = replay_test_recording(5);
+        let mut game = replay_test_game(5, "complete");
+        game.play_of_the_game = Some(replay_test_highlight(&recording));
+        let mut corrupt = replay_test_game(6, "complete");
+        let mut clip = replay_test_highlight(&replay_test_recording(6));
+        clip.end_sync_hash ^= 1;
+        corrupt.play_of_the_game = Some(clip);
+        let app = replay_test_app(
+            [
+                (2, replay_test_game(2, "complete")),
+                (5, game),
+                (6, corrupt),
+            ]
+            .into_iter()
+            .collect(),
+            None,
+        );
+
+        for (path, content_type, signature) in [
+            ("/api/games/5/highlight/render", "image/gif", &b"GIF89a"[..]),
+            (
+                "/api/games/5/highlight/render?format=apng",
+                "image/apng",
+                &b"\x89PNG"[..],
+            ),
+        ] {
+            let response = get_path(app.clone(), path).await;
+            assert_eq!(response.status(), StatusCode::OK, "{path}");
+            assert_eq!(response.headers()[header::CONTENT_TYPE], content_type);
+            assert_eq!(
+                response.headers()[header::CACHE_CONTROL],
+                "public, max-age=86400, immutable"
+            );
+            let image = axum::body::to_bytes(response.into_body(), MAX_REPLAY_RESPONSE_BYTES)
+                .await
+                .unwrap();
+            assert!(image.starts_with(signature), "{path}");
+        }
+
+        // Unlike the JSON route, rendering re-simulates the clip, so a clip
+        // that no longer reaches its end hash fails instead of drawing a lie.
+        let corrupt = get_path(app.clone(), "/api/games/6/highlight/render").await;
+        assert_eq!(corrupt.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let bad_format = get_path(app.clone(), "/api/games/5/highlight/render?format=webm").await;
+        assert_eq!(bad_format.status(), StatusCode::BAD_REQUEST);
+        for path in [
+            "/api/games/2/highlight/render",
+            "/api/games/999/highlight/render",
+        ] {
+            let unavailable = get_path(app.clone(), path).await;
+            assert_eq!(unavailable.status(), StatusCode::NOT_FOUND, "{path}");
+        }
+    }
+
     #[tokio::test]
     async fn anonymous_replay_route_returns_verified_json_through_cache_aside() {
         let recording = replay_test_recording(7);
@@ -1413,6 +1793,53 @@ mod deferred_http_tests {
         assert_eq!(cached.headers()["x-snaketron-replay-source"], "cache");
     }
 
+    #[tokio::test]
+    async fn replay_keyframe_route_serves_the_latest_keyframe_at_or_before_the_tick() {
+        let mut recording = replay_test_recording(10);
+        let state = recording.anchors[0].state.clone();
+        recording.keyframes = [10, 20]
+            .into_iter()
+            .map(|tick| {
+                let mut state = state.clone();
+                state.tick = tick;
+                ReplayKeyframe {
+                    tick,
+                    sequence: 0,
+                    sync_hash: state.sync_hash(),
+                    state,
+                }
+            })
+            .collect();
+        recording.end_tick = 30;
+        let bytes = crate::completion::canonical_json_bytes(&recording).unwrap();
+        let store = Arc::new(InMemoryReplayStore::new());
+        let metadata = store.put_recording(10, &bytes).await.unwrap();
+        let repository = Arc::new(ReplayRepository::new(
+            store,
+            Arc::new(InMemoryReplayCache::new()),
+        ));
+        let mut game = replay_test_game(10, "complete");
+        game.replay_object = Some(metadata);
+        let app = replay_test_app([(10, game)].into_iter().collect(), Some(repository));
+
+        let response = get_path(app.clone(), "/api/games/10/replay/keyframe?tick=25").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let keyframe: ReplayKeyframe = serde_json::from_slice(
+            &axum::body::to_bytes(response.into_body(), MAX_REPLAY_RESPONSE_BYTES)
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(keyframe.tick, 20);
+        assert_eq!(keyframe.sync_hash, recording.keyframes[1].sync_hash);
+
+        let before_first = get_path(app.clone(), "/api/games/10/replay/keyframe?tick=9").await;
+        assert_eq!(before_first.status(), StatusCode::NOT_FOUND);
+        let missing_tick = get_path(app, "/api/games/10/replay/keyframe").await;
+        assert_eq!(missing_tick.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn replay_route_relies_on_persist_game_verification_without_resimulation() {
         let store = Arc::new(InMemoryReplayStore::new());
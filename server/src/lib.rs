@@ -34,6 +34,7 @@ pub mod region_cache;
 pub mod rematch;
 pub mod replay;
 pub mod replay_cache;
+pub mod replay_render;
 pub mod replay_repository;
 pub mod replay_store;
 pub mod replication;
@@ -9,38 +9,11 @@ const PHASE_ACTIVE: u8 = 1;
 const PHASE_DRAINING: u8 = 2;
 const PHASE_STOPPING: u8 = 3;
 
+pub use common::WS_PROTOCOL_VERSION;
+
 /// Capabilities advertised only after the server has actually authenticated a
 /// WebSocket. Keep these stable: clients use them to decide whether a planned
 /// make-before-break handoff is supported.
-///
-/// Version 12 adds the rematch: an opt-in on the results card that converges
-/// everyone who ticks it onto one lobby, plus the live roster of who is still
-/// looking at that card.
-///
-/// Version 11 adds the social layer: a per-region online-player roster and
-/// player-to-player challenges, both server-pushed. It also makes finished
-/// matches publicly addressable, though that surface is HTTP and does not
-/// cross this socket.
-///
-/// Version 10 is the same kind of collision as version 7 above, and is
-/// resolved the same way. Two branches each shipped a wire change and each
-/// independently claimed 9; git merged the constants silently, so 9 would
-/// have named two mutually unintelligible protocols. Version 10 carries
-/// both: deterministic death causes on `SnakeDied` events, and the
-/// server-coordinated, provider-neutral lobby ad-break barrier with runtime
-/// advertisement configuration and per-session distribution routing.
-///
-/// Version 8 adds authoritative combo configuration/state and enriched food
-/// events so clients can predict growth and render the same food value.
-///
-/// Version 7 carries the pre-match readiness gate and the inactivity protocol
-/// together. Each shipped a wire change and each independently claimed version
-/// 6, so 6 would now name two mutually unintelligible protocols: a client
-/// built for either one would pass this hard-cutover equality check and then
-/// fail to understand half the messages it receives. This must stay in lockstep
-/// with `GAMEPLAY_PROTOCOL_VERSION` in client/web/constants.ts; the bot and
-/// loadtest clients import this constant directly so they cannot drift at all.
-pub const WS_PROTOCOL_VERSION: u16 = 12;
 pub const WS_BASE_CAPABILITIES: &[&str] = &[
     "explicit-auth-v1",
     "planned-drain-v1",
@@ -53,6 +26,9 @@ pub const WS_BASE_CAPABILITIES: &[&str] = &[
     "ad-break-v1",
     "social-presence-v1",
     "rematch-v1",
+    "binary-game-events-v1",
+    "snapshot-delta-v1",
+    common::SPECTATE_CAPABILITY,
 ];
 
 /// A planned task-removal notification. The absolute deadline avoids clients
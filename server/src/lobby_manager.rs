@@ -1075,6 +1075,9 @@ pub fn lobby_membership_valid_until_ms<'a>(
 }
 
 /// Host-selected matchmaking preferences for a lobby
+/// Most AI opponents one solo practice game seats.
+pub const MAX_PRACTICE_AI_OPPONENTS: u8 = 3;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-gen", derive(ts_rs::TS))]
 #[cfg_attr(feature = "ts-gen", ts(export))]
@@ -1082,6 +1085,11 @@ pub struct LobbyPreferences {
     // TODO: Use an enum for selected modes instead of a string
     pub selected_modes: Vec<String>,
     pub competitive: bool,
+    /// AI opponents to practise against when the lobby plays solo or a
+    /// custom game.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts-gen", ts(optional))]
+    pub ai_opponents: Option<common::AiOpponents>,
 }
 
 impl Default for LobbyPreferences {
@@ -1089,6 +1097,7 @@ impl Default for LobbyPreferences {
         Self {
             selected_modes: vec!["duel".to_string()],
             competitive: false,
+            ai_opponents: None,
         }
     }
 }
@@ -2406,11 +2415,18 @@ impl LobbyManager {
         LobbyPreferences {
             selected_modes: sanitized,
             competitive: preferences.competitive,
+            ai_opponents: preferences
+                .ai_opponents
+                .filter(|request| request.count > 0)
+                .map(|request| common::AiOpponents {
+                    count: request.count.min(MAX_PRACTICE_AI_OPPONENTS),
+                    ..request
+                }),
         }
     }
 
     fn is_valid_lobby_mode(mode: &str) -> bool {
-        matches!(mode, "duel" | "2v2" | "solo" | "ffa")
+        matches!(mode, "duel" | "2v2" | "solo" | "ffa" | "hill" | "ctf")
     }
 
     fn validate_lobby_code(lobby_code: &str) -> Result<()> {
@@ -5,8 +5,12 @@ use server::db::{Database, dynamodb::DynamoDatabase};
 use server::game_server::{
     BOOST_SPEED_MULTIPLIER_ENV, GameServer, GameServerConfig, resolve_boost_config,
 };
+use server::matchmaking::{
+    AI_BACKFILL_DIFFICULTY_ENV, AI_BACKFILL_WAIT_MS_ENV, resolve_ai_backfill_config,
+};
 use server::player_idle::{
-    PLAYER_IDLE_COUNTDOWN_MS_ENV, PLAYER_IDLE_GRACE_MS_ENV, resolve_player_idle_config,
+    PLAYER_IDLE_AUTOPILOT_ENV, PLAYER_IDLE_COUNTDOWN_MS_ENV, PLAYER_IDLE_GRACE_MS_ENV,
+    resolve_player_idle_autopilot, resolve_player_idle_config,
 };
 use server::ws_server::TestJwtVerifier;
 use std::env;
@@ -138,18 +142,40 @@ async fn main() -> Result<()> {
 
     let configured_idle_grace_ms = env::var(PLAYER_IDLE_GRACE_MS_ENV).ok();
     let configured_idle_countdown_ms = env::var(PLAYER_IDLE_COUNTDOWN_MS_ENV).ok();
+    let configured_idle_autopilot = env::var(PLAYER_IDLE_AUTOPILOT_ENV).ok();
     let player_idle_config = resolve_player_idle_config(
         configured_idle_grace_ms.as_deref(),
         configured_idle_countdown_ms.as_deref(),
     )
-    .context("Invalid player inactivity configuration")?;
+    .context("Invalid player inactivity configuration")?
+    .with_autopilot(
+        resolve_player_idle_autopilot(configured_idle_autopilot.as_deref())
+            .context("Invalid player inactivity configuration")?,
+    );
     info!(
         idle_grace_ms = player_idle_config.idle_grace_ms(),
         kick_countdown_ms = player_idle_config.kick_countdown_ms(),
         total_timeout_ms = player_idle_config.total_timeout_ms(),
+        autopilot = ?player_idle_config.autopilot(),
         "Resolved inactivity policy for new multiplayer matches"
     );
 
+    let configured_backfill_wait_ms = env::var(AI_BACKFILL_WAIT_MS_ENV).ok();
+    let configured_backfill_difficulty = env::var(AI_BACKFILL_DIFFICULTY_ENV).ok();
+    let ai_backfill = resolve_ai_backfill_config(
+        configured_backfill_wait_ms.as_deref(),
+        configured_backfill_difficulty.as_deref(),
+    )
+    .context("Invalid AI backfill configuration")?;
+    match ai_backfill {
+        Some(backfill) => info!(
+            wait_ms = backfill.wait_ms,
+            difficulty = %backfill.difficulty,
+            "Thin quickmatch queues will be backfilled with AI players"
+        ),
+        None => info!("AI backfill is off; quickmatch seats only people"),
+    }
+
     // Create server configuration
     // Composition root for hosted services. `snaketron-io` (or any operator)
     // registers deployment-specific background work here; the public server
@@ -172,6 +198,7 @@ async fn main() -> Result<()> {
         boost_config,
         player_idle_config,
         match_ready_window_ms: common::MATCH_READY_WINDOW_MS,
+        ai_backfill,
         test_mode: false,
         ads_config,
     };
@@ -1,6 +1,9 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
-use common::{BoostConfig, GAME_START_COUNTDOWN_MS, GameState, GameType, boost_config_for};
+use common::{
+    AI_PLAYER_SLOTS, AiDifficulty, AiOpponents, ArenaMap, BoostConfig, GAME_START_COUNTDOWN_MS,
+    GameState, GameType, boost_config_for,
+};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -13,7 +16,7 @@ use crate::db::Database;
 use crate::game_bus::GameBus;
 use crate::game_executor::PARTITION_COUNT;
 use crate::game_executor::StreamEvent;
-use crate::lobby_manager::LobbyManager;
+use crate::lobby_manager::{LobbyManager, MAX_PRACTICE_AI_OPPONENTS};
 use crate::matchmaking_manager::{
     ActiveMatch, GameCreatedOutboxRecord, MATCHMAKING_GAME_TYPES, MatchCommitOutcome, MatchStatus,
     MatchmakingManager, QueuedPlayer,
@@ -29,6 +32,47 @@ const GAME_START_DELAY_MS: i64 = GAME_START_COUNTDOWN_MS;
 const FFA_MAX_RECURSION_DEPTH: usize = 8;
 const GAME_CREATED_OUTBOX_LANE_CAPACITY: usize = 1;
 
+/// How long, in milliseconds, a quickmatch lobby waits before server-run AI
+/// takes the seats nobody else is there to fill. Unset leaves backfill off.
+pub const AI_BACKFILL_WAIT_MS_ENV: &str = "SNAKETRON_AI_BACKFILL_WAIT_MS";
+/// Difficulty of backfilled AI players; `medium` when unset.
+pub const AI_BACKFILL_DIFFICULTY_ENV: &str = "SNAKETRON_AI_BACKFILL_DIFFICULTY";
+
+/// Fill the seats a thin quickmatch queue leaves empty with AI players once
+/// the longest wait reaches `wait_ms`. Competitive queues and the stress pool
+/// never backfill. The humans in a backfilled match are rated as usual; the
+/// AI seats never take MMR or reach the leaderboards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AiBackfillConfig {
+    pub wait_ms: i64,
+    pub difficulty: AiDifficulty,
+}
+
+/// Resolve optional environment values into a backfill policy, or `None`
+/// when no wait is configured.
+pub fn resolve_ai_backfill_config(
+    wait_ms: Option<&str>,
+    difficulty: Option<&str>,
+) -> Result<Option<AiBackfillConfig>> {
+    let Some(wait_ms) = wait_ms else {
+        return Ok(None);
+    };
+    let wait_ms = wait_ms
+        .trim()
+        .parse::<u32>()
+        .with_context(|| format!("{AI_BACKFILL_WAIT_MS_ENV} must be a number of milliseconds"))?;
+    let difficulty = match difficulty {
+        Some(difficulty) => difficulty
+            .parse()
+            .with_context(|| format!("{AI_BACKFILL_DIFFICULTY_ENV} is not a difficulty"))?,
+        None => AiDifficulty::Medium,
+    };
+    Ok(Some(AiBackfillConfig {
+        wait_ms: i64::from(wait_ms),
+        difficulty,
+    }))
+}
+
 struct GameCreatedOutboxDelivery {
     record: GameCreatedOutboxRecord,
     expected_payload: String,
@@ -264,17 +308,36 @@ struct MatchmakingCombination {
     spectators: Vec<(String, Vec<usize>)>,
     total_players: usize,
     avg_mmr: i32,
+    /// Seats left to server-run AI, if the queue was too thin to fill them.
+    ai_backfill: Option<AiBackfillSeats>,
+}
+
+/// AI players a backfilled combination seats after its people.
+#[derive(Debug, Clone)]
+struct AiBackfillSeats {
+    difficulty: AiDifficulty,
+    /// One entry per AI player: the team it joins, or `None` without teams.
+    teams: Vec<Option<common::TeamId>>,
 }
 
 impl MatchmakingCombination {
+    fn ai_seat_count(&self) -> usize {
+        self.ai_backfill
+            .as_ref()
+            .map_or(0, |backfill| backfill.teams.len())
+    }
+
     /// Check if this combination is valid for the given game type
     fn is_valid(&self, game_type: &GameType) -> bool {
+        let seats = self.total_players + self.ai_seat_count();
         match game_type {
             GameType::Solo => self.total_players == 1 && self.team_assignments.is_empty(),
-            GameType::TeamMatch { per_team } => {
+            GameType::TeamMatch { per_team }
+            | GameType::KingOfTheHill { per_team }
+            | GameType::CaptureTheFlag { per_team } => {
                 let total_needed = (per_team * 2) as usize;
                 // Check we have the right number of players
-                if self.total_players != total_needed {
+                if seats != total_needed {
                     return false;
                 }
                 // Check we have team assignments
@@ -286,12 +349,10 @@ impl MatchmakingCombination {
                 for assignment in &self.team_assignments {
                     assigned_count += assignment.member_indices.len();
                 }
-                assigned_count == total_needed
+                assigned_count + self.ai_seat_count() == total_needed
             }
             GameType::FreeForAll { max_players } => {
-                self.total_players >= 2
-                    && self.total_players <= *max_players as usize
-                    && self.team_assignments.is_empty()
+                seats >= 2 && seats <= *max_players as usize && self.team_assignments.is_empty()
             }
             _ => false,
         }
@@ -309,7 +370,11 @@ fn find_best_lobby_combination(
 
     match game_type {
         GameType::Solo => find_solo_combination(lobbies),
-        GameType::TeamMatch { per_team } => find_team_combination(lobbies, *per_team as usize),
+        GameType::TeamMatch { per_team }
+        | GameType::KingOfTheHill { per_team }
+        | GameType::CaptureTheFlag { per_team } => {
+            find_team_combination(lobbies, *per_team as usize)
+        }
         GameType::FreeForAll { max_players } => {
             find_ffa_combination(lobbies, *max_players as usize)
         }
@@ -329,6 +394,7 @@ fn find_solo_combination(
             spectators: Vec::new(),
             total_players: 1,
             avg_mmr: lobby.avg_mmr,
+            ai_backfill: None,
         });
     }
 
@@ -354,6 +420,7 @@ fn find_solo_combination(
             },
             total_players: 1,
             avg_mmr: lobby.avg_mmr,
+            ai_backfill: None,
         });
     }
 
@@ -564,6 +631,7 @@ fn build_combination(
         spectators,
         total_players,
         avg_mmr,
+        ai_backfill: None,
     })
 }
 
@@ -631,6 +699,7 @@ fn find_single_lobby_split(
             spectators: Vec::new(),
             total_players: total_needed,
             avg_mmr: lobby.avg_mmr,
+            ai_backfill: None,
         });
     }
 
@@ -700,6 +769,7 @@ fn find_team_match_with_spectators(
                 spectators: vec![(lobby.lobby_code.clone(), spectator_indices)],
                 total_players: total_needed,
                 avg_mmr: lobby.avg_mmr,
+                ai_backfill: None,
             });
         }
     }
@@ -801,6 +871,7 @@ fn find_ffa_combination(
             spectators,
             total_players,
             avg_mmr,
+            ai_backfill: None,
         }
     }
 
@@ -909,12 +980,110 @@ fn find_ffa_combination(
     )
 }
 
+/// Seat whole lobbies, `priority` first and then the rest in queue order, and
+/// leave the seats still empty to AI. Nothing is formed until `priority` has
+/// waited `config.wait_ms`, and parties stay together unless one is too big
+/// for a single team, so a waiting party that the exact matchers cannot
+/// place may also be completed by people alone.
+fn find_backfill_combination(
+    lobbies: &[crate::matchmaking_manager::QueuedLobby],
+    priority: &crate::matchmaking_manager::QueuedLobby,
+    game_type: &GameType,
+    now_ms: i64,
+    config: AiBackfillConfig,
+) -> Option<MatchmakingCombination> {
+    if now_ms - priority.queued_at < config.wait_ms {
+        return None;
+    }
+    // Room left on each side: two teams, or one pool without teams.
+    let (mut room, teamed) = match game_type {
+        GameType::TeamMatch { per_team }
+        | GameType::KingOfTheHill { per_team }
+        | GameType::CaptureTheFlag { per_team } => (vec![*per_team as usize; 2], true),
+        GameType::FreeForAll { max_players } => (vec![*max_players as usize], false),
+        _ => return None,
+    };
+
+    let ordered = std::iter::once(priority).chain(
+        lobbies
+            .iter()
+            .filter(|lobby| lobby.lobby_code != priority.lobby_code),
+    );
+    let mut used_lobbies = Vec::new();
+    let mut team_assignments = Vec::new();
+    let mut total_players = 0;
+    let mut total_mmr_weighted = 0;
+    for lobby in ordered {
+        let members = lobby.members.len();
+        if members == 0 || members > room.iter().sum::<usize>() {
+            continue;
+        }
+        // The roomiest side first; a party only splits when no side holds it.
+        let mut sides: Vec<usize> = (0..room.len()).collect();
+        sides.sort_by_key(|side| std::cmp::Reverse(room[*side]));
+        if room[sides[0]] >= members {
+            sides.truncate(1);
+        }
+        let mut next_member = 0;
+        for side in sides {
+            let take = room[side].min(members - next_member);
+            if take == 0 {
+                continue;
+            }
+            room[side] -= take;
+            if teamed {
+                team_assignments.push(TeamAssignment {
+                    lobby_code: lobby.lobby_code.clone(),
+                    member_indices: (next_member..next_member + take).collect(),
+                    team_id: common::TeamId(side as u8),
+                });
+            }
+            next_member += take;
+        }
+        used_lobbies.push(lobby.clone());
+        total_players += members;
+        total_mmr_weighted += lobby.avg_mmr * members as i32;
+        if room.iter().all(|left| *left == 0) {
+            break;
+        }
+    }
+    if used_lobbies
+        .first()
+        .is_none_or(|lobby| lobby.lobby_code != priority.lobby_code)
+    {
+        return None;
+    }
+
+    let teams: Vec<Option<common::TeamId>> = room
+        .iter()
+        .enumerate()
+        .flat_map(|(side, left)| {
+            std::iter::repeat_n(teamed.then_some(common::TeamId(side as u8)), *left)
+        })
+        .collect();
+    if teams.len() > AI_PLAYER_SLOTS as usize {
+        return None;
+    }
+    Some(MatchmakingCombination {
+        lobbies: used_lobbies,
+        team_assignments,
+        spectators: Vec::new(),
+        total_players,
+        avg_mmr: total_mmr_weighted / total_players as i32,
+        ai_backfill: (!teams.is_empty()).then_some(AiBackfillSeats {
+            difficulty: config.difficulty,
+            teams,
+        }),
+    })
+}
+
 /// Main matchmaking loop
 pub async fn run_matchmaking_loop(
     mut matchmaking_manager: MatchmakingManager,
     cancellation_token: CancellationToken,
     lobby_manager: Arc<LobbyManager>,
     db: Arc<dyn Database>,
+    ai_backfill: Option<AiBackfillConfig>,
 ) -> Result<()> {
     info!("Starting adaptive matchmaking loop");
 
@@ -946,6 +1115,7 @@ pub async fn run_matchmaking_loop(
                         matchmaking_pool,
                         lobby_manager.clone(),
                         db.clone(),
+                        ai_backfill,
                     )
                     .await
                     {
@@ -1066,7 +1236,12 @@ async fn create_lobby_matches(
     matchmaking_pool: MatchmakingPool,
     lobby_manager: Arc<LobbyManager>,
     db: Arc<dyn Database>,
+    ai_backfill: Option<AiBackfillConfig>,
 ) -> Result<usize> {
+    // Only public quickmatch fills its thin queues with AI.
+    let ai_backfill = ai_backfill.filter(|_| {
+        queue_mode == common::QueueMode::Quickmatch && matchmaking_pool == MatchmakingPool::Public
+    });
     // Get all queued lobbies for this game type and queue mode
     let mut available_lobbies = matchmaking_manager
         .get_queued_lobbies_in_pool(&game_type, &queue_mode, matchmaking_pool)
@@ -1173,16 +1348,35 @@ async fn create_lobby_matches(
                 );
                 comb
             }
-            None => {
-                // No valid combinations found from compatible lobbies
-                // This means we need to wait longer or the game type requirements can't be met
-                warn!(
-                    game_type = ?game_type,
-                    compatible_lobbies = compatible_lobbies.len(),
-                    "No valid lobby combinations found from compatible lobbies"
-                );
-                break;
-            }
+            None => match ai_backfill.and_then(|config| {
+                find_backfill_combination(
+                    &compatible_lobbies,
+                    priority_lobby,
+                    &game_type,
+                    now,
+                    config,
+                )
+            }) {
+                Some(comb) => {
+                    info!(
+                        lobbies_in_combo = comb.lobbies.len(),
+                        total_players = comb.total_players,
+                        ai_players = comb.ai_seat_count(),
+                        "Backfilling a thin queue with AI players"
+                    );
+                    comb
+                }
+                None => {
+                    // No valid combinations found from compatible lobbies
+                    // This means we need to wait longer or the game type requirements can't be met
+                    warn!(
+                        game_type = ?game_type,
+                        compatible_lobbies = compatible_lobbies.len(),
+                        "No valid lobby combinations found from compatible lobbies"
+                    );
+                    break;
+                }
+            },
         };
 
         // Validate the combination
@@ -1313,7 +1507,9 @@ async fn prepare_game_from_lobbies(
     let start_ms = Utc::now().timestamp_millis() + GAME_START_DELAY_MS;
 
     let (width, height) = match game_type {
-        GameType::TeamMatch { .. } => (60, 40),
+        GameType::TeamMatch { .. }
+        | GameType::KingOfTheHill { .. }
+        | GameType::CaptureTheFlag { .. } => (60, 40),
         _ => (40, 40),
     };
 
@@ -1423,6 +1619,19 @@ async fn prepare_game_from_lobbies(
         }
     }
 
+    // Practice opponents are seated after the people so the reserved AI ids
+    // never shift a human's spawn slot.
+    if let Some(request) = requested_ai_opponents(game_type, combination) {
+        game_state.add_ai_opponents(&request)?;
+    }
+    // Backfilled seats likewise; the executor drives them inside the
+    // simulation, and AI seats take no rating of their own.
+    if let Some(backfill) = &combination.ai_backfill {
+        for team in &backfill.teams {
+            game_state.add_ai_player(backfill.difficulty, *team)?;
+        }
+    }
+
     game_state.spawn_initial_food();
 
     // Hold the match until every player has confirmed the pre-match briefing,
@@ -1468,6 +1677,28 @@ async fn prepare_game_from_lobbies(
     })
 }
 
+/// AI opponents to seat in a prepared match. Only solo practice and custom
+/// games take them; a custom game's own settings win over the lobby's ask.
+/// Rated queues never fill seats with AI.
+fn requested_ai_opponents(
+    game_type: &GameType,
+    combination: &MatchmakingCombination,
+) -> Option<AiOpponents> {
+    let lobby_request = combination
+        .lobbies
+        .first()
+        .and_then(|lobby| lobby.ai_opponents);
+    let request = match game_type {
+        GameType::Solo => lobby_request,
+        GameType::Custom { settings } => settings.ai_opponents.or(lobby_request),
+        _ => None,
+    }?;
+    (request.count > 0).then_some(AiOpponents {
+        count: request.count.min(MAX_PRACTICE_AI_OPPONENTS),
+        ..request
+    })
+}
+
 #[allow(clippy::too_many_arguments)]
 fn build_match_game_state(
     width: u16,
@@ -1479,7 +1710,10 @@ fn build_match_game_state(
     boost_config: &BoostConfig,
     player_idle_config: PlayerIdleConfig,
 ) -> Result<GameState> {
-    let mut state = if let Some(mut mode_config) = boost_config_for(&game_type, width, height) {
+    // The mode's map for these dimensions. Rotations swap in a different
+    // `ArenaMap` here; everything below keys on the map, not the size.
+    let map = ArenaMap::default_for(&game_type, width, height);
+    let mut state = if let Some(mut mode_config) = boost_config_for(&game_type, &map) {
         // Startup configuration owns balance. The mode owns its fuel model and
         // layout: FFA uses the canonical field pads while Solo is unlimited
         // and has no pads. Copying only balance fields prevents a team-shaped
@@ -1489,9 +1723,8 @@ fn build_match_game_state(
         mode_config.packet_charge_ms = boost_config.packet_charge_ms;
         mode_config.pad_respawn_ms = boost_config.pad_respawn_ms;
         mode_config.rules_version = boost_config.rules_version;
-        GameState::new_with_boost_config(
-            width,
-            height,
+        GameState::new_on_map_with_boost_config(
+            &map,
             game_type,
             queue_mode,
             rng_seed,
@@ -1499,11 +1732,12 @@ fn build_match_game_state(
             mode_config,
         )?
     } else {
-        GameState::new(width, height, game_type, queue_mode, rng_seed, start_ms)
+        GameState::new_on_map(&map, game_type, queue_mode, rng_seed, start_ms)?
     };
 
     state.properties.player_idle_timeout_ms = player_idle_config.total_timeout_ms();
     state.properties.player_idle_warning_ms = player_idle_config.kick_countdown_ms();
+    state.properties.idle_autopilot = player_idle_config.autopilot();
     state
         .validate_boost_invariants()
         .context("Configured match state failed validation")?;
@@ -1595,6 +1829,7 @@ mod tests {
             queued_at,
             requesting_user_id: user_ids[0],
             matchmaking_pool: MatchmakingPool::Public,
+            ai_opponents: None,
             queue_identity_json: None,
         }
     }
@@ -1742,12 +1977,14 @@ mod tests {
 
         assert_eq!(state.properties.player_idle_timeout_ms, 20_000);
         assert_eq!(state.properties.player_idle_warning_ms, 10_000);
+        assert_eq!(state.properties.idle_autopilot, None);
         Ok(())
     }
 
     #[test]
     fn configured_player_idle_policy_is_snapshotted_into_every_match_branch() -> Result<()> {
-        let player_idle_config = PlayerIdleConfig::new(12_345, 6_789)?;
+        let player_idle_config =
+            PlayerIdleConfig::new(12_345, 6_789)?.with_autopilot(Some(AiDifficulty::Hard));
         let cases = [
             (60, 40, GameType::TeamMatch { per_team: 1 }),
             (40, 40, GameType::FreeForAll { max_players: 4 }),
@@ -1775,6 +2012,7 @@ mod tests {
 
             assert_eq!(state.properties.player_idle_timeout_ms, 19_134);
             assert_eq!(state.properties.player_idle_warning_ms, 6_789);
+            assert_eq!(state.properties.idle_autopilot, Some(AiDifficulty::Hard));
         }
 
         Ok(())
@@ -2093,6 +2331,7 @@ mod tests {
             queued_at: 0,
             requesting_user_id: 5,
             matchmaking_pool: MatchmakingPool::Public,
+            ai_opponents: None,
             queue_identity_json: None,
         };
 
@@ -2105,6 +2344,36 @@ mod tests {
         assert!(combo.spectators.is_empty());
     }
 
+    #[test]
+    fn only_practice_matches_seat_requested_ai_opponents() {
+        let mut lobby = ffa_lobby("PRACTICE", &[5], 0);
+        lobby.ai_opponents = Some(AiOpponents {
+            count: 9,
+            difficulty: common::AiDifficulty::Hard,
+        });
+        let combo = find_best_lobby_combination(&[lobby], &GameType::Solo)
+            .expect("a one-player lobby should form one solo match");
+
+        let solo = requested_ai_opponents(&GameType::Solo, &combo).expect("solo seats AI");
+        assert_eq!(solo.count, MAX_PRACTICE_AI_OPPONENTS);
+        assert_eq!(solo.difficulty, common::AiDifficulty::Hard);
+
+        let custom = GameType::Custom {
+            settings: common::CustomGameSettings {
+                ai_opponents: Some(AiOpponents {
+                    count: 1,
+                    difficulty: common::AiDifficulty::Easy,
+                }),
+                ..Default::default()
+            },
+        };
+        let seated = requested_ai_opponents(&custom, &combo).expect("custom seats AI");
+        assert_eq!(seated.count, 1);
+        assert_eq!(seated.difficulty, common::AiDifficulty::Easy);
+
+        assert!(requested_ai_opponents(&GameType::FreeForAll { max_players: 4 }, &combo).is_none());
+    }
+
     #[test]
     fn duo_lobby_splits_into_duel() {
         let lobby = QueuedLobby {
@@ -2134,6 +2403,7 @@ mod tests {
             queued_at: 0,
             requesting_user_id: 10,
             matchmaking_pool: MatchmakingPool::Public,
+            ai_opponents: None,
             queue_identity_json: None,
         };
 
@@ -2180,6 +2450,7 @@ mod tests {
             queued_at: 0,
             requesting_user_id: 20,
             matchmaking_pool: MatchmakingPool::Public,
+            ai_opponents: None,
             queue_identity_json: None,
         };
 
@@ -2226,4 +2497,74 @@ mod tests {
         assert_eq!(combo.lobbies.len(), 2);
         assert!(combo.spectators.is_empty());
     }
+
+    #[test]
+    fn ai_backfill_is_off_unless_a_wait_is_configured() {
+        assert_eq!(
+            resolve_ai_backfill_config(None, Some("hard")).unwrap(),
+            None
+        );
+        assert_eq!(
+            resolve_ai_backfill_config(Some(" 15000 "), None).unwrap(),
+            Some(AiBackfillConfig {
+                wait_ms: 15_000,
+                difficulty: AiDifficulty::Medium,
+            })
+        );
+        assert!(resolve_ai_backfill_config(Some("soon"), None).is_err());
+        assert!(resolve_ai_backfill_config(Some("15000"), Some("brutal")).is_err());
+    }
+
+    #[test]
+    fn a_thin_team_queue_fills_its_empty_seats_with_ai_after_the_wait() {
+        let config = AiBackfillConfig {
+            wait_ms: 10_000,
+            difficulty: AiDifficulty::Hard,
+        };
+        let now = Utc::now().timestamp_millis();
+        let trio = ffa_lobby("TRIO", &[1, 2, 3], now - 12_000);
+        let two_v_two = GameType::TeamMatch { per_team: 2 };
+        assert!(find_best_lobby_combination(std::slice::from_ref(&trio), &two_v_two).is_none());
+        assert!(
+            find_backfill_combination(&[trio.clone()], &trio, &two_v_two, now - 5_000, config)
+                .is_none(),
+            "nothing is backfilled before the wait"
+        );
+
+        let combo = find_backfill_combination(&[trio.clone()], &trio, &two_v_two, now, config)
+            .expect("a trio that waited out the backfill delay gets one AI teammate");
+        assert!(combo.is_valid(&two_v_two));
+        assert_eq!(combo.total_players, 3);
+        let backfill = combo.ai_backfill.as_ref().unwrap();
+        assert_eq!(backfill.difficulty, AiDifficulty::Hard);
+        assert_eq!(backfill.teams, vec![Some(common::TeamId(1))]);
+
+        // A solo waiting alongside takes the short side instead of an AI.
+        let solo = ffa_lobby("SOLO", &[4], now);
+        let combo =
+            find_backfill_combination(&[trio.clone(), solo], &trio, &two_v_two, now, config)
+                .expect("a trio and a solo fill a 2v2 between them");
+        assert!(combo.is_valid(&two_v_two));
+        assert_eq!(combo.total_players, 4);
+        assert!(combo.ai_backfill.is_none());
+        assert_eq!(combo.team_assignments.last().unwrap().lobby_code, "SOLO");
+    }
+
+    #[test]
+    fn a_lone_ffa_player_is_topped_up_to_a_full_table() {
+        let config = AiBackfillConfig {
+            wait_ms: 10_000,
+            difficulty: AiDifficulty::Easy,
+        };
+        let now = Utc::now().timestamp_millis();
+        let lone = ffa_lobby("LONE", &[1], now - 10_000);
+        let ffa = GameType::FreeForAll { max_players: 4 };
+        assert!(find_best_lobby_combination(std::slice::from_ref(&lone), &ffa).is_none());
+
+        let combo = find_backfill_combination(&[lone.clone()], &lone, &ffa, now, config)
+            .expect("a lone FFA player gets AI opponents");
+        assert!(combo.is_valid(&ffa));
+        assert!(combo.team_assignments.is_empty());
+        assert_eq!(combo.ai_backfill.unwrap().teams, vec![None; 3]);
+    }
 }
@@ -15,13 +15,17 @@ use tokio::time::sleep;
 use tracing::{error, info, warn};
 
 /// Fixed queue families consumed by the production matchmaking loop. Metrics
-/// aggregate these ten queues without adding game-type or mode dimensions.
-pub const MATCHMAKING_GAME_TYPES: [GameType; 5] = [
+/// aggregate these eighteen queues without adding game-type or mode dimensions.
+pub const MATCHMAKING_GAME_TYPES: [GameType; 9] = [
     GameType::Solo,
     GameType::FreeForAll { max_players: 2 },
     GameType::FreeForAll { max_players: 4 },
     GameType::TeamMatch { per_team: 1 },
     GameType::TeamMatch { per_team: 2 },
+    GameType::KingOfTheHill { per_team: 1 },
+    GameType::KingOfTheHill { per_team: 2 },
+    GameType::CaptureTheFlag { per_team: 1 },
+    GameType::CaptureTheFlag { per_team: 2 },
 ];
 pub const MATCHMAKING_QUEUE_MODES: [common::QueueMode; 2] = [
     common::QueueMode::Quickmatch,
@@ -48,6 +52,9 @@ pub struct QueuedLobby {
     pub requesting_user_id: u32, // Who initiated the queue request (for spectator preference)
     #[serde(default)]
     pub matchmaking_pool: MatchmakingPool,
+    /// AI opponents the lobby asked for. Only solo practice seats them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_opponents: Option<common::AiOpponents>,
     /// Exact immutable Redis member observed by the sampler/by-code lookup.
     /// It is runtime provenance, never part of the wire/storage schema.
     #[serde(skip)]
@@ -1269,6 +1276,7 @@ impl MatchmakingManager {
             matchmaking_pool,
             expected_ad_break_id,
             fence,
+            None,
         )
         .await
     }
@@ -1288,6 +1296,7 @@ impl MatchmakingManager {
         matchmaking_pool: MatchmakingPool,
         expected_ad_break_id: Option<&str>,
         membership_fence: LobbyMembershipFence,
+        ai_opponents: Option<common::AiOpponents>,
     ) -> Result<()> {
         if members.is_empty() || members.len() > MAX_LOBBY_MEMBERS {
             return Err(anyhow!(
@@ -1323,6 +1332,7 @@ impl MatchmakingManager {
             queued_at: timestamp,
             requesting_user_id,
             matchmaking_pool,
+            ai_opponents,
             queue_identity_json: None,
         };
 
@@ -2340,6 +2350,7 @@ mod tests {
             queued_at: 1,
             requesting_user_id: 1,
             matchmaking_pool: MatchmakingPool::Stress,
+            ai_opponents: None,
             queue_identity_json: None,
         };
         let mut queued_json = serde_json::to_value(queued).unwrap();
@@ -10,6 +10,10 @@ use skillratings::weng_lin::{
 use std::collections::{HashMap, HashSet};
 use tracing::{debug, error, info, warn};
 
+/// AI seats have no account and no rating of their own. They stand in at the
+/// rating every new account starts from, and never receive a delta.
+const AI_SEAT_RATING: i32 = 1000;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MmrEffectSpec {
     pub user_id: u32,
@@ -63,6 +67,11 @@ fn user_id_for_snake(game_state: &GameState, snake_id: u32) -> Result<u32> {
 fn winning_team_for_mmr(game_state: &GameState) -> Result<Option<TeamId>> {
     match mmr_outcome_override(game_state)? {
         MmrOutcomeOverride::ScoreBased => {
+            // A best-of-N match is won on rounds. The score table only holds
+            // the deciding round, so it says nothing about the series.
+            if let Some(rounds) = &game_state.rounds {
+                return Ok(unique_winning_team(&rounds.round_wins));
+            }
             let team_scores = game_state
                 .team_scores
                 .as_ref()
@@ -105,7 +114,12 @@ pub async fn calculate_mmr_effect_specs(
     }
 
     let (deltas, winners) = match &game_state.game_type {
-        GameType::TeamMatch { per_team } => (
+        // King of the hill and capture the flag report their points through
+        // the same team score table, so every team mode rates on the team
+        // that finished ahead.
+        GameType::TeamMatch { per_team }
+        | GameType::KingOfTheHill { per_team }
+        | GameType::CaptureTheFlag { per_team } => (
             calculate_team_match_mmr_deltas(db, game_state, *per_team).await?,
             get_team_match_winners(game_state)?,
         ),
@@ -173,7 +187,9 @@ pub async fn persist_player_mmr(
 
     // Calculate MMR deltas and determine winners based on game type
     let (mmr_deltas, winners) = match &game_state.game_type {
-        GameType::TeamMatch { per_team } => {
+        GameType::TeamMatch { per_team }
+        | GameType::KingOfTheHill { per_team }
+        | GameType::CaptureTheFlag { per_team } => {
             let deltas = calculate_team_match_mmr_deltas(db, game_state, *per_team).await?;
             let winners = get_team_match_winners(game_state)?;
             (deltas, winners)
@@ -253,6 +269,7 @@ async fn calculate_team_match_mmr_deltas(
     let all_users: Vec<i32> = team_0_users
         .iter()
         .chain(team_1_users.iter())
+        .filter(|&&id| !game_state.is_ai_driven(id))
         .map(|&id| id as i32)
         .collect();
     let mmr_map = db.get_user_mmrs(&all_users).await?;
@@ -260,6 +277,9 @@ async fn calculate_team_match_mmr_deltas(
 
     // Extract MMRs based on queue mode
     let get_mmr = |user_id: u32| -> i32 {
+        if game_state.is_ai_driven(user_id) {
+            return AI_SEAT_RATING;
+        }
         mmr_map
             .get(&(user_id as i32))
             .map(|(ranked, casual)| match game_state.queue_mode {
@@ -322,6 +342,7 @@ async fn calculate_team_match_mmr_deltas(
         let new_mmr = new_team_1[i].rating as i32;
         deltas.insert(user_id, new_mmr - old_mmr);
     }
+    exclude_ai_seats(game_state, &mut deltas);
     suppress_idle_rating_gains(game_state, &mut deltas);
 
     info!(
@@ -355,16 +376,27 @@ fn unique_winning_team(team_scores: &HashMap<TeamId, u32>) -> Option<TeamId> {
 }
 
 /// Removal for inactivity can never become a rating reward, even if the
-/// remaining teammates later win. Losses still apply, preserving the cost of
-/// abandoning a match without creating a positive AFK outcome.
+/// remaining teammates later win, and neither can a stretch on autopilot.
+/// Losses still apply, preserving the cost of abandoning a match without
+/// creating a positive AFK outcome.
 fn suppress_idle_rating_gains(game_state: &GameState, deltas: &mut HashMap<u32, i32>) {
-    for user_id in &game_state.idle_kicked_user_ids {
+    for user_id in game_state
+        .idle_kicked_user_ids
+        .iter()
+        .chain(&game_state.autopiloted_user_ids)
+    {
         if let Some(delta) = deltas.get_mut(user_id) {
             *delta = (*delta).min(0);
         }
     }
 }
 
+/// AI seats shape everyone else's result but take no rating themselves, so
+/// they never reach an MMR update or the leaderboard.
+fn exclude_ai_seats(game_state: &GameState, deltas: &mut HashMap<u32, i32>) {
+    deltas.retain(|user_id, _| !game_state.is_ai_driven(*user_id));
+}
+
 /// Return FFA participants in rating order. An inactivity survivor is forced
 /// into first place and removed players sort behind every active finisher;
 /// players within each group retain their ordinary score order.
@@ -415,12 +447,19 @@ async fn calculate_ffa_mmr_deltas(
     let (player_scores, forced_winner) = ordered_ffa_player_scores(game_state)?;
 
     // Get current MMRs
-    let all_users: Vec<i32> = player_scores.iter().map(|(id, _)| *id as i32).collect();
+    let all_users: Vec<i32> = player_scores
+        .iter()
+        .filter(|(id, _)| !game_state.is_ai_driven(*id))
+        .map(|(id, _)| *id as i32)
+        .collect();
     let mmr_map = db.get_user_mmrs(&all_users).await?;
     ensure_all_mmr_users_exist(&mmr_map, &all_users)?;
 
     // Extract MMRs based on queue mode
     let get_mmr = |user_id: u32| -> i32 {
+        if game_state.is_ai_driven(user_id) {
+            return AI_SEAT_RATING;
+        }
         mmr_map
             .get(&(user_id as i32))
             .map(|(ranked, casual)| match game_state.queue_mode {
@@ -455,6 +494,7 @@ async fn calculate_ffa_mmr_deltas(
         let mut deltas = HashMap::new();
         deltas.insert(user_0, new_rating_0.rating as i32 - get_mmr(user_0));
         deltas.insert(user_1, new_rating_1.rating as i32 - get_mmr(user_1));
+        exclude_ai_seats(game_state, &mut deltas);
         suppress_idle_rating_gains(game_state, &mut deltas);
 
         return Ok(deltas);
@@ -520,6 +560,7 @@ async fn calculate_ffa_mmr_deltas(
             .collect::<Vec<_>>()
     );
 
+    exclude_ai_seats(game_state, &mut deltas);
     suppress_idle_rating_gains(game_state, &mut deltas);
     Ok(deltas)
 }
@@ -622,7 +663,11 @@ fn get_team_match_winners(game_state: &GameState) -> Result<HashSet<u32>> {
             let Some(snake) = game_state.arena.snakes.get(player.snake_id as usize) else {
                 continue;
             };
-            if snake.team_id == Some(winning_team) && !game_state.is_player_idle_kicked(*user_id) {
+            if snake.team_id == Some(winning_team)
+                && !game_state.is_player_idle_kicked(*user_id)
+                && !game_state.was_on_autopilot(*user_id)
+                && !game_state.is_ai_driven(*user_id)
+            {
                 winners.insert(*user_id);
             }
         }
@@ -638,7 +683,10 @@ fn get_ffa_winners(game_state: &GameState) -> Result<HashSet<u32>> {
     match mmr_outcome_override(game_state)? {
         MmrOutcomeOverride::NoContest => return Ok(winners),
         MmrOutcomeOverride::WinningSnake(snake_id) => {
-            winners.insert(user_id_for_snake(game_state, snake_id)?);
+            let user_id = user_id_for_snake(game_state, snake_id)?;
+            if !game_state.is_ai_driven(user_id) {
+                winners.insert(user_id);
+            }
             return Ok(winners);
         }
         MmrOutcomeOverride::ScoreBased => {}
@@ -670,9 +718,10 @@ fn get_ffa_winners(game_state: &GameState) -> Result<HashSet<u32>> {
         .max()
         .unwrap_or(0);
 
-    // Add all players with max score (handles ties)
+    // Add all players with max score (handles ties). An AI seat can top the
+    // table, but it is never a winner on the leaderboard.
     for (user_id, score) in player_scores {
-        if score == max_score {
+        if score == max_score && !game_state.is_ai_driven(user_id) {
             winners.insert(user_id);
         }
     }
@@ -697,8 +746,12 @@ async fn persist_solo_high_scores(
         region
     );
 
-    // For each player, insert their high score
+    // For each player, insert their high score. Practice AI never reaches
+    // the leaderboard.
     for (user_id, player) in &game_state.players {
+        if game_state.is_ai_driven(*user_id) {
+            continue;
+        }
         let score = game_state
             .scores
             .get(&player.snake_id)
@@ -897,6 +950,73 @@ mod tests {
         assert_eq!(get_team_match_winners(&state).unwrap(), HashSet::from([22]));
     }
 
+    #[test]
+    fn best_of_three_completion_is_decided_by_round_wins() {
+        let mut state = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Competitive,
+            Some(7),
+            0,
+        );
+        for (user_id, name) in [(11, "eleven"), (22, "twenty-two")] {
+            state
+                .add_player(user_id, Some(name.into()))
+                .expect("team player should be added");
+        }
+        let rounds = state
+            .rounds
+            .as_mut()
+            .expect("competitive team matches are played over rounds");
+        rounds.round_wins.insert(TeamId(0), 2);
+        rounds.round_wins.insert(TeamId(1), 1);
+        rounds.current_round = 3;
+        rounds.rounds_completed = 3;
+        // The series, not the last round's score line, decides the result.
+        let team_scores = state
+            .team_scores
+            .as_mut()
+            .expect("team match should have team scores");
+        team_scores.insert(TeamId(0), 3);
+        team_scores.insert(TeamId(1), 3);
+        state.status = GameStatus::Complete {
+            winning_snake_id: Some(state.players[&11].snake_id),
+        };
+
+        assert_eq!(winning_team_for_mmr(&state).unwrap(), Some(TeamId(0)));
+        assert_eq!(get_team_match_winners(&state).unwrap(), HashSet::from([11]));
+    }
+
+    #[test]
+    fn king_of_the_hill_is_rated_on_hill_points() {
+        let mut state = GameState::new(
+            60,
+            40,
+            GameType::KingOfTheHill { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(7),
+            0,
+        );
+        for (user_id, name) in [(11, "eleven"), (22, "twenty-two")] {
+            state
+                .add_player(user_id, Some(name.into()))
+                .expect("hill player should be added");
+        }
+        let team_scores = state
+            .team_scores
+            .as_mut()
+            .expect("king of the hill keeps team scores");
+        team_scores.insert(TeamId(0), 12);
+        team_scores.insert(TeamId(1), 30);
+        state.status = GameStatus::Complete {
+            winning_snake_id: Some(state.players[&22].snake_id),
+        };
+
+        assert_eq!(winning_team_for_mmr(&state).unwrap(), Some(TeamId(1)));
+        assert_eq!(get_team_match_winners(&state).unwrap(), HashSet::from([22]));
+    }
+
     #[test]
     fn ordinary_ffa_completion_places_removed_players_last() {
         let mut state = ffa_state();
@@ -929,4 +1049,19 @@ mod tests {
         suppress_idle_rating_gains(&state, &mut negative);
         assert_eq!(negative, HashMap::from([(11, -18), (22, 18)]));
     }
+
+    #[test]
+    fn autopiloted_players_neither_gain_rating_nor_count_as_winners() {
+        let mut state = team_state();
+        state.autopiloted_user_ids = vec![22];
+        let mut deltas = HashMap::from([(11, -18), (22, 18)]);
+        suppress_idle_rating_gains(&state, &mut deltas);
+        assert_eq!(deltas, HashMap::from([(11, -18), (22, 0)]));
+
+        let winning_team = state.arena.snakes[state.players[&22].snake_id as usize]
+            .team_id
+            .expect("team assignment");
+        state.team_scores = Some(HashMap::from([(winning_team, 5)]));
+        assert!(get_team_match_winners(&state).unwrap().is_empty());
+    }
 }
@@ -1,7 +1,9 @@
 use anyhow::{Context, Result, bail};
+use common::AiDifficulty;
 
 pub const PLAYER_IDLE_GRACE_MS_ENV: &str = "SNAKETRON_PLAYER_IDLE_GRACE_MS";
 pub const PLAYER_IDLE_COUNTDOWN_MS_ENV: &str = "SNAKETRON_PLAYER_IDLE_COUNTDOWN_MS";
+pub const PLAYER_IDLE_AUTOPILOT_ENV: &str = "SNAKETRON_PLAYER_IDLE_AUTOPILOT";
 
 const DEFAULT_PLAYER_IDLE_GRACE_MS: u32 = 10_000;
 const DEFAULT_PLAYER_IDLE_COUNTDOWN_MS: u32 = 10_000;
@@ -15,6 +17,7 @@ const DEFAULT_PLAYER_IDLE_COUNTDOWN_MS: u32 = 10_000;
 pub struct PlayerIdleConfig {
     idle_grace_ms: u32,
     kick_countdown_ms: u32,
+    autopilot: Option<AiDifficulty>,
 }
 
 impl PlayerIdleConfig {
@@ -32,9 +35,16 @@ impl PlayerIdleConfig {
         Ok(Self {
             idle_grace_ms,
             kick_countdown_ms,
+            autopilot: None,
         })
     }
 
+    /// Hand an idle team player's snake to the AI at `difficulty` instead of
+    /// kicking them, for as long as a teammate is still present.
+    pub fn with_autopilot(self, autopilot: Option<AiDifficulty>) -> Self {
+        Self { autopilot, ..self }
+    }
+
     pub fn idle_grace_ms(self) -> u32 {
         self.idle_grace_ms
     }
@@ -43,6 +53,10 @@ impl PlayerIdleConfig {
         self.kick_countdown_ms
     }
 
+    pub fn autopilot(self) -> Option<AiDifficulty> {
+        self.autopilot
+    }
+
     pub fn total_timeout_ms(self) -> u32 {
         // Every instance is created through `new` or the checked constants in
         // `Default`, so this addition cannot overflow.
@@ -55,6 +69,7 @@ impl Default for PlayerIdleConfig {
         Self {
             idle_grace_ms: DEFAULT_PLAYER_IDLE_GRACE_MS,
             kick_countdown_ms: DEFAULT_PLAYER_IDLE_COUNTDOWN_MS,
+            autopilot: None,
         }
     }
 }
@@ -89,6 +104,19 @@ pub fn resolve_player_idle_config(
     )
 }
 
+/// `off` or an unset value keeps the kick; anything else names the
+/// difficulty that drives idle players' snakes.
+pub fn resolve_player_idle_autopilot(autopilot: Option<&str>) -> Result<Option<AiDifficulty>> {
+    match autopilot.map(str::trim) {
+        None => Ok(None),
+        Some(value) if value.eq_ignore_ascii_case("off") => Ok(None),
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .with_context(|| format!("{PLAYER_IDLE_AUTOPILOT_ENV} must be off or a difficulty")),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +128,7 @@ mod tests {
         assert_eq!(config.idle_grace_ms(), 10_000);
         assert_eq!(config.kick_countdown_ms(), 10_000);
         assert_eq!(config.total_timeout_ms(), 20_000);
+        assert_eq!(config.autopilot(), None);
     }
 
     #[test]
@@ -126,4 +155,19 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn autopilot_is_off_unless_a_difficulty_is_named() {
+        assert_eq!(resolve_player_idle_autopilot(None).unwrap(), None);
+        assert_eq!(resolve_player_idle_autopilot(Some(" OFF ")).unwrap(), None);
+        assert_eq!(
+            resolve_player_idle_autopilot(Some("hard")).unwrap(),
+            Some(AiDifficulty::Hard)
+        );
+        assert!(resolve_player_idle_autopilot(Some("sometimes")).is_err());
+
+        let config = PlayerIdleConfig::default().with_autopilot(Some(AiDifficulty::Easy));
+        assert_eq!(config.autopilot(), Some(AiDifficulty::Easy));
+        assert_eq!(config.total_timeout_ms(), 20_000);
+    }
 }
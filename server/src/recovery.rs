@@ -699,6 +699,7 @@ impl ReplayRecordingState {
             messages,
             end_tick: final_state.tick,
             end_sync_hash: final_state.sync_hash(),
+            keyframes: Vec::new(),
         };
         Ok(Some(recording))
     }
@@ -707,10 +708,12 @@ impl ReplayRecordingState {
         if !self.enabled {
             return Ok(None);
         }
-        let recording = self
+        let mut recording = self
             .assemble(game_id, final_state)
             .expect("enabled replay recorder assembles a recording");
-        recording.verify_end_hash()?;
+        // Indexing replays the archive to its end, so it doubles as the
+        // end-hash verification finalization has always required.
+        recording.index_keyframes()?;
         Ok(Some(recording))
     }
 
@@ -728,6 +731,7 @@ impl ReplayRecordingState {
             messages: self.messages.clone(),
             end_tick: final_state.tick,
             end_sync_hash: final_state.sync_hash(),
+            keyframes: Vec::new(),
         })
     }
 }
@@ -1349,7 +1353,8 @@ pub fn stream_id_leq(left: &str, right: &str) -> Result<bool> {
 mod tests {
     use super::*;
     use common::{
-        CommandId, CustomGameSettings, Direction, GameCommand, GameMode, GameType, QueueMode,
+        CommandId, CustomGameSettings, Direction, EdgePolicy, GameCommand, GameMode, GameType,
+        QueueMode,
     };
 
     fn identity(sequence: u64) -> ClientCommandIdentityV2 {
@@ -1701,6 +1706,11 @@ mod tests {
             is_private: false,
             allow_spectators: true,
             snake_start_length: 4,
+            power_ups: false,
+            shrinking_zone: false,
+            edge_policy: EdgePolicy::Solid,
+            death_food: false,
+            ai_opponents: None,
         };
         let mut state = GameState::new(
             settings.arena_width,
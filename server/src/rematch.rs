@@ -95,10 +95,14 @@ pub fn rematch_game_type(original: &GameType, opted_in: usize) -> Option<GameTyp
         1 => matches!(original, GameType::Solo).then_some(GameType::Solo),
         2 => Some(match original {
             GameType::TeamMatch { per_team: 1 } => GameType::TeamMatch { per_team: 1 },
+            GameType::KingOfTheHill { per_team: 1 } => GameType::KingOfTheHill { per_team: 1 },
+            GameType::CaptureTheFlag { per_team: 1 } => GameType::CaptureTheFlag { per_team: 1 },
             _ => GameType::FreeForAll { max_players: 2 },
         }),
         4 => Some(match original {
             GameType::TeamMatch { per_team: 2 } => GameType::TeamMatch { per_team: 2 },
+            GameType::KingOfTheHill { per_team: 2 } => GameType::KingOfTheHill { per_team: 2 },
+            GameType::CaptureTheFlag { per_team: 2 } => GameType::CaptureTheFlag { per_team: 2 },
             _ => GameType::FreeForAll { max_players: 4 },
         }),
         _ => None,
@@ -352,6 +356,12 @@ mod tests {
 
         let ffa = GameType::FreeForAll { max_players: 4 };
         assert_eq!(rematch_game_type(&ffa, 4), Some(ffa));
+
+        let hill = GameType::KingOfTheHill { per_team: 1 };
+        assert_eq!(rematch_game_type(&hill, 2), Some(hill));
+
+        let ctf = GameType::CaptureTheFlag { per_team: 2 };
+        assert_eq!(rematch_game_type(&ctf, 4), Some(ctf));
     }
 
     #[test]
@@ -0,0 +1,1212 @@
+//! Replays drawn to animated images without a browser.
+//!
+//! Sharing a match used to mean running the canvas renderer in headless
+//! Chromium (`tools/video`). This draws the same board on the CPU — field,
+//! base zones, food, Boost pads, power-ups, flags, snakes in their skins'
+//! palette colours, walls — as flat cells, and encodes the frames as a GIF or
+//! an APNG. Both the server and the `render_replay` tool use it.
+//!
+//! It is a share preview rather than a second game renderer: no gradients,
+//! labels or skin animation, and every frame of a file shares one palette.
+//! Each frame is a spectator's view, so snakes take the colours the client
+//! gives them when nobody in particular is watching.
+
+use crate::skin_catalog::DEFAULT_SKIN_REF;
+use anyhow::{Context, Result, bail, ensure};
+use common::{
+    GameRecordingV1, GameState, HIGHLIGHT_CLIP_FORMAT_VERSION, HighlightClip,
+    HighlightSpeedSegment, Position, PowerUpKind, Snake, TeamId,
+};
+use skin_schema::color::Rgb;
+use skin_schema::{ColorPair, SkinDoc};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Longest side a rendered frame may have, in pixels.
+pub const MAX_RENDER_SIDE_PX: u32 = 1024;
+/// Longest side a frame is fitted to when no cell size is asked for.
+pub const DEFAULT_RENDER_SIDE_PX: u32 = 480;
+/// Most frames one file may hold. Longer windows skip ticks between frames
+/// rather than grow the file.
+pub const MAX_RENDER_FRAMES: usize = 900;
+/// Shortest frame worth emitting. Browsers slow GIF frames under 20 ms to
+/// 100 ms, so faster matches skip ticks instead.
+const MIN_FRAME_MS: u32 = 20;
+/// How long the last frame holds before the animation loops.
+const END_HOLD_MS: f64 = 1_000.0;
+const MAX_CELL_PX: u16 = 32;
+
+/// The image container a replay is encoded into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderFormat {
+    Gif,
+    Apng,
+}
+
+impl RenderFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gif" => Ok(Self::Gif),
+            "apng" | "png" => Ok(Self::Apng),
+            other => bail!("unknown render format '{other}' (expected gif or apng)"),
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Gif => "image/gif",
+            Self::Apng => "image/apng",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Gif => "gif",
+            Self::Apng => "png",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+    pub format: RenderFormat,
+    /// Side of one arena cell in pixels. `None` fits the longer side of the
+    /// arena to [`DEFAULT_RENDER_SIDE_PX`].
+    pub cell_px: Option<u16>,
+    pub max_frames: usize,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            format: RenderFormat::Gif,
+            cell_px: None,
+            max_frames: MAX_RENDER_FRAMES,
+        }
+    }
+}
+
+/// Render `start_tick` through `end_tick` of a recording. A window that runs
+/// to the recording's end is checked against its end hash on the way.
+pub fn render_recording(
+    recording: &GameRecordingV1,
+    start_tick: u32,
+    end_tick: u32,
+    options: &RenderOptions,
+) -> Result<Vec<u8>> {
+    render_ticks(recording, start_tick, end_tick, 0, &[], options)
+}
+
+/// Render a highlight clip's window, turned and paced the way the clip
+/// presents it. The replay that draws the frames also verifies the clip.
+pub fn render_clip(clip: &HighlightClip, options: &RenderOptions) -> Result<Vec<u8>> {
+    ensure!(
+        clip.clip_format_version == HIGHLIGHT_CLIP_FORMAT_VERSION,
+        "unsupported highlight clip version"
+    );
+    render_ticks(
+        &clip.recording(),
+        clip.window.start_tick,
+        clip.window.end_tick,
+        clip.presentation.rotation,
+        &clip.presentation.segments,
+        options,
+    )
+}
+
+fn render_ticks(
+    recording: &GameRecordingV1,
+    start_tick: u32,
+    end_tick: u32,
+    rotation: i32,
+    segments: &[HighlightSpeedSegment],
+    options: &RenderOptions,
+) -> Result<Vec<u8>> {
+    ensure!(options.max_frames > 0, "max frames must be positive");
+    ensure!(
+        matches!(rotation, 0 | 90 | 180 | 270),
+        "unsupported rotation {rotation}"
+    );
+    let palette = Palette::new();
+    let mut renderer: Option<Renderer> = None;
+    let mut sink = FrameSink::new(options.format, &palette);
+    let mut stride = 1;
+    let mut final_hash = 0;
+    let mut tick = start_tick;
+    recording.for_each_tick(start_tick, end_tick, |state| {
+        let renderer = match &mut renderer {
+            Some(renderer) => renderer,
+            None => {
+                let tick_ms = state.properties.tick_duration_ms.max(1);
+                let ticks = (end_tick - start_tick) as usize + 1;
+                stride = (MIN_FRAME_MS.div_ceil(tick_ms) as usize)
+                    .max(ticks.div_ceil(options.max_frames));
+                renderer.insert(Renderer::new(state, options.cell_px, &palette)?)
+            }
+        };
+        // Ticks after the match ended repeat its last state, which the sink
+        // folds into one held frame.
+        if ((tick - start_tick) as usize).is_multiple_of(stride) {
+            renderer.draw(state);
+            let (pixels, width, height) = rotated(&renderer.canvas, rotation);
+            sink.push(pixels, width, height)?;
+        }
+        let scale = segments
+            .iter()
+            .find(|segment| tick < segment.until_tick)
+            .map_or(1.0, |segment| f64::from(segment.time_scale).max(0.01));
+        sink.hold(f64::from(state.properties.tick_duration_ms.max(1)) / scale);
+        final_hash = state.sync_hash();
+        tick += 1;
+        Ok(())
+    })?;
+    if end_tick == recording.end_tick {
+        ensure!(
+            final_hash == recording.end_sync_hash,
+            "replay does not reach its end hash: expected {:#018x}, got {:#018x}",
+            recording.end_sync_hash,
+            final_hash
+        );
+    }
+    sink.hold(END_HOLD_MS);
+    sink.finish()
+}
+
+// Board colours, flattened from the client renderer's constants. Translucent
+// washes are pre-blended over the white field.
+type Color = [u8; 3];
+const FIELD: Color = [0xff, 0xff, 0xff];
+/// `rgba(0, 0, 0, 0.3)` grid dots.
+const GRID_DOT: Color = [0xb3, 0xb3, 0xb3];
+/// `rgba(220, 38, 38, 0.16)` over cells outside the battle royale zone.
+const ZONE_CLOSED: Color = [0xf9, 0xdc, 0xdc];
+const ZONE_EDGE: Color = [0xdc, 0x26, 0x26];
+/// `rgba(15, 23, 42, 0.08)` over a hill nobody holds.
+const HILL_UNCLAIMED: Color = [0xec, 0xed, 0xee];
+const HILL_EDGE: Color = [0xf5, 0x9e, 0x0b];
+const OBSTACLE: Color = [0x47, 0x55, 0x69];
+const FLAG_POLE: Color = [0x33, 0x41, 0x55];
+const FOOD: Color = [0x85, 0xb8, 0x85];
+const FOOD_BORDER: Color = [0x5e, 0x8a, 0x5e];
+const NOS_BLUE: Color = [0x3b, 0x82, 0xf6];
+const NOS_ORANGE: Color = [0xff, 0x64, 0x1e];
+const CORPSE_FILL: Color = [0xf0, 0xf0, 0xf0];
+const CORPSE_BORDER: Color = [0xd0, 0xd0, 0xd0];
+/// The classic base theme, which is what a spectator's board wears.
+const FRIENDLY_ZONE: Color = [0xe6, 0xf4, 0xfa];
+const ENEMY_ZONE: Color = [0xff, 0xe6, 0xe6];
+const FRIENDLY_WALL: Color = [0x7a, 0xa8, 0xc1];
+const ENEMY_WALL: Color = [0xc1, 0x88, 0x88];
+
+fn power_up_colors(kind: PowerUpKind) -> (Color, Color) {
+    match kind {
+        PowerUpKind::Shield => ([0x3f, 0x8f, 0xd6], [0x1f, 0x5a, 0x8f]),
+        PowerUpKind::Ghost => ([0xb9, 0xb4, 0xd6], [0x6f, 0x6a, 0x96]),
+        PowerUpKind::Magnet => ([0xd6, 0x5a, 0x5a], [0x8f, 0x2f, 0x2f]),
+    }
+}
+
+/// Every skin this module has a document for, by catalogue ref. Skins the
+/// client paints in code rather than from a document wear classic's palette
+/// here.
+static SKIN_DOCUMENTS: LazyLock<Vec<(&'static str, SkinDoc)>> = LazyLock::new(|| {
+    [
+        (
+            DEFAULT_SKIN_REF,
+            include_str!("../../skin-schema/skins/classic.skin.json"),
+        ),
+        (
+            "aurora@1",
+            include_str!("../../skin-schema/skins/aurora.skin.json"),
+        ),
+        (
+            "tidewave@1",
+            include_str!("../../skin-schema/skins/tidewave.skin.json"),
+        ),
+        (
+            "voltage@1",
+            include_str!("../../skin-schema/skins/voltage.skin.json"),
+        ),
+        (
+            "lantern@1",
+            include_str!("../../skin-schema/skins/lantern.skin.json"),
+        ),
+    ]
+    .into_iter()
+    .map(|(skin_ref, json)| {
+        let doc = skin_schema::load(json)
+            .unwrap_or_else(|errors| panic!("shipped skin {skin_ref} is invalid: {errors:?}"));
+        (skin_ref, doc)
+    })
+    .collect()
+});
+
+fn skin_document(skin_ref: Option<&str>) -> &'static SkinDoc {
+    let documents = &*SKIN_DOCUMENTS;
+    let (_, doc) = skin_ref
+        .and_then(|skin_ref| documents.iter().find(|(known, _)| *known == skin_ref))
+        .unwrap_or(&documents[0]);
+    doc
+}
+
+fn hex_color(hex: &str) -> Color {
+    let Some(rgb) = Rgb::parse(hex) else {
+        return [0, 0, 0];
+    };
+    let byte = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    [byte(rgb.r), byte(rgb.g), byte(rgb.b)]
+}
+
+/// The pair the client's spectator view paints a snake with: team games by
+/// side and teammate slot, duels as two opponents, free-for-all by slot.
+fn spectator_colors<'a>(doc: &'a SkinDoc, snakes: &[Snake], index: usize) -> &'a ColorPair {
+    let snake = &snakes[index];
+    let palette = &doc.palette;
+    if snakes.iter().any(|snake| snake.team_id.is_some()) {
+        let shade = snakes[..index]
+            .iter()
+            .filter(|candidate| candidate.team_id == snake.team_id)
+            .count()
+            % 2;
+        return match snake.team_id {
+            Some(TeamId(0)) => &palette.friendly[shade],
+            _ => &palette.enemy[shade],
+        };
+    }
+    if snakes.len() == 2 {
+        return &palette.enemy[0];
+    }
+    &palette.free_for_all[index % 4]
+}
+
+/// One palette for a whole file: every board colour and every colour a
+/// shipped skin document can put on a snake.
+struct Palette {
+    colors: Vec<Color>,
+}
+
+impl Palette {
+    fn new() -> Self {
+        let mut colors = vec![
+            FIELD,
+            GRID_DOT,
+            ZONE_CLOSED,
+            ZONE_EDGE,
+            HILL_UNCLAIMED,
+            HILL_EDGE,
+            OBSTACLE,
+            FLAG_POLE,
+            FOOD,
+            FOOD_BORDER,
+            NOS_BLUE,
+            NOS_ORANGE,
+            CORPSE_FILL,
+            CORPSE_BORDER,
+            FRIENDLY_ZONE,
+            ENEMY_ZONE,
+            FRIENDLY_WALL,
+            ENEMY_WALL,
+        ];
+        for kind in [PowerUpKind::Shield, PowerUpKind::Ghost, PowerUpKind::Magnet] {
+            let (fill, outline) = power_up_colors(kind);
+            colors.extend([fill, outline]);
+        }
+        for (_, doc) in SKIN_DOCUMENTS.iter() {
+            let palette = &doc.palette;
+            for pair in palette
+                .friendly
+                .iter()
+                .chain(&palette.enemy)
+                .chain(&palette.free_for_all)
+            {
+                colors.extend([hex_color(&pair.fill), hex_color(&pair.outline)]);
+            }
+            colors.push(hex_color(&doc.head.core_color));
+            colors.push(hex_color(&doc.outline.boost_band.color));
+        }
+        let mut unique = Vec::with_capacity(colors.len());
+        for color in colors {
+            if !unique.contains(&color) {
+                unique.push(color);
+            }
+        }
+        assert!(unique.len() <= 256, "render palette overflows 256 colours");
+        Self { colors: unique }
+    }
+
+    /// The entry for `color`, or the nearest one if it is not in the palette.
+    fn index(&self, color: Color) -> u8 {
+        let distance = |entry: &Color| -> u32 {
+            entry
+                .iter()
+                .zip(color)
+                .map(|(a, b)| u32::from(a.abs_diff(b)).pow(2))
+                .sum()
+        };
+        self.colors
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, entry)| distance(entry))
+            .map_or(0, |(index, _)| index as u8)
+    }
+}
+
+/// Palette indices for the fixed board colours.
+struct Ink {
+    field: u8,
+    grid_dot: u8,
+    zone_closed: u8,
+    zone_edge: u8,
+    hill_unclaimed: u8,
+    hill_edge: u8,
+    obstacle: u8,
+    flag_pole: u8,
+    food: u8,
+    food_border: u8,
+    nos_blue: u8,
+    nos_orange: u8,
+    corpse_fill: u8,
+    corpse_border: u8,
+    friendly_zone: u8,
+    enemy_zone: u8,
+    friendly_wall: u8,
+    enemy_wall: u8,
+}
+
+impl Ink {
+    fn new(palette: &Palette) -> Self {
+        Self {
+            field: palette.index(FIELD),
+            grid_dot: palette.index(GRID_DOT),
+            zone_closed: palette.index(ZONE_CLOSED),
+            zone_edge: palette.index(ZONE_EDGE),
+            hill_unclaimed: palette.index(HILL_UNCLAIMED),
+            hill_edge: palette.index(HILL_EDGE),
+            obstacle: palette.index(OBSTACLE),
+            flag_pole: palette.index(FLAG_POLE),
+            food: palette.index(FOOD),
+            food_border: palette.index(FOOD_BORDER),
+            nos_blue: palette.index(NOS_BLUE),
+            nos_orange: palette.index(NOS_ORANGE),
+            corpse_fill: palette.index(CORPSE_FILL),
+            corpse_border: palette.index(CORPSE_BORDER),
+            friendly_zone: palette.index(FRIENDLY_ZONE),
+            enemy_zone: palette.index(ENEMY_ZONE),
+            friendly_wall: palette.index(FRIENDLY_WALL),
+            enemy_wall: palette.index(ENEMY_WALL),
+        }
+    }
+}
+
+/// An indexed-colour frame. Drawing clips to the frame, so callers can pass
+/// rectangles that hang off any edge.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn fill_rect(&mut self, x: i64, y: i64, width: i64, height: i64, ink: u8) {
+        let x0 = x.clamp(0, i64::from(self.width));
+        let y0 = y.clamp(0, i64::from(self.height));
+        let x1 = (x + width).clamp(0, i64::from(self.width));
+        let y1 = (y + height).clamp(0, i64::from(self.height));
+        for row in y0..y1 {
+            let start = (row * i64::from(self.width)) as usize;
+            self.pixels[start + x0 as usize..start + x1.max(x0) as usize].fill(ink);
+        }
+    }
+
+    fn stroke_rect(&mut self, x: i64, y: i64, width: i64, height: i64, line: i64, ink: u8) {
+        self.fill_rect(x, y, width, line, ink);
+        self.fill_rect(x, y + height - line, width, line, ink);
+        self.fill_rect(x, y, line, height, ink);
+        self.fill_rect(x + width - line, y, line, height, ink);
+    }
+
+    /// Fill every pixel whose centre lies within `radius` of the centre.
+    fn fill_disc(&mut self, center_x: f64, center_y: f64, radius: f64, ink: u8) {
+        let x0 = (center_x - radius).floor().max(0.0) as i64;
+        let y0 = (center_y - radius).floor().max(0.0) as i64;
+        let x1 = ((center_x + radius).ceil() as i64).min(i64::from(self.width));
+        let y1 = ((center_y + radius).ceil() as i64).min(i64::from(self.height));
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let dx = x as f64 + 0.5 - center_x;
+                let dy = y as f64 + 0.5 - center_y;
+                if dx * dx + dy * dy <= radius * radius {
+                    self.pixels[(y * i64::from(self.width) + x) as usize] = ink;
+                }
+            }
+        }
+    }
+}
+
+struct Renderer<'a> {
+    palette: &'a Palette,
+    ink: Ink,
+    cell: i64,
+    canvas: Canvas,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(state: &GameState, cell_px: Option<u16>, palette: &'a Palette) -> Result<Self> {
+        let arena = &state.arena;
+        let longest = u32::from(arena.width.max(arena.height).max(1));
+        let cell = match cell_px {
+            Some(cell_px) => {
+                ensure!(
+                    (1..=MAX_CELL_PX).contains(&cell_px),
+                    "cell size must be between 1 and {MAX_CELL_PX} px"
+                );
+                u32::from(cell_px)
+            }
+            None => (DEFAULT_RENDER_SIDE_PX / longest).clamp(1, u32::from(MAX_CELL_PX)),
+        };
+        let width = u32::from(arena.width) * cell;
+        let height = u32::from(arena.height) * cell;
+        ensure!(width > 0 && height > 0, "arena has no area to render");
+        ensure!(
+            width <= MAX_RENDER_SIDE_PX && height <= MAX_RENDER_SIDE_PX,
+            "a {width}x{height} px frame is larger than {MAX_RENDER_SIDE_PX} px; use a smaller cell size"
+        );
+        Ok(Self {
+            palette,
+            ink: Ink::new(palette),
+            cell: i64::from(cell),
+            canvas: Canvas {
+                width,
+                height,
+                pixels: vec![0; (width * height) as usize],
+            },
+        })
+    }
+
+    fn cell_rect(&mut self, x: i64, y: i64, width: i64, height: i64, inset: i64, ink: u8) {
+        let cell = self.cell;
+        self.canvas.fill_rect(
+            x * cell + inset,
+            y * cell + inset,
+            width * cell - 2 * inset,
+            height * cell - 2 * inset,
+            ink,
+        );
+    }
+
+    fn cell_center(&self, position: &Position) -> (f64, f64) {
+        let cell = self.cell as f64;
+        (
+            (f64::from(position.x) + 0.5) * cell,
+            (f64::from(position.y) + 0.5) * cell,
+        )
+    }
+
+    /// Paint one frame, bottom layer first, in the client's order.
+    fn draw(&mut self, state: &GameState) {
+        let arena = &state.arena;
+        let (width, height) = (i64::from(arena.width), i64::from(arena.height));
+        let cell = self.cell;
+        let line = (cell / 4).max(1);
+        self.canvas.pixels.fill(self.ink.field);
+
+        if let Some(config) = &arena.team_zone_config {
+            let depth = i64::from(config.end_zone_depth);
+            self.cell_rect(0, 0, depth, height, 0, self.ink.friendly_zone);
+            self.cell_rect(width - depth, 0, depth, height, 0, self.ink.enemy_zone);
+        }
+
+        if cell >= 4 {
+            for y in 1..height {
+                for x in 1..width {
+                    self.canvas
+                        .fill_rect(x * cell, y * cell, 1, 1, self.ink.grid_dot);
+                }
+            }
+        }
+
+        if let Some(zone) = &arena.zone {
+            let (min_x, min_y) = (i64::from(zone.min_x), i64::from(zone.min_y));
+            let (max_x, max_y) = (i64::from(zone.max_x), i64::from(zone.max_y));
+            let ink = self.ink.zone_closed;
+            self.cell_rect(0, 0, width, min_y, 0, ink);
+            self.cell_rect(0, max_y, width, height - max_y, 0, ink);
+            self.cell_rect(0, min_y, min_x, max_y - min_y, 0, ink);
+            self.cell_rect(max_x, min_y, width - max_x, max_y - min_y, 0, ink);
+        }
+
+        if let (Some(config), Some(control)) = (&state.properties.hill, &state.hill) {
+            let bounds = config.bounds(arena.width, arena.height);
+            let (x, y) = (i64::from(bounds.min_x), i64::from(bounds.min_y));
+            let (hill_width, hill_height) =
+                (i64::from(bounds.max_x) - x, i64::from(bounds.max_y) - y);
+            let fill = match control.holder {
+                Some(TeamId(0)) => self.ink.friendly_zone,
+                Some(_) => self.ink.enemy_zone,
+                None => self.ink.hill_unclaimed,
+            };
+            let edge = if control.contested {
+                self.ink.zone_edge
+            } else {
+                self.ink.hill_edge
+            };
+            self.cell_rect(x, y, hill_width, hill_height, 0, fill);
+            self.canvas.stroke_rect(
+                x * cell,
+                y * cell,
+                hill_width * cell,
+                hill_height * cell,
+                2.min(cell),
+                edge,
+            );
+        }
+
+        let radius = cell as f64 / 2.0;
+        for food in &arena.food {
+            // Clear the grid dots at the corners, as the client does.
+            self.canvas.fill_rect(
+                i64::from(food.x) * cell - 1,
+                i64::from(food.y) * cell - 1,
+                cell + 2,
+                cell + 2,
+                self.ink.field,
+            );
+            let (center_x, center_y) = self.cell_center(food);
+            self.canvas
+                .fill_disc(center_x, center_y, radius, self.ink.food_border);
+            self.canvas
+                .fill_disc(center_x, center_y, radius - 1.0, self.ink.food);
+        }
+
+        // Cooling pads are absent from the art, as they are in the client.
+        for pad in arena
+            .boost_pads
+            .iter()
+            .filter(|pad| pad.respawn_at_tick.is_none())
+        {
+            let (x, y) = (i64::from(pad.position.x), i64::from(pad.position.y));
+            let size = i64::from(pad.size_cells.max(1));
+            self.cell_rect(x, y, size, size, 0, self.ink.nos_blue);
+            self.canvas.fill_rect(
+                x * cell + size * cell / 4,
+                y * cell + size * cell / 4,
+                size * cell / 2,
+                size * cell / 2,
+                self.ink.nos_orange,
+            );
+        }
+
+        for pickup in &arena.power_ups {
+            let (fill, outline) = power_up_colors(pickup.kind);
+            let (center_x, center_y) = self.cell_center(&pickup.position);
+            self.canvas
+                .fill_disc(center_x, center_y, radius, self.palette.index(outline));
+            self.canvas.fill_disc(
+                center_x,
+                center_y,
+                radius - (cell as f64 / 8.0).max(1.0),
+                self.palette.index(fill),
+            );
+        }
+
+        for flag in &state.flags {
+            let Some(position) = flag.position(arena) else {
+                continue;
+            };
+            let (x, y) = (i64::from(position.x) * cell, i64::from(position.y) * cell);
+            let pole = (cell / 8).max(1);
+            let cloth = match flag.team_id {
+                TeamId(0) => self.ink.friendly_wall,
+                _ => self.ink.enemy_wall,
+            };
+            self.canvas
+                .fill_rect(x + cell / 4, y, pole, cell, self.ink.flag_pole);
+            self.canvas
+                .fill_rect(x + cell / 4 + pole, y, cell / 2, cell / 2, cloth);
+        }
+
+        let snake_skins: HashMap<u32, &str> = state
+            .players
+            .iter()
+            .filter_map(|(user_id, player)| {
+                let skin_ref = state.skins.get(user_id)?;
+                Some((player.snake_id, skin_ref.as_str()))
+            })
+            .collect();
+        for (index, snake) in arena.snakes.iter().enumerate() {
+            let doc = skin_document(snake_skins.get(&(index as u32)).copied());
+            for (dx, dy) in arena.seam_offsets(snake) {
+                self.draw_snake(&arena.snakes, index, doc, (dx, dy), line);
+            }
+        }
+
+        if let Some(config) = &arena.team_zone_config {
+            let depth = i64::from(config.end_zone_depth);
+            let goal_center = height as f64 / 2.0;
+            let goal_half_width = f64::from(config.goal_width) / 2.0;
+            let goal_start = (goal_center - goal_half_width).floor() as i64;
+            let goal_end = (goal_center + goal_half_width).ceil() as i64;
+            let wall = (cell / 3).max(2);
+            for (x, ink) in [
+                (depth, self.ink.friendly_wall),
+                (width - depth, self.ink.enemy_wall),
+            ] {
+                let left = x * cell - wall / 2;
+                self.canvas.fill_rect(left, 0, wall, goal_start * cell, ink);
+                self.canvas
+                    .fill_rect(left, goal_end * cell, wall, (height - goal_end) * cell, ink);
+            }
+        }
+
+        if let Some(map) = &arena.map {
+            for block in &map.obstacles {
+                self.cell_rect(
+                    i64::from(block.position.x),
+                    i64::from(block.position.y),
+                    i64::from(block.width),
+                    i64::from(block.height),
+                    0,
+                    self.ink.obstacle,
+                );
+            }
+        }
+
+        if let Some(zone) = &arena.zone {
+            let (x, y) = (i64::from(zone.min_x), i64::from(zone.min_y));
+            let (zone_width, zone_height) = (i64::from(zone.max_x) - x, i64::from(zone.max_y) - y);
+            if zone_width < width || zone_height < height {
+                self.canvas.stroke_rect(
+                    x * cell,
+                    y * cell,
+                    zone_width * cell,
+                    zone_height * cell,
+                    2.min(cell),
+                    self.ink.zone_edge,
+                );
+            }
+        }
+    }
+
+    /// One copy of one snake: each straight run of the compressed body as a
+    /// rectangle, outline first and fill inset over it, so runs that meet at
+    /// a turn share one silhouette.
+    fn draw_snake(
+        &mut self,
+        snakes: &[Snake],
+        index: usize,
+        doc: &SkinDoc,
+        (dx, dy): (i16, i16),
+        line: i64,
+    ) {
+        let snake = &snakes[index];
+        let runs: Vec<(i64, i64, i64, i64)> = match snake.body.as_slice() {
+            [] => return,
+            [only] => vec![(i64::from(only.x), i64::from(only.y), 1, 1)],
+            body => body
+                .windows(2)
+                .map(|pair| {
+                    let (a, b) = (&pair[0], &pair[1]);
+                    let (x, y) = (a.x.min(b.x), a.y.min(b.y));
+                    (
+                        i64::from(x),
+                        i64::from(y),
+                        i64::from(a.x.abs_diff(b.x)) + 1,
+                        i64::from(a.y.abs_diff(b.y)) + 1,
+                    )
+                })
+                .collect(),
+        };
+        let runs: Vec<_> = runs
+            .into_iter()
+            .map(|(x, y, w, h)| (x + i64::from(dx), y + i64::from(dy), w, h))
+            .collect();
+        let inset = if self.cell >= 4 {
+            line.min(self.cell / 4)
+        } else {
+            0
+        };
+
+        if !snake.is_alive {
+            for &(x, y, w, h) in &runs {
+                self.cell_rect(x, y, w, h, 0, self.ink.corpse_border);
+            }
+            for &(x, y, w, h) in &runs {
+                self.cell_rect(x, y, w, h, inset, self.ink.corpse_fill);
+            }
+            return;
+        }
+
+        let pair = spectator_colors(doc, snakes, index);
+        let fill = self.palette.index(hex_color(&pair.fill));
+        let outline = self.palette.index(hex_color(&pair.outline));
+        if snake.boost().active {
+            let band = self.palette.index(hex_color(&doc.outline.boost_band.color));
+            for &(x, y, w, h) in &runs {
+                self.cell_rect(x, y, w, h, -line, band);
+            }
+        }
+        for &(x, y, w, h) in &runs {
+            self.cell_rect(x, y, w, h, 0, outline);
+        }
+        // A ghost keeps only its outline, the flat stand-in for the client's
+        // see-through body.
+        if !snake.power_ups.ghost() {
+            for &(x, y, w, h) in &runs {
+                self.cell_rect(x, y, w, h, inset, fill);
+            }
+        }
+        let head = &snake.body[0];
+        let (center_x, center_y) = self.cell_center(&Position {
+            x: head.x + dx,
+            y: head.y + dy,
+        });
+        let core = self.palette.index(hex_color(&doc.head.core_color));
+        self.canvas.fill_disc(
+            center_x,
+            center_y,
+            doc.head.core_ratio * self.cell as f64,
+            core,
+        );
+    }
+}
+
+/// The canvas turned clockwise by `rotation` degrees, the way the client
+/// turns the board for a presentation.
+fn rotated(canvas: &Canvas, rotation: i32) -> (Vec<u8>, u32, u32) {
+    let (width, height) = (canvas.width as usize, canvas.height as usize);
+    let (out_width, out_height) = match rotation {
+        90 | 270 => (height, width),
+        _ => (width, height),
+    };
+    if rotation == 0 {
+        return (canvas.pixels.clone(), canvas.width, canvas.height);
+    }
+    let mut pixels = vec![0; canvas.pixels.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let (to_x, to_y) = match rotation {
+                90 => (height - 1 - y, x),
+                180 => (width - 1 - x, height - 1 - y),
+                _ => (y, width - 1 - x),
+            };
+            pixels[to_y * out_width + to_x] = canvas.pixels[y * width + x];
+        }
+    }
+    (pixels, out_width as u32, out_height as u32)
+}
+
+/// The part of a frame that changed, as `(x, y, width, height)`.
+type Rect = (u32, u32, u32, u32);
+
+fn changed_rect(previous: &[u8], next: &[u8], width: u32) -> Option<Rect> {
+    let width = width as usize;
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for (row, (before, after)) in previous
+        .chunks_exact(width)
+        .zip(next.chunks_exact(width))
+        .enumerate()
+    {
+        let Some(first) = before.iter().zip(after).position(|(a, b)| a != b) else {
+            continue;
+        };
+        let last = before
+            .iter()
+            .zip(after)
+            .rposition(|(a, b)| a != b)
+            .unwrap_or(first);
+        bounds = Some(match bounds {
+            None => (first, row, last, row),
+            Some((x0, y0, x1, _)) => (x0.min(first), y0, x1.max(last), row),
+        });
+    }
+    bounds.map(|(x0, y0, x1, y1)| {
+        (
+            x0 as u32,
+            y0 as u32,
+            (x1 - x0 + 1) as u32,
+            (y1 - y0 + 1) as u32,
+        )
+    })
+}
+
+fn cropped(pixels: &[u8], width: u32, (x, y, rect_width, rect_height): Rect) -> Vec<u8> {
+    let mut out = Vec::with_capacity((rect_width * rect_height) as usize);
+    for row in y..y + rect_height {
+        let start = (row * width + x) as usize;
+        out.extend_from_slice(&pixels[start..start + rect_width as usize]);
+    }
+    out
+}
+
+struct PendingFrame {
+    pixels: Vec<u8>,
+    delay_ms: f64,
+}
+
+/// Collects frames, folds repeats into the previous frame's delay, and hands
+/// the encoder only the rectangle each frame changed.
+struct FrameSink<'a> {
+    format: RenderFormat,
+    palette: &'a Palette,
+    encoder: Option<Encoder>,
+    size: (u32, u32),
+    pending: Option<PendingFrame>,
+    written: Option<Vec<u8>>,
+}
+
+impl<'a> FrameSink<'a> {
+    fn new(format: RenderFormat, palette: &'a Palette) -> Self {
+        Self {
+            format,
+            palette,
+            encoder: None,
+            size: (0, 0),
+            pending: None,
+            written: None,
+        }
+    }
+
+    fn push(&mut self, pixels: Vec<u8>, width: u32, height: u32) -> Result<()> {
+        if self.encoder.is_none() {
+            self.size = (width, height);
+            self.encoder = Some(Encoder::new(self.format, width, height, self.palette)?);
+        }
+        ensure!(
+            self.size == (width, height),
+            "the arena changed size mid-replay"
+        );
+        if let Some(pending) = &self.pending
+            && pending.pixels == pixels
+        {
+            return Ok(());
+        }
+        if let Some(pending) = self.pending.take() {
+            self.write(pending)?;
+        }
+        self.pending = Some(PendingFrame {
+            pixels,
+            delay_ms: 0.0,
+        });
+        Ok(())
+    }
+
+    /// Extend how long the latest frame stays on screen.
+    fn hold(&mut self, delay_ms: f64) {
+        if let Some(pending) = &mut self.pending {
+            pending.delay_ms += delay_ms;
+        }
+    }
+
+    fn write(&mut self, frame: PendingFrame) -> Result<()> {
+        let (width, height) = self.size;
+        let rect = self
+            .written
+            .as_deref()
+            .map_or(Some((0, 0, width, height)), |written| {
+                changed_rect(written, &frame.pixels, width)
+            })
+            .unwrap_or((0, 0, 1, 1));
+        let encoder = self.encoder.as_mut().context("no frame to encode")?;
+        encoder.frame(cropped(&frame.pixels, width, rect), rect, frame.delay_ms)?;
+        self.written = Some(frame.pixels);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<u8>> {
+        let pending = self.pending.take().context("replay window has no frames")?;
+        self.write(pending)?;
+        let encoder = self.encoder.take().context("no frame to encode")?;
+        encoder.finish()
+    }
+}
+
+enum Encoder {
+    Gif(GifEncoder),
+    Apng(ApngEncoder),
+}
+
+impl Encoder {
+    fn new(format: RenderFormat, width: u32, height: u32, palette: &Palette) -> Result<Self> {
+        let palette: Vec<u8> = palette.colors.iter().flatten().copied().collect();
+        Ok(match format {
+            RenderFormat::Gif => Self::Gif(GifEncoder::new(width, height, &palette)?),
+            RenderFormat::Apng => Self::Apng(ApngEncoder {
+                width,
+                height,
+                palette,
+                frames: Vec::new(),
+            }),
+        })
+    }
+
+    fn frame(&mut self, pixels: Vec<u8>, rect: Rect, delay_ms: f64) -> Result<()> {
+        match self {
+            Self::Gif(encoder) => encoder.frame(pixels, rect, delay_ms),
+            Self::Apng(encoder) => {
+                encoder.frames.push((pixels, rect, delay_ms));
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(self) -> Result<Vec<u8>> {
+        match self {
+            Self::Gif(encoder) => Ok(encoder.encoder.into_inner()?),
+            Self::Apng(encoder) => encoder.finish(),
+        }
+    }
+}
+
+/// A GIF that loops forever. Each frame is drawn over the last, so it only
+/// carries the rectangle that changed.
+struct GifEncoder {
+    encoder: gif::Encoder<Vec<u8>>,
+    elapsed_ms: f64,
+}
+
+impl GifEncoder {
+    fn new(width: u32, height: u32, palette: &[u8]) -> Result<Self> {
+        let mut encoder = gif::Encoder::new(Vec::new(), width as u16, height as u16, palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        Ok(Self {
+            encoder,
+            elapsed_ms: 0.0,
+        })
+    }
+
+    fn frame(&mut self, pixels: Vec<u8>, (x, y, width, height): Rect, delay_ms: f64) -> Result<()> {
+        // GIF delays are whole centiseconds. Rounding the running clock
+        // rather than each frame keeps a long replay at the match's pace.
+        let start = (self.elapsed_ms / 10.0).round();
+        self.elapsed_ms += delay_ms;
+        let delay = ((self.elapsed_ms / 10.0).round() - start).clamp(2.0, 65_535.0) as u16;
+        self.encoder.write_frame(&gif::Frame {
+            delay,
+            dispose: gif::DisposalMethod::Keep,
+            left: x as u16,
+            top: y as u16,
+            width: width as u16,
+            height: height as u16,
+            buffer: pixels.into(),
+            ..gif::Frame::default()
+        })?;
+        Ok(())
+    }
+}
+
+/// An APNG, held until the end because its header counts the frames. The
+/// first frame is full size, so viewers without animation show it still.
+struct ApngEncoder {
+    width: u32,
+    height: u32,
+    palette: Vec<u8>,
+    frames: Vec<(Vec<u8>, Rect, f64)>,
+}
+
+impl ApngEncoder {
+    fn finish(self) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette);
+        encoder.set_animated(self.frames.len() as u32, 0)?;
+        let mut writer = encoder.write_header()?;
+        writer.set_dispose_op(png::DisposeOp::None)?;
+        writer.set_blend_op(png::BlendOp::Source)?;
+        for (pixels, (x, y, width, height), delay_ms) in self.frames {
+            // Move to the origin first: the encoder checks each step against
+            // the frame it is replacing.
+            writer.reset_frame_position()?;
+            writer.set_frame_dimension(width, height)?;
+            writer.set_frame_position(x, y)?;
+            writer.set_frame_delay(delay_ms.round().clamp(0.0, 65_535.0) as u16, 1_000)?;
+            writer.write_image_data(&pixels)?;
+        }
+        writer.finish()?;
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{
+        GAME_RECORDING_FORMAT_VERSION, GAMEPLAY_REPLAY_VERSION, GameEvent, RecordedGameMessage,
+        ReplayAnchor, ReplayVisibility, ScenarioScript,
+    };
+
+    fn combo_recording() -> GameRecordingV1 {
+        let loaded =
+            ScenarioScript::from_json(include_str!("../../client/web/scenarios/combo-frenzy.json"))
+                .unwrap()
+                .load()
+                .unwrap();
+        let run = loaded.run().unwrap();
+        let messages = run
+            .events
+            .iter()
+            .filter(|(_, _, event)| !matches!(event, GameEvent::Snapshot { .. }))
+            .enumerate()
+            .map(|(index, (tick, _, event))| RecordedGameMessage {
+                tick: *tick,
+                sequence: index as u64 + 1,
+                event: event.clone(),
+            })
+            .collect();
+        GameRecordingV1 {
+            format_version: GAME_RECORDING_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: 24,
+            visibility: ReplayVisibility::Public,
+            anchors: vec![ReplayAnchor {
+                tick: loaded.initial_state.tick,
+                sequence: 0,
+                state: loaded.initial_state,
+            }],
+            messages,
+            end_tick: run.final_state.tick,
+            end_sync_hash: run.final_state.sync_hash(),
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// Composite every frame of a GIF, returning its width and each full
+    /// frame with its delay in centiseconds.
+    fn gif_frames(gif: &[u8]) -> (u32, Vec<(Vec<u8>, u16)>) {
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(gif).unwrap();
+        let width = u32::from(decoder.width());
+        let mut canvas = vec![0u8; usize::from(decoder.width()) * usize::from(decoder.height())];
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.dispose, gif::DisposalMethod::Keep);
+            for (row, line) in frame.buffer.chunks(usize::from(frame.width)).enumerate() {
+                let start = (u32::from(frame.top) + row as u32) * width + u32::from(frame.left);
+                canvas[start as usize..start as usize + line.len()].copy_from_slice(line);
+            }
+            frames.push((canvas.clone(), frame.delay));
+        }
+        (width, frames)
+    }
+
+    #[test]
+    fn a_recording_renders_as_a_looping_gif_of_its_board() {
+        let recording = combo_recording();
+        let options = RenderOptions {
+            cell_px: Some(6),
+            ..RenderOptions::default()
+        };
+        let gif = render_recording(&recording, 0, recording.end_tick, &options).unwrap();
+        assert!(gif.windows(11).any(|window| window == b"NETSCAPE2.0"));
+
+        let (width, frames) = gif_frames(&gif);
+        let state = recording.anchors[0].state.clone();
+        assert_eq!(width, u32::from(state.arena.width) * 6);
+        assert!(frames.len() > 2 && frames.len() <= recording.end_tick as usize + 1);
+        // Frames play at the match's own pace, and the last one holds.
+        let tick_cs = (state.properties.tick_duration_ms / 10) as u16;
+        assert!(
+            frames[..frames.len() - 1]
+                .iter()
+                .all(|(_, delay)| delay % tick_cs == 0)
+        );
+        assert!(frames.last().unwrap().1 >= 100);
+
+        // Solo play is a free-for-all slot 0 snake in the classic palette.
+        let palette = Palette::new();
+        let classic = skin_document(None);
+        let fill = palette.index(hex_color(&classic.palette.free_for_all[0].fill));
+        let food = palette.index(FOOD);
+        assert!(frames[0].0.contains(&fill));
+        assert!(frames[0].0.contains(&food));
+
+        let mut forged = recording.clone();
+        forged.end_sync_hash ^= 1;
+        assert!(render_recording(&forged, 0, forged.end_tick, &options).is_err());
+        assert!(render_recording(&recording, 0, recording.end_tick + 1, &options).is_err());
+    }
+
+    #[test]
+    fn an_apng_counts_its_frames_and_opens_on_a_full_image() {
+        let recording = combo_recording();
+        let options = RenderOptions {
+            format: RenderFormat::Apng,
+            cell_px: Some(4),
+            max_frames: 5,
+        };
+        let apng = render_recording(&recording, 2, recording.end_tick, &options).unwrap();
+        let mut decoder = png::Decoder::new(apng.as_slice());
+        decoder.set_transformations(png::Transformations::IDENTITY);
+        let mut reader = decoder.read_info().unwrap();
+        let info = reader.info();
+        let (width, height) = (info.width, info.height);
+        assert_eq!(info.color_type, png::ColorType::Indexed);
+        let palette_len = info.palette.as_ref().unwrap().len() / 3;
+        let frame_count = info.animation_control().unwrap().num_frames;
+        assert!((2..=5).contains(&frame_count));
+
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let first = reader.next_frame(&mut buffer).unwrap();
+        assert_eq!((first.width, first.height), (width, height));
+        assert!(
+            buffer[..first.buffer_size()]
+                .iter()
+                .all(|&index| usize::from(index) < palette_len)
+        );
+        let mut decoded = 1;
+        while decoded < frame_count {
+            reader.next_frame(&mut buffer).unwrap();
+            decoded += 1;
+        }
+        reader.finish().unwrap();
+    }
+
+    #[test]
+    fn snakes_wear_their_skin_documents_palette() {
+        let mut state = combo_recording().anchors[0].state.clone();
+        let user_id = *state.players.keys().next().expect("scenario has a player");
+        state.skins.insert(user_id, "aurora@1".into());
+        let palette = Palette::new();
+        let mut renderer = Renderer::new(&state, Some(8), &palette).unwrap();
+        renderer.draw(&state);
+
+        let aurora = skin_document(Some("aurora@1"));
+        let classic = skin_document(Some("gambit@1"));
+        let pair = &aurora.palette.free_for_all[0];
+        assert_ne!(pair.fill, classic.palette.free_for_all[0].fill);
+        assert!(
+            renderer
+                .canvas
+                .pixels
+                .contains(&palette.index(hex_color(&pair.fill)))
+        );
+        assert!(
+            !renderer
+                .canvas
+                .pixels
+                .contains(&palette.index(hex_color(&classic.palette.free_for_all[0].fill)))
+        );
+    }
+
+    #[test]
+    fn quarter_turns_match_the_clients_rotation() {
+        let canvas = Canvas {
+            width: 3,
+            height: 2,
+            pixels: vec![0, 1, 2, 3, 4, 5],
+        };
+        assert_eq!(rotated(&canvas, 90), (vec![3, 0, 4, 1, 5, 2], 2, 3));
+        assert_eq!(rotated(&canvas, 180), (vec![5, 4, 3, 2, 1, 0], 3, 2));
+        assert_eq!(rotated(&canvas, 270), (vec![2, 5, 1, 4, 0, 3], 2, 3));
+    }
+
+    #[test]
+    fn formats_parse_by_name() {
+        assert_eq!(RenderFormat::parse("GIF").unwrap(), RenderFormat::Gif);
+        assert_eq!(RenderFormat::parse("apng").unwrap(), RenderFormat::Apng);
+        assert_eq!(RenderFormat::Apng.content_type(), "image/apng");
+        assert!(RenderFormat::parse("webp").is_err());
+    }
+}
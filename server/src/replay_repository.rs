@@ -5,6 +5,7 @@
 //! to the caller's durable metadata reference are ever returned.
 
 use anyhow::{Context, Result, bail};
+use common::ReplayKeyframe;
 use opentelemetry::metrics::Counter;
 use opentelemetry::{KeyValue, global};
 use sha2::{Digest, Sha256};
@@ -15,8 +16,8 @@ use tracing::warn;
 
 use crate::replay_cache::{ReplayCache, ReplayCacheLookup, ReplayCachePutOutcome};
 use crate::replay_store::{
-    REPLAY_MANIFEST_OBJECT_FORMAT_VERSION, ReplayChunkManifestV1, ReplayObjectMetadata,
-    ReplayRecording, ReplayStore,
+    REPLAY_MANIFEST_OBJECT_FORMAT_VERSION, ReplayChunkManifestV1, ReplayKeyframeSpanV1,
+    ReplayObjectMetadata, ReplayRecording, ReplayStore, recording_keyframe_spans,
 };
 
 const MAX_RECONSTRUCTED_REPLAY_BYTES: u64 = 512 * 1024 * 1024;
@@ -59,6 +60,12 @@ pub struct ReplayRangeLoad {
     pub source: ReplayLoadSource,
 }
 
+#[derive(Debug, Clone)]
+pub struct ReplayKeyframeLoad {
+    pub keyframe: ReplayKeyframe,
+    pub source: ReplayLoadSource,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct ReplayRepositoryMetricsSnapshot {
     pub cache_hits: u64,
@@ -312,16 +319,9 @@ impl ReplayRepository {
             self.store.validate_reference(expected)?;
             return Ok(Some(expected.uncompressed_bytes));
         }
-        let Some(manifest_load) = self.get_object(expected).await? else {
+        let Some((manifest, _)) = self.get_manifest(expected).await? else {
             return Ok(None);
         };
-        let manifest: ReplayChunkManifestV1 =
-            serde_json::from_slice(&manifest_load.recording.bytes)
-                .context("malformed replay chunk manifest")?;
-        manifest.validate()?;
-        if manifest.game_id != expected.game_id {
-            bail!("replay manifest targets a different game");
-        }
         Ok(Some(manifest.recording_uncompressed_bytes))
     }
 
@@ -333,21 +333,13 @@ impl ReplayRepository {
             return self.get_object(expected).await;
         }
 
-        let Some(manifest_load) = self.get_object(expected).await? else {
+        let Some((manifest, mut source)) = self.get_manifest(expected).await? else {
             return Ok(None);
         };
-        let manifest: ReplayChunkManifestV1 =
-            serde_json::from_slice(&manifest_load.recording.bytes)
-                .context("malformed replay chunk manifest")?;
-        manifest.validate()?;
-        if manifest.game_id != expected.game_id {
-            bail!("replay manifest targets a different game");
-        }
         if manifest.recording_uncompressed_bytes > MAX_RECONSTRUCTED_REPLAY_BYTES {
             bail!("replay manifest exceeds the reconstruction safety limit");
         }
 
-        let mut source = manifest_load.source;
         let mut bytes = Vec::with_capacity(
             usize::try_from(manifest.recording_uncompressed_bytes)
                 .context("replay length does not fit this platform")?,
@@ -395,6 +387,61 @@ impl ReplayRepository {
             }));
         }
 
+        let Some((manifest, source)) = self.get_manifest(expected).await? else {
+            return Ok(None);
+        };
+        self.read_manifest_range(&manifest, source, start, end)
+            .await
+    }
+
+    /// Load the latest keyframe at or before `target_tick`, range-reading
+    /// only its span of the recording. `None` means the recording is missing
+    /// or indexes no such keyframe; callers then seek through the archive.
+    pub async fn get_keyframe(
+        &self,
+        expected: &ReplayObjectMetadata,
+        target_tick: u32,
+    ) -> Result<Option<ReplayKeyframeLoad>> {
+        let (tick, bytes, source) =
+            if expected.format_version != REPLAY_MANIFEST_OBJECT_FORMAT_VERSION {
+                // Legacy one-object recordings are small enough to index on read.
+                let Some(loaded) = self.get_object(expected).await? else {
+                    return Ok(None);
+                };
+                let spans = recording_keyframe_spans(&loaded.recording.bytes)?;
+                let Some(span) = latest_keyframe_span(&spans, target_tick) else {
+                    return Ok(None);
+                };
+                let bytes = loaded.recording.bytes[span.start as usize..span.end as usize].to_vec();
+                (span.tick, bytes, loaded.source)
+            } else {
+                let Some((manifest, source)) = self.get_manifest(expected).await? else {
+                    return Ok(None);
+                };
+                let Some(span) = latest_keyframe_span(&manifest.keyframes, target_tick) else {
+                    return Ok(None);
+                };
+                let Some(range) = self
+                    .read_manifest_range(&manifest, source, span.start, span.end)
+                    .await?
+                else {
+                    return Ok(None);
+                };
+                (span.tick, range.bytes, range.source)
+            };
+        let keyframe: ReplayKeyframe =
+            serde_json::from_slice(&bytes).context("malformed replay keyframe")?;
+        if keyframe.tick != tick {
+            bail!("replay keyframe does not match its indexed tick");
+        }
+        keyframe.validate()?;
+        Ok(Some(ReplayKeyframeLoad { keyframe, source }))
+    }
+
+    async fn get_manifest(
+        &self,
+        expected: &ReplayObjectMetadata,
+    ) -> Result<Option<(ReplayChunkManifestV1, ReplayLoadSource)>> {
         let Some(manifest_load) = self.get_object(expected).await? else {
             return Ok(None);
         };
@@ -405,13 +452,22 @@ impl ReplayRepository {
         if manifest.game_id != expected.game_id {
             bail!("replay manifest targets a different game");
         }
+        Ok(Some((manifest, manifest_load.source)))
+    }
+
+    async fn read_manifest_range(
+        &self,
+        manifest: &ReplayChunkManifestV1,
+        mut source: ReplayLoadSource,
+        start: u64,
+        end: u64,
+    ) -> Result<Option<ReplayRangeLoad>> {
         let total = manifest.recording_uncompressed_bytes;
         validate_replay_range(start, end, total)?;
 
         let capacity = usize::try_from(end - start)
             .context("requested replay range does not fit this platform")?;
         let mut bytes = Vec::with_capacity(capacity);
-        let mut source = manifest_load.source;
         let mut chunk_start = 0u64;
         for chunk in &manifest.chunks {
             let chunk_end = chunk_start
@@ -548,6 +604,15 @@ impl ReplayRepository {
     }
 }
 
+fn latest_keyframe_span(
+    spans: &[ReplayKeyframeSpanV1],
+    target_tick: u32,
+) -> Option<ReplayKeyframeSpanV1> {
+    spans[..spans.partition_point(|span| span.tick <= target_tick)]
+        .last()
+        .copied()
+}
+
 fn validate_replay_range(start: u64, end: u64, total: u64) -> Result<()> {
     if start >= end {
         bail!("replay byte range must be non-empty");
@@ -661,6 +726,10 @@ mod tests {
     use crate::replay_cache::{FailingReplayCache, InMemoryReplayCache, ReplayCacheFailureMode};
     use crate::replay_store::{InMemoryReplayStore, REPLAY_CHUNK_UNCOMPRESSED_BYTES};
     use async_trait::async_trait;
+    use common::{
+        GAME_RECORDING_FORMAT_VERSION, GAMEPLAY_REPLAY_VERSION, GameRecordingV1, GameState,
+        GameType, QueueMode, ReplayAnchor, ReplayVisibility,
+    };
 
     #[derive(Clone)]
     struct CountingReplayStore {
@@ -870,6 +939,73 @@ mod tests {
         assert_eq!(cached_full.source, ReplayLoadSource::Cache);
     }
 
+    #[tokio::test]
+    async fn keyframe_seek_reads_one_indexed_span_of_a_large_manifest() {
+        let mut state = GameState::new(40, 40, GameType::Solo, QueueMode::Quickmatch, Some(3), 0);
+        state.add_player(1, Some("seeker".into())).unwrap();
+        let keyframe_at = |tick: u32| {
+            let mut state = state.clone();
+            state.tick = tick;
+            ReplayKeyframe {
+                tick,
+                sequence: 0,
+                sync_hash: state.sync_hash(),
+                state,
+            }
+        };
+        let keyframe_bytes = crate::completion::canonical_json_bytes(&keyframe_at(1))
+            .unwrap()
+            .len();
+        let count = (2 * REPLAY_CHUNK_UNCOMPRESSED_BYTES / keyframe_bytes + 1) as u32;
+        let recording = GameRecordingV1 {
+            format_version: GAME_RECORDING_FORMAT_VERSION,
+            gameplay_version: GAMEPLAY_REPLAY_VERSION,
+            game_id: 109,
+            visibility: ReplayVisibility::Public,
+            anchors: vec![ReplayAnchor {
+                tick: 0,
+                sequence: 0,
+                state: state.clone(),
+            }],
+            messages: Vec::new(),
+            end_tick: count * 10,
+            end_sync_hash: 0,
+            keyframes: (1..=count).map(|index| keyframe_at(index * 10)).collect(),
+        };
+        let bytes = crate::completion::canonical_json_bytes(&recording).unwrap();
+        let durable = InMemoryReplayStore::new();
+        let expected = durable.put_recording(109, &bytes).await.unwrap();
+        assert_eq!(
+            expected.format_version,
+            REPLAY_MANIFEST_OBJECT_FORMAT_VERSION
+        );
+        let store = Arc::new(CountingReplayStore::new(durable));
+        let repository = ReplayRepository::new(store.clone(), Arc::new(InMemoryReplayCache::new()));
+
+        assert!(
+            repository
+                .get_keyframe(&expected, 9)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let seek_tick = count / 2 * 10 + 7;
+        let calls_before = store.get_call_count();
+        let loaded = repository
+            .get_keyframe(&expected, seek_tick)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.keyframe.tick, count / 2 * 10);
+        assert_eq!(
+            loaded.keyframe.sync_hash,
+            keyframe_at(count / 2 * 10).sync_hash
+        );
+        assert_eq!(loaded.source, ReplayLoadSource::ObjectStore);
+        // The manifest is cached by now; a keyframe spans at most two chunks.
+        assert!(store.get_call_count() - calls_before <= 2);
+    }
+
     #[tokio::test]
     async fn stale_same_game_manifest_cannot_replace_committed_content() {
         let durable = InMemoryReplayStore::new();
@@ -17,6 +17,7 @@ use flate2::Compression;
 use flate2::GzBuilder;
 use flate2::read::GzDecoder;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::io::{Read, Write};
@@ -103,6 +104,18 @@ pub struct ReplayChunkManifestV1 {
     pub recording_uncompressed_sha256: String,
     pub recording_uncompressed_bytes: u64,
     pub chunks: Vec<ReplayObjectMetadata>,
+    /// Where each recording keyframe sits in the reconstructed bytes, so a
+    /// seek range-reads one checkpoint instead of the whole archive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keyframes: Vec<ReplayKeyframeSpanV1>,
+}
+
+/// Half-open byte span of one `ReplayKeyframe` in canonical recording JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayKeyframeSpanV1 {
+    pub tick: u32,
+    pub start: u64,
+    pub end: u64,
 }
 
 impl ReplayChunkManifestV1 {
@@ -133,8 +146,55 @@ impl ReplayChunkManifestV1 {
         if total != self.recording_uncompressed_bytes {
             bail!("replay manifest chunk lengths do not match the recording length");
         }
-        Ok(())
+        validate_keyframe_spans(&self.keyframes, total)
+    }
+}
+
+/// Index the keyframes of canonical recording JSON by byte span. Bytes that
+/// are not a recording, or a recording without keyframes, index nothing.
+pub fn recording_keyframe_spans(recording_bytes: &[u8]) -> Result<Vec<ReplayKeyframeSpanV1>> {
+    #[derive(Deserialize)]
+    struct RecordingKeyframes<'a> {
+        #[serde(default, borrow)]
+        keyframes: Vec<&'a RawValue>,
+    }
+    #[derive(Deserialize)]
+    struct KeyframeTick {
+        tick: u32,
+    }
+
+    let Ok(recording) = serde_json::from_slice::<RecordingKeyframes>(recording_bytes) else {
+        return Ok(Vec::new());
+    };
+    let base = recording_bytes.as_ptr() as usize;
+    let mut spans = Vec::with_capacity(recording.keyframes.len());
+    for keyframe in recording.keyframes {
+        let KeyframeTick { tick } =
+            serde_json::from_str(keyframe.get()).context("recording keyframe has no tick")?;
+        let start = (keyframe.get().as_ptr() as usize - base) as u64;
+        spans.push(ReplayKeyframeSpanV1 {
+            tick,
+            start,
+            end: start + keyframe.get().len() as u64,
+        });
     }
+    validate_keyframe_spans(&spans, recording_bytes.len() as u64)?;
+    Ok(spans)
+}
+
+fn validate_keyframe_spans(spans: &[ReplayKeyframeSpanV1], total: u64) -> Result<()> {
+    for span in spans {
+        if span.start >= span.end || span.end > total {
+            bail!("replay keyframe span lies outside the recording");
+        }
+    }
+    if !spans
+        .windows(2)
+        .all(|pair| pair[0].tick < pair[1].tick && pair[0].end <= pair[1].start)
+    {
+        bail!("replay keyframe spans must be strictly ordered");
+    }
+    Ok(())
 }
 
 /// A verified recording returned in its original, uncompressed form.
@@ -556,6 +616,7 @@ fn encode_recording_objects(
         recording_uncompressed_sha256: sha256_hex(recording_bytes),
         recording_uncompressed_bytes: recording_bytes.len() as u64,
         chunks,
+        keyframes: recording_keyframe_spans(recording_bytes)?,
     };
     manifest.validate()?;
     let manifest_bytes =
@@ -8,25 +8,106 @@
 //! bridge") and re-anchor on the next authoritative `Snapshot`; everything in
 //! between is governed by the per-subscription continuity rules in
 //! [`GameEventSubscription`].
+//!
+//! The one thing kept per game is [`SnapshotHistory`]: the last few snapshots
+//! this gateway sent, so a subscriber that already holds one can be sent the
+//! next as a `SnapshotDelta` against it. Each delta is computed once and
+//! shared by every subscriber holding the same base.
+//!
+//! Events reach subscribers as shared [`RoutedEvent`]s, which encode their
+//! wire frame once per encoding however many local sockets forward them.
 
 use crate::game_bus::{GameBus, PartitionEvent, PartitionEventSubscription};
 use anyhow::Result;
-use common::{GameEvent, GameEventMessage, GameState, GameStatus};
-use std::collections::HashMap;
-use std::sync::Arc;
+use common::{
+    GameEvent, GameEventMessage, GameState, GameStatus, WireEncoding, encode_game_event_frame,
+    encode_game_event_text, snapshot_delta,
+};
+use std::collections::{HashMap, VecDeque};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
 use tokio::sync::{Mutex, RwLock, broadcast};
+use tokio_tungstenite::tungstenite::{Bytes, Message, Utf8Bytes};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
 /// Per-game broadcast channels, created only when a local socket subscribes.
 /// A game with no local subscriber has no entry and costs nothing.
-pub type GameEventBroadcasters = Arc<RwLock<HashMap<u32, broadcast::Sender<GameEventMessage>>>>;
+pub type GameEventBroadcasters = Arc<RwLock<HashMap<u32, broadcast::Sender<Arc<RoutedEvent>>>>>;
+
+/// A game event on its way to local sockets, with each wire frame encoded the
+/// first time a socket needs it and shared by every socket after that.
+#[derive(Debug)]
+pub struct RoutedEvent {
+    message: GameEventMessage,
+    text: OnceLock<Utf8Bytes>,
+    binary: OnceLock<Bytes>,
+}
+
+impl RoutedEvent {
+    pub fn new(message: GameEventMessage) -> Arc<Self> {
+        Arc::new(Self {
+            message,
+            text: OnceLock::new(),
+            binary: OnceLock::new(),
+        })
+    }
+
+    /// The message itself, cloned only if another socket still shares it.
+    pub fn into_message(self: Arc<Self>) -> GameEventMessage {
+        Arc::try_unwrap(self)
+            .map(|event| event.message)
+            .unwrap_or_else(|shared| shared.message.clone())
+    }
+
+    /// The event as a frame in `encoding`. A MessagePack frame that cannot be
+    /// encoded goes out as JSON, which every client also reads.
+    pub fn frame(&self, encoding: WireEncoding) -> Result<Message> {
+        if encoding == WireEncoding::MessagePack {
+            match self.binary_frame() {
+                Ok(frame) => return Ok(Message::Binary(frame)),
+                Err(error) => {
+                    warn!(%error, "Sending a game event as JSON after MessagePack encoding failed");
+                }
+            }
+        }
+        Ok(Message::Text(self.text_frame()?))
+    }
+
+    fn text_frame(&self) -> Result<Utf8Bytes> {
+        if let Some(text) = self.text.get() {
+            return Ok(text.clone());
+        }
+        let text = Utf8Bytes::from(encode_game_event_text(&self.message)?);
+        Ok(self.text.get_or_init(|| text).clone())
+    }
+
+    fn binary_frame(&self) -> Result<Bytes> {
+        if let Some(binary) = self.binary.get() {
+            return Ok(binary.clone());
+        }
+        let binary = Bytes::from(encode_game_event_frame(&self.message)?);
+        Ok(self.binary.get_or_init(|| binary).clone())
+    }
+}
+
+impl Deref for RoutedEvent {
+    type Target = GameEventMessage;
+
+    fn deref(&self) -> &GameEventMessage {
+        &self.message
+    }
+}
 
 /// How often a cold subscription (or a fresh join) may publish a targeted
 /// snapshot request, per game, per gateway — coalesced across every local
 /// socket waiting on that game.
 const TARGETED_REQUEST_INTERVAL_MS: i64 = 500;
 
+/// Snapshots kept per game as delta bases. A resync normally acknowledges the
+/// last snapshot or the one before, so a short history covers it.
+const SNAPSHOT_HISTORY_DEPTH: usize = 4;
+
 /// Tracks one partition reader's readiness.
 #[derive(Debug, Clone)]
 pub struct ReplicationStatus {
@@ -104,6 +185,142 @@ impl SnapshotRequester {
     }
 }
 
+/// The last few snapshots this gateway sent for each game with a local
+/// subscriber. These are frames already on the wire, never a state the
+/// gateway derived, so a delta is only ever computed against a snapshot some
+/// subscriber was actually sent.
+#[derive(Default)]
+pub struct SnapshotHistory {
+    games: std::sync::Mutex<HashMap<u32, GameSnapshots>>,
+}
+
+/// A delta computed at most once; `None` records that the full snapshot is
+/// the smaller frame.
+type SharedDelta = Arc<OnceLock<Option<Arc<RoutedEvent>>>>;
+
+/// One game's delta bases, and the deltas computed between them.
+#[derive(Default)]
+struct GameSnapshots {
+    bases: VecDeque<Arc<RoutedEvent>>,
+    /// Keyed by `(base_tick, tick)`. The first subscriber to need a pair
+    /// computes it; the rest wait on the cell and share its frames.
+    deltas: HashMap<(u32, u32), SharedDelta>,
+}
+
+fn snapshot_tick(event: &RoutedEvent) -> Option<u32> {
+    match &event.event {
+        GameEvent::Snapshot { game_state } => Some(game_state.tick),
+        _ => None,
+    }
+}
+
+impl SnapshotHistory {
+    /// Keep `snapshot` as a base. Every subscriber of the game remembers the
+    /// same shared event, so only the first does any work. A snapshot for a
+    /// tick already held replaces the older one: after a failover the new
+    /// owner's state is the one later snapshots descend from, and deltas
+    /// touching the old one are dropped with it.
+    fn remember(&self, game_id: u32, snapshot: &Arc<RoutedEvent>) {
+        let Some(tick) = snapshot_tick(snapshot) else {
+            return;
+        };
+        let mut games = self.games.lock().expect("snapshot history lock poisoned");
+        let history = games.entry(game_id).or_default();
+        if history.bases.iter().any(|base| Arc::ptr_eq(base, snapshot)) {
+            return;
+        }
+        history
+            .bases
+            .retain(|base| snapshot_tick(base) != Some(tick));
+        history.bases.push_back(snapshot.clone());
+        while history.bases.len() > SNAPSHOT_HISTORY_DEPTH {
+            history.bases.pop_front();
+        }
+        let held: Vec<u32> = history
+            .bases
+            .iter()
+            .filter_map(|base| snapshot_tick(base))
+            .collect();
+        history.deltas.retain(|&(base_tick, target_tick), _| {
+            base_tick != tick
+                && target_tick != tick
+                && held.contains(&base_tick)
+                && held.contains(&target_tick)
+        });
+    }
+
+    /// `snapshot` as a delta against the held base at `base_tick`, when there
+    /// is one and the delta is the smaller frame. Computed once per pair of
+    /// held snapshots, however many subscribers ask.
+    fn delta(
+        &self,
+        game_id: u32,
+        base_tick: u32,
+        snapshot: &Arc<RoutedEvent>,
+    ) -> Option<Arc<RoutedEvent>> {
+        let tick = snapshot_tick(snapshot)?;
+        let (base, cell) = {
+            let mut games = self.games.lock().expect("snapshot history lock poisoned");
+            let history = games.get_mut(&game_id)?;
+            let base = history
+                .bases
+                .iter()
+                .find(|base| snapshot_tick(base) == Some(base_tick))?
+                .clone();
+            // A snapshot that is not the one held for its tick (a failover
+            // replaced it meanwhile) is still diffed, just not cached.
+            let cell = if history.bases.iter().any(|held| Arc::ptr_eq(held, snapshot)) {
+                history.deltas.entry((base_tick, tick)).or_default().clone()
+            } else {
+                Arc::new(OnceLock::new())
+            };
+            (base, cell)
+        };
+        cell.get_or_init(|| delta_if_smaller(&base, snapshot))
+            .clone()
+    }
+
+    fn forget(&self, game_id: u32) {
+        self.games
+            .lock()
+            .expect("snapshot history lock poisoned")
+            .remove(&game_id);
+    }
+
+    fn retain_games(&self, mut keep: impl FnMut(u32) -> bool) {
+        self.games
+            .lock()
+            .expect("snapshot history lock poisoned")
+            .retain(|game_id, _| keep(*game_id));
+    }
+}
+
+/// The snapshot as a delta against `base`, when that is the smaller frame.
+/// Sizes are compared on the JSON frames both events would go out as, which
+/// are kept for whichever subscribers send them. Any failure just means the
+/// full snapshot goes out.
+fn delta_if_smaller(base: &RoutedEvent, snapshot: &RoutedEvent) -> Option<Arc<RoutedEvent>> {
+    let (GameEvent::Snapshot { game_state: base }, GameEvent::Snapshot { game_state: target }) =
+        (&base.event, &snapshot.event)
+    else {
+        return None;
+    };
+    if base.tick >= target.tick {
+        return None;
+    }
+    let delta = RoutedEvent::new(GameEventMessage {
+        game_id: snapshot.game_id,
+        tick: snapshot.tick,
+        sequence: snapshot.sequence,
+        stream_seq: snapshot.stream_seq,
+        user_id: snapshot.user_id,
+        event: snapshot_delta(base, target).ok()?,
+    });
+    let delta_len = delta.text_frame().ok()?.len();
+    let full_len = snapshot.text_frame().ok()?.len();
+    (delta_len < full_len).then_some(delta)
+}
+
 /// Continuity state of one socket's subscription to one game.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Continuity {
@@ -116,15 +333,10 @@ enum Continuity {
 }
 
 /// What [`GameEventSubscription::next`] hands the forwarding loop.
-///
-/// This value is consumed immediately by the caller's `match` and never
-/// stored. Boxing the event would add a heap allocation to every live game
-/// event only to reduce stack padding on the cold `WentCold` arm.
-#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum SubscriptionUpdate {
     /// Forward this event to the socket.
-    Event(GameEventMessage),
+    Event(Arc<RoutedEvent>),
     /// The subscription lost continuity (gap or broadcast lag) and suppressed
     /// delivery until the next snapshot; a targeted request is already paced.
     /// Any in-flight work tied to the previous snapshot is now stale.
@@ -145,23 +357,34 @@ pub enum SubscriptionUpdate {
 ///   messages, forwarded by stable command identity even while cold;
 /// - a sequence gap or local broadcast lag returns the subscription to cold
 ///   rather than forwarding unverified continuations.
+///
+/// Whether a snapshot goes out whole or as a delta is also decided here, per
+/// subscriber; see [`GameEventSubscription::encode_snapshot`].
 pub struct GameEventSubscription {
-    inner: broadcast::Receiver<GameEventMessage>,
+    inner: broadcast::Receiver<Arc<RoutedEvent>>,
     game_id: u32,
     requester: Arc<SnapshotRequester>,
     continuity: Continuity,
+    history: Arc<SnapshotHistory>,
+    /// Tick of the snapshot the client holds: the one its resync request
+    /// acknowledged, then each one this subscription sends. The socket is
+    /// ordered, and a client that cannot apply a snapshot asks to resync,
+    /// which replaces this subscription.
+    acknowledged_snapshot: Option<u32>,
 }
 
 impl GameEventSubscription {
     /// Build a subscription over a bare channel with a detached requester,
     /// for unit tests of continuity behavior.
     #[cfg(test)]
-    pub(crate) fn for_test(inner: broadcast::Receiver<GameEventMessage>, game_id: u32) -> Self {
+    pub(crate) fn for_test(inner: broadcast::Receiver<Arc<RoutedEvent>>, game_id: u32) -> Self {
         Self {
             inner,
             game_id,
             requester: Arc::new(SnapshotRequester::detached()),
             continuity: Continuity::Cold,
+            history: Arc::new(SnapshotHistory::default()),
+            acknowledged_snapshot: None,
         }
     }
 
@@ -175,6 +398,34 @@ impl GameEventSubscription {
         };
     }
 
+    /// Record that the client holds the snapshot at `tick`, so the next one
+    /// may be sent as a delta against it.
+    pub fn acknowledge_snapshot(&mut self, tick: u32) {
+        self.acknowledged_snapshot = Some(tick);
+    }
+
+    /// Prepare a snapshot for this subscriber. It is kept as a base for later
+    /// deltas, and goes out as a delta itself when this gateway still holds
+    /// the snapshot the client acknowledged and the delta is the smaller
+    /// frame. Terminal snapshots always go out whole: nothing follows them,
+    /// and every completion path reads the full state.
+    pub fn encode_snapshot(&mut self, event_msg: Arc<RoutedEvent>) -> Arc<RoutedEvent> {
+        let GameEvent::Snapshot { game_state } = &event_msg.event else {
+            return event_msg;
+        };
+        if matches!(game_state.status, GameStatus::Complete { .. }) {
+            self.history.forget(self.game_id);
+            self.acknowledged_snapshot = None;
+            return event_msg;
+        }
+
+        self.history.remember(self.game_id, &event_msg);
+        self.acknowledged_snapshot
+            .replace(game_state.tick)
+            .and_then(|base_tick| self.history.delta(self.game_id, base_tick, &event_msg))
+            .unwrap_or(event_msg)
+    }
+
     /// Receive the next forwardable event, or `None` when the game's channel
     /// is gone (terminal teardown, or the reader worker failed — which is
     /// task-fatal anyway).
@@ -219,7 +470,7 @@ impl GameEventSubscription {
                 self.continuity = Continuity::Live {
                     last_stream_seq: event_msg.stream_seq,
                 };
-                return Some(SubscriptionUpdate::Event(event_msg));
+                return Some(SubscriptionUpdate::Event(self.encode_snapshot(event_msg)));
             }
 
             if event_msg.stream_seq == 0 {
@@ -304,7 +555,7 @@ async fn forward_event(channels: &GameEventBroadcasters, event_msg: GameEventMes
         match channels.get(&game_id) {
             // SendError means the last receiver is gone — normal as
             // clients finish or switch generations.
-            Some(sender) => sender.send(event_msg).is_err(),
+            Some(sender) => sender.send(RoutedEvent::new(event_msg)).is_err(),
             None => false,
         }
     };
@@ -532,6 +783,7 @@ pub struct GameEventRouter {
     channels: GameEventBroadcasters,
     statuses: Arc<RwLock<HashMap<u32, Arc<RwLock<ReplicationStatus>>>>>,
     requester: Arc<SnapshotRequester>,
+    snapshots: Arc<SnapshotHistory>,
 }
 
 impl GameEventRouter {
@@ -568,6 +820,7 @@ impl GameEventRouter {
             channels,
             statuses,
             requester,
+            snapshots: Arc::new(SnapshotHistory::default()),
         })
     }
 
@@ -585,6 +838,8 @@ impl GameEventRouter {
             // Opportunistically drop channels whose last subscriber left, so
             // abandoned games do not accumulate entries.
             channels.retain(|id, sender| *id == game_id || sender.receiver_count() > 0);
+            self.snapshots
+                .retain_games(|id| id == game_id || channels.contains_key(&id));
             channels
                 .entry(game_id)
                 .or_insert_with(|| broadcast::channel(1028).0)
@@ -596,6 +851,8 @@ impl GameEventRouter {
             game_id,
             requester: self.requester.clone(),
             continuity: Continuity::Cold,
+            history: self.snapshots.clone(),
+            acknowledged_snapshot: None,
         }
     }
 
@@ -667,7 +924,7 @@ impl GameEventRouter {
 #[cfg(test)]
 mod tests {
     use super::{
-        GameEventBroadcasters, GameEventRouter, PartitionEventRouter, RequestPacer,
+        GameEventBroadcasters, GameEventRouter, PartitionEventRouter, RequestPacer, RoutedEvent,
         SnapshotRequester, SubscriptionUpdate, TARGETED_REQUEST_INTERVAL_MS,
     };
     use crate::game_bus::{GameBus, SnapshotRequest};
@@ -756,7 +1013,7 @@ mod tests {
     }
 
     fn test_subscription() -> (
-        broadcast::Sender<GameEventMessage>,
+        broadcast::Sender<Arc<RoutedEvent>>,
         super::GameEventSubscription,
     ) {
         let (tx, rx) = broadcast::channel(16);
@@ -765,7 +1022,7 @@ mod tests {
 
     fn expect_event(update: Option<SubscriptionUpdate>) -> GameEventMessage {
         match update {
-            Some(SubscriptionUpdate::Event(event)) => event,
+            Some(SubscriptionUpdate::Event(event)) => event.into_message(),
             other => panic!("expected a forwarded event, got {other:?}"),
         }
     }
@@ -797,9 +1054,9 @@ mod tests {
     async fn cold_subscription_suppresses_deltas_until_snapshot_reanchors() {
         let (tx, mut subscription) = test_subscription();
 
-        tx.send(event(1, 41)).unwrap(); // suppressed: no anchor yet
-        tx.send(snapshot(2, 42)).unwrap();
-        tx.send(event(3, 43)).unwrap();
+        tx.send(RoutedEvent::new(event(1, 41))).unwrap(); // suppressed: no anchor yet
+        tx.send(RoutedEvent::new(snapshot(2, 42))).unwrap();
+        tx.send(RoutedEvent::new(event(3, 43))).unwrap();
 
         let got = expect_event(subscription.next().await);
         assert!(matches!(got.event, GameEvent::Snapshot { .. }));
@@ -817,10 +1074,10 @@ mod tests {
         // A restarted executor begins a new stream: snapshot at stream_seq 2,
         // then ordinary events 3, 4. Without re-anchoring, ALL of these would
         // be filtered as stale (< 500) and the client would be wedged.
-        tx.send(snapshot(7, 2)).unwrap();
-        tx.send(event(8, 3)).unwrap();
-        tx.send(event(8, 2)).unwrap(); // duplicate/stale vs the new anchor
-        tx.send(event(9, 4)).unwrap();
+        tx.send(RoutedEvent::new(snapshot(7, 2))).unwrap();
+        tx.send(RoutedEvent::new(event(8, 3))).unwrap();
+        tx.send(RoutedEvent::new(event(8, 2))).unwrap(); // duplicate/stale vs the new anchor
+        tx.send(RoutedEvent::new(event(9, 4))).unwrap();
 
         let got = expect_event(subscription.next().await);
         assert!(matches!(got.event, GameEvent::Snapshot { .. }));
@@ -829,14 +1086,153 @@ mod tests {
         assert_eq!(expect_event(subscription.next().await).stream_seq, 4);
     }
 
+    fn running_state(ticks: u32) -> GameState {
+        let mut state = GameState::new(
+            60,
+            40,
+            GameType::TeamMatch { per_team: 1 },
+            QueueMode::Quickmatch,
+            Some(3),
+            0,
+        );
+        state.add_player(1, None).unwrap();
+        state.add_player(2, None).unwrap();
+        state.spawn_initial_food();
+        state.status = GameStatus::Started { server_id: 1 };
+        for _ in 0..ticks {
+            state.tick_forward(false).unwrap();
+        }
+        state
+    }
+
+    fn snapshot_of(state: &GameState, stream_seq: u64) -> GameEventMessage {
+        GameEventMessage {
+            game_id: 1,
+            tick: state.tick,
+            sequence: state.event_sequence,
+            stream_seq,
+            user_id: None,
+            event: GameEvent::Snapshot {
+                game_state: state.clone(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn later_snapshots_go_out_as_deltas_against_the_previous_one() {
+        let (tx, mut subscription) = test_subscription();
+        let first = running_state(5);
+        let second = running_state(25);
+
+        tx.send(RoutedEvent::new(snapshot_of(&first, 10))).unwrap();
+        tx.send(RoutedEvent::new(snapshot_of(&second, 30))).unwrap();
+
+        let got = expect_event(subscription.next().await);
+        assert!(matches!(got.event, GameEvent::Snapshot { .. }));
+        let got = expect_event(subscription.next().await);
+        assert_eq!(got.stream_seq, 30);
+        let GameEvent::SnapshotDelta {
+            base_tick,
+            changes,
+            hash,
+        } = got.event
+        else {
+            panic!("expected a delta, got {:?}", got.event);
+        };
+        assert_eq!(base_tick, first.tick);
+        let rebuilt = common::apply_snapshot_delta(&first, base_tick, &changes, hash).unwrap();
+        assert_eq!(rebuilt.sync_hash(), second.sync_hash());
+    }
+
+    #[tokio::test]
+    async fn a_resync_gets_a_delta_only_against_a_snapshot_this_gateway_sent() {
+        let history = Arc::new(super::SnapshotHistory::default());
+        let subscribe = |acknowledged: Option<u32>| {
+            let (tx, rx) = broadcast::channel(16);
+            let mut subscription = super::GameEventSubscription::for_test(rx, 1);
+            subscription.history = history.clone();
+            if let Some(tick) = acknowledged {
+                subscription.acknowledge_snapshot(tick);
+            }
+            (tx, subscription)
+        };
+        let held = running_state(5);
+        let (tx, mut earlier) = subscribe(None);
+        tx.send(RoutedEvent::new(snapshot_of(&held, 10))).unwrap();
+        expect_event(earlier.next().await);
+
+        let current = running_state(40);
+        let (tx, mut resynced) = subscribe(Some(held.tick));
+        tx.send(RoutedEvent::new(snapshot_of(&current, 50)))
+            .unwrap();
+        assert!(matches!(
+            expect_event(resynced.next().await).event,
+            GameEvent::SnapshotDelta { base_tick, .. } if base_tick == held.tick
+        ));
+
+        let (tx, mut unknown_base) = subscribe(Some(held.tick + 1));
+        tx.send(RoutedEvent::new(snapshot_of(&current, 50)))
+            .unwrap();
+        assert!(matches!(
+            expect_event(unknown_base.next().await).event,
+            GameEvent::Snapshot { .. }
+        ));
+
+        // The terminal snapshot is always whole and retires the game's bases.
+        let mut finished = running_state(45);
+        finished.status = GameStatus::Complete {
+            winning_snake_id: None,
+        };
+        let (tx, mut terminal) = subscribe(Some(current.tick));
+        tx.send(RoutedEvent::new(snapshot_of(&finished, 60)))
+            .unwrap();
+        assert!(matches!(
+            expect_event(terminal.next().await).event,
+            GameEvent::Snapshot { .. }
+        ));
+        assert!(!history.games.lock().unwrap().contains_key(&1));
+    }
+
+    #[tokio::test]
+    async fn subscribers_holding_the_same_base_share_one_delta() {
+        let history = Arc::new(super::SnapshotHistory::default());
+        let (tx, _) = broadcast::channel(16);
+        let mut subscriptions: Vec<_> = (0..2)
+            .map(|_| {
+                let mut subscription = super::GameEventSubscription::for_test(tx.subscribe(), 1);
+                subscription.history = history.clone();
+                subscription
+            })
+            .collect();
+        let first = running_state(5);
+        tx.send(RoutedEvent::new(snapshot_of(&first, 10))).unwrap();
+        tx.send(RoutedEvent::new(snapshot_of(&running_state(25), 30)))
+            .unwrap();
+
+        let mut deltas = Vec::new();
+        for subscription in &mut subscriptions {
+            expect_event(subscription.next().await);
+            let Some(SubscriptionUpdate::Event(delta)) = subscription.next().await else {
+                panic!("expected a forwarded event");
+            };
+            assert!(matches!(
+                delta.event,
+                GameEvent::SnapshotDelta { base_tick, .. } if base_tick == first.tick
+            ));
+            deltas.push(delta);
+        }
+        assert!(Arc::ptr_eq(&deltas[0], &deltas[1]));
+        assert_eq!(history.games.lock().unwrap()[&1].deltas.len(), 1);
+    }
+
     #[tokio::test]
     async fn bridge_anchor_forwards_contiguous_deltas_without_a_live_snapshot() {
         let (tx, mut subscription) = test_subscription();
         subscription.anchor(100);
 
-        tx.send(event(1, 100)).unwrap(); // already folded into the bridge
-        tx.send(event(2, 101)).unwrap();
-        tx.send(event(3, 102)).unwrap();
+        tx.send(RoutedEvent::new(event(1, 100))).unwrap(); // already folded into the bridge
+        tx.send(RoutedEvent::new(event(2, 101))).unwrap();
+        tx.send(RoutedEvent::new(event(3, 102))).unwrap();
 
         assert_eq!(expect_event(subscription.next().await).stream_seq, 101);
         assert_eq!(expect_event(subscription.next().await).stream_seq, 102);
@@ -847,10 +1243,10 @@ mod tests {
         let (tx, mut subscription) = test_subscription();
         subscription.anchor(100);
 
-        tx.send(event(1, 105)).unwrap(); // gap: 101..=104 lost
-        tx.send(event(2, 106)).unwrap(); // suppressed while cold
-        tx.send(snapshot(3, 107)).unwrap();
-        tx.send(event(4, 108)).unwrap();
+        tx.send(RoutedEvent::new(event(1, 105))).unwrap(); // gap: 101..=104 lost
+        tx.send(RoutedEvent::new(event(2, 106))).unwrap(); // suppressed while cold
+        tx.send(RoutedEvent::new(snapshot(3, 107))).unwrap();
+        tx.send(RoutedEvent::new(event(4, 108))).unwrap();
 
         assert!(matches!(
             subscription.next().await,
@@ -881,8 +1277,8 @@ mod tests {
             command_id_client: None,
             session_rejected_from: None,
         };
-        tx.send(event(1, 0)).unwrap(); // unsequenced state event: dropped
-        tx.send(rejection).unwrap();
+        tx.send(RoutedEvent::new(event(1, 0))).unwrap(); // unsequenced state event: dropped
+        tx.send(RoutedEvent::new(rejection)).unwrap();
 
         let got = expect_event(subscription.next().await);
         assert!(matches!(got.event, GameEvent::CommandRejected { .. }));
@@ -895,14 +1291,15 @@ mod tests {
 
         // Overflow the 16-slot channel so the receiver observes Lagged.
         for stream_seq in 1..=40u64 {
-            tx.send(event(stream_seq, stream_seq)).unwrap();
+            tx.send(RoutedEvent::new(event(stream_seq, stream_seq)))
+                .unwrap();
         }
         assert!(matches!(
             subscription.next().await,
             Some(SubscriptionUpdate::WentCold)
         ));
 
-        tx.send(snapshot(41, 41)).unwrap();
+        tx.send(RoutedEvent::new(snapshot(41, 41))).unwrap();
         let got = expect_event(subscription.next().await);
         assert!(matches!(got.event, GameEvent::Snapshot { .. }));
         assert_eq!(got.stream_seq, 41);
@@ -912,7 +1309,7 @@ mod tests {
     async fn subscription_ends_when_the_channel_closes() {
         let (tx, mut subscription) = test_subscription();
         subscription.anchor(0);
-        tx.send(event(1, 1)).unwrap();
+        tx.send(RoutedEvent::new(event(1, 1))).unwrap();
         drop(tx);
 
         assert_eq!(expect_event(subscription.next().await).stream_seq, 1);
@@ -927,6 +1324,7 @@ mod tests {
             channels: channels.clone(),
             statuses: Default::default(),
             requester: Arc::new(SnapshotRequester::detached()),
+            snapshots: Default::default(),
         };
         let mut subscription = manager.subscribe_to_game(1).await;
 
@@ -950,6 +1348,7 @@ mod tests {
             channels: Default::default(),
             statuses: Default::default(),
             requester: Arc::new(SnapshotRequester::detached()),
+            snapshots: Default::default(),
         };
 
         let abandoned = manager.subscribe_to_game(7).await;
@@ -980,6 +1379,7 @@ mod tests {
                 channels: channels.clone(),
                 statuses: Default::default(),
                 requester: Arc::new(SnapshotRequester::new(bus.clone())),
+                snapshots: Default::default(),
             };
             let mut subscription = manager.subscribe_to_game(1).await;
 
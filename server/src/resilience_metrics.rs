@@ -175,6 +175,12 @@ fn combo_metric_dimensions(state: &GameState, snake_id: u32) -> ComboMetricDimen
         GameType::TeamMatch { per_team: 1 } => "duel",
         GameType::TeamMatch { per_team: 2 } => "2v2",
         GameType::TeamMatch { .. } => "other-team",
+        GameType::KingOfTheHill { per_team: 1 } => "hill",
+        GameType::KingOfTheHill { per_team: 2 } => "hill-2v2",
+        GameType::KingOfTheHill { .. } => "other-hill",
+        GameType::CaptureTheFlag { per_team: 1 } => "ctf",
+        GameType::CaptureTheFlag { per_team: 2 } => "ctf-2v2",
+        GameType::CaptureTheFlag { .. } => "other-ctf",
         GameType::Solo => "solo",
         GameType::FreeForAll { .. } => "free-for-all",
         GameType::Custom { .. } => "custom",
@@ -207,6 +213,12 @@ fn boost_metric_dimensions(state: &GameState, snake_id: u32) -> BoostMetricDimen
         GameType::TeamMatch { per_team: 1 } => "duel",
         GameType::TeamMatch { per_team: 2 } => "2v2",
         GameType::TeamMatch { .. } => "other-team",
+        GameType::KingOfTheHill { per_team: 1 } => "hill",
+        GameType::KingOfTheHill { per_team: 2 } => "hill-2v2",
+        GameType::KingOfTheHill { .. } => "other-hill",
+        GameType::CaptureTheFlag { per_team: 1 } => "ctf",
+        GameType::CaptureTheFlag { per_team: 2 } => "ctf-2v2",
+        GameType::CaptureTheFlag { .. } => "other-ctf",
         GameType::Solo => "solo",
         GameType::FreeForAll { .. } => "free-for-all",
         GameType::Custom { .. } => "custom",
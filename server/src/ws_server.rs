@@ -33,11 +33,13 @@ use crate::recovery::{
 use crate::redis_keys::RedisKeys;
 use crate::redis_utils::RedisConnection;
 use crate::rematch::{RematchState, RematchStore};
+use crate::replication::RoutedEvent;
 use crate::user_cache::UserCache;
 use anyhow::{Context, Result, anyhow};
 use chrono::Utc;
 use common::{
-    ClientCommandIdentityV2, GameCommandMessage, GameEvent, GameEventMessage, GameState, GameStatus,
+    ClientCommandIdentityV2, GameCommandMessage, GameEvent, GameEventMessage, GameState,
+    GameStatus, SpectateGame, WireEncoding,
 };
 use futures_util::SinkExt;
 use redis::AsyncCommands;
@@ -45,6 +47,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::future::{Future, pending};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 use tokio::sync::{Mutex, broadcast, mpsc};
@@ -135,8 +138,15 @@ pub enum WSMessage {
         /// policy because the client's available SDK is unknown.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         distribution: Option<ClientDistribution>,
+        /// How the client wants `GameEvent` frames encoded. Absent means JSON.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "ts-gen", ts(optional))]
+        encoding: Option<WireEncoding>,
     },
     JoinGame(u32),
+    /// Client -> server request to watch a live game without a seat. Lives in
+    /// common so a spectating client need not link the server.
+    SpectateGame(SpectateGame),
     LeaveGame,
     /// At-least-once client command. The gateway canonicalizes `game_id` and
     /// `user_id` from the authenticated connection before publishing it.
@@ -204,6 +214,11 @@ pub enum WSMessage {
         capabilities: Vec<String>,
         #[cfg_attr(feature = "ts-gen", ts(type = "number"))]
         socket_generation: u64,
+        /// The encoding `GameEvent` frames will use from here on, echoed only
+        /// to a client that asked for one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "ts-gen", ts(optional))]
+        encoding: Option<WireEncoding>,
     },
     /// Server -> client distribution capability. Live pre-match authorization
     /// is carried by each lobby break's targeted user IDs.
@@ -220,6 +235,12 @@ pub enum WSMessage {
     /// needs its event subscription restarted with a fresh snapshot.
     RequestResync {
         game_id: u32,
+        /// Tick of the last snapshot the client applied. When the gateway
+        /// still holds that snapshot, the fresh one may arrive as a
+        /// `SnapshotDelta` against it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "ts-gen", ts(optional))]
+        base_tick: Option<u32>,
     },
     Ping {
         #[cfg_attr(feature = "ts-gen", ts(type = "number"))]
@@ -316,6 +337,11 @@ pub enum WSMessage {
     UpdateLobbyPreferences {
         selected_modes: Vec<String>,
         competitive: bool,
+        /// AI opponents for solo practice or a custom game. Absent leaves the
+        /// lobby without.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[cfg_attr(feature = "ts-gen", ts(optional))]
+        ai_opponents: Option<common::AiOpponents>,
     },
     LobbyRegionMismatch {
         target_region: String,
@@ -415,6 +441,7 @@ ws_message_variant_names![
     Token,
     Authenticate,
     JoinGame,
+    SpectateGame,
     LeaveGame,
     GameCommandV2,
     GameEvent,
@@ -1189,6 +1216,36 @@ pub async fn handle_websocket(
     crate::analytics::sink::record_connection_closed(&ws_analytics);
 }
 
+/// The encoding this connection's `GameEvent` frames go out in, shared between
+/// the message loop, which sets it once `Authenticate` asks for one, and the
+/// game event tasks, which encode each frame with it.
+///
+/// Read per frame, so a frame queued just before the switch still goes out in
+/// the old encoding; the client reads both.
+#[derive(Clone, Default)]
+struct GameEventEncoding(Arc<AtomicBool>);
+
+impl GameEventEncoding {
+    fn set(&self, encoding: WireEncoding) {
+        self.0
+            .store(encoding == WireEncoding::MessagePack, Ordering::Release);
+    }
+
+    fn get(&self) -> WireEncoding {
+        if self.0.load(Ordering::Acquire) {
+            WireEncoding::MessagePack
+        } else {
+            WireEncoding::Json
+        }
+    }
+
+    /// `event` as this connection's frame. A broadcast event is encoded once
+    /// per encoding and the frame shared by every socket forwarding it.
+    fn frame(&self, event: &RoutedEvent) -> Result<Message> {
+        event.frame(self.get())
+    }
+}
+
 fn tungstenite_message_bytes(message: &Message) -> usize {
     match message {
         Message::Text(text) => text.len(),
@@ -1241,7 +1298,8 @@ fn outbound_message_type(message: &Message) -> &'static str {
         Message::Text(text) => wire_tag(text)
             .and_then(interned_message_type)
             .unwrap_or(UNNAMED_MESSAGE_TYPE),
-        Message::Binary(_) => "binary_frame",
+        // Binary frames are only ever MessagePack game events.
+        Message::Binary(_) => "GameEvent",
         Message::Ping(_) => "ping_frame",
         Message::Pong(_) => "pong_frame",
         Message::Close(_) => "close_frame",
@@ -1462,6 +1520,9 @@ async fn handle_websocket_connection(
     // authenticated identity to be present *as*.
     let mut social_session: Option<SocialSession> = None;
 
+    // JSON until `Authenticate` asks for something else.
+    let game_event_encoding = GameEventEncoding::default();
+
     // Spawn task to forward messages from channel to WebSocket
     let ws_analytics_for_forwarder = ws_analytics.clone();
     let forward_task = tokio::spawn(async move {
@@ -1638,7 +1699,7 @@ async fn handle_websocket_connection(
                                 );
                             }
                             match parsed {
-                                Ok(WSMessage::RequestResync { game_id: resync_game_id }) => {
+                                Ok(WSMessage::RequestResync { game_id: resync_game_id, base_tick }) => {
                                     crate::resilience_metrics::record_websocket_resync_requested(1);
                                     // The client detected loss or divergence (stream
                                     // gap, repeated fingerprint mismatch, or a dead
@@ -1669,6 +1730,7 @@ async fn handle_websocket_connection(
                                             )
                                             .await;
                                             let ws_tx_clone = ws_tx.clone();
+                                            let game_event_encoding_clone = game_event_encoding.clone();
                                             let event_router_clone = event_router.clone();
                                             let db_clone = db.clone();
                                             let game_bus_clone = game_bus.clone();
@@ -1677,7 +1739,9 @@ async fn handle_websocket_connection(
                                                 subscribe_to_game_events(
                                                     resync_game_id,
                                                     user_id,
+                                                    base_tick,
                                                     ws_tx_clone,
+                                                    game_event_encoding_clone,
                                                     event_router_clone,
                                                     db_clone,
                                                     game_bus_clone,
@@ -1723,7 +1787,10 @@ async fn handle_websocket_connection(
                                     // successful re-entry into the connection's previously authorized
                                     // game. Successful JoinGame retries still restart subscriptions.
                                     let requested_game_id = match &ws_message {
-                                        WSMessage::JoinGame(game_id) => Some(*game_id),
+                                        WSMessage::JoinGame(game_id)
+                                        | WSMessage::SpectateGame(SpectateGame { game_id, .. }) => {
+                                            Some(*game_id)
+                                        }
                                         _ => None,
                                     };
 
@@ -1748,6 +1815,7 @@ async fn handle_websocket_connection(
                                         &cancellation_token,
                                         &ads_config,
                                         &ws_analytics,
+                                        &game_event_encoding,
                                     ).await {
                                         Ok(mut new_state) => {
                                             // Check if we're entering a game or lobby.
@@ -1833,6 +1901,7 @@ async fn handle_websocket_connection(
                                                     }
 
                                                     let ws_tx_clone = ws_tx.clone();
+                                                    let game_event_encoding_clone = game_event_encoding.clone();
                                                     let event_router_clone = event_router.clone();
                                                     let db_clone = db.clone();
                                                     let game_bus_clone = game_bus.clone();
@@ -1842,7 +1911,9 @@ async fn handle_websocket_connection(
                                                         subscribe_to_game_events(
                                                             game_id,
                                                             user_id,
+                                                            None,
                                                             ws_tx_clone,
+                                                            game_event_encoding_clone,
                                                             event_router_clone,
                                                             db_clone,
                                                             game_bus_clone,
@@ -2274,6 +2345,11 @@ async fn queue_existing_lobby_for_game_types(
     if lobby_metadata.matchmaking_pool != matchmaking_pool {
         return Err(anyhow!("Lobby belongs to a different matchmaking pool"));
     }
+    let ai_opponents = lobby_manager
+        .get_lobby_preferences(lobby_code)
+        .await
+        .context("Failed to load lobby preferences before queueing")?
+        .ai_opponents;
 
     let members_map = lobby_manager
         .get_lobby_members(lobby_code)
@@ -2334,6 +2410,7 @@ async fn queue_existing_lobby_for_game_types(
                 matchmaking_pool,
                 expected_ad_break_id,
                 membership_fence,
+                ai_opponents,
             )
             .await;
         drop(mm_guard);
@@ -3165,7 +3242,10 @@ fn canonical_command_identity(
 }
 
 fn snapshot_requires_command_outcomes(event: &GameEvent) -> bool {
-    matches!(event, GameEvent::Snapshot { .. })
+    matches!(
+        event,
+        GameEvent::Snapshot { .. } | GameEvent::SnapshotDelta { .. }
+    )
 }
 
 fn command_outcomes_for_user(
@@ -3656,14 +3736,18 @@ fn recovery_bridge_snapshot(envelope: &RecoveryEnvelopeV2, user_id: u32) -> Game
 
 async fn send_recovery_bridge_snapshot(
     ws_tx: &mpsc::Sender<Message>,
+    encoding: &GameEventEncoding,
+    subscription: &mut crate::replication::GameEventSubscription,
     envelope: &RecoveryEnvelopeV2,
     user_id: u32,
 ) -> bool {
-    let recovery_snapshot = recovery_bridge_snapshot(envelope, user_id);
-    let Ok(json) = serde_json::to_string(&WSMessage::GameEvent(recovery_snapshot)) else {
+    let recovery_snapshot = subscription.encode_snapshot(RoutedEvent::new(
+        recovery_bridge_snapshot(envelope, user_id),
+    ));
+    let Ok(msg) = encoding.frame(&recovery_snapshot) else {
         return false;
     };
-    ws_tx.send(Message::Text(json.into())).await.is_ok()
+    ws_tx.send(msg).await.is_ok()
 }
 
 /// Load the game's durable terminal state, if one exists. Absence, failure,
@@ -3907,6 +3991,7 @@ async fn anchor_first_frame(
     game_id: u32,
     user_id: u32,
     ws_tx: &mpsc::Sender<Message>,
+    encoding: &GameEventEncoding,
     subscription: &mut crate::replication::GameEventSubscription,
     event_router: &Arc<crate::replication::GameEventRouter>,
     db: &Arc<dyn Database>,
@@ -3922,6 +4007,7 @@ async fn anchor_first_frame(
         {
             send_completed_game_snapshot(
                 ws_tx,
+                encoding,
                 game_bus,
                 cluster_namespace,
                 game_id,
@@ -3956,6 +4042,7 @@ async fn anchor_first_frame(
             if let Some(terminal_state) = load_durable_terminal_state(db, game_id).await {
                 send_completed_game_snapshot(
                     ws_tx,
+                    encoding,
                     game_bus,
                     cluster_namespace,
                     game_id,
@@ -3966,7 +4053,9 @@ async fn anchor_first_frame(
                 .await;
                 return FirstFrame::Served;
             }
-            if !send_recovery_bridge_snapshot(ws_tx, &envelope, user_id).await {
+            if !send_recovery_bridge_snapshot(ws_tx, encoding, subscription, &envelope, user_id)
+                .await
+            {
                 return FirstFrame::Unavailable;
             }
             subscription.anchor(envelope.next_event_stream_sequence);
@@ -3988,13 +4077,15 @@ async fn anchor_first_frame(
         loop {
             match subscription.next().await {
                 Some(crate::replication::SubscriptionUpdate::Event(event_msg)) => {
-                    if matches!(event_msg.event, GameEvent::Snapshot { .. }) {
+                    if snapshot_requires_command_outcomes(&event_msg.event) {
                         break Some(event_msg);
                     }
                     // A zero-seq terminal rejection still reaches the player
                     // during warm-up; nothing else passes while cold.
-                    let json = serde_json::to_string(&WSMessage::GameEvent(event_msg)).unwrap();
-                    if ws_tx.send(Message::Text(json.into())).await.is_err() {
+                    let Ok(msg) = encoding.frame(&event_msg) else {
+                        break None;
+                    };
+                    if ws_tx.send(msg).await.is_err() {
                         break None;
                     }
                 }
@@ -4005,15 +4096,18 @@ async fn anchor_first_frame(
     })
     .await;
 
-    if let Ok(Some(mut snapshot_event)) = warmup {
+    if let Ok(Some(snapshot_event)) = warmup {
+        let mut snapshot_event = snapshot_event.into_message();
         snapshot_event.user_id = Some(user_id);
         let terminal = matches!(
             &snapshot_event.event,
             GameEvent::Snapshot { game_state }
                 if matches!(game_state.status, GameStatus::Complete { .. })
         );
-        let json = serde_json::to_string(&WSMessage::GameEvent(snapshot_event)).unwrap();
-        if ws_tx.send(Message::Text(json.into())).await.is_err() {
+        let Ok(msg) = encoding.frame(&RoutedEvent::new(snapshot_event)) else {
+            return FirstFrame::Unavailable;
+        };
+        if ws_tx.send(msg).await.is_err() {
             return FirstFrame::Unavailable;
         }
         if terminal {
@@ -4048,6 +4142,7 @@ async fn anchor_first_frame(
                     Ok(game_state) if matches!(game_state.status, GameStatus::Complete { .. }) => {
                         send_completed_game_snapshot(
                             ws_tx,
+                            encoding,
                             game_bus,
                             cluster_namespace,
                             game_id,
@@ -4108,7 +4203,9 @@ async fn anchor_first_frame(
 async fn subscribe_to_game_events(
     game_id: u32,
     user_id: u32,
+    acknowledged_snapshot: Option<u32>,
     ws_tx: mpsc::Sender<Message>,
+    encoding: GameEventEncoding,
     event_router: Arc<crate::replication::GameEventRouter>,
     db: Arc<dyn Database>,
     game_bus: Arc<GameBus>,
@@ -4124,11 +4221,15 @@ async fn subscribe_to_game_events(
     // guarantees no event between first frame and subscription can be
     // missed; the subscription's continuity rules drop the overlap instead.
     let mut subscription = event_router.subscribe_to_game(game_id).await;
+    if let Some(tick) = acknowledged_snapshot {
+        subscription.acknowledge_snapshot(tick);
+    }
 
     let anchor_result = anchor_first_frame(
         game_id,
         user_id,
         &ws_tx,
+        &encoding,
         &mut subscription,
         &event_router,
         &db,
@@ -4233,8 +4334,13 @@ async fn subscribe_to_game_events(
             drop(command_outcome_replay.take());
         }
 
-        let json = serde_json::to_string(&WSMessage::GameEvent(event_msg)).unwrap();
-        let msg = Message::Text(json.into());
+        let msg = match encoding.frame(&event_msg) {
+            Ok(msg) => msg,
+            Err(error) => {
+                error!(game_id, %error, "Failed to encode game event; ending subscription");
+                break;
+            }
+        };
         if let Err(e) = ws_tx.try_send(msg.clone()) {
             match e {
                 mpsc::error::TrySendError::Full(_) => {
@@ -4473,6 +4579,7 @@ async fn send_command_outcome_barrier(
 
 async fn send_game_snapshot(
     ws_tx: &mpsc::Sender<Message>,
+    encoding: &GameEventEncoding,
     game_id: u32,
     user_id: u32,
     game_state: &GameState,
@@ -4487,15 +4594,15 @@ async fn send_game_snapshot(
             game_state: game_state.clone(),
         },
     };
-    let json = serde_json::to_string(&WSMessage::GameEvent(snapshot_event))?;
     ws_tx
-        .send(Message::Text(json.into()))
+        .send(encoding.frame(&RoutedEvent::new(snapshot_event))?)
         .await
         .context("WebSocket channel closed while sending game snapshot")
 }
 
 async fn send_completed_game_snapshot(
     ws_tx: &mpsc::Sender<Message>,
+    encoding: &GameEventEncoding,
     game_bus: &GameBus,
     cluster_namespace: &ClusterNamespace,
     game_id: u32,
@@ -4551,6 +4658,7 @@ async fn send_completed_game_snapshot(
     };
     if !send_completed_game_snapshot_from_resolved(
         ws_tx,
+        encoding,
         game_id,
         user_id,
         game_state,
@@ -4568,13 +4676,14 @@ async fn send_completed_game_snapshot(
 
 async fn send_completed_game_snapshot_from_resolved(
     ws_tx: &mpsc::Sender<Message>,
+    encoding: &GameEventEncoding,
     game_id: u32,
     user_id: u32,
     game_state: &GameState,
     resolved: ResolvedCommandState,
     terminal_rejection_reason: Option<&str>,
 ) -> bool {
-    if let Err(error) = send_game_snapshot(ws_tx, game_id, user_id, game_state).await {
+    if let Err(error) = send_game_snapshot(ws_tx, encoding, game_id, user_id, game_state).await {
         error!(game_id, user_id, %error, "Failed to send completed game snapshot");
         return false;
     }
@@ -5002,6 +5111,8 @@ async fn authenticate_ws_connection(
     jwt_token: String,
     protocol_version: Option<u16>,
     distribution: Option<ClientDistribution>,
+    encoding: Option<WireEncoding>,
+    game_event_encoding: &GameEventEncoding,
     jwt_verifier: &Arc<dyn JwtVerifier>,
     db: &Arc<dyn Database>,
     ws_tx: &mpsc::Sender<Message>,
@@ -5096,11 +5207,17 @@ async fn authenticate_ws_connection(
                     .context("WebSocket closed before advertisement configuration")?;
             }
 
+            // Switched before the acknowledgement is queued, so every game
+            // event the client can see after it already uses the encoding.
+            if let Some(encoding) = encoding {
+                game_event_encoding.set(encoding);
+            }
             let authenticated = WSMessage::Authenticated {
                 task_boot_id: lifecycle.task_boot_id().to_owned(),
                 protocol_version: WS_PROTOCOL_VERSION,
                 capabilities: lifecycle.protocol_capabilities(),
                 socket_generation,
+                encoding,
             };
             ws_tx
                 .send(Message::Text(serde_json::to_string(&authenticated)?.into()))
@@ -5198,6 +5315,7 @@ async fn process_ws_message(
     // Taken only so the session id can be attached to the connection: it is
     // minted inside this function and nowhere else.
     ws_analytics: &crate::analytics::ws_sink::WsConnection,
+    game_event_encoding: &GameEventEncoding,
 ) -> Result<ConnectionState> {
     use tracing::debug;
     let state_str = match &state {
@@ -5261,6 +5379,8 @@ async fn process_ws_message(
                         jwt_token,
                         None,
                         None,
+                        None,
+                        game_event_encoding,
                         jwt_verifier,
                         db,
                         ws_tx,
@@ -5282,6 +5402,7 @@ async fn process_ws_message(
                     protocol_version,
                     anon_id,
                     distribution,
+                    encoding,
                 } => {
                     // Validated at the boundary so a malformed or unbounded
                     // client string can never reach an analytics event.
@@ -5314,6 +5435,8 @@ async fn process_ws_message(
                         jwt_token,
                         Some(protocol_version),
                         distribution,
+                        encoding,
+                        game_event_encoding,
                         jwt_verifier,
                         db,
                         ws_tx,
@@ -5563,6 +5686,7 @@ async fn process_ws_message(
                 WSMessage::UpdateLobbyPreferences {
                     selected_modes,
                     competitive,
+                    ai_opponents,
                 } => {
                     {
                         if let Some(ref lobby_handle) = lobby {
@@ -5583,6 +5707,7 @@ async fn process_ws_message(
                                         &lobby_manager::LobbyPreferences {
                                             selected_modes,
                                             competitive,
+                                            ai_opponents,
                                         },
                                     )
                                     .await?;
@@ -5915,6 +6040,62 @@ async fn process_ws_message(
                         websocket_id,
                     })
                 }
+                WSMessage::SpectateGame(SpectateGame {
+                    game_id: requested_game_id,
+                    game_code,
+                }) => {
+                    info!(
+                        "User {} ({}) spectating game {}",
+                        metadata.username, metadata.user_id, requested_game_id
+                    );
+
+                    // A code names its own game; it must be the one asked for,
+                    // or the subscription would follow a different game than
+                    // the one the client is waiting on.
+                    let admitted = spectate_game(
+                        db,
+                        metadata.user_id,
+                        requested_game_id,
+                        game_code.as_deref(),
+                        metadata.matchmaking_pool,
+                        game_bus,
+                        cluster_namespace,
+                    )
+                    .await
+                    .and_then(|actual_game_id| {
+                        if actual_game_id == requested_game_id {
+                            Ok(())
+                        } else {
+                            Err(anyhow!("Game code does not match this game"))
+                        }
+                    });
+                    if let Err(error) = admitted {
+                        warn!(
+                            game_id = requested_game_id,
+                            user_id = metadata.user_id,
+                            "Denied spectator join: {error:#}"
+                        );
+                        send_game_load_failed(ws_tx, requested_game_id, error.to_string()).await;
+                        return Ok(ConnectionState::Authenticated {
+                            metadata,
+                            lobby_handle: lobby,
+                            game_id: None,
+                            websocket_id,
+                        });
+                    }
+
+                    ws_tx
+                        .send(Message::Text(
+                            serde_json::to_string(&WSMessage::SpectatorJoined)?.into(),
+                        ))
+                        .await?;
+                    Ok(ConnectionState::Authenticated {
+                        metadata,
+                        lobby_handle: lobby,
+                        game_id: Some(requested_game_id),
+                        websocket_id,
+                    })
+                }
                 WSMessage::LeaveGame => {
                     if let Some(current_game_id) = game_id {
                         info!(
@@ -7296,13 +7477,14 @@ async fn check_game_host(db: &Arc<dyn Database>, game_id: u32, user_id: i32) ->
     Ok(host_user_id == Some(user_id))
 }
 
-#[allow(dead_code)] // custom-game/lobby feature scaffolding, not wired up yet
 async fn spectate_game(
     db: &Arc<dyn Database>,
     user_id: i32,
     game_id: u32,
     game_code: Option<&str>,
     matchmaking_pool: MatchmakingPool,
+    game_bus: &Arc<GameBus>,
+    cluster_namespace: &ClusterNamespace,
 ) -> Result<u32> {
     ensure_custom_game_access(matchmaking_pool)?;
 
@@ -7310,12 +7492,16 @@ async fn spectate_game(
     let actual_game_id = if let Some(code) = game_code {
         let game = db
             .get_game_by_code(code)
-            .await?
+            .await
+            .context("Could not look up the game")?
             .ok_or_else(|| anyhow::anyhow!("Invalid game code"))?;
 
         // Check if spectators are allowed for private games
         if game.is_private {
-            let lobby = db.get_custom_lobby_by_code(code).await?;
+            let lobby = db
+                .get_custom_lobby_by_code(code)
+                .await
+                .context("Could not look up the game")?;
 
             if let Some(lobby) = lobby {
                 let allow_spectators = lobby
@@ -7335,18 +7521,38 @@ async fn spectate_game(
         game.id as u32
     } else {
         // Direct game_id access - check if game exists and is public
-        let game = db.get_game_by_id(game_id as i32).await?;
+        let game = db
+            .get_game_by_id(game_id as i32)
+            .await
+            .context("Could not look up the game")?;
 
         match game {
             Some(g) if !g.is_private => game_id, // Public game, allow spectating
             Some(_) => return Err(anyhow::anyhow!("Cannot spectate private game without code")),
-            None => return Err(anyhow::anyhow!("Game not found")),
+            None => {
+                // A live game has no database record until it completes; its
+                // recovery envelope is the record until then.
+                let envelope = game_bus
+                    .get_recovery(cluster_namespace, game_id)
+                    .await
+                    .context("Could not look up the game")?
+                    .ok_or_else(|| anyhow::anyhow!("Game not found"))?;
+                let state = &envelope.game_state;
+                if state.is_stress_test {
+                    return Err(anyhow::anyhow!("Game not found"));
+                }
+                if game_state_is_private(state) {
+                    return Err(anyhow::anyhow!("Cannot spectate private game without code"));
+                }
+                game_id
+            }
         }
     };
 
     // Add spectator to the game
     db.add_spectator_to_game(actual_game_id as i32, user_id)
-        .await?;
+        .await
+        .context("Could not join the game as a spectator")?;
 
     info!(
         "User {} joined as spectator for game {}",
@@ -7355,6 +7561,12 @@ async fn spectate_game(
     Ok(actual_game_id)
 }
 
+/// Same rule completion uses to mark a finished game private in match history.
+fn game_state_is_private(state: &GameState) -> bool {
+    state.game_code.is_some()
+        || matches!(&state.game_type, common::GameType::Custom { settings } if settings.is_private)
+}
+
 fn ensure_custom_game_access(matchmaking_pool: MatchmakingPool) -> Result<()> {
     if matchmaking_pool == MatchmakingPool::Stress {
         return Err(anyhow!(
@@ -7367,8 +7579,8 @@ fn ensure_custom_game_access(matchmaking_pool: MatchmakingPool) -> Result<()> {
 #[cfg(test)]
 mod lifecycle_protocol_tests {
     use super::{
-        CommandOutcomeReplay, GameChatBroadcast, GameJoinAuthorizationError, GameSubscriptionInput,
-        PlayerMetadata, TERMINAL_COMMAND_REJECTION_REASON, WSMessage,
+        CommandOutcomeReplay, GameChatBroadcast, GameEventEncoding, GameJoinAuthorizationError,
+        GameSubscriptionInput, PlayerMetadata, TERMINAL_COMMAND_REJECTION_REASON, WSMessage,
         abort_and_join_game_event_forwarder, acknowledge_lobby_match_handoff,
         canonical_command_identity, command_outcomes_for_user, ensure_custom_game_access,
         game_join_denied, game_join_failure_message, load_game_chat_history,
@@ -7395,9 +7607,10 @@ mod lifecycle_protocol_tests {
     };
     use crate::redis_keys::RedisKeys;
     use crate::redis_utils::{RedisConnection, create_connection_manager};
+    use crate::replication::RoutedEvent;
     use common::{
         ClientCommandIdentityV2, GameEvent, GameEventMessage, GameState, GameStatus, GameType,
-        QueueMode,
+        QueueMode, WireEncoding, decode_game_event_frame,
     };
     use redis::{AsyncCommands, Client};
     use std::collections::BTreeMap;
@@ -7626,6 +7839,7 @@ mod lifecycle_protocol_tests {
             protocol_version: WS_PROTOCOL_VERSION,
             capabilities: vec!["planned-drain-v1".to_owned()],
             socket_generation: 3,
+            encoding: None,
         })
         .unwrap();
         assert_eq!(value["Authenticated"]["task_boot_id"], "task-a");
@@ -7643,6 +7857,7 @@ mod lifecycle_protocol_tests {
             protocol_version: WS_PROTOCOL_VERSION,
             anon_id: None,
             distribution: Some(ClientDistribution::Web),
+            encoding: None,
         })
         .unwrap();
         assert_eq!(value["Authenticate"]["token"], "jwt");
@@ -7739,6 +7954,89 @@ mod lifecycle_protocol_tests {
         ));
     }
 
+    #[test]
+    fn authentication_request_can_omit_the_encoding() {
+        let value: WSMessage = serde_json::from_value(serde_json::json!({
+            "Authenticate": {
+                "token": "jwt",
+                "protocol_version": WS_PROTOCOL_VERSION
+            }
+        }))
+        .unwrap();
+        assert!(matches!(
+            value,
+            WSMessage::Authenticate { encoding: None, .. }
+        ));
+
+        let value: WSMessage = serde_json::from_value(serde_json::json!({
+            "Authenticate": {
+                "token": "jwt",
+                "protocol_version": WS_PROTOCOL_VERSION,
+                "encoding": "MessagePack"
+            }
+        }))
+        .unwrap();
+        assert!(matches!(
+            value,
+            WSMessage::Authenticate {
+                encoding: Some(WireEncoding::MessagePack),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn game_events_follow_the_connection_encoding_from_one_shared_encode() {
+        let mut game_state = GameState::new(
+            40,
+            40,
+            GameType::Solo,
+            QueueMode::Quickmatch,
+            Some(u64::MAX),
+            0,
+        );
+        game_state.add_player(7, None).unwrap();
+        let event = GameEventMessage {
+            game_id: 3,
+            tick: 0,
+            sequence: u64::MAX,
+            stream_seq: 1,
+            user_id: Some(7),
+            event: GameEvent::Snapshot { game_state },
+        };
+        let routed = RoutedEvent::new(event.clone());
+
+        let json_socket = GameEventEncoding::default();
+        let Message::Text(text) = json_socket.frame(&routed).unwrap() else {
+            panic!("a JSON connection should get a text frame");
+        };
+        assert!(matches!(
+            serde_json::from_str::<WSMessage>(&text).unwrap(),
+            WSMessage::GameEvent(decoded) if decoded.stream_seq == event.stream_seq
+        ));
+
+        let binary_sockets = [GameEventEncoding::default(), GameEventEncoding::default()];
+        let frames: Vec<_> = binary_sockets
+            .iter()
+            .map(|encoding| {
+                encoding.set(WireEncoding::MessagePack);
+                let Message::Binary(frame) = encoding.frame(&routed).unwrap() else {
+                    panic!("a game event should go out as a binary frame");
+                };
+                frame
+            })
+            .collect();
+        assert_eq!(
+            frames[0].as_ptr(),
+            frames[1].as_ptr(),
+            "each socket encoded the broadcast again"
+        );
+        assert_eq!(
+            serde_json::to_value(decode_game_event_frame(&frames[0]).unwrap()).unwrap(),
+            serde_json::to_value(&event).unwrap()
+        );
+    }
+
     #[test]
     fn only_the_exact_gameplay_protocol_is_admitted() {
         assert!(validate_client_protocol_version(Some(WS_PROTOCOL_VERSION)).is_ok());
@@ -7836,8 +8134,19 @@ mod lifecycle_protocol_tests {
             "lease-token".to_owned(),
         );
         let (tx, mut rx) = mpsc::channel(2);
+        let (_events_tx, events_rx) = broadcast::channel(2);
+        let mut subscription = crate::replication::GameEventSubscription::for_test(events_rx, 42);
 
-        assert!(send_recovery_bridge_snapshot(&tx, &envelope, 5).await);
+        assert!(
+            send_recovery_bridge_snapshot(
+                &tx,
+                &GameEventEncoding::default(),
+                &mut subscription,
+                &envelope,
+                5
+            )
+            .await
+        );
         assert!(matches!(
             decode_ws_message(rx.recv().await.unwrap()),
             WSMessage::GameEvent(_)
@@ -7871,7 +8180,7 @@ mod lifecycle_protocol_tests {
             },
         };
         events_tx
-            .send(live_event)
+            .send(RoutedEvent::new(live_event))
             .expect("subscription should remain attached");
 
         let input = timeout(
@@ -8392,6 +8701,7 @@ mod lifecycle_protocol_tests {
         assert!(
             send_completed_game_snapshot_from_resolved(
                 &tx,
+                &GameEventEncoding::default(),
                 42,
                 5,
                 &state,
@@ -8445,6 +8755,7 @@ mod lifecycle_protocol_tests {
         assert!(
             send_completed_game_snapshot_from_resolved(
                 &tx,
+                &GameEventEncoding::default(),
                 42,
                 5,
                 &state,
@@ -8495,7 +8806,9 @@ mod message_type_naming_tests {
     use crate::ads::{AdBreakResolution, ClientAdsConfig};
     use crate::challenges::ChallengeInbox;
     use crate::presence::RegionRoster;
-    use common::{CommandId, Direction, GameCommand, GameType, QueueMode};
+    use common::{
+        CommandId, Direction, GameCommand, GameType, QueueMode, SpectatorRequest, SpectatorUpdate,
+    };
 
     /// One value of every `WSMessage` variant.
     ///
@@ -8511,8 +8824,13 @@ mod message_type_naming_tests {
                 protocol_version: WS_PROTOCOL_VERSION,
                 anon_id: None,
                 distribution: None,
+                encoding: None,
             },
             WSMessage::JoinGame(1),
+            WSMessage::SpectateGame(SpectateGame {
+                game_id: 1,
+                game_code: None,
+            }),
             WSMessage::LeaveGame,
             WSMessage::GameCommandV2 {
                 command_id: ClientCommandIdentityV2 {
@@ -8583,10 +8901,14 @@ mod message_type_naming_tests {
                 protocol_version: WS_PROTOCOL_VERSION,
                 capabilities: Vec::new(),
                 socket_generation: 1,
+                encoding: None,
             },
             WSMessage::AdConfiguration(ClientAdsConfig::default()),
             WSMessage::PlayerReady { game_id: 1 },
-            WSMessage::RequestResync { game_id: 1 },
+            WSMessage::RequestResync {
+                game_id: 1,
+                base_tick: Some(3),
+            },
             WSMessage::Ping { client_time: 0 },
             WSMessage::Pong {
                 client_time: 0,
@@ -8658,6 +8980,7 @@ mod message_type_naming_tests {
             WSMessage::UpdateLobbyPreferences {
                 selected_modes: Vec::new(),
                 competitive: false,
+                ai_opponents: None,
             },
             WSMessage::LobbyRegionMismatch {
                 target_region: "euw1".to_owned(),
@@ -8738,6 +9061,70 @@ mod message_type_naming_tests {
         assert_eq!(names.len(), 52, "every variant must be covered");
     }
 
+    /// Compare only the fields `view` keeps: a spectator reads a subset of
+    /// each message, and whatever it reads must say what the server said.
+    fn assert_spectator_view(frame: &serde_json::Value, view: &serde_json::Value) {
+        match (frame, view) {
+            (serde_json::Value::Object(frame), serde_json::Value::Object(view)) => {
+                for (key, value) in view {
+                    let sent = frame.get(key).unwrap_or(&serde_json::Value::Null);
+                    assert_spectator_view(sent, value);
+                }
+            }
+            _ => assert_eq!(frame, view),
+        }
+    }
+
+    /// The terminal speaks to the server through common's spectator messages
+    /// rather than this enum, so the two must agree frame for frame.
+    #[test]
+    fn spectator_messages_mean_the_same_to_the_server() {
+        let requests = [
+            SpectatorRequest::Authenticate {
+                token: "jwt".to_owned(),
+                protocol_version: WS_PROTOCOL_VERSION,
+                encoding: Some(WireEncoding::Json),
+            },
+            SpectatorRequest::SpectateGame(SpectateGame {
+                game_id: 1,
+                game_code: Some("ABCD".to_owned()),
+            }),
+            SpectatorRequest::RequestResync {
+                game_id: 1,
+                base_tick: Some(2),
+            },
+            SpectatorRequest::Ping { client_time: 3 },
+        ];
+        for request in requests {
+            let frame = serde_json::to_string(&request).unwrap();
+            let message: WSMessage = serde_json::from_str(&frame).unwrap();
+            assert_eq!(serde_json::to_string(&message).unwrap(), frame);
+        }
+
+        let understood: Vec<&str> = every_variant()
+            .iter()
+            .filter_map(|message| {
+                let frame = serde_json::to_value(message).unwrap();
+                let update = serde_json::from_value::<SpectatorUpdate>(frame.clone()).ok()?;
+                assert_spectator_view(&frame, &serde_json::to_value(&update).unwrap());
+                Some(message.variant_name())
+            })
+            .collect();
+        assert_eq!(
+            understood,
+            [
+                "GameEvent",
+                "Authenticated",
+                "Pong",
+                "SpectatorJoined",
+                "AccessDenied",
+                "GameLoadFailed",
+                "GameWarming",
+                "Drain",
+            ]
+        );
+    }
+
     /// The names go into an analytics column, so they must stay inside the
     /// shape the wire-tag reader accepts — otherwise the two directions would
     /// label the same message differently.
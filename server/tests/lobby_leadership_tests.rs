@@ -111,6 +111,7 @@ async fn non_leader_cannot_change_lobby_preferences() -> Result<()> {
         .send_message(WSMessage::UpdateLobbyPreferences {
             selected_modes: vec!["ffa".to_string()],
             competitive: true,
+            ai_opponents: None,
         })
         .await?;
 
@@ -132,12 +133,14 @@ async fn non_leader_cannot_change_lobby_preferences() -> Result<()> {
         .send_message(WSMessage::UpdateLobbyPreferences {
             selected_modes: vec!["ffa".to_string()],
             competitive: true,
+            ai_opponents: None,
         })
         .await?;
 
     let expected = LobbyPreferences {
         selected_modes: vec!["ffa".to_string()],
         competitive: true,
+        ai_opponents: None,
     };
     timeout(Duration::from_secs(5), async {
         loop {
@@ -273,6 +276,7 @@ async fn leadership_passes_to_a_remaining_member_when_the_host_leaves() -> Resul
         .send_message(WSMessage::UpdateLobbyPreferences {
             selected_modes: vec!["2v2".to_string()],
             competitive: false,
+            ai_opponents: None,
         })
         .await?;
 
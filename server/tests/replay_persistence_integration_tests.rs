@@ -42,6 +42,7 @@ fn recorded_completion(game_id: u32) -> CompletionRecordV1 {
         messages: Vec::new(),
         end_tick: final_state.tick,
         end_sync_hash: final_state.sync_hash(),
+        keyframes: Vec::new(),
     };
     let play_of_the_game = HighlightClip {
         clip_format_version: common::HIGHLIGHT_CLIP_FORMAT_VERSION,
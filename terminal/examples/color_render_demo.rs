@@ -1,4 +1,4 @@
-use common::{Arena, Direction, Position, Snake};
+use common::{Arena, Direction, EdgePolicy, OccupancyGrid, Position, Snake};
 use terminal::render::{
     arena::ArenaRenderer,
     standard_renderer::StandardRenderer,
@@ -60,6 +60,11 @@ fn main() {
         ],
         boost_pads: Vec::new(),
         team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: None,
+        edges: EdgePolicy::Solid,
+        occupancy: OccupancyGrid::default(),
     };
 
     println!("=== Color-based Snake Rendering Demo ===\n");
@@ -4,8 +4,9 @@ use ratatui::Frame;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::live::connection::{LiveConnection, LiveOptions};
 use crate::replay::reader::ReplayReader;
-use crate::views::{ReplaySelectorState, ReplayViewerState, View};
+use crate::views::{LiveViewerState, ReplaySelectorState, ReplayViewerState, View};
 
 #[derive(Debug)]
 pub enum AppCommand {
@@ -17,6 +18,7 @@ pub enum AppCommand {
 pub enum AppState {
     ReplaySelector(Box<ReplaySelectorState>),
     ReplayViewer(Box<ReplayViewerState>),
+    LiveViewer(Box<LiveViewerState>),
 }
 
 pub struct App {
@@ -33,10 +35,22 @@ impl App {
         })
     }
 
+    /// Spectate a running game instead of browsing replays. Leaving the live
+    /// view quits; there is no selector to go back to.
+    pub fn live(options: LiveOptions, replay_dir: PathBuf) -> Result<Self> {
+        let game_id = options.game_id;
+        let connection = LiveConnection::spawn(options)?;
+        Ok(Self {
+            state: AppState::LiveViewer(Box::new(LiveViewerState::new(connection, game_id))),
+            replay_dir,
+        })
+    }
+
     pub fn handle_input(&mut self, key: KeyEvent) -> Option<AppCommand> {
         match &mut self.state {
             AppState::ReplaySelector(selector) => selector.handle_input(key),
             AppState::ReplayViewer(viewer) => viewer.handle_input(key),
+            AppState::LiveViewer(viewer) => viewer.handle_input(key),
         }
     }
 
@@ -44,6 +58,7 @@ impl App {
         match &mut self.state {
             AppState::ReplaySelector(selector) => selector.update(dt),
             AppState::ReplayViewer(viewer) => viewer.update(dt),
+            AppState::LiveViewer(viewer) => viewer.update(dt),
         }
     }
 
@@ -51,6 +66,7 @@ impl App {
         match &self.state {
             AppState::ReplaySelector(selector) => selector.render(frame),
             AppState::ReplayViewer(viewer) => viewer.render(frame),
+            AppState::LiveViewer(viewer) => viewer.render(frame),
         }
     }
 
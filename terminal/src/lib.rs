@@ -1,4 +1,5 @@
 pub mod app;
+pub mod live;
 pub mod render;
 pub mod replay;
 pub mod views;
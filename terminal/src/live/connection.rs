@@ -0,0 +1,319 @@
+use anyhow::{bail, Context, Result};
+use common::{
+    decode_game_event_frame, GameEventMessage, SpectateGame, SpectatorRequest, SpectatorUpdate,
+    WireEncoding, SPECTATE_CAPABILITY, WS_PROTOCOL_VERSION,
+};
+use futures_util::{Sink, SinkExt, StreamExt};
+use reqwest::Url;
+use serde::Deserialize;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc as async_mpsc;
+use tokio::time::MissedTickBehavior;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use tracing::debug;
+
+/// Pings double as the clock probe for prediction and as a keepalive.
+const PING_INTERVAL: Duration = Duration::from_secs(5);
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+/// Upper bound on a server-suggested `GameWarming` retry.
+const MAX_JOIN_RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Display name of the guest account made for an anonymous session.
+const GUEST_NICKNAME: &str = "Spectator";
+
+#[derive(Debug, Clone)]
+pub struct LiveOptions {
+    /// WebSocket endpoint, e.g. `ws://localhost:8080/ws`.
+    pub server_url: String,
+    /// HTTP origin of the API, for signing in as a guest. Defaults to the
+    /// WebSocket endpoint's origin.
+    pub api_url: Option<String>,
+    /// JWT to watch as. Without one the session signs in as a fresh guest,
+    /// which is enough for any public game.
+    pub token: Option<String>,
+    pub game_id: u32,
+    /// Code of a private game that admits spectators.
+    pub game_code: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GuestSession {
+    token: String,
+}
+
+#[derive(Debug)]
+pub enum LiveUpdate {
+    /// Progress of the session, for the status line.
+    Status(String),
+    /// Boxed: a snapshot carries a whole `GameState`.
+    Event(Box<GameEventMessage>),
+    /// Estimated `server clock - local clock`, from the latest ping.
+    ClockOffset(i64),
+    /// The connection dropped; another attempt follows shortly.
+    Disconnected(String),
+    /// The server refused the session for good.
+    Failed(String),
+}
+
+enum SessionEnd {
+    Reconnect(String),
+    Stop(String),
+}
+
+/// A spectator session running on its own runtime, so the synchronous draw
+/// loop can poll it between frames.
+pub struct LiveConnection {
+    updates: mpsc::Receiver<LiveUpdate>,
+    requests: async_mpsc::UnboundedSender<SpectatorRequest>,
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl LiveConnection {
+    pub fn spawn(options: LiveOptions) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .context("Failed to start the live session runtime")?;
+        let (update_tx, updates) = mpsc::channel();
+        let (requests, request_rx) = async_mpsc::unbounded_channel();
+        runtime.spawn(run(options, update_tx, request_rx));
+        Ok(Self {
+            updates,
+            requests,
+            _runtime: runtime,
+        })
+    }
+
+    /// Everything the session has received since the last call.
+    pub fn drain(&self) -> Vec<LiveUpdate> {
+        self.updates.try_iter().collect()
+    }
+
+    /// Queue a message for the server. Dropped while disconnected.
+    pub fn send(&self, message: SpectatorRequest) {
+        let _ = self.requests.send(message);
+    }
+}
+
+async fn run(
+    options: LiveOptions,
+    updates: mpsc::Sender<LiveUpdate>,
+    mut requests: async_mpsc::UnboundedReceiver<SpectatorRequest>,
+) {
+    // A guest token is made once and reused, so reconnecting does not leave
+    // a trail of guest accounts behind.
+    let mut token = options.token.clone();
+    loop {
+        let _ = updates.send(LiveUpdate::Status(format!(
+            "Connecting to {}",
+            options.server_url
+        )));
+        let end = session(&options, &mut token, &updates, &mut requests)
+            .await
+            .unwrap_or_else(|error| SessionEnd::Reconnect(format!("{error:#}")));
+        match end {
+            SessionEnd::Reconnect(reason) => {
+                if updates.send(LiveUpdate::Disconnected(reason)).is_err() {
+                    return;
+                }
+            }
+            SessionEnd::Stop(reason) => {
+                let _ = updates.send(LiveUpdate::Failed(reason));
+                return;
+            }
+        }
+        // Requests made for the old socket (a resync) mean nothing to the
+        // next one, which starts from a fresh snapshot anyway.
+        while requests.try_recv().is_ok() {}
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn session(
+    options: &LiveOptions,
+    token: &mut Option<String>,
+    updates: &mpsc::Sender<LiveUpdate>,
+    requests: &mut async_mpsc::UnboundedReceiver<SpectatorRequest>,
+) -> Result<SessionEnd> {
+    let token = match token {
+        Some(token) => token.clone(),
+        None => {
+            let _ = updates.send(LiveUpdate::Status("Signing in as a guest".to_string()));
+            token.insert(guest_token(options).await?).clone()
+        }
+    };
+    let (socket, _) = connect_async(options.server_url.as_str())
+        .await
+        .with_context(|| format!("Failed to connect to {}", options.server_url))?;
+    let (mut writer, mut reader) = socket.split();
+    send_ws(
+        &mut writer,
+        SpectatorRequest::Authenticate {
+            token,
+            protocol_version: WS_PROTOCOL_VERSION,
+            encoding: Some(WireEncoding::Json),
+        },
+    )
+    .await?;
+    let _ = updates.send(LiveUpdate::Status("Authenticating".to_string()));
+
+    let mut authenticated = false;
+    let mut ping = tokio::time::interval(PING_INTERVAL);
+    ping.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        tokio::select! {
+            frame = reader.next() => {
+                let Some(frame) = frame else {
+                    return Ok(SessionEnd::Reconnect("Connection closed".to_string()));
+                };
+                // JSON is requested, but a binary frame is still a game event
+                // in MessagePack and decodes the same way the browser's does.
+                let decoded = match frame.context("WebSocket failed")? {
+                    Message::Text(text) => {
+                        serde_json::from_str::<SpectatorUpdate>(&text).map_err(anyhow::Error::from)
+                    }
+                    Message::Binary(bytes) => {
+                        decode_game_event_frame(&bytes).map(SpectatorUpdate::GameEvent)
+                    }
+                    Message::Close(frame) => {
+                        return Ok(SessionEnd::Reconnect(format!(
+                            "Server closed the connection: {frame:?}"
+                        )));
+                    }
+                    _ => continue,
+                };
+                let message = match decoded {
+                    Ok(message) => message,
+                    Err(error) => {
+                        debug!(%error, "Ignoring unrecognised WebSocket message");
+                        continue;
+                    }
+                };
+                match message {
+                    SpectatorUpdate::Authenticated { capabilities, .. } => {
+                        let spectatable = capabilities
+                            .iter()
+                            .any(|capability| capability == SPECTATE_CAPABILITY);
+                        if !spectatable {
+                            return Ok(SessionEnd::Stop(
+                                "The server does not support spectating".to_string(),
+                            ));
+                        }
+                        authenticated = true;
+                        send_ws(&mut writer, spectate_request(options)).await?;
+                        let status = format!("Joining game {}", options.game_id);
+                        let _ = updates.send(LiveUpdate::Status(status));
+                    }
+                    SpectatorUpdate::SpectatorJoined => {
+                        let status = format!("Watching game {}", options.game_id);
+                        let _ = updates.send(LiveUpdate::Status(status));
+                    }
+                    SpectatorUpdate::GameEvent(event) => {
+                        if updates.send(LiveUpdate::Event(Box::new(event))).is_err() {
+                            return Ok(SessionEnd::Stop("Viewer closed".to_string()));
+                        }
+                    }
+                    SpectatorUpdate::GameWarming { retry_after_ms, .. } => {
+                        let status = format!("Game {} is warming up", options.game_id);
+                        let _ = updates.send(LiveUpdate::Status(status));
+                        let delay = Duration::from_millis(retry_after_ms).min(MAX_JOIN_RETRY_DELAY);
+                        tokio::time::sleep(delay).await;
+                        send_ws(&mut writer, spectate_request(options)).await?;
+                    }
+                    SpectatorUpdate::Pong { client_time, server_time } => {
+                        let midpoint = client_time + (now_ms() - client_time) / 2;
+                        let _ = updates.send(LiveUpdate::ClockOffset(server_time - midpoint));
+                    }
+                    SpectatorUpdate::GameLoadFailed { reason, .. }
+                    | SpectatorUpdate::AccessDenied { reason } => {
+                        return Ok(SessionEnd::Stop(reason));
+                    }
+                    SpectatorUpdate::Drain { .. } => {
+                        return Ok(SessionEnd::Reconnect("Server is draining".to_string()));
+                    }
+                }
+            }
+            request = requests.recv() => {
+                let Some(request) = request else {
+                    return Ok(SessionEnd::Stop("Viewer closed".to_string()));
+                };
+                send_ws(&mut writer, request).await?;
+            }
+            _ = ping.tick(), if authenticated => {
+                send_ws(&mut writer, SpectatorRequest::Ping { client_time: now_ms() }).await?;
+            }
+        }
+    }
+}
+
+fn spectate_request(options: &LiveOptions) -> SpectatorRequest {
+    SpectatorRequest::SpectateGame(SpectateGame {
+        game_id: options.game_id,
+        game_code: options.game_code.clone(),
+    })
+}
+
+/// Sign in as a new guest and return its JWT.
+async fn guest_token(options: &LiveOptions) -> Result<String> {
+    let endpoint = guest_endpoint(options)?;
+    let response = reqwest::Client::new()
+        .post(endpoint.clone())
+        .json(&serde_json::json!({ "nickname": GUEST_NICKNAME }))
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach {endpoint}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        bail!("Guest sign-in failed with status {status}: {body}");
+    }
+    let session = response
+        .json::<GuestSession>()
+        .await
+        .context("Failed to parse the guest sign-in response")?;
+    Ok(session.token)
+}
+
+/// `POST /api/auth/guest` on the API origin, which is the WebSocket
+/// endpoint's own origin unless `api_url` names another.
+fn guest_endpoint(options: &LiveOptions) -> Result<Url> {
+    let origin = match &options.api_url {
+        Some(api_url) => {
+            Url::parse(api_url).with_context(|| format!("Invalid API URL {api_url}"))?
+        }
+        None => {
+            let mut origin = Url::parse(&options.server_url)
+                .with_context(|| format!("Invalid server URL {}", options.server_url))?;
+            let scheme = match origin.scheme() {
+                "wss" => "https",
+                _ => "http",
+            };
+            origin.set_scheme(scheme).map_err(|_| {
+                anyhow::anyhow!("Cannot derive an API URL from {}", options.server_url)
+            })?;
+            origin
+        }
+    };
+    origin
+        .join("/api/auth/guest")
+        .context("Failed to build the guest sign-in URL")
+}
+
+async fn send_ws<S>(ws_writer: &mut S, msg: SpectatorRequest) -> Result<()>
+where
+    S: Sink<Message> + Unpin,
+    S::Error: std::error::Error + Send + Sync + 'static,
+{
+    let payload = serde_json::to_string(&msg)?;
+    ws_writer.send(Message::Text(payload.into())).await?;
+    Ok(())
+}
+
+pub fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,108 @@
+//! Live spectating: a WebSocket session joined to a running game, and the
+//! engine that keeps a local copy of it in step with the server.
+
+pub mod connection;
+
+use anyhow::Result;
+use common::{GameEngine, GameEvent, GameEventMessage, GameState, SyncStatus};
+
+/// A resync the engine needs after losing the stream. `base_tick` names the
+/// last applied snapshot so the server may answer with a delta against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResyncRequest {
+    pub base_tick: Option<u32>,
+}
+
+/// One spectated game, kept in sync from the server's `GameEvent` frames.
+///
+/// The engine is built from the first snapshot; frames that arrive before it
+/// have nothing to apply to and are dropped.
+pub struct LiveGame {
+    game_id: u32,
+    engine: Option<GameEngine>,
+    resync_in_flight: bool,
+}
+
+impl LiveGame {
+    pub fn new(game_id: u32) -> Self {
+        Self {
+            game_id,
+            engine: None,
+            resync_in_flight: false,
+        }
+    }
+
+    pub fn game_id(&self) -> u32 {
+        self.game_id
+    }
+
+    /// Apply one server frame. An error means the frame could not be applied;
+    /// the engine then asks for a resync through [`Self::take_resync_request`].
+    pub fn apply(&mut self, message: &GameEventMessage) -> Result<()> {
+        if message.game_id != self.game_id {
+            return Ok(());
+        }
+        if matches!(
+            message.event,
+            GameEvent::Snapshot { .. } | GameEvent::SnapshotDelta { .. }
+        ) {
+            self.resync_in_flight = false;
+        }
+        if let Some(engine) = self.engine.as_mut() {
+            return engine.process_server_event(message);
+        }
+        let GameEvent::Snapshot { game_state } = &message.event else {
+            return Ok(());
+        };
+        let mut engine = GameEngine::try_new_from_snapshot_state(self.game_id, game_state.clone())?;
+        // Processing the snapshot as well anchors the stream sequence and the
+        // delta base, exactly as a later resync snapshot would.
+        engine.process_server_event(message)?;
+        self.engine = Some(engine);
+        Ok(())
+    }
+
+    /// The resync to send, if the engine has lost the stream and no request
+    /// is already waiting on its snapshot.
+    pub fn take_resync_request(&mut self) -> Option<ResyncRequest> {
+        let engine = self.engine.as_mut()?;
+        if !engine.sync_status().needs_resync || self.resync_in_flight {
+            return None;
+        }
+        engine.clear_needs_resync();
+        self.resync_in_flight = true;
+        Some(ResyncRequest {
+            base_tick: engine.snapshot_base_tick(),
+        })
+    }
+
+    /// Forget an outstanding resync, e.g. because the connection that carried
+    /// it dropped. The next snapshot re-anchors the engine regardless.
+    pub fn reset_resync(&mut self) {
+        self.resync_in_flight = false;
+    }
+
+    /// Predict forward to `server_now_ms`. Between server frames, which can
+    /// be a second apart, this is what keeps the snakes moving.
+    pub fn advance(&mut self, server_now_ms: i64) -> Result<()> {
+        match self.engine.as_mut() {
+            Some(engine) => engine.rebuild_predicted_state(server_now_ms),
+            None => Ok(()),
+        }
+    }
+
+    /// The state to draw: the prediction when there is one, otherwise the
+    /// last authoritative state. `None` until the first snapshot.
+    pub fn state(&self) -> Option<&GameState> {
+        let engine = self.engine.as_ref()?;
+        Some(
+            engine
+                .predicted_state()
+                .unwrap_or_else(|| engine.committed_state()),
+        )
+    }
+
+    pub fn sync_status(&self) -> Option<&SyncStatus> {
+        self.engine.as_ref().map(GameEngine::sync_status)
+    }
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event},
     execute,
@@ -13,27 +13,43 @@ use std::path::PathBuf;
 use std::time::Duration;
 
 use terminal::app::{App, AppCommand};
+use terminal::live::connection::LiveOptions;
+
+const DEFAULT_REPLAY_DIR: &str = "/tmp/snaketron_replays";
+const DEFAULT_SERVER_URL: &str = "ws://localhost:8080/ws";
+const LIVE_USAGE: &str = "Usage: snaketron live --game GAME_ID [--code GAME_CODE] \
+    [--server WS_URL] [--api HTTP_URL] [--token JWT]\n\
+    The token defaults to $SNAKETRON_TOKEN; without one the viewer signs in as a guest.\n\
+    A private game also needs its --code.";
 
 fn main() -> Result<()> {
     // Initialize logging
     tracing_subscriber::fmt::init();
 
-    // Removed debug code
-
-    // Get replay directory from args or use centralized default
-    let replay_dir = std::env::args()
-        .nth(1)
-        .map(PathBuf::from)
-        .unwrap_or_else(|| PathBuf::from("/tmp/snaketron_replays"));
-
-    println!("Looking for replays in: {:?}", replay_dir);
-
-    // Check if directory exists
-    if !replay_dir.exists() {
-        eprintln!("Replay directory does not exist: {:?}", replay_dir);
-        eprintln!("Creating directory...");
-        std::fs::create_dir_all(&replay_dir)?;
-    }
+    let mut args = std::env::args().skip(1).peekable();
+
+    // Create app: `snaketron live ...` spectates a running game, anything
+    // else browses a replay directory.
+    let mut app = if args.peek().map(String::as_str) == Some("live") {
+        args.next();
+        App::live(parse_live_options(args)?, PathBuf::from(DEFAULT_REPLAY_DIR))?
+    } else {
+        // Get replay directory from args or use centralized default
+        let replay_dir = args
+            .next()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_REPLAY_DIR));
+
+        println!("Looking for replays in: {:?}", replay_dir);
+
+        // Check if directory exists
+        if !replay_dir.exists() {
+            eprintln!("Replay directory does not exist: {:?}", replay_dir);
+            eprintln!("Creating directory...");
+            std::fs::create_dir_all(&replay_dir)?;
+        }
+        App::new(replay_dir)?
+    };
 
     // Setup terminal
     enable_raw_mode()?;
@@ -42,9 +58,6 @@ fn main() -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app
-    let mut app = App::new(replay_dir)?;
-
     // Run app
     let res = run_app(&mut terminal, &mut app);
 
@@ -64,6 +77,41 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn parse_live_options(mut args: impl Iterator<Item = String>) -> Result<LiveOptions> {
+    let mut server_url = DEFAULT_SERVER_URL.to_string();
+    let mut api_url = None;
+    let mut token = std::env::var("SNAKETRON_TOKEN").ok();
+    let mut game_id = None;
+    let mut game_code = None;
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("{flag} needs a value\n{LIVE_USAGE}"))
+        };
+        match flag.as_str() {
+            "--server" => server_url = value()?,
+            "--api" => api_url = Some(value()?),
+            "--token" => token = Some(value()?),
+            "--code" => game_code = Some(value()?),
+            "--game" => {
+                game_id = Some(
+                    value()?
+                        .parse()
+                        .context("--game must be a numeric game id")?,
+                )
+            }
+            _ => bail!("unknown option {flag}\n{LIVE_USAGE}"),
+        }
+    }
+    Ok(LiveOptions {
+        server_url,
+        api_url,
+        token,
+        game_id: game_id.with_context(|| format!("pass --game\n{LIVE_USAGE}"))?,
+        game_code,
+    })
+}
+
 fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     let mut last_update = std::time::Instant::now();
 
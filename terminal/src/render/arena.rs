@@ -1,7 +1,7 @@
 use super::snake::SnakeRenderer;
 use super::traits::GameObjectRenderer;
 use super::types::{CharGrid, RenderConfig};
-use common::Arena;
+use common::{Arena, Position};
 
 pub struct ArenaRenderer<R: GameObjectRenderer> {
     renderer: R,
@@ -19,6 +19,19 @@ impl<R: GameObjectRenderer> ArenaRenderer<R> {
             config.chars_per_point,
         );
 
+        // Closed battle royale cells go down first, so anything still lying
+        // or caught outside the ring draws over the shading.
+        if arena.zone.is_some() {
+            for y in 0..arena.height as i16 {
+                for x in 0..arena.width as i16 {
+                    if arena.is_outside_zone(&Position { x, y }) {
+                        let pattern = self.renderer.render_zone_closed();
+                        grid.set_logical_point(x as usize, y as usize, &pattern);
+                    }
+                }
+            }
+        }
+
         // Render food
         for food in &arena.food {
             if food.x >= 0
@@ -51,12 +64,27 @@ impl<R: GameObjectRenderer> ArenaRenderer<R> {
             }
         }
 
+        for pickup in &arena.power_ups {
+            let position = pickup.position;
+            if position.x >= 0
+                && position.x < arena.width as i16
+                && position.y >= 0
+                && position.y < arena.height as i16
+            {
+                let pattern = self.renderer.render_power_up(pickup.kind);
+                grid.set_logical_point(position.x as usize, position.y as usize, &pattern);
+            }
+        }
+
         // Render snakes
         for (idx, snake) in arena.snakes.iter().enumerate() {
             if snake.is_alive {
                 let positions = SnakeRenderer::expand_snake_body(snake);
 
                 for (i, pos) in positions.iter().enumerate() {
+                    // On a wrapping field the part of the body behind a seam
+                    // comes back in on the far side.
+                    let pos = arena.wrap_position(*pos);
                     if pos.x >= 0
                         && pos.x < arena.width as i16
                         && pos.y >= 0
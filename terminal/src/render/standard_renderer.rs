@@ -1,6 +1,6 @@
 use super::traits::GameObjectRenderer;
 use super::types::{CharDimensions, CharPattern};
-use common::Direction;
+use common::{Direction, PowerUpKind};
 use ratatui::style::{Color, Style};
 
 pub struct StandardRenderer {
@@ -111,6 +111,22 @@ impl GameObjectRenderer for StandardRenderer {
         CharPattern::new_with_style(chars, style)
     }
 
+    fn render_power_up(&self, kind: PowerUpKind) -> CharPattern {
+        // One glyph per kind, so the three stay distinguishable without color.
+        let (glyph, color) = match kind {
+            PowerUpKind::Shield => ('◆', Color::LightCyan),
+            PowerUpKind::Ghost => ('◌', Color::Gray),
+            PowerUpKind::Magnet => ('∩', Color::LightMagenta),
+        };
+        let chars = vec![vec![glyph; self.char_dims.horizontal]; self.char_dims.vertical];
+        CharPattern::new_with_style(chars, Style::default().fg(color))
+    }
+
+    fn render_zone_closed(&self) -> CharPattern {
+        let chars = vec![vec!['░'; self.char_dims.horizontal]; self.char_dims.vertical];
+        CharPattern::new_with_style(chars, Style::default().fg(Color::Red))
+    }
+
     fn render_empty(&self) -> CharPattern {
         CharPattern::empty(self.char_dims)
     }
@@ -1,5 +1,5 @@
 use super::types::{CharDimensions, CharPattern};
-use common::Direction;
+use common::{Direction, PowerUpKind};
 
 // Lib-facing API; the snaketron bin compiles these modules too and uses a subset.
 #[allow(dead_code)]
@@ -18,5 +18,10 @@ pub trait GameObjectRenderer {
 
     fn render_boost_pad(&self) -> CharPattern;
 
+    fn render_power_up(&self, kind: PowerUpKind) -> CharPattern;
+
+    /// A cell the battle royale zone has closed over.
+    fn render_zone_closed(&self) -> CharPattern;
+
     fn render_empty(&self) -> CharPattern;
 }
@@ -0,0 +1,286 @@
+use super::arena_view::render_arena;
+use super::View;
+use crate::app::AppCommand;
+use crate::live::connection::{now_ms, LiveConnection, LiveUpdate};
+use crate::live::LiveGame;
+use common::{GameEvent, GameState, GameStatus, SpectatorRequest};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// How many recent event lines the log keeps.
+const EVENT_LOG_CAPACITY: usize = 200;
+const MIN_WIDTH_FOR_TWO_COLUMN: u16 = 100;
+
+pub struct LiveViewerState {
+    connection: LiveConnection,
+    game: LiveGame,
+    status: String,
+    clock_offset_ms: i64,
+    event_log: VecDeque<String>,
+}
+
+impl LiveViewerState {
+    pub fn new(connection: LiveConnection, game_id: u32) -> Self {
+        Self {
+            connection,
+            game: LiveGame::new(game_id),
+            status: "Starting".to_string(),
+            clock_offset_ms: 0,
+            event_log: VecDeque::new(),
+        }
+    }
+
+    fn log(&mut self, line: String) {
+        if self.event_log.len() == EVENT_LOG_CAPACITY {
+            self.event_log.pop_front();
+        }
+        self.event_log.push_back(line);
+    }
+
+    fn handle_update(&mut self, update: LiveUpdate) {
+        match update {
+            LiveUpdate::Status(status) => self.status = status,
+            LiveUpdate::Event(message) => {
+                // Heartbeats arrive every second and only confirm the hash.
+                if !matches!(message.event, GameEvent::TickHash { .. }) {
+                    self.log(format!(
+                        "{:>6}  {}",
+                        message.tick,
+                        event_name(&message.event)
+                    ));
+                }
+                if matches!(message.event, GameEvent::Snapshot { .. }) {
+                    self.status = format!("Watching game {}", self.game.game_id());
+                }
+                if let Err(error) = self.game.apply(&message) {
+                    self.log(format!("sync error at tick {}: {error:#}", message.tick));
+                }
+            }
+            LiveUpdate::ClockOffset(offset_ms) => self.clock_offset_ms = offset_ms,
+            LiveUpdate::Disconnected(reason) => {
+                self.game.reset_resync();
+                self.status = format!("Disconnected: {reason}");
+                self.log(format!("disconnected: {reason}"));
+            }
+            LiveUpdate::Failed(reason) => {
+                self.status = format!("Stopped: {reason}");
+                self.log(format!("session ended: {reason}"));
+            }
+        }
+    }
+
+    fn render_header(&self, state: Option<&GameState>) -> Paragraph<'_> {
+        let tick = state
+            .map(|state| format!("Tick: {}", state.tick))
+            .unwrap_or_else(|| "Waiting for snapshot".to_string());
+        Paragraph::new(format!(
+            "Live game {} | {} | {}",
+            self.game.game_id(),
+            tick,
+            self.status
+        ))
+        .style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL))
+    }
+
+    fn render_players(&self, state: &GameState) -> Paragraph<'_> {
+        let mut lines = vec![Line::from(vec![
+            Span::raw("Status: "),
+            Span::styled(status_text(state), Style::default().fg(Color::Yellow)),
+        ])];
+        let mut players: Vec<_> = state.players.iter().collect();
+        players.sort_by_key(|(_, player)| player.snake_id);
+        for (user_id, player) in players {
+            let Some(snake) = state.arena.snakes.get(player.snake_id as usize) else {
+                continue;
+            };
+            let name = state
+                .usernames
+                .get(user_id)
+                .cloned()
+                .unwrap_or_else(|| format!("Player {user_id}"));
+            let score = state.scores.get(&player.snake_id).copied().unwrap_or(0);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{name:<16}"),
+                    if snake.is_alive {
+                        Style::default().fg(Color::White)
+                    } else {
+                        Style::default().fg(Color::DarkGray)
+                    },
+                ),
+                Span::raw(format!(
+                    " {:<5} length {:>3}  score {:>4}",
+                    if snake.is_alive { "alive" } else { "dead" },
+                    snake.length(),
+                    score
+                )),
+            ]));
+        }
+        Paragraph::new(lines).block(Block::default().title("Players").borders(Borders::ALL))
+    }
+
+    fn render_sync(&self) -> Paragraph<'_> {
+        let line = match self.game.sync_status() {
+            Some(sync) => format!(
+                "Hash probes: {} ({} mismatched) | Stream gaps: {} | Clock offset: {} ms",
+                sync.total_probes,
+                sync.total_mismatches,
+                sync.stream_gap_count,
+                self.clock_offset_ms
+            ),
+            None => "No snapshot yet".to_string(),
+        };
+        Paragraph::new(vec![
+            Line::from(line),
+            Line::from(Span::styled(
+                "q/Esc: Quit",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ])
+        .block(Block::default().title("Sync").borders(Borders::ALL))
+    }
+
+    fn render_event_log(&self, frame: &mut Frame, area: Rect) {
+        let visible = area.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = self
+            .event_log
+            .iter()
+            .skip(self.event_log.len().saturating_sub(visible))
+            .map(|line| Line::from(line.as_str()))
+            .collect();
+        let log = Paragraph::new(lines)
+            .block(Block::default().title("Events").borders(Borders::ALL))
+            .style(Style::default().fg(Color::White));
+        frame.render_widget(log, area);
+    }
+
+    fn render_info(&self, frame: &mut Frame, area: Rect, state: Option<&GameState>) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),  // Header
+                Constraint::Length(10), // Players
+                Constraint::Length(4),  // Sync health and controls
+                Constraint::Min(5),     // Event log
+            ])
+            .split(area);
+        frame.render_widget(self.render_header(state), chunks[0]);
+        match state {
+            Some(state) => frame.render_widget(self.render_players(state), chunks[1]),
+            None => frame.render_widget(
+                Paragraph::new("Waiting for the game")
+                    .block(Block::default().title("Players").borders(Borders::ALL)),
+                chunks[1],
+            ),
+        }
+        frame.render_widget(self.render_sync(), chunks[2]);
+        self.render_event_log(frame, chunks[3]);
+    }
+}
+
+impl View for LiveViewerState {
+    fn handle_input(&mut self, key: KeyEvent) -> Option<AppCommand> {
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => Some(AppCommand::Quit),
+            _ => None,
+        }
+    }
+
+    fn update(&mut self, _dt: Duration) {
+        for update in self.connection.drain() {
+            self.handle_update(update);
+        }
+        if let Some(resync) = self.game.take_resync_request() {
+            self.log(format!(
+                "requesting resync from tick {:?}",
+                resync.base_tick
+            ));
+            self.connection.send(SpectatorRequest::RequestResync {
+                game_id: self.game.game_id(),
+                base_tick: resync.base_tick,
+            });
+        }
+        if let Err(error) = self.game.advance(now_ms() + self.clock_offset_ms) {
+            self.log(format!("prediction failed: {error:#}"));
+        }
+    }
+
+    fn render(&self, frame: &mut Frame) {
+        let state = self.game.state();
+        let wide = frame.area().width >= MIN_WIDTH_FOR_TWO_COLUMN;
+        let chunks = Layout::default()
+            .direction(if wide {
+                Direction::Horizontal
+            } else {
+                Direction::Vertical
+            })
+            .margin(1)
+            .constraints(if wide {
+                [Constraint::Percentage(50), Constraint::Percentage(50)]
+            } else {
+                [Constraint::Min(20), Constraint::Length(26)]
+            })
+            .split(frame.area());
+
+        match state {
+            Some(state) => render_arena(frame, chunks[0], &state.arena),
+            None => frame.render_widget(
+                Paragraph::new(self.status.as_str())
+                    .alignment(Alignment::Center)
+                    .block(Block::default().title("Arena").borders(Borders::ALL)),
+                chunks[0],
+            ),
+        }
+        self.render_info(frame, chunks[1], state);
+    }
+}
+
+fn status_text(state: &GameState) -> String {
+    match &state.status {
+        GameStatus::Stopped => "Waiting to start".to_string(),
+        GameStatus::Started { .. } => "In progress".to_string(),
+        GameStatus::Complete {
+            winning_snake_id: Some(winner),
+        } => {
+            let name = state
+                .players
+                .iter()
+                .find(|(_, player)| player.snake_id == *winner)
+                .and_then(|(user_id, _)| state.usernames.get(user_id));
+            match name {
+                Some(name) => format!("Complete - Winner: {name}"),
+                None => "Complete - Winner: Unknown".to_string(),
+            }
+        }
+        GameStatus::Complete {
+            winning_snake_id: None,
+        } => "Complete - Draw".to_string(),
+    }
+}
+
+/// The event's variant name, which is all a one-line log has room for.
+fn event_name(event: &GameEvent) -> String {
+    match serde_json::to_value(event) {
+        Ok(serde_json::Value::Object(fields)) => fields
+            .keys()
+            .next()
+            .cloned()
+            .unwrap_or_else(|| "Event".to_string()),
+        Ok(serde_json::Value::String(name)) => name,
+        _ => "Event".to_string(),
+    }
+}
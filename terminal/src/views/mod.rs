@@ -1,6 +1,9 @@
+pub mod arena_view;
+pub mod live_viewer;
 pub mod replay_selector;
 pub mod replay_viewer;
 
+pub use live_viewer::LiveViewerState;
 pub use replay_selector::ReplaySelectorState;
 pub use replay_viewer::ReplayViewerState;
 
@@ -1,8 +1,6 @@
+use super::arena_view::render_arena;
 use super::View;
 use crate::app::AppCommand;
-use crate::render::arena::ArenaRenderer;
-use crate::render::standard_renderer::StandardRenderer;
-use crate::render::types::{CharDimensions, RenderConfig};
 use crate::replay::{player::ReplayPlayer, ReplayData};
 use common::GameStatus;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
@@ -323,88 +321,7 @@ impl ReplayViewerState {
     }
 
     fn render_arena(&self, frame: &mut Frame, area: Rect) {
-        let arena = &self.player.current_state.arena;
-        let block = Block::default().title("Arena").borders(Borders::ALL);
-
-        let inner = block.inner(area);
-        frame.render_widget(block, area);
-
-        // Create renderer with 2x1 characters per point
-        let char_dims = CharDimensions::new(2, 1);
-        let renderer = StandardRenderer::new(char_dims);
-        let arena_renderer = ArenaRenderer::new(renderer);
-        let config = RenderConfig {
-            chars_per_point: char_dims,
-        };
-
-        // Render the arena to a character grid
-        let char_grid = arena_renderer.render(arena, &config);
-
-        // Calculate arena dimensions including borders
-        let arena_width = (arena.width as usize * char_dims.horizontal) + 2; // +2 for left and right borders
-        let arena_height = (arena.height as usize * char_dims.vertical) + 2; // +2 for top and bottom borders
-
-        // Calculate centering offsets
-        let x_offset = inner.width.saturating_sub(arena_width as u16) / 2;
-        let y_offset = inner.height.saturating_sub(arena_height as u16) / 2;
-
-        // Create lines with borders and proper positioning
-        let mut final_lines: Vec<Line> = Vec::new();
-
-        // Add vertical spacing for centering
-        for _ in 0..y_offset {
-            final_lines.push(Line::from(""));
-        }
-
-        // Top border
-        let mut top_border = " ".repeat(x_offset as usize);
-        top_border.push('┌');
-        for _ in 0..(arena.width as usize * char_dims.horizontal) {
-            top_border.push('─');
-        }
-        top_border.push('┐');
-        final_lines.push(Line::from(vec![Span::styled(
-            top_border,
-            Style::default().fg(Color::DarkGray),
-        )]));
-
-        // Convert grid to styled lines with side borders
-        let grid_lines = char_grid.into_styled_lines();
-        for (chars, styles) in grid_lines {
-            let mut line_spans = Vec::new();
-
-            // Left padding and border
-            if x_offset > 0 {
-                line_spans.push(Span::raw(" ".repeat(x_offset as usize)));
-            }
-            line_spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
-
-            // Arena content
-            for (ch, style) in chars.into_iter().zip(styles) {
-                line_spans.push(Span::styled(ch.to_string(), style));
-            }
-
-            // Right border
-            line_spans.push(Span::styled("│", Style::default().fg(Color::DarkGray)));
-
-            final_lines.push(Line::from(line_spans));
-        }
-
-        // Bottom border
-        let mut bottom_border = " ".repeat(x_offset as usize);
-        bottom_border.push('└');
-        for _ in 0..(arena.width as usize * char_dims.horizontal) {
-            bottom_border.push('─');
-        }
-        bottom_border.push('┘');
-        final_lines.push(Line::from(vec![Span::styled(
-            bottom_border,
-            Style::default().fg(Color::DarkGray),
-        )]));
-
-        let game_view = Paragraph::new(final_lines);
-
-        frame.render_widget(game_view, inner);
+        render_arena(frame, area, &self.player.current_state.arena);
     }
 
     fn render_status(&self) -> Paragraph<'_> {
@@ -0,0 +1,105 @@
+use anyhow::{bail, Context, Result};
+use common::{encode_game_event_frame, GameEvent, GameEventMessage};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+use terminal::live::connection::{LiveConnection, LiveOptions, LiveUpdate};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+const GAME_ID: u32 = 42;
+
+/// Play the server's side of one spectator session: check the client's
+/// requests, then send a game event as a binary MessagePack frame.
+async fn serve_spectator(listener: TcpListener) -> Result<Vec<Value>> {
+    let (stream, _) = listener.accept().await?;
+    let mut socket = tokio_tungstenite::accept_async(stream).await?;
+    let mut requests = Vec::new();
+    while requests.len() < 2 {
+        let frame = socket.next().await.context("client hung up")??;
+        let Message::Text(text) = frame else {
+            continue;
+        };
+        let request: Value = serde_json::from_str(&text)?;
+        if request.get("Ping").is_some() {
+            continue;
+        }
+        if request.get("Authenticate").is_some() {
+            let authenticated = json!({ "Authenticated": {
+                "task_boot_id": "boot",
+                "protocol_version": 15,
+                "capabilities": ["spectate-v1"],
+                "socket_generation": 1,
+            }});
+            socket
+                .send(Message::Text(authenticated.to_string().into()))
+                .await?;
+        }
+        requests.push(request);
+    }
+    socket
+        .send(Message::Text(json!("SpectatorJoined").to_string().into()))
+        .await?;
+    let event = GameEventMessage {
+        game_id: GAME_ID,
+        tick: 7,
+        sequence: 0,
+        stream_seq: 1,
+        user_id: None,
+        event: GameEvent::TickHash {
+            hash: 99,
+            server_ts_ms: 0,
+        },
+    };
+    socket
+        .send(Message::Binary(encode_game_event_frame(&event)?.into()))
+        .await?;
+    // Hold the socket open until the client has read the frame.
+    let _ = socket.next().await;
+    Ok(requests)
+}
+
+#[test]
+fn a_spectator_asks_for_json_joins_as_a_spectator_and_reads_binary_frames() -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    let listener = runtime.block_on(TcpListener::bind("127.0.0.1:0"))?;
+    let address = listener.local_addr()?;
+    let server = runtime.spawn(serve_spectator(listener));
+
+    let connection = LiveConnection::spawn(LiveOptions {
+        server_url: format!("ws://{address}/ws"),
+        api_url: None,
+        token: Some("jwt".to_string()),
+        game_id: GAME_ID,
+        game_code: None,
+    })?;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let event = loop {
+        if Instant::now() > deadline {
+            bail!("no game event arrived");
+        }
+        let event = connection
+            .drain()
+            .into_iter()
+            .find_map(|update| match update {
+                LiveUpdate::Event(event) => Some(event),
+                _ => None,
+            });
+        if let Some(event) = event {
+            break event;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(event.tick, 7);
+    assert!(matches!(event.event, GameEvent::TickHash { hash: 99, .. }));
+
+    drop(connection);
+    let requests = runtime.block_on(server)??;
+    assert_eq!(requests[0]["Authenticate"]["encoding"], json!("Json"));
+    assert_eq!(
+        requests[1],
+        json!({ "SpectateGame": { "game_id": GAME_ID } }),
+        "the viewer must use the spectator path, not JoinGame"
+    );
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use common::{GameEvent, GameEventMessage, GameState, GameStatus, GameType, QueueMode};
+use terminal::live::{LiveGame, ResyncRequest};
+
+const GAME_ID: u32 = 42;
+const START_MS: i64 = 1_700_000_000_000;
+
+fn started_state() -> Result<GameState> {
+    let mut state = GameState::new(
+        20,
+        20,
+        GameType::FreeForAll { max_players: 2 },
+        QueueMode::Quickmatch,
+        Some(12345),
+        START_MS,
+    );
+    state.add_player(1, Some("Ada".to_string()))?;
+    state.add_player(2, Some("Grace".to_string()))?;
+    state.status = GameStatus::Started { server_id: 1 };
+    Ok(state)
+}
+
+fn frame(tick: u32, stream_seq: u64, event: GameEvent) -> GameEventMessage {
+    GameEventMessage {
+        game_id: GAME_ID,
+        tick,
+        sequence: 0,
+        stream_seq,
+        user_id: None,
+        event,
+    }
+}
+
+fn snapshot(state: &GameState, stream_seq: u64) -> GameEventMessage {
+    frame(
+        state.tick,
+        stream_seq,
+        GameEvent::Snapshot {
+            game_state: state.clone(),
+        },
+    )
+}
+
+/// The heartbeat the server would send once the game reaches `tick`.
+fn tick_hash(state: &GameState, tick: u32, stream_seq: u64) -> Result<GameEventMessage> {
+    let mut server = state.clone();
+    while server.current_tick() < tick {
+        server.tick_forward(true)?;
+    }
+    Ok(frame(
+        tick,
+        stream_seq,
+        GameEvent::TickHash {
+            hash: server.sync_hash(),
+            server_ts_ms: START_MS,
+        },
+    ))
+}
+
+#[test]
+fn frames_before_the_first_snapshot_are_dropped() -> Result<()> {
+    let state = started_state()?;
+    let mut game = LiveGame::new(GAME_ID);
+
+    game.apply(&tick_hash(&state, 2, 3)?)?;
+    assert!(game.state().is_none());
+    assert!(game.take_resync_request().is_none());
+
+    let mut other_game = snapshot(&state, 4);
+    other_game.game_id = GAME_ID + 1;
+    game.apply(&other_game)?;
+    assert!(
+        game.state().is_none(),
+        "another game's snapshot was applied"
+    );
+
+    game.apply(&snapshot(&state, 4))?;
+    assert_eq!(game.state().map(|state| state.tick), Some(state.tick));
+    assert_eq!(game.state().map(|state| state.usernames.len()), Some(2));
+    Ok(())
+}
+
+#[test]
+fn heartbeats_verify_the_engine_and_a_gap_asks_for_one_resync() -> Result<()> {
+    let state = started_state()?;
+    let mut game = LiveGame::new(GAME_ID);
+    game.apply(&snapshot(&state, 1))?;
+
+    game.apply(&tick_hash(&state, state.tick + 3, 2)?)?;
+    let sync = game.sync_status().expect("engine exists after a snapshot");
+    assert_eq!(sync.total_probes, 1);
+    assert_eq!(sync.last_probe_matched, Some(true));
+    assert!(game.take_resync_request().is_none());
+
+    // Stream sequence 3 never arrives.
+    game.apply(&tick_hash(&state, state.tick + 6, 4)?)?;
+    assert_eq!(
+        game.take_resync_request(),
+        Some(ResyncRequest {
+            base_tick: Some(state.tick)
+        })
+    );
+    game.apply(&tick_hash(&state, state.tick + 9, 6)?)?;
+    assert!(
+        game.take_resync_request().is_none(),
+        "a second resync was requested while the first was in flight"
+    );
+
+    // The answering snapshot re-anchors the stream.
+    let mut resynced = state.clone();
+    for _ in 0..9 {
+        resynced.tick_forward(true)?;
+    }
+    game.apply(&snapshot(&resynced, 7))?;
+    game.apply(&tick_hash(&resynced, resynced.tick + 1, 8)?)?;
+    assert!(game.take_resync_request().is_none());
+    assert_eq!(
+        game.sync_status().and_then(|sync| sync.last_probe_matched),
+        Some(true)
+    );
+    Ok(())
+}
+
+#[test]
+fn prediction_keeps_the_game_moving_between_frames() -> Result<()> {
+    let state = started_state()?;
+    let tick_ms = state.properties.tick_duration_ms as i64;
+    let mut game = LiveGame::new(GAME_ID);
+    game.apply(&snapshot(&state, 1))?;
+
+    game.advance(START_MS + 5 * tick_ms)?;
+    assert_eq!(game.state().map(|state| state.tick), Some(5));
+    Ok(())
+}
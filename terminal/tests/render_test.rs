@@ -1,4 +1,4 @@
-use common::{Arena, BoostPad, Direction, Position, Snake};
+use common::{Arena, BoostPad, Direction, EdgePolicy, OccupancyGrid, Position, Snake, ZoneBounds};
 use terminal::render::{
     arena::ArenaRenderer,
     standard_renderer::StandardRenderer,
@@ -24,6 +24,11 @@ fn test_2x1_rendering() {
         food: vec![Position { x: 7, y: 7 }],
         boost_pads: Vec::new(),
         team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: None,
+        edges: EdgePolicy::Solid,
+        occupancy: OccupancyGrid::default(),
     };
 
     // Create renderer with 2x1 configuration
@@ -71,6 +76,11 @@ fn test_1x1_rendering() {
         food: vec![Position { x: 3, y: 3 }],
         boost_pads: Vec::new(),
         team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: None,
+        edges: EdgePolicy::Solid,
+        occupancy: OccupancyGrid::default(),
     };
 
     // Create renderer with 1x1 configuration
@@ -109,6 +119,11 @@ fn test_custom_dimensions() {
         food: vec![Position { x: 1, y: 1 }],
         boost_pads: Vec::new(),
         team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: None,
+        edges: EdgePolicy::Solid,
+        occupancy: OccupancyGrid::default(),
     };
 
     let char_dims = CharDimensions::new(3, 2);
@@ -166,6 +181,11 @@ fn boost_rendering_uses_authoritative_footprints_and_hides_cooldowns() {
             },
         ],
         team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: None,
+        edges: EdgePolicy::Solid,
+        occupancy: OccupancyGrid::default(),
     };
     let char_dims = CharDimensions::new(1, 1);
     let lines = ArenaRenderer::new(StandardRenderer::new(char_dims))
@@ -182,3 +202,80 @@ fn boost_rendering_uses_authoritative_footprints_and_hides_cooldowns() {
     }
     assert_eq!(lines[4][5], ' ', "cooling packet must not render");
 }
+
+#[test]
+fn battle_royale_zone_shades_closed_cells_under_everything_else() {
+    let arena = Arena {
+        width: 5,
+        height: 5,
+        snakes: vec![Snake::new(
+            vec![Position { x: 0, y: 2 }, Position { x: 0, y: 3 }],
+            Direction::Up,
+            true,
+            0,
+            None,
+        )],
+        food: vec![Position { x: 4, y: 4 }],
+        boost_pads: Vec::new(),
+        team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: Some(ZoneBounds::inset(5, 5, 1)),
+        edges: EdgePolicy::Solid,
+        occupancy: OccupancyGrid::default(),
+    };
+    let char_dims = CharDimensions::new(1, 1);
+    let lines = ArenaRenderer::new(StandardRenderer::new(char_dims))
+        .render(
+            &arena,
+            &RenderConfig {
+                chars_per_point: char_dims,
+            },
+        )
+        .into_lines();
+
+    assert_eq!(lines[0][0], '░');
+    assert_eq!(lines[2][2], ' ', "the open zone renders as plain field");
+    assert_eq!(
+        lines[2][0], '█',
+        "a snake caught outside draws over the shading"
+    );
+    assert_eq!(lines[4][4], '●');
+}
+
+#[test]
+fn wraparound_snakes_draw_their_tail_on_the_far_side_of_the_seam() {
+    let arena = Arena {
+        width: 5,
+        height: 3,
+        snakes: vec![Snake::new(
+            vec![Position { x: 1, y: 1 }, Position { x: -2, y: 1 }],
+            Direction::Right,
+            true,
+            0,
+            None,
+        )],
+        food: Vec::new(),
+        boost_pads: Vec::new(),
+        team_zone_config: None,
+        map: None,
+        power_ups: Vec::new(),
+        zone: None,
+        edges: EdgePolicy::Wrap,
+        occupancy: OccupancyGrid::default(),
+    };
+    let char_dims = CharDimensions::new(1, 1);
+    let lines = ArenaRenderer::new(StandardRenderer::new(char_dims))
+        .render(
+            &arena,
+            &RenderConfig {
+                chars_per_point: char_dims,
+            },
+        )
+        .into_lines();
+
+    assert_eq!(lines[1][0], '█');
+    assert_eq!(lines[1][2], ' ');
+    assert_eq!(lines[1][3], '█', "the tail crossed the left seam");
+    assert_eq!(lines[1][4], '█');
+}